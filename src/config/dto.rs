@@ -1,14 +1,296 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ipnet::IpNet;
 use serde::Deserialize;
 
+use crate::core::error::AppError;
+use crate::core::locale::Locale;
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
-    pub port: u16,
+    /// `MCP_BIND_ADDRESS` and port, or `MCP_UNIX_SOCKET`.
+    pub listen: ListenAddress,
+    /// `MCP_TLS_CERT_PATH` and `MCP_TLS_KEY_PATH`; HTTPS is served when both are set.
+    pub tls: Option<TlsConfig>,
+    /// `CORS_ALLOWED_ORIGINS`; browser clients from these origins may call the API. No
+    /// CORS headers are sent when unset.
+    pub cors_allowed_origins: Option<CorsOrigins>,
+    /// `MCP_ALLOWED_CIDRS`; when set, `/api/mcp` refuses peers outside these networks.
+    pub allowed_cidrs: Option<AllowedCidrs>,
+    /// Take the client address from `Forwarded` / `X-Forwarded-For`, as set by a reverse
+    /// proxy in front of the server.
+    pub trust_proxy_headers: bool,
+    /// The first client key; also the secret mixed into audit argument digests.
     pub api_key: String,
+    /// Every client key with its name, from `MCP_API_KEY_FILE`, `MCP_API_KEYS` or the
+    /// single `MCP_API_KEY`.
+    pub api_keys: ApiKeysConfig,
+    /// Key file re-read whenever it changes, so keys rotate without a restart.
+    pub api_key_file: Option<String>,
+    /// How often the key file's modification time is checked, in seconds.
+    pub api_key_file_poll_secs: u64,
+    /// Second key accepted on every route that also unlocks admin-only tools.
+    pub admin_api_key: Option<String>,
+    /// Accept `?api_key=` on `/api/mcp` for clients that cannot set headers.
+    pub allow_query_api_key: bool,
+    /// Largest request body `/api/mcp` accepts, in bytes.
+    pub max_request_bytes: usize,
     pub disable_proxy: bool,
     pub cache_enabled: bool,
     pub relevance_threshold: f32,
     pub cache_ttl: CacheTtlConfig,
     pub db_path: String,
+    pub tool_staleness_threshold: u64,
+    pub stats_snapshot_interval: u64,
+    pub upstream_base_url: Option<String>,
+    /// Directory every upstream response is written to as a sanitised fixture. Only
+    /// honoured by builds with the `record` feature.
+    pub upstream_record_dir: Option<String>,
+    /// `FAULTS` rules injecting upstream failures, delays and truncation. Only honoured by
+    /// builds with the `fault-injection` feature.
+    pub faults: Option<String>,
+    pub upstream_requests_per_second: u32,
+    /// Smallest per-host concurrency the adaptive limiter shrinks to under push-back.
+    pub upstream_concurrency_floor: usize,
+    /// Largest per-host concurrency, and each host's starting window.
+    pub upstream_concurrency_ceiling: usize,
+    /// Consecutive failed requests after which an upstream host is marked down; `0`
+    /// disables availability tracking.
+    pub upstream_down_after_failures: u32,
+    /// How long a host stays marked down before requests are tried again.
+    pub upstream_down_secs: u64,
+    /// Tool calls taking at least this long are logged as slow and counted; `0` disables.
+    pub slow_request_ms: u64,
+    /// Upstream attempts taking at least this long to answer are logged as slow and
+    /// counted; `0` disables.
+    pub slow_upstream_ms: u64,
+    /// Send each tool call's correlation id upstream as `X-Correlation-Id`.
+    pub upstream_correlation_header: bool,
+    /// Fetch the member record in the background after a constituency lookup resolves an
+    /// MP, so the profile call that follows is a cache hit.
+    pub prefetch_member_after_lookup: bool,
+    pub enable_rest_api: bool,
+    pub export_max_rows: usize,
+    /// Path to an ed25519 PKCS#8 PEM key used to sign research responses.
+    pub response_signing_key: Option<String>,
+    /// Fail tool calls whose output does not match the declared outputSchema.
+    pub strict_output_validation: bool,
+    /// Reject tool arguments with unknown properties; when false they are stripped and logged.
+    pub strict_args: bool,
+    /// `MCP_STRICT`: reject JSON-RPC requests with undeclared top-level members. Implies
+    /// `strict_args`.
+    pub strict: bool,
+    pub tool_timeouts: ToolTimeoutConfig,
+    pub tool_byte_budgets: ToolByteBudgetConfig,
+    /// JSON file replacing the bundled voting-record policy area mapping.
+    pub policy_areas_path: Option<String>,
+    /// Directory refreshed reference data is written to and preferred from.
+    pub reference_data_dir: Option<String>,
+    /// Checksum manifest listing the canonical reference datasets.
+    pub reference_data_manifest_url: Option<String>,
+    /// Hours between scheduled reference data refreshes (0 disables).
+    pub reference_data_refresh_hours: u64,
+    /// Maximum `resource_link` content blocks appended to a tool result (0 disables).
+    pub resource_link_limit: usize,
+    /// Oldest expired research entry that may be served while sources are degraded (0 disables).
+    pub research_max_stale_secs: u64,
+    /// Delay before re-running research that was answered from a stale entry.
+    pub research_stale_refresh_secs: u64,
+    /// Distinct search terms each research collection may try, keywords before expansions.
+    pub research_max_terms: usize,
+    /// `MCP_DEFAULT_LOCALE`: language of research prose when a request names none.
+    pub default_locale: Locale,
+    pub tool_filter: ToolFilterConfig,
+    /// Days `tools/call` audit entries are kept (0 keeps them forever).
+    pub audit_retention_days: u64,
+    /// Days before a stored research brief is compacted to its summary and citations
+    /// (0 never compacts).
+    pub research_compact_after_days: u64,
+    /// Collect k-anonymous research topic and constituency counts for `admin.usage_summary`.
+    pub usage_stats_enabled: bool,
+    /// Smallest count a topic or constituency needs before `admin.usage_summary` lists it.
+    pub usage_k_threshold: u64,
+    /// Seconds shutdown waits for in-flight `/api/mcp` requests before aborting them.
+    pub shutdown_grace_secs: u64,
+    /// `POST /api/mcp` requests answered at once; more queue, then are shed.
+    pub max_concurrent_requests: usize,
+    /// `research.run` calls executing at once, within `max_concurrent_requests`.
+    pub max_concurrent_research: usize,
+    /// Seconds a request queues for a free slot before it is rejected as busy.
+    pub request_queue_wait_secs: u64,
+}
+
+pub const DEFAULT_PORT: u16 = 4100;
+
+/// Where the HTTP server accepts connections.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum ListenAddress {
+    Tcp(SocketAddr),
+    /// A Unix domain socket, created with mode 0660 and removed on shutdown.
+    Unix(PathBuf),
+}
+
+impl ListenAddress {
+    /// Combine the raw `MCP_BIND_ADDRESS`, port and `MCP_UNIX_SOCKET` values, each `None`
+    /// when unset. TCP binds to `0.0.0.0:4100` by default; a socket excludes both.
+    pub fn parse(
+        bind_address: Option<&str>,
+        port: Option<&str>,
+        unix_socket: Option<&str>,
+    ) -> Result<Self, AppError> {
+        if let Some(path) = unix_socket {
+            if bind_address.is_some() || port.is_some() {
+                return Err(AppError::configuration(
+                    "MCP_UNIX_SOCKET cannot be combined with MCP_BIND_ADDRESS or a port; \
+                     unset one of them",
+                ));
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+
+        let ip = match bind_address {
+            Some(raw) => {
+                let unbracketed = raw
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.strip_suffix(']'));
+                unbracketed.unwrap_or(raw).parse::<IpAddr>().map_err(|_| {
+                    AppError::configuration(format!(
+                        "invalid MCP_BIND_ADDRESS \"{raw}\": expected an IP address such as \
+                         127.0.0.1 or ::1, without a port"
+                    ))
+                })?
+            }
+            None => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        };
+        let port = match port {
+            Some(raw) => raw
+                .parse::<u16>()
+                .map_err(|err| AppError::configuration(format!("invalid port: {err}")))?,
+            None => DEFAULT_PORT,
+        };
+        Ok(Self::Tcp(SocketAddr::new(ip, port)))
+    }
+}
+
+impl fmt::Display for ListenAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(address) => address.fmt(f),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// PEM certificate chain and private key for serving HTTPS.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Pair the raw `MCP_TLS_CERT_PATH` and `MCP_TLS_KEY_PATH` values, each `None` when
+    /// unset. Neither means plain HTTP; setting only one is an error.
+    pub fn parse(
+        cert_path: Option<&str>,
+        key_path: Option<&str>,
+    ) -> Result<Option<Self>, AppError> {
+        match (cert_path, key_path) {
+            (None, None) => Ok(None),
+            (Some(cert_path), Some(key_path)) => Ok(Some(Self {
+                cert_path: PathBuf::from(cert_path),
+                key_path: PathBuf::from(key_path),
+            })),
+            (Some(_), None) => Err(AppError::configuration(
+                "MCP_TLS_CERT_PATH is set without MCP_TLS_KEY_PATH; set both to serve HTTPS",
+            )),
+            (None, Some(_)) => Err(AppError::configuration(
+                "MCP_TLS_KEY_PATH is set without MCP_TLS_CERT_PATH; set both to serve HTTPS",
+            )),
+        }
+    }
+}
+
+/// Origins allowed to call the server from a browser.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum CorsOrigins {
+    /// `*`: any origin.
+    Any,
+    List(Vec<String>),
+}
+
+impl CorsOrigins {
+    /// Parse the comma-separated `CORS_ALLOWED_ORIGINS` value. `None` when it lists
+    /// nothing; `*` may not be mixed with named origins.
+    pub fn parse(raw: &str) -> Result<Option<Self>, AppError> {
+        let origins: Vec<String> = raw
+            .split(',')
+            .map(|origin| origin.trim().trim_end_matches('/').to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect();
+        if origins.is_empty() {
+            return Ok(None);
+        }
+        if origins.iter().any(|origin| origin == "*") {
+            if origins.len() > 1 {
+                return Err(AppError::configuration(
+                    "CORS_ALLOWED_ORIGINS cannot combine * with named origins",
+                ));
+            }
+            return Ok(Some(Self::Any));
+        }
+        for origin in &origins {
+            let well_formed = origin.split_once("://").is_some_and(|(scheme, host)| {
+                matches!(scheme, "http" | "https") && !host.is_empty() && !host.contains('/')
+            }) && origin.is_ascii()
+                && !origin.chars().any(|c| c.is_ascii_control() || c == ' ');
+            if !well_formed {
+                return Err(AppError::configuration(format!(
+                    "CORS_ALLOWED_ORIGINS entry {origin} is not an origin such as https://app.example.org"
+                )));
+            }
+        }
+        Ok(Some(Self::List(origins)))
+    }
+}
+
+/// Networks whose addresses may reach `/api/mcp`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AllowedCidrs(Vec<IpNet>);
+
+impl AllowedCidrs {
+    /// Parse the comma-separated `MCP_ALLOWED_CIDRS` value. A bare address stands for
+    /// itself alone. `None` when it lists nothing.
+    pub fn parse(raw: &str) -> Result<Option<Self>, AppError> {
+        let networks = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .parse::<IpNet>()
+                    .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                    .map(|network| network.trunc())
+                    .map_err(|_| {
+                        AppError::configuration(format!(
+                            "MCP_ALLOWED_CIDRS entry {entry} is not a CIDR block such as 10.0.0.0/8"
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((!networks.is_empty()).then_some(Self(networks)))
+    }
+
+    /// Whether `address` is inside one of the networks. IPv4 addresses mapped into IPv6
+    /// (`::ffff:10.0.0.1`, as dual-stack sockets report them) match IPv4 blocks.
+    pub fn contains(&self, address: IpAddr) -> bool {
+        let address = address.to_canonical();
+        self.0.iter().any(|network| network.contains(&address))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -20,5 +302,316 @@ pub struct CacheTtlConfig {
     pub research: u64,
     pub activity: u64,
     pub votes: u64,
+    /// `parliament.search_hansard` results.
+    pub hansard: u64,
+    /// `parliament.fetch_written_questions` results.
+    pub written_questions: u64,
+    /// `parliament.fetch_edms` results.
+    pub edms: u64,
+    /// `parliament.fetch_oral_questions` results.
+    pub oral_questions: u64,
     pub constituency: u64,
+    pub bill_divisions: u64,
+    /// `parliament.fetch_committees` results.
+    pub committees: u64,
+    /// `parliament.fetch_statutory_instruments` results.
+    pub statutory_instruments: u64,
+    /// `parliament.fetch_division_detail` results; published divisions do not change.
+    pub division_detail: u64,
+    /// The enumerated constituency list; current MPs refresh on `members`.
+    pub constituency_list: u64,
+}
+
+/// Which tools the server exposes. A tool is available when it is in `enabled` (or
+/// `enabled` is unset) and not in `disabled`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolFilterConfig {
+    /// `TOOLS_ENABLED`; `None` exposes every tool.
+    pub enabled: Option<Vec<String>>,
+    /// `TOOLS_DISABLED`.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+impl ToolFilterConfig {
+    pub fn allows(&self, tool_name: &str) -> bool {
+        let enabled = self
+            .enabled
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|name| name == tool_name));
+        enabled && !self.disabled.iter().any(|name| name == tool_name)
+    }
+
+    /// Reject names that are not in `known`, so typos fail at startup instead of silently
+    /// exposing (or hiding) the wrong tools.
+    pub fn validate(&self, known: &[String]) -> Result<(), AppError> {
+        let lists = [
+            ("TOOLS_ENABLED", self.enabled.as_deref().unwrap_or_default()),
+            ("TOOLS_DISABLED", self.disabled.as_slice()),
+        ];
+
+        for (key, names) in lists {
+            let unknown: Vec<&str> = names
+                .iter()
+                .filter(|name| !known.contains(name))
+                .map(String::as_str)
+                .collect();
+            if !unknown.is_empty() {
+                return Err(AppError::configuration(format!(
+                    "{key} references unknown tools: {}",
+                    unknown.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Upper bound on a single `tools/call`. `research.run` fans out to several upstreams, so
+/// it defaults to three times the single-fetch limit.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolTimeoutConfig {
+    /// `TOOL_TIMEOUT_SECONDS`; `0` disables the timeout.
+    pub default_secs: u64,
+    /// Per-tool overrides keyed by variable name (`TOOL_TIMEOUT_<TOOL_NAME>`).
+    pub overrides: HashMap<String, u64>,
+}
+
+impl ToolTimeoutConfig {
+    pub fn for_tool(&self, tool_name: &str) -> Option<Duration> {
+        let secs = match self.overrides.get(&Self::env_key(tool_name)) {
+            Some(secs) => *secs,
+            None if tool_name == "research.run" => self.default_secs.saturating_mul(3),
+            None => self.default_secs,
+        };
+
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    /// Environment variable holding the override for `tool_name`, e.g.
+    /// `research.run` -> `TOOL_TIMEOUT_RESEARCH_RUN`.
+    pub fn env_key(tool_name: &str) -> String {
+        let suffix = tool_name
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() {
+                    ch.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        format!("TOOL_TIMEOUT_{suffix}")
+    }
+}
+
+/// Per-call caps on decompressed upstream bytes (`TOOL_BYTE_BUDGETS`). Tools without an
+/// entry are unlimited.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolByteBudgetConfig {
+    pub budgets: HashMap<String, u64>,
+}
+
+impl ToolByteBudgetConfig {
+    pub fn for_tool(&self, tool_name: &str) -> Option<u64> {
+        self.budgets.get(tool_name).copied()
+    }
+
+    /// Parse `tool=bytes` pairs separated by commas.
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        let mut budgets = HashMap::new();
+        for pair in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (tool, bytes) = pair.split_once('=').ok_or_else(|| {
+                AppError::configuration(format!(
+                    "TOOL_BYTE_BUDGETS entry \"{pair}\" must look like tool=bytes"
+                ))
+            })?;
+            let bytes = bytes.trim().parse::<u64>().map_err(|err| {
+                AppError::configuration(format!(
+                    "invalid TOOL_BYTE_BUDGETS entry \"{pair}\": {err}"
+                ))
+            })?;
+            budgets.insert(tool.trim().to_string(), bytes);
+        }
+
+        Ok(Self { budgets })
+    }
+
+    pub fn validate(&self, known: &[String]) -> Result<(), AppError> {
+        let mut unknown: Vec<&str> = self
+            .budgets
+            .keys()
+            .filter(|name| !known.contains(name))
+            .map(String::as_str)
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+
+        unknown.sort_unstable();
+        Err(AppError::configuration(format!(
+            "TOOL_BYTE_BUDGETS references unknown tools: {}",
+            unknown.join(", ")
+        )))
+    }
+}
+
+/// Name the admin key is logged and audited under.
+pub const ADMIN_KEY_NAME: &str = "admin";
+
+/// Name given to `MCP_API_KEY` when `MCP_API_KEYS` is unset.
+pub const DEFAULT_KEY_NAME: &str = "default";
+
+/// Shortest key `MCP_API_KEYS` accepts.
+pub const MIN_NAMED_KEY_LEN: usize = 16;
+
+/// Client API keys, each with a name that identifies its traffic in logs and the audit log.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApiKeysConfig {
+    pub keys: Vec<NamedApiKey>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedApiKey {
+    pub name: String,
+    pub key: String,
+}
+
+impl ApiKeysConfig {
+    /// A lone `MCP_API_KEY`, named `default`.
+    pub fn single(key: impl Into<String>) -> Self {
+        Self {
+            keys: vec![NamedApiKey {
+                name: DEFAULT_KEY_NAME.to_string(),
+                key: key.into(),
+            }],
+        }
+    }
+
+    /// Parse `name:key` pairs separated by commas. Names must be unique, made of ASCII
+    /// letters, digits, `-`, `_` or `.`, and keys at least [`MIN_NAMED_KEY_LEN`] long.
+    pub fn parse(raw: &str) -> Result<Self, AppError> {
+        let mut parsed = Self::default();
+        for pair in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let position = parsed.keys.len() + 1;
+            let (name, key) = pair.split_once(':').ok_or_else(|| {
+                AppError::configuration(format!(
+                    "MCP_API_KEYS entry {position} must look like name:key"
+                ))
+            })?;
+            parsed.push("MCP_API_KEYS", &format!("entry {position}"), name, key)?;
+        }
+        parsed.ensure_not_empty("MCP_API_KEYS")
+    }
+
+    /// Parse an `MCP_API_KEY_FILE`: one `name:key` pair or bare key per line, with blank
+    /// lines and `#` comments skipped. Bare keys are named `key1`, `key2`, ... by position.
+    /// The rules are those of [`ApiKeysConfig::parse`].
+    pub fn parse_file(contents: &str) -> Result<Self, AppError> {
+        let mut parsed = Self::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let location = format!("line {}", index + 1);
+            match line.split_once(':') {
+                Some((name, key)) => parsed.push("MCP_API_KEY_FILE", &location, name, key)?,
+                None => {
+                    let name = format!("key{}", parsed.keys.len() + 1);
+                    parsed.push("MCP_API_KEY_FILE", &location, &name, line)?
+                }
+            }
+        }
+        parsed.ensure_not_empty("MCP_API_KEY_FILE")
+    }
+
+    /// Read and parse the key file at `path`.
+    pub fn from_file(path: &str) -> Result<Self, AppError> {
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            AppError::configuration(format!("cannot read MCP_API_KEY_FILE {path}: {err}"))
+        })?;
+        Self::parse_file(&contents)
+    }
+
+    /// `ADMIN_API_KEY` must not double as a client key, nor share the name it is logged under.
+    pub fn check_admin_key(&self, admin_key: Option<&str>) -> Result<(), AppError> {
+        let Some(admin_key) = admin_key else {
+            return Ok(());
+        };
+        if self.contains(admin_key) {
+            return Err(AppError::configuration(
+                "ADMIN_API_KEY must differ from every client API key",
+            ));
+        }
+        if self.keys.iter().any(|named| named.name == ADMIN_KEY_NAME) {
+            return Err(AppError::configuration(format!(
+                "the key name \"{ADMIN_KEY_NAME}\" is reserved for ADMIN_API_KEY"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.iter().any(|named| named.key == key)
+    }
+
+    /// Keys never appear in errors: only the name, or where the entry is.
+    fn push(
+        &mut self,
+        source: &str,
+        location: &str,
+        name: &str,
+        key: &str,
+    ) -> Result<(), AppError> {
+        let (name, key) = (name.trim(), key.trim());
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(AppError::configuration(format!(
+                "{source} {location} has an invalid name; use letters, digits, '-', '_' or '.'"
+            )));
+        }
+        if self.keys.iter().any(|existing| existing.name == name) {
+            return Err(AppError::configuration(format!(
+                "{source} names must be unique; \"{name}\" is repeated"
+            )));
+        }
+        if key.chars().count() < MIN_NAMED_KEY_LEN {
+            return Err(AppError::configuration(format!(
+                "{source} key \"{name}\" must be at least {MIN_NAMED_KEY_LEN} characters"
+            )));
+        }
+        if let Some(existing) = self.keys.iter().find(|existing| existing.key == key) {
+            return Err(AppError::configuration(format!(
+                "{source} keys \"{}\" and \"{name}\" are identical",
+                existing.name
+            )));
+        }
+        self.keys.push(NamedApiKey {
+            name: name.to_string(),
+            key: key.to_string(),
+        });
+        Ok(())
+    }
+
+    fn ensure_not_empty(self, source: &str) -> Result<Self, AppError> {
+        if self.keys.is_empty() {
+            return Err(AppError::configuration(format!(
+                "{source} is set but lists no keys"
+            )));
+        }
+        Ok(self)
+    }
 }
@@ -1,7 +1,12 @@
 use std::env;
 
-use crate::config::dto::{AppConfig, CacheTtlConfig};
+use crate::config::dto::{
+    AllowedCidrs, ApiKeysConfig, AppConfig, CacheTtlConfig, CorsOrigins, ListenAddress, TlsConfig,
+    ToolByteBudgetConfig, ToolFilterConfig, ToolTimeoutConfig,
+};
 use crate::core::error::AppError;
+use crate::core::locale::Locale;
+use crate::features::mcp::schemas::build_tool_schemas;
 
 pub fn load_config() -> Result<AppConfig, AppError> {
     dotenvy::dotenv().ok();
@@ -9,13 +14,65 @@ pub fn load_config() -> Result<AppConfig, AppError> {
     let port = env::var("MCP_SERVER_PORT")
         .or_else(|_| env::var("DEEP_RESEARCH_MCP_PORT"))
         .or_else(|_| env::var("PORT"))
-        .unwrap_or_else(|_| "4100".to_string())
-        .parse::<u16>()
-        .map_err(|err| AppError::configuration(format!("invalid port: {err}")))?;
+        .ok();
+    let listen = ListenAddress::parse(
+        non_empty_env("MCP_BIND_ADDRESS").as_deref(),
+        port.as_deref().map(str::trim),
+        non_empty_env("MCP_UNIX_SOCKET").as_deref(),
+    )?;
+    let tls = TlsConfig::parse(
+        non_empty_env("MCP_TLS_CERT_PATH").as_deref(),
+        non_empty_env("MCP_TLS_KEY_PATH").as_deref(),
+    )?;
+    if tls.is_some() && matches!(listen, ListenAddress::Unix(_)) {
+        return Err(AppError::configuration(
+            "MCP_TLS_CERT_PATH cannot be combined with MCP_UNIX_SOCKET; TLS is only served over TCP",
+        ));
+    }
+    let cors_allowed_origins = match non_empty_env("CORS_ALLOWED_ORIGINS") {
+        Some(raw) => CorsOrigins::parse(&raw)?,
+        None => None,
+    };
+
+    let allowed_cidrs = match non_empty_env("MCP_ALLOWED_CIDRS") {
+        Some(raw) => AllowedCidrs::parse(&raw)?,
+        None => None,
+    };
+    let trust_proxy_headers = parse_bool_env("TRUST_PROXY_HEADERS", false);
+
+    let api_key_file = env::var("MCP_API_KEY_FILE")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let api_key_file_poll_secs = parse_u64_env("MCP_API_KEY_FILE_POLL_SECS", 30).max(1);
+    let api_keys = match (
+        api_key_file.as_deref(),
+        env::var("MCP_API_KEYS")
+            .ok()
+            .filter(|value| !value.trim().is_empty()),
+    ) {
+        (Some(path), _) => ApiKeysConfig::from_file(path)?,
+        (None, Some(raw)) => ApiKeysConfig::parse(&raw)?,
+        (None, None) => ApiKeysConfig::single(
+            env::var("MCP_API_KEY")
+                .or_else(|_| env::var("DEEP_RESEARCH_API_KEY"))
+                .map_err(|_| {
+                    AppError::configuration(
+                        "MCP_API_KEY, MCP_API_KEYS or MCP_API_KEY_FILE is required".to_string(),
+                    )
+                })?,
+        ),
+    };
+    let api_key = api_keys.keys[0].key.clone();
+
+    let admin_api_key = env::var("ADMIN_API_KEY")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    api_keys.check_admin_key(admin_api_key.as_deref())?;
 
-    let api_key = env::var("MCP_API_KEY")
-        .or_else(|_| env::var("DEEP_RESEARCH_API_KEY"))
-        .map_err(|_| AppError::configuration("MCP_API_KEY is required".to_string()))?;
+    let allow_query_api_key = parse_bool_env("ALLOW_QUERY_API_KEY", false);
+    let max_request_bytes = parse_u64_env("MAX_REQUEST_BYTES", 1_048_576) as usize;
 
     let disable_proxy = env::var("MCP_DISABLE_PROXY")
         .ok()
@@ -37,24 +94,209 @@ pub fn load_config() -> Result<AppConfig, AppError> {
         research: parse_u64_env("CACHE_TTL_RESEARCH", 604800),
         activity: parse_u64_env("CACHE_TTL_ACTIVITY", 21600),
         votes: parse_u64_env("CACHE_TTL_VOTES", 21600),
+        hansard: parse_u64_env("CACHE_TTL_HANSARD", 3600),
+        written_questions: parse_u64_env("CACHE_TTL_WRITTEN_QUESTIONS", 3600),
+        edms: parse_u64_env("CACHE_TTL_EDMS", 3600),
+        oral_questions: parse_u64_env("CACHE_TTL_ORAL_QUESTIONS", 3600),
         constituency: parse_u64_env("CACHE_TTL_CONSTITUENCY", 86400),
+        bill_divisions: parse_u64_env("CACHE_TTL_BILL_DIVISIONS", 21600),
+        committees: parse_u64_env("CACHE_TTL_COMMITTEES", 21600),
+        statutory_instruments: parse_u64_env("CACHE_TTL_STATUTORY_INSTRUMENTS", 3600),
+        division_detail: parse_u64_env("CACHE_TTL_DIVISION_DETAIL", 604800),
+        constituency_list: parse_u64_env("CACHE_TTL_CONSTITUENCY_LIST", 2592000),
     };
 
     let db_path = env::var("MCP_DB_PATH")
         .or_else(|_| env::var("DEEP_RESEARCH_DB_PATH"))
         .unwrap_or_else(|_| "./data/db".to_string());
 
+    let tool_staleness_threshold = parse_u64_env("TOOL_STALENESS_THRESHOLD_SECS", 3600);
+    let stats_snapshot_interval = parse_u64_env("STATS_SNAPSHOT_INTERVAL_SECS", 60);
+
+    let upstream_base_url = env::var("UPSTREAM_BASE_URL")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let upstream_record_dir = env::var("UPSTREAM_RECORD_DIR")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let faults = non_empty_env("FAULTS");
+    let upstream_requests_per_second = env::var("UPSTREAM_REQUESTS_PER_SECOND")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .unwrap_or(5);
+    let upstream_concurrency_ceiling =
+        parse_u64_env("UPSTREAM_CONCURRENCY_CEILING", 8).max(1) as usize;
+    let upstream_concurrency_floor = (parse_u64_env("UPSTREAM_CONCURRENCY_FLOOR", 1) as usize)
+        .clamp(1, upstream_concurrency_ceiling);
+    let upstream_down_after_failures =
+        parse_u64_env("UPSTREAM_DOWN_AFTER_FAILURES", 3).min(u64::from(u32::MAX)) as u32;
+    let upstream_down_secs = parse_u64_env("UPSTREAM_DOWN_SECS", 60);
+    let slow_request_ms = parse_u64_env("SLOW_REQUEST_MS", 10_000);
+    let slow_upstream_ms = parse_u64_env("SLOW_UPSTREAM_MS", 3_000);
+    let upstream_correlation_header = parse_bool_env("UPSTREAM_CORRELATION_HEADER", true);
+    let prefetch_member_after_lookup = parse_bool_env("PREFETCH_MEMBER_AFTER_LOOKUP", true);
+    let enable_rest_api = parse_bool_env("ENABLE_REST_API", false);
+    let export_max_rows = parse_u64_env("EXPORT_MAX_ROWS", 10_000) as usize;
+    let response_signing_key = env::var("RESPONSE_SIGNING_KEY")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let strict_output_validation = parse_bool_env("STRICT_OUTPUT_VALIDATION", false);
+    let strict = parse_bool_env("MCP_STRICT", false);
+    let strict_args = strict || parse_bool_env("MCP_STRICT_ARGS", true);
+    let research_max_stale_secs = parse_u64_env("RESEARCH_MAX_STALE_SECS", 2_592_000);
+    let research_stale_refresh_secs = parse_u64_env("RESEARCH_STALE_REFRESH_SECS", 60);
+    let research_max_terms = (parse_u64_env("RESEARCH_MAX_TERMS", 6) as usize).max(1);
+    let default_locale = match non_empty_env("MCP_DEFAULT_LOCALE") {
+        None => Locale::default(),
+        Some(tag) => Locale::parse(&tag).ok_or_else(|| {
+            AppError::configuration(format!(
+                "MCP_DEFAULT_LOCALE must be one of {}, got {tag}",
+                Locale::supported_list()
+            ))
+        })?,
+    };
+    let audit_retention_days = parse_u64_env("AUDIT_RETENTION_DAYS", 90);
+    let research_compact_after_days = parse_u64_env("RESEARCH_COMPACT_AFTER_DAYS", 90);
+    let usage_stats_enabled = parse_bool_env("USAGE_STATS_ENABLED", false);
+    let usage_k_threshold = parse_u64_env("USAGE_K_THRESHOLD", 5).max(1);
+    let shutdown_grace_secs = parse_u64_env("SHUTDOWN_GRACE_SECONDS", 30);
+    let max_concurrent_requests = parse_u64_env("MAX_CONCURRENT_REQUESTS", 32).max(1) as usize;
+    let max_concurrent_research = parse_u64_env("MAX_CONCURRENT_RESEARCH", 4).max(1) as usize;
+    let request_queue_wait_secs = parse_u64_env("REQUEST_QUEUE_WAIT_SECS", 5);
+    let resource_link_limit = parse_u64_env("RESOURCE_LINK_LIMIT", 10) as usize;
+    let policy_areas_path = env::var("POLICY_AREAS_PATH")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let reference_data_dir = non_empty_env("REFERENCE_DATA_DIR");
+    let reference_data_manifest_url = non_empty_env("REFERENCE_DATA_MANIFEST_URL");
+    let reference_data_refresh_hours = parse_u64_env("REFERENCE_DATA_REFRESH_HOURS", 0);
+    if reference_data_refresh_hours > 0
+        && (reference_data_dir.is_none() || reference_data_manifest_url.is_none())
+    {
+        return Err(AppError::configuration(
+            "REFERENCE_DATA_REFRESH_HOURS requires REFERENCE_DATA_DIR and REFERENCE_DATA_MANIFEST_URL",
+        ));
+    }
+    let tool_timeouts = load_tool_timeouts()?;
+    let tool_filter = ToolFilterConfig {
+        enabled: parse_list_env("TOOLS_ENABLED"),
+        disabled: parse_list_env("TOOLS_DISABLED").unwrap_or_default(),
+    };
+    let known_tools: Vec<String> = build_tool_schemas()
+        .0
+        .into_iter()
+        .map(|definition| definition.name)
+        .collect();
+    tool_filter.validate(&known_tools)?;
+    let tool_byte_budgets =
+        ToolByteBudgetConfig::parse(&env::var("TOOL_BYTE_BUDGETS").unwrap_or_default())?;
+    tool_byte_budgets.validate(&known_tools)?;
+
     Ok(AppConfig {
-        port,
+        listen,
+        tls,
+        cors_allowed_origins,
+        allowed_cidrs,
+        trust_proxy_headers,
         api_key,
+        api_keys,
+        api_key_file,
+        api_key_file_poll_secs,
+        admin_api_key,
+        allow_query_api_key,
+        max_request_bytes,
         disable_proxy,
         cache_enabled,
         relevance_threshold,
         cache_ttl,
         db_path,
+        tool_staleness_threshold,
+        stats_snapshot_interval,
+        upstream_base_url,
+        upstream_record_dir,
+        faults,
+        upstream_requests_per_second,
+        upstream_concurrency_floor,
+        upstream_concurrency_ceiling,
+        upstream_down_after_failures,
+        upstream_down_secs,
+        slow_request_ms,
+        slow_upstream_ms,
+        upstream_correlation_header,
+        prefetch_member_after_lookup,
+        enable_rest_api,
+        export_max_rows,
+        response_signing_key,
+        strict_output_validation,
+        strict_args,
+        strict,
+        tool_timeouts,
+        tool_byte_budgets,
+        policy_areas_path,
+        reference_data_dir,
+        reference_data_manifest_url,
+        reference_data_refresh_hours,
+        resource_link_limit,
+        research_max_stale_secs,
+        research_stale_refresh_secs,
+        research_max_terms,
+        default_locale,
+        tool_filter,
+        audit_retention_days,
+        research_compact_after_days,
+        usage_stats_enabled,
+        usage_k_threshold,
+        shutdown_grace_secs,
+        max_concurrent_requests,
+        max_concurrent_research,
+        request_queue_wait_secs,
+    })
+}
+
+fn load_tool_timeouts() -> Result<ToolTimeoutConfig, AppError> {
+    const DEFAULT_KEY: &str = "TOOL_TIMEOUT_SECONDS";
+
+    let mut overrides = std::collections::HashMap::new();
+    for (key, value) in env::vars() {
+        if !key.starts_with("TOOL_TIMEOUT_") || key == DEFAULT_KEY {
+            continue;
+        }
+        let secs = value
+            .trim()
+            .parse::<u64>()
+            .map_err(|err| AppError::configuration(format!("invalid {key}: {err}")))?;
+        overrides.insert(key, secs);
+    }
+
+    Ok(ToolTimeoutConfig {
+        default_secs: parse_u64_env(DEFAULT_KEY, 30),
+        overrides,
     })
 }
 
+/// Comma-separated names; `None` when the variable is unset or lists nothing.
+fn parse_list_env(key: &str) -> Option<Vec<String>> {
+    let names: Vec<String> = env::var(key)
+        .ok()?
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    (!names.is_empty()).then_some(names)
+}
+
+/// The trimmed value, or `None` when the variable is unset or blank.
+fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
 fn parse_bool_env(key: &str, default: bool) -> bool {
     env::var(key)
         .map(|value| matches!(value.as_str(), "true" | "1" | "TRUE" | "True"))
@@ -2,5 +2,9 @@ mod dto;
 mod loader;
 
 #[allow(unused_imports)]
-pub use dto::{AppConfig, CacheTtlConfig};
+pub use dto::{
+    ADMIN_KEY_NAME, AllowedCidrs, ApiKeysConfig, AppConfig, CacheTtlConfig, CorsOrigins,
+    DEFAULT_KEY_NAME, DEFAULT_PORT, ListenAddress, NamedApiKey, TlsConfig, ToolByteBudgetConfig,
+    ToolFilterConfig, ToolTimeoutConfig,
+};
 pub use loader::load_config;
@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use tokio::sync::Notify;
+
+/// Caps concurrent requests to each upstream host with an AIMD window: once the window has
+/// been filled, every successful response grows the host's limit by `1 / limit` (about one
+/// slot per window of successes), and push-back (HTTP 429 or 503) halves it, never below
+/// `floor` or above `ceiling`. Hosts start at `ceiling`.
+pub struct AdaptiveLimiter {
+    floor: usize,
+    ceiling: usize,
+    hosts: Mutex<HashMap<String, Arc<HostWindow>>>,
+}
+
+struct HostWindow {
+    state: Mutex<WindowState>,
+    released: Notify,
+}
+
+struct WindowState {
+    limit: f64,
+    in_flight: usize,
+    /// Whether every slot has been in use since the limit last changed by a whole slot.
+    /// Successes in a window that was never full say nothing about a larger one; without
+    /// this a quiet spell would let the limit drift back to the ceiling and the next burst
+    /// would hit the host at full concurrency.
+    saturated: bool,
+    /// Bumped on every decrease. Only push-back to requests admitted under the current
+    /// generation shrinks the window, so one burst of 429s halves it once rather than
+    /// once per rejected request.
+    generation: u64,
+}
+
+/// A slot in a host's window, released on drop. Report the outcome with
+/// [`AdaptivePermit::succeeded`] or [`AdaptivePermit::throttled`]; other failures leave
+/// the window unchanged.
+pub struct AdaptivePermit {
+    window: Arc<HostWindow>,
+    generation: u64,
+    floor: usize,
+    ceiling: usize,
+}
+
+impl AdaptiveLimiter {
+    /// `floor` is raised to at least one and `ceiling` to at least `floor`.
+    pub fn new(floor: usize, ceiling: usize) -> Self {
+        let floor = floor.max(1);
+        Self {
+            floor,
+            ceiling: ceiling.max(floor),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for a free slot in `host`'s window.
+    pub async fn acquire(&self, host: &str) -> AdaptivePermit {
+        let window = self.window(host);
+        loop {
+            let released = window.released.notified();
+            {
+                let mut state = lock(&window.state);
+                if state.in_flight < state.limit as usize {
+                    state.in_flight += 1;
+                    if state.in_flight == state.limit as usize {
+                        state.saturated = true;
+                    }
+                    return AdaptivePermit {
+                        window: window.clone(),
+                        generation: state.generation,
+                        floor: self.floor,
+                        ceiling: self.ceiling,
+                    };
+                }
+            }
+            released.await;
+        }
+    }
+
+    /// Current concurrency limit of every host contacted so far.
+    pub fn limits(&self) -> BTreeMap<String, usize> {
+        lock(&self.hosts)
+            .iter()
+            .map(|(host, window)| (host.clone(), lock(&window.state).limit as usize))
+            .collect()
+    }
+
+    fn window(&self, host: &str) -> Arc<HostWindow> {
+        lock(&self.hosts)
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                Arc::new(HostWindow {
+                    state: Mutex::new(WindowState {
+                        limit: self.ceiling as f64,
+                        in_flight: 0,
+                        saturated: false,
+                        generation: 0,
+                    }),
+                    released: Notify::new(),
+                })
+            })
+            .clone()
+    }
+}
+
+impl AdaptivePermit {
+    /// The host answered normally: grow the window additively if it has been filled.
+    pub fn succeeded(self) {
+        let mut state = lock(&self.window.state);
+        if !state.saturated {
+            return;
+        }
+        let before = state.limit as usize;
+        state.limit = (state.limit + 1.0 / state.limit).min(self.ceiling as f64);
+        if state.limit as usize > before {
+            state.saturated = false;
+            self.window.released.notify_one();
+        }
+    }
+
+    /// The host pushed back: halve the window, once per generation.
+    pub fn throttled(self) {
+        let mut state = lock(&self.window.state);
+        if state.generation == self.generation {
+            state.limit = (state.limit / 2.0).max(self.floor as f64);
+            state.generation += 1;
+            state.saturated = false;
+            tracing::debug!(
+                limit = state.limit as usize,
+                "upstream pushed back; shrinking window"
+            );
+        }
+    }
+}
+
+impl Drop for AdaptivePermit {
+    fn drop(&mut self) {
+        lock(&self.window.state).in_flight -= 1;
+        self.window.released.notify_one();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
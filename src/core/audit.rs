@@ -0,0 +1,230 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sled::Tree;
+use tokio::task;
+
+use crate::core::error::AppError;
+use crate::core::signing::canonicalize;
+
+/// How a tool call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    /// The arguments or the request were rejected before or by the tool.
+    Rejected,
+    Error,
+    Timeout,
+    Cancelled,
+}
+
+impl AuditOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditOutcome::Success => "success",
+            AuditOutcome::Rejected => "rejected",
+            AuditOutcome::Error => "error",
+            AuditOutcome::Timeout => "timeout",
+            AuditOutcome::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// One `tools/call`. Arguments are kept as a keyed digest plus a copy with postcode and API
+/// key fields blanked, so neither reaches the database but calls can still be replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Hex form of the storage key; filled in when entries are read back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Unix seconds when the call started.
+    pub timestamp: u64,
+    pub tool: String,
+    #[serde(rename = "argumentsSha256")]
+    pub arguments_sha256: String,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    pub outcome: AuditOutcome,
+    /// `upstream`, `internal`, `config` or `bad_request` for failed calls.
+    #[serde(rename = "errorKind", default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    #[serde(rename = "correlationId", default)]
+    pub correlation_id: Option<String>,
+    /// Name of the API key the call was made with; the key itself is never stored.
+    #[serde(
+        rename = "apiKeyName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub api_key_name: Option<String>,
+    /// The arguments with every redacted field set to `null`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+    /// JSON pointers into `arguments` of the fields that were blanked.
+    #[serde(
+        rename = "redactedFields",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub redacted_fields: Vec<String>,
+    /// Id of the entry this call replayed, for `admin.replay_call`.
+    #[serde(rename = "replayOf", default, skip_serializing_if = "Option::is_none")]
+    pub replay_of: Option<String>,
+}
+
+/// Copy `arguments` with every field whose name contains `postcode`, and every `apiKey`
+/// field, set to `null`, returning the copy and the JSON pointers of the blanked fields.
+pub fn redact_arguments(arguments: &Value) -> (Value, Vec<String>) {
+    let mut redacted = arguments.clone();
+    let mut pointers = Vec::new();
+    redact_value(&mut redacted, String::new(), &mut pointers);
+    (redacted, pointers)
+}
+
+fn redact_value(value: &mut Value, pointer: String, pointers: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let child_pointer = format!("{pointer}/{}", escape_pointer(key));
+                if is_redacted_field(key) && !child.is_null() {
+                    *child = Value::Null;
+                    pointers.push(child_pointer);
+                } else {
+                    redact_value(child, child_pointer, pointers);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                redact_value(child, format!("{pointer}/{index}"), pointers);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_redacted_field(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.contains("postcode") || key == "apikey"
+}
+
+pub(crate) fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Append-only record of tool calls in the `audit` sled tree. Keys are the big-endian start
+/// time followed by a sequence number, so iteration order is chronological.
+pub struct AuditLog {
+    tree: Tree,
+    /// Mixed into argument digests; see [`AuditLog::hash_arguments`].
+    hash_key: Vec<u8>,
+    /// Seeded from the clock so keys stay unique across restarts within the same second.
+    sequence: AtomicU64,
+}
+
+impl AuditLog {
+    pub fn new(tree: Tree, hash_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            tree,
+            hash_key: hash_key.into(),
+            sequence: AtomicU64::new(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_nanos() as u64)
+                    .unwrap_or(0),
+            ),
+        }
+    }
+
+    /// SHA-256 over the deployment key and the canonical arguments. Identical calls share a
+    /// digest, but short values such as postcodes cannot be recovered by hashing guesses
+    /// without the key.
+    pub fn hash_arguments(&self, arguments: &Value) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.hash_key);
+        hasher.update(canonicalize(arguments).as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Store `entry` and return its id.
+    pub async fn record(&self, entry: &AuditEntry) -> Result<String, AppError> {
+        let data = serde_json::to_vec(entry)
+            .map_err(|err| AppError::internal(format!("failed to encode audit entry: {err}")))?;
+        let tree = self.tree.clone();
+        let mut key = entry.timestamp.to_be_bytes().to_vec();
+        key.extend_from_slice(&self.sequence.fetch_add(1, Ordering::Relaxed).to_be_bytes());
+        let id = hex::encode(&key);
+
+        task::spawn_blocking(move || -> Result<(), AppError> {
+            tree.insert(key, data)
+                .map_err(|err| AppError::internal(format!("failed to write audit entry: {err}")))?;
+            Ok(())
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("audit task join error: {err}")))??;
+        Ok(id)
+    }
+
+    /// Look up one entry by the id [`AuditLog::record`] returned.
+    pub async fn get(&self, id: &str) -> Result<Option<AuditEntry>, AppError> {
+        let Ok(key) = hex::decode(id) else {
+            return Ok(None);
+        };
+        let tree = self.tree.clone();
+        task::spawn_blocking(move || -> Result<Option<AuditEntry>, AppError> {
+            tree.get(&key)
+                .map_err(|err| AppError::internal(format!("audit lookup failed: {err}")))?
+                .map(|bytes| decode_entry(&key, &bytes))
+                .transpose()
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("audit task join error: {err}")))?
+    }
+
+    /// The newest `limit` entries, newest first.
+    pub async fn recent(&self, limit: usize) -> Result<Vec<AuditEntry>, AppError> {
+        let tree = self.tree.clone();
+        task::spawn_blocking(move || -> Result<Vec<AuditEntry>, AppError> {
+            tree.iter()
+                .rev()
+                .take(limit)
+                .map(|item| {
+                    let (key, bytes) = item
+                        .map_err(|err| AppError::internal(format!("audit lookup failed: {err}")))?;
+                    decode_entry(&key, &bytes)
+                })
+                .collect()
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("audit task join error: {err}")))?
+    }
+
+    /// Remove entries recorded before `cutoff` (Unix seconds); returns how many went.
+    pub async fn prune_before(&self, cutoff: u64) -> Result<usize, AppError> {
+        let tree = self.tree.clone();
+        task::spawn_blocking(move || -> Result<usize, AppError> {
+            let mut removed = 0;
+            for item in tree.range(..cutoff.to_be_bytes().to_vec()) {
+                let (key, _) =
+                    item.map_err(|err| AppError::internal(format!("audit scan failed: {err}")))?;
+                tree.remove(key)
+                    .map_err(|err| AppError::internal(format!("audit prune failed: {err}")))?;
+                removed += 1;
+            }
+            Ok(removed)
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("audit task join error: {err}")))?
+    }
+}
+
+fn decode_entry(key: &[u8], bytes: &[u8]) -> Result<AuditEntry, AppError> {
+    let mut entry: AuditEntry = serde_json::from_slice(bytes)
+        .map_err(|err| AppError::internal(format!("failed to decode audit entry: {err}")))?;
+    entry.id = Some(hex::encode(key));
+    Ok(entry)
+}
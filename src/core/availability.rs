@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Tracks which upstream hosts are currently failing so callers can skip them without
+/// waiting on retries. A host is marked down after `threshold` consecutive failed
+/// requests and stays down for `cooldown_secs`; the first request after that is let
+/// through, and a success clears the mark.
+pub struct AvailabilityTracker {
+    threshold: u32,
+    cooldown_secs: u64,
+    hosts: Mutex<HashMap<String, HostHealth>>,
+}
+
+#[derive(Default)]
+struct HostHealth {
+    consecutive_failures: u32,
+    down_since: Option<u64>,
+}
+
+impl AvailabilityTracker {
+    /// `threshold` of zero disables tracking: no host is ever marked down.
+    pub fn new(threshold: u32, cooldown_secs: u64) -> Self {
+        Self {
+            threshold,
+            cooldown_secs,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record the outcome of one request (after retries) to `host` at steady time `now`.
+    pub fn record(&self, host: &str, success: bool, now: u64) {
+        if self.threshold == 0 {
+            return;
+        }
+        let Ok(mut hosts) = self.hosts.lock() else {
+            return;
+        };
+        if success {
+            hosts.remove(host);
+            return;
+        }
+        let health = hosts.entry(host.to_string()).or_default();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.threshold {
+            if health.down_since.is_some() {
+                tracing::debug!(
+                    host,
+                    "upstream host still failing; availability mark renewed"
+                );
+            } else {
+                tracing::warn!(
+                    host,
+                    failures = health.consecutive_failures,
+                    "upstream host marked unavailable"
+                );
+            }
+            health.down_since = Some(now);
+        }
+    }
+
+    /// Whether `host` is marked down at steady time `now`.
+    pub fn is_down(&self, host: &str, now: u64) -> bool {
+        let Ok(hosts) = self.hosts.lock() else {
+            return false;
+        };
+        hosts
+            .get(host)
+            .and_then(|health| health.down_since)
+            .is_some_and(|since| now < since.saturating_add(self.cooldown_secs))
+    }
+}
@@ -0,0 +1,59 @@
+//! Range checks for argument DTO fields, mirroring the `minimum`/`maximum` of the tool's
+//! input schema so serde rejects exactly what the schema does:
+//!
+//! ```ignore
+//! #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 10>")]
+//! pub limit: Option<usize>,
+//! ```
+
+use std::fmt::Display;
+
+use serde::de::{Deserialize, Deserializer, Error};
+
+/// A number in `MIN..=MAX`.
+pub fn required<'de, D, T, const MIN: i64, const MAX: i64>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + TryInto<i64> + Copy + Display,
+{
+    let value = T::deserialize(deserializer)?;
+    check::<D::Error, T>(value, MIN, MAX)
+}
+
+/// An optional number in `MIN..=MAX`; `null` reads as absent.
+pub fn optional<'de, D, T, const MIN: i64, const MAX: i64>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + TryInto<i64> + Copy + Display,
+{
+    Option::<T>::deserialize(deserializer)?
+        .map(|value| check::<D::Error, T>(value, MIN, MAX))
+        .transpose()
+}
+
+/// An optional fraction in `0.0..=1.0`, such as a relevance threshold.
+pub fn optional_fraction<'de, D>(deserializer: D) -> Result<Option<f32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<f32>::deserialize(deserializer)? {
+        Some(value) if !(0.0..=1.0).contains(&value) => Err(D::Error::custom(format!(
+            "{value} is out of range, expected a number between 0 and 1"
+        ))),
+        value => Ok(value),
+    }
+}
+
+fn check<E: Error, T: TryInto<i64> + Copy + Display>(value: T, min: i64, max: i64) -> Result<T, E> {
+    match value.try_into() {
+        Ok(number) if (min..=max).contains(&number) => Ok(value),
+        _ if max == i64::MAX => Err(E::custom(format!(
+            "{value} is out of range, expected at least {min}"
+        ))),
+        _ => Err(E::custom(format!(
+            "{value} is out of range, expected a number between {min} and {max}"
+        ))),
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+
+/// Short git commit the binary was built from; the build script leaves it empty (or
+/// `unknown`) when built outside a checkout without `GIT_COMMIT` set.
+const GIT_COMMIT: &str = match option_env!("MCP_GIT_COMMIT") {
+    Some(commit) => commit,
+    None => "",
+};
+/// Unix time the build script ran (or `SOURCE_DATE_EPOCH` for reproducible builds).
+const BUILT_AT: &str = match option_env!("MCP_BUILT_AT") {
+    Some(built_at) => built_at,
+    None => "",
+};
+/// Reported for build metadata that could not be captured.
+const UNKNOWN: &str = "unknown";
+
+/// Identifies the running build, for `/api/version`, `initialize`'s `serverInfo`, the
+/// health details and the data of internal errors.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    /// RFC 3339 build time.
+    pub built_at: String,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self::from_parts(GIT_COMMIT, BUILT_AT)
+    }
+
+    /// Build info from the raw build script values: a commit and a Unix timestamp. Missing
+    /// values read `unknown`.
+    pub fn from_parts(git_commit: &'static str, built_at: &str) -> Self {
+        let git_commit = match git_commit.trim() {
+            "" => UNKNOWN,
+            commit => commit,
+        };
+        let built_at = built_at
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_else(|| UNKNOWN.to_string());
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit,
+            built_at,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+tokio::task_local! {
+    static CURRENT_METER: Arc<ByteMeter>;
+}
+
+/// Upstream payload bytes read while serving a single tool call, measured after
+/// decompression, against the tool's optional `TOOL_BYTE_BUDGETS` entry.
+#[derive(Debug, Default)]
+pub struct ByteMeter {
+    budget: Option<u64>,
+    used: AtomicU64,
+}
+
+impl ByteMeter {
+    pub fn new(budget: Option<u64>) -> Self {
+        Self {
+            budget,
+            used: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, bytes: u64) {
+        self.used.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn used(&self) -> u64 {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    pub fn budget(&self) -> Option<u64> {
+        self.budget
+    }
+
+    /// The budget has been reached; fetches that have not started yet should be skipped.
+    pub fn exhausted(&self) -> bool {
+        self.budget.is_some_and(|budget| self.used() >= budget)
+    }
+}
+
+/// Run `future` with `meter` counting every upstream payload it reads. Work spawned onto
+/// other tasks is not counted.
+pub async fn metered<F: Future>(meter: Arc<ByteMeter>, future: F) -> F::Output {
+    CURRENT_METER.scope(meter, future).await
+}
+
+/// Charge `bytes` to the current call's meter; a no-op outside [`metered`].
+pub fn record_bytes(bytes: u64) {
+    let _ = CURRENT_METER.try_with(|meter| meter.record(bytes));
+}
+
+/// Whether the current call has used up its byte budget. Always `false` outside
+/// [`metered`] or when the tool has no budget.
+pub fn budget_exhausted() -> bool {
+    CURRENT_METER
+        .try_with(|meter| meter.exhausted())
+        .unwrap_or(false)
+}
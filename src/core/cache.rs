@@ -1,15 +1,72 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use serde_json::Value;
 use tokio::sync::RwLock;
 
+use crate::core::error::AppError;
+
+/// How one lookup may use the response caches, from a tool's `enableCache` and
+/// `cacheOnly` arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Read the cache, fetch on a miss and store what was fetched.
+    #[default]
+    Normal,
+    /// Neither read nor write the cache (`enableCache: false`).
+    Bypass,
+    /// Answer only from the cache; a miss is [`AppError::NotCached`] and nothing is fetched.
+    CacheOnly,
+}
+
+impl CachePolicy {
+    pub fn from_args(
+        enable_cache: Option<bool>,
+        cache_only: Option<bool>,
+    ) -> Result<Self, AppError> {
+        match (enable_cache.unwrap_or(true), cache_only.unwrap_or(false)) {
+            (true, false) => Ok(Self::Normal),
+            (false, false) => Ok(Self::Bypass),
+            (true, true) => Ok(Self::CacheOnly),
+            (false, true) => Err(AppError::bad_request(
+                "cacheOnly cannot be combined with enableCache: false",
+            )),
+        }
+    }
+
+    pub fn reads(self) -> bool {
+        matches!(self, Self::Normal | Self::CacheOnly)
+    }
+
+    pub fn writes(self) -> bool {
+        self == Self::Normal
+    }
+
+    /// Fail with [`AppError::NotCached`] instead of going upstream for `what`.
+    pub fn allow_fetch(self, what: &str) -> Result<(), AppError> {
+        if self == Self::CacheOnly {
+            return Err(AppError::not_cached(format!("{what} is not cached")));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct CacheManager {
     enabled: bool,
     capacity: usize,
     store: Arc<RwLock<HashMap<String, CacheEntry>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+/// Lookups answered from the cache versus those that fell through, since startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
 }
 
 struct CacheEntry {
@@ -23,6 +80,8 @@ impl CacheManager {
             enabled,
             capacity: capacity as usize,
             store: Arc::new(RwLock::new(HashMap::new())),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -34,14 +93,28 @@ impl CacheManager {
         let mut guard = self.store.write().await;
         if let Some(entry) = guard.get(key) {
             if Instant::now() <= entry.expires_at {
+                self.hits.fetch_add(1, Ordering::Relaxed);
                 return Some(entry.value.clone());
             }
         }
 
         guard.remove(key);
+        self.misses.fetch_add(1, Ordering::Relaxed);
         None
     }
 
+    pub fn counters(&self) -> CacheCounters {
+        CacheCounters {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Entries currently held, including expired ones not yet evicted.
+    pub async fn entry_count(&self) -> usize {
+        self.store.read().await.len()
+    }
+
     pub async fn insert(&self, key: String, value: Value, ttl_seconds: u64) {
         if !self.enabled {
             return;
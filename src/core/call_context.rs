@@ -0,0 +1,61 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+tokio::task_local! {
+    static CURRENT_CALL: Arc<CallContext>;
+}
+
+/// Facts gathered while a single tool call runs and reported back in the result's `_meta`.
+#[derive(Debug, Default)]
+pub struct CallContext {
+    tool: Option<String>,
+    cache_hits: AtomicU64,
+    upstream_fetches: AtomicU64,
+}
+
+impl CallContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn for_tool(tool: &str) -> Self {
+        Self {
+            tool: Some(tool.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// `Some(true)` when every lookup was answered from a cache, `Some(false)` when anything
+    /// went upstream, and `None` when the call made no lookups at all.
+    pub fn cached(&self) -> Option<bool> {
+        let hits = self.cache_hits.load(Ordering::Relaxed);
+        let fetches = self.upstream_fetches.load(Ordering::Relaxed);
+        (hits > 0 || fetches > 0).then_some(fetches == 0)
+    }
+}
+
+/// Run `future` with `context` collecting its cache hits and upstream fetches. Work spawned
+/// onto other tasks is not counted.
+pub async fn with_call_context<F: Future>(context: Arc<CallContext>, future: F) -> F::Output {
+    CURRENT_CALL.scope(context, future).await
+}
+
+/// Note that the current call was answered from a cache; a no-op outside [`with_call_context`].
+pub fn record_cache_hit() {
+    let _ = CURRENT_CALL.try_with(|context| context.cache_hits.fetch_add(1, Ordering::Relaxed));
+}
+
+/// Note that the current call read an upstream response.
+pub fn record_upstream_fetch() {
+    let _ =
+        CURRENT_CALL.try_with(|context| context.upstream_fetches.fetch_add(1, Ordering::Relaxed));
+}
+
+/// Name of the tool the current task is running for, if any.
+pub fn current_tool() -> Option<String> {
+    CURRENT_CALL
+        .try_with(|context| context.tool.clone())
+        .ok()
+        .flatten()
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use tracing::field::{Field, Visit};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::core::notifications::NotificationSink;
+
+/// Event targets forwarded to clients: this crate's modules and the research service's
+/// `research` target. Events from dependencies never leave the server.
+const FORWARDED_TARGETS: &[&str] = &[env!("CARGO_CRATE_NAME"), "research"];
+
+tokio::task_local! {
+    static CLIENT_LOG: ClientLogScope;
+}
+
+/// MCP log levels (RFC 5424 severities), lowest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    #[default]
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl LogLevel {
+    /// Tracing has no levels above `ERROR`; `TRACE` is reported as `debug`.
+    pub fn from_tracing(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => Self::Error,
+            Level::WARN => Self::Warning,
+            Level::INFO => Self::Info,
+            _ => Self::Debug,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Notice => "notice",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+            Self::Alert => "alert",
+            Self::Emergency => "emergency",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ClientLogScope {
+    sink: NotificationSink,
+    min_level: LogLevel,
+}
+
+/// Run `future` forwarding its tracing events at or above `min_level` to `sink` as
+/// `notifications/message`. Without a sink (plain HTTP responses) nothing is forwarded.
+pub async fn with_client_log<F: Future>(
+    sink: Option<NotificationSink>,
+    min_level: LogLevel,
+    future: F,
+) -> F::Output {
+    match sink {
+        Some(sink) => {
+            CLIENT_LOG
+                .scope(ClientLogScope { sink, min_level }, future)
+                .await
+        }
+        None => future.await,
+    }
+}
+
+/// Tracing layer that turns events raised inside [`with_client_log`] into MCP
+/// `notifications/message`. It filters itself, so it can sit beside any other layer.
+pub fn client_log_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    ClientLogLayer.with_filter(ClientLogFilter)
+}
+
+struct ClientLogLayer;
+
+impl<S: Subscriber> Layer<S> for ClientLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let logger = metadata
+            .target()
+            .strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::"))
+            .unwrap_or(metadata.target())
+            .replace("::", ".");
+        let params = json!({
+            "level": LogLevel::from_tracing(metadata.level()).as_str(),
+            "logger": logger,
+            "data": visitor.data,
+        });
+        let _ = CLIENT_LOG.try_with(|scope| scope.sink.notify("notifications/message", params));
+    }
+}
+
+struct ClientLogFilter;
+
+impl ClientLogFilter {
+    fn forwarded_target(metadata: &Metadata<'_>) -> bool {
+        metadata.is_event()
+            && FORWARDED_TARGETS.iter().any(|target| {
+                metadata
+                    .target()
+                    .strip_prefix(target)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+    }
+}
+
+impl<S> Filter<S> for ClientLogFilter {
+    fn enabled(&self, metadata: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        Self::forwarded_target(metadata)
+            && CLIENT_LOG
+                .try_with(|scope| LogLevel::from_tracing(metadata.level()) >= scope.min_level)
+                .unwrap_or(false)
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        // Whether a call is being forwarded changes per task, so matching callsites are
+        // re-checked every time.
+        if Self::forwarded_target(metadata) {
+            Interest::sometimes()
+        } else {
+            Interest::never()
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    data: Map<String, Value>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.data.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.data.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.data.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.data.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.data
+            .insert(field.name().to_string(), json!(format!("{value:?}")));
+    }
+}
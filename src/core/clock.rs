@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How far ahead of [`Clock::steady_secs`] a cache stamp may be before it counts as
+/// written "in the future" (by a process whose clock was ahead of this one).
+pub const FUTURE_TOLERANCE_SECS: u64 = 60;
+
+/// Wall-clock and monotonic readings. The system source backs every [`Clock`] outside
+/// tests; [`MockClock`] lets tests step either reading.
+pub trait TimeSource: Send + Sync {
+    /// Seconds since the Unix epoch. May jump in either direction (NTP corrections,
+    /// snapshot restores).
+    fn wall_secs(&self) -> u64;
+    /// Time since an arbitrary fixed point; never goes backwards.
+    fn monotonic(&self) -> Duration;
+}
+
+struct SystemSource {
+    start: Instant,
+}
+
+impl TimeSource for SystemSource {
+    fn wall_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0)
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Time source for cache TTL bookkeeping. Freshness is measured in "steady" seconds: the
+/// wall clock when the clock was created plus monotonic time since, so stepping the
+/// system clock while the server runs neither extends nor cuts short any entry's TTL.
+/// Entries keep a wall-clock `stored_at` for display.
+///
+/// Clones share the count of future stamps seen.
+#[derive(Clone)]
+pub struct Clock {
+    source: Arc<dyn TimeSource>,
+    anchor_wall: u64,
+    anchor_monotonic: Duration,
+    future_stamps: Arc<AtomicU64>,
+}
+
+impl Clock {
+    pub fn system() -> Self {
+        Self::new(Arc::new(SystemSource {
+            start: Instant::now(),
+        }))
+    }
+
+    pub fn new(source: Arc<dyn TimeSource>) -> Self {
+        Self {
+            anchor_wall: source.wall_secs(),
+            anchor_monotonic: source.monotonic(),
+            source,
+            future_stamps: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Current wall-clock seconds since the Unix epoch.
+    pub fn wall_secs(&self) -> u64 {
+        self.source.wall_secs()
+    }
+
+    /// Wall-clock seconds at creation plus monotonic seconds since.
+    pub fn steady_secs(&self) -> u64 {
+        let elapsed = self
+            .source
+            .monotonic()
+            .saturating_sub(self.anchor_monotonic);
+        self.anchor_wall.saturating_add(elapsed.as_secs())
+    }
+
+    /// Seconds since `stamp`, a [`Clock::steady_secs`] reading taken when an entry was
+    /// written (possibly by an earlier process). `None` when the stamp is more than
+    /// [`FUTURE_TOLERANCE_SECS`] ahead: the caller should treat the entry as just written
+    /// and re-stamp it. Such stamps are counted and logged.
+    pub fn age_secs(&self, stamp: u64) -> Option<u64> {
+        let now = self.steady_secs();
+        if stamp > now.saturating_add(FUTURE_TOLERANCE_SECS) {
+            self.future_stamps.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                stamp,
+                now,
+                "cache entry stamped in the future; treating it as just written"
+            );
+            return None;
+        }
+        Some(now.saturating_sub(stamp))
+    }
+
+    /// Cache entries found stamped in the future since startup.
+    pub fn future_stamps(&self) -> u64 {
+        self.future_stamps.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+/// A [`TimeSource`] that only moves when told to. Clones share their readings.
+#[derive(Clone, Default)]
+pub struct MockClock {
+    wall: Arc<AtomicU64>,
+    monotonic: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    pub fn new(wall_secs: u64) -> Self {
+        Self {
+            wall: Arc::new(AtomicU64::new(wall_secs)),
+            monotonic: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Let `secs` pass: both readings move forward.
+    pub fn advance(&self, secs: u64) {
+        self.wall.fetch_add(secs, Ordering::Relaxed);
+        self.monotonic.fetch_add(secs, Ordering::Relaxed);
+    }
+
+    /// Step the wall clock by `secs` (negative for backwards) without time passing.
+    pub fn step_wall(&self, secs: i64) {
+        let wall = self.wall.load(Ordering::Relaxed);
+        self.wall
+            .store(wall.saturating_add_signed(secs), Ordering::Relaxed);
+    }
+
+    pub fn clock(&self) -> Clock {
+        Clock::new(Arc::new(self.clone()))
+    }
+}
+
+impl TimeSource for MockClock {
+    fn wall_secs(&self) -> u64 {
+        self.wall.load(Ordering::Relaxed)
+    }
+
+    fn monotonic(&self) -> Duration {
+        Duration::from_secs(self.monotonic.load(Ordering::Relaxed))
+    }
+}
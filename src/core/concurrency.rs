@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// At most `limit` holders at once; later arrivals queue for up to `wait` and are then
+/// turned away, so a burst sheds load instead of piling up behind slow upstreams.
+pub struct ConcurrencyLimit {
+    permits: Arc<Semaphore>,
+    limit: usize,
+    wait: Duration,
+    shed: AtomicU64,
+}
+
+/// A held slot; released when dropped.
+pub struct ConcurrencyPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl ConcurrencyLimit {
+    /// `limit` is raised to 1; a zero `wait` rejects as soon as every slot is taken.
+    pub fn new(limit: usize, wait: Duration) -> Self {
+        let limit = limit.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(limit)),
+            limit,
+            wait,
+            shed: AtomicU64::new(0),
+        }
+    }
+
+    /// Take a slot, waiting up to the configured time for one to free up. `None` when
+    /// none did; the rejection is counted.
+    pub async fn acquire(&self) -> Option<ConcurrencyPermit> {
+        self.acquire_many(1).await
+    }
+
+    /// Take `count` slots at once, all or none, on the same terms as [`Self::acquire`].
+    /// More slots than the limit are never granted.
+    pub async fn acquire_many(&self, count: usize) -> Option<ConcurrencyPermit> {
+        let Some(count) = u32::try_from(count).ok().filter(|_| count <= self.limit) else {
+            self.shed.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let permits = Arc::clone(&self.permits);
+        let permit = match Arc::clone(&permits).try_acquire_many_owned(count) {
+            Ok(permit) => Some(permit),
+            Err(_) if self.wait.is_zero() => None,
+            Err(_) => tokio::time::timeout(self.wait, permits.acquire_many_owned(count))
+                .await
+                .ok()
+                .and_then(Result::ok),
+        };
+        if permit.is_none() {
+            self.shed.fetch_add(1, Ordering::Relaxed);
+        }
+        permit.map(|permit| ConcurrencyPermit { _permit: permit })
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Slots currently held.
+    pub fn in_flight(&self) -> usize {
+        self.limit - self.permits.available_permits()
+    }
+
+    /// Callers turned away since startup.
+    pub fn shed(&self) -> u64 {
+        self.shed.load(Ordering::Relaxed)
+    }
+}
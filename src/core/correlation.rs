@@ -0,0 +1,55 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+/// Header carrying the correlation id on outgoing upstream requests.
+pub const CORRELATION_HEADER: &str = "X-Correlation-Id";
+
+const MAX_CLIENT_ID_LEN: usize = 64;
+
+tokio::task_local! {
+    static CURRENT_CORRELATION_ID: String;
+}
+
+/// Pick the correlation id for a tool call: the client's `X-Request-Id` when it is a short
+/// token of letters, digits, `-`, `_` or `.`, otherwise a fresh random id.
+pub fn correlation_id_for(client_request_id: Option<&str>) -> String {
+    client_request_id
+        .map(str::trim)
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_CLIENT_ID_LEN
+                && value
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(new_correlation_id)
+}
+
+/// A 32-character hex id, unique per process and unpredictable enough for log correlation.
+pub fn new_correlation_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(nanos.to_le_bytes());
+    hasher.update(COUNTER.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+    hasher.update(std::process::id().to_le_bytes());
+    hex::encode(&hasher.finalize()[..16])
+}
+
+/// Run `future` with `id` as the correlation id of every upstream request it makes.
+pub async fn with_correlation_id<F: Future>(id: String, future: F) -> F::Output {
+    CURRENT_CORRELATION_ID.scope(id, future).await
+}
+
+/// The correlation id of the tool call being served, if any.
+pub fn current_correlation_id() -> Option<String> {
+    CURRENT_CORRELATION_ID.try_with(Clone::clone).ok()
+}
@@ -1,3 +1,5 @@
+use std::any::Any;
+
 use axum::Json;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -5,6 +7,8 @@ use serde::Serialize;
 use serde_json::Value;
 use thiserror::Error;
 
+use crate::core::correlation::current_correlation_id;
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("configuration error: {message}")]
@@ -18,6 +22,10 @@ pub enum AppError {
     },
     #[error("internal error: {message}")]
     Internal { message: String },
+    /// A `cacheOnly` lookup found nothing cached. Tools answer it with an empty
+    /// `cacheMiss` result rather than an error.
+    #[error("not cached: {message}")]
+    NotCached { message: String },
 }
 
 impl AppError {
@@ -33,7 +41,14 @@ impl AppError {
         }
     }
 
-    pub fn upstream_with_data(message: impl Into<String>, data: Value) -> Self {
+    /// An upstream failure. Raised while serving a request, `data` also records that
+    /// request's id as `requestId` so the failure can be matched to its logs.
+    pub fn upstream_with_data(message: impl Into<String>, mut data: Value) -> Self {
+        if let (Some(object), Some(request_id)) = (data.as_object_mut(), current_correlation_id()) {
+            object
+                .entry("requestId")
+                .or_insert_with(|| Value::String(request_id));
+        }
         Self::Upstream {
             message: message.into(),
             data: Some(data),
@@ -45,6 +60,12 @@ impl AppError {
             message: message.into(),
         }
     }
+
+    pub fn not_cached(message: impl Into<String>) -> Self {
+        Self::NotCached {
+            message: message.into(),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -60,6 +81,7 @@ impl IntoResponse for AppError {
             }
             Self::BadRequest { message } => (StatusCode::BAD_REQUEST, message),
             Self::Upstream { message, .. } => (StatusCode::BAD_GATEWAY, message),
+            Self::NotCached { message } => (StatusCode::NOT_FOUND, message),
         };
 
         let body = Json(ErrorResponse { error: message });
@@ -67,3 +89,13 @@ impl IntoResponse for AppError {
         (status, body).into_response()
     }
 }
+
+/// The message a panic was raised with, when it was a string (as `panic!` and failed
+/// `unwrap`s produce).
+pub fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload")
+}
@@ -0,0 +1,200 @@
+//! Upstream fault injection for resilience tests, only in builds with the `fault-injection`
+//! feature. `FAULTS` lists rules per host, separated by `;`, each a target and
+//! comma-separated settings: `members:error_rate=0.5;bills:latency_ms=2000,truncate_rate=0.1`.
+//!
+//! A target is `*`, a full host, or one label of it: `bills` matches
+//! `bills-api.parliament.uk` and `legislation` matches `www.legislation.gov.uk`. Hosts are
+//! the real API hosts, even when `UPSTREAM_BASE_URL` redirects requests. The first rule
+//! matching a host applies to every attempt against it, retries included.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use axum::http::header::CONTENT_LENGTH;
+use serde::Serialize;
+
+use crate::core::error::AppError;
+
+#[derive(Debug, Default, Serialize)]
+pub struct FaultConfig {
+    rules: Vec<FaultRule>,
+    /// Attempts failed, delayed or truncated so far.
+    #[serde(serialize_with = "serialize_count")]
+    injected: AtomicU64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultRule {
+    pub target: String,
+    /// Share of attempts answered with an injected `503` instead of being sent.
+    pub error_rate: f64,
+    /// Delay added before every attempt.
+    pub latency_ms: u64,
+    /// Share of successful responses cut to half their body.
+    pub truncate_rate: f64,
+}
+
+/// What to do to one upstream attempt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fault {
+    pub delay: Option<Duration>,
+    pub fail: bool,
+    pub truncate: bool,
+}
+
+impl FaultConfig {
+    /// Parse a `FAULTS` value; an empty one injects nothing.
+    pub fn parse(spec: &str) -> Result<Self, AppError> {
+        let mut rules = Vec::new();
+        for rule in spec
+            .split(';')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+        {
+            let invalid = |reason: &str| {
+                AppError::configuration(format!("invalid FAULTS rule {rule:?}: {reason}"))
+            };
+            let (target, settings) = rule
+                .split_once(':')
+                .ok_or_else(|| invalid("expected host:setting=value"))?;
+            let target = target.trim();
+            if target.is_empty() {
+                return Err(invalid("missing host"));
+            }
+
+            let mut parsed = FaultRule {
+                target: target.to_ascii_lowercase(),
+                error_rate: 0.0,
+                latency_ms: 0,
+                truncate_rate: 0.0,
+            };
+            for setting in settings.split(',').map(str::trim) {
+                let (name, value) = setting
+                    .split_once('=')
+                    .ok_or_else(|| invalid("expected setting=value"))?;
+                let value = value.trim();
+                match name.trim() {
+                    "error_rate" => {
+                        parsed.error_rate = parse_rate(value)
+                            .ok_or_else(|| invalid("error_rate must be between 0 and 1"))?
+                    }
+                    "truncate_rate" => {
+                        parsed.truncate_rate = parse_rate(value)
+                            .ok_or_else(|| invalid("truncate_rate must be between 0 and 1"))?
+                    }
+                    "latency_ms" => {
+                        parsed.latency_ms = value
+                            .parse()
+                            .map_err(|_| invalid("latency_ms must be a whole number"))?
+                    }
+                    other => {
+                        return Err(invalid(&format!(
+                            "unknown setting {other:?}; expected error_rate, latency_ms or truncate_rate"
+                        )));
+                    }
+                }
+            }
+            rules.push(parsed);
+        }
+        Ok(Self {
+            rules,
+            injected: AtomicU64::new(0),
+        })
+    }
+
+    pub fn rules(&self) -> &[FaultRule] {
+        &self.rules
+    }
+
+    pub fn injected(&self) -> u64 {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    /// Draw the faults for one attempt against `host`.
+    pub fn sample(&self, host: &str) -> Fault {
+        let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| matches_host(&rule.target, host))
+        else {
+            return Fault::default();
+        };
+        let fault = Fault {
+            delay: (rule.latency_ms > 0).then(|| Duration::from_millis(rule.latency_ms)),
+            fail: roll(rule.error_rate),
+            truncate: roll(rule.truncate_rate),
+        };
+        if fault != Fault::default() {
+            self.injected.fetch_add(1, Ordering::Relaxed);
+            tracing::debug!(host, ?fault, "injecting upstream fault");
+        }
+        fault
+    }
+}
+
+/// The response an injected failure stands in for.
+pub fn failure_response() -> reqwest::Response {
+    axum::http::Response::builder()
+        .status(503)
+        .header("content-type", "text/plain")
+        .body("injected fault")
+        .expect("static response")
+        .into()
+}
+
+/// `response` with only the first half of its body, as if the connection dropped.
+pub async fn truncate(response: reqwest::Response) -> reqwest::Result<reqwest::Response> {
+    let status = response.status();
+    let mut headers = response.headers().clone();
+    headers.remove(CONTENT_LENGTH);
+    let body = response.bytes().await?;
+
+    let mut truncated = axum::http::Response::new(body.slice(..body.len() / 2));
+    *truncated.status_mut() = status;
+    *truncated.headers_mut() = headers;
+    Ok(truncated.into())
+}
+
+fn parse_rate(value: &str) -> Option<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|rate| (0.0..=1.0).contains(rate))
+}
+
+fn matches_host(target: &str, host: &str) -> bool {
+    target == "*"
+        || target.eq_ignore_ascii_case(host)
+        || host.split('.').any(|label| {
+            label.eq_ignore_ascii_case(target)
+                || label
+                    .strip_prefix(target)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+}
+
+/// True with probability `rate`. Rates of 0 and 1 are exact, so tests can rely on them.
+fn roll(rate: f64) -> bool {
+    if rate <= 0.0 {
+        return false;
+    }
+    if rate >= 1.0 {
+        return true;
+    }
+    // `RandomState` is randomly keyed per process; no test build needs a better source.
+    static DRAWS: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(DRAWS.fetch_add(1, Ordering::Relaxed));
+    let unit = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+    unit < rate
+}
+
+fn serialize_count<S: serde::Serializer>(
+    count: &AtomicU64,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(count.load(Ordering::Relaxed))
+}
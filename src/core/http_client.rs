@@ -13,3 +13,41 @@ pub fn build_http_client(disable_proxy: bool) -> Result<Client, reqwest::Error>
 
     builder.build()
 }
+
+const SENSITIVE_QUERY_KEYS: &[&str] = &[
+    "key",
+    "api_key",
+    "apikey",
+    "token",
+    "access_token",
+    "secret",
+    "password",
+    "signature",
+    "auth",
+];
+
+/// Drop credential-like query parameters from a URL before it is shown to clients.
+/// Unparseable input is reduced to everything before the query string.
+pub fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        return url.split('?').next().unwrap_or_default().to_string();
+    };
+
+    let retained = parsed
+        .query_pairs()
+        .filter(|(name, _)| {
+            !SENSITIVE_QUERY_KEYS
+                .iter()
+                .any(|key| name.eq_ignore_ascii_case(key))
+        })
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+
+    if retained.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(retained);
+    }
+
+    parsed.to_string()
+}
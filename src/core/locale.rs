@@ -0,0 +1,50 @@
+//! Languages the server writes its own prose in (research summaries and advisories).
+//! Parliamentary data is passed through as published, in English.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    /// Welsh.
+    Cy,
+}
+
+impl Locale {
+    pub const SUPPORTED: &[Locale] = &[Locale::En, Locale::Cy];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Cy => "cy",
+        }
+    }
+
+    /// Read a language tag by its primary subtag, so `cy`, `CY` and `cy-GB` are Welsh.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_']).next().unwrap_or_default();
+        Self::SUPPORTED
+            .iter()
+            .copied()
+            .find(|locale| language.eq_ignore_ascii_case(locale.code()))
+    }
+
+    /// `en, cy`, for error messages and schema descriptions.
+    pub fn supported_list() -> String {
+        Self::SUPPORTED
+            .iter()
+            .map(|locale| locale.code())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
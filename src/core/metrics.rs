@@ -0,0 +1,479 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::core::cache::CacheCounters;
+use crate::core::clock::Clock;
+use crate::core::concurrency::ConcurrencyLimit;
+
+/// Upper bounds, in seconds, of the tool and upstream latency histogram buckets.
+const LATENCY_BUCKETS_SECS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+/// Distinct unknown field names tracked before further names are counted as `_other`.
+const MAX_UNKNOWN_FIELD_SERIES: usize = 100;
+/// Unknown field names longer than this are counted as `_other`.
+const MAX_UNKNOWN_FIELD_LEN: usize = 64;
+
+/// Process-wide request counters rendered in the Prometheus text format. Labels are limited to
+/// known method and tool names, and a capped set of unknown field names, so clients cannot
+/// grow the series set without bound.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    requests: Mutex<BTreeMap<(String, &'static str), u64>>,
+    tool_calls: Mutex<BTreeMap<(String, &'static str), u64>>,
+    tool_latency: Mutex<BTreeMap<String, Histogram>>,
+    slow_tool_calls: Mutex<BTreeMap<String, u64>>,
+    query_api_key_requests: AtomicU64,
+    panics: Mutex<BTreeMap<&'static str, u64>>,
+    /// Client-chosen names, so the series set is capped at `MAX_UNKNOWN_FIELD_SERIES`.
+    unknown_fields: Mutex<BTreeMap<(String, &'static str), u64>>,
+}
+
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS_SECS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if let Some(index) = LATENCY_BUCKETS_SECS.iter().position(|bound| secs <= *bound) {
+            self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one JSON-RPC request; `ok` is false when it was answered with an error.
+    pub fn record_request(&self, method: &str, ok: bool) {
+        let status = if ok { "ok" } else { "error" };
+        increment(&self.requests, (method.to_string(), status));
+    }
+
+    /// Count one finished `tools/call` and add its latency to the tool's histogram.
+    pub fn record_tool_call(&self, tool: &str, outcome: &'static str, elapsed: Duration) {
+        increment(&self.tool_calls, (tool.to_string(), outcome));
+        if let Ok(mut guard) = self.tool_latency.lock() {
+            guard
+                .entry(tool.to_string())
+                .or_insert_with(Histogram::new)
+                .observe(elapsed);
+        }
+    }
+
+    /// Count a tool call that took at least `SLOW_REQUEST_MS`.
+    pub fn record_slow_tool_call(&self, tool: &str) {
+        if let Ok(mut guard) = self.slow_tool_calls.lock() {
+            *guard.entry(tool.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Count a request authenticated with `?api_key=` instead of the header.
+    pub fn record_query_api_key(&self) {
+        self.query_api_key_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count one recovered panic. `scope` is `tool` for a panic inside a tool call (answered
+    /// with an `isError` result) and `request` for one anywhere else in a request.
+    pub fn record_panic(&self, scope: &'static str) {
+        if let Ok(mut guard) = self.panics.lock() {
+            *guard.entry(scope).or_insert(0) += 1;
+        }
+    }
+
+    /// Count one field a client sent that the protocol or tool schema does not declare.
+    /// `location` is `request` for top-level JSON-RPC members and `arguments` for tool
+    /// arguments.
+    pub fn record_unknown_field(&self, location: &'static str, field: &str) {
+        let Ok(mut guard) = self.unknown_fields.lock() else {
+            return;
+        };
+        let key = (field.to_string(), location);
+        let tracked = guard.contains_key(&key)
+            || (field.len() <= MAX_UNKNOWN_FIELD_LEN && guard.len() < MAX_UNKNOWN_FIELD_SERIES);
+        let key = if tracked {
+            key
+        } else {
+            ("_other".to_string(), location)
+        };
+        *guard.entry(key).or_insert(0) += 1;
+    }
+
+    /// Render every series in the Prometheus text exposition format (version 0.0.4).
+    /// `upstream_limits` is the adaptive concurrency limit of each upstream host.
+    /// `upstream` holds the Parliament client's per-host request metrics, `scans` the
+    /// research cache's scan timings, `clock` the Sled caches' TTL clock and `limits` each
+    /// load-shedding concurrency limit by scope.
+    pub fn render(
+        &self,
+        cache: CacheCounters,
+        upstream_limits: &BTreeMap<String, usize>,
+        upstream: &UpstreamMetrics,
+        scans: &ScanMetrics,
+        clock: &Clock,
+        limits: &[(&'static str, &ConcurrencyLimit)],
+    ) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP mcp_jsonrpc_requests_total JSON-RPC requests by method and status.\n");
+        out.push_str("# TYPE mcp_jsonrpc_requests_total counter\n");
+        if let Ok(guard) = self.requests.lock() {
+            for ((method, status), count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_jsonrpc_requests_total{{method=\"{}\",status=\"{status}\"}} {count}",
+                    escape_label(method)
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP mcp_tool_calls_total Finished tools/call requests by tool and outcome.\n",
+        );
+        out.push_str("# TYPE mcp_tool_calls_total counter\n");
+        if let Ok(guard) = self.tool_calls.lock() {
+            for ((tool, outcome), count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_tool_calls_total{{tool=\"{}\",outcome=\"{outcome}\"}} {count}",
+                    escape_label(tool)
+                );
+            }
+        }
+
+        out.push_str("# HELP mcp_tool_call_duration_seconds Time spent serving tools/call.\n");
+        out.push_str("# TYPE mcp_tool_call_duration_seconds histogram\n");
+        if let Ok(guard) = self.tool_latency.lock() {
+            for (tool, histogram) in guard.iter() {
+                let labels = format!("tool=\"{}\"", escape_label(tool));
+                write_histogram(
+                    &mut out,
+                    "mcp_tool_call_duration_seconds",
+                    &labels,
+                    histogram,
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP mcp_slow_tool_calls_total Tool calls that took at least SLOW_REQUEST_MS.\n",
+        );
+        out.push_str("# TYPE mcp_slow_tool_calls_total counter\n");
+        if let Ok(guard) = self.slow_tool_calls.lock() {
+            for (tool, count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_slow_tool_calls_total{{tool=\"{}\"}} {count}",
+                    escape_label(tool)
+                );
+            }
+        }
+
+        out.push_str("# HELP mcp_cache_hits_total In-memory upstream cache hits.\n");
+        out.push_str("# TYPE mcp_cache_hits_total counter\n");
+        let _ = writeln!(out, "mcp_cache_hits_total {}", cache.hits);
+        out.push_str("# HELP mcp_cache_misses_total In-memory upstream cache misses.\n");
+        out.push_str("# TYPE mcp_cache_misses_total counter\n");
+        let _ = writeln!(out, "mcp_cache_misses_total {}", cache.misses);
+        out.push_str(
+            "# HELP mcp_cache_future_stamps_total Cached entries found stamped in the future and treated as just written.\n",
+        );
+        out.push_str("# TYPE mcp_cache_future_stamps_total counter\n");
+        let _ = writeln!(
+            out,
+            "mcp_cache_future_stamps_total {}",
+            clock.future_stamps()
+        );
+        out.push_str(
+            "# HELP mcp_query_api_key_requests_total Requests that sent the API key in the query string.\n",
+        );
+        out.push_str("# TYPE mcp_query_api_key_requests_total counter\n");
+        let _ = writeln!(
+            out,
+            "mcp_query_api_key_requests_total {}",
+            self.query_api_key_requests.load(Ordering::Relaxed)
+        );
+        out.push_str(
+            "# HELP mcp_panics_total Panics recovered while serving requests, by scope.\n",
+        );
+        out.push_str("# TYPE mcp_panics_total counter\n");
+        if let Ok(guard) = self.panics.lock() {
+            for (scope, count) in guard.iter() {
+                let _ = writeln!(out, "mcp_panics_total{{scope=\"{scope}\"}} {count}");
+            }
+        }
+        out.push_str(
+            "# HELP mcp_unknown_fields_total Undeclared request members and tool arguments by name.\n",
+        );
+        out.push_str("# TYPE mcp_unknown_fields_total counter\n");
+        if let Ok(guard) = self.unknown_fields.lock() {
+            for ((field, location), count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_unknown_fields_total{{location=\"{location}\",field=\"{}\"}} {count}",
+                    escape_label(field)
+                );
+            }
+        }
+        out.push_str(
+            "# HELP mcp_upstream_concurrency_limit Adaptive in-flight request limit per upstream host.\n",
+        );
+        out.push_str("# TYPE mcp_upstream_concurrency_limit gauge\n");
+        for (host, limit) in upstream_limits {
+            let _ = writeln!(
+                out,
+                "mcp_upstream_concurrency_limit{{host=\"{}\"}} {limit}",
+                escape_label(host)
+            );
+        }
+        out.push_str(
+            "# HELP mcp_concurrency_in_flight Requests holding a concurrency slot, by scope.\n",
+        );
+        out.push_str("# TYPE mcp_concurrency_in_flight gauge\n");
+        for (scope, limit) in limits {
+            let _ = writeln!(
+                out,
+                "mcp_concurrency_in_flight{{scope=\"{scope}\"}} {}",
+                limit.in_flight()
+            );
+        }
+        out.push_str("# HELP mcp_concurrency_limit Concurrency slots available, by scope.\n");
+        out.push_str("# TYPE mcp_concurrency_limit gauge\n");
+        for (scope, limit) in limits {
+            let _ = writeln!(
+                out,
+                "mcp_concurrency_limit{{scope=\"{scope}\"}} {}",
+                limit.limit()
+            );
+        }
+        out.push_str(
+            "# HELP mcp_concurrency_shed_total Requests rejected as busy after queueing, by scope.\n",
+        );
+        out.push_str("# TYPE mcp_concurrency_shed_total counter\n");
+        for (scope, limit) in limits {
+            let _ = writeln!(
+                out,
+                "mcp_concurrency_shed_total{{scope=\"{scope}\"}} {}",
+                limit.shed()
+            );
+        }
+        upstream.render_into(&mut out);
+        scans.render_into(&mut out);
+
+        out
+    }
+}
+
+/// Upstream API requests by host and calling tool, recorded by the Parliament client. Hosts
+/// come from the client's fixed base URLs and tools from the registered tool names; requests
+/// made outside a tool call are labelled `none`.
+#[derive(Default)]
+pub struct UpstreamMetrics {
+    requests: Mutex<BTreeMap<(String, String, &'static str), u64>>,
+    retries: Mutex<BTreeMap<(String, String), u64>>,
+    slow: Mutex<BTreeMap<(String, String), u64>>,
+    latency: Mutex<BTreeMap<(String, String), Histogram>>,
+}
+
+impl UpstreamMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one request attempt by status class (`2xx`, `4xx`, ...; `error` when no response
+    /// arrived) and add its latency to the host and tool's histogram.
+    pub fn record_attempt(&self, host: &str, tool: &str, status: Option<u16>, elapsed: Duration) {
+        if let Ok(mut guard) = self.requests.lock() {
+            *guard
+                .entry((host.to_string(), tool.to_string(), status_class(status)))
+                .or_insert(0) += 1;
+        }
+        if let Ok(mut guard) = self.latency.lock() {
+            guard
+                .entry((host.to_string(), tool.to_string()))
+                .or_insert_with(Histogram::new)
+                .observe(elapsed);
+        }
+    }
+
+    /// Count a failed attempt that is about to be retried.
+    pub fn record_retry(&self, host: &str, tool: &str) {
+        if let Ok(mut guard) = self.retries.lock() {
+            *guard
+                .entry((host.to_string(), tool.to_string()))
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// Count an attempt that took at least `SLOW_UPSTREAM_MS` to answer.
+    pub fn record_slow(&self, host: &str, tool: &str) {
+        if let Ok(mut guard) = self.slow.lock() {
+            *guard
+                .entry((host.to_string(), tool.to_string()))
+                .or_insert(0) += 1;
+        }
+    }
+
+    fn render_into(&self, out: &mut String) {
+        out.push_str(
+            "# HELP mcp_upstream_requests_total Upstream request attempts by host, tool and status class.\n",
+        );
+        out.push_str("# TYPE mcp_upstream_requests_total counter\n");
+        if let Ok(guard) = self.requests.lock() {
+            for ((host, tool, class), count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_upstream_requests_total{{{},status=\"{class}\"}} {count}",
+                    upstream_labels(host, tool)
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP mcp_upstream_retries_total Upstream attempts that failed and were retried.\n",
+        );
+        out.push_str("# TYPE mcp_upstream_retries_total counter\n");
+        if let Ok(guard) = self.retries.lock() {
+            for ((host, tool), count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_upstream_retries_total{{{}}} {count}",
+                    upstream_labels(host, tool)
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP mcp_upstream_slow_requests_total Upstream attempts that took at least SLOW_UPSTREAM_MS to answer.\n",
+        );
+        out.push_str("# TYPE mcp_upstream_slow_requests_total counter\n");
+        if let Ok(guard) = self.slow.lock() {
+            for ((host, tool), count) in guard.iter() {
+                let _ = writeln!(
+                    out,
+                    "mcp_upstream_slow_requests_total{{{}}} {count}",
+                    upstream_labels(host, tool)
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP mcp_upstream_request_duration_seconds Time until an upstream attempt answered or failed.\n",
+        );
+        out.push_str("# TYPE mcp_upstream_request_duration_seconds histogram\n");
+        if let Ok(guard) = self.latency.lock() {
+            for ((host, tool), histogram) in guard.iter() {
+                write_histogram(
+                    out,
+                    "mcp_upstream_request_duration_seconds",
+                    &upstream_labels(host, tool),
+                    histogram,
+                );
+            }
+        }
+    }
+}
+
+/// Time taken by full or paged walks of a Sled tree, by scan name (`list_cached`,
+/// `compact`). Scans that fail or are dropped part-way are not recorded.
+#[derive(Default)]
+pub struct ScanMetrics {
+    latency: Mutex<BTreeMap<&'static str, Histogram>>,
+}
+
+impl ScanMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_scan(&self, scan: &'static str, elapsed: Duration) {
+        if let Ok(mut guard) = self.latency.lock() {
+            guard
+                .entry(scan)
+                .or_insert_with(Histogram::new)
+                .observe(elapsed);
+        }
+    }
+
+    fn render_into(&self, out: &mut String) {
+        out.push_str(
+            "# HELP mcp_cache_scan_duration_seconds Time spent walking a Sled cache tree.\n",
+        );
+        out.push_str("# TYPE mcp_cache_scan_duration_seconds histogram\n");
+        if let Ok(guard) = self.latency.lock() {
+            for (scan, histogram) in guard.iter() {
+                write_histogram(
+                    out,
+                    "mcp_cache_scan_duration_seconds",
+                    &format!("scan=\"{scan}\""),
+                    histogram,
+                );
+            }
+        }
+    }
+}
+
+fn status_class(status: Option<u16>) -> &'static str {
+    match status {
+        Some(100..=199) => "1xx",
+        Some(200..=299) => "2xx",
+        Some(300..=399) => "3xx",
+        Some(400..=499) => "4xx",
+        Some(_) => "5xx",
+        None => "error",
+    }
+}
+
+fn upstream_labels(host: &str, tool: &str) -> String {
+    format!(
+        "host=\"{}\",tool=\"{}\"",
+        escape_label(host),
+        escape_label(tool)
+    )
+}
+
+/// Write the cumulative buckets, sum and count of one histogram series.
+fn write_histogram(out: &mut String, name: &str, labels: &str, histogram: &Histogram) {
+    let mut cumulative = 0;
+    for (bound, bucket) in LATENCY_BUCKETS_SECS.iter().zip(&histogram.buckets) {
+        cumulative += bucket.load(Ordering::Relaxed);
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {cumulative}");
+    }
+    let count = histogram.count.load(Ordering::Relaxed);
+    let sum = histogram.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+    let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {count}");
+    let _ = writeln!(out, "{name}_sum{{{labels}}} {sum}");
+    let _ = writeln!(out, "{name}_count{{{labels}}} {count}");
+}
+
+fn increment(series: &Mutex<BTreeMap<(String, &'static str), u64>>, key: (String, &'static str)) {
+    if let Ok(mut guard) = series.lock() {
+        *guard.entry(key).or_insert(0) += 1;
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,3 +1,25 @@
+pub mod adaptive_limit;
+pub mod audit;
+pub mod availability;
+pub mod bounds;
+pub mod build_info;
+pub mod byte_budget;
 pub mod cache;
+pub mod call_context;
+pub mod client_log;
+pub mod clock;
+pub mod concurrency;
+pub mod correlation;
 pub mod error;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 pub mod http_client;
+pub mod locale;
+pub mod metrics;
+pub mod notifications;
+pub mod preferences;
+pub mod rate_limit;
+pub mod recording;
+pub mod signing;
+pub mod stats;
+pub mod usage;
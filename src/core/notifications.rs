@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::{Value, json};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Channel used by transports that can push JSON-RPC notifications to the client while a
+/// request is still being processed (e.g. an SSE response stream).
+#[derive(Debug, Clone)]
+pub struct NotificationSink {
+    sender: UnboundedSender<Value>,
+}
+
+impl NotificationSink {
+    pub fn new(sender: UnboundedSender<Value>) -> Self {
+        Self { sender }
+    }
+
+    pub fn notify(&self, method: &str, params: Value) {
+        let message = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        });
+
+        if self.sender.send(message).is_err() {
+            tracing::debug!(
+                method,
+                "notification receiver dropped; discarding notification"
+            );
+        }
+    }
+
+    pub fn send_raw(&self, message: Value) {
+        if self.sender.send(message).is_err() {
+            tracing::debug!("notification receiver dropped; discarding message");
+        }
+    }
+}
+
+/// Emits `notifications/progress` messages for a client-supplied progress token.
+#[derive(Debug)]
+pub struct ProgressReporter {
+    sink: NotificationSink,
+    token: Value,
+    completed: AtomicU64,
+}
+
+impl ProgressReporter {
+    pub fn new(sink: NotificationSink, token: Value) -> Self {
+        Self {
+            sink,
+            token,
+            completed: AtomicU64::new(0),
+        }
+    }
+
+    /// Record one more completed unit of work out of `total` and notify the client.
+    pub fn advance(&self, total: u64, message: &str) {
+        let progress = self.completed.fetch_add(1, Ordering::SeqCst) + 1;
+        self.sink.notify(
+            "notifications/progress",
+            json!({
+                "progressToken": self.token,
+                "progress": progress,
+                "total": total,
+                "message": message,
+            }),
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sled::Tree;
+use tokio::task;
+
+use crate::core::bounds;
+use crate::core::error::AppError;
+
+/// How a successful tool result's text content block renders the payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TextContentMode {
+    /// Indented JSON.
+    #[default]
+    Pretty,
+    /// Single-line JSON.
+    Compact,
+    /// Indented JSON in a fenced `json` code block.
+    Markdown,
+}
+
+impl TextContentMode {
+    /// The text content block for `payload`. Depends on nothing but the payload, so the
+    /// same result always renders the same way.
+    pub fn render(self, payload: &Value) -> serde_json::Result<String> {
+        match self {
+            Self::Pretty => serde_json::to_string_pretty(payload),
+            Self::Compact => serde_json::to_string(payload),
+            Self::Markdown => serde_json::to_string_pretty(payload)
+                .map(|rendered| format!("```json\n{rendered}\n```")),
+        }
+    }
+}
+
+/// Defaults applied to one API key's tool calls when the call does not say otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPreferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_content: Option<TextContentMode>,
+    /// `limit` for `research.run` calls that omit it, within that tool's bounds.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "bounds::optional::<_, _, 1, 10>"
+    )]
+    pub research_limit: Option<usize>,
+    /// Whether results carry `_meta`; on unless turned off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_meta: Option<bool>,
+}
+
+/// Per-key preferences in the `preferences` sled tree, set with
+/// `admin.set_key_preferences`. Entries are keyed by the SHA-256 of the API key so the
+/// key itself is never written to disk.
+pub struct PreferenceStore {
+    tree: Tree,
+}
+
+impl PreferenceStore {
+    pub fn new(tree: Tree) -> Self {
+        Self { tree }
+    }
+
+    pub async fn get(&self, api_key: &str) -> Result<Option<KeyPreferences>, AppError> {
+        let tree = self.tree.clone();
+        let key = storage_key(api_key);
+        task::spawn_blocking(move || -> Result<Option<KeyPreferences>, AppError> {
+            tree.get(key)
+                .map_err(|err| AppError::internal(format!("preference lookup failed: {err}")))?
+                .map(|bytes| {
+                    serde_json::from_slice(&bytes).map_err(|err| {
+                        AppError::internal(format!("failed to decode key preferences: {err}"))
+                    })
+                })
+                .transpose()
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("preference task join error: {err}")))?
+    }
+
+    /// Replace the key's preferences; empty preferences remove the entry.
+    pub async fn set(&self, api_key: &str, preferences: &KeyPreferences) -> Result<(), AppError> {
+        let data = serde_json::to_vec(preferences).map_err(|err| {
+            AppError::internal(format!("failed to encode key preferences: {err}"))
+        })?;
+        let clear = *preferences == KeyPreferences::default();
+        let tree = self.tree.clone();
+        let key = storage_key(api_key);
+        task::spawn_blocking(move || -> Result<(), AppError> {
+            let result = if clear {
+                tree.remove(key).map(|_| ())
+            } else {
+                tree.insert(key, data).map(|_| ())
+            };
+            result.map_err(|err| AppError::internal(format!("failed to store preferences: {err}")))
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("preference task join error: {err}")))?
+    }
+}
+
+fn storage_key(api_key: &str) -> Vec<u8> {
+    Sha256::digest(api_key.as_bytes()).to_vec()
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::{Instant, sleep_until};
+
+/// Spaces out requests to each upstream host so bulk work (exports, research fan-out)
+/// stays within the fair-use limits of the Parliament APIs.
+pub struct RateLimiter {
+    interval: Option<Duration>,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    /// `requests_per_second` of zero disables limiting entirely.
+    pub fn new(requests_per_second: u32) -> Self {
+        let interval = (requests_per_second > 0)
+            .then(|| Duration::from_secs_f64(1.0 / f64::from(requests_per_second)));
+
+        Self {
+            interval,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until a request to `host` may be issued.
+    pub async fn acquire(&self, host: &str) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        let scheduled = {
+            let mut slots = self.next_slot.lock().await;
+            let now = Instant::now();
+            let slot = slots.entry(host.to_string()).or_insert(now);
+            let scheduled = (*slot).max(now);
+            *slot = scheduled + interval;
+            scheduled
+        };
+
+        sleep_until(scheduled).await;
+    }
+}
@@ -0,0 +1,257 @@
+//! Upstream responses captured as test fixtures. With the `record` feature and
+//! `UPSTREAM_RECORD_DIR` set, every response the Parliament client reads is written there
+//! as a [`RecordedResponse`]; tests load a directory of them with
+//! [`RecordedResponse::load_dir`] and serve them from a mock upstream.
+//!
+//! Fixtures are sanitised as they are captured: anything shaped like a UK postcode in the
+//! path, query or body is replaced with [`REDACTED_POSTCODE`], and file names are derived
+//! from the sanitised request only.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use reqwest::Url;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::core::error::AppError;
+
+pub const REDACTED_POSTCODE: &str = "[postcode]";
+
+/// Response headers worth keeping; the rest vary per request or identify the server.
+const RECORDED_HEADERS: &[&str] = &[
+    "content-type",
+    "cache-control",
+    "etag",
+    "last-modified",
+    "retry-after",
+];
+
+/// One upstream response, as stored in a fixture file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedResponse {
+    /// Which API answered, e.g. `bills` or `members`.
+    pub source: String,
+    pub path: String,
+    /// Decoded query pairs in request order.
+    pub query: Vec<(String, String)>,
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: RecordedBody,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedBody {
+    Json(Value),
+    /// Anything that is not JSON, such as the legislation Atom feed.
+    Text(String),
+}
+
+impl RecordedResponse {
+    /// Capture a response to `url`, which should be the real API URL rather than a
+    /// redirected one, so the source is named after the API.
+    pub fn capture(url: &Url, status: u16, headers: &HeaderMap, body: &[u8]) -> Self {
+        let path = url
+            .path()
+            .split('/')
+            .map(|segment| {
+                let decoded = segment.replace("%20", " ");
+                let redacted = redact_postcodes(&decoded);
+                if redacted == decoded {
+                    segment.to_string()
+                } else {
+                    redacted
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let query = url
+            .query_pairs()
+            .map(|(name, value)| (name.into_owned(), redact_postcodes(&value)))
+            .collect();
+        let headers = RECORDED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = headers.get(*name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        let body = match serde_json::from_slice::<Value>(body) {
+            Ok(mut json) => {
+                redact_json(&mut json);
+                RecordedBody::Json(json)
+            }
+            Err(_) => RecordedBody::Text(redact_postcodes(&String::from_utf8_lossy(body))),
+        };
+
+        Self {
+            source: source_name(url),
+            path,
+            query,
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// `<source>-<hash>.json`, the hash covering the sanitised path and query, so repeated
+    /// requests overwrite one fixture.
+    pub fn file_name(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.path.as_bytes());
+        for (name, value) in &self.query {
+            hasher.update(b"\0");
+            hasher.update(name.as_bytes());
+            hasher.update(b"=");
+            hasher.update(value.as_bytes());
+        }
+        let digest = hex::encode(hasher.finalize());
+        format!("{}-{}.json", self.source, &digest[..16])
+    }
+
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match &self.body {
+            RecordedBody::Json(json) => serde_json::to_vec(json).unwrap_or_default(),
+            RecordedBody::Text(text) => text.clone().into_bytes(),
+        }
+    }
+
+    /// Write the fixture into `dir`, returning its path.
+    pub fn write_to(&self, dir: &Path) -> Result<PathBuf, AppError> {
+        let path = dir.join(self.file_name());
+        let contents = serde_json::to_vec_pretty(self)
+            .map_err(|err| AppError::internal(format!("failed to encode fixture: {err}")))?;
+        std::fs::write(&path, contents).map_err(|err| {
+            AppError::internal(format!("failed to write fixture {}: {err}", path.display()))
+        })?;
+        Ok(path)
+    }
+
+    /// Every `*.json` fixture in `dir`, ordered by file name.
+    pub fn load_dir(dir: &Path) -> Result<Vec<Self>, AppError> {
+        let read_error = |err: std::io::Error| {
+            AppError::configuration(format!("cannot read fixtures in {}: {err}", dir.display()))
+        };
+        let mut paths = std::fs::read_dir(dir)
+            .map_err(read_error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(read_error)?;
+        paths.retain(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        });
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                let contents = std::fs::read(path).map_err(read_error)?;
+                serde_json::from_slice(&contents).map_err(|err| {
+                    AppError::configuration(format!("invalid fixture {}: {err}", path.display()))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Writes every upstream response into `UPSTREAM_RECORD_DIR`.
+#[cfg(feature = "record")]
+#[derive(Debug)]
+pub struct UpstreamRecorder {
+    dir: PathBuf,
+}
+
+#[cfg(feature = "record")]
+impl UpstreamRecorder {
+    pub fn new(dir: &str) -> Result<Self, AppError> {
+        std::fs::create_dir_all(dir).map_err(|err| {
+            AppError::configuration(format!("cannot create UPSTREAM_RECORD_DIR {dir}: {err}"))
+        })?;
+        tracing::warn!(%dir, "recording upstream responses as fixtures");
+        Ok(Self { dir: dir.into() })
+    }
+
+    /// Failures are logged; recording never fails the request.
+    pub fn record(&self, url: &Url, status: u16, headers: &HeaderMap, body: &[u8]) {
+        let recorded = RecordedResponse::capture(url, status, headers, body);
+        match recorded.write_to(&self.dir) {
+            Ok(path) => tracing::debug!(path = %path.display(), "recorded upstream response"),
+            Err(error) => tracing::warn!(%error, "failed to record upstream response"),
+        }
+    }
+}
+
+fn source_name(url: &Url) -> String {
+    let host = url.host_str().unwrap_or("unknown");
+    let source = match host {
+        "bills-api.parliament.uk" => "bills",
+        "members-api.parliament.uk" => "members",
+        "lda.data.parliament.uk" => "lda",
+        "www.legislation.gov.uk" => "legislation",
+        "api.postcodes.io" => "postcodes",
+        "commonsvotes-api.parliament.uk" => "commons-votes",
+        "lordsvotes-api.parliament.uk" => "lords-votes",
+        "committees-api.parliament.uk" => "committees",
+        "statutoryinstruments-api.parliament.uk" => "statutory-instruments",
+        "oralquestionsandmotions-api.parliament.uk" => "edms",
+        other => other,
+    };
+    source.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "-")
+}
+
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::String(text) => *text = redact_postcodes(text),
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        Value::Object(fields) => fields.values_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Replace every UK postcode in `text`, written with or without its space, in either case.
+pub fn redact_postcodes(text: &str) -> String {
+    let is_word = |c: char| c.is_ascii_alphanumeric();
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_word) {
+        redacted.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !is_word(c)).unwrap_or(rest.len());
+        let (word, after) = rest.split_at(end);
+
+        // "SW1A 1AA": an outward code, one space, then the inward code.
+        if let Some(inward) = after.strip_prefix(' ') {
+            let inward_end = inward.find(|c: char| !is_word(c)).unwrap_or(inward.len());
+            if inward_end == 3 && is_postcode(&format!("{word}{}", &inward[..3])) {
+                redacted.push_str(REDACTED_POSTCODE);
+                rest = &inward[3..];
+                continue;
+            }
+        }
+        redacted.push_str(if is_postcode(word) {
+            REDACTED_POSTCODE
+        } else {
+            word
+        });
+        rest = after;
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+/// The outward code is one of A9, A99, AA9, AA99, A9A or AA9A, the inward code 9AA.
+fn is_postcode(compact: &str) -> bool {
+    let shape: String = compact
+        .chars()
+        .map(|c| if c.is_ascii_digit() { '9' } else { 'A' })
+        .collect();
+    let Some(outward) = shape.strip_suffix("9AA") else {
+        return false;
+    };
+    matches!(outward, "A9" | "A99" | "AA9" | "AA99" | "A9A" | "AA9A")
+}
@@ -0,0 +1,158 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use chrono::Utc;
+use ed25519_dalek::pkcs8::DecodePrivateKey;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::core::error::AppError;
+
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Detached signature attached to signed tool responses. The signed message is
+/// `"{signedAt}\n{payloadSha256}"`, binding the timestamp to the payload digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureBlock {
+    pub algorithm: String,
+    pub signed_at: String,
+    pub payload_sha256: String,
+    pub signature: String,
+    pub public_key: String,
+}
+
+/// Signs structured responses so a cited payload can later be shown to be unmodified.
+pub struct ResponseSigner {
+    signing_key: SigningKey,
+    verifying_key: VerifyingKey,
+}
+
+impl ResponseSigner {
+    pub fn new(signing_key: SigningKey) -> Self {
+        let verifying_key = signing_key.verifying_key();
+        Self {
+            signing_key,
+            verifying_key,
+        }
+    }
+
+    /// Load a PKCS#8 PEM private key, e.g. one produced by
+    /// `openssl genpkey -algorithm ed25519`.
+    pub fn from_pem_file(path: impl AsRef<Path>) -> Result<Self, AppError> {
+        let path = path.as_ref();
+        let pem = std::fs::read_to_string(path).map_err(|err| {
+            AppError::configuration(format!(
+                "failed to read RESPONSE_SIGNING_KEY at {}: {err}",
+                path.display()
+            ))
+        })?;
+        let signing_key = SigningKey::from_pkcs8_pem(&pem).map_err(|err| {
+            AppError::configuration(format!(
+                "RESPONSE_SIGNING_KEY at {} is not an ed25519 PKCS#8 key: {err}",
+                path.display()
+            ))
+        })?;
+
+        Ok(Self::new(signing_key))
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.verifying_key.as_bytes())
+    }
+
+    pub fn sign(&self, payload: &Value) -> SignatureBlock {
+        let signed_at = Utc::now().to_rfc3339();
+        let payload_sha256 = payload_digest(payload);
+        let signature = self
+            .signing_key
+            .sign(signed_message(&signed_at, &payload_sha256).as_bytes());
+
+        SignatureBlock {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            signed_at,
+            payload_sha256,
+            signature: hex::encode(signature.to_bytes()),
+            public_key: self.public_key_hex(),
+        }
+    }
+
+    /// Check `block` against `payload` using this server's public key (not the key
+    /// embedded in the block, which a forger could replace).
+    pub fn verify(&self, payload: &Value, block: &SignatureBlock) -> Result<(), String> {
+        if block.algorithm != SIGNATURE_ALGORITHM {
+            return Err(format!("unsupported algorithm {}", block.algorithm));
+        }
+
+        let digest = payload_digest(payload);
+        if digest != block.payload_sha256 {
+            return Err("payload digest does not match the signed digest".to_string());
+        }
+
+        let bytes =
+            hex::decode(&block.signature).map_err(|_| "signature is not valid hex".to_string())?;
+        let signature = Signature::from_slice(&bytes)
+            .map_err(|_| "signature has the wrong length".to_string())?;
+
+        self.verifying_key
+            .verify(
+                signed_message(&block.signed_at, &digest).as_bytes(),
+                &signature,
+            )
+            .map_err(|_| "signature was not produced by this server's key".to_string())
+    }
+}
+
+pub fn payload_digest(payload: &Value) -> String {
+    hex::encode(Sha256::digest(canonicalize(payload).as_bytes()))
+}
+
+fn signed_message(signed_at: &str, digest: &str) -> String {
+    format!("{signed_at}\n{digest}")
+}
+
+/// Deterministic JSON encoding: object keys sorted, no insignificant whitespace.
+pub fn canonicalize(value: &Value) -> String {
+    let mut output = String::new();
+    write_canonical(value, &mut output);
+    output
+}
+
+fn write_canonical(value: &Value, output: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            output.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_string(key, output);
+                output.push(':');
+                write_canonical(value, output);
+            }
+            output.push('}');
+        }
+        Value::Array(items) => {
+            output.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_canonical(item, output);
+            }
+            output.push(']');
+        }
+        Value::String(text) => write_string(text, output),
+        other => {
+            let _ = write!(output, "{other}");
+        }
+    }
+}
+
+fn write_string(text: &str, output: &mut String) {
+    output.push_str(&Value::String(text.to_string()).to_string());
+}
@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sled::Tree;
+use tokio::task;
+
+use crate::core::error::AppError;
+
+const SNAPSHOT_KEY: &str = "snapshot";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionStats {
+    pub last_success: Option<u64>,
+    pub last_failure: Option<u64>,
+    pub success_count: u64,
+    pub failure_count: u64,
+    /// Decompressed upstream payload bytes, summed over every call or response.
+    #[serde(default)]
+    pub upstream_bytes: u64,
+    /// Largest single call (tools) or response (upstreams), in bytes.
+    #[serde(default)]
+    pub largest_payload_bytes: u64,
+}
+
+impl ExecutionStats {
+    fn record(&mut self, success: bool, at: u64) {
+        if success {
+            self.last_success = Some(self.last_success.map_or(at, |value| value.max(at)));
+            self.success_count += 1;
+        } else {
+            self.last_failure = Some(self.last_failure.map_or(at, |value| value.max(at)));
+            self.failure_count += 1;
+        }
+    }
+
+    fn record_bytes(&mut self, bytes: u64) {
+        self.upstream_bytes = self.upstream_bytes.saturating_add(bytes);
+        self.largest_payload_bytes = self.largest_payload_bytes.max(bytes);
+    }
+
+    /// A target is stale when it has failed within the threshold window but has not
+    /// succeeded within it (or has never succeeded at all).
+    pub fn is_stale(&self, threshold_secs: u64, now: u64) -> bool {
+        let Some(last_failure) = self.last_failure else {
+            return false;
+        };
+
+        let failure_recent = now.saturating_sub(last_failure) <= threshold_secs;
+        let success_outdated = match self.last_success {
+            Some(last_success) => {
+                last_success < last_failure && now.saturating_sub(last_success) > threshold_secs
+            }
+            None => true,
+        };
+
+        failure_recent && success_outdated
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    pub tools: BTreeMap<String, ExecutionStats>,
+    pub upstreams: BTreeMap<String, ExecutionStats>,
+}
+
+/// Tracks the last successful and failed executions per tool and per upstream host so
+/// operators can tell when an integration last worked without trawling logs.
+#[derive(Default)]
+pub struct StatsRegistry {
+    tools: RwLock<HashMap<String, ExecutionStats>>,
+    upstreams: RwLock<HashMap<String, ExecutionStats>>,
+}
+
+impl StatsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_tool(&self, tool: &str, success: bool) {
+        self.record_tool_at(tool, success, now_timestamp());
+    }
+
+    pub fn record_tool_at(&self, tool: &str, success: bool, at: u64) {
+        record_entry(&self.tools, tool, success, at);
+    }
+
+    pub fn record_upstream(&self, host: &str, success: bool) {
+        self.record_upstream_at(host, success, now_timestamp());
+    }
+
+    pub fn record_upstream_at(&self, host: &str, success: bool, at: u64) {
+        record_entry(&self.upstreams, host, success, at);
+    }
+
+    /// Charge the upstream bytes one call of `tool` read. Calls that read nothing are not
+    /// recorded.
+    pub fn record_tool_bytes(&self, tool: &str, bytes: u64) {
+        record_bytes_entry(&self.tools, tool, bytes);
+    }
+
+    /// Charge the decompressed size of one response from `host`.
+    pub fn record_upstream_bytes(&self, host: &str, bytes: u64) {
+        record_bytes_entry(&self.upstreams, host, bytes);
+    }
+
+    pub fn tool_stats(&self, tool: &str) -> Option<ExecutionStats> {
+        read_entry(&self.tools, tool)
+    }
+
+    pub fn upstream_stats(&self, host: &str) -> Option<ExecutionStats> {
+        read_entry(&self.upstreams, host)
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            tools: copy_entries(&self.tools),
+            upstreams: copy_entries(&self.upstreams),
+        }
+    }
+
+    pub fn staleness_warning(&self, tool: &str, threshold_secs: u64, now: u64) -> Option<String> {
+        let stats = self.tool_stats(tool)?;
+        if !stats.is_stale(threshold_secs, now) {
+            return None;
+        }
+
+        let warning = match stats.last_success {
+            Some(last_success) => format!(
+                "{tool} has failed recently and last succeeded at {}; results may be unavailable or stale.",
+                format_timestamp(last_success)
+            ),
+            None => format!(
+                "{tool} has failed recently and has not succeeded since the server started tracking it."
+            ),
+        };
+
+        Some(warning)
+    }
+
+    /// Render the snapshot for the health details endpoint and `admin.tool_stats`.
+    pub fn describe(&self, threshold_secs: u64, now: u64) -> Value {
+        let snapshot = self.snapshot();
+        let render = |entries: &BTreeMap<String, ExecutionStats>| -> Value {
+            let mut rendered = serde_json::Map::new();
+            for (name, stats) in entries {
+                rendered.insert(
+                    name.clone(),
+                    json!({
+                        "lastSuccessAt": stats.last_success.map(format_timestamp),
+                        "lastFailureAt": stats.last_failure.map(format_timestamp),
+                        "successCount": stats.success_count,
+                        "failureCount": stats.failure_count,
+                        "upstreamBytes": stats.upstream_bytes,
+                        "largestPayloadBytes": stats.largest_payload_bytes,
+                        "stale": stats.is_stale(threshold_secs, now),
+                    }),
+                );
+            }
+            Value::Object(rendered)
+        };
+
+        json!({
+            "stalenessThresholdSecs": threshold_secs,
+            "tools": render(&snapshot.tools),
+            "upstreams": render(&snapshot.upstreams),
+        })
+    }
+
+    pub async fn persist(&self, tree: &Tree) -> Result<(), AppError> {
+        let data = serde_json::to_vec(&self.snapshot())
+            .map_err(|err| AppError::internal(format!("failed to encode stats snapshot: {err}")))?;
+
+        let tree_clone = tree.clone();
+        task::spawn_blocking(move || -> Result<(), AppError> {
+            tree_clone.insert(SNAPSHOT_KEY, data).map_err(|err| {
+                AppError::internal(format!("failed to write stats snapshot: {err}"))
+            })?;
+            Ok(())
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("stats task join error: {err}")))??;
+
+        tree.flush_async()
+            .await
+            .map_err(|err| AppError::internal(format!("failed to flush stats snapshot: {err}")))?;
+
+        Ok(())
+    }
+
+    pub async fn restore(&self, tree: &Tree) -> Result<(), AppError> {
+        let tree = tree.clone();
+        let snapshot = task::spawn_blocking(move || -> Result<Option<StatsSnapshot>, AppError> {
+            let maybe_bytes = tree
+                .get(SNAPSHOT_KEY)
+                .map_err(|err| AppError::internal(format!("stats lookup failed: {err}")))?;
+
+            match maybe_bytes {
+                Some(bytes) => serde_json::from_slice(&bytes).map(Some).map_err(|err| {
+                    AppError::internal(format!("failed to decode stats snapshot: {err}"))
+                }),
+                None => Ok(None),
+            }
+        })
+        .await
+        .map_err(|err| AppError::internal(format!("stats task join error: {err}")))??;
+
+        if let Some(snapshot) = snapshot {
+            merge_entries(&self.tools, snapshot.tools);
+            merge_entries(&self.upstreams, snapshot.upstreams);
+        }
+
+        Ok(())
+    }
+}
+
+fn record_entry(map: &RwLock<HashMap<String, ExecutionStats>>, key: &str, success: bool, at: u64) {
+    match map.write() {
+        Ok(mut guard) => guard
+            .entry(key.to_string())
+            .or_default()
+            .record(success, at),
+        Err(error) => tracing::error!(error = %error, "stats registry lock poisoned"),
+    }
+}
+
+fn record_bytes_entry(map: &RwLock<HashMap<String, ExecutionStats>>, key: &str, bytes: u64) {
+    if bytes == 0 {
+        return;
+    }
+    match map.write() {
+        Ok(mut guard) => guard
+            .entry(key.to_string())
+            .or_default()
+            .record_bytes(bytes),
+        Err(error) => tracing::error!(error = %error, "stats registry lock poisoned"),
+    }
+}
+
+fn read_entry(map: &RwLock<HashMap<String, ExecutionStats>>, key: &str) -> Option<ExecutionStats> {
+    map.read().ok().and_then(|guard| guard.get(key).cloned())
+}
+
+fn copy_entries(map: &RwLock<HashMap<String, ExecutionStats>>) -> BTreeMap<String, ExecutionStats> {
+    map.read()
+        .map(|guard| {
+            guard
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn merge_entries(
+    map: &RwLock<HashMap<String, ExecutionStats>>,
+    restored: BTreeMap<String, ExecutionStats>,
+) {
+    if let Ok(mut guard) = map.write() {
+        for (key, value) in restored {
+            guard.entry(key).or_insert(value);
+        }
+    }
+}
+
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|value| value.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Longest window `admin.usage_summary` can report on; older buckets are dropped.
+pub const MAX_WINDOW_HOURS: u64 = 720;
+/// Window reported when `admin.usage_summary` is called without `windowHours`.
+pub const DEFAULT_WINDOW_HOURS: u64 = 168;
+const SECS_PER_HOUR: u64 = 3600;
+/// Hex characters of the topic digest that are kept.
+const TOPIC_HASH_LEN: usize = 16;
+
+/// Aggregate usage counters for shared deployments, kept apart from the audit log so
+/// either can be enabled on its own. Research topics are stored only as truncated
+/// SHA-256 digests and constituencies only as names; postcodes never reach the
+/// collector. Counts live in memory, in hourly buckets, and reset on restart.
+#[derive(Debug)]
+pub struct UsageCollector {
+    k_threshold: u64,
+    buckets: Mutex<BTreeMap<u64, HourBucket>>,
+}
+
+#[derive(Debug, Default)]
+struct HourBucket {
+    research_runs: u64,
+    topics: HashMap<String, u64>,
+    constituency_lookups: u64,
+    constituencies: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    pub window_hours: u64,
+    /// Buckets with fewer requests than this are folded into `suppressed`.
+    pub k_threshold: u64,
+    pub research_runs: u64,
+    pub constituency_lookups: u64,
+    /// Most requested first.
+    pub topics: Vec<TopicCount>,
+    /// Most requested first.
+    pub constituencies: Vec<ConstituencyCount>,
+    pub suppressed: SuppressedCounts,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicCount {
+    /// First 16 hex characters of the SHA-256 of the lower-cased, whitespace-collapsed topic.
+    pub topic_hash: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConstituencyCount {
+    pub constituency: String,
+    pub count: u64,
+}
+
+/// Requests whose bucket stayed under the threshold, counted but not itemised.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct SuppressedCounts {
+    pub topics: u64,
+    pub constituencies: u64,
+}
+
+impl UsageCollector {
+    /// `k_threshold` is the smallest count a topic or constituency needs to be listed.
+    pub fn new(k_threshold: u64) -> Self {
+        Self {
+            k_threshold: k_threshold.max(1),
+            buckets: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn record_research_topic(&self, topic: &str) {
+        let digest = topic_hash(topic);
+        self.with_current_bucket(|bucket| {
+            bucket.research_runs += 1;
+            *bucket.topics.entry(digest).or_insert(0) += 1;
+        });
+    }
+
+    pub fn record_constituency(&self, constituency: &str) {
+        let constituency = constituency.trim();
+        if constituency.is_empty() {
+            return;
+        }
+        self.with_current_bucket(|bucket| {
+            bucket.constituency_lookups += 1;
+            *bucket
+                .constituencies
+                .entry(constituency.to_string())
+                .or_insert(0) += 1;
+        });
+    }
+
+    /// Totals over the last `window_hours` (1 to [`MAX_WINDOW_HOURS`], counting the
+    /// current hour), with every topic and constituency under the threshold suppressed.
+    pub fn summary(&self, window_hours: u64) -> UsageSummary {
+        let window_hours = window_hours.clamp(1, MAX_WINDOW_HOURS);
+        let since = current_hour().saturating_sub(window_hours - 1);
+
+        let mut research_runs = 0;
+        let mut constituency_lookups = 0;
+        let mut topics: HashMap<String, u64> = HashMap::new();
+        let mut constituencies: HashMap<String, u64> = HashMap::new();
+        for bucket in self.lock().range(since..).map(|(_, bucket)| bucket) {
+            research_runs += bucket.research_runs;
+            constituency_lookups += bucket.constituency_lookups;
+            for (digest, count) in &bucket.topics {
+                *topics.entry(digest.clone()).or_insert(0) += count;
+            }
+            for (name, count) in &bucket.constituencies {
+                *constituencies.entry(name.clone()).or_insert(0) += count;
+            }
+        }
+
+        let mut suppressed = SuppressedCounts::default();
+        let (topics, hidden) = self.apply_threshold(topics);
+        suppressed.topics = hidden;
+        let topics = topics
+            .into_iter()
+            .map(|(topic_hash, count)| TopicCount { topic_hash, count })
+            .collect();
+        let (constituencies, hidden) = self.apply_threshold(constituencies);
+        suppressed.constituencies = hidden;
+        let constituencies = constituencies
+            .into_iter()
+            .map(|(constituency, count)| ConstituencyCount {
+                constituency,
+                count,
+            })
+            .collect();
+
+        UsageSummary {
+            window_hours,
+            k_threshold: self.k_threshold,
+            research_runs,
+            constituency_lookups,
+            topics,
+            constituencies,
+            suppressed,
+        }
+    }
+
+    /// Split `counts` into listed entries (most requested first) and the total of the rest.
+    fn apply_threshold(&self, counts: HashMap<String, u64>) -> (Vec<(String, u64)>, u64) {
+        let (mut listed, hidden): (Vec<_>, Vec<_>) = counts
+            .into_iter()
+            .partition(|(_, count)| *count >= self.k_threshold);
+        listed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        (listed, hidden.iter().map(|(_, count)| count).sum())
+    }
+
+    fn with_current_bucket(&self, update: impl FnOnce(&mut HourBucket)) {
+        let hour = current_hour();
+        let mut buckets = self.lock();
+        update(buckets.entry(hour).or_default());
+        let oldest = hour.saturating_sub(MAX_WINDOW_HOURS - 1);
+        *buckets = buckets.split_off(&oldest);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u64, HourBucket>> {
+        self.buckets.lock().unwrap_or_else(|error| {
+            tracing::error!("usage collector mutex poisoned; recovering");
+            PoisonError::into_inner(error)
+        })
+    }
+}
+
+fn topic_hash(topic: &str) -> String {
+    let normalised = topic
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let digest = hex::encode(Sha256::digest(normalised.as_bytes()));
+    digest[..TOPIC_HASH_LEN].to_string()
+}
+
+fn current_hour() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / SECS_PER_HOUR)
+        .unwrap_or_default()
+}
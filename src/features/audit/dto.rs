@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditQuery {
+    /// Entries to return, newest first; defaults to 50 and is capped at 1000.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayCallArgs {
+    #[serde(rename = "auditId")]
+    pub audit_id: String,
+    /// Values for the entry's `redactedFields`, keyed by the same JSON pointers.
+    #[serde(rename = "redactedValues", default)]
+    pub redacted_values: HashMap<String, Value>,
+}
@@ -0,0 +1,28 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use serde_json::{Value, json};
+
+use crate::core::error::AppError;
+use crate::features::audit::dto::AuditQuery;
+use crate::server::AppState;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 1000;
+
+/// Return the most recent `tools/call` audit entries, newest first.
+pub async fn handle_audit_log(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<Value>, AppError> {
+    let log = state
+        .service
+        .audit_log()
+        .ok_or_else(|| AppError::configuration("audit log is not enabled"))?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let entries = log.recent(limit).await?;
+
+    Ok(Json(json!({
+        "retentionDays": state.config.audit_retention_days,
+        "entries": entries,
+    })))
+}
@@ -0,0 +1,5 @@
+pub mod dto;
+pub mod handler;
+
+pub use dto::{AuditQuery, ReplayCallArgs};
+pub use handler::handle_audit_log;
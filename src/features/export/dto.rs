@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExportQuery {
+    pub query: Option<String>,
+    /// Only export records dated on or after this day (`YYYY-MM-DD`).
+    pub since: Option<String>,
+    /// Row limit, clamped to `EXPORT_MAX_ROWS`.
+    pub limit: Option<usize>,
+}
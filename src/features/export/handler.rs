@@ -0,0 +1,69 @@
+use std::convert::Infallible;
+
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use chrono::NaiveDate;
+use serde_json::json;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::core::error::AppError;
+use crate::features::export::dto::ExportQuery;
+use crate::features::parliament::ExportOptions;
+use crate::server::AppState;
+
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Stream a whole dataset as newline-delimited JSON. An upstream failure part-way through
+/// is reported as a final `{"error": ...}` line since the status has already been sent.
+pub async fn handle_export_dataset(
+    State(state): State<AppState>,
+    Path(dataset): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    let since = query
+        .since
+        .as_deref()
+        .map(|value| {
+            NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+                AppError::bad_request(format!("since must be a YYYY-MM-DD date, received {value}"))
+            })
+        })
+        .transpose()?;
+    let max_rows = query.limit.map_or(state.config.export_max_rows, |limit| {
+        limit.min(state.config.export_max_rows)
+    });
+    let search = query
+        .query
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let receiver = state.parliament_client.export_dataset(
+        &dataset,
+        ExportOptions {
+            query: search,
+            since,
+            max_rows,
+            page_size: None,
+        },
+    )?;
+
+    let lines = ReceiverStream::new(receiver).map(|item| {
+        let line = match item {
+            Ok(value) => value.to_string(),
+            Err(error) => {
+                tracing::warn!(%error, "dataset export aborted");
+                json!({ "error": error.to_string() }).to_string()
+            }
+        };
+        Ok::<_, Infallible>(format!("{line}\n"))
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(lines),
+    )
+        .into_response())
+}
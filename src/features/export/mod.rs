@@ -0,0 +1,5 @@
+pub mod dto;
+pub mod handler;
+
+pub use dto::ExportQuery;
+pub use handler::handle_export_dataset;
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Protocol versions that predate the `completions` capability.
+const PRE_COMPLETIONS_PROTOCOLS: &[&str] = &["1.0"];
+
+/// The `capabilities` object of an `initialize` result. Capabilities are absent unless
+/// switched on, so the result only advertises what the server can serve right now.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ServerCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<ToolsCapability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    completions: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logging: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolsCapability {
+    list_changed: bool,
+}
+
+impl ServerCapabilities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `list_changed` should only be set when a transport can push
+    /// `notifications/tools/list_changed` to the client.
+    pub fn with_tools(mut self, list_changed: bool) -> Self {
+        self.tools = Some(ToolsCapability { list_changed });
+        self
+    }
+
+    pub fn with_completions(mut self) -> Self {
+        self.completions = Some(Map::new());
+        self
+    }
+
+    pub fn with_logging(mut self) -> Self {
+        self.logging = Some(Map::new());
+        self
+    }
+
+    /// Drop capabilities that the negotiated protocol version does not define.
+    pub fn for_protocol(mut self, protocol_version: &str) -> Self {
+        if PRE_COMPLETIONS_PROTOCOLS.contains(&protocol_version) {
+            self.completions = None;
+        }
+        self
+    }
+}
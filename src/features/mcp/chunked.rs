@@ -0,0 +1,181 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use serde_json::{Value, json};
+
+use crate::core::byte_budget::budget_exhausted;
+use crate::core::error::AppError;
+use crate::core::notifications::NotificationSink;
+use crate::features::mcp::dto::ToolContent;
+use crate::features::parliament::client::voting_export_limit;
+use crate::features::parliament::{
+    ExportVotingRecordArgs, MemberDivisionVote, ParliamentClient, VotingRecordExportResult,
+};
+
+/// Tools whose content is delivered in chunks when the client accepts an SSE response.
+pub const CHUNKED_TOOLS: &[&str] = &["parliament.export_voting_record"];
+
+/// Notification carrying one content block of a chunked `tools/call` before its response.
+pub const CHUNK_NOTIFICATION: &str = "notifications/tools/chunk";
+
+const VOTING_RECORD_COLUMNS: [&str; 8] = [
+    "divisionId",
+    "number",
+    "date",
+    "title",
+    "vote",
+    "teller",
+    "ayes",
+    "noes",
+];
+
+/// A tool call whose content blocks are produced one at a time. Every block is drained, in
+/// order, before `finish` builds the call's `structuredContent`.
+pub trait ChunkedToolCall: Send {
+    fn next_chunk(&mut self) -> impl Future<Output = Option<Result<ToolContent, AppError>>> + Send;
+
+    fn finish(self) -> Result<Value, AppError>;
+}
+
+/// Where the blocks of a chunked call go: pushed to the client as they arrive when the
+/// transport streams, otherwise held back for the final result.
+pub struct ChunkDelivery {
+    request_id: Value,
+    sink: Option<NotificationSink>,
+    sent: AtomicUsize,
+    buffered: Mutex<Vec<ToolContent>>,
+}
+
+impl ChunkDelivery {
+    pub fn new(request_id: Value, sink: Option<NotificationSink>) -> Self {
+        Self {
+            request_id,
+            sink,
+            sent: AtomicUsize::new(0),
+            buffered: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn push(&self, content: ToolContent) {
+        let Some(sink) = &self.sink else {
+            if let Ok(mut buffered) = self.buffered.lock() {
+                buffered.push(content);
+            }
+            return;
+        };
+
+        let index = self.sent.fetch_add(1, Ordering::SeqCst);
+        sink.notify(
+            CHUNK_NOTIFICATION,
+            json!({
+                "requestId": self.request_id,
+                "index": index,
+                "content": [content],
+            }),
+        );
+    }
+
+    /// Blocks not already streamed to the client, for the final result's `content`.
+    pub fn take_buffered(&self) -> Vec<ToolContent> {
+        self.buffered
+            .lock()
+            .map(|mut buffered| std::mem::take(&mut *buffered))
+            .unwrap_or_default()
+    }
+}
+
+/// Drain `call` into `delivery` and return its `structuredContent`. The first failing
+/// block fails the whole call.
+pub async fn deliver_chunks<C: ChunkedToolCall>(
+    mut call: C,
+    delivery: &ChunkDelivery,
+) -> Result<Value, AppError> {
+    while let Some(chunk) = call.next_chunk().await {
+        delivery.push(chunk?);
+    }
+    call.finish()
+}
+
+/// `parliament.export_voting_record`: the MP's Commons votes as CSV, one block per Votes
+/// API page, with the header row at the top of the first block.
+pub struct VotingRecordCsv {
+    mp_id: u32,
+    limit: usize,
+    pages: BoxStream<'static, Result<Vec<MemberDivisionVote>, AppError>>,
+    rows: usize,
+    chunks: usize,
+}
+
+impl VotingRecordCsv {
+    pub fn start(
+        client: &Arc<ParliamentClient>,
+        args: &ExportVotingRecordArgs,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            mp_id: args.mp_id,
+            limit: voting_export_limit(args.limit),
+            pages: client.voting_record_pages(args)?,
+            rows: 0,
+            chunks: 0,
+        })
+    }
+}
+
+impl ChunkedToolCall for VotingRecordCsv {
+    async fn next_chunk(&mut self) -> Option<Result<ToolContent, AppError>> {
+        let votes = match self.pages.next().await? {
+            Ok(votes) => votes,
+            Err(error) => return Some(Err(error)),
+        };
+
+        let mut text = String::new();
+        if self.chunks == 0 {
+            text.push_str(&VOTING_RECORD_COLUMNS.join(","));
+            text.push('\n');
+        }
+        for vote in &votes {
+            let number = vote.number.map(|number| number.to_string());
+            let fields = [
+                vote.division_id.to_string(),
+                number.unwrap_or_default(),
+                vote.date.clone(),
+                csv_field(&vote.title),
+                vote.vote.clone(),
+                vote.teller.to_string(),
+                vote.ayes.to_string(),
+                vote.noes.to_string(),
+            ];
+            text.push_str(&fields.join(","));
+            text.push('\n');
+        }
+        self.rows += votes.len();
+        self.chunks += 1;
+        Some(Ok(ToolContent::text(text)))
+    }
+
+    fn finish(self) -> Result<Value, AppError> {
+        let result = VotingRecordExportResult {
+            mp_id: self.mp_id,
+            columns: VOTING_RECORD_COLUMNS.map(str::to_string).to_vec(),
+            rows: self.rows,
+            chunks: self.chunks,
+            limit: self.limit,
+            truncated_by_budget: budget_exhausted(),
+        };
+        serde_json::to_value(result)
+            .map_err(|err| AppError::internal(format!("failed to serialise export summary: {err}")))
+    }
+}
+
+/// Quote a CSV field when it holds a comma, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use crate::features::mcp::dto::CompletionResult;
+use crate::features::parliament::datasets::DATASETS;
+use crate::features::parliament::relevance::fuzzy_word_match;
+
+/// The MCP spec caps a single completion response at 100 values.
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// Datasets whose `searchTerm` is a Commons member's name.
+const MEMBER_DATASETS: &[&str] = &["members", "commonsmembers"];
+
+/// Candidates read from the Parliament cache when a completion is requested, rather than
+/// fixed at startup. Completion never fetches: until the list has been cached (by
+/// `parliament.list_constituencies`) these sources offer nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachedIndex {
+    /// Names from the cached constituency list.
+    ConstituencyNames,
+    /// Names of the sitting MPs from the cached members listing.
+    MemberNames,
+}
+
+impl CachedIndex {
+    /// Whether the index suits the other arguments the client has already filled in.
+    fn applies(&self, context: &HashMap<String, String>) -> bool {
+        match self {
+            Self::ConstituencyNames => true,
+            Self::MemberNames => context
+                .get("dataset")
+                .is_some_and(|dataset| MEMBER_DATASETS.contains(&dataset.trim())),
+        }
+    }
+}
+
+/// Candidate values for tool arguments, keyed by tool name and argument name.
+pub struct CompletionRegistry {
+    candidates: HashMap<(String, String), Vec<String>>,
+    cached: HashMap<(String, String), CachedIndex>,
+}
+
+impl CompletionRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            candidates: HashMap::new(),
+            cached: HashMap::new(),
+        };
+
+        registry.register(
+            "parliament.fetch_core_dataset",
+            "dataset",
+            DATASETS.iter().map(|descriptor| descriptor.name),
+        );
+        registry.register(
+            "parliament.search_uk_law",
+            "legislationType",
+            ["primary", "secondary", "all"],
+        );
+        registry.register("parliament.fetch_bills", "house", ["commons", "lords"]);
+        registry.register_cached(
+            "parliament.list_constituencies",
+            "search",
+            CachedIndex::ConstituencyNames,
+        );
+        registry.register_cached(
+            "parliament.fetch_core_dataset",
+            "searchTerm",
+            CachedIndex::MemberNames,
+        );
+
+        registry
+    }
+
+    pub fn register<I, S>(&mut self, tool: &str, argument: &str, values: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.candidates
+            .entry((tool.to_string(), argument.to_string()))
+            .or_default()
+            .extend(values.into_iter().map(Into::into));
+    }
+
+    pub fn register_cached(&mut self, tool: &str, argument: &str, index: CachedIndex) {
+        self.cached
+            .insert((tool.to_string(), argument.to_string()), index);
+    }
+
+    /// Whether any argument of `tool` has candidates.
+    pub fn covers_tool(&self, tool: &str) -> bool {
+        self.candidates
+            .keys()
+            .chain(self.cached.keys())
+            .any(|(name, _)| name == tool)
+    }
+
+    /// The cached index completing `argument` of `tool`, if it suits `context` (the
+    /// arguments already filled in).
+    pub fn cached_index(
+        &self,
+        tool: &str,
+        argument: &str,
+        context: &HashMap<String, String>,
+    ) -> Option<CachedIndex> {
+        self.cached
+            .get(&(tool.to_string(), argument.to_string()))
+            .copied()
+            .filter(|index| index.applies(context))
+    }
+
+    /// Candidates starting with `prefix` (case-insensitive). Unknown tool/argument pairs
+    /// yield an empty result rather than an error.
+    pub fn complete(&self, tool: &str, argument: &str, prefix: &str) -> CompletionResult {
+        let prefix = prefix.trim().to_lowercase();
+        let matches = self
+            .candidates
+            .get(&(tool.to_string(), argument.to_string()))
+            .map(|values| {
+                values
+                    .iter()
+                    .filter(|value| value.to_lowercase().starts_with(&prefix))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        capped(matches)
+    }
+}
+
+/// `values` matching the partial `value`, best first: names starting with it, then names
+/// with a word starting with it, then names in which every typed word matches some word
+/// by prefix or one edit (so `peckam` finds "Camberwell and Peckham"). Ties keep the
+/// order of `values`.
+pub fn rank_fuzzy(values: &[String], value: &str) -> CompletionResult {
+    let partial = value.trim().to_lowercase();
+    let typed = words(&partial);
+    let mut ranked = values
+        .iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let names = words(&lower);
+            let tier = if lower.starts_with(&partial) {
+                0
+            } else if names.iter().any(|word| word.starts_with(&partial)) {
+                1
+            } else if !typed.is_empty()
+                && typed
+                    .iter()
+                    .all(|typed| names.iter().any(|word| fuzzy_word_match(typed, word)))
+            {
+                2
+            } else {
+                return None;
+            };
+            Some((tier, candidate))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|(tier, _)| *tier);
+
+    capped(
+        ranked
+            .into_iter()
+            .map(|(_, candidate)| candidate.clone())
+            .collect(),
+    )
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn capped(matches: Vec<String>) -> CompletionResult {
+    let total = matches.len();
+    let values = matches
+        .into_iter()
+        .take(MAX_COMPLETION_VALUES)
+        .collect::<Vec<_>>();
+
+    CompletionResult {
+        has_more: total > values.len(),
+        total,
+        values,
+    }
+}
+
+impl Default for CompletionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+use crate::core::build_info::BuildInfo;
+use crate::core::client_log::LogLevel;
+use crate::features::mcp::errors::ToolErrorKind;
 
 #[derive(Debug, Deserialize)]
 pub struct JsonRpcRequest {
@@ -10,6 +14,9 @@ pub struct JsonRpcRequest {
     pub id: Option<Value>,
     pub method: String,
     pub params: Option<Value>,
+    /// Top-level members JSON-RPC does not define, e.g. a stray `sessionId`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -61,6 +68,14 @@ pub struct CallToolParams {
     pub name: String,
     #[serde(default)]
     pub arguments: Value,
+    /// Client metadata such as trace ids or a `progressToken`.
+    #[serde(rename = "_meta", default)]
+    pub meta: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetLevelParams {
+    pub level: LogLevel,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -72,6 +87,8 @@ pub struct ToolDefinition {
     pub input_schema: Value,
     #[serde(rename = "outputSchema", skip_serializing_if = "Option::is_none")]
     pub output_schema: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -89,11 +106,95 @@ pub struct ToolCallResult {
     pub structured_content: Option<Value>,
     #[serde(rename = "isError", skip_serializing_if = "Option::is_none")]
     pub is_error: Option<bool>,
+    /// Result metadata: the client's echoed trace fields plus `correlationId`, `durationMs`
+    /// and, when the call made lookups, `cached`.
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+/// Machine-readable detail attached as `structuredContent` to failed tool results.
+#[derive(Debug, Serialize)]
+pub struct ToolErrorData {
+    pub kind: ToolErrorKind,
+    pub status: Option<u16>,
+    pub url: Option<String>,
+    pub retryable: bool,
+    #[serde(rename = "timeoutSecs", skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Matches the `X-Correlation-Id` sent on the call's upstream requests.
+    #[serde(rename = "correlationId", skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// The running build, on internal errors only, so reports name the build that failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildInfo>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ToolContent {
+#[serde(tag = "type")]
+pub enum ToolContent {
+    #[serde(rename = "text")]
+    Text { text: String },
+    /// A link to an external page the client can open; see [`crate::features::mcp::links`].
+    #[serde(rename = "resource_link")]
+    ResourceLink {
+        uri: String,
+        name: String,
+        title: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+}
+
+impl ToolContent {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text { text: text.into() }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompleteParams {
+    #[serde(rename = "ref")]
+    pub reference: CompletionReference,
+    pub argument: CompletionArgument,
+    #[serde(default)]
+    pub context: CompletionContext,
+}
+
+/// Arguments the client has already filled in, which narrow some completions.
+#[derive(Debug, Default, Deserialize)]
+pub struct CompletionContext {
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompletionReference {
     #[serde(rename = "type")]
     pub kind: String,
-    pub text: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompletionArgument {
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompletionResult {
+    pub values: Vec<String>,
+    pub total: usize,
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HealthQuery {
+    /// Also probe the Members API (HEAD, 2-second timeout).
+    #[serde(default)]
+    pub deep: bool,
 }
@@ -0,0 +1,228 @@
+use std::sync::OnceLock;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Hex characters of the catalogue digest used as its version.
+const CATALOGUE_VERSION_LEN: usize = 12;
+
+/// `kind` of a failed tool result's `structuredContent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolErrorKind {
+    Upstream,
+    Config,
+    Internal,
+    BadRequest,
+    Timeout,
+}
+
+impl ToolErrorKind {
+    pub const ALL: &'static [Self] = &[
+        Self::Upstream,
+        Self::Config,
+        Self::Internal,
+        Self::BadRequest,
+        Self::Timeout,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Upstream => "upstream",
+            Self::Config => "config",
+            Self::Internal => "internal",
+            Self::BadRequest => "bad_request",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    /// Whether a call failing this way may succeed if repeated. Upstream failures are
+    /// retryable unless the API answered with a status other than 408, 429 or 5xx.
+    pub fn retryable(self, upstream_status: Option<u16>) -> bool {
+        match self {
+            Self::Upstream => {
+                upstream_status.is_none_or(|status| status == 408 || status == 429 || status >= 500)
+            }
+            Self::Timeout => true,
+            Self::Config | Self::Internal | Self::BadRequest => false,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Upstream => {
+                "A Parliament or legislation API failed or could not be reached; status and url say which."
+            }
+            Self::Config => "The server is not configured for this tool or option.",
+            Self::Internal => "The server failed while building the result.",
+            Self::BadRequest => "The arguments were understood but cannot be answered.",
+            Self::Timeout => {
+                "The tool did not finish within its timeout; timeoutSecs gives the limit."
+            }
+        }
+    }
+
+    fn action(self) -> &'static str {
+        match self {
+            Self::Upstream => {
+                "Retry with backoff when retryable is true; otherwise change the arguments."
+            }
+            Self::Config => "Do not retry; ask the operator to enable the feature.",
+            Self::Internal => "Do not retry; report the correlationId to the operator.",
+            Self::BadRequest => "Fix the arguments before calling again.",
+            Self::Timeout => "Retry later, or narrow the request (fewer terms, smaller limit).",
+        }
+    }
+}
+
+/// JSON-RPC `error.code` values the server answers with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonRpcErrorCode {
+    ParseError,
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    ServerError,
+    /// The server is still starting, or the session is not initialized or was terminated.
+    NotInitialized,
+    /// Every request or `research.run` slot stayed taken for `REQUEST_QUEUE_WAIT_SECS`.
+    ServerBusy,
+}
+
+impl JsonRpcErrorCode {
+    pub const ALL: &'static [Self] = &[
+        Self::ParseError,
+        Self::InvalidRequest,
+        Self::MethodNotFound,
+        Self::InvalidParams,
+        Self::ServerError,
+        Self::NotInitialized,
+        Self::ServerBusy,
+    ];
+
+    pub fn code(self) -> i32 {
+        match self {
+            Self::ParseError => -32700,
+            Self::InvalidRequest => -32600,
+            Self::MethodNotFound => -32601,
+            Self::InvalidParams => -32602,
+            Self::ServerError => -32000,
+            Self::NotInitialized => -32002,
+            Self::ServerBusy => -32003,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ParseError => "parse_error",
+            Self::InvalidRequest => "invalid_request",
+            Self::MethodNotFound => "method_not_found",
+            Self::InvalidParams => "invalid_params",
+            Self::ServerError => "server_error",
+            Self::NotInitialized => "not_initialized",
+            Self::ServerBusy => "server_busy",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::ParseError => "The body was not a JSON-RPC request.",
+            Self::InvalidRequest => {
+                "The request is malformed, too large, reuses an in-flight id, or needs the admin API key."
+            }
+            Self::MethodNotFound => "The method, tool, or disabled tool is unknown to the server.",
+            Self::InvalidParams => "The params or tool arguments failed validation.",
+            Self::ServerError => "The server could not encode or validate its own response.",
+            Self::NotInitialized => {
+                "The server is still starting, or the session has not completed initialize and notifications/initialized, or was terminated."
+            }
+            Self::ServerBusy => {
+                "Too many requests (or research.run calls) were already running; this one queued and was turned away."
+            }
+        }
+    }
+
+    fn retryable(self) -> bool {
+        matches!(self, Self::NotInitialized | Self::ServerBusy)
+    }
+
+    fn action(self) -> &'static str {
+        match self {
+            Self::ParseError | Self::InvalidRequest => "Fix the request before sending it again.",
+            Self::MethodNotFound => "Call tools/list to see what is available.",
+            Self::InvalidParams => "Fix the arguments named in the message.",
+            Self::ServerError => "Do not retry; report it to the operator.",
+            Self::NotInitialized => {
+                "Wait for /api/ready if the server is starting; otherwise call initialize, send notifications/initialized, then retry."
+            }
+            Self::ServerBusy => "Retry after a short backoff.",
+        }
+    }
+}
+
+/// Everything `utilities.error_catalogue` returns.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCatalogue {
+    /// Changes whenever any entry changes; also reported in `initialize`'s `serverInfo`.
+    pub version: String,
+    pub tool_errors: Vec<ToolErrorEntry>,
+    pub json_rpc_errors: Vec<JsonRpcErrorEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ToolErrorEntry {
+    pub kind: ToolErrorKind,
+    pub description: String,
+    /// Without an upstream status; see the description for upstream failures.
+    pub retryable: bool,
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonRpcErrorEntry {
+    pub code: i32,
+    pub name: String,
+    pub description: String,
+    pub retryable: bool,
+    pub action: String,
+}
+
+/// The catalogue, built once from [`ToolErrorKind`] and [`JsonRpcErrorCode`].
+pub fn error_catalogue() -> &'static ErrorCatalogue {
+    static CATALOGUE: OnceLock<ErrorCatalogue> = OnceLock::new();
+    CATALOGUE.get_or_init(|| {
+        let tool_errors: Vec<ToolErrorEntry> = ToolErrorKind::ALL
+            .iter()
+            .map(|&kind| ToolErrorEntry {
+                kind,
+                description: kind.description().to_string(),
+                retryable: kind.retryable(None),
+                action: kind.action().to_string(),
+            })
+            .collect();
+        let json_rpc_errors: Vec<JsonRpcErrorEntry> = JsonRpcErrorCode::ALL
+            .iter()
+            .map(|&code| JsonRpcErrorEntry {
+                code: code.code(),
+                name: code.name().to_string(),
+                description: code.description().to_string(),
+                retryable: code.retryable(),
+                action: code.action().to_string(),
+            })
+            .collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(&tool_errors).unwrap_or_default());
+        hasher.update(serde_json::to_vec(&json_rpc_errors).unwrap_or_default());
+        let mut version = hex::encode(hasher.finalize());
+        version.truncate(CATALOGUE_VERSION_LEN);
+
+        ErrorCatalogue {
+            version,
+            tool_errors,
+            json_rpc_errors,
+        }
+    })
+}
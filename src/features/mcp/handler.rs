@@ -1,39 +1,107 @@
-use axum::Json;
-use axum::extract::State;
-use axum::http::{HeaderMap, StatusCode};
-use axum::response::IntoResponse;
-use serde_json::{Value, json};
+use std::convert::Infallible;
+use std::time::Duration;
 
-use crate::core::error::AppError;
-use crate::features::mcp::dto::{JsonRpcError, JsonRpcErrorResponse, JsonRpcRequest};
-use crate::server::AppState;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use serde_json::{Map, Value, json};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::Instrument;
+
+use crate::core::concurrency::ConcurrencyPermit;
+use crate::core::notifications::NotificationSink;
+use crate::features::mcp::chunked::CHUNKED_TOOLS;
+use crate::features::mcp::dto::{HealthQuery, JsonRpcError, JsonRpcErrorResponse, JsonRpcRequest};
+use crate::features::mcp::errors::JsonRpcErrorCode;
+use crate::features::mcp::service::{RequestContext, server_busy};
+use crate::features::mcp::session::new_session_id;
+use crate::server::fallback::invalid_request;
+use crate::server::request_id::REQUEST_ID_HEADER;
+use crate::server::{AccessLogRecord, ApiKeyName, AppState, InFlightGuard};
+
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+const CREDENTIAL_HEADERS: [&str; 2] = ["x-api-key", "authorization"];
+
+/// Upper bound on the `?deep=true` Members API probe.
+const MEMBERS_API_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub async fn handle_mcp(
     State(state): State<AppState>,
+    key_name: Option<Extension<ApiKeyName>>,
+    access: Option<Extension<AccessLogRecord>>,
     headers: HeaderMap,
-    Json(payload): Json<Value>,
-) -> impl IntoResponse {
-    // Debug: Log all headers for troubleshooting
-    tracing::debug!(
-        headers = ?headers.iter().map(|(k, v)| (k.as_str(), v.to_str().unwrap_or("<invalid>"))).collect::<Vec<_>>(),
-        "received request headers"
-    );
+    payload: Result<Json<Value>, JsonRejection>,
+) -> Response {
+    let payload = match payload {
+        Ok(Json(payload)) => payload,
+        Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+            return body_too_large(state.config.max_request_bytes);
+        }
+        Err(JsonRejection::MissingJsonContentType(_)) => {
+            return unsupported_content_type(&headers);
+        }
+        Err(rejection) => return unparseable_body(rejection),
+    };
+    if let Some(Extension(record)) = &access {
+        record_rpc(record, &payload);
+    }
+
+    if !state.readiness.is_ready() {
+        return server_starting(payload.get("id").cloned().unwrap_or(Value::Null));
+    }
+    // Each batch entry runs concurrently, so a batch takes one slot per entry.
+    let slots = match &payload {
+        Value::Array(batch) if batch.len() > state.request_limit.limit() => {
+            return batch_too_large(batch.len(), state.request_limit.limit());
+        }
+        Value::Array(batch) => batch.len().max(1),
+        _ => 1,
+    };
+    let Some(slot) = state.request_limit.acquire_many(slots).await else {
+        let id = payload.get("id").cloned().unwrap_or(Value::Null);
+        let error = server_busy(id, "MAX_CONCURRENT_REQUESTS");
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, "1")],
+            Json(json!(error)),
+        )
+            .into_response();
+    };
+    let in_flight = state.in_flight.track();
+
+    tracing::debug!(headers = ?loggable_headers(&headers), "received request headers");
+
+    let Some(context) = bind_session(&state, request_context(&headers, key_name), &payload) else {
+        return session_not_found();
+    };
+    let issued_session = context
+        .issued_session
+        .then(|| context.session_id.clone())
+        .flatten();
+
+    if let Value::Array(batch) = payload {
+        return handle_batch(state, context, batch).await;
+    }
 
-    match serde_json::from_value::<JsonRpcRequest>(payload) {
+    let mut response = match serde_json::from_value::<JsonRpcRequest>(payload) {
         Ok(request) => {
-            let header_protocol_version = headers
-                .get("MCP-Protocol-Version")
-                .and_then(|value| value.to_str().ok())
-                .map(|value| value.to_string());
-
-            match state
-                .service
-                .handle_jsonrpc(request, header_protocol_version)
-                .await
+            let streams_logs = is_tool_call(&request) && state.service.wants_log_messages(&context);
+            if accepts_event_stream(&headers)
+                && (requests_progress(&request) || streams_logs || calls_chunked_tool(&request))
             {
-                Ok(Some(success)) => Json(json!(success)).into_response(),
-                Ok(None) => StatusCode::NO_CONTENT.into_response(),
-                Err(error) => Json(json!(error)).into_response(),
+                stream_response(state, request, context, in_flight, slot)
+            } else {
+                match state.service.handle_jsonrpc(request, context).await {
+                    Ok(Some(success)) => Json(json!(success)).into_response(),
+                    Ok(None) => StatusCode::NO_CONTENT.into_response(),
+                    Err(error) => Json(json!(error)).into_response(),
+                }
             }
         }
         Err(err) => {
@@ -41,16 +109,442 @@ pub async fn handle_mcp(
                 jsonrpc: "2.0".to_string(),
                 id: Value::Null,
                 error: JsonRpcError {
-                    code: -32700,
+                    code: JsonRpcErrorCode::ParseError.code(),
                     message: format!("failed to parse request: {err}"),
                     data: None,
                 },
             };
             Json(json!(error)).into_response()
         }
+    };
+    if let Some(value) = issued_session.and_then(|id| HeaderValue::from_str(&id).ok()) {
+        response.headers_mut().insert(SESSION_ID_HEADER, value);
+    }
+    response
+}
+
+/// Request headers for the debug log, with credentials masked. Query-string keys are
+/// copied into `x-api-key` by the auth middleware, so they are masked here too.
+fn loggable_headers(headers: &HeaderMap) -> Vec<(&str, &str)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if CREDENTIAL_HEADERS.contains(&name.as_str()) {
+                "<redacted>"
+            } else {
+                value.to_str().unwrap_or("<invalid>")
+            };
+            (name.as_str(), value)
+        })
+        .collect()
+}
+
+/// Point the request at its session. An `Mcp-Session-Id` must name a session the caller's
+/// key holds, or the request is answered with 404. Without one, `initialize` is issued a fresh id,
+/// returned in the response header, and other requests use the key's default session.
+fn bind_session(
+    state: &AppState,
+    context: RequestContext,
+    payload: &Value,
+) -> Option<RequestContext> {
+    if context.session_id.is_some() {
+        return state.service.session_is_known(&context).then_some(context);
     }
+    if payload.get("method").and_then(Value::as_str) == Some("initialize") {
+        return Some(context.with_issued_session_id(new_session_id()));
+    }
+    Some(
+        match state.service.default_session(context.key_name.as_deref()) {
+            Some(session_id) => context.with_session_id(session_id),
+            None => context,
+        },
+    )
+}
+
+fn session_not_found() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": {
+                "code": "session_not_found",
+                "message": "no active session matches the Mcp-Session-Id header"
+            }
+        })),
+    )
+        .into_response()
+}
+
+/// Note the JSON-RPC method and, for tool calls, the tool name for the access log.
+fn record_rpc(record: &AccessLogRecord, payload: &Value) {
+    if payload.is_array() {
+        record.set_rpc("batch", None);
+        return;
+    }
+    let Some(method) = payload.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let tool = matches!(method, "tools/call" | "call_tool")
+        .then(|| payload.pointer("/params/name").and_then(Value::as_str))
+        .flatten();
+    record.set_rpc(method, tool);
+}
+
+/// A JSON-RPC batch is answered with one JSON array; progress and log notifications are
+/// not streamed for batched requests.
+async fn handle_batch(state: AppState, context: RequestContext, batch: Vec<Value>) -> Response {
+    if batch.is_empty() {
+        let error = JsonRpcErrorResponse {
+            jsonrpc: "2.0".to_string(),
+            id: Value::Null,
+            error: JsonRpcError {
+                code: JsonRpcErrorCode::InvalidRequest.code(),
+                message: "empty batch".to_string(),
+                data: None,
+            },
+        };
+        return Json(json!(error)).into_response();
+    }
+
+    let responses = state.service.handle_batch(batch, context).await;
+    if responses.is_empty() {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        Json(Value::Array(responses)).into_response()
+    }
+}
+
+fn batch_too_large(len: usize, limit: usize) -> Response {
+    let error = JsonRpcErrorResponse {
+        jsonrpc: "2.0".to_string(),
+        id: Value::Null,
+        error: JsonRpcError {
+            code: JsonRpcErrorCode::InvalidRequest.code(),
+            message: format!("batch of {len} requests exceeds the limit of {limit}"),
+            data: Some(json!({"limit": "MAX_CONCURRENT_REQUESTS", "max": limit})),
+        },
+    };
+    Json(json!(error)).into_response()
+}
+
+/// `GET /api/mcp`: a long-lived SSE stream of server-initiated notifications such as
+/// `notifications/tools/list_changed`. Clients that only POST never open it.
+pub async fn handle_mcp_stream(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if !accepts_event_stream(&headers) {
+        return invalid_request(
+            StatusCode::NOT_ACCEPTABLE,
+            "GET /api/mcp opens the server notification stream and needs `Accept: text/event-stream`".to_string(),
+            Some(json!({
+                "hint": "send MCP requests as POST application/json to /api/mcp"
+            })),
+        );
+    }
+
+    let mut notifications = state.service.subscribe_notifications();
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = sender.closed() => break,
+                message = notifications.recv() => match message {
+                    Ok(message) => {
+                        if sender.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "notification stream lagged; messages dropped");
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+    });
+
+    Sse::new(event_stream(receiver))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// `DELETE /api/mcp`: end the session named by `Mcp-Session-Id`, cancelling its in-flight
+/// requests. Missing session ids, and ids the caller's key has no session under, are
+/// answered with 404.
+pub async fn handle_mcp_delete(
+    State(state): State<AppState>,
+    key_name: Option<Extension<ApiKeyName>>,
+    headers: HeaderMap,
+) -> Response {
+    if state
+        .service
+        .terminate_session(&request_context(&headers, key_name))
+    {
+        return StatusCode::NO_CONTENT.into_response();
+    }
+    session_not_found()
+}
+
+/// `GET /api/health`: liveness only. Reports the in-memory cache size, plus a Members API
+/// probe with `?deep=true`, and never touches Sled, so a slow start or a failing disk does
+/// not get the process restarted; readiness is `/api/ready`. An unreachable Members API
+/// reports `degraded` with 200.
+pub async fn handle_healthcheck(
+    State(state): State<AppState>,
+    Query(query): Query<HealthQuery>,
+) -> Response {
+    let mut checks = Map::new();
+    let mut degraded = false;
+
+    if query.deep {
+        let members_api = match state
+            .parliament_client
+            .probe_members_api(MEMBERS_API_PROBE_TIMEOUT)
+            .await
+        {
+            Ok(true) => "ok",
+            Ok(false) => "timeout",
+            Err(error) => {
+                tracing::warn!(%error, "health check: members API unreachable");
+                "error"
+            }
+        };
+        degraded |= members_api != "ok";
+        checks.insert("members_api".to_string(), json!(members_api));
+    }
+
+    let body = json!({
+        "status": if degraded { "degraded" } else { "ok" },
+        "checks": checks,
+        "cacheEntries": state.parliament_client.cache_entries().await,
+    });
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+/// `GET /api/ready`: 503 until start-up has finished, then a Sled write; 503 again when
+/// Sled is not writable.
+pub async fn handle_readiness(State(state): State<AppState>) -> Response {
+    if !state.readiness.is_ready() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "starting" })),
+        )
+            .into_response();
+    }
+
+    let storage = state.parliament_client.check_storage().await;
+    if let Err(error) = &storage {
+        tracing::error!(%error, "readiness check: sled is not writable");
+    }
+    let (status, label) = if storage.is_ok() {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+    let body = json!({
+        "status": label,
+        "checks": { "sled": check_label(storage.is_ok()) },
+    });
+    (status, Json(body)).into_response()
+}
+
+fn check_label(ok: bool) -> &'static str {
+    if ok { "ok" } else { "error" }
+}
+
+/// Build and protocol information for triage; needs no API key.
+pub async fn handle_version(State(state): State<AppState>) -> Json<Value> {
+    Json(state.service.version_info())
+}
+
+pub async fn handle_health_details(State(state): State<AppState>) -> Json<Value> {
+    let stats = state.service.describe_stats();
+    #[allow(unused_mut)]
+    let mut details = json!({
+        "status": "ok",
+        "tools": stats["tools"],
+        "upstreams": stats["upstreams"],
+        "stalenessThresholdSecs": stats["stalenessThresholdSecs"],
+        "build": state.service.build_info(),
+    });
+    #[cfg(feature = "fault-injection")]
+    {
+        details["faults"] = json!(*state.parliament_client.faults());
+    }
+    Json(details)
+}
+
+/// Prometheus scrape endpoint for request, tool, cache and upstream counters.
+pub async fn handle_metrics(State(state): State<AppState>) -> Response {
+    let body = state.service.metrics().render(
+        state.parliament_client.cache_counters(),
+        &state.parliament_client.concurrency_limits(),
+        state.parliament_client.upstream_metrics(),
+        state.service.research_scan_metrics(),
+        state.parliament_client.clock(),
+        &[
+            ("requests", &state.request_limit),
+            ("research", state.service.research_limit()),
+        ],
+    );
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response()
+}
+
+/// Answer the request as an SSE stream so notifications emitted while the request runs
+/// (e.g. `notifications/progress`, `notifications/message` or `notifications/tools/chunk`)
+/// reach the client before the final response.
+fn stream_response(
+    state: AppState,
+    request: JsonRpcRequest,
+    context: RequestContext,
+    in_flight: InFlightGuard,
+    slot: ConcurrencyPermit,
+) -> Response {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let sink = NotificationSink::new(sender);
+    let context = context.with_notifications(sink.clone());
+
+    // The spawned task keeps the `request` span so its logs still carry the request id.
+    tokio::spawn(
+        async move {
+            let _in_flight = in_flight;
+            let _slot = slot;
+            let message = match state.service.handle_jsonrpc(request, context).await {
+                Ok(Some(success)) => json!(success),
+                Ok(None) => return,
+                Err(error) => json!(error),
+            };
+            sink.send_raw(message);
+        }
+        .in_current_span(),
+    );
+
+    Sse::new(event_stream(receiver)).into_response()
+}
+
+/// Start-up has not finished; clients retry once `/api/ready` answers 200.
+fn server_starting(id: Value) -> Response {
+    let error = JsonRpcErrorResponse {
+        jsonrpc: "2.0".to_string(),
+        id,
+        error: JsonRpcError {
+            code: JsonRpcErrorCode::NotInitialized.code(),
+            message: "server starting".to_string(),
+            data: None,
+        },
+    };
+    (StatusCode::SERVICE_UNAVAILABLE, Json(json!(error))).into_response()
+}
+
+/// The body exceeded `MAX_REQUEST_BYTES`; answer in JSON-RPC rather than axum's plain-text 413.
+fn body_too_large(limit: usize) -> Response {
+    let error = JsonRpcErrorResponse {
+        jsonrpc: "2.0".to_string(),
+        id: Value::Null,
+        error: JsonRpcError {
+            code: JsonRpcErrorCode::InvalidRequest.code(),
+            message: format!("request body exceeds the {limit}-byte limit (MAX_REQUEST_BYTES)"),
+            data: Some(json!({ "maxRequestBytes": limit })),
+        },
+    };
+    (StatusCode::PAYLOAD_TOO_LARGE, Json(json!(error))).into_response()
+}
+
+/// A POST without `Content-Type: application/json`, refused before the body is read.
+fn unsupported_content_type(headers: &HeaderMap) -> Response {
+    let content_type = header_value(headers, header::CONTENT_TYPE.as_str());
+    let received = match &content_type {
+        Some(content_type) => format!("Content-Type `{content_type}`"),
+        None => "no Content-Type".to_string(),
+    };
+    invalid_request(
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        format!("unsupported request: {received}"),
+        Some(json!({
+            "hint": "the MCP endpoint only accepts POST application/json"
+        })),
+    )
+}
+
+/// A JSON body that does not parse; answered as a JSON-RPC parse error rather than axum's
+/// plain-text 400.
+fn unparseable_body(rejection: JsonRejection) -> Response {
+    let error = JsonRpcErrorResponse {
+        jsonrpc: "2.0".to_string(),
+        id: Value::Null,
+        error: JsonRpcError {
+            code: JsonRpcErrorCode::ParseError.code(),
+            message: rejection.body_text(),
+            data: None,
+        },
+    };
+    (rejection.status(), Json(json!(error))).into_response()
+}
+
+fn event_stream(
+    receiver: mpsc::UnboundedReceiver<Value>,
+) -> impl tokio_stream::Stream<Item = Result<Event, Infallible>> {
+    UnboundedReceiverStream::new(receiver).map(|message| {
+        Ok::<_, Infallible>(Event::default().event("message").data(message.to_string()))
+    })
+}
+
+fn request_context(headers: &HeaderMap, key_name: Option<Extension<ApiKeyName>>) -> RequestContext {
+    let mut context = RequestContext::new(header_value(headers, "MCP-Protocol-Version"));
+    if let Some(api_key) = header_value(headers, "x-api-key") {
+        context = context.with_api_key(api_key);
+    }
+    if let Some(Extension(ApiKeyName(name))) = key_name {
+        context = context.with_key_name(name);
+    }
+    if let Some(session_id) = header_value(headers, SESSION_ID_HEADER) {
+        context = context.with_session_id(session_id);
+    }
+    if let Some(request_id) = header_value(headers, REQUEST_ID_HEADER) {
+        context = context.with_request_id(request_id);
+    }
+    context
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.contains("text/event-stream"))
+}
+
+fn requests_progress(request: &JsonRpcRequest) -> bool {
+    request
+        .params
+        .as_ref()
+        .and_then(|params| params.get("_meta"))
+        .and_then(|meta| meta.get("progressToken"))
+        .is_some()
+}
+
+fn is_tool_call(request: &JsonRpcRequest) -> bool {
+    matches!(request.method.as_str(), "tools/call" | "call_tool")
 }
 
-pub async fn handle_healthcheck() -> Result<Json<Value>, AppError> {
-    Ok(Json(json!({ "status": "ok" })))
+fn calls_chunked_tool(request: &JsonRpcRequest) -> bool {
+    is_tool_call(request)
+        && request
+            .params
+            .as_ref()
+            .and_then(|params| params.get("name"))
+            .and_then(Value::as_str)
+            .is_some_and(|name| CHUNKED_TOOLS.contains(&name))
 }
@@ -0,0 +1,165 @@
+//! Member and bill id arguments in the forms clients actually send: a number, a numeric
+//! string, or the record's page on members.parliament.uk or bills.parliament.uk.
+
+use reqwest::Url;
+use serde_json::{Value, json};
+
+/// Which Parliament record an id argument names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    Member,
+    Bill,
+}
+
+impl IdKind {
+    fn host(self) -> &'static str {
+        match self {
+            Self::Member => "members.parliament.uk",
+            Self::Bill => "bills.parliament.uk",
+        }
+    }
+
+    /// The path segment preceding the id on the record's page.
+    fn segment(self) -> &'static str {
+        match self {
+            Self::Member => "member",
+            Self::Bill => "bills",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Member => "member",
+            Self::Bill => "bill",
+        }
+    }
+
+    fn example(self) -> u32 {
+        match self {
+            Self::Member => 4514,
+            Self::Bill => 3737,
+        }
+    }
+
+    fn example_url(self) -> String {
+        format!(
+            "https://{}/{}/{}",
+            self.host(),
+            self.segment(),
+            self.example()
+        )
+    }
+}
+
+/// Id arguments resolved before validation, by tool. `mpIds` is a list of ids.
+const ID_ARGUMENTS: &[(&str, &str, IdKind)] = &[
+    ("parliament.fetch_bill_divisions", "billId", IdKind::Bill),
+    ("parliament.fetch_bill_detail", "billId", IdKind::Bill),
+    ("parliament.fetch_mp_activity", "mpId", IdKind::Member),
+    ("parliament.fetch_member_roles", "mpId", IdKind::Member),
+    ("parliament.fetch_members_bulk", "mpIds", IdKind::Member),
+    ("parliament.fetch_mp_voting_record", "mpId", IdKind::Member),
+    ("parliament.export_voting_record", "mpId", IdKind::Member),
+    (
+        "parliament.fetch_written_questions",
+        "askingMemberId",
+        IdKind::Member,
+    ),
+    ("parliament.fetch_edms", "sponsorId", IdKind::Member),
+    (
+        "parliament.fetch_oral_questions",
+        "askingMemberId",
+        IdKind::Member,
+    ),
+    ("parliament.search_hansard", "memberId", IdKind::Member),
+    ("research.run", "mpId", IdKind::Member),
+];
+
+/// Input schema for an id argument: a positive integer, a numeric string or a page URL.
+pub fn id_schema(kind: IdKind) -> Value {
+    json!({
+        "description": format!(
+            "The {} id, e.g. {}; a numeric string or the {} URL ({}) is also accepted.",
+            kind.label(),
+            kind.example(),
+            kind.host(),
+            kind.example_url()
+        ),
+        "oneOf": [
+            {"type": "integer", "minimum": 1},
+            {"type": "string", "pattern": "^\\s*[0-9]+\\s*$"},
+            {
+                "type": "string",
+                "pattern": format!("{}/{}/[0-9]+", kind.host().replace('.', "\\."), kind.segment())
+            }
+        ]
+    })
+}
+
+/// Replace string forms of `tool`'s id arguments with their numeric id. Numbers and values
+/// of other types are left for schema validation to judge.
+pub fn resolve_id_arguments(tool: &str, arguments: &mut Value) -> Result<(), String> {
+    for (_, name, kind) in ID_ARGUMENTS.iter().filter(|(owner, ..)| *owner == tool) {
+        match arguments.get_mut(*name) {
+            Some(Value::Array(items)) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    resolve_in_place(*kind, item, &format!("{name}[{index}]"))?;
+                }
+            }
+            Some(value) => resolve_in_place(*kind, value, name)?,
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+fn resolve_in_place(kind: IdKind, value: &mut Value, name: &str) -> Result<(), String> {
+    if let Value::String(text) = value {
+        let id = resolve_id(kind, text).ok_or_else(|| {
+            format!(
+                "{name} must be a {} id such as {}, \"{}\" or {}; got \"{text}\"",
+                kind.label(),
+                kind.example(),
+                kind.example(),
+                kind.example_url()
+            )
+        })?;
+        *value = json!(id);
+    }
+    Ok(())
+}
+
+/// The id in `text`: a positive number, or a `kind` page URL such as
+/// `https://members.parliament.uk/member/4514/contact`. The scheme may be left off.
+pub fn resolve_id(kind: IdKind, text: &str) -> Option<u32> {
+    let text = text.trim();
+    if text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return text.parse().ok().filter(|id| *id > 0);
+    }
+
+    let url = if text.contains("://") {
+        Url::parse(text)
+    } else {
+        Url::parse(&format!("https://{text}"))
+    }
+    .ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?;
+    if host != kind.host() && host.strip_prefix("www.") != Some(kind.host()) {
+        return None;
+    }
+    let mut segments = url.path_segments()?;
+    if !segments
+        .next()
+        .is_some_and(|segment| segment.eq_ignore_ascii_case(kind.segment()))
+    {
+        return None;
+    }
+    let id = segments.next()?;
+    if id.is_empty() || !id.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    id.parse().ok().filter(|id| *id > 0)
+}
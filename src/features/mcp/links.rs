@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::features::mcp::dto::ToolContent;
+
+const TITLE_FIELDS: &[&str] = &["title", "shortTitle", "name"];
+
+/// Fields holding a browsable page URL in each tool's payload. Tools not listed here
+/// never get links.
+fn link_fields(tool_name: &str) -> &'static [&'static str] {
+    match tool_name {
+        // Bill and division `link`s, debate `link`s and legislation `uri`s.
+        "research.run" => &["link", "uri"],
+        "parliament.search_uk_law" => &["url"],
+        "parliament.search_hansard" => &["url"],
+        "parliament.fetch_written_questions" => &["url"],
+        "parliament.fetch_edms" => &["url"],
+        "parliament.fetch_division_detail" => &["link"],
+        // The bill's own `link` and its publications' `url`s.
+        "parliament.fetch_bill_detail" => &["link", "url"],
+        "parliament.fetch_committees" => &["url"],
+        "parliament.fetch_statutory_instruments" => &["url"],
+        "parliament.fetch_legislation" => &["uri"],
+        "parliament.fetch_legislation_text" => &["url"],
+        _ => &[],
+    }
+}
+
+/// Build at most `limit` `resource_link` content blocks for the known link fields in
+/// `payload`, skipping duplicates and anything that is not an http(s) URL.
+pub fn extract_resource_links(tool_name: &str, payload: &Value, limit: usize) -> Vec<ToolContent> {
+    let fields = link_fields(tool_name);
+    let mut links = Vec::new();
+    if fields.is_empty() || limit == 0 {
+        return links;
+    }
+
+    let mut seen = HashSet::new();
+    collect_links(payload, fields, limit, &mut seen, &mut links);
+    links
+}
+
+fn collect_links(
+    value: &Value,
+    fields: &[&str],
+    limit: usize,
+    seen: &mut HashSet<String>,
+    links: &mut Vec<ToolContent>,
+) {
+    if links.len() >= limit {
+        return;
+    }
+
+    match value {
+        Value::Object(map) => {
+            let url = fields
+                .iter()
+                .filter_map(|field| map.get(*field).and_then(Value::as_str))
+                .map(str::trim)
+                .find(|url| is_web_url(url));
+
+            if let Some(url) = url {
+                if seen.insert(url.to_string()) {
+                    let title = TITLE_FIELDS
+                        .iter()
+                        .filter_map(|field| map.get(*field).and_then(Value::as_str))
+                        .map(str::trim)
+                        .find(|title| !title.is_empty())
+                        .unwrap_or(url)
+                        .to_string();
+                    links.push(ToolContent::ResourceLink {
+                        uri: url.to_string(),
+                        name: title.clone(),
+                        title,
+                        mime_type: "text/html".to_string(),
+                    });
+                }
+            }
+
+            for child in map.values() {
+                collect_links(child, fields, limit, seen, links);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_links(item, fields, limit, seen, links);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_web_url(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://")
+}
@@ -1,7 +1,17 @@
+pub mod capabilities;
+pub mod chunked;
+pub mod completions;
 pub mod dto;
+pub mod errors;
 pub mod handler;
+pub mod ids;
+pub mod links;
 pub mod schemas;
 pub mod service;
+pub mod session;
 
-pub use handler::{handle_healthcheck, handle_mcp};
-pub use service::McpService;
+pub use handler::{
+    handle_health_details, handle_healthcheck, handle_mcp, handle_mcp_delete, handle_mcp_stream,
+    handle_metrics, handle_readiness, handle_version,
+};
+pub use service::{McpService, RequestContext};
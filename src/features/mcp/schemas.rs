@@ -1,8 +1,32 @@
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
 use serde_json::{Value, json};
 
+use crate::core::locale::Locale;
+use crate::core::usage::{MAX_WINDOW_HOURS, UsageSummary};
 use crate::features::mcp::dto::ToolDefinition;
+use crate::features::mcp::errors::ErrorCatalogue;
+use crate::features::mcp::ids::{IdKind, id_schema};
+use crate::features::parliament::client::{
+    MAX_COMMITTEE_INQUIRY_LOOKUPS, MAX_EDM_SIGNATORY_LOOKUPS, MAX_VOTING_EXPORT_ROWS, SI_CANDIDATES,
+};
+use crate::features::parliament::sessions::SESSION_PATTERN;
+use crate::features::parliament::{
+    BillDetail, ConstituencyLookupResult, DivisionDetail, FetchBillDivisionsResult,
+    FetchCommitteesResult, FetchEdmsResult, FetchMembersBulkResult, FetchOralQuestionsResult,
+    FetchStatutoryInstrumentsResult, FetchWrittenQuestionsResult, LegislationText,
+    ListConstituenciesResult, MemberRolesResult, MpActivityResult, MpVoteRecord,
+    PolicyAreaClassifier, SearchHansardResult, UkLawResult, VotingRecordExportResult,
+};
+use crate::features::reference_data::{REFERENCE_DATASETS, RefreshReport};
+use crate::features::research::handler::MAX_LIST_CACHED_LIMIT;
+use crate::features::research::{
+    KeywordSuggestionsResult, ListCachedResearchResult, MAX_RESULT_LIMIT, MAX_SUGGESTION_LIMIT,
+    ResearchResponseDto,
+};
+use crate::features::utilities::{CurrentDatetimeDto, VerifySignatureDto};
 
 pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
     let mut definitions = Vec::new();
@@ -23,9 +47,11 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
                 "page": {"type": "integer", "minimum": 0},
                 "perPage": {"type": "integer", "minimum": 1, "maximum": 100},
                 "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema(),
                 "fuzzyMatch": {"type": "boolean"},
                 "applyRelevance": {"type": "boolean"},
-                "relevanceThreshold": {"type": "number", "minimum": 0.0, "maximum": 1.0}
+                "relevanceThreshold": {"type": "number", "minimum": 0.0, "maximum": 1.0},
+                "fields": fields_schema()
             },
             "additionalProperties": false
         }),
@@ -49,11 +75,18 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
             "properties": {
                 "searchTerm": {"type": "string"},
                 "house": {"type": "string", "enum": ["commons", "lords"]},
-                "session": {"type": "string"},
+                "session": {
+                    "type": "string",
+                    "pattern": SESSION_PATTERN,
+                    "description": "Parliamentary session as \"YYYY-YY\" (\"2024-25\"; \"2024/25\" and \"2024-2025\" are also accepted), or \"current\" / \"previous\".",
+                    "examples": ["2024-25"]
+                },
                 "parliamentNumber": {"type": "integer", "minimum": 1},
                 "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema(),
                 "applyRelevance": {"type": "boolean"},
-                "relevanceThreshold": {"type": "number", "minimum": 0.0, "maximum": 1.0}
+                "relevanceThreshold": {"type": "number", "minimum": 0.0, "maximum": 1.0},
+                "fields": fields_schema()
             },
             "additionalProperties": false
         }),
@@ -63,6 +96,31 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
         })),
     );
 
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_bill_divisions",
+        "Parliament: Fetch bill divisions",
+        "List the Commons and Lords divisions held on a bill, grouped by stage. Accepts a billId or the bill's exact short title; divisions matched only by a similar title are flagged lowConfidence.",
+        json!({
+            "type": "object",
+            "anyOf": [
+                {"required": ["billId"]},
+                {"required": ["billTitle"]}
+            ],
+            "properties": {
+                "billId": id_schema(IdKind::Bill),
+                "billTitle": {"type": "string", "minLength": 1},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(
+            output_schema_for::<FetchBillDivisionsResult>(),
+        )),
+    );
+
     push_tool(
         &mut definitions,
         &mut input_schemas,
@@ -76,12 +134,13 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
                 "year": {"type": "integer", "minimum": 1800},
                 "type": {"type": "string", "enum": ["all", "ukpga", "ukci", "ukla", "nisi"]},
                 "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema(),
                 "applyRelevance": {"type": "boolean"},
                 "relevanceThreshold": {"type": "number", "minimum": 0.0, "maximum": 1.0}
             },
             "additionalProperties": false
         }),
-        Some(json!({
+        Some(with_cache_miss(json!({
             "description": "Structured summary of legislation feed entries.",
             "type": "array",
             "items": {
@@ -95,7 +154,7 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
                 },
                 "required": ["title"]
             }
-        })),
+        }))),
     );
 
     push_tool(
@@ -103,32 +162,45 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
         &mut input_schemas,
         "parliament.fetch_mp_activity",
         "Parliament: Fetch MP activity",
-        "List recent activity (debates, questions, statements) for a given MP.",
+        "List an MP's recent spoken contributions, written questions and early day motions, newest first.",
         json!({
             "type": "object",
             "required": ["mpId"],
             "properties": {
-                "mpId": {"type": "integer", "minimum": 1},
+                "mpId": id_schema(IdKind::Member),
                 "limit": {"type": "integer", "minimum": 1, "maximum": 50},
-                "enableCache": {"type": "boolean"}
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
             },
             "additionalProperties": false
         }),
-        Some(json!({
-            "type": "array",
-            "items": {
-                "type": "object",
-                "properties": {
-                    "id": {"type": "string"},
-                    "date": {"type": "string"},
-                    "type": {"type": "string"},
-                    "title": {"type": "string"},
-                    "description": {"type": "string"},
-                    "url": {"type": ["string", "null"], "format": "uri"}
+        Some(with_cache_miss(output_schema_for::<MpActivityResult>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_members_bulk",
+        "Parliament: Fetch members in bulk",
+        "Resolve up to 100 member ids to name, party, constituency and current status in one call.",
+        json!({
+            "type": "object",
+            "required": ["mpIds"],
+            "properties": {
+                "mpIds": {
+                    "type": "array",
+                    "items": id_schema(IdKind::Member),
+                    "minItems": 1,
+                    "maxItems": 100
                 },
-                "required": ["id", "date", "type", "title", "description"]
-            }
-        })),
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(
+            output_schema_for::<FetchMembersBulkResult>(),
+        )),
     );
 
     push_tool(
@@ -136,33 +208,92 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
         &mut input_schemas,
         "parliament.fetch_mp_voting_record",
         "Parliament: Fetch MP voting record",
-        "Summarise an MP's voting record, optionally filtering by date range or bill.",
+        "Summarise an MP's voting record, optionally filtering by date range or bill. With groupBy \"policyArea\" the divisions are tallied per policy area instead of listed.",
         json!({
             "type": "object",
             "required": ["mpId"],
             "properties": {
-                "mpId": {"type": "integer", "minimum": 1},
+                "mpId": id_schema(IdKind::Member),
                 "fromDate": {"type": "string", "format": "date"},
                 "toDate": {"type": "string", "format": "date"},
                 "billId": {"type": "string"},
+                "house": {
+                    "type": "string",
+                    "enum": ["commons", "lords"],
+                    "description": "Which house's divisions to read; defaults to commons. Lords votes are recorded as Content or Not Content."
+                },
                 "limit": {"type": "integer", "minimum": 1, "maximum": 100},
-                "enableCache": {"type": "boolean"}
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema(),
+                "groupBy": {
+                    "type": "string",
+                    "enum": ["policyArea"],
+                    "description": policy_area_description()
+                }
             },
             "additionalProperties": false
         }),
-        Some(json!({
-            "type": "array",
-            "items": {
-                "type": "object",
-                "properties": {
-                    "divisionId": {"type": ["string", "null"]},
-                    "title": {"type": ["string", "null"]},
-                    "date": {"type": ["string", "null"]},
-                    "vote": {"type": ["string", "null"]},
-                    "majority": {"type": ["string", "null"]}
+        Some(with_cache_miss(json!({
+            "anyOf": [
+                {
+                    "type": "array",
+                    "items": {"$ref": "#/$defs/voteRecord"}
+                },
+                {
+                    "type": "object",
+                    "required": ["groupBy", "totalDivisions", "areas", "other"],
+                    "properties": {
+                        "groupBy": {"const": "policyArea"},
+                        "totalDivisions": {"type": "integer"},
+                        "areas": {
+                            "type": "array",
+                            "items": {"$ref": "#/$defs/policyAreaTally"}
+                        },
+                        "other": {"$ref": "#/$defs/policyAreaTally"},
+                        "coverageNote": {"type": "string"}
+                    }
+                }
+            ],
+            "$defs": {
+                "voteRecord": output_schema_for::<MpVoteRecord>(),
+                "policyAreaTally": {
+                    "type": "object",
+                    "required": ["id", "label", "for", "against", "absent", "total", "divisions"],
+                    "properties": {
+                        "id": {"type": "string"},
+                        "label": {"type": "string"},
+                        "for": {"type": "integer"},
+                        "against": {"type": "integer"},
+                        "absent": {"type": "integer"},
+                        "total": {"type": "integer"},
+                        "divisions": {
+                            "type": "array",
+                            "items": {"$ref": "#/$defs/voteRecord"}
+                        }
+                    }
                 }
             }
-        })),
+        }))),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.export_voting_record",
+        "Parliament: Export MP voting record (CSV)",
+        "Export every Commons division an MP voted in as CSV, newest first. The CSV arrives as text content blocks, one per upstream page; over SSE each block is sent as a notifications/tools/chunk message before the final result.",
+        json!({
+            "type": "object",
+            "required": ["mpId"],
+            "properties": {
+                "mpId": id_schema(IdKind::Member),
+                "fromDate": {"type": "string", "format": "date"},
+                "toDate": {"type": "string", "format": "date"},
+                "limit": {"type": "integer", "minimum": 1, "maximum": MAX_VOTING_EXPORT_ROWS}
+            },
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<VotingRecordExportResult>()),
     );
 
     push_tool(
@@ -176,19 +307,43 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
             "required": ["postcode"],
             "properties": {
                 "postcode": {"type": "string", "minLength": 2},
-                "enableCache": {"type": "boolean"}
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
             },
             "additionalProperties": false
         }),
-        Some(json!({
+        Some(with_cache_miss(
+            output_schema_for::<ConstituencyLookupResult>(),
+        )),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.list_constituencies",
+        "Parliament: List constituencies",
+        "Enumerate every Westminster constituency with its ONS code, nation and current MP, optionally filtered by nation, region or name prefix. Served from a cached copy of the full list.",
+        json!({
             "type": "object",
             "properties": {
-                "constituencyCode": {"type": ["string", "null"]},
-                "constituencyName": {"type": ["string", "null"]},
-                "mpId": {"type": ["integer", "null"]},
-                "mpName": {"type": ["string", "null"]}
-            }
-        })),
+                "nation": {
+                    "type": "string",
+                    "enum": ["England", "Scotland", "Wales", "Northern Ireland"]
+                },
+                "region": {"type": "string", "minLength": 1},
+                "search": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "Case-insensitive constituency name prefix."
+                },
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(
+            output_schema_for::<ListConstituenciesResult>(),
+        )),
     );
 
     push_tool(
@@ -204,26 +359,270 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
                 "query": {"type": "string", "minLength": 1},
                 "legislationType": {"type": "string", "enum": ["primary", "secondary", "all"]},
                 "limit": {"type": "integer", "minimum": 1, "maximum": 50},
-                "enableCache": {"type": "boolean"}
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
             },
             "additionalProperties": false
         }),
-        Some(json!({
-            "type": "array",
-            "items": {
-                "type": "object",
-                "properties": {
-                    "title": {"type": "string"},
-                    "year": {"type": ["string", "null"]},
-                    "legislationType": {"type": "string"},
-                    "isInForce": {"type": "boolean"},
-                    "url": {"type": "string", "format": "uri"},
-                    "summary": {"type": ["string", "null"]},
-                    "lastUpdated": {"type": ["string", "null"]}
+        Some(with_cache_miss(output_schema_for::<Vec<UkLawResult>>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.search_hansard",
+        "Parliament: Search Hansard",
+        "Search what was said in the Commons and Lords, newest sitting first, with a snippet and a link to each contribution in Hansard.",
+        json!({
+            "type": "object",
+            "required": ["query"],
+            "properties": {
+                "query": {"type": "string", "minLength": 1},
+                "house": {"type": "string", "enum": ["commons", "lords"]},
+                "fromDate": {"type": "string", "format": "date"},
+                "toDate": {"type": "string", "format": "date"},
+                "memberId": id_schema(IdKind::Member),
+                "limit": {"type": "integer", "minimum": 1, "maximum": 100},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<SearchHansardResult>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_committees",
+        "Parliament: Fetch committees",
+        "Current Commons, Lords and joint committees matching a search, optionally with their open inquiries; or one committee by id with its current members.",
+        json!({
+            "type": "object",
+            "properties": {
+                "committeeId": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Look up one committee with its members; cannot be combined with searchTerm or house."
                 },
-                "required": ["title", "legislationType", "isInForce", "url"]
-            }
-        })),
+                "searchTerm": {"type": "string"},
+                "house": {"type": "string", "enum": ["commons", "lords", "joint"]},
+                "includeInquiries": {
+                    "type": "boolean",
+                    "description": format!("List the open inquiries of each of the first {MAX_COMMITTEE_INQUIRY_LOOKUPS} committees.")
+                },
+                "limit": {"type": "integer", "minimum": 1, "maximum": 50},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<FetchCommitteesResult>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_bill_detail",
+        "Parliament: Fetch bill detail",
+        "One bill's titles, current stage, sponsors, stage-by-stage history and publications (bill text, explanatory notes).",
+        json!({
+            "type": "object",
+            "required": ["billId"],
+            "properties": {
+                "billId": id_schema(IdKind::Bill),
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<BillDetail>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_legislation_text",
+        "Parliament: Fetch legislation text",
+        "The current wording of an Act or instrument, or one of its sections, from legislation.gov.uk, with its extent and notes on amendments.",
+        json!({
+            "type": "object",
+            "properties": {
+                "uri": {
+                    "type": "string",
+                    "description": "A legislation.gov.uk URI or path, e.g. https://www.legislation.gov.uk/ukpga/2008/27/section/1; alternative to type, year and number."
+                },
+                "type": {"type": "string", "description": "e.g. ukpga or uksi."},
+                "year": {"type": "integer", "minimum": 1800},
+                "number": {"type": "integer", "minimum": 1},
+                "section": {"type": "string", "description": "e.g. 1 or 3A; the whole document when absent."},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<LegislationText>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_member_roles",
+        "Parliament: Fetch member roles",
+        "An MP's current and past committee memberships and government or opposition posts, current roles first.",
+        json!({
+            "type": "object",
+            "required": ["mpId"],
+            "properties": {
+                "mpId": id_schema(IdKind::Member),
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<MemberRolesResult>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_division_detail",
+        "Parliament: Fetch division detail",
+        "One Commons or Lords division with its totals and the full lists of members voting each way, tellers included.",
+        json!({
+            "type": "object",
+            "required": ["divisionId"],
+            "properties": {
+                "divisionId": {"type": "integer", "minimum": 1},
+                "house": {"type": "string", "enum": ["commons", "lords"]},
+                "namesOnly": {
+                    "type": "boolean",
+                    "description": "Leave out each member's party to shorten the result."
+                },
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<DivisionDetail>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_edms",
+        "Parliament: Fetch early day motions",
+        "Early day motions, newest tabled first, with their primary sponsor and signature count, optionally listing who signed the first few.",
+        json!({
+            "type": "object",
+            "properties": {
+                "searchTerm": {"type": "string"},
+                "sponsorId": id_schema(IdKind::Member),
+                "status": {"type": "string", "enum": ["published", "withdrawn"]},
+                "tabledFrom": {"type": "string", "format": "date"},
+                "tabledTo": {"type": "string", "format": "date"},
+                "limit": {"type": "integer", "minimum": 1, "maximum": 50},
+                "includeSignatories": {
+                    "type": "boolean",
+                    "description": format!("List the members who signed each of the first {MAX_EDM_SIGNATORY_LOOKUPS} motions.")
+                },
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<FetchEdmsResult>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_statutory_instruments",
+        "Parliament: Fetch statutory instruments",
+        "Statutory instruments laid before Parliament, most recently laid first, with their SI number, laying department, procedure and scrutiny status.",
+        json!({
+            "type": "object",
+            "properties": {
+                "searchTerm": {
+                    "type": "string",
+                    "description": format!("Words in the instrument's name; other filters apply to the {SI_CANDIDATES} most recent matches.")
+                },
+                "department": {"type": "string", "description": "Part of the laying department's name, e.g. Education."},
+                "procedure": {"type": "string", "enum": ["affirmative", "negative"]},
+                "laidFrom": {"type": "string", "format": "date"},
+                "laidTo": {"type": "string", "format": "date"},
+                "limit": {"type": "integer", "minimum": 1, "maximum": 50},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<
+            FetchStatutoryInstrumentsResult,
+        >())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_oral_questions",
+        "Parliament: Fetch oral questions",
+        "Oral questions scheduled for, or already answered in, the Commons chamber, by answering date, with the asking member and answering department.",
+        json!({
+            "type": "object",
+            "properties": {
+                "answeringDateFrom": {"type": "string", "format": "date"},
+                "answeringDateTo": {"type": "string", "format": "date"},
+                "answeringBody": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Answering body (department) id, as listed by the Members API's answering bodies."
+                },
+                "askingMemberId": id_schema(IdKind::Member),
+                "status": {
+                    "type": "string",
+                    "enum": ["tabled", "answered"],
+                    "description": "tabled: due to be answered today or later; answered: answering day has passed. Both when omitted."
+                },
+                "limit": {"type": "integer", "minimum": 1, "maximum": 100},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(
+            output_schema_for::<FetchOralQuestionsResult>(),
+        )),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "parliament.fetch_written_questions",
+        "Parliament: Fetch written questions",
+        "Written parliamentary questions, newest tabled first, filtered by asking member, answering department, tabled dates, answered status or text, with the answer once given.",
+        json!({
+            "type": "object",
+            "properties": {
+                "askingMemberId": id_schema(IdKind::Member),
+                "answeringBody": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Answering body (department) id, as listed by the Members API's answering bodies."
+                },
+                "tabledFrom": {"type": "string", "format": "date"},
+                "tabledTo": {"type": "string", "format": "date"},
+                "answered": {"type": "string", "enum": ["any", "answered", "unanswered"]},
+                "searchTerm": {"type": "string"},
+                "limit": {"type": "integer", "minimum": 1, "maximum": 100},
+                "enableCache": {"type": "boolean"},
+                "cacheOnly": cache_only_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(with_cache_miss(output_schema_for::<
+            FetchWrittenQuestionsResult,
+        >())),
     );
 
     push_tool(
@@ -239,110 +638,67 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
                 "topic": {"type": "string", "minLength": 1},
                 "billKeywords": {"type": "array", "items": {"type": "string"}},
                 "debateKeywords": {"type": "array", "items": {"type": "string"}},
-                "mpId": {"type": "integer", "minimum": 1},
+                "mpId": id_schema(IdKind::Member),
                 "includeStateOfParties": {"type": "boolean"},
-                "limit": {"type": "integer", "minimum": 1, "maximum": 10}
+                "limit": {"type": "integer", "minimum": 1, "maximum": MAX_RESULT_LIMIT},
+                "maxAgeSecs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Ignore cached results older than this; must not exceed the research cache TTL."
+                },
+                "cacheOnly": {
+                    "type": "boolean",
+                    "description": "Answer from cached briefs and upstream responses only; collections with nothing cached are skipped."
+                },
+                "locale": {
+                    "type": "string",
+                    "description": format!(
+                        "Language of the summary and advisories: one of {}. Defaults to MCP_DEFAULT_LOCALE; other values fall back to English with a note. Parliamentary data is not translated.",
+                        Locale::supported_list()
+                    )
+                }
             },
             "additionalProperties": false
         }),
-        Some(json!({
+        Some(with_signature(output_schema_for::<ResearchResponseDto>())),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "research.list_cached",
+        "Research: List cached briefs",
+        "List stored research briefs, a page at a time in storage order, with their topic, status, summary and citation URLs. Pass a page's nextCursor as cursor to read the next; the last page has none. Briefs older than RESEARCH_COMPACT_AFTER_DAYS are marked compacted: only this history remains and research.run rebuilds them. Requires the admin API key.",
+        json!({
             "type": "object",
             "properties": {
-                "summary": {"type": "string"},
-                "bills": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "title": {"type": "string"},
-                            "stage": {"type": ["string", "null"]},
-                            "lastUpdate": {"type": ["string", "null"]},
-                            "link": {"type": ["string", "null"], "format": "uri"}
-                        },
-                        "required": ["title"]
-                    }
-                },
-                "debates": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "title": {"type": "string"},
-                            "house": {"type": ["string", "null"]},
-                            "date": {"type": ["string", "null"]},
-                            "link": {"type": ["string", "null"], "format": "uri"},
-                            "highlight": {"type": ["string", "null"]}
-                        },
-                        "required": ["title"]
-                    }
-                },
-                "legislation": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "title": {"type": "string"},
-                            "year": {"type": ["string", "null"]},
-                            "type": {"type": ["string", "null"]},
-                            "uri": {"type": ["string", "null"], "format": "uri"}
-                        },
-                        "required": ["title"]
-                    }
-                },
-                "votes": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "divisionNumber": {"type": ["string", "null"]},
-                            "title": {"type": "string"},
-                            "date": {"type": ["string", "null"]},
-                            "ayes": {"type": ["integer", "null"]},
-                            "noes": {"type": ["integer", "null"]},
-                            "result": {"type": ["string", "null"]},
-                            "link": {"type": ["string", "null"], "format": "uri"}
-                        },
-                        "required": ["title"]
-                    }
-                },
-                "mpSpeeches": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "memberName": {"type": ["string", "null"]},
-                            "date": {"type": ["string", "null"]},
-                            "excerpt": {"type": ["string", "null"]},
-                            "source": {"type": ["string", "null"], "format": "uri"}
-                        }
-                    }
-                },
-                "stateOfParties": {
-                    "type": ["object", "null"],
-                    "properties": {
-                        "totalSeats": {"type": ["integer", "null"]},
-                        "lastUpdated": {"type": ["string", "null"]},
-                        "parties": {
-                            "type": "array",
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "name": {"type": "string"},
-                                    "seats": {"type": ["integer", "null"]}
-                                },
-                                "required": ["name"]
-                            }
-                        }
-                    }
-                },
-                "advisories": {
-                    "type": "array",
-                    "items": {"type": "string"}
-                },
-                "cached": {"type": "boolean"}
+                "limit": {"type": "integer", "minimum": 1, "maximum": MAX_LIST_CACHED_LIMIT},
+                "cursor": {
+                    "type": "string",
+                    "description": "nextCursor from the previous page."
+                }
             },
-            "required": ["summary", "bills", "debates", "legislation", "votes", "mpSpeeches", "advisories", "cached"]
-        })),
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<ListCachedResearchResult>()),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "research.suggest_keywords",
+        "Research: Suggest keywords",
+        "Suggest search keywords for a topic from the titles of recent bills, Commons debates and early day motions that match it, ranked by how many titles use each phrase and how specific it is. Each suggestion cites the title it came from and can be passed straight into research.run's billKeywords or debateKeywords. Read-only; results are cached for CACHE_TTL_RESEARCH.",
+        json!({
+            "type": "object",
+            "properties": {
+                "topic": {"type": "string", "minLength": 1},
+                "limit": {"type": "integer", "minimum": 1, "maximum": MAX_SUGGESTION_LIMIT}
+            },
+            "required": ["topic"],
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<KeywordSuggestionsResult>()),
     );
 
     push_tool(
@@ -356,19 +712,322 @@ pub fn build_tool_schemas() -> (Vec<ToolDefinition>, HashMap<String, Value>) {
             "properties": {},
             "additionalProperties": false
         }),
+        Some(output_schema_for::<CurrentDatetimeDto>()),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "utilities.verify_signature",
+        "Utilities: Verify response signature",
+        "Check that a signed tool response is unmodified and was signed by this server's key.",
+        json!({
+            "type": "object",
+            "required": ["payload"],
+            "properties": {
+                "payload": {"type": "object"},
+                "signature": signature_schema()
+            },
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<VerifySignatureDto>()),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "utilities.error_catalogue",
+        "Utilities: Error catalogue",
+        "List the tool error kinds and JSON-RPC error codes this server can return, with whether each is retryable and how a client should react. The version matches serverInfo.errorCatalogueVersion from initialize.",
+        json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<ErrorCatalogue>()),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "admin.tool_stats",
+        "Admin: Tool statistics",
+        "Report when each tool and upstream host last succeeded or failed and how many upstream bytes each has read, flagging stale integrations.",
+        json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }),
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "stalenessThresholdSecs": {"type": "integer"},
+                "tools": {"type": "object", "additionalProperties": {"$ref": "#/$defs/executionStats"}},
+                "upstreams": {"type": "object", "additionalProperties": {"$ref": "#/$defs/executionStats"}}
+            },
+            "required": ["stalenessThresholdSecs", "tools", "upstreams"],
+            "$defs": {
+                "executionStats": {
+                    "type": "object",
+                    "properties": {
+                        "lastSuccessAt": {"type": ["string", "null"]},
+                        "lastFailureAt": {"type": ["string", "null"]},
+                        "successCount": {"type": "integer"},
+                        "failureCount": {"type": "integer"},
+                        "upstreamBytes": {"type": "integer"},
+                        "largestPayloadBytes": {"type": "integer"},
+                        "stale": {"type": "boolean"}
+                    }
+                }
+            }
+        })),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "admin.usage_summary",
+        "Admin: Usage summary",
+        "Report how often research topics (as hashes) and constituencies were requested over a recent window. Entries requested fewer than USAGE_K_THRESHOLD times are only counted in suppressed. Requires the admin API key and USAGE_STATS_ENABLED.",
+        json!({
+            "type": "object",
+            "properties": {
+                "windowHours": {"type": "integer", "minimum": 1, "maximum": MAX_WINDOW_HOURS}
+            },
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<UsageSummary>()),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "admin.set_key_preferences",
+        "Admin: Set key preferences",
+        "Store default result formatting and research limit for an API key, applied to its tool calls when they do not say otherwise. Replaces any earlier preferences; an empty preferences object clears them. Requires the admin API key.",
+        json!({
+            "type": "object",
+            "required": ["apiKey", "preferences"],
+            "properties": {
+                "apiKey": {"type": "string", "minLength": 1},
+                "preferences": key_preferences_schema()
+            },
+            "additionalProperties": false
+        }),
         Some(json!({
             "type": "object",
             "properties": {
-                "utc": {"type": "string"},
-                "local": {"type": "string"}
+                "preferences": key_preferences_schema()
             },
-            "required": ["utc", "local"]
+            "required": ["preferences"]
         })),
     );
 
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "admin.replay_call",
+        "Admin: Replay audited call",
+        "Re-run a tool call recorded in the audit log, e.g. after an upstream failure. Postcodes are not stored, so any fields listed in the entry's redactedFields must be supplied again in redactedValues. Requires the admin API key.",
+        json!({
+            "type": "object",
+            "required": ["auditId"],
+            "properties": {
+                "auditId": {"type": "string", "minLength": 1},
+                "redactedValues": {
+                    "type": "object",
+                    "description": "Values for the original entry's redactedFields, keyed by the same JSON pointers (e.g. \"/postcode\").",
+                    "additionalProperties": true
+                }
+            },
+            "additionalProperties": false
+        }),
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "tool": {"type": "string"},
+                "originalAuditId": {"type": "string"},
+                "replayAuditId": {"type": "string"},
+                "outcome": {"type": "string", "enum": ["success", "rejected", "error"]},
+                "result": {},
+                "error": {
+                    "type": ["object", "null"],
+                    "properties": {
+                        "kind": {"type": "string"},
+                        "message": {"type": "string"},
+                        "retryable": {"type": "boolean"}
+                    }
+                }
+            },
+            "required": ["tool", "originalAuditId", "replayAuditId", "outcome"]
+        })),
+    );
+
+    push_tool(
+        &mut definitions,
+        &mut input_schemas,
+        "admin.refresh_reference_data",
+        "Admin: Refresh reference data",
+        "Download the reference datasets listed in REFERENCE_DATA_MANIFEST_URL, check each against the manifest's checksum and row count, and swap the ones that pass into REFERENCE_DATA_DIR, where they take precedence over the compiled-in copies. A dataset that fails keeps its current copy. Requires the admin API key.",
+        json!({
+            "type": "object",
+            "properties": {
+                "datasets": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": REFERENCE_DATASETS.iter().map(|dataset| dataset.name).collect::<Vec<_>>()
+                    },
+                    "minItems": 1,
+                    "uniqueItems": true,
+                    "description": "Datasets to refresh; all of them when omitted."
+                }
+            },
+            "additionalProperties": false
+        }),
+        Some(output_schema_for::<RefreshReport>()),
+    );
+
     (definitions, input_schemas)
 }
 
+/// Remove the `null` properties `schema` does not require, in `arguments` and in any
+/// nested object the schema describes, and return their JSON pointers. The argument DTOs
+/// read a `null` optional as absent, so the schema check does too.
+pub fn drop_null_optionals(schema: &Value, arguments: &mut Value) -> Vec<String> {
+    let mut dropped = Vec::new();
+    drop_nulls_at(schema, arguments, String::new(), &mut dropped);
+    dropped
+}
+
+fn drop_nulls_at(schema: &Value, value: &mut Value, pointer: String, dropped: &mut Vec<String>) {
+    let (Some(object), Some(properties)) =
+        (value.as_object_mut(), schema["properties"].as_object())
+    else {
+        return;
+    };
+    let required = |name: &str| {
+        schema["required"]
+            .as_array()
+            .is_some_and(|required| required.iter().any(|entry| entry == name))
+    };
+
+    object.retain(|name, child| {
+        let keep = !child.is_null() || required(name) || !properties.contains_key(name);
+        if !keep {
+            dropped.push(format!("{pointer}/{name}"));
+        }
+        keep
+    });
+    for (name, child) in object.iter_mut() {
+        if let Some(child_schema) = properties.get(name) {
+            drop_nulls_at(child_schema, child, format!("{pointer}/{name}"), dropped);
+        }
+    }
+}
+
+/// The `outputSchema` of a tool returning `T`, generated from its serde representation so
+/// the schema cannot drift from the DTO.
+fn output_schema_for<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| settings.meta_schema = None)
+        .into_generator();
+    serde_json::to_value(generator.into_root_schema_for::<T>())
+        .expect("generated schemas serialise to JSON")
+}
+
+/// Signed tools gain a top-level `signature` when `RESPONSE_SIGNING_KEY` is configured.
+fn with_signature(mut schema: Value) -> Value {
+    if let Some(properties) = schema["properties"].as_object_mut() {
+        properties.insert("signature".to_string(), signature_schema());
+    }
+    schema
+}
+
+/// Tools taking `cacheOnly` may instead answer with an empty cache-miss result. Root
+/// definitions move to the wrapper so the original schema's `$ref`s still resolve.
+fn with_cache_miss(mut schema: Value) -> Value {
+    let mut wrapper = serde_json::Map::new();
+    if let Some(object) = schema.as_object_mut() {
+        for key in ["definitions", "$defs"] {
+            if let Some(definitions) = object.remove(key) {
+                wrapper.insert(key.to_string(), definitions);
+            }
+        }
+    }
+    wrapper.insert(
+        "anyOf".to_string(),
+        json!([
+            schema,
+            {
+                "type": "object",
+                "required": ["items", "cacheOnly", "cacheMiss"],
+                "properties": {
+                    "items": {"type": "array", "maxItems": 0},
+                    "cacheOnly": {"const": true},
+                    "cacheMiss": {"const": true}
+                }
+            }
+        ]),
+    );
+    Value::Object(wrapper)
+}
+
+fn cache_only_schema() -> Value {
+    json!({
+        "type": "boolean",
+        "description": "Answer from the memory and sled caches only, never calling upstream; a miss returns {items: [], cacheOnly: true, cacheMiss: true}."
+    })
+}
+
+fn fields_schema() -> Value {
+    json!({
+        "type": "array",
+        "description": "Dot paths (e.g. \"latestHouseMembership.membershipFrom\") to keep on each item; missing paths come back as null.",
+        "items": {"type": "string", "minLength": 1},
+        "minItems": 1,
+        "maxItems": 50
+    })
+}
+
+fn key_preferences_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "textContent": {
+                "type": "string",
+                "enum": ["pretty", "compact", "markdown"],
+                "description": "How the text content block renders the result: indented JSON, single-line JSON, or indented JSON in a fenced code block."
+            },
+            "researchLimit": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": MAX_RESULT_LIMIT,
+                "description": "limit for research.run calls that omit it."
+            },
+            "includeMeta": {
+                "type": "boolean",
+                "description": "Whether results carry _meta (correlation id, duration, cache status)."
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn signature_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "algorithm": {"type": "string", "enum": ["ed25519"]},
+            "signedAt": {"type": "string"},
+            "payloadSha256": {"type": "string"},
+            "signature": {"type": "string"},
+            "publicKey": {"type": "string"}
+        },
+        "required": ["algorithm", "signedAt", "payloadSha256", "signature", "publicKey"]
+    })
+}
+
 fn push_tool(
     definitions: &mut Vec<ToolDefinition>,
     input_schemas: &mut HashMap<String, Value>,
@@ -385,5 +1044,18 @@ fn push_tool(
         description: description.to_string(),
         input_schema,
         output_schema,
+        annotations: None,
     });
 }
+
+fn policy_area_description() -> String {
+    let areas: Vec<String> = PolicyAreaClassifier::builtin()
+        .areas()
+        .iter()
+        .map(|area| format!("{} ({})", area.id, area.label))
+        .collect();
+    format!(
+        "Tally divisions by policy area. Built-in areas: {}; unmatched divisions are counted under \"other\". Deployments may replace the mapping with POLICY_AREAS_PATH.",
+        areas.join(", ")
+    )
+}
@@ -1,54 +1,334 @@
 use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
-use jsonschema::JSONSchema;
-use serde_json::{Value, json};
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::{JSONSchema, ValidationError};
+use serde_json::{Map, Value, json};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
-use crate::core::error::AppError;
+use crate::config::{ADMIN_KEY_NAME, AppConfig, ToolFilterConfig};
+use crate::core::audit::{AuditEntry, AuditLog, AuditOutcome, escape_pointer, redact_arguments};
+use crate::core::build_info::BuildInfo;
+use crate::core::byte_budget::{ByteMeter, metered};
+use crate::core::call_context::{CallContext, with_call_context};
+use crate::core::client_log::with_client_log;
+use crate::core::concurrency::ConcurrencyLimit;
+use crate::core::correlation::{correlation_id_for, current_correlation_id, with_correlation_id};
+use crate::core::error::{AppError, panic_message};
+use crate::core::http_client::redact_url;
+use crate::core::metrics::{MetricsRegistry, ScanMetrics};
+use crate::core::notifications::{NotificationSink, ProgressReporter};
+use crate::core::preferences::{KeyPreferences, PreferenceStore, TextContentMode};
+use crate::core::signing::ResponseSigner;
+use crate::core::stats::{StatsRegistry, now_timestamp};
+use crate::core::usage::{DEFAULT_WINDOW_HOURS, UsageCollector};
+use crate::features::audit::ReplayCallArgs;
+use crate::features::mcp::capabilities::ServerCapabilities;
+use crate::features::mcp::chunked::{ChunkDelivery, VotingRecordCsv, deliver_chunks};
+use crate::features::mcp::completions::{CachedIndex, CompletionRegistry, rank_fuzzy};
 use crate::features::mcp::dto::{
-    CallToolParams, InitializeParams, JsonRpcError, JsonRpcErrorResponse, JsonRpcRequest,
-    JsonRpcSuccess, ListToolsParams, ToolCallResult, ToolContent, ToolDefinition, ToolListResult,
+    CallToolParams, CompleteParams, CompletionResult, InitializeParams, JsonRpcError,
+    JsonRpcErrorResponse, JsonRpcRequest, JsonRpcSuccess, ListToolsParams, SetLevelParams,
+    ToolCallResult, ToolContent, ToolDefinition, ToolErrorData, ToolListResult,
 };
-use crate::features::mcp::schemas::build_tool_schemas;
+use crate::features::mcp::errors::{JsonRpcErrorCode, ToolErrorKind, error_catalogue};
+use crate::features::mcp::ids::resolve_id_arguments;
+use crate::features::mcp::links::extract_resource_links;
+use crate::features::mcp::schemas::{build_tool_schemas, drop_null_optionals};
+use crate::features::mcp::session::{SessionRef, SessionRegistry};
 use crate::features::parliament::{
-    FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs, FetchMpActivityArgs,
-    FetchMpVotingRecordArgs, LookupConstituencyArgs, ParliamentClient, SearchUkLawArgs,
-    handle_fetch_bills, handle_fetch_core_dataset, handle_fetch_legislation,
-    handle_fetch_mp_activity, handle_fetch_mp_voting_record, handle_lookup_constituency_offline,
-    handle_search_uk_law,
+    ExportVotingRecordArgs, FetchBillDetailArgs, FetchBillDivisionsArgs, FetchBillsArgs,
+    FetchCommitteesArgs, FetchCoreDatasetArgs, FetchDivisionDetailArgs, FetchEdmsArgs,
+    FetchLegislationArgs, FetchLegislationTextArgs, FetchMemberRolesArgs, FetchMembersBulkArgs,
+    FetchMpActivityArgs, FetchMpVotingRecordArgs, FetchOralQuestionsArgs,
+    FetchStatutoryInstrumentsArgs, FetchWrittenQuestionsArgs, ListConstituenciesArgs,
+    LookupConstituencyArgs, ParliamentClient, SearchHansardArgs, SearchUkLawArgs,
+    handle_fetch_bill_detail, handle_fetch_bill_divisions, handle_fetch_bills,
+    handle_fetch_committees, handle_fetch_core_dataset, handle_fetch_division_detail,
+    handle_fetch_edms, handle_fetch_legislation, handle_fetch_legislation_text,
+    handle_fetch_member_roles, handle_fetch_members_bulk, handle_fetch_mp_activity,
+    handle_fetch_mp_voting_record, handle_fetch_oral_questions, handle_fetch_statutory_instruments,
+    handle_fetch_written_questions, handle_list_constituencies, handle_lookup_constituency_offline,
+    handle_search_hansard, handle_search_uk_law,
 };
-use crate::features::research::{ResearchRequestDto, ResearchService, handle_run_research};
-use crate::features::utilities::{DateTimeService, handle_current_datetime};
+use crate::features::preferences::SetKeyPreferencesArgs;
+use crate::features::reference_data::RefreshReferenceDataArgs;
+use crate::features::research::{
+    ListCachedResearchArgs, ResearchRequestDto, ResearchRunContext, ResearchService,
+    SuggestKeywordsArgs, handle_list_cached_research, handle_run_research, handle_suggest_keywords,
+};
+use crate::features::usage::UsageSummaryArgs;
+use crate::features::utilities::{
+    DateTimeService, VerifySignatureArgs, handle_current_datetime, handle_verify_signature,
+};
+use crate::server::{KeyRing, SharedKeyRing};
 
 const JSON_RPC_VERSION: &str = "2.0";
 const SUPPORTED_PROTOCOL_VERSIONS: &[&str] =
     &["2025-06-26", "2025-06-18", "2025-03-26", "1.1", "1.0"];
 const PROTOCOL_VERSION_1_1_ALIASES: &[&str] = &["2025-06-26", "2025-06-18", "2025-03-26", "1.1"];
+/// Tools whose structured output is signed when `RESPONSE_SIGNING_KEY` is configured.
+const SIGNED_TOOLS: &[&str] = &["research.run"];
+/// Tools that only callers presenting `ADMIN_API_KEY` may run.
+const ADMIN_ONLY_TOOLS: &[&str] = &[
+    "admin.replay_call",
+    "admin.usage_summary",
+    "admin.set_key_preferences",
+    "admin.refresh_reference_data",
+    "research.list_cached",
+];
+/// `tools/call` `_meta` fields that choose how the result is rendered; never echoed.
+const FORMAT_META_FIELDS: &[&str] = &["textContent", "includeMeta"];
+/// Longest string accepted anywhere in `tools/call` arguments; no tool needs more.
+const MAX_ARGUMENT_STRING_BYTES: usize = 64 * 1024;
+/// Server-initiated notifications buffered per subscriber before the slowest one lags.
+const SERVER_NOTIFICATION_CAPACITY: usize = 16;
+/// Methods reported under their own name in request metrics; anything else is `other`.
+const METRIC_METHODS: &[&str] = &[
+    "initialize",
+    "notifications/initialized",
+    "initialized",
+    "list_tools",
+    "tools/list",
+    "call_tool",
+    "tools/call",
+    "notifications/cancelled",
+    "completion/complete",
+    "ping",
+    "logging/setLevel",
+    "admin/tools/reload",
+    "session/reset",
+];
+
+/// Per-request transport details threaded from the HTTP layer into the service.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub protocol_version: Option<String>,
+    pub notifications: Option<NotificationSink>,
+    /// The `x-api-key` presented with the request.
+    pub api_key: Option<String>,
+    /// The configured name of that key, recorded in the audit log.
+    pub key_name: Option<String>,
+    /// The `Mcp-Session-Id` presented with the request, or the one issued to it.
+    pub session_id: Option<String>,
+    /// Set when the server issued `session_id` to this `initialize`; the session then also
+    /// serves the key's requests that carry no `Mcp-Session-Id`.
+    pub issued_session: bool,
+    /// The `X-Request-Id` presented with the request, reused as the tool call's
+    /// correlation id when it is well-formed.
+    pub request_id: Option<String>,
+}
+
+impl RequestContext {
+    pub fn new(protocol_version: Option<String>) -> Self {
+        Self {
+            protocol_version,
+            notifications: None,
+            api_key: None,
+            key_name: None,
+            session_id: None,
+            issued_session: false,
+            request_id: None,
+        }
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_key_name(mut self, key_name: impl Into<String>) -> Self {
+        self.key_name = Some(key_name.into());
+        self
+    }
+
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Address a session the server has just issued; see [`Self::issued_session`].
+    pub fn with_issued_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self.issued_session = true;
+        self
+    }
+
+    /// The session this request addresses, scoped to its key name.
+    pub fn session(&self) -> SessionRef<'_> {
+        SessionRef {
+            owner: self.key_name.as_deref(),
+            id: self.session_id.as_deref(),
+        }
+    }
+
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    pub fn with_notifications(mut self, sink: NotificationSink) -> Self {
+        self.notifications = Some(sink);
+        self
+    }
+}
 
 pub struct McpService {
+    config: Arc<AppConfig>,
+    stats: Arc<StatsRegistry>,
     parliament_client: Arc<ParliamentClient>,
     research_service: Arc<ResearchService>,
     utilities_service: Arc<DateTimeService>,
+    /// Every tool the server knows; `tool_filter` decides which are active.
     tool_schemas: Vec<ToolDefinition>,
     argument_validators: HashMap<String, JSONSchema>,
-    negotiated_protocol: Mutex<Option<String>>,
-    initialize_called: AtomicBool,
-    client_ready: AtomicBool,
+    /// Raw input schemas, for suggesting valid names in place of unknown arguments.
+    argument_schemas: HashMap<String, Value>,
+    output_validators: HashMap<String, JSONSchema>,
+    tool_filter: RwLock<ToolFilterConfig>,
+    /// Set once a transport that can push server-initiated notifications is mounted.
+    push_transport: AtomicBool,
+    server_notifications: broadcast::Sender<Value>,
+    completions: CompletionRegistry,
+    sessions: SessionRegistry,
+    /// Requests that are still being answered, keyed by session and JSON-encoded id.
+    in_flight: Mutex<HashMap<InFlightKey, CancellationToken>>,
+    response_signer: Option<Arc<ResponseSigner>>,
+    audit_log: Option<Arc<AuditLog>>,
+    usage: Option<Arc<UsageCollector>>,
+    preferences: Option<Arc<PreferenceStore>>,
+    metrics: MetricsRegistry,
+    key_ring: SharedKeyRing,
+    /// Caps concurrent `research.run` executions, which each fan out to several APIs.
+    research_limit: ConcurrencyLimit,
+    build: BuildInfo,
+}
+
+/// What is known about a `tools/call` before it runs; completed by `finish_call`.
+struct PendingCall {
+    timestamp: u64,
+    started: Instant,
+    tool: String,
+    /// Only computed when an audit log is attached.
+    audited: Option<AuditedArguments>,
+    correlation_id: String,
+    key_name: Option<String>,
+}
+
+/// The `_meta` object attached to every `tools/call` result.
+struct ResultMeta {
+    correlation_id: String,
+    /// Trace fields from the request's `_meta`, echoed back unchanged.
+    echoed: Map<String, Value>,
+    duration_ms: u64,
+    cached: Option<bool>,
+}
+
+impl ResultMeta {
+    fn new(pending: &PendingCall, echoed: Map<String, Value>, call: &CallContext) -> Self {
+        Self {
+            correlation_id: pending.correlation_id.clone(),
+            echoed,
+            duration_ms: pending.started.elapsed().as_millis() as u64,
+            cached: call.cached(),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let mut meta = self.echoed.clone();
+        meta.insert("correlationId".to_string(), json!(self.correlation_id));
+        meta.insert("durationMs".to_string(), json!(self.duration_ms));
+        if let Some(cached) = self.cached {
+            meta.insert("cached".to_string(), json!(cached));
+        }
+        Value::Object(meta)
+    }
+}
+
+/// How a successful `tools/call` result is rendered: the request's `_meta.textContent` and
+/// `_meta.includeMeta` when given, otherwise the caller's key preferences.
+#[derive(Debug, Clone, Copy)]
+struct ResultFormat {
+    text_content: TextContentMode,
+    include_meta: bool,
+}
+
+impl ResultFormat {
+    fn resolve(meta: Option<&Value>, preferences: &KeyPreferences) -> Self {
+        let requested = |field: &str| meta.and_then(|meta| meta.get(field));
+        Self {
+            text_content: requested("textContent")
+                .and_then(|value| serde_json::from_value(value.clone()).ok())
+                .or(preferences.text_content)
+                .unwrap_or_default(),
+            include_meta: requested("includeMeta")
+                .and_then(Value::as_bool)
+                .or(preferences.include_meta)
+                .unwrap_or(true),
+        }
+    }
+
+    fn render(&self, payload: &Value) -> serde_json::Result<String> {
+        self.text_content.render(payload)
+    }
+}
+
+struct AuditedArguments {
+    sha256: String,
+    redacted: Value,
+    redacted_fields: Vec<String>,
+}
+
+impl AuditedArguments {
+    fn new(log: &AuditLog, arguments: &Value) -> Self {
+        let (redacted, redacted_fields) = redact_arguments(arguments);
+        Self {
+            sha256: log.hash_arguments(arguments),
+            redacted,
+            redacted_fields,
+        }
+    }
+}
+
+/// `(key name, session id, JSON-encoded request id)`. Encoding keeps `1` and `"1"` distinct.
+type InFlightKey = (Option<String>, String, String);
+
+/// Removes a request from the in-flight registry when its handler finishes or is dropped.
+struct InFlightGuard<'a> {
+    registry: &'a Mutex<HashMap<InFlightKey, CancellationToken>>,
+    key: InFlightKey,
+    cancellation: CancellationToken,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut guard) = self.registry.lock() {
+            guard.remove(&self.key);
+        }
+    }
 }
 
 impl McpService {
     pub fn new(
+        config: Arc<AppConfig>,
         parliament_client: Arc<ParliamentClient>,
         research_service: Arc<ResearchService>,
+        stats: Arc<StatsRegistry>,
     ) -> Self {
         let (tool_schemas, input_schemas) = build_tool_schemas();
         let mut argument_validators = HashMap::new();
 
-        for (name, schema) in input_schemas {
-            match JSONSchema::compile(&schema) {
+        for (name, schema) in &input_schemas {
+            match JSONSchema::compile(schema) {
                 Ok(compiled) => {
-                    argument_validators.insert(name, compiled);
+                    argument_validators.insert(name.clone(), compiled);
                 }
                 Err(err) => {
                     tracing::error!(
@@ -59,61 +339,354 @@ impl McpService {
                 }
             }
         }
+
+        let mut output_validators = HashMap::new();
+        for definition in &tool_schemas {
+            let Some(schema) = definition.output_schema.as_ref() else {
+                continue;
+            };
+            match JSONSchema::compile(schema) {
+                Ok(compiled) => {
+                    output_validators.insert(definition.name.clone(), compiled);
+                }
+                Err(err) => {
+                    tracing::error!(
+                        tool = %definition.name,
+                        error = %err,
+                        "failed to compile JSON schema for tool output"
+                    );
+                }
+            }
+        }
         let utilities_service = Arc::new(DateTimeService::new());
+        let (server_notifications, _) = broadcast::channel(SERVER_NOTIFICATION_CAPACITY);
+        let key_ring = KeyRing::shared(&config);
+        let research_limit = ConcurrencyLimit::new(
+            config.max_concurrent_research,
+            Duration::from_secs(config.request_queue_wait_secs),
+        );
 
         Self {
+            tool_filter: RwLock::new(config.tool_filter.clone()),
+            push_transport: AtomicBool::new(false),
+            server_notifications,
+            config,
+            stats,
             parliament_client,
             research_service,
             utilities_service,
             tool_schemas,
             argument_validators,
-            negotiated_protocol: Mutex::new(None),
-            initialize_called: AtomicBool::new(false),
-            client_ready: AtomicBool::new(false),
+            argument_schemas: input_schemas,
+            output_validators,
+            completions: CompletionRegistry::new(),
+            sessions: SessionRegistry::new(),
+            in_flight: Mutex::new(HashMap::new()),
+            response_signer: None,
+            audit_log: None,
+            usage: None,
+            preferences: None,
+            metrics: MetricsRegistry::new(),
+            key_ring,
+            research_limit,
+            build: BuildInfo::current(),
         }
     }
 
-    pub fn negotiated_protocol_version(&self) -> Option<String> {
-        match self.negotiated_protocol.lock() {
-            Ok(guard) => guard.clone(),
-            Err(error) => {
-                tracing::error!(error = %error, "protocol version mutex poisoned");
-                None
-            }
+    pub fn with_response_signer(mut self, signer: Arc<ResponseSigner>) -> Self {
+        self.response_signer = Some(signer);
+        self
+    }
+
+    /// Record every `tools/call` in `log`.
+    pub fn with_audit_log(mut self, log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
+    /// Count research topics and constituency lookups for `admin.usage_summary`.
+    pub fn with_usage_collector(mut self, usage: Arc<UsageCollector>) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Apply per-key defaults stored with `admin.set_key_preferences`.
+    pub fn with_preference_store(mut self, preferences: Arc<PreferenceStore>) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+
+    pub fn audit_log(&self) -> Option<&Arc<AuditLog>> {
+        self.audit_log.as_ref()
+    }
+
+    pub fn metrics(&self) -> &MetricsRegistry {
+        &self.metrics
+    }
+
+    pub fn research_scan_metrics(&self) -> &ScanMetrics {
+        self.research_service.scan_metrics()
+    }
+
+    /// Slots for `research.run` (`MAX_CONCURRENT_RESEARCH`).
+    pub fn research_limit(&self) -> &ConcurrencyLimit {
+        &self.research_limit
+    }
+
+    /// The accepted API keys, shared with the auth middleware and the key file reloader.
+    pub fn key_ring(&self) -> SharedKeyRing {
+        self.key_ring.clone()
+    }
+
+    /// Advertise `tools.listChanged` to clients initialized from now on. Called by transports
+    /// that forward [`McpService::subscribe_notifications`] to the client.
+    pub fn enable_push_notifications(&self) {
+        self.push_transport.store(true, Ordering::SeqCst);
+    }
+
+    /// Server-initiated JSON-RPC notifications such as `notifications/tools/list_changed`.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<Value> {
+        self.server_notifications.subscribe()
+    }
+
+    /// Names of the tools currently exposed through `tools/list` and `tools/call`.
+    pub fn active_tools(&self) -> Vec<String> {
+        let filter = read_lock(&self.tool_filter);
+        self.tool_schemas
+            .iter()
+            .filter(|definition| filter.allows(&definition.name))
+            .map(|definition| definition.name.clone())
+            .collect()
+    }
+
+    /// The running build (crate name, version, git commit, build time), the MCP protocol
+    /// versions it negotiates and its active tools. Served by `/api/version` and embedded in
+    /// `initialize`'s `serverInfo`.
+    pub fn build_info(&self) -> &BuildInfo {
+        &self.build
+    }
+
+    pub fn version_info(&self) -> Value {
+        let mut info = json!(self.build);
+        info["supportedProtocolVersions"] = json!(SUPPORTED_PROTOCOL_VERSIONS);
+        info["tools"] = json!(self.active_tools());
+        info
+    }
+
+    /// Replace the tool filter at runtime. Returns whether the active tool set changed, in
+    /// which case subscribers receive `notifications/tools/list_changed`.
+    pub fn reload_tools(&self, filter: ToolFilterConfig) -> Result<bool, AppError> {
+        let known: Vec<String> = self
+            .tool_schemas
+            .iter()
+            .map(|definition| definition.name.clone())
+            .collect();
+        filter.validate(&known)?;
+
+        let before = self.active_tools();
+        *self
+            .tool_filter
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = filter;
+        let after = self.active_tools();
+
+        let changed = before != after;
+        if changed {
+            tracing::info!(tools = ?after, "active tool set changed");
+            // No subscribers is not an error: POST-only clients simply re-list on demand.
+            let _ = self.server_notifications.send(json!({
+                "jsonrpc": JSON_RPC_VERSION,
+                "method": "notifications/tools/list_changed",
+            }));
         }
+        Ok(changed)
     }
 
+    /// What `initialize` advertises, from the server's current state: `tools.listChanged`
+    /// once a push transport is mounted, and `completions` while at least one active tool
+    /// has completion candidates.
+    pub fn server_capabilities(&self) -> ServerCapabilities {
+        let mut capabilities = ServerCapabilities::new()
+            .with_tools(self.push_transport.load(Ordering::SeqCst))
+            .with_logging();
+        if self
+            .active_tools()
+            .iter()
+            .any(|tool| self.completions.covers_tool(tool))
+        {
+            capabilities = capabilities.with_completions();
+        }
+        capabilities
+    }
+
+    fn is_admin(&self, context: &RequestContext) -> bool {
+        self.config.admin_api_key.is_some()
+            && self.config.admin_api_key.as_deref() == context.api_key.as_deref()
+    }
+
+    fn tool_allowed(&self, tool_name: &str) -> bool {
+        read_lock(&self.tool_filter).allows(tool_name)
+    }
+
+    pub fn negotiated_protocol_version(&self, session: SessionRef) -> Option<String> {
+        self.sessions
+            .get(session)
+            .and_then(|session| session.negotiated_protocol)
+    }
+
+    pub fn stats(&self) -> &StatsRegistry {
+        &self.stats
+    }
+
+    pub fn describe_stats(&self) -> Value {
+        self.stats
+            .describe(self.config.tool_staleness_threshold, now_timestamp())
+    }
+
+    /// Answer one request. Its id is held for the session until the response is produced;
+    /// a second request reusing it meanwhile is rejected with -32600.
     pub async fn handle_jsonrpc(
         &self,
         request: JsonRpcRequest,
-        header_protocol_version: Option<String>,
+        context: RequestContext,
     ) -> Result<Option<JsonRpcSuccess>, JsonRpcErrorResponse> {
+        let method = metric_method_label(&request.method);
+        let response = match self.claim_request_id(request.id.as_ref(), &context) {
+            Ok(in_flight) => {
+                let cancellation = in_flight
+                    .as_ref()
+                    .map(|guard| guard.cancellation.clone())
+                    .unwrap_or_default();
+                self.dispatch_jsonrpc(request, context, cancellation).await
+            }
+            Err(error) => Err(error),
+        };
+        self.metrics.record_request(method, response.is_ok());
+        response
+    }
+
+    /// Answer a JSON-RPC batch. Every id is claimed before any request starts, so
+    /// duplicates within the batch are rejected deterministically; the rest run
+    /// concurrently and their responses are returned in request order, whatever order
+    /// they complete in. Notifications produce no entry.
+    pub async fn handle_batch(&self, batch: Vec<Value>, context: RequestContext) -> Vec<Value> {
+        let mut responses: Vec<Option<Value>> = vec![None; batch.len()];
+        let mut pending = FuturesUnordered::new();
+
+        for (index, entry) in batch.into_iter().enumerate() {
+            let request = match serde_json::from_value::<JsonRpcRequest>(entry) {
+                Ok(request) => request,
+                Err(error) => {
+                    self.metrics.record_request("other", false);
+                    responses[index] = Some(json!(self.invalid_request_response(
+                        None,
+                        JsonRpcErrorCode::InvalidRequest,
+                        format!("invalid request in batch: {error}"),
+                    )));
+                    continue;
+                }
+            };
+            let method = metric_method_label(&request.method);
+            let in_flight = match self.claim_request_id(request.id.as_ref(), &context) {
+                Ok(in_flight) => in_flight,
+                Err(error) => {
+                    self.metrics.record_request(method, false);
+                    responses[index] = Some(json!(error));
+                    continue;
+                }
+            };
+            let context = context.clone();
+            pending.push(Box::pin(async move {
+                let cancellation = in_flight
+                    .as_ref()
+                    .map(|guard| guard.cancellation.clone())
+                    .unwrap_or_default();
+                let response = self.dispatch_jsonrpc(request, context, cancellation).await;
+                drop(in_flight);
+                self.metrics.record_request(method, response.is_ok());
+                (index, response)
+            }));
+        }
+
+        while let Some((index, response)) = pending.next().await {
+            responses[index] = match response {
+                Ok(Some(success)) => Some(json!(success)),
+                Ok(None) => None,
+                Err(error) => Some(json!(error)),
+            };
+        }
+        responses.into_iter().flatten().collect()
+    }
+
+    async fn dispatch_jsonrpc(
+        &self,
+        request: JsonRpcRequest,
+        context: RequestContext,
+        cancellation: CancellationToken,
+    ) -> Result<Option<JsonRpcSuccess>, JsonRpcErrorResponse> {
+        let header_protocol_version = context.protocol_version.clone();
         let JsonRpcRequest {
             jsonrpc,
             id,
             method,
             params,
+            extra,
         } = request;
 
         if jsonrpc != JSON_RPC_VERSION {
             return Err(self.invalid_request_response(
                 id,
-                -32600,
+                JsonRpcErrorCode::InvalidRequest,
                 format!("unsupported jsonrpc version: {jsonrpc}"),
             ));
         }
 
+        if !extra.is_empty() {
+            let members: Vec<&str> = extra.keys().map(String::as_str).collect();
+            for member in &members {
+                self.metrics.record_unknown_field("request", member);
+            }
+            if self.config.strict {
+                return Err(self.invalid_request_response(
+                    id,
+                    JsonRpcErrorCode::InvalidRequest,
+                    format!(
+                        "unknown request {} {}; a JSON-RPC request only has jsonrpc, id, method and params",
+                        if members.len() == 1 { "member" } else { "members" },
+                        quoted_list(&members)
+                    ),
+                ));
+            }
+            tracing::debug!(
+                method,
+                members = %members.join(", "),
+                "ignoring unknown JSON-RPC request members"
+            );
+        }
+
+        if method != "initialize" && self.sessions.is_terminated(context.session()) {
+            if id.is_none() {
+                tracing::debug!(method, "ignoring notification for a terminated session");
+                return Ok(None);
+            }
+            return Err(self.invalid_request_response(
+                id,
+                JsonRpcErrorCode::NotInitialized,
+                "session terminated; call initialize to start a new session".to_string(),
+            ));
+        }
+
         match method.as_str() {
             "initialize" => {
                 let request_id = self.require_request_id(&id, "initialize")?;
                 let header_version = header_protocol_version.clone().ok_or_else(|| {
                     self.invalid_request_response(
                         Some(request_id.clone()),
-                        -32600,
+                        JsonRpcErrorCode::InvalidRequest,
                         "initialize requires MCP-Protocol-Version header".to_string(),
                     )
                 })?;
-                self.handle_initialize(request_id, params, header_version)
+                self.handle_initialize(request_id, params, header_version, &context)
                     .await
                     .map(Some)
             }
@@ -122,35 +695,107 @@ impl McpService {
                 // to maintain compatibility with clients that omit headers on notifications. If the
                 // header is present, still validate it against the negotiated version.
                 if header_protocol_version.is_some() {
-                    self.ensure_protocol_header(header_protocol_version.as_deref(), &id)?;
+                    self.ensure_protocol_header(header_protocol_version.as_deref(), &id, &context)?;
                 }
-                self.handle_initialized_notification(method.as_str());
+                self.handle_initialized_notification(method.as_str(), &context);
                 Ok(None)
             }
             "list_tools" | "tools/list" => {
                 let request_id = self.require_request_id(&id, "tools/list")?;
                 let id_for_header = Some(request_id.clone());
-                self.ensure_protocol_header(header_protocol_version.as_deref(), &id_for_header)?;
-                self.ensure_ready(Some(request_id.clone()))?;
+                self.ensure_protocol_header(
+                    header_protocol_version.as_deref(),
+                    &id_for_header,
+                    &context,
+                )?;
+                self.ensure_ready(Some(request_id.clone()), &context)?;
                 self.handle_list_tools(request_id, params).await.map(Some)
             }
             "call_tool" | "tools/call" => {
                 let request_id = self.require_request_id(&id, "tools/call")?;
                 let id_for_header = Some(request_id.clone());
-                self.ensure_protocol_header(header_protocol_version.as_deref(), &id_for_header)?;
-                self.ensure_ready(Some(request_id.clone()))?;
-                self.handle_call_tool(request_id, params).await.map(Some)
+                self.ensure_protocol_header(
+                    header_protocol_version.as_deref(),
+                    &id_for_header,
+                    &context,
+                )?;
+                self.ensure_ready(Some(request_id.clone()), &context)?;
+                self.handle_call_tool(request_id, params, &context, cancellation)
+                    .await
+            }
+            "notifications/cancelled" => {
+                if header_protocol_version.is_some() {
+                    self.ensure_protocol_header(header_protocol_version.as_deref(), &id, &context)?;
+                }
+                self.handle_cancelled_notification(params, &context);
+                Ok(None)
+            }
+            "completion/complete" => {
+                let request_id = self.require_request_id(&id, "completion/complete")?;
+                let id_for_header = Some(request_id.clone());
+                self.ensure_protocol_header(
+                    header_protocol_version.as_deref(),
+                    &id_for_header,
+                    &context,
+                )?;
+                self.ensure_ready(Some(request_id.clone()), &context)?;
+                self.handle_complete(request_id, params).await.map(Some)
             }
             "ping" => {
                 let request_id = self.require_request_id(&id, "ping")?;
                 let id_for_header = Some(request_id.clone());
-                self.ensure_protocol_header(header_protocol_version.as_deref(), &id_for_header)?;
-                self.ensure_initialized(Some(request_id.clone()))?;
+                self.ensure_protocol_header(
+                    header_protocol_version.as_deref(),
+                    &id_for_header,
+                    &context,
+                )?;
+                self.ensure_initialized(Some(request_id.clone()), &context)?;
                 self.handle_ping(request_id).map(Some)
             }
-            other => {
-                Err(self.invalid_request_response(id, -32601, format!("unknown method: {other}")))
+            "logging/setLevel" => {
+                let request_id = self.require_request_id(&id, "logging/setLevel")?;
+                let id_for_header = Some(request_id.clone());
+                self.ensure_protocol_header(
+                    header_protocol_version.as_deref(),
+                    &id_for_header,
+                    &context,
+                )?;
+                self.ensure_ready(Some(request_id.clone()), &context)?;
+                self.handle_set_log_level(request_id, params, &context)
+                    .map(Some)
+            }
+            "admin/tools/reload" => {
+                let request_id = self.require_request_id(&id, "admin/tools/reload")?;
+                let id_for_header = Some(request_id.clone());
+                self.ensure_protocol_header(
+                    header_protocol_version.as_deref(),
+                    &id_for_header,
+                    &context,
+                )?;
+                self.ensure_ready(Some(request_id.clone()), &context)?;
+                if !self.is_admin(&context) {
+                    return Err(self.invalid_request_response(
+                        Some(request_id),
+                        JsonRpcErrorCode::InvalidRequest,
+                        "method admin/tools/reload requires the admin API key".to_string(),
+                    ));
+                }
+                self.handle_reload_tools(request_id, params).map(Some)
+            }
+            "session/reset" => {
+                let request_id = self.require_request_id(&id, "session/reset")?;
+                self.handle_session_reset(request_id, &context).map(Some)
             }
+            other if id.is_none() || other.starts_with("notifications/") => {
+                // Notifications never get a response, even when we don't understand them.
+                tracing::debug!(method = other, "ignoring unhandled notification");
+                Ok(None)
+            }
+            other => Err(self.invalid_request_response(
+                id,
+                JsonRpcErrorCode::MethodNotFound,
+                format!("unknown method: {other}"),
+            )),
         }
     }
 
@@ -159,19 +804,20 @@ impl McpService {
         id: Value,
         params: Option<Value>,
         header_protocol_version: String,
+        context: &RequestContext,
     ) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
         let params = match params {
             Some(value) => serde_json::from_value::<InitializeParams>(value).map_err(|err| {
                 self.invalid_request_response(
                     Some(id.clone()),
-                    -32602,
+                    JsonRpcErrorCode::InvalidParams,
                     format!("invalid initialize params: {err}"),
                 )
             })?,
             None => {
                 return Err(self.invalid_request_response(
                     Some(id.clone()),
-                    -32602,
+                    JsonRpcErrorCode::InvalidParams,
                     "missing initialize params".to_string(),
                 ));
             }
@@ -183,7 +829,7 @@ impl McpService {
         ) {
             return Err(self.invalid_request_response(
                 Some(id.clone()),
-                -32600,
+                JsonRpcErrorCode::InvalidRequest,
                 format!(
                     "MCP-Protocol-Version header mismatch: payload requested {} but header provided {}",
                     params.protocol_version, header_protocol_version
@@ -191,59 +837,71 @@ impl McpService {
             ));
         }
 
-        let negotiated = self
-            .negotiate_protocol_version(&params.protocol_version)
-            .ok_or_else(|| {
-                self.invalid_request_response(
-                    Some(id.clone()),
-                    -32600,
-                    format!("unsupported protocolVersion: {}", params.protocol_version),
-                )
-            })?;
+        let negotiated = self.negotiate_protocol_version(&params.protocol_version);
+        if !Self::protocol_headers_compatible(&params.protocol_version, &negotiated) {
+            tracing::info!(
+                requested = %params.protocol_version,
+                offered = %negotiated,
+                "unsupported protocolVersion requested; offering the latest supported version"
+            );
+        }
 
         if !params.capabilities.is_object() {
             return Err(self.invalid_request_response(
                 Some(id.clone()),
-                -32602,
+                JsonRpcErrorCode::InvalidParams,
                 "capabilities must be an object".to_string(),
             ));
         }
 
-        tracing::info!(
-            client = %params.client_info.name,
-            version = %params.client_info.version,
-            "client initialized"
-        );
+        let client = format!("{} {}", params.client_info.name, params.client_info.version);
+        let previous = self
+            .sessions
+            .initialize(
+                context.session(),
+                context.api_key.clone(),
+                client,
+                negotiated.clone(),
+            )
+            .map_err(|message| {
+                self.invalid_request_response(
+                    Some(id.clone()),
+                    JsonRpcErrorCode::InvalidRequest,
+                    message,
+                )
+            })?;
+        if context.issued_session {
+            self.sessions.set_default_session(context.session());
+        }
+
+        match previous {
+            Some(previous) => tracing::info!(
+                client = %params.client_info.name,
+                version = %params.client_info.version,
+                previous_client = previous.client.as_deref().unwrap_or("unknown"),
+                session = context.session_id.as_deref().unwrap_or_default(),
+                "client re-initialized; session state reset"
+            ),
+            None => tracing::info!(
+                client = %params.client_info.name,
+                version = %params.client_info.version,
+                "client initialized"
+            ),
+        }
         tracing::debug!(
             protocol = %params.protocol_version,
             capabilities = ?params.capabilities,
             "initialize payload"
         );
 
-        match self.negotiated_protocol.lock() {
-            Ok(mut guard) => {
-                *guard = Some(negotiated.clone());
-            }
-            Err(error) => {
-                tracing::error!(error = %error, "failed to record negotiated protocol version");
-            }
-        }
-
-        self.initialize_called.store(true, Ordering::SeqCst);
-        self.client_ready.store(false, Ordering::SeqCst);
-
+        let mut server_info = self.version_info();
+        server_info["description"] =
+            json!("Model Context Protocol server for UK Parliament research");
+        server_info["errorCatalogueVersion"] = json!(error_catalogue().version);
         let result = json!({
             "protocolVersion": negotiated,
-            "serverInfo": {
-                "name": env!("CARGO_PKG_NAME"),
-                "version": env!("CARGO_PKG_VERSION"),
-                "description": "Model Context Protocol server for UK Parliament research"
-            },
-            "capabilities": {
-                "tools": {
-                    "listChanged": false
-                }
-            },
+            "serverInfo": server_info,
+            "capabilities": self.server_capabilities().for_protocol(&negotiated),
             "instructions": "Call the initialized notification after a successful initialize response, then use tools/list to discover available tools."
         });
 
@@ -263,7 +921,7 @@ impl McpService {
             Some(value) => serde_json::from_value::<ListToolsParams>(value).map_err(|err| {
                 self.invalid_request_response(
                     Some(id.clone()),
-                    -32602,
+                    JsonRpcErrorCode::InvalidParams,
                     format!("invalid tools/list params: {err}"),
                 )
             })?,
@@ -277,7 +935,24 @@ impl McpService {
             );
         }
 
-        let tools = self.tool_schemas.clone();
+        let now = now_timestamp();
+        let filter = read_lock(&self.tool_filter).clone();
+        let tools = self
+            .tool_schemas
+            .iter()
+            .filter(|tool| filter.allows(&tool.name))
+            .cloned()
+            .map(|mut tool| {
+                if let Some(warning) = self.stats.staleness_warning(
+                    &tool.name,
+                    self.config.tool_staleness_threshold,
+                    now,
+                ) {
+                    tool.annotations = Some(json!({ "stalenessWarning": warning }));
+                }
+                tool
+            })
+            .collect::<Vec<_>>();
 
         let result = serde_json::to_value(ToolListResult {
             tools,
@@ -297,15 +972,105 @@ impl McpService {
         })
     }
 
-    async fn handle_call_tool(
+    async fn handle_complete(
         &self,
         id: Value,
         params: Option<Value>,
     ) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
+        let params = params
+            .ok_or_else(|| "missing completion/complete params".to_string())
+            .and_then(|value| {
+                serde_json::from_value::<CompleteParams>(value)
+                    .map_err(|err| format!("invalid completion/complete params: {err}"))
+            })
+            .map_err(|message| {
+                self.invalid_request_response(
+                    Some(id.clone()),
+                    JsonRpcErrorCode::InvalidParams,
+                    message,
+                )
+            })?;
+
+        let completion = match (params.reference.kind.as_str(), &params.reference.name) {
+            ("ref/tool", Some(tool)) if self.tool_allowed(tool) => {
+                match self.completions.cached_index(
+                    tool,
+                    &params.argument.name,
+                    &params.context.arguments,
+                ) {
+                    Some(index) => {
+                        self.complete_from_cache(index, &params.argument.value)
+                            .await
+                    }
+                    None => self.completions.complete(
+                        tool,
+                        &params.argument.name,
+                        &params.argument.value,
+                    ),
+                }
+            }
+            _ => CompletionResult::default(),
+        };
+
+        Ok(JsonRpcSuccess {
+            jsonrpc: JSON_RPC_VERSION.to_string(),
+            id,
+            result: json!({ "completion": completion }),
+        })
+    }
+
+    /// Completions from a cached index. A cache that cannot be read offers nothing rather
+    /// than failing the request.
+    async fn complete_from_cache(&self, index: CachedIndex, value: &str) -> CompletionResult {
+        let names = match index {
+            CachedIndex::ConstituencyNames => {
+                self.parliament_client.cached_constituency_names().await
+            }
+            CachedIndex::MemberNames => self.parliament_client.cached_member_names().await,
+        };
+        match names {
+            Ok(names) => rank_fuzzy(&names, value),
+            Err(err) => {
+                tracing::warn!(error = %err, ?index, "completion cache read failed");
+                CompletionResult::default()
+            }
+        }
+    }
+
+    fn handle_reload_tools(
+        &self,
+        id: Value,
+        params: Option<Value>,
+    ) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
+        let changed = serde_json::from_value::<ToolFilterConfig>(params.unwrap_or(json!({})))
+            .map_err(|err| format!("invalid admin/tools/reload params: {err}"))
+            .and_then(|filter| self.reload_tools(filter).map_err(|err| err.to_string()))
+            .map_err(|message| {
+                self.invalid_request_response(
+                    Some(id.clone()),
+                    JsonRpcErrorCode::InvalidParams,
+                    message,
+                )
+            })?;
+
+        Ok(JsonRpcSuccess {
+            jsonrpc: JSON_RPC_VERSION.to_string(),
+            id,
+            result: json!({ "changed": changed, "tools": self.active_tools() }),
+        })
+    }
+
+    async fn handle_call_tool(
+        &self,
+        id: Value,
+        params: Option<Value>,
+        context: &RequestContext,
+        cancellation: CancellationToken,
+    ) -> Result<Option<JsonRpcSuccess>, JsonRpcErrorResponse> {
         let params_value = params.ok_or_else(|| {
             self.invalid_request_response(
                 Some(id.clone()),
-                -32602,
+                JsonRpcErrorCode::InvalidParams,
                 "missing call_tool params".to_string(),
             )
         })?;
@@ -313,117 +1078,767 @@ impl McpService {
         let params = serde_json::from_value::<CallToolParams>(params_value).map_err(|err| {
             self.invalid_request_response(
                 Some(id.clone()),
-                -32602,
+                JsonRpcErrorCode::InvalidParams,
                 format!("invalid call_tool params: {err}"),
             )
         })?;
 
+        let progress = params
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.get("progressToken"))
+            .filter(|token| token.is_string() || token.is_number())
+            .cloned()
+            .zip(context.notifications.clone())
+            .map(|(token, sink)| ProgressReporter::new(sink, token));
+        let echoed_meta = echoed_client_meta(params.meta.as_ref());
+
         let tool_name = params.name;
-        let arguments = if params.arguments.is_null() {
+        let mut arguments = if params.arguments.is_null() {
             json!({})
         } else {
             params.arguments
         };
 
-        let call_result: Result<Value, AppError> = match tool_name.as_str() {
+        if !self.tool_allowed(&tool_name) {
+            return Err(self.invalid_request_response(
+                Some(id),
+                JsonRpcErrorCode::MethodNotFound,
+                format!("tool {tool_name} is disabled on this server"),
+            ));
+        }
+        if ADMIN_ONLY_TOOLS.contains(&tool_name.as_str()) && !self.is_admin(context) {
+            return Err(self.invalid_request_response(
+                Some(id),
+                JsonRpcErrorCode::InvalidRequest,
+                format!("tool {tool_name} requires the admin API key"),
+            ));
+        }
+        if let Some((pointer, len)) = oversized_string(&arguments, String::new()) {
+            return Err(self.invalid_request_response(
+                Some(id),
+                JsonRpcErrorCode::InvalidParams,
+                format!(
+                    "argument {pointer} is {len} bytes; string arguments are limited to {MAX_ARGUMENT_STRING_BYTES} bytes"
+                ),
+            ));
+        }
+
+        let preferences = self.key_preferences(context).await;
+        let format = ResultFormat::resolve(params.meta.as_ref(), &preferences);
+        if let (Some(limit), "research.run", Some(object)) = (
+            preferences.research_limit,
+            tool_name.as_str(),
+            arguments.as_object_mut(),
+        ) {
+            object.entry("limit").or_insert_with(|| json!(limit));
+        }
+
+        // Held until the call finishes; waiting for it does not count against the timeout.
+        let _research_slot = if tool_name == "research.run" {
+            match self.research_limit.acquire().await {
+                Some(slot) => Some(slot),
+                None => return Err(server_busy(id, "MAX_CONCURRENT_RESEARCH")),
+            }
+        } else {
+            None
+        };
+
+        let meter = Arc::new(ByteMeter::new(
+            self.config.tool_byte_budgets.for_tool(&tool_name),
+        ));
+        let call_context = Arc::new(CallContext::for_tool(&tool_name));
+        let log_level = self
+            .sessions
+            .get(context.session())
+            .and_then(|session| session.log_level)
+            .unwrap_or_default();
+        let correlation_id = correlation_id_for(context.request_id.as_deref());
+        let chunks = ChunkDelivery::new(id.clone(), context.notifications.clone());
+        let pending = PendingCall {
+            timestamp: now_timestamp(),
+            started: Instant::now(),
+            tool: tool_name.clone(),
+            audited: self
+                .audit_log
+                .as_ref()
+                .map(|log| AuditedArguments::new(log, &arguments)),
+            correlation_id: correlation_id.clone(),
+            key_name: context.key_name.clone(),
+        };
+        // Upstream logs raised while the tool runs carry the call's id through this span.
+        let span = tracing::info_span!(
+            "tool_call",
+            tool = %tool_name,
+            correlation_id = %correlation_id,
+        );
+        // Boxed: the tool futures are large enough to overflow small runtime stacks inline.
+        // A panic inside the tool fails this call alone, as an `isError` result.
+        let execution = AssertUnwindSafe(Box::pin(
+            with_correlation_id(
+                correlation_id.clone(),
+                metered(
+                    Arc::clone(&meter),
+                    with_call_context(
+                        Arc::clone(&call_context),
+                        with_client_log(
+                            context.notifications.clone(),
+                            log_level,
+                            self.run_tool(
+                                &id,
+                                &tool_name,
+                                arguments,
+                                progress.as_ref(),
+                                &chunks,
+                                &cancellation,
+                            ),
+                        ),
+                    ),
+                ),
+            )
+            .instrument(span),
+        ))
+        .catch_unwind();
+        let timeout = self.config.tool_timeouts.for_tool(&tool_name);
+        let call_result = tokio::select! {
+            biased;
+            _ = cancellation.cancelled() => {
+                tracing::info!(tool = %tool_name, id = %id, "tools/call cancelled by client");
+                self.finish_call(&pending, AuditOutcome::Cancelled, None).await;
+                return Ok(None);
+            }
+            result = with_timeout(timeout, execution) => match result {
+                Some(Ok(Ok(result))) => result,
+                Some(Ok(Err(rejection))) => {
+                    self.finish_call(&pending, AuditOutcome::Rejected, None).await;
+                    return Err(rejection);
+                }
+                None => {
+                    cancellation.cancel();
+                    self.stats.record_tool(&tool_name, false);
+                    self.stats.record_tool_bytes(&tool_name, meter.used());
+                    tracing::info!(
+                        tool = %tool_name,
+                        correlation_id = %correlation_id,
+                        upstream_bytes = meter.used(),
+                        outcome = "timeout",
+                        "tools/call finished"
+                    );
+                    self.finish_call(&pending, AuditOutcome::Timeout, None).await;
+                    let timeout = timeout.unwrap_or_default();
+                    let meta = ResultMeta::new(&pending, echoed_meta, &call_context);
+                    return Ok(Some(self.tool_timeout_error(id, &tool_name, timeout, &meta)));
+                }
+                Some(Err(panic)) => {
+                    self.metrics.record_panic("tool");
+                    tracing::error!(
+                        tool = %tool_name,
+                        correlation_id = %correlation_id,
+                        panic = panic_message(panic.as_ref()),
+                        "tool panicked"
+                    );
+                    Err(AppError::internal(format!("tool {tool_name} failed unexpectedly")))
+                }
+            },
+        };
+
+        self.stats.record_tool_bytes(&tool_name, meter.used());
+        tracing::info!(
+            tool = %tool_name,
+            correlation_id = %correlation_id,
+            upstream_bytes = meter.used(),
+            outcome = if call_result.is_ok() { "success" } else { "error" },
+            "tools/call finished"
+        );
+        match &call_result {
+            Ok(_) => self.stats.record_tool(&tool_name, true),
+            Err(AppError::BadRequest { .. }) => {}
+            Err(_) => self.stats.record_tool(&tool_name, false),
+        }
+        let (outcome, error_kind) = match &call_result {
+            Ok(_) => (AuditOutcome::Success, None),
+            Err(error @ AppError::BadRequest { .. }) => (
+                AuditOutcome::Rejected,
+                Some(describe_tool_error_data(error).kind.as_str().to_string()),
+            ),
+            Err(error) => (
+                AuditOutcome::Error,
+                Some(describe_tool_error_data(error).kind.as_str().to_string()),
+            ),
+        };
+        self.finish_call(&pending, outcome, error_kind).await;
+
+        let meta = ResultMeta::new(&pending, echoed_meta, &call_context);
+        match call_result {
+            Ok(payload) => {
+                let payload = self.sign_payload(&tool_name, payload);
+                let chunks = chunks.take_buffered();
+                self.build_tool_success(id, &tool_name, payload, chunks, &meta, format)
+                    .map(Some)
+            }
+            Err(AppError::BadRequest { message }) => Err(self.invalid_request_response(
+                Some(id),
+                JsonRpcErrorCode::InvalidParams,
+                message,
+            )),
+            Err(error) => Ok(Some(self.tool_execution_error(
+                id,
+                tool_name.as_str(),
+                error,
+                &meta,
+            ))),
+        }
+    }
+
+    /// Count a finished call in the metrics and append it to the audit log. A failed audit
+    /// write is logged but never fails the call itself.
+    async fn finish_call(
+        &self,
+        pending: &PendingCall,
+        outcome: AuditOutcome,
+        error_kind: Option<String>,
+    ) {
+        // Unknown tool names come straight from the client; keep them out of the label set.
+        if self
+            .tool_schemas
+            .iter()
+            .any(|definition| definition.name == pending.tool)
+        {
+            let elapsed = pending.started.elapsed();
+            self.metrics
+                .record_tool_call(&pending.tool, outcome.as_str(), elapsed);
+            let slow_after = self.config.slow_request_ms;
+            if slow_after > 0 && elapsed >= Duration::from_millis(slow_after) {
+                self.metrics.record_slow_tool_call(&pending.tool);
+                tracing::warn!(
+                    tool = %pending.tool,
+                    outcome = outcome.as_str(),
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    "slow tools/call"
+                );
+            }
+        }
+
+        let (Some(log), Some(audited)) = (self.audit_log.as_ref(), pending.audited.as_ref()) else {
+            return;
+        };
+
+        let entry = AuditEntry {
+            id: None,
+            timestamp: pending.timestamp,
+            tool: pending.tool.clone(),
+            arguments_sha256: audited.sha256.clone(),
+            duration_ms: pending.started.elapsed().as_millis() as u64,
+            outcome,
+            error_kind,
+            correlation_id: Some(pending.correlation_id.clone()),
+            api_key_name: pending.key_name.clone(),
+            arguments: Some(audited.redacted.clone()),
+            redacted_fields: audited.redacted_fields.clone(),
+            replay_of: None,
+        };
+        if let Err(error) = log.record(&entry).await {
+            tracing::error!(tool = %pending.tool, %error, "failed to write audit entry");
+        }
+    }
+
+    /// Attach a `signature` block to the output of signed tools. A no-op unless a
+    /// signing key is configured.
+    fn sign_payload(&self, tool_name: &str, mut payload: Value) -> Value {
+        let Some(signer) = self.response_signer.as_ref() else {
+            return payload;
+        };
+        if !SIGNED_TOOLS.contains(&tool_name) || !payload.is_object() {
+            return payload;
+        }
+
+        let block = signer.sign(&payload);
+        match serde_json::to_value(block) {
+            Ok(block) => {
+                if let Some(map) = payload.as_object_mut() {
+                    map.insert("signature".to_string(), block);
+                }
+            }
+            Err(error) => tracing::error!(error = %error, "failed to encode response signature"),
+        }
+        payload
+    }
+
+    /// Hold `id` for the session until the returned guard is dropped. Notifications and
+    /// null ids are not tracked; an id that is already in flight is rejected with -32600.
+    fn claim_request_id(
+        &self,
+        id: Option<&Value>,
+        context: &RequestContext,
+    ) -> Result<Option<InFlightGuard<'_>>, JsonRpcErrorResponse> {
+        let Some(id) = id.filter(|id| !id.is_null()) else {
+            return Ok(None);
+        };
+        let key = in_flight_key(context, id);
+        let cancellation = CancellationToken::new();
+        match self.in_flight.lock() {
+            Ok(mut guard) => {
+                if guard.contains_key(&key) {
+                    return Err(self.invalid_request_response(
+                        Some(id.clone()),
+                        JsonRpcErrorCode::InvalidRequest,
+                        "request id already in use".to_string(),
+                    ));
+                }
+                guard.insert(key.clone(), cancellation.clone());
+            }
+            Err(error) => tracing::error!(error = %error, "in-flight registry mutex poisoned"),
+        }
+        Ok(Some(InFlightGuard {
+            registry: &self.in_flight,
+            key,
+            cancellation,
+        }))
+    }
+
+    /// Cancel the in-flight request named by `params.requestId`. Unknown or already
+    /// completed ids are ignored, as the notification may race the response.
+    fn handle_cancelled_notification(&self, params: Option<Value>, context: &RequestContext) {
+        let Some(request_id) = params
+            .as_ref()
+            .and_then(|value| value.get("requestId"))
+            .filter(|value| value.is_string() || value.is_number())
+        else {
+            tracing::warn!("ignoring notifications/cancelled without a valid requestId");
+            return;
+        };
+
+        let reason = params
+            .as_ref()
+            .and_then(|value| value.get("reason"))
+            .and_then(Value::as_str)
+            .unwrap_or("none given");
+
+        let token = self
+            .in_flight
+            .lock()
+            .ok()
+            .and_then(|guard| guard.get(&in_flight_key(context, request_id)).cloned());
+
+        match token {
+            Some(token) => {
+                tracing::info!(id = %request_id, reason, "cancelling in-flight request");
+                token.cancel();
+            }
+            None => {
+                tracing::debug!(id = %request_id, "cancellation for unknown or completed request");
+            }
+        }
+    }
+
+    /// Dispatch a tool call. The outer error is a protocol-level failure (bad arguments,
+    /// unknown tool); the inner result is the tool's own outcome.
+    async fn run_tool(
+        &self,
+        id: &Value,
+        tool_name: &str,
+        arguments: Value,
+        progress: Option<&ProgressReporter>,
+        chunks: &ChunkDelivery,
+        cancellation: &CancellationToken,
+    ) -> Result<Result<Value, AppError>, JsonRpcErrorResponse> {
+        let call_result = match tool_name {
             "parliament.fetch_core_dataset" => {
-                let args = self.deserialize_arguments::<FetchCoreDatasetArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
+                let args =
+                    self.deserialize_arguments::<FetchCoreDatasetArgs>(id, tool_name, arguments)?;
                 handle_fetch_core_dataset(&self.parliament_client, args).await
             }
             "parliament.fetch_bills" => {
-                let args = self.deserialize_arguments::<FetchBillsArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
+                let args =
+                    self.deserialize_arguments::<FetchBillsArgs>(id, tool_name, arguments)?;
                 handle_fetch_bills(&self.parliament_client, args).await
             }
+            "parliament.fetch_bill_divisions" => {
+                let args =
+                    self.deserialize_arguments::<FetchBillDivisionsArgs>(id, tool_name, arguments)?;
+                handle_fetch_bill_divisions(&self.parliament_client, args).await
+            }
             "parliament.fetch_legislation" => {
-                let args = self.deserialize_arguments::<FetchLegislationArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
+                let args =
+                    self.deserialize_arguments::<FetchLegislationArgs>(id, tool_name, arguments)?;
                 handle_fetch_legislation(&self.parliament_client, args).await
             }
             "parliament.fetch_mp_activity" => {
-                let args = self.deserialize_arguments::<FetchMpActivityArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
+                let args =
+                    self.deserialize_arguments::<FetchMpActivityArgs>(id, tool_name, arguments)?;
                 handle_fetch_mp_activity(&self.parliament_client, args).await
             }
+            "parliament.fetch_members_bulk" => {
+                let args =
+                    self.deserialize_arguments::<FetchMembersBulkArgs>(id, tool_name, arguments)?;
+                handle_fetch_members_bulk(&self.parliament_client, args).await
+            }
             "parliament.fetch_mp_voting_record" => {
-                let args = self.deserialize_arguments::<FetchMpVotingRecordArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
+                let args = self
+                    .deserialize_arguments::<FetchMpVotingRecordArgs>(id, tool_name, arguments)?;
                 handle_fetch_mp_voting_record(&self.parliament_client, args).await
             }
-            "parliament.lookup_constituency_offline" => {
-                let args = self.deserialize_arguments::<LookupConstituencyArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
+            "parliament.fetch_committees" => {
+                let args =
+                    self.deserialize_arguments::<FetchCommitteesArgs>(id, tool_name, arguments)?;
+                handle_fetch_committees(&self.parliament_client, args).await
+            }
+            "parliament.fetch_bill_detail" => {
+                let args =
+                    self.deserialize_arguments::<FetchBillDetailArgs>(id, tool_name, arguments)?;
+                handle_fetch_bill_detail(&self.parliament_client, args).await
+            }
+            "parliament.fetch_legislation_text" => {
+                let args = self
+                    .deserialize_arguments::<FetchLegislationTextArgs>(id, tool_name, arguments)?;
+                handle_fetch_legislation_text(&self.parliament_client, args).await
+            }
+            "parliament.fetch_member_roles" => {
+                let args =
+                    self.deserialize_arguments::<FetchMemberRolesArgs>(id, tool_name, arguments)?;
+                handle_fetch_member_roles(&self.parliament_client, args).await
+            }
+            "parliament.fetch_division_detail" => {
+                let args = self
+                    .deserialize_arguments::<FetchDivisionDetailArgs>(id, tool_name, arguments)?;
+                handle_fetch_division_detail(&self.parliament_client, args).await
+            }
+            "parliament.fetch_edms" => {
+                let args = self.deserialize_arguments::<FetchEdmsArgs>(id, tool_name, arguments)?;
+                handle_fetch_edms(&self.parliament_client, args).await
+            }
+            "parliament.fetch_oral_questions" => {
+                let args =
+                    self.deserialize_arguments::<FetchOralQuestionsArgs>(id, tool_name, arguments)?;
+                handle_fetch_oral_questions(&self.parliament_client, args).await
+            }
+            "parliament.fetch_statutory_instruments" => {
+                let args = self.deserialize_arguments::<FetchStatutoryInstrumentsArgs>(
+                    id, tool_name, arguments,
                 )?;
-                handle_lookup_constituency_offline(&self.parliament_client, args).await
+                handle_fetch_statutory_instruments(&self.parliament_client, args).await
+            }
+            "parliament.fetch_written_questions" => {
+                let args = self
+                    .deserialize_arguments::<FetchWrittenQuestionsArgs>(id, tool_name, arguments)?;
+                handle_fetch_written_questions(&self.parliament_client, args).await
+            }
+            "parliament.export_voting_record" => {
+                let args =
+                    self.deserialize_arguments::<ExportVotingRecordArgs>(id, tool_name, arguments)?;
+                match VotingRecordCsv::start(&self.parliament_client, &args) {
+                    Ok(export) => deliver_chunks(export, chunks).await,
+                    Err(err) => Err(err),
+                }
+            }
+            "parliament.lookup_constituency_offline" => {
+                let args =
+                    self.deserialize_arguments::<LookupConstituencyArgs>(id, tool_name, arguments)?;
+                let result = handle_lookup_constituency_offline(
+                    &self.parliament_client,
+                    args,
+                    self.config.prefetch_member_after_lookup,
+                )
+                .await;
+                if let (Some(usage), Ok(payload)) = (&self.usage, &result) {
+                    if let Some(constituency) = payload["constituencyName"].as_str() {
+                        usage.record_constituency(constituency);
+                    }
+                }
+                result
+            }
+            "parliament.list_constituencies" => {
+                let args =
+                    self.deserialize_arguments::<ListConstituenciesArgs>(id, tool_name, arguments)?;
+                handle_list_constituencies(&self.parliament_client, args).await
+            }
+            "parliament.search_hansard" => {
+                let args =
+                    self.deserialize_arguments::<SearchHansardArgs>(id, tool_name, arguments)?;
+                handle_search_hansard(&self.parliament_client, args).await
             }
             "parliament.search_uk_law" => {
-                let args = self.deserialize_arguments::<SearchUkLawArgs>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
+                let args =
+                    self.deserialize_arguments::<SearchUkLawArgs>(id, tool_name, arguments)?;
                 handle_search_uk_law(&self.parliament_client, args).await
             }
             "research.run" => {
-                let args = self.deserialize_arguments::<ResearchRequestDto>(
-                    &id,
-                    tool_name.as_str(),
-                    arguments.clone(),
-                )?;
-                match handle_run_research(&self.research_service, args).await {
+                let args =
+                    self.deserialize_arguments::<ResearchRequestDto>(id, tool_name, arguments)?;
+                if let Some(usage) = &self.usage {
+                    usage.record_research_topic(&args.topic);
+                }
+                let context = ResearchRunContext {
+                    progress,
+                    cancellation: Some(cancellation),
+                };
+                match handle_run_research(&self.research_service, args, context).await {
                     Ok(result) => serde_json::to_value(result).map_err(|err| {
                         AppError::internal(format!("failed to serialize research response: {err}"))
                     }),
                     Err(err) => Err(err),
                 }
             }
+            "research.list_cached" => {
+                let args =
+                    self.deserialize_arguments::<ListCachedResearchArgs>(id, tool_name, arguments)?;
+                handle_list_cached_research(&self.research_service, args)
+                    .await
+                    .and_then(|result| {
+                        serde_json::to_value(result).map_err(|err| {
+                            AppError::internal(format!(
+                                "failed to serialize cached research: {err}"
+                            ))
+                        })
+                    })
+            }
+            "research.suggest_keywords" => {
+                let args =
+                    self.deserialize_arguments::<SuggestKeywordsArgs>(id, tool_name, arguments)?;
+                handle_suggest_keywords(&self.research_service, args)
+                    .await
+                    .and_then(|result| {
+                        serde_json::to_value(result).map_err(|err| {
+                            AppError::internal(format!(
+                                "failed to serialize keyword suggestions: {err}"
+                            ))
+                        })
+                    })
+            }
             "utilities.current_datetime" => {
                 let result = handle_current_datetime(&self.utilities_service);
                 serde_json::to_value(result).map_err(|err| {
                     AppError::internal(format!("failed to serialize datetime payload: {err}"))
                 })
             }
+            "utilities.verify_signature" => {
+                let args =
+                    self.deserialize_arguments::<VerifySignatureArgs>(id, tool_name, arguments)?;
+                handle_verify_signature(self.response_signer.as_deref(), args).and_then(|result| {
+                    serde_json::to_value(result).map_err(|err| {
+                        AppError::internal(format!(
+                            "failed to serialize signature verification: {err}"
+                        ))
+                    })
+                })
+            }
+            "utilities.error_catalogue" => {
+                self.deserialize_arguments::<Value>(id, tool_name, arguments)?;
+                serde_json::to_value(error_catalogue()).map_err(|err| {
+                    AppError::internal(format!("failed to serialize error catalogue: {err}"))
+                })
+            }
+            "admin.tool_stats" => {
+                self.deserialize_arguments::<Value>(id, tool_name, arguments)?;
+                Ok(self.describe_stats())
+            }
+            "admin.usage_summary" => {
+                let args =
+                    self.deserialize_arguments::<UsageSummaryArgs>(id, tool_name, arguments)?;
+                match &self.usage {
+                    Some(usage) => {
+                        let summary =
+                            usage.summary(args.window_hours.unwrap_or(DEFAULT_WINDOW_HOURS));
+                        serde_json::to_value(summary).map_err(|err| {
+                            AppError::internal(format!("failed to serialize usage summary: {err}"))
+                        })
+                    }
+                    None => Err(AppError::configuration(
+                        "usage statistics are not enabled (USAGE_STATS_ENABLED)",
+                    )),
+                }
+            }
+            "admin.set_key_preferences" => {
+                let args =
+                    self.deserialize_arguments::<SetKeyPreferencesArgs>(id, tool_name, arguments)?;
+                self.set_key_preferences(args).await
+            }
+            "admin.replay_call" => {
+                let args =
+                    self.deserialize_arguments::<ReplayCallArgs>(id, tool_name, arguments)?;
+                self.replay_call(id, args, cancellation).await?
+            }
+            "admin.refresh_reference_data" => {
+                let args = self
+                    .deserialize_arguments::<RefreshReferenceDataArgs>(id, tool_name, arguments)?;
+                self.parliament_client
+                    .refresh_reference_data(args)
+                    .await
+                    .and_then(|report| {
+                        serde_json::to_value(report).map_err(|err| {
+                            AppError::internal(format!(
+                                "failed to serialize reference data report: {err}"
+                            ))
+                        })
+                    })
+            }
             other => {
                 return Err(self.invalid_request_response(
-                    Some(id),
-                    -32601,
+                    Some(id.clone()),
+                    JsonRpcErrorCode::MethodNotFound,
                     format!("unknown tool: {other}"),
                 ));
             }
         };
 
-        match call_result {
-            Ok(payload) => self.build_tool_success(id, payload),
-            Err(AppError::BadRequest { message }) => {
-                Err(self.invalid_request_response(Some(id), -32602, message))
+        Ok(call_result.or_else(cache_miss_result))
+    }
+
+    /// Preferences stored for the caller's API key. A failed lookup is logged and treated
+    /// as no preferences, so it never fails the call.
+    async fn key_preferences(&self, context: &RequestContext) -> KeyPreferences {
+        let (Some(store), Some(api_key)) = (self.preferences.as_ref(), context.api_key.as_deref())
+        else {
+            return KeyPreferences::default();
+        };
+        match store.get(api_key).await {
+            Ok(preferences) => preferences.unwrap_or_default(),
+            Err(error) => {
+                tracing::warn!(%error, "failed to load key preferences; using defaults");
+                KeyPreferences::default()
+            }
+        }
+    }
+
+    async fn set_key_preferences(&self, args: SetKeyPreferencesArgs) -> Result<Value, AppError> {
+        let Some(store) = self.preferences.as_ref() else {
+            return Err(AppError::configuration("key preferences are not enabled"));
+        };
+        if self.key_ring.load().resolve(&args.api_key).is_none() {
+            return Err(AppError::bad_request(
+                "apiKey must be MCP_API_KEY or ADMIN_API_KEY",
+            ));
+        }
+
+        store.set(&args.api_key, &args.preferences).await?;
+        tracing::info!(preferences = ?args.preferences, "key preferences updated");
+        serde_json::to_value(&args.preferences)
+            .map(|preferences| json!({ "preferences": preferences }))
+            .map_err(|err| AppError::internal(format!("failed to serialize preferences: {err}")))
+    }
+
+    /// Re-run the call recorded in audit entry `args.audit_id` through the normal argument
+    /// validation and dispatch, recording the rerun as a new entry that points back at it.
+    /// Redacted fields are never recovered from storage; the caller must supply them.
+    async fn replay_call(
+        &self,
+        id: &Value,
+        args: ReplayCallArgs,
+        cancellation: &CancellationToken,
+    ) -> Result<Result<Value, AppError>, JsonRpcErrorResponse> {
+        let Some(log) = self.audit_log.as_ref() else {
+            return Ok(Err(AppError::configuration("audit log is not enabled")));
+        };
+        let original = match log.get(&args.audit_id).await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => {
+                return Ok(Err(AppError::bad_request(format!(
+                    "no audit entry with id {}",
+                    args.audit_id
+                ))));
+            }
+            Err(error) => return Ok(Err(error)),
+        };
+        if ADMIN_ONLY_TOOLS.contains(&original.tool.as_str()) {
+            return Ok(Err(AppError::bad_request(format!(
+                "{} calls cannot be replayed",
+                original.tool
+            ))));
+        }
+        if !self.tool_allowed(&original.tool) {
+            return Ok(Err(AppError::bad_request(format!(
+                "tool {} is disabled on this server",
+                original.tool
+            ))));
+        }
+        let Some(mut arguments) = original.arguments.clone() else {
+            return Ok(Err(AppError::bad_request(format!(
+                "audit entry {} has no stored arguments to replay",
+                args.audit_id
+            ))));
+        };
+        let missing: Vec<&str> = original
+            .redacted_fields
+            .iter()
+            .filter(|pointer| !args.redacted_values.contains_key(pointer.as_str()))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Ok(Err(AppError::bad_request(format!(
+                "audit entry {} had redacted fields; supply {} in redactedValues",
+                args.audit_id,
+                missing.join(", ")
+            ))));
+        }
+        for pointer in &original.redacted_fields {
+            if let Some(slot) = arguments.pointer_mut(pointer) {
+                *slot = args.redacted_values[pointer].clone();
             }
-            Err(error) => Ok(self.tool_execution_error(id, tool_name.as_str(), error)),
         }
+
+        let audited = AuditedArguments::new(log, &arguments);
+        let timestamp = now_timestamp();
+        let started = Instant::now();
+        // A replay answers with the summary only; chunked content is not re-sent.
+        let chunks = ChunkDelivery::new(id.clone(), None);
+        let result =
+            Box::pin(self.run_tool(id, &original.tool, arguments, None, &chunks, cancellation))
+                .await?;
+
+        if !matches!(result, Err(AppError::BadRequest { .. })) {
+            self.stats.record_tool(&original.tool, result.is_ok());
+        }
+        let outcome = match &result {
+            Ok(_) => AuditOutcome::Success,
+            Err(AppError::BadRequest { .. }) => AuditOutcome::Rejected,
+            Err(_) => AuditOutcome::Error,
+        };
+        let error = result.as_ref().err().map(|error| {
+            let data = describe_tool_error_data(error);
+            json!({
+                "kind": data.kind,
+                "message": error.to_string(),
+                "retryable": data.retryable,
+            })
+        });
+        let entry = AuditEntry {
+            id: None,
+            timestamp,
+            tool: original.tool.clone(),
+            arguments_sha256: audited.sha256,
+            duration_ms: started.elapsed().as_millis() as u64,
+            outcome,
+            error_kind: error
+                .as_ref()
+                .and_then(|error| error["kind"].as_str())
+                .map(str::to_string),
+            correlation_id: current_correlation_id(),
+            // Only the admin key may replay calls.
+            api_key_name: Some(ADMIN_KEY_NAME.to_string()),
+            arguments: Some(audited.redacted),
+            redacted_fields: audited.redacted_fields,
+            replay_of: original.id.clone(),
+        };
+        let replay_id = match log.record(&entry).await {
+            Ok(replay_id) => replay_id,
+            Err(error) => return Ok(Err(error)),
+        };
+
+        Ok(Ok(json!({
+            "tool": original.tool,
+            "originalAuditId": original.id,
+            "replayAuditId": replay_id,
+            "outcome": outcome,
+            "result": result.ok(),
+            "error": error,
+        })))
     }
 
     fn deserialize_arguments<T>(
         &self,
         id: &Value,
         tool_name: &str,
-        value: Value,
+        mut value: Value,
     ) -> Result<T, JsonRpcErrorResponse>
     where
         T: serde::de::DeserializeOwned,
@@ -431,22 +1846,92 @@ impl McpService {
         if !value.is_object() {
             return Err(self.invalid_request_response(
                 Some(id.clone()),
-                -32602,
+                JsonRpcErrorCode::InvalidParams,
                 "tool arguments must be an object".to_string(),
             ));
         }
 
+        if let Err(message) = resolve_id_arguments(tool_name, &mut value) {
+            return Err(self.invalid_request_response(
+                Some(id.clone()),
+                JsonRpcErrorCode::InvalidParams,
+                message,
+            ));
+        }
+
+        if let Some(schema) = self.argument_schemas.get(tool_name) {
+            let dropped = drop_null_optionals(schema, &mut value);
+            if !dropped.is_empty() {
+                tracing::debug!(
+                    tool = tool_name,
+                    fields = %dropped.join(", "),
+                    "treating null optional arguments as absent"
+                );
+            }
+        }
+
         if let Some(validator) = self.argument_validators.get(tool_name) {
-            if let Err(errors) = validator.validate(&value) {
-                let message = errors
-                    .into_iter()
-                    .map(|error| error.to_string())
+            let violations: Vec<ArgumentViolation> = match validator.validate(&value) {
+                Ok(()) => Vec::new(),
+                Err(errors) => errors.map(ArgumentViolation::from).collect(),
+            };
+            let lenient = !self.config.strict_args
+                && violations
+                    .iter()
+                    .all(|violation| matches!(violation, ArgumentViolation::Unknown { .. }));
+
+            for violation in &violations {
+                if let ArgumentViolation::Unknown { properties, .. } = violation {
+                    for property in properties {
+                        self.metrics.record_unknown_field("arguments", property);
+                    }
+                }
+            }
+
+            if lenient {
+                for violation in violations {
+                    if let ArgumentViolation::Unknown { path, properties } = violation {
+                        tracing::debug!(
+                            tool = tool_name,
+                            path = %path,
+                            properties = %properties.join(", "),
+                            "ignoring unknown tool arguments"
+                        );
+                        if let Some(object) =
+                            value.pointer_mut(&path).and_then(Value::as_object_mut)
+                        {
+                            for property in &properties {
+                                object.remove(property);
+                            }
+                        }
+                    }
+                }
+            } else if !violations.is_empty() {
+                let message = violations
+                    .iter()
+                    .filter(|violation| {
+                        self.config.strict_args
+                            || !matches!(violation, ArgumentViolation::Unknown { .. })
+                    })
+                    .map(|violation| violation.describe(self.argument_schemas.get(tool_name)))
                     .collect::<Vec<_>>()
                     .join("; ");
+                // The schema stays authoritative, but the two layers should never disagree;
+                // see tests/schema_consistency_tests.rs.
+                let constraint_violated = violations
+                    .iter()
+                    .any(|violation| matches!(violation, ArgumentViolation::Other(_)));
+                if constraint_violated && serde_json::from_value::<T>(value.clone()).is_ok() {
+                    tracing::warn!(
+                        tool = tool_name,
+                        violations = %message,
+                        "input schema rejected arguments its DTO accepts"
+                    );
+                }
 
                 return Err(self.invalid_request_response(
                     Some(id.clone()),
-                    -32602,
+                    JsonRpcErrorCode::InvalidParams,
                     format!("invalid tool arguments: {message}"),
                 ));
             }
@@ -460,31 +1945,76 @@ impl McpService {
         serde_json::from_value::<T>(value).map_err(|err| {
             self.invalid_request_response(
                 Some(id.clone()),
-                -32602,
+                JsonRpcErrorCode::InvalidParams,
                 format!("invalid tool arguments: {err}"),
             )
         })
     }
 
+    /// Check a tool payload against the tool's declared `outputSchema`. Tools without an
+    /// output schema always pass.
+    pub fn validate_tool_output(&self, tool_name: &str, payload: &Value) -> Result<(), String> {
+        let Some(validator) = self.output_validators.get(tool_name) else {
+            return Ok(());
+        };
+
+        validator.validate(payload).map_err(|errors| {
+            errors
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+    }
+
     fn build_tool_success(
         &self,
         id: Value,
+        tool_name: &str,
         payload: Value,
+        chunks: Vec<ToolContent>,
+        meta: &ResultMeta,
+        format: ResultFormat,
     ) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
-        let rendered = serde_json::to_string_pretty(&payload).map_err(|err| {
+        let rendered = format.render(&payload).map_err(|err| {
             self.internal_error_response(
                 Some(id.clone()),
                 format!("failed to render tool payload: {err}"),
             )
         })?;
 
+        let mut content = vec![ToolContent::text(rendered)];
+        content.extend(chunks);
+
+        if let Err(message) = self.validate_tool_output(tool_name, &payload) {
+            tracing::warn!(
+                tool = tool_name,
+                errors = %message,
+                "tool output does not match its outputSchema"
+            );
+
+            if self.config.strict_output_validation {
+                return Err(self.internal_error_response(
+                    Some(id),
+                    format!("{tool_name} returned output that does not match its outputSchema: {message}"),
+                ));
+            }
+
+            content.push(ToolContent::text(format!(
+                "Advisory: this result does not match the declared outputSchema for {tool_name} ({message}); fields may be missing or renamed."
+            )));
+        }
+
+        content.extend(extract_resource_links(
+            tool_name,
+            &payload,
+            self.config.resource_link_limit,
+        ));
+
         let tool_result = ToolCallResult {
-            content: vec![ToolContent {
-                kind: "text".to_string(),
-                text: rendered,
-            }],
+            content,
             structured_content: Some(payload),
             is_error: None,
+            meta: format.include_meta.then(|| meta.to_value()),
         };
 
         let result = serde_json::to_value(tool_result).map_err(|err| {
@@ -501,19 +2031,86 @@ impl McpService {
         })
     }
 
-    fn tool_execution_error(&self, id: Value, tool_name: &str, error: AppError) -> JsonRpcSuccess {
+    fn tool_execution_error(
+        &self,
+        id: Value,
+        tool_name: &str,
+        error: AppError,
+        meta: &ResultMeta,
+    ) -> JsonRpcSuccess {
         let sanitized_message = self.describe_tool_error(tool_name, &error);
-        tracing::warn!(tool = tool_name, message = %sanitized_message, "tool execution failed");
+        tracing::warn!(
+            tool = tool_name,
+            correlation_id = %meta.correlation_id,
+            message = %sanitized_message,
+            "tool execution failed"
+        );
         tracing::debug!(tool = tool_name, error = ?error, "detailed tool execution failure");
 
+        self.tool_error_result(
+            id,
+            tool_name,
+            sanitized_message,
+            describe_tool_error_data(&error),
+            meta,
+        )
+    }
+
+    fn tool_timeout_error(
+        &self,
+        id: Value,
+        tool_name: &str,
+        timeout: Duration,
+        meta: &ResultMeta,
+    ) -> JsonRpcSuccess {
+        let secs = timeout.as_secs();
+        let message = format!("tool execution exceeded {secs}s");
+        tracing::warn!(
+            tool = tool_name,
+            timeout_secs = secs,
+            "tool execution timed out"
+        );
+
+        self.tool_error_result(
+            id,
+            tool_name,
+            message,
+            ToolErrorData {
+                kind: ToolErrorKind::Timeout,
+                status: None,
+                url: None,
+                retryable: true,
+                timeout_secs: Some(secs),
+                correlation_id: None,
+                build: None,
+            },
+            meta,
+        )
+    }
+
+    fn tool_error_result(
+        &self,
+        id: Value,
+        tool_name: &str,
+        sanitized_message: String,
+        mut data: ToolErrorData,
+        meta: &ResultMeta,
+    ) -> JsonRpcSuccess {
         let fallback_message = sanitized_message.clone();
+        data.correlation_id = Some(meta.correlation_id.clone());
+        if data.kind == ToolErrorKind::Internal {
+            data.build = Some(self.build.clone());
+        }
+        let structured_content = serde_json::to_value(data)
+            .inspect_err(|err| {
+                tracing::error!(tool = tool_name, error = %err, "failed to encode tool error data");
+            })
+            .ok();
         let tool_result = ToolCallResult {
-            content: vec![ToolContent {
-                kind: "text".to_string(),
-                text: sanitized_message,
-            }],
-            structured_content: None,
+            content: vec![ToolContent::text(sanitized_message)],
+            structured_content,
             is_error: Some(true),
+            meta: Some(meta.to_value()),
         };
 
         let result = match serde_json::to_value(tool_result) {
@@ -543,8 +2140,8 @@ impl McpService {
         }
     }
 
-    fn handle_initialized_notification(&self, method: &str) {
-        if !self.initialize_called.load(Ordering::SeqCst) {
+    fn handle_initialized_notification(&self, method: &str, context: &RequestContext) {
+        if !self.sessions.mark_ready(context.session()) {
             tracing::warn!(
                 method,
                 "received {method} before initialize; ignoring notification"
@@ -552,23 +2149,75 @@ impl McpService {
             return;
         }
 
-        self.client_ready.store(true, Ordering::SeqCst);
         tracing::info!(method, "client signalled readiness via {method}");
     }
 
-    fn ensure_ready(&self, id: Option<Value>) -> Result<(), JsonRpcErrorResponse> {
-        if !self.initialize_called.load(Ordering::SeqCst) {
+    /// Drop all state for the caller's session so the next request must start again with
+    /// `initialize`. Resetting a session that does not exist is not an error.
+    fn handle_session_reset(
+        &self,
+        id: Value,
+        context: &RequestContext,
+    ) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
+        let existed = self.sessions.reset(context.session());
+        tracing::info!(
+            session = context.session_id.as_deref().unwrap_or_default(),
+            existed,
+            "session reset"
+        );
+
+        Ok(JsonRpcSuccess {
+            jsonrpc: JSON_RPC_VERSION.to_string(),
+            id,
+            result: json!({ "reset": existed }),
+        })
+    }
+
+    /// `DELETE /api/mcp`: end the caller's named session and cancel its in-flight
+    /// requests. Returns `false` when the caller's key has no such session.
+    pub fn terminate_session(&self, context: &RequestContext) -> bool {
+        let Some(session_id) = context.session_id.as_deref() else {
+            return false;
+        };
+        if !self.sessions.terminate(context.session()) {
+            return false;
+        }
+
+        let mut cancelled = 0;
+        match self.in_flight.lock() {
+            Ok(guard) => {
+                for ((owner, session, _), token) in guard.iter() {
+                    if *owner == context.key_name && session == session_id {
+                        token.cancel();
+                        cancelled += 1;
+                    }
+                }
+            }
+            Err(error) => tracing::error!(error = %error, "in-flight registry mutex poisoned"),
+        }
+        tracing::info!(session = session_id, cancelled, "session terminated");
+        true
+    }
+
+    fn ensure_ready(
+        &self,
+        id: Option<Value>,
+        context: &RequestContext,
+    ) -> Result<(), JsonRpcErrorResponse> {
+        let session = self.sessions.get(context.session()).unwrap_or_default();
+
+        if !session.initialized {
             return Err(self.invalid_request_response(
                 id.clone(),
-                -32002,
+                JsonRpcErrorCode::NotInitialized,
                 "client must call initialize before invoking this method".to_string(),
             ));
         }
 
-        if !self.client_ready.load(Ordering::SeqCst) {
+        if !session.ready {
             return Err(self.invalid_request_response(
                 id,
-                -32002,
+                JsonRpcErrorCode::NotInitialized,
                 "client must send the initialized notification before invoking this method"
                     .to_string(),
             ));
@@ -577,11 +2226,20 @@ impl McpService {
         Ok(())
     }
 
-    fn ensure_initialized(&self, id: Option<Value>) -> Result<(), JsonRpcErrorResponse> {
-        if !self.initialize_called.load(Ordering::SeqCst) {
+    fn ensure_initialized(
+        &self,
+        id: Option<Value>,
+        context: &RequestContext,
+    ) -> Result<(), JsonRpcErrorResponse> {
+        let initialized = self
+            .sessions
+            .get(context.session())
+            .is_some_and(|session| session.initialized);
+
+        if !initialized {
             return Err(self.invalid_request_response(
                 id,
-                -32002,
+                JsonRpcErrorCode::NotInitialized,
                 "client must call initialize before invoking this method".to_string(),
             ));
         }
@@ -589,6 +2247,61 @@ impl McpService {
         Ok(())
     }
 
+    /// `logging/setLevel`: the minimum level of `notifications/message` sent during this
+    /// session's tool calls. Once set, tool calls that accept `text/event-stream` are
+    /// streamed even without a progress token so the messages can be delivered.
+    fn handle_set_log_level(
+        &self,
+        id: Value,
+        params: Option<Value>,
+        context: &RequestContext,
+    ) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
+        let params = params
+            .ok_or_else(|| "missing logging/setLevel params".to_string())
+            .and_then(|params| {
+                serde_json::from_value::<SetLevelParams>(params)
+                    .map_err(|err| format!("invalid logging/setLevel params: {err}"))
+            })
+            .map_err(|message| {
+                self.invalid_request_response(
+                    Some(id.clone()),
+                    JsonRpcErrorCode::InvalidParams,
+                    message,
+                )
+            })?;
+
+        self.sessions.set_log_level(context.session(), params.level);
+        tracing::debug!(
+            session = context.session_id.as_deref().unwrap_or_default(),
+            level = params.level.as_str(),
+            "client log level set"
+        );
+
+        Ok(JsonRpcSuccess {
+            jsonrpc: JSON_RPC_VERSION.to_string(),
+            id,
+            result: json!({}),
+        })
+    }
+
+    /// Whether the caller's key has a live or recently terminated session under the
+    /// request's `Mcp-Session-Id`.
+    pub fn session_is_known(&self, context: &RequestContext) -> bool {
+        self.sessions.is_known(context.session())
+    }
+
+    /// The session issued to the key's latest `initialize` without `Mcp-Session-Id`.
+    pub fn default_session(&self, key_name: Option<&str>) -> Option<String> {
+        self.sessions.default_session(key_name)
+    }
+
+    /// Whether the caller's session asked for log messages with `logging/setLevel`.
+    pub fn wants_log_messages(&self, context: &RequestContext) -> bool {
+        self.sessions
+            .get(context.session())
+            .is_some_and(|session| session.log_level.is_some())
+    }
+
     fn handle_ping(&self, id: Value) -> Result<JsonRpcSuccess, JsonRpcErrorResponse> {
         Ok(JsonRpcSuccess {
             jsonrpc: JSON_RPC_VERSION.to_string(),
@@ -603,13 +2316,14 @@ impl McpService {
         &self,
         header_protocol_version: Option<&str>,
         id: &Option<Value>,
+        context: &RequestContext,
     ) -> Result<(), JsonRpcErrorResponse> {
-        let expected_version = match self.negotiated_protocol_version() {
+        let expected_version = match self.negotiated_protocol_version(context.session()) {
             Some(version) => version,
             None => {
                 return Err(self.invalid_request_response(
                     id.clone(),
-                    -32002,
+                    JsonRpcErrorCode::NotInitialized,
                     "client must call initialize before invoking this method".to_string(),
                 ));
             }
@@ -619,7 +2333,7 @@ impl McpService {
             Some(value) if Self::protocol_headers_compatible(value, &expected_version) => Ok(()),
             Some(value) => Err(self.invalid_request_response(
                 id.clone(),
-                -32600,
+                JsonRpcErrorCode::InvalidRequest,
                 format!(
                     "MCP-Protocol-Version header mismatch: expected {expected_version}, received {value}"
                 ),
@@ -658,22 +2372,22 @@ impl McpService {
         }
     }
 
-    fn negotiate_protocol_version(&self, requested: &str) -> Option<String> {
+    /// The version to answer `initialize` with. Per the MCP spec an unsupported request is
+    /// not an error: the server offers its newest version and the client decides whether to
+    /// continue. Requests still pinned to the unsupported version then fail the header check.
+    fn negotiate_protocol_version(&self, requested: &str) -> String {
         // If the exact version is supported, return it.
-        if SUPPORTED_PROTOCOL_VERSIONS.iter().any(|v| *v == requested) {
-            return Some(requested.to_string());
+        if SUPPORTED_PROTOCOL_VERSIONS.contains(&requested) {
+            return requested.to_string();
         }
 
         // Backward/forward-compatibility mapping:
         // Treat the date-based protocol tag as equivalent to 1.1 for capability purposes.
-        if PROTOCOL_VERSION_1_1_ALIASES
-            .iter()
-            .any(|alias| *alias == requested)
-        {
-            return Some("1.1".to_string());
+        if PROTOCOL_VERSION_1_1_ALIASES.contains(&requested) {
+            return "1.1".to_string();
         }
 
-        None
+        SUPPORTED_PROTOCOL_VERSIONS[0].to_string()
     }
 
     fn protocol_headers_compatible(a: &str, b: &str) -> bool {
@@ -688,38 +2402,33 @@ impl McpService {
 
     fn describe_tool_error(&self, tool_name: &str, error: &AppError) -> String {
         match error {
-            AppError::Upstream { data, .. } => {
-                if let Some(status) = data
-                    .as_ref()
-                    .and_then(|value| value.get("status"))
-                    .and_then(|value| value.as_u64())
-                {
-                    format!("Upstream service responded with HTTP {status}")
-                } else {
-                    format!("Upstream service request for {tool_name} failed")
-                }
-            }
+            AppError::Upstream { data, .. } => match upstream_status(data.as_ref()) {
+                Some(429) => "Upstream service responded with HTTP 429 (rate limited; retry later)"
+                    .to_string(),
+                Some(status) => format!("Upstream service responded with HTTP {status}"),
+                None => format!("Upstream service request for {tool_name} failed"),
+            },
             AppError::Configuration { .. } => {
                 format!("Server configuration prevented running {tool_name}")
             }
             AppError::Internal { .. } => {
                 format!("Internal error while executing {tool_name}")
             }
-            AppError::BadRequest { message } => message.clone(),
+            AppError::BadRequest { message } | AppError::NotCached { message } => message.clone(),
         }
     }
 
     fn invalid_request_response(
         &self,
         id: Option<Value>,
-        code: i32,
+        code: JsonRpcErrorCode,
         message: String,
     ) -> JsonRpcErrorResponse {
         JsonRpcErrorResponse {
             jsonrpc: JSON_RPC_VERSION.to_string(),
             id: id.unwrap_or(Value::Null),
             error: JsonRpcError {
-                code,
+                code: code.code(),
                 message,
                 data: None,
             },
@@ -731,10 +2440,281 @@ impl McpService {
             jsonrpc: JSON_RPC_VERSION.to_string(),
             id: id.unwrap_or(Value::Null),
             error: JsonRpcError {
-                code: -32000,
+                code: JsonRpcErrorCode::ServerError.code(),
                 message,
-                data: None,
+                data: Some(json!({ "build": self.build })),
             },
         }
     }
 }
+
+/// Await `future`, giving up after `timeout` when one is set. `None` means it timed out.
+async fn with_timeout<F: std::future::Future>(
+    timeout: Option<Duration>,
+    future: F,
+) -> Option<F::Output> {
+    match timeout {
+        Some(limit) => tokio::time::timeout(limit, future).await.ok(),
+        None => Some(future.await),
+    }
+}
+
+/// One schema violation in a tool's arguments. Unknown properties are kept apart so lenient
+/// mode (`MCP_STRICT_ARGS=false`) can strip them instead of failing the call.
+enum ArgumentViolation {
+    Unknown {
+        /// JSON pointer to the object holding the properties; empty for the top level.
+        path: String,
+        properties: Vec<String>,
+    },
+    Other(String),
+}
+
+impl ArgumentViolation {
+    /// `schema` is the tool's input schema, used to suggest the declared names closest to
+    /// unknown properties.
+    fn describe(&self, schema: Option<&Value>) -> String {
+        match self {
+            ArgumentViolation::Unknown { path, properties } => {
+                let noun = if properties.len() == 1 {
+                    "property"
+                } else {
+                    "properties"
+                };
+                let names: Vec<&str> = properties.iter().map(String::as_str).collect();
+                let mut message = format!("unknown {noun} {}", quoted_list(&names));
+                if !path.is_empty() {
+                    message.push_str(&format!(" at {path}"));
+                }
+                let declared: Vec<&str> = schema
+                    .and_then(|schema| schema_at(schema, path))
+                    .and_then(|schema| schema.get("properties"))
+                    .and_then(Value::as_object)
+                    .map(|declared| declared.keys().map(String::as_str).collect())
+                    .unwrap_or_default();
+                let suggestions: Vec<String> = properties
+                    .iter()
+                    .filter_map(|property| {
+                        closest_name(property, &declared)
+                            .map(|name| format!("`{name}` for `{property}`"))
+                    })
+                    .collect();
+                if !suggestions.is_empty() {
+                    message.push_str(&format!(" (did you mean {}?)", suggestions.join(", ")));
+                } else if !declared.is_empty() {
+                    message.push_str(&format!(" (expected one of {})", quoted_list(&declared)));
+                }
+                message
+            }
+            ArgumentViolation::Other(message) => message.clone(),
+        }
+    }
+}
+
+impl From<ValidationError<'_>> for ArgumentViolation {
+    fn from(error: ValidationError<'_>) -> Self {
+        match error.kind {
+            ValidationErrorKind::AdditionalProperties { unexpected } => {
+                ArgumentViolation::Unknown {
+                    path: error.instance_path.to_string(),
+                    properties: unexpected,
+                }
+            }
+            _ => ArgumentViolation::Other(error.to_string()),
+        }
+    }
+}
+
+fn quoted_list(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The subschema describing the argument value at JSON `pointer`, following `properties`
+/// for object keys and `items` for array indices.
+fn schema_at<'a>(schema: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer
+        .split('/')
+        .skip(1)
+        .try_fold(schema, |schema, segment| {
+            let key = segment.replace("~1", "/").replace("~0", "~");
+            schema
+                .get("properties")
+                .and_then(|properties| properties.get(&key))
+                .or_else(|| schema.get("items"))
+        })
+}
+
+/// The declared name a client most likely meant: the one with the smallest
+/// case-insensitive edit distance, if that distance is small for the name's length.
+fn closest_name<'a>(unknown: &str, declared: &[&'a str]) -> Option<&'a str> {
+    let unknown = unknown.to_lowercase();
+    let allowed = (unknown.chars().count() / 3).max(1);
+    declared
+        .iter()
+        .map(|name| (edit_distance(&unknown, &name.to_lowercase()), *name))
+        .filter(|(distance, _)| *distance <= allowed)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Levenshtein distance counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, left) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, right) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left != *right);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The scalar fields of a request's `_meta` (trace ids and the like) to echo on the result.
+/// The progress token, formatting fields and nested values are not echoed.
+fn echoed_client_meta(meta: Option<&Value>) -> Map<String, Value> {
+    meta.and_then(Value::as_object)
+        .map(|fields| {
+            fields
+                .iter()
+                .filter(|(key, value)| {
+                    key.as_str() != "progressToken"
+                        && !FORMAT_META_FIELDS.contains(&key.as_str())
+                        && (value.is_string() || value.is_number() || value.is_boolean())
+                })
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// JSON pointer and byte length of the first string longer than `MAX_ARGUMENT_STRING_BYTES`.
+fn oversized_string(value: &Value, pointer: String) -> Option<(String, usize)> {
+    match value {
+        Value::String(text) if text.len() > MAX_ARGUMENT_STRING_BYTES => {
+            Some((pointer, text.len()))
+        }
+        Value::Object(map) => map.iter().find_map(|(key, child)| {
+            oversized_string(child, format!("{pointer}/{}", escape_pointer(key)))
+        }),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .find_map(|(index, child)| oversized_string(child, format!("{pointer}/{index}"))),
+        _ => None,
+    }
+}
+
+/// The error for a request shed because every slot under `setting` stayed taken for
+/// `REQUEST_QUEUE_WAIT_SECS`.
+pub(crate) fn server_busy(id: Value, setting: &str) -> JsonRpcErrorResponse {
+    JsonRpcErrorResponse {
+        jsonrpc: JSON_RPC_VERSION.to_string(),
+        id,
+        error: JsonRpcError {
+            code: JsonRpcErrorCode::ServerBusy.code(),
+            message: "server busy".to_string(),
+            data: Some(json!({ "reason": "server busy, retry", "limit": setting })),
+        },
+    }
+}
+
+fn in_flight_key(context: &RequestContext, id: &Value) -> InFlightKey {
+    (
+        context.key_name.clone(),
+        context.session_id.clone().unwrap_or_default(),
+        id.to_string(),
+    )
+}
+
+fn metric_method_label(method: &str) -> &'static str {
+    METRIC_METHODS
+        .iter()
+        .find(|known| **known == method)
+        .copied()
+        .unwrap_or("other")
+}
+
+/// A `cacheOnly` call that found nothing cached answers with an empty result, not an error.
+fn cache_miss_result(error: AppError) -> Result<Value, AppError> {
+    match error {
+        AppError::NotCached { message } => {
+            tracing::debug!(%message, "cacheOnly call missed the cache");
+            Ok(json!({ "items": [], "cacheOnly": true, "cacheMiss": true }))
+        }
+        other => Err(other),
+    }
+}
+
+fn upstream_status(data: Option<&Value>) -> Option<u16> {
+    data.and_then(|value| value.get("status"))
+        .and_then(Value::as_u64)
+        .and_then(|status| u16::try_from(status).ok())
+}
+
+/// Classify a tool failure for clients. Upstream errors without a status are network
+/// failures, which are worth retrying along with 408, 429 and 5xx responses.
+fn describe_tool_error_data(error: &AppError) -> ToolErrorData {
+    match error {
+        AppError::Upstream { data, .. } => {
+            let status = upstream_status(data.as_ref());
+            let url = data
+                .as_ref()
+                .and_then(|value| value.get("url"))
+                .and_then(Value::as_str)
+                .map(redact_url);
+            let retryable = ToolErrorKind::Upstream.retryable(status);
+
+            ToolErrorData {
+                kind: ToolErrorKind::Upstream,
+                status,
+                url,
+                retryable,
+                timeout_secs: None,
+                correlation_id: None,
+                build: None,
+            }
+        }
+        AppError::Configuration { .. } => ToolErrorData {
+            kind: ToolErrorKind::Config,
+            status: None,
+            url: None,
+            retryable: false,
+            timeout_secs: None,
+            correlation_id: None,
+            build: None,
+        },
+        AppError::Internal { .. } => ToolErrorData {
+            kind: ToolErrorKind::Internal,
+            status: None,
+            url: None,
+            retryable: false,
+            timeout_secs: None,
+            correlation_id: None,
+            build: None,
+        },
+        AppError::BadRequest { .. } | AppError::NotCached { .. } => ToolErrorData {
+            kind: ToolErrorKind::BadRequest,
+            status: None,
+            url: None,
+            retryable: false,
+            timeout_secs: None,
+            correlation_id: None,
+            build: None,
+        },
+    }
+}
+
+fn read_lock(
+    filter: &RwLock<ToolFilterConfig>,
+) -> std::sync::RwLockReadGuard<'_, ToolFilterConfig> {
+    filter
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,280 @@
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::core::client_log::LogLevel;
+
+/// The session a request addresses: the name of the API key it was made with and its
+/// `Mcp-Session-Id`. Sessions belong to the key that started them, so other keys can
+/// neither see nor use them whatever session id they send, and each key has its own
+/// default session for requests without the header.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionRef<'a> {
+    pub owner: Option<&'a str>,
+    pub id: Option<&'a str>,
+}
+
+type SessionKey = (Option<String>, String);
+
+/// Sessions kept at once; past this, starting one evicts the least recently used.
+pub const MAX_SESSIONS: usize = 10_000;
+
+/// Terminated sessions remembered so requests under them are told the session ended;
+/// beyond this the oldest are forgotten and their ids become simply unknown.
+pub const MAX_TERMINATED_SESSIONS: usize = 1_000;
+
+impl SessionRef<'_> {
+    fn key(self) -> SessionKey {
+        (
+            self.owner.map(str::to_string),
+            self.id.unwrap_or_default().to_string(),
+        )
+    }
+}
+
+/// Lifecycle state of one MCP session.
+#[derive(Debug, Clone, Default)]
+pub struct SessionState {
+    pub negotiated_protocol: Option<String>,
+    /// Set by a successful `initialize`.
+    pub initialized: bool,
+    /// Set by the `initialized` notification that follows `initialize`.
+    pub ready: bool,
+    /// API key the session was initialized with; a later initialize must match it.
+    pub api_key: Option<String>,
+    pub client: Option<String>,
+    /// Minimum level for `notifications/message`, set by `logging/setLevel`.
+    pub log_level: Option<LogLevel>,
+}
+
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    sessions: Mutex<Sessions>,
+    /// Sessions ended by `DELETE /api/mcp`; requests under them fail until re-initialized.
+    terminated: Mutex<Terminated>,
+}
+
+/// Live sessions, each stamped with when it was last used, and the session issued to each
+/// key's latest header-less `initialize`, which the key's requests without the header use.
+#[derive(Debug, Default)]
+struct Sessions {
+    states: HashMap<SessionKey, (SessionState, u64)>,
+    defaults: HashMap<Option<String>, String>,
+    clock: u64,
+}
+
+impl Sessions {
+    fn touch(&mut self, key: &SessionKey) -> Option<&mut SessionState> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.states.get_mut(key).map(|(state, last_used)| {
+            *last_used = clock;
+            state
+        })
+    }
+
+    /// Store `state`, first evicting the least recently used session when a new one would
+    /// take the registry past [`MAX_SESSIONS`].
+    fn insert(&mut self, key: SessionKey, state: SessionState) -> Option<SessionState> {
+        if !self.states.contains_key(&key) && self.states.len() >= MAX_SESSIONS {
+            let oldest = self
+                .states
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.states.remove(&oldest);
+                if self.defaults.get(&oldest.0) == Some(&oldest.1) {
+                    self.defaults.remove(&oldest.0);
+                }
+                tracing::debug!(session = %oldest.1, "evicted least recently used session");
+            }
+        }
+        self.clock += 1;
+        self.states
+            .insert(key, (state, self.clock))
+            .map(|(previous, _)| previous)
+    }
+}
+
+/// Terminated sessions, oldest first in `order`.
+#[derive(Debug, Default)]
+struct Terminated {
+    keys: HashSet<SessionKey>,
+    order: VecDeque<SessionKey>,
+}
+
+impl Terminated {
+    fn insert(&mut self, key: SessionKey) {
+        if !self.keys.insert(key.clone()) {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > MAX_TERMINATED_SESSIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &SessionKey) {
+        if self.keys.remove(key) {
+            self.order.retain(|terminated| terminated != key);
+        }
+    }
+}
+
+impl SessionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, session: SessionRef) -> Option<SessionState> {
+        self.lock().touch(&session.key()).cloned()
+    }
+
+    /// Whether the key has a live or recently terminated session under this id.
+    pub fn is_known(&self, session: SessionRef) -> bool {
+        self.lock().states.contains_key(&session.key()) || self.is_terminated(session)
+    }
+
+    /// The session issued to the key's latest `initialize` sent without `Mcp-Session-Id`.
+    pub fn default_session(&self, owner: Option<&str>) -> Option<String> {
+        self.lock()
+            .defaults
+            .get(&owner.map(str::to_string))
+            .cloned()
+    }
+
+    /// Make `session` the one its key's requests without `Mcp-Session-Id` address.
+    pub fn set_default_session(&self, session: SessionRef) {
+        if let Some(id) = session.id {
+            self.lock()
+                .defaults
+                .insert(session.owner.map(str::to_string), id.to_string());
+        }
+    }
+
+    /// Start (or restart) a session. A repeat initialize discards all previous state,
+    /// including readiness and the negotiated protocol, unless it presents a different
+    /// API key, in which case the existing session is left untouched. Returns the state
+    /// that was replaced.
+    pub fn initialize(
+        &self,
+        session: SessionRef,
+        api_key: Option<String>,
+        client: String,
+        negotiated_protocol: String,
+    ) -> Result<Option<SessionState>, String> {
+        let mut sessions = self.lock();
+        let key = session.key();
+
+        if let Some((existing, _)) = sessions.states.get(&key) {
+            if existing.initialized && existing.api_key != api_key {
+                return Err(
+                    "initialize rejected: the session was initialized with a different API key"
+                        .to_string(),
+                );
+            }
+        }
+
+        lock_or_recover(&self.terminated).remove(&key);
+        let previous = sessions.insert(
+            key,
+            SessionState {
+                negotiated_protocol: Some(negotiated_protocol),
+                initialized: true,
+                ready: false,
+                api_key,
+                client: Some(client),
+                log_level: None,
+            },
+        );
+
+        Ok(previous)
+    }
+
+    /// Mark the session ready. Returns `false` when it has not been initialized.
+    pub fn mark_ready(&self, session: SessionRef) -> bool {
+        match self.lock().touch(&session.key()) {
+            Some(state) if state.initialized => {
+                state.ready = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Set the session's minimum log level. Returns `false` when it has not been initialized.
+    pub fn set_log_level(&self, session: SessionRef, level: LogLevel) -> bool {
+        match self.lock().touch(&session.key()) {
+            Some(state) if state.initialized => {
+                state.log_level = Some(level);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Forget the session entirely. Returns whether it existed.
+    pub fn reset(&self, session: SessionRef) -> bool {
+        self.lock().states.remove(&session.key()).is_some()
+    }
+
+    /// End a named session: its state is dropped and later requests under the id are
+    /// reported as terminated until it is initialized again. Returns whether it existed;
+    /// the default session, which has no id, cannot be terminated.
+    pub fn terminate(&self, session: SessionRef) -> bool {
+        if session.id.is_none() {
+            return false;
+        }
+        let key = session.key();
+        let existed = self.lock().states.remove(&key).is_some();
+        if existed {
+            lock_or_recover(&self.terminated).insert(key);
+        }
+        existed
+    }
+
+    pub fn is_terminated(&self, session: SessionRef) -> bool {
+        session.id.is_some()
+            && lock_or_recover(&self.terminated)
+                .keys
+                .contains(&session.key())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Sessions> {
+        lock_or_recover(&self.sessions)
+    }
+}
+
+/// A fresh session id: 32 hex characters, unpredictable and unique per process.
+pub fn new_session_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    let mut digest = Sha256::new();
+    digest.update(hasher.finish().to_le_bytes());
+    digest.update(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default()
+            .to_le_bytes(),
+    );
+    digest.update(std::process::id().to_le_bytes());
+    hex::encode(&digest.finalize()[..16])
+}
+
+fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| {
+        tracing::error!("session registry mutex poisoned; recovering");
+        PoisonError::into_inner(error)
+    })
+}
@@ -1,4 +1,9 @@
+pub mod audit;
+pub mod export;
 pub mod mcp;
 pub mod parliament;
+pub mod preferences;
+pub mod reference_data;
 pub mod research;
+pub mod usage;
 pub mod utilities;
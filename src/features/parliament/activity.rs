@@ -0,0 +1,126 @@
+//! Parsing for the sources `parliament.fetch_mp_activity` merges: spoken contributions from
+//! the Hansard API, written questions from the Written Questions and Statements API and
+//! early day motions from the Oral Questions and Motions API.
+
+use std::cmp::Reverse;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::features::parliament::dto::MpActivityEntry;
+use crate::features::parliament::hansard::parse_contributions;
+use crate::features::parliament::helpers::{json_day, json_items, json_text, plain_excerpt};
+use crate::features::parliament::written_questions::parse_questions;
+
+/// Longest `description` kept; questions and motions can run to hundreds of words.
+const MAX_DESCRIPTION_CHARS: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivitySource {
+    SpokenContributions,
+    WrittenQuestions,
+    EarlyDayMotions,
+}
+
+impl ActivitySource {
+    /// How the source is named in notes to the caller.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SpokenContributions => "spoken contributions",
+            Self::WrittenQuestions => "written questions",
+            Self::EarlyDayMotions => "early day motions",
+        }
+    }
+}
+
+/// Entries from a Hansard API `search/contributions/Spoken.json` response.
+pub fn parse_spoken_contributions(payload: &Value) -> Vec<MpActivityEntry> {
+    parse_contributions(payload)
+        .into_iter()
+        .map(|contribution| MpActivityEntry {
+            id: format!("contribution-{}", contribution.id),
+            date: contribution.sitting_date,
+            activity_type: "Spoken contribution".to_string(),
+            title: contribution.title,
+            description: contribution.snippet,
+            url: Some(contribution.url),
+        })
+        .collect()
+}
+
+/// Entries from a Written Questions API `writtenquestions/questions` response.
+pub fn parse_written_questions(payload: &Value) -> Vec<MpActivityEntry> {
+    parse_questions(payload)
+        .into_iter()
+        .map(|question| {
+            let title = question
+                .heading
+                .or_else(|| question.uin.map(|uin| format!("Written question {uin}")))
+                .unwrap_or_else(|| "Written question".to_string());
+            let description = match question.answering_body {
+                Some(body) => format!("To {body}: {}", question.question_text),
+                None => question.question_text,
+            };
+            MpActivityEntry {
+                id: format!("written-question-{}", question.id),
+                date: question.date_tabled,
+                activity_type: "Written question".to_string(),
+                title,
+                description: plain_excerpt(&description, MAX_DESCRIPTION_CHARS),
+                url: question.url,
+            }
+        })
+        .collect()
+}
+
+/// Entries from an Oral Questions and Motions API `EarlyDayMotions/list` response, dated
+/// when `mp_id` signed: their sponsorship date, else the day the motion was tabled.
+pub fn parse_early_day_motions(payload: &Value, mp_id: u32) -> Vec<MpActivityEntry> {
+    json_items(payload, "Response")
+        .filter_map(|item| {
+            let id = value_id(item.get("Id")?)?;
+            let signed = item
+                .get("Sponsors")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .find(|sponsor| {
+                    sponsor.get("MemberId").and_then(Value::as_u64) == Some(u64::from(mp_id))
+                })
+                .and_then(|sponsor| json_day(sponsor, "CreatedWhen"));
+            let date = signed.or_else(|| json_day(item, "DateTabled"))?;
+            Some(MpActivityEntry {
+                id: format!("edm-{id}"),
+                date,
+                activity_type: "EDM".to_string(),
+                title: json_text(item, "Title").unwrap_or_else(|| format!("Early day motion {id}")),
+                description: plain_excerpt(
+                    &json_text(item, "MotionText").unwrap_or_default(),
+                    MAX_DESCRIPTION_CHARS,
+                ),
+                url: Some(format!("https://edm.parliament.uk/early-day-motion/{id}")),
+            })
+        })
+        .collect()
+}
+
+/// Newest first, keeping at most `limit`. Entries on the same day stay in source order.
+pub fn merge_activity(
+    sources: impl IntoIterator<Item = Vec<MpActivityEntry>>,
+    limit: usize,
+) -> Vec<MpActivityEntry> {
+    let mut entries: Vec<MpActivityEntry> = sources.into_iter().flatten().collect();
+    entries.sort_by_key(|entry| Reverse(entry.date.clone()));
+    entries.truncate(limit);
+    entries
+}
+
+fn value_id(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(number) => Some(number.to_string()),
+        Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,116 @@
+//! A bill's progress, sponsors and publications from the Bills API, behind
+//! `parliament.fetch_bill_detail`.
+
+use serde_json::Value;
+
+use crate::features::parliament::bill_stages::describe_bill_stage;
+use crate::features::parliament::client::BILLS_BASE;
+use crate::features::parliament::dto::{
+    BillDetail, BillProgressStage, BillPublication, BillSponsor, PublicationLink,
+};
+use crate::features::parliament::helpers::{json_day, json_items, json_text};
+
+/// Assemble a bill from its `Bills/{id}` and `Bills/{id}/Stages` responses; publications
+/// are added by the caller. `None` when the bill payload has no short title.
+pub fn parse_bill_detail(bill_id: u32, bill: &Value, stages: &Value) -> Option<BillDetail> {
+    Some(BillDetail {
+        bill_id,
+        short_title: json_text(bill, "shortTitle")?,
+        long_title: json_text(bill, "longTitle"),
+        current_house: json_text(bill, "currentHouse"),
+        current_stage: bill.get("currentStage").and_then(parse_stage),
+        is_act: bill.get("isAct").and_then(Value::as_bool).unwrap_or(false),
+        withdrawn: bill
+            .get("billWithdrawn")
+            .is_some_and(|value| !value.is_null()),
+        defeated: bill
+            .get("isDefeated")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        sponsors: json_items(bill, "sponsors")
+            .filter_map(parse_sponsor)
+            .collect(),
+        stages: json_items(stages, "items")
+            .filter_map(parse_stage)
+            .collect(),
+        publications: Vec::new(),
+        link: format!("https://bills.parliament.uk/bills/{bill_id}"),
+        note: None,
+    })
+}
+
+/// Publications from a `Bills/{id}/Publications` response, oldest first, each with its
+/// web links and downloadable files.
+pub fn parse_publications(payload: &Value) -> Vec<BillPublication> {
+    let mut publications: Vec<BillPublication> = json_items(payload, "publications")
+        .filter_map(|publication| {
+            let id = publication.get("id").and_then(Value::as_u64);
+            let mut links: Vec<PublicationLink> = json_items(publication, "links")
+                .filter_map(|link| {
+                    Some(PublicationLink {
+                        title: json_text(link, "title"),
+                        url: json_text(link, "url")?,
+                        content_type: json_text(link, "contentType"),
+                    })
+                })
+                .collect();
+            if let Some(id) = id {
+                links.extend(json_items(publication, "files").filter_map(|file| {
+                    let file_id = file.get("id").and_then(Value::as_u64)?;
+                    Some(PublicationLink {
+                        title: json_text(file, "filename"),
+                        url: format!("{BILLS_BASE}/Publications/{id}/Documents/{file_id}/Download"),
+                        content_type: json_text(file, "contentType"),
+                    })
+                }));
+            }
+            Some(BillPublication {
+                title: json_text(publication, "title")?,
+                publication_type: publication
+                    .get("publicationType")
+                    .and_then(|kind| json_text(kind, "name")),
+                house: json_text(publication, "house"),
+                date: json_day(publication, "displayDate"),
+                links,
+            })
+        })
+        .collect();
+    publications.sort_by(|a, b| a.date.cmp(&b.date));
+    publications
+}
+
+/// A stage as the Bills API lists it, dated by its first sitting.
+fn parse_stage(item: &Value) -> Option<BillProgressStage> {
+    let stage = json_text(item, "description")?;
+    let sittings: Vec<String> = json_items(item, "stageSittings")
+        .filter_map(|sitting| json_day(sitting, "date"))
+        .collect();
+    Some(BillProgressStage {
+        explanation: describe_bill_stage(&stage).explanation,
+        stage,
+        abbreviation: json_text(item, "abbreviation"),
+        house: json_text(item, "house"),
+        date: sittings.iter().min().cloned(),
+        sittings: sittings.len(),
+    })
+}
+
+fn parse_sponsor(item: &Value) -> Option<BillSponsor> {
+    let member = item.get("member");
+    let organisation = item
+        .get("organisation")
+        .and_then(|organisation| json_text(organisation, "name"));
+    let name = member.and_then(|member| json_text(member, "name"));
+    if name.is_none() && organisation.is_none() {
+        return None;
+    }
+    Some(BillSponsor {
+        member_id: member
+            .and_then(|member| member.get("memberId"))
+            .and_then(Value::as_u64)
+            .and_then(|id| u32::try_from(id).ok()),
+        name,
+        party: member.and_then(|member| json_text(member, "party")),
+        organisation,
+    })
+}
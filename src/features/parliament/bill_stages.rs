@@ -0,0 +1,137 @@
+/// A bill stage as it appears in the Bills API, with a plain-English explanation for
+/// letters and briefs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BillStage {
+    /// Abbreviation used by the Bills API (e.g. `2R`).
+    pub code: &'static str,
+    pub name: &'static str,
+    pub explanation: &'static str,
+    /// Other spellings of the full name the API uses.
+    pub aliases: &'static [&'static str],
+}
+
+/// Stages of both Houses, including Lords-only committee routes and ping-pong.
+pub const BILL_STAGES: &[BillStage] = &[
+    BillStage {
+        code: "1R",
+        name: "1st reading",
+        explanation: "The bill is formally introduced and published; there is no debate.",
+        aliases: &["first reading"],
+    },
+    BillStage {
+        code: "2R",
+        name: "2nd reading",
+        explanation: "The first debate on the general principles of the bill, followed by a vote on whether it should continue.",
+        aliases: &["second reading"],
+    },
+    BillStage {
+        code: "2RC",
+        name: "Second reading committee",
+        explanation: "The second reading debate is held in a committee rather than on the floor of the House, usually for uncontroversial bills.",
+        aliases: &["2nd reading committee"],
+    },
+    BillStage {
+        code: "CS",
+        name: "Committee stage",
+        explanation: "The bill is examined line by line and amendments to individual clauses are debated.",
+        aliases: &["committee"],
+    },
+    BillStage {
+        code: "PBC",
+        name: "Public bill committee",
+        explanation: "Commons committee stage taken by a small committee of MPs, which can also hear evidence from outside experts.",
+        aliases: &["public bill committee stage"],
+    },
+    BillStage {
+        code: "CWH",
+        name: "Committee of the whole House",
+        explanation: "Committee stage taken on the floor of the House so that every member can propose and vote on amendments.",
+        aliases: &["committee of the whole house stage"],
+    },
+    BillStage {
+        code: "GC",
+        name: "Grand Committee",
+        explanation: "Lords committee stage held away from the chamber; any peer can take part but amendments can only be agreed unanimously.",
+        aliases: &["grand committee stage"],
+    },
+    BillStage {
+        code: "PRS",
+        name: "Pre-report stage",
+        explanation: "The bill has left committee and amendments are being tabled ahead of report stage.",
+        aliases: &[],
+    },
+    BillStage {
+        code: "RS",
+        name: "Report stage",
+        explanation: "The whole House considers the bill as amended in committee and can make further amendments.",
+        aliases: &["report", "consideration of bill as amended"],
+    },
+    BillStage {
+        code: "3R",
+        name: "3rd reading",
+        explanation: "The final debate on the bill in this House; in the Lords, tidying-up amendments can still be made.",
+        aliases: &["third reading"],
+    },
+    BillStage {
+        code: "CLA",
+        name: "Consideration of Lords amendments",
+        explanation: "Ping-pong: the Commons decides whether to accept the changes the Lords made to the bill.",
+        aliases: &["commons consideration of lords amendments"],
+    },
+    BillStage {
+        code: "CCA",
+        name: "Consideration of Commons amendments",
+        explanation: "Ping-pong: the Lords decides whether to accept the Commons' response to its amendments.",
+        aliases: &["lords consideration of commons amendments"],
+    },
+    BillStage {
+        code: "PP",
+        name: "Ping-pong",
+        explanation: "The bill is passing between the two Houses until both agree on the exact wording.",
+        aliases: &["ping pong"],
+    },
+    BillStage {
+        code: "RA",
+        name: "Royal Assent",
+        explanation: "The monarch has agreed to the bill, making it an Act of Parliament.",
+        aliases: &[],
+    },
+];
+
+/// What to show for a stage value taken from the Bills API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageDescription {
+    /// The full stage name, or the raw value when it is not in the dictionary.
+    pub name: String,
+    pub code: Option<String>,
+    pub explanation: Option<String>,
+}
+
+/// Find a stage by its code or any of its names, ignoring case.
+pub fn find_bill_stage(value: &str) -> Option<&'static BillStage> {
+    let value = value.trim();
+    BILL_STAGES.iter().find(|stage| {
+        stage.code.eq_ignore_ascii_case(value)
+            || stage.name.eq_ignore_ascii_case(value)
+            || stage
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(value))
+    })
+}
+
+/// Expand a raw stage code or name; unknown values pass through unchanged.
+pub fn describe_bill_stage(raw: &str) -> StageDescription {
+    match find_bill_stage(raw) {
+        Some(stage) => StageDescription {
+            name: stage.name.to_string(),
+            code: Some(stage.code.to_string()),
+            explanation: Some(stage.explanation.to_string()),
+        },
+        None => StageDescription {
+            name: raw.trim().to_string(),
+            code: None,
+            explanation: None,
+        },
+    }
+}
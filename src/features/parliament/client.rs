@@ -1,38 +1,175 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use arc_swap::ArcSwap;
 use chrono::NaiveDate;
+use futures::future::join_all;
+use futures::stream::{self, BoxStream, StreamExt};
 use reqwest::Url;
 use roxmltree::Document;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use sled::Tree;
+use tokio::sync::mpsc;
 use tokio::time::sleep;
 
 use crate::config::AppConfig;
-use crate::core::cache::CacheManager;
+use crate::core::adaptive_limit::AdaptiveLimiter;
+use crate::core::availability::AvailabilityTracker;
+use crate::core::byte_budget::{budget_exhausted, record_bytes};
+use crate::core::cache::{CacheCounters, CacheManager, CachePolicy};
+use crate::core::call_context::{current_tool, record_cache_hit, record_upstream_fetch};
+use crate::core::clock::Clock;
+use crate::core::correlation::{CORRELATION_HEADER, current_correlation_id};
 use crate::core::error::AppError;
-use crate::core::http_client::build_http_client;
+#[cfg(feature = "fault-injection")]
+use crate::core::fault_injection::{self, FaultConfig};
+use crate::core::http_client::{build_http_client, redact_url};
+use crate::core::metrics::UpstreamMetrics;
+use crate::core::rate_limit::RateLimiter;
+#[cfg(feature = "record")]
+use crate::core::recording::UpstreamRecorder;
+use crate::core::stats::StatsRegistry;
+use crate::features::parliament::activity::{
+    ActivitySource, merge_activity, parse_early_day_motions, parse_spoken_contributions,
+    parse_written_questions,
+};
+use crate::features::parliament::bill_detail::{parse_bill_detail, parse_publications};
+use crate::features::parliament::committees::{
+    self, parse_committee, parse_committees, parse_inquiries,
+    parse_members as parse_committee_members,
+};
+use crate::features::parliament::datasets::{ExportOptions, find_dataset, stream_dataset};
+use crate::features::parliament::divisions::{
+    DivisionHouse, RawDivision, group_divisions, member_vote_record, parse_division_detail,
+    parse_divisions, parse_member_votes, parse_stage_sittings,
+};
 use crate::features::parliament::dto::{
-    ConstituencyLookupResult, FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs,
-    FetchMpActivityArgs, FetchMpVotingRecordArgs, LookupConstituencyArgs, MemberInfo,
-    MpActivityEntry, MpVoteRecord, SearchUkLawArgs, UkLawResult,
+    AnsweredStatus, BillDetail, CommitteeHouse, ConstituencyListing, ConstituencyLookupResult,
+    CurrentMp, DivisionDetail, EdmStatus, ExportVotingRecordArgs, FetchBillDetailArgs,
+    FetchBillDivisionsArgs, FetchBillDivisionsResult, FetchBillsArgs, FetchCommitteesArgs,
+    FetchCommitteesResult, FetchCoreDatasetArgs, FetchDivisionDetailArgs, FetchEdmsArgs,
+    FetchEdmsResult, FetchLegislationArgs, FetchLegislationTextArgs, FetchMemberRolesArgs,
+    FetchMembersBulkArgs, FetchMembersBulkResult, FetchMpActivityArgs, FetchMpVotingRecordArgs,
+    FetchOralQuestionsArgs, FetchOralQuestionsResult, FetchStatutoryInstrumentsArgs,
+    FetchStatutoryInstrumentsResult, FetchWrittenQuestionsArgs, FetchWrittenQuestionsResult,
+    LegislationText, ListConstituenciesArgs, ListConstituenciesResult, LookupConstituencyArgs,
+    MemberDivisionVote, MemberLookupEntry, MemberRolesResult, MemberSummary, MpActivityEntry,
+    MpActivityResult, MpVoteRecord, OralQuestionStatus, SearchHansardArgs, SearchHansardResult,
+    SearchUkLawArgs, UkLawResult,
+};
+use crate::features::parliament::edms::{self, parse_motions, parse_signatories};
+use crate::features::parliament::hansard::{parse_contributions, total_results};
+use crate::features::parliament::helpers::{
+    is_plausible_postcode, json_items, normalise_postcode, read_cache, write_cache,
+};
+use crate::features::parliament::legislation_text::parse_legislation_text;
+use crate::features::parliament::member_roles::{parse_committee_roles, parse_posts, sort_roles};
+use crate::features::parliament::oral_questions;
+use crate::features::parliament::policy_areas::{PolicyAreaClassifier, matches_bill_id};
+use crate::features::parliament::relevance::RelevanceFilter;
+use crate::features::parliament::statutory_instruments::{attach_numbers, parse_instruments};
+use crate::features::parliament::uk_law::parse_uk_law_feed;
+use crate::features::parliament::written_questions::{self, parse_questions};
+use crate::features::reference_data::{
+    ReferenceDataStore, RefreshReferenceDataArgs, RefreshReport, RefreshStatus,
 };
-use crate::features::parliament::helpers::{normalise_postcode, read_cache, write_cache};
 
-const CORE_DATASET_BASE: &str = "https://lda.data.parliament.uk";
-const MEMBERS_API_BASE: &str = "https://members-api.parliament.uk/api/Members/search";
-const BILLS_BASE: &str = "https://bills-api.parliament.uk/api/v1";
+pub(crate) const CORE_DATASET_BASE: &str = "https://lda.data.parliament.uk";
+pub(crate) const MEMBERS_API_BASE: &str = "https://members-api.parliament.uk/api/Members/search";
+pub(crate) const BILLS_BASE: &str = "https://bills-api.parliament.uk/api/v1";
 const LEGISLATION_BASE: &str = "https://www.legislation.gov.uk";
+/// Hosts of the bases above, as [`ParliamentClient::host_down`] names them.
+pub(crate) const BILLS_HOST: &str = "bills-api.parliament.uk";
+pub(crate) const CORE_DATASET_HOST: &str = "lda.data.parliament.uk";
+pub(crate) const LEGISLATION_HOST: &str = "www.legislation.gov.uk";
+/// Largest legislation.gov.uk `data.xml` read; whole Acts can run to many megabytes.
+pub const MAX_LEGISLATION_XML_BYTES: usize = 2_000_000;
 const RETRY_ATTEMPTS: usize = 3;
 const RETRY_DELAY_MS: u64 = 500;
 const MEMBERS_SEARCH_BASE: &str = "https://members-api.parliament.uk/api/Members/Search";
+const MEMBERS_BASE: &str = "https://members-api.parliament.uk/api/Members";
+/// Sled key written and removed by the storage health check.
+const HEALTH_KEY: &str = "health";
+const POSTCODES_BASE: &str = "https://api.postcodes.io/postcodes";
+pub const MAX_BULK_MEMBER_IDS: usize = 100;
+const BULK_MEMBER_CONCURRENCY: usize = 8;
+const LOCATION_CONSTITUENCY_SEARCH: &str =
+    "https://members-api.parliament.uk/api/Location/Constituency/Search";
+const CONSTITUENCY_LIST_KEY: &str = "constituency_list";
+const CONSTITUENCY_MPS_KEY: &str = "constituency_list_mps";
+/// The Members API caps `take` at 20.
+const LIST_PAGE_SIZE: usize = 20;
+/// Far above the ~33 pages 650 constituencies need; guards against a non-advancing API.
+const MAX_LIST_PAGES: usize = 100;
+const COMMONS_DIVISIONS_SEARCH: &str =
+    "https://commonsvotes-api.parliament.uk/data/divisions.json/search";
+const LORDS_DIVISIONS_SEARCH: &str = "https://lordsvotes-api.parliament.uk/data/Divisions/search";
+const DIVISION_LOOKUP_CONCURRENCY: usize = 4;
+const COMMONS_DIVISION: &str = "https://commonsvotes-api.parliament.uk/data/division";
+const LORDS_DIVISION: &str = "https://lordsvotes-api.parliament.uk/data/Divisions";
+const COMMONS_MEMBER_VOTING: &str =
+    "https://commonsvotes-api.parliament.uk/data/divisions.json/membervoting";
+const LORDS_MEMBER_VOTING: &str =
+    "https://lordsvotes-api.parliament.uk/data/Divisions/membervoting";
+/// The Commons Votes API caps `take` at 25; Lords pages are read at the same size.
+const MEMBER_VOTING_PAGE_SIZE: usize = 25;
+/// Pages `parliament.fetch_mp_voting_record` reads looking for divisions matching a
+/// `billId` filter; four are enough for 100 unfiltered votes.
+const MAX_VOTING_RECORD_PAGES: usize = 20;
+const DEFAULT_VOTING_EXPORT_ROWS: usize = 1000;
+pub const MAX_VOTING_EXPORT_ROWS: usize = 5000;
+const HANSARD_SPOKEN_CONTRIBUTIONS: &str =
+    "https://hansard-api.parliament.uk/search/contributions/Spoken.json";
+const WRITTEN_QUESTIONS: &str =
+    "https://questions-statements-api.parliament.uk/api/writtenquestions/questions";
+const EARLY_DAY_MOTIONS: &str =
+    "https://oralquestionsandmotions-api.parliament.uk/EarlyDayMotions/list";
+const ORAL_QUESTIONS: &str = "https://oralquestionsandmotions-api.parliament.uk/oralquestions/list";
+const EARLY_DAY_MOTION: &str = "https://oralquestionsandmotions-api.parliament.uk/EarlyDayMotion";
+/// Motions `parliament.fetch_edms` lists signatories for; each costs a request.
+pub const MAX_EDM_SIGNATORY_LOOKUPS: usize = 5;
+const COMMITTEES: &str = "https://committees-api.parliament.uk/api/Committees";
+const COMMITTEE_MEMBERS: &str = "https://committees-api.parliament.uk/api/Members";
+const COMMITTEE_BUSINESS: &str = "https://committees-api.parliament.uk/api/CommitteeBusiness";
+/// Committees whose open inquiries `includeInquiries` lists, at one request each.
+pub const MAX_COMMITTEE_INQUIRY_LOOKUPS: usize = 5;
+const STATUTORY_INSTRUMENTS: &str =
+    "https://statutoryinstruments-api.parliament.uk/api/v2/StatutoryInstrument";
+/// Instruments `parliament.fetch_statutory_instruments` reads before filtering.
+pub const SI_CANDIDATES: usize = 100;
+/// The Hansard API's page size for `parliament.search_hansard`.
+const HANSARD_PAGE_SIZE: usize = 20;
+/// Entries `parliament.fetch_mp_activity` reads from each source, and keeps in its cache:
+/// the largest `limit` it accepts.
+const ACTIVITY_PER_SOURCE: usize = 50;
+/// Relevance-filtered views are cheap to rebuild from the cached response, so they are
+/// only kept long enough to serve a burst of identical searches.
+const RELEVANCE_VIEW_TTL_SECS: u64 = 60;
+const RELEVANCE_VIEW_CAPACITY: u64 = 256;
 
 pub struct ParliamentClient {
     config: Arc<AppConfig>,
     cache: CacheManager,
+    /// Relevance-filtered views of cached responses; see [`ParliamentClient::relevant_view`].
+    views: CacheManager,
     http_client: reqwest::Client,
     cache_tree: Tree,
+    stats: Arc<StatsRegistry>,
+    rate_limiter: RateLimiter,
+    concurrency: AdaptiveLimiter,
+    upstream_metrics: Arc<UpstreamMetrics>,
+    availability: AvailabilityTracker,
+    upstream_base: Option<Url>,
+    /// Swapped when `admin.refresh_reference_data` downloads a new mapping.
+    policy_areas: ArcSwap<PolicyAreaClassifier>,
+    reference_data: ReferenceDataStore,
+    clock: Clock,
+    #[cfg(feature = "record")]
+    recorder: Option<UpstreamRecorder>,
+    #[cfg(feature = "fault-injection")]
+    faults: ArcSwap<FaultConfig>,
 }
 
 impl ParliamentClient {
@@ -40,16 +177,170 @@ impl ParliamentClient {
         config: Arc<AppConfig>,
         cache: CacheManager,
         cache_tree: Tree,
+        stats: Arc<StatsRegistry>,
     ) -> Result<Self, AppError> {
         let http_client = build_http_client(config.disable_proxy)
             .map_err(|err| AppError::internal(format!("failed to build HTTP client: {err}")))?;
+        let upstream_base = config
+            .upstream_base_url
+            .as_deref()
+            .map(Url::parse)
+            .transpose()
+            .map_err(|err| AppError::configuration(format!("invalid UPSTREAM_BASE_URL: {err}")))?;
+        let rate_limiter = RateLimiter::new(config.upstream_requests_per_second);
+        let concurrency = AdaptiveLimiter::new(
+            config.upstream_concurrency_floor,
+            config.upstream_concurrency_ceiling,
+        );
+        let reference_data = ReferenceDataStore::new(config.reference_data_dir.as_deref());
+        let policy_areas =
+            PolicyAreaClassifier::load(config.policy_areas_path.as_deref(), &reference_data)?;
+        #[cfg(feature = "record")]
+        let recorder = config
+            .upstream_record_dir
+            .as_deref()
+            .map(UpstreamRecorder::new)
+            .transpose()?;
+        #[cfg(not(feature = "record"))]
+        if config.upstream_record_dir.is_some() {
+            tracing::warn!("UPSTREAM_RECORD_DIR is ignored: built without the `record` feature");
+        }
+        #[cfg(feature = "fault-injection")]
+        let faults = FaultConfig::parse(config.faults.as_deref().unwrap_or_default())?;
+        #[cfg(not(feature = "fault-injection"))]
+        if config.faults.is_some() {
+            tracing::warn!("FAULTS is ignored: built without the `fault-injection` feature");
+        }
+
+        let availability = AvailabilityTracker::new(
+            config.upstream_down_after_failures,
+            config.upstream_down_secs,
+        );
 
         Ok(Self {
+            views: CacheManager::new(config.cache_enabled, RELEVANCE_VIEW_CAPACITY),
             config,
             cache,
             http_client,
             cache_tree,
+            stats,
+            rate_limiter,
+            concurrency,
+            upstream_metrics: Arc::new(UpstreamMetrics::new()),
+            availability,
+            upstream_base,
+            policy_areas: ArcSwap::from_pointee(policy_areas),
+            reference_data,
+            clock: Clock::system(),
+            #[cfg(feature = "record")]
+            recorder,
+            #[cfg(feature = "fault-injection")]
+            faults: ArcSwap::from_pointee(faults),
+        })
+    }
+
+    /// Measure Sled cache freshness with `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock behind Sled cache freshness.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Hit and miss counts of the in-memory response cache.
+    pub fn cache_counters(&self) -> CacheCounters {
+        self.cache.counters()
+    }
+
+    /// Entries in the in-memory response cache.
+    pub async fn cache_entries(&self) -> usize {
+        self.cache.entry_count().await
+    }
+
+    /// Prove the Sled cache tree is writable by inserting and removing a `health` key.
+    pub async fn check_storage(&self) -> Result<(), AppError> {
+        let tree = self.cache_tree.clone();
+        tokio::task::spawn_blocking(move || -> Result<(), AppError> {
+            tree.insert(HEALTH_KEY, b"ok".to_vec())
+                .and_then(|_| tree.remove(HEALTH_KEY))
+                .map(|_| ())
+                .map_err(|err| AppError::internal(format!("sled health check failed: {err}")))
         })
+        .await
+        .map_err(|err| AppError::internal(format!("sled health task join error: {err}")))?
+    }
+
+    /// Send one HEAD request to the Members API, bypassing retries. `Ok(false)` means it
+    /// did not answer within `timeout`.
+    pub async fn probe_members_api(&self, timeout: Duration) -> Result<bool, AppError> {
+        let url = Url::parse(MEMBERS_BASE)
+            .map_err(|err| AppError::internal(format!("invalid members url: {err}")))?;
+        let target = self.resolve_upstream(&url);
+        self.rate_limiter
+            .acquire(target.host_str().unwrap_or_default())
+            .await;
+
+        match self.http_client.head(target).timeout(timeout).send().await {
+            Ok(response) if response.status().is_server_error() => {
+                Err(AppError::upstream_with_data(
+                    format!("members API answered {}", response.status()),
+                    json!({ "status": response.status().as_u16() }),
+                ))
+            }
+            Ok(_) => Ok(true),
+            Err(err) if err.is_timeout() => Ok(false),
+            Err(err) => Err(AppError::upstream_with_data(
+                format!("network error contacting members API: {err}"),
+                json!({ "url": redact_url(url.as_str()) }),
+            )),
+        }
+    }
+
+    /// Current adaptive concurrency limit of every upstream host contacted so far.
+    pub fn concurrency_limits(&self) -> BTreeMap<String, usize> {
+        self.concurrency.limits()
+    }
+
+    /// Per-host request, retry and latency metrics of every upstream call made so far.
+    pub fn upstream_metrics(&self) -> &UpstreamMetrics {
+        &self.upstream_metrics
+    }
+
+    pub fn policy_areas(&self) -> Arc<PolicyAreaClassifier> {
+        self.policy_areas.load_full()
+    }
+
+    /// Download the datasets listed in `REFERENCE_DATA_MANIFEST_URL` into
+    /// `REFERENCE_DATA_DIR` and start using the ones that were updated.
+    pub async fn refresh_reference_data(
+        &self,
+        args: RefreshReferenceDataArgs,
+    ) -> Result<RefreshReport, AppError> {
+        let manifest_url = self
+            .config
+            .reference_data_manifest_url
+            .as_deref()
+            .ok_or_else(|| AppError::configuration("REFERENCE_DATA_MANIFEST_URL is not set"))?;
+        let report = self
+            .reference_data
+            .refresh(&self.http_client, manifest_url, args.datasets.as_deref())
+            .await?;
+
+        if report
+            .datasets
+            .iter()
+            .any(|dataset| dataset.status == RefreshStatus::Updated)
+        {
+            let policy_areas = PolicyAreaClassifier::load(
+                self.config.policy_areas_path.as_deref(),
+                &self.reference_data,
+            )?;
+            self.policy_areas.store(Arc::new(policy_areas));
+        }
+        Ok(report)
     }
 
     pub async fn fetch_core_dataset(&self, args: FetchCoreDatasetArgs) -> Result<Value, AppError> {
@@ -59,44 +350,145 @@ impl ParliamentClient {
             page,
             per_page,
             enable_cache,
+            cache_only,
             fuzzy_match,
             apply_relevance,
             relevance_threshold,
+            // Projection is applied by the handler, after caching.
+            fields: _,
         } = args;
 
-        let cache_enabled = enable_cache.unwrap_or(true);
-        let apply_relevance = apply_relevance.unwrap_or(false);
-        let relevance_threshold = relevance_threshold.unwrap_or(self.config.relevance_threshold);
-        let fuzzy_match = fuzzy_match.unwrap_or(false);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let relevance = self.relevance_filter(
+            apply_relevance,
+            relevance_threshold,
+            fuzzy_match.unwrap_or(false),
+        );
+        let search_term = sanitise_optional_text(search_term);
 
-        match dataset.as_str() {
+        let payload = match dataset.as_str() {
             "members" | "commonsmembers" | "lordsmembers" => {
-                self.fetch_members_dataset(
-                    dataset,
-                    search_term,
-                    page,
-                    per_page,
-                    cache_enabled,
-                    apply_relevance,
-                    relevance_threshold,
-                    fuzzy_match,
-                )
-                .await
+                self.fetch_members_dataset(&dataset, search_term.as_deref(), page, per_page, policy)
+                    .await?
             }
             _ => {
                 self.fetch_legacy_core_dataset(
-                    dataset,
-                    search_term,
+                    &dataset,
+                    search_term.as_deref(),
                     page,
                     per_page,
-                    cache_enabled,
-                    apply_relevance,
-                    relevance_threshold,
-                    fuzzy_match,
+                    policy,
                 )
-                .await
+                .await?
             }
+        };
+        Ok(self
+            .relevant_view(payload, search_term.as_deref(), relevance)
+            .await)
+    }
+
+    /// The filter `applyRelevance`, `relevanceThreshold` and `fuzzyMatch` ask for; `None`
+    /// unless relevance filtering was requested.
+    fn relevance_filter(
+        &self,
+        apply_relevance: Option<bool>,
+        relevance_threshold: Option<f32>,
+        fuzzy: bool,
+    ) -> Option<RelevanceFilter> {
+        apply_relevance.unwrap_or(false).then(|| RelevanceFilter {
+            threshold: relevance_threshold.unwrap_or(self.config.relevance_threshold),
+            fuzzy,
+        })
+    }
+
+    /// `payload`, a cached or fresh upstream response, narrowed by `relevance` to the items
+    /// matching `search_term`. Filtering happens after the response cache, so one cached
+    /// response serves every threshold; filtered views are memoised briefly by payload
+    /// hash, term, threshold and fuzziness.
+    async fn relevant_view(
+        &self,
+        payload: Value,
+        search_term: Option<&str>,
+        relevance: Option<RelevanceFilter>,
+    ) -> Value {
+        let (Some(relevance), Some(term)) = (relevance, search_term) else {
+            return payload;
+        };
+        let key = relevance.view_key(&payload, term);
+        if let Some(view) = self.views.get(&key).await {
+            return view;
+        }
+        let view = relevance.apply(payload, term);
+        self.views
+            .insert(key, view.clone(), RELEVANCE_VIEW_TTL_SECS)
+            .await;
+        view
+    }
+
+    /// Stream every record of a registered dataset for the NDJSON export endpoint. Pages
+    /// bypass the response cache since each is only read once.
+    pub fn export_dataset(
+        self: &Arc<Self>,
+        dataset: &str,
+        options: ExportOptions,
+    ) -> Result<mpsc::Receiver<Result<Value, AppError>>, AppError> {
+        let descriptor = find_dataset(dataset)
+            .ok_or_else(|| AppError::bad_request(format!("unknown dataset: {dataset}")))?;
+        if options.since.is_some() && !descriptor.supports_since() {
+            return Err(AppError::bad_request(format!(
+                "dataset {} does not support the since filter",
+                descriptor.name
+            )));
         }
+
+        let client = Arc::clone(self);
+        Ok(stream_dataset(descriptor, options, move |url| {
+            let client = client.clone();
+            async move { client.get_json(url).await }
+        }))
+    }
+
+    /// Page through an MP's Commons votes, newest first, yielding one page of votes per
+    /// upstream request. Pages are only requested as the stream is polled and bypass the
+    /// caches; an upstream error ends the stream as its last item, and no further page is
+    /// requested once the call's byte budget is used up.
+    pub fn voting_record_pages(
+        self: &Arc<Self>,
+        args: &ExportVotingRecordArgs,
+    ) -> Result<BoxStream<'static, Result<Vec<MemberDivisionVote>, AppError>>, AppError> {
+        let from_date = parse_optional_date("fromDate", args.from_date.as_deref())?;
+        let to_date = parse_optional_date("toDate", args.to_date.as_deref())?;
+        let max_rows = voting_export_limit(args.limit);
+
+        let base = member_voting_url(DivisionHouse::Commons, args.mp_id, from_date, to_date)?;
+
+        let client = Arc::clone(self);
+        // State: rows emitted so far, or `None` once the last page has been yielded.
+        let pages = stream::unfold(Some(0usize), move |emitted| {
+            let client = Arc::clone(&client);
+            let base = base.clone();
+            async move {
+                let skip = emitted?;
+                if skip > 0 && budget_exhausted() {
+                    return None;
+                }
+                let url = member_voting_page(DivisionHouse::Commons, base, skip);
+                let mut votes = match client.get_json(url).await {
+                    Ok(payload) => parse_member_votes(DivisionHouse::Commons, &payload),
+                    Err(error) => return Some((Err(error), None)),
+                };
+                let last_page = votes.len() < MEMBER_VOTING_PAGE_SIZE;
+                votes.truncate(max_rows - skip);
+                let emitted = skip + votes.len();
+                let next = (!last_page && emitted < max_rows).then_some(emitted);
+                // An empty first page is still yielded so the export carries its header.
+                if votes.is_empty() && skip > 0 {
+                    return None;
+                }
+                Some((Ok(votes), next))
+            }
+        });
+        Ok(pages.boxed())
     }
 
     pub async fn fetch_bills(&self, args: FetchBillsArgs) -> Result<Value, AppError> {
@@ -106,15 +498,24 @@ impl ParliamentClient {
             session,
             parliament_number,
             enable_cache,
+            cache_only,
             apply_relevance,
             relevance_threshold,
+            // Projection is applied by the handler, after caching.
+            fields: _,
         } = args;
 
         let search_term = sanitise_optional_text(search_term);
         let house = house
             .map(|value| value.trim().to_lowercase())
             .filter(|value| !value.is_empty());
-        let session = sanitise_optional_text(session);
+        let today = chrono::DateTime::from_timestamp(self.clock.wall_secs() as i64, 0)
+            .unwrap_or_default()
+            .date_naive();
+        let session = session
+            .map(|session| session.resolve(today))
+            .transpose()
+            .map_err(AppError::bad_request)?;
 
         if let Some(ref house_value) = house {
             if !matches!(house_value.as_str(), "commons" | "lords") {
@@ -143,17 +544,250 @@ impl ParliamentClient {
             }
         }
 
-        let cache_enabled = enable_cache.unwrap_or(true);
-        let apply_relevance = apply_relevance.unwrap_or(false);
-        let relevance_threshold = relevance_threshold.unwrap_or(self.config.relevance_threshold);
-        let cache_key = format!(
-            "bills:{}:relevance:{}:threshold:{:.3}",
-            url, apply_relevance, relevance_threshold
-        );
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let relevance = self.relevance_filter(apply_relevance, relevance_threshold, false);
+        let cache_key = format!("bills:{url}");
         let ttl = self.config.cache_ttl.bills;
 
-        self.execute_request(url, cache_key, cache_enabled, ttl)
-            .await
+        let payload = self.execute_request(url, cache_key, policy, ttl).await?;
+        Ok(self
+            .relevant_view(payload, search_term.as_deref(), relevance)
+            .await)
+    }
+
+    /// Find the divisions held on a bill: read its stage sittings from the Bills API, query
+    /// both Votes APIs for divisions on those dates and keep the ones whose titles match
+    /// the bill, grouped by stage.
+    pub async fn fetch_bill_divisions(
+        &self,
+        args: FetchBillDivisionsArgs,
+    ) -> Result<FetchBillDivisionsResult, AppError> {
+        let FetchBillDivisionsArgs {
+            bill_id,
+            bill_title,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let bill_id = match (bill_id, sanitise_optional_text(bill_title)) {
+            (Some(bill_id), _) => bill_id,
+            (None, Some(title)) => {
+                // Titles are resolved upstream, so only a billId can be answered from cache.
+                policy.allow_fetch(&format!("the bill id for \"{title}\""))?;
+                self.resolve_bill_id(&title).await?
+            }
+            (None, None) => {
+                return Err(AppError::bad_request(
+                    "either billId or billTitle is required".to_string(),
+                ));
+            }
+        };
+
+        let cache_key = format!("bill_divisions:{bill_id}");
+        if policy.reads() {
+            if let Some(cached) = read_cache::<FetchBillDivisionsResult>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.bill_divisions,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("bill {bill_id}'s divisions"))?;
+
+        let id_segment = bill_id.to_string();
+        let bill = self
+            .get_json(build_url(BILLS_BASE, &["Bills", &id_segment])?)
+            .await?;
+        let title = bill
+            .get("shortTitle")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::internal(format!("bill {bill_id} has no shortTitle")))?
+            .to_string();
+
+        let mut stages_url = build_url(BILLS_BASE, &["Bills", &id_segment, "Stages"])?;
+        stages_url.query_pairs_mut().append_pair("Take", "100");
+        let sittings = parse_stage_sittings(&self.get_json(stages_url).await?);
+
+        let mut days: Vec<(DivisionHouse, NaiveDate)> = sittings
+            .iter()
+            .map(|sitting| (sitting.house, sitting.date))
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+
+        let mut divisions = Vec::new();
+        let mut truncated_by_budget = false;
+        let lookups = stream::iter(days)
+            .map(|(house, date)| async move {
+                if budget_exhausted() {
+                    return None;
+                }
+                Some(self.fetch_divisions_on(house, date).await)
+            })
+            .buffered(DIVISION_LOOKUP_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+        for lookup in lookups {
+            match lookup {
+                Some(lookup) => divisions.extend(lookup?),
+                None => truncated_by_budget = true,
+            }
+        }
+
+        let stages = group_divisions(&title, &sittings, divisions);
+        let total_divisions = stages.iter().map(|stage| stage.divisions.len()).sum();
+        let low_confidence_count = stages
+            .iter()
+            .flat_map(|stage| &stage.divisions)
+            .filter(|division| division.low_confidence)
+            .count();
+        let result = FetchBillDivisionsResult {
+            bill_id,
+            title,
+            link: format!("https://bills.parliament.uk/bills/{bill_id}"),
+            stages,
+            total_divisions,
+            low_confidence_count,
+            truncated_by_budget,
+        };
+
+        if policy.writes() && !truncated_by_budget {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
+        }
+
+        Ok(result)
+    }
+
+    /// A bill's titles, sponsors, stage history and publications, from three concurrent
+    /// Bills API requests. The bill and its stages are required; if the publications fail
+    /// the bill is returned without them, with a note, and is not cached.
+    pub async fn fetch_bill_detail(
+        &self,
+        args: FetchBillDetailArgs,
+    ) -> Result<BillDetail, AppError> {
+        let FetchBillDetailArgs {
+            bill_id,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!("bill_detail:{bill_id}");
+        if policy.reads() {
+            if let Some(cached) = read_cache::<BillDetail>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.bills,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("bill {bill_id}"))?;
+
+        let id_segment = bill_id.to_string();
+        let bill_url = build_url(BILLS_BASE, &["Bills", &id_segment])?;
+        let mut stages_url = build_url(BILLS_BASE, &["Bills", &id_segment, "Stages"])?;
+        stages_url.query_pairs_mut().append_pair("Take", "100");
+        let publications_url = build_url(BILLS_BASE, &["Bills", &id_segment, "Publications"])?;
+        let (bill, stages, publications) = tokio::join!(
+            self.get_json(bill_url),
+            self.get_json(stages_url),
+            self.get_json(publications_url)
+        );
+
+        let mut detail = parse_bill_detail(bill_id, &bill?, &stages?)
+            .ok_or_else(|| AppError::internal(format!("bill {bill_id} has no shortTitle")))?;
+        match publications {
+            Ok(publications) => detail.publications = parse_publications(&publications),
+            Err(error) => {
+                tracing::warn!(bill_id, %error, "bill publications failed");
+                detail.note =
+                    Some("Could not fetch the bill's publications from Parliament.".to_string());
+            }
+        }
+
+        if detail.note.is_none() && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &detail).await?;
+        }
+
+        Ok(detail)
+    }
+
+    /// Resolve an exact (case-insensitive) short title to a bill id via the bills search.
+    async fn resolve_bill_id(&self, title: &str) -> Result<u32, AppError> {
+        let mut url = build_url(BILLS_BASE, &["Bills"])?;
+        url.query_pairs_mut()
+            .append_pair("SearchTerm", title)
+            .append_pair("Take", "20");
+        let payload = self.get_json(url).await?;
+
+        let candidates: Vec<(u32, &str)> = payload
+            .get("items")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| {
+                let bill_id = item.get("billId")?.as_u64()?;
+                let short_title = item.get("shortTitle")?.as_str()?;
+                Some((u32::try_from(bill_id).ok()?, short_title))
+            })
+            .collect();
+
+        if let Some((bill_id, _)) = candidates
+            .iter()
+            .find(|(_, short_title)| short_title.trim().eq_ignore_ascii_case(title))
+        {
+            return Ok(*bill_id);
+        }
+
+        let suggestions: Vec<&str> = candidates
+            .iter()
+            .take(5)
+            .map(|(_, short_title)| *short_title)
+            .collect();
+        Err(AppError::bad_request(if suggestions.is_empty() {
+            format!("no bill is titled \"{title}\"")
+        } else {
+            format!(
+                "no bill is titled exactly \"{title}\"; similar bills: {}",
+                suggestions.join("; ")
+            )
+        }))
+    }
+
+    async fn fetch_divisions_on(
+        &self,
+        house: DivisionHouse,
+        date: NaiveDate,
+    ) -> Result<Vec<RawDivision>, AppError> {
+        let date_text = date.to_string();
+        let (base, start_key, end_key, take_key) = match house {
+            DivisionHouse::Commons => (
+                COMMONS_DIVISIONS_SEARCH,
+                "queryParameters.startDate",
+                "queryParameters.endDate",
+                "queryParameters.take",
+            ),
+            DivisionHouse::Lords => (LORDS_DIVISIONS_SEARCH, "StartDate", "EndDate", "take"),
+        };
+
+        let mut url = Url::parse(base)
+            .map_err(|err| AppError::internal(format!("invalid divisions url: {err}")))?;
+        url.query_pairs_mut()
+            .append_pair(start_key, &date_text)
+            .append_pair(end_key, &date_text)
+            .append_pair(take_key, "100");
+
+        let payload = self.get_json(url).await?;
+        Ok(parse_divisions(house, &payload))
     }
 
     pub async fn fetch_legislation(&self, args: FetchLegislationArgs) -> Result<Value, AppError> {
@@ -170,6 +804,7 @@ impl ParliamentClient {
             year,
             legislation_type,
             enable_cache,
+            cache_only,
             apply_relevance,
             relevance_threshold,
         } = args;
@@ -180,8 +815,16 @@ impl ParliamentClient {
             .filter(|value| !value.is_empty())
             .unwrap_or_else(|| "all".to_string());
 
-        let mut url = Url::parse(&format!("{LEGISLATION_BASE}/{legislation_type}/data.feed"))
-            .map_err(|err| AppError::internal(format!("invalid legislation url: {err}")))?;
+        if !legislation_type
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        {
+            return Err(AppError::bad_request(format!(
+                "invalid legislation type: {legislation_type}"
+            )));
+        }
+
+        let mut url = build_url(LEGISLATION_BASE, &[&legislation_type, "data.feed"])?;
 
         {
             let mut query_pairs = url.query_pairs_mut();
@@ -193,182 +836,377 @@ impl ParliamentClient {
             }
         }
 
-        let cache_enabled = enable_cache.unwrap_or(true);
-        let apply_relevance = apply_relevance.unwrap_or(false);
-        let relevance_threshold = relevance_threshold.unwrap_or(self.config.relevance_threshold);
-        let cache_key = format!(
-            "legislation:{}:relevance:{}:threshold:{:.3}",
-            url, apply_relevance, relevance_threshold
-        );
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let relevance = self.relevance_filter(apply_relevance, relevance_threshold, false);
+        let cache_key = format!("legislation:{url}");
         let ttl = self.config.cache_ttl.legislation;
 
-        if cache_enabled {
+        if policy.reads() {
             if let Some(cached) = self.cache.get(&cache_key).await {
-                return Ok(cached);
+                record_cache_hit();
+                return Ok(self
+                    .relevant_view(cached, title.as_deref(), relevance)
+                    .await);
             }
+            policy.allow_fetch(&redact_url(url.as_str()))?;
+            tracing::debug!(url = %redact_url(url.as_str()), "cache miss; fetching from upstream");
         }
 
-        let mut last_error: Option<AppError> = None;
+        let body = self.fetch_body(&url).await?;
+        let body = String::from_utf8(body)
+            .map_err(|err| AppError::internal(format!("failed to read legislation feed: {err}")))?;
+        let parsed = parse_legislation_feed(&body)?;
 
-        for attempt in 0..RETRY_ATTEMPTS {
-            let response = self.http_client.get(url.clone()).send().await;
+        if policy.writes() {
+            self.cache.insert(cache_key, parsed.clone(), ttl).await;
+        }
 
-            match response {
-                Ok(resp) if resp.status().is_success() => {
-                    let body = resp.text().await.map_err(|err| {
-                        AppError::internal(format!("failed to read legislation feed: {err}"))
-                    })?;
-                    let parsed = parse_legislation_feed(&body)?;
-
-                    if cache_enabled {
-                        self.cache
-                            .insert(cache_key.clone(), parsed.clone(), ttl)
-                            .await;
-                    }
+        Ok(self
+            .relevant_view(parsed, title.as_deref(), relevance)
+            .await)
+    }
 
-                    return Ok(parsed);
-                }
-                Ok(resp) => {
-                    let status = resp.status();
-                    let text = resp
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "<failed to read body>".to_string());
-                    let snippet = text.chars().take(512).collect::<String>();
-                    last_error = Some(AppError::upstream_with_data(
-                        format!("request to {url} failed with {status}"),
-                        json!({
-                            "url": url.as_str(),
-                            "status": status.as_u16(),
-                            "body": snippet,
-                        }),
-                    ));
-                }
-                Err(err) => {
-                    last_error = Some(AppError::upstream_with_data(
-                        format!("network error contacting {url}: {err}"),
-                        json!({
-                            "url": url.as_str(),
-                            "status": Value::Null,
-                            "error": err.to_string(),
-                        }),
-                    ));
-                }
+    /// The plain-text wording of a piece of legislation, or one of its sections, read from
+    /// legislation.gov.uk's `data.xml`. Documents over [`MAX_LEGISLATION_XML_BYTES`] are
+    /// refused rather than parsed.
+    pub async fn fetch_legislation_text(
+        &self,
+        args: FetchLegislationTextArgs,
+    ) -> Result<LegislationText, AppError> {
+        let FetchLegislationTextArgs {
+            uri,
+            legislation_type,
+            year,
+            number,
+            section,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let (mut segments, uri_section) = match (
+            sanitise_optional_text(uri),
+            sanitise_optional_text(legislation_type),
+            year,
+            number,
+        ) {
+            (Some(uri), None, None, None) => legislation_path(&uri)?,
+            (None, Some(kind), Some(year), Some(number)) => (
+                vec![kind.to_lowercase(), year.to_string(), number.to_string()],
+                None,
+            ),
+            _ => {
+                return Err(AppError::bad_request(
+                    "give either uri, or type, year and number".to_string(),
+                ));
+            }
+        };
+        let section = match (sanitise_optional_text(section), uri_section) {
+            (Some(section), Some(in_uri)) if !section.eq_ignore_ascii_case(&in_uri) => {
+                return Err(AppError::bad_request(format!(
+                    "section {section} does not match section {in_uri} in the uri"
+                )));
             }
+            (section, in_uri) => section.or(in_uri),
+        };
+        if let Some(section) = &section {
+            segments.extend(["section".to_string(), section.clone()]);
+        }
+        if let Some(segment) = segments.iter().find(|segment| {
+            !segment
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        }) {
+            return Err(AppError::bad_request(format!(
+                "invalid legislation path segment: {segment}"
+            )));
+        }
 
-            if attempt < RETRY_ATTEMPTS - 1 {
-                sleep(Duration::from_millis(RETRY_DELAY_MS * (attempt as u64 + 1))).await;
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!("legislation_text:{}", segments.join("/"));
+        if policy.reads() {
+            if let Some(cached) = read_cache::<LegislationText>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.legislation,
+            )
+            .await?
+            {
+                return Ok(cached);
             }
         }
+        policy.allow_fetch(&format!("the text of {}", segments.join("/")))?;
+
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let url = build_url(LEGISLATION_BASE, &segments)?;
+        let data_url = build_url(url.as_str(), &["data.xml"])?;
+        let body = self
+            .fetch_body_capped(&data_url, MAX_LEGISLATION_XML_BYTES)
+            .await?;
+        let xml = String::from_utf8(body).map_err(|err| {
+            AppError::internal(format!("failed to read legislation document: {err}"))
+        })?;
+        let text = parse_legislation_text(&xml, section.as_deref(), url.to_string())?;
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &text).await?;
+        }
 
-        Err(last_error.unwrap_or_else(|| AppError::internal("request failed")))
+        Ok(text)
     }
 
+    /// Recent spoken contributions, written questions and EDM signatures for a member,
+    /// newest first. A source that fails is named in the result rather than failing the
+    /// call; only complete results are cached, so the next call retries it.
     pub async fn fetch_mp_activity(
         &self,
         args: FetchMpActivityArgs,
-    ) -> Result<Vec<MpActivityEntry>, AppError> {
+    ) -> Result<MpActivityResult, AppError> {
         let FetchMpActivityArgs {
             mp_id,
             limit,
             enable_cache,
+            cache_only,
         } = args;
 
         let max_items = limit.unwrap_or(10).clamp(1, 50) as usize;
-        let cache_enabled = enable_cache.unwrap_or(true);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
         let cache_key = format!("activity:{mp_id}");
 
-        if cache_enabled {
+        if policy.reads() {
             if let Some(mut cached) = read_cache::<Vec<MpActivityEntry>>(
                 &self.cache_tree,
+                &self.clock,
                 &cache_key,
                 self.config.cache_ttl.activity,
             )
             .await?
             {
-                if cached.len() > max_items {
-                    cached.truncate(max_items);
+                cached.truncate(max_items);
+                return Ok(MpActivityResult {
+                    activities: cached,
+                    unavailable_sources: Vec::new(),
+                    note: None,
+                });
+            }
+        }
+        policy.allow_fetch(&format!("activity for member {mp_id}"))?;
+
+        // Every source is read to the largest `limit`, so the cached list serves any limit.
+        let (spoken, questions, motions) = tokio::join!(
+            self.get_json(activity_url(
+                HANSARD_SPOKEN_CONTRIBUTIONS,
+                &[
+                    ("queryParameters.memberId", mp_id.to_string()),
+                    ("queryParameters.take", ACTIVITY_PER_SOURCE.to_string()),
+                    ("queryParameters.orderBy", "SittingDateDesc".to_string()),
+                ],
+            )?),
+            self.get_json(activity_url(
+                WRITTEN_QUESTIONS,
+                &[
+                    ("askingMemberId", mp_id.to_string()),
+                    ("take", ACTIVITY_PER_SOURCE.to_string()),
+                ],
+            )?),
+            self.get_json(activity_url(
+                EARLY_DAY_MOTIONS,
+                &[
+                    ("parameters.memberId", mp_id.to_string()),
+                    ("parameters.take", ACTIVITY_PER_SOURCE.to_string()),
+                ],
+            )?),
+        );
+
+        let mut sources = Vec::new();
+        let mut unavailable_sources = Vec::new();
+        let mut first_error = None;
+        for (source, outcome) in [
+            (ActivitySource::SpokenContributions, spoken),
+            (ActivitySource::WrittenQuestions, questions),
+            (ActivitySource::EarlyDayMotions, motions),
+        ] {
+            match outcome {
+                Ok(payload) => sources.push(match source {
+                    ActivitySource::SpokenContributions => parse_spoken_contributions(&payload),
+                    ActivitySource::WrittenQuestions => parse_written_questions(&payload),
+                    ActivitySource::EarlyDayMotions => parse_early_day_motions(&payload, mp_id),
+                }),
+                Err(error) => {
+                    tracing::warn!(mp_id, ?source, %error, "activity source failed");
+                    unavailable_sources.push(source);
+                    first_error.get_or_insert(error);
                 }
-                return Ok(cached);
+            }
+        }
+        if sources.is_empty() {
+            if let Some(error) = first_error {
+                return Err(error);
             }
         }
 
-        // Try alternative data sources since /Activity endpoint doesn't exist
-        let mut entries = Vec::new();
+        let mut activities = merge_activity(sources, ACTIVITY_PER_SOURCE);
+        if unavailable_sources.is_empty() && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &activities).await?;
+        }
+        activities.truncate(max_items);
+
+        let note = (!unavailable_sources.is_empty()).then(|| {
+            let labels: Vec<&str> = unavailable_sources
+                .iter()
+                .map(|source| source.label())
+                .collect();
+            format!(
+                "Could not fetch {} from Parliament; this list may be missing recent activity of that kind.",
+                labels.join(" or ")
+            )
+        });
+        Ok(MpActivityResult {
+            activities,
+            unavailable_sources,
+            note,
+        })
+    }
 
-        // Try to get MP information first
-        match self.fetch_member_info(mp_id).await {
-            Ok(member_info) => {
-                // Create a basic activity entry from member info
-                let activity = MpActivityEntry {
-                    id: format!("member_info_{mp_id}"),
-                    title: format!("Member Information: {}", member_info.name_display_as),
-                    date: member_info
-                        .membership_start_date
-                        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
-                    description: format!(
-                        "Current member for {}",
-                        member_info
-                            .constituency
-                            .unwrap_or_else(|| "Unknown constituency".to_string())
-                    ),
-                    activity_type: "Member Information".to_string(),
-                    url: None,
-                };
-                entries.push(activity);
-            }
-            Err(_) => {
-                // If member info fails, create a generic entry
-                let activity = MpActivityEntry {
-                    id: format!("generic_{mp_id}"),
-                    title: "MP Activity Information".to_string(),
-                    date: chrono::Utc::now().to_rfc3339(),
-                    description: "Activity data not available from Parliament API".to_string(),
-                    activity_type: "Information".to_string(),
-                    url: None,
+    /// Resolve many members at once. Cached members are served from Sled, the rest are
+    /// fetched concurrently (still subject to the rate limiter); ids that cannot be resolved
+    /// get an error entry instead of failing the batch.
+    pub async fn fetch_members_bulk(
+        &self,
+        args: FetchMembersBulkArgs,
+    ) -> Result<FetchMembersBulkResult, AppError> {
+        let FetchMembersBulkArgs {
+            mut mp_ids,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        mp_ids.sort_unstable();
+        mp_ids.dedup();
+        if mp_ids.is_empty() {
+            return Err(AppError::bad_request("mpIds must not be empty".to_string()));
+        }
+        if mp_ids.len() > MAX_BULK_MEMBER_IDS {
+            return Err(AppError::bad_request(format!(
+                "mpIds accepts at most {MAX_BULK_MEMBER_IDS} ids"
+            )));
+        }
+
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let requested = mp_ids.len();
+        let members = stream::iter(mp_ids)
+            .map(|mp_id| async move {
+                if budget_exhausted() {
+                    return None;
+                }
+                let entry = match self.fetch_member_summary(mp_id, policy).await {
+                    Ok(summary) => MemberLookupEntry::Found(summary),
+                    Err(error) => MemberLookupEntry::Failed {
+                        error: error.to_string(),
+                    },
                 };
-                entries.push(activity);
-            }
+                Some((mp_id, entry))
+            })
+            .buffer_unordered(BULK_MEMBER_CONCURRENCY)
+            .filter_map(std::future::ready)
+            .collect::<std::collections::BTreeMap<_, _>>()
+            .await;
+
+        let failed = members
+            .values()
+            .filter(|entry| matches!(entry, MemberLookupEntry::Failed { .. }))
+            .count();
+        if policy == CachePolicy::CacheOnly && failed == requested {
+            return Err(AppError::not_cached(
+                "none of the requested members are cached",
+            ));
         }
 
-        if cache_enabled {
-            write_cache(&self.cache_tree, &cache_key, &entries).await?;
+        Ok(FetchMembersBulkResult {
+            resolved: members.len() - failed,
+            failed,
+            truncated_by_budget: members.len() < requested,
+            members,
+        })
+    }
+
+    async fn fetch_member_summary(
+        &self,
+        mp_id: u32,
+        policy: CachePolicy,
+    ) -> Result<MemberSummary, AppError> {
+        let cache_key = format!("member:{mp_id}");
+        if policy.reads() {
+            if let Some(cached) = read_cache::<MemberSummary>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.members,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
         }
+        let payload = self.fetch_member_payload(mp_id, policy).await?;
+        let summary = parse_member_summary(mp_id, &payload)
+            .ok_or_else(|| AppError::bad_request(format!("member {mp_id} was not found")))?;
 
-        if entries.len() > max_items {
-            entries.truncate(max_items);
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &summary).await?;
         }
 
-        Ok(entries)
+        Ok(summary)
     }
 
-    async fn fetch_member_info(&self, mp_id: u32) -> Result<MemberInfo, AppError> {
-        let url = format!("https://members-api.parliament.uk/api/Members/{mp_id}");
-        let url = Url::parse(&url)
-            .map_err(|err| AppError::internal(format!("invalid member url: {err}")))?;
+    /// The Members API `/Members/{id}` payload, cached on the `members` TTL. Member
+    /// summaries and profiles are both read from it, so one fetch (or one
+    /// [`ParliamentClient::spawn_member_prefetch`]) serves either.
+    async fn fetch_member_payload(
+        &self,
+        mp_id: u32,
+        policy: CachePolicy,
+    ) -> Result<Value, AppError> {
+        let cache_key = format!("member-payload:{mp_id}");
+        if policy.reads() {
+            if let Some(cached) = read_cache::<Value>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.members,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("member {mp_id}"))?;
 
+        let url = build_url(MEMBERS_BASE, &[&mp_id.to_string()])?;
         let payload = self.get_json(url).await?;
-        let member_data = payload
-            .get("value")
-            .ok_or_else(|| AppError::internal("missing member data".to_string()))?;
-
-        Ok(MemberInfo {
-            name_display_as: member_data
-                .get("nameDisplayAs")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown")
-                .to_string(),
-            membership_start_date: member_data
-                .get("latestHouseMembership")
-                .and_then(|v| v.get("membershipStartDate"))
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            constituency: member_data
-                .get("latestHouseMembership")
-                .and_then(|v| v.get("membershipFrom"))
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-        })
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &payload).await?;
+        }
+        Ok(payload)
+    }
+
+    /// Warm the member payload cache for `mp_id` in the background, for the profile call
+    /// that usually follows a constituency lookup. Runs through the usual rate limiter;
+    /// failures are logged and otherwise ignored, and nothing is fetched when the payload is
+    /// already cached.
+    pub fn spawn_member_prefetch(self: &Arc<Self>, mp_id: u32) {
+        let client = Arc::clone(self);
+        tokio::spawn(async move {
+            match client
+                .fetch_member_payload(mp_id, CachePolicy::Normal)
+                .await
+            {
+                Ok(_) => tracing::debug!(mp_id, "member prefetched after constituency lookup"),
+                Err(error) => {
+                    tracing::debug!(mp_id, %error, "member prefetch after constituency lookup failed")
+                }
+            }
+        });
     }
 
     pub async fn fetch_mp_voting_record(
@@ -380,225 +1218,1248 @@ impl ParliamentClient {
             from_date,
             to_date,
             bill_id,
+            house,
             limit,
             enable_cache,
+            cache_only,
+            ..
         } = args;
 
         let max_items = limit.unwrap_or(25).clamp(1, 100) as usize;
-        let cache_enabled = enable_cache.unwrap_or(true);
-        let cache_key = format!("votes:{mp_id}");
+        let from_date = parse_optional_date("fromDate", from_date.as_deref())?;
+        let to_date = parse_optional_date("toDate", to_date.as_deref())?;
+        let bill_filter = bill_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_lowercase);
+        let house = house.unwrap_or(DivisionHouse::Commons);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let date_key =
+            |date: Option<NaiveDate>| date.map(|date| date.to_string()).unwrap_or_default();
+        let cache_key = format!(
+            "votes:{}:{mp_id}:{}:{}:{}:{max_items}",
+            house.as_str(),
+            date_key(from_date),
+            date_key(to_date),
+            bill_filter.as_deref().unwrap_or_default()
+        );
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<Vec<MpVoteRecord>>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.votes,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("the voting record of member {mp_id}"))?;
+
+        // Dates are filtered upstream; the Votes API has no bill filter, so matching
+        // divisions are collected across pages until `limit` are found.
+        let base = member_voting_url(house, mp_id, from_date, to_date)?;
+        let mut entries = Vec::new();
+        let mut skip = 0;
+        for _ in 0..MAX_VOTING_RECORD_PAGES {
+            let url = member_voting_page(house, base.clone(), skip);
+            let payload = self.get_json(url).await?;
+            let page_len = payload.as_array().map_or(0, Vec::len);
+            skip += page_len;
+
+            entries.extend(
+                parse_member_votes(house, &payload)
+                    .into_iter()
+                    .map(|vote| member_vote_record(house, vote))
+                    .filter(|entry| {
+                        bill_filter
+                            .as_deref()
+                            .is_none_or(|bill_id| matches_bill_id(entry, bill_id))
+                    }),
+            );
+            if page_len < MEMBER_VOTING_PAGE_SIZE || entries.len() >= max_items {
+                break;
+            }
+        }
+        entries.truncate(max_items);
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &entries).await?;
+        }
+
+        Ok(entries)
+    }
+
+    pub async fn lookup_constituency_offline(
+        &self,
+        args: LookupConstituencyArgs,
+    ) -> Result<ConstituencyLookupResult, AppError> {
+        let LookupConstituencyArgs {
+            postcode,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let normalised = normalise_postcode(&postcode)
+            .ok_or_else(|| AppError::bad_request("postcode must not be empty".to_string()))?;
+        if !is_plausible_postcode(&normalised) {
+            return Err(AppError::bad_request(format!(
+                "postcode {postcode} is not a valid UK postcode"
+            )));
+        }
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!("constituency:{normalised}");
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<ConstituencyLookupResult>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.constituency,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch("the constituency for this postcode")?;
+
+        // Use Postcodes.io API as backup instead of CSV dataset
+        let maybe_lookup = self.lookup_constituency_from_api(&normalised).await?;
+
+        let mut lookup = maybe_lookup.ok_or_else(|| {
+            AppError::bad_request(format!(
+                "postcode {postcode} could not be matched to a constituency"
+            ))
+        })?;
+
+        if let Some(name) = lookup.constituency_name.clone() {
+            if let Some(summary) = self.lookup_current_mp_for_constituency(&name).await? {
+                lookup.mp_id = Some(summary.id);
+                lookup.mp_name = Some(summary.name);
+            }
+        }
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &lookup).await?;
+        }
+
+        Ok(lookup)
+    }
+
+    /// Enumerate every current constituency, filtered locally. The list is fetched from the
+    /// Members API location endpoint once per `constituency_list` TTL; the sitting MPs are
+    /// joined in from a separate members listing cached on the shorter `members` TTL.
+    pub async fn list_constituencies(
+        &self,
+        args: ListConstituenciesArgs,
+    ) -> Result<ListConstituenciesResult, AppError> {
+        let ListConstituenciesArgs {
+            nation,
+            region,
+            search,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let listings = self.constituency_list(policy).await?;
+        let mps = self.current_mps_by_constituency(policy).await?;
+
+        let matches = |field: &Option<String>, wanted: &Option<String>| match wanted {
+            Some(wanted) => field
+                .as_deref()
+                .is_some_and(|value| value.eq_ignore_ascii_case(wanted.trim())),
+            None => true,
+        };
+        let prefix = search
+            .as_deref()
+            .map(|text| text.trim().to_lowercase())
+            .filter(|text| !text.is_empty());
+
+        let total = listings.len();
+        let constituencies: Vec<ConstituencyListing> = listings
+            .into_iter()
+            .filter(|listing| matches(&listing.nation, &nation))
+            .filter(|listing| matches(&listing.region, &region))
+            .filter(|listing| {
+                prefix
+                    .as_deref()
+                    .is_none_or(|prefix| listing.name.to_lowercase().starts_with(prefix))
+            })
+            .map(|mut listing| {
+                if let Some(mp) = mps.get(&listing.id) {
+                    listing.mp_id = Some(mp.id);
+                    listing.mp_name = Some(mp.name.clone());
+                    listing.mp_party = mp.party.clone();
+                }
+                listing
+            })
+            .collect();
+
+        Ok(ListConstituenciesResult {
+            total,
+            matched: constituencies.len(),
+            constituencies,
+        })
+    }
+
+    /// Constituency names from the cached list, or none when it has not been cached (or
+    /// has expired). Never fetches.
+    pub async fn cached_constituency_names(&self) -> Result<Vec<String>, AppError> {
+        let listings = read_cache::<Vec<ConstituencyListing>>(
+            &self.cache_tree,
+            &self.clock,
+            CONSTITUENCY_LIST_KEY,
+            self.config.cache_ttl.constituency_list,
+        )
+        .await?;
+        Ok(listings
+            .unwrap_or_default()
+            .into_iter()
+            .map(|listing| listing.name)
+            .collect())
+    }
+
+    /// Names of the sitting MPs from the cached members listing, alphabetically, or none
+    /// when it has not been cached. Never fetches.
+    pub async fn cached_member_names(&self) -> Result<Vec<String>, AppError> {
+        let mps = read_cache::<BTreeMap<u32, CurrentMp>>(
+            &self.cache_tree,
+            &self.clock,
+            CONSTITUENCY_MPS_KEY,
+            self.config.cache_ttl.members,
+        )
+        .await?;
+        let mut names: Vec<String> = mps
+            .unwrap_or_default()
+            .into_values()
+            .map(|mp| mp.name)
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    async fn constituency_list(
+        &self,
+        policy: CachePolicy,
+    ) -> Result<Vec<ConstituencyListing>, AppError> {
+        if policy.reads() {
+            if let Some(cached) = read_cache::<Vec<ConstituencyListing>>(
+                &self.cache_tree,
+                &self.clock,
+                CONSTITUENCY_LIST_KEY,
+                self.config.cache_ttl.constituency_list,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch("the constituency list")?;
+
+        let items = self
+            .fetch_all_pages(LOCATION_CONSTITUENCY_SEARCH, &[])
+            .await?;
+        let mut listings: Vec<ConstituencyListing> = items
+            .iter()
+            .filter_map(parse_constituency_listing)
+            .collect();
+        listings.sort_by(|a, b| a.name.cmp(&b.name));
+        listings.dedup_by_key(|listing| listing.id);
+
+        if policy.writes() {
+            write_cache(
+                &self.cache_tree,
+                &self.clock,
+                CONSTITUENCY_LIST_KEY,
+                &listings,
+            )
+            .await?;
+        }
+
+        Ok(listings)
+    }
+
+    async fn current_mps_by_constituency(
+        &self,
+        policy: CachePolicy,
+    ) -> Result<BTreeMap<u32, CurrentMp>, AppError> {
+        if policy.reads() {
+            if let Some(cached) = read_cache::<BTreeMap<u32, CurrentMp>>(
+                &self.cache_tree,
+                &self.clock,
+                CONSTITUENCY_MPS_KEY,
+                self.config.cache_ttl.members,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch("the current MPs list")?;
+
+        let items = self
+            .fetch_all_pages(
+                MEMBERS_SEARCH_BASE,
+                &[("House", "Commons"), ("IsCurrentMember", "true")],
+            )
+            .await?;
+        let mps: BTreeMap<u32, CurrentMp> = items.iter().filter_map(parse_current_mp).collect();
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, CONSTITUENCY_MPS_KEY, &mps).await?;
+        }
+
+        Ok(mps)
+    }
+
+    /// Collect the `items` of every page of a Members API search, advancing `skip` by the
+    /// number of items each page returned until `totalResults` is reached or a page is empty.
+    async fn fetch_all_pages(
+        &self,
+        base: &str,
+        params: &[(&str, &str)],
+    ) -> Result<Vec<Value>, AppError> {
+        let mut items = Vec::new();
+        for _ in 0..MAX_LIST_PAGES {
+            let mut url = Url::parse(base)
+                .map_err(|err| AppError::internal(format!("invalid url {base}: {err}")))?;
+            {
+                let mut query_pairs = url.query_pairs_mut();
+                for (key, value) in params {
+                    query_pairs.append_pair(key, value);
+                }
+                query_pairs.append_pair("skip", &items.len().to_string());
+                query_pairs.append_pair("take", &LIST_PAGE_SIZE.to_string());
+            }
+
+            let payload = self.get_json(url).await?;
+            let page = payload
+                .get("items")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            if page.is_empty() {
+                return Ok(items);
+            }
+            items.extend(page);
+
+            let total = payload.get("totalResults").and_then(Value::as_u64);
+            if total.is_none_or(|total| items.len() as u64 >= total) {
+                return Ok(items);
+            }
+        }
+
+        Err(AppError::upstream_with_data(
+            format!("{base} did not finish within {MAX_LIST_PAGES} pages"),
+            json!({"url": base, "status": Value::Null}),
+        ))
+    }
+
+    /// Statutory instruments from the Statutory Instruments API, most recently laid first.
+    /// The API is asked for up to [`SI_CANDIDATES`] instruments matching `searchTerm`,
+    /// which are then filtered by department, procedure and laying date. SI numbers come
+    /// from legislation.gov.uk's `uksi` feed, read alongside; if it fails the instruments
+    /// are returned unnumbered with a note and the result is not cached.
+    pub async fn fetch_statutory_instruments(
+        &self,
+        args: FetchStatutoryInstrumentsArgs,
+    ) -> Result<FetchStatutoryInstrumentsResult, AppError> {
+        let FetchStatutoryInstrumentsArgs {
+            search_term,
+            department,
+            procedure,
+            laid_from,
+            laid_to,
+            limit,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let max_items = limit.unwrap_or(20).clamp(1, 50) as usize;
+        let laid_from = parse_optional_date("laidFrom", laid_from.as_deref())?;
+        let laid_to = parse_optional_date("laidTo", laid_to.as_deref())?;
+        let search_term = sanitise_optional_text(search_term);
+        let department = sanitise_optional_text(department).map(|name| name.to_lowercase());
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let text = |value: Option<String>| value.unwrap_or_default();
+        let cache_key = format!(
+            "statutory_instruments:{}:{}:{}:{}:{}:{max_items}",
+            text(search_term.as_ref().map(|term| term.to_lowercase())),
+            text(department.clone()),
+            text(procedure.map(|procedure| format!("{procedure:?}"))),
+            text(laid_from.map(|date| date.to_string())),
+            text(laid_to.map(|date| date.to_string()))
+        );
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<FetchStatutoryInstrumentsResult>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.statutory_instruments,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch("these statutory instruments")?;
+
+        let mut instruments_url = Url::parse(STATUTORY_INSTRUMENTS).map_err(|err| {
+            AppError::internal(format!("invalid statutory instruments api url: {err}"))
+        })?;
+        let mut feed_url = build_url(LEGISLATION_BASE, &["uksi", "data.feed"])?;
+        instruments_url
+            .query_pairs_mut()
+            .append_pair("Take", &SI_CANDIDATES.to_string());
+        if let Some(term) = &search_term {
+            instruments_url.query_pairs_mut().append_pair("Name", term);
+            feed_url.query_pairs_mut().append_pair("title", term);
+        }
+        let (payload, made) = tokio::join!(
+            self.get_json(instruments_url),
+            self.fetch_uk_law_feed(&feed_url)
+        );
+        let payload = payload?;
+
+        let today = chrono::DateTime::from_timestamp(self.clock.wall_secs() as i64, 0)
+            .unwrap_or_default()
+            .date_naive();
+        let mut instruments = parse_instruments(&payload, today);
+        instruments.retain(|instrument| {
+            let laid = instrument
+                .laid_date
+                .as_deref()
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+            department.as_ref().is_none_or(|department| {
+                instrument
+                    .department
+                    .as_ref()
+                    .is_some_and(|name| name.to_lowercase().contains(department))
+            }) && procedure.is_none_or(|procedure| instrument.procedure == Some(procedure))
+                && laid_from.is_none_or(|from| laid.is_some_and(|laid| laid >= from))
+                && laid_to.is_none_or(|to| laid.is_some_and(|laid| laid <= to))
+        });
+        instruments.sort_by(|a, b| b.laid_date.cmp(&a.laid_date));
+        instruments.truncate(max_items);
+
+        let mut notes = Vec::new();
+        let mut complete = true;
+        let available = payload
+            .get("totalResults")
+            .and_then(Value::as_u64)
+            .unwrap_or_default();
+        if available > SI_CANDIDATES as u64 {
+            notes.push(format!(
+                "Only the {SI_CANDIDATES} most recent of {available} instruments were filtered; narrow searchTerm to reach older ones."
+            ));
+        }
+        match made {
+            Ok(made) => attach_numbers(&mut instruments, &made),
+            Err(error) => {
+                tracing::warn!(%error, "uksi feed failed");
+                notes.push("Could not fetch SI numbers from legislation.gov.uk.".to_string());
+                complete = false;
+            }
+        }
+        let result = FetchStatutoryInstrumentsResult {
+            instruments,
+            note: (!notes.is_empty()).then(|| notes.join(" ")),
+        };
+
+        if complete && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
+        }
+
+        Ok(result)
+    }
+
+    /// Search legislation.gov.uk by title through its Atom feed. A feed with no entries
+    /// gives an empty list; an unavailable feed is an upstream error.
+    pub async fn search_uk_law(&self, args: SearchUkLawArgs) -> Result<Vec<UkLawResult>, AppError> {
+        let SearchUkLawArgs {
+            query,
+            legislation_type,
+            limit,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let max_items = limit.unwrap_or(10).clamp(1, 50) as usize;
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!(
+            "uk_law:{}:{}",
+            query,
+            legislation_type.as_deref().unwrap_or("all")
+        );
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<Vec<UkLawResult>>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.legislation,
+            )
+            .await?
+            {
+                if cached.len() > max_items {
+                    return Ok(cached.into_iter().take(max_items).collect());
+                }
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch("this UK law search")?;
+
+        let search_type = match legislation_type.as_deref() {
+            Some("primary") => "primary",
+            Some("secondary") => "secondary",
+            _ => "primary+secondary",
+        };
+        let mut url = build_url(LEGISLATION_BASE, &[search_type, "data.feed"])?;
+        url.query_pairs_mut().append_pair("title", &query);
+
+        let mut results = self.fetch_uk_law_feed(&url).await?;
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &results).await?;
+        }
+
+        results.truncate(max_items);
+
+        Ok(results)
+    }
+
+    async fn fetch_uk_law_feed(&self, url: &Url) -> Result<Vec<UkLawResult>, AppError> {
+        let body = self.fetch_body(url).await?;
+        let feed = String::from_utf8(body)
+            .map_err(|err| AppError::internal(format!("failed to read legislation feed: {err}")))?;
+        parse_uk_law_feed(&feed)
+    }
+
+    /// Search spoken contributions in Hansard, newest sitting first, reading pages until
+    /// `limit` matches are found. A page that fails after the first ends the search early
+    /// with what was found; such a short result is not cached.
+    pub async fn search_hansard(
+        &self,
+        args: SearchHansardArgs,
+    ) -> Result<SearchHansardResult, AppError> {
+        let SearchHansardArgs {
+            query,
+            house,
+            from_date,
+            to_date,
+            member_id,
+            limit,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(AppError::bad_request("query must not be empty".to_string()));
+        }
+        let max_items = limit.unwrap_or(20).clamp(1, 100) as usize;
+        let from_date = parse_optional_date("fromDate", from_date.as_deref())?;
+        let to_date = parse_optional_date("toDate", to_date.as_deref())?;
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let date_key =
+            |date: Option<NaiveDate>| date.map(|date| date.to_string()).unwrap_or_default();
+        let cache_key = format!(
+            "hansard:{}:{}:{}:{}:{}:{max_items}",
+            query.to_lowercase(),
+            house.map(DivisionHouse::as_str).unwrap_or_default(),
+            date_key(from_date),
+            date_key(to_date),
+            member_id.map(|id| id.to_string()).unwrap_or_default()
+        );
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<SearchHansardResult>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.hansard,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("this Hansard search for {query:?}"))?;
+
+        let mut base = Url::parse(HANSARD_SPOKEN_CONTRIBUTIONS)
+            .map_err(|err| AppError::internal(format!("invalid hansard api url: {err}")))?;
+        {
+            let mut pairs = base.query_pairs_mut();
+            pairs
+                .append_pair("queryParameters.searchTerm", query)
+                .append_pair("queryParameters.orderBy", "SittingDateDesc");
+            if let Some(house) = house {
+                pairs.append_pair("queryParameters.house", house.as_str());
+            }
+            if let Some(date) = from_date {
+                pairs.append_pair("queryParameters.startDate", &date.to_string());
+            }
+            if let Some(date) = to_date {
+                pairs.append_pair("queryParameters.endDate", &date.to_string());
+            }
+            if let Some(member_id) = member_id {
+                pairs.append_pair("queryParameters.memberId", &member_id.to_string());
+            }
+        }
+
+        let mut result = SearchHansardResult {
+            results: Vec::new(),
+            total_results: None,
+        };
+        let mut complete = true;
+        let mut skip = 0;
+        while result.results.len() < max_items {
+            let take = (max_items - result.results.len()).min(HANSARD_PAGE_SIZE);
+            let mut url = base.clone();
+            url.query_pairs_mut()
+                .append_pair("queryParameters.skip", &skip.to_string())
+                .append_pair("queryParameters.take", &take.to_string());
+            let payload = match self.get_hansard_json(url).await {
+                Ok(payload) => payload,
+                Err(error) if skip == 0 => return Err(error),
+                Err(error) => {
+                    tracing::warn!(%error, skip, "hansard search page failed; returning earlier pages");
+                    complete = false;
+                    break;
+                }
+            };
+            result.total_results = total_results(&payload).or(result.total_results);
+            let page_len = json_items(&payload, "Results").count();
+            skip += page_len;
+            result.results.extend(parse_contributions(&payload));
+            let exhausted = result
+                .total_results
+                .is_some_and(|total| skip as u64 >= total);
+            if page_len < take || exhausted {
+                break;
+            }
+        }
+        result.results.truncate(max_items);
+
+        if complete && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
+        }
+
+        Ok(result)
+    }
+
+    /// [`Self::get_json`] for the Hansard API, which now and then answers `200` with an
+    /// HTML error page; that is reported as an upstream failure rather than a parse error.
+    async fn get_hansard_json(&self, url: Url) -> Result<Value, AppError> {
+        let body = self.fetch_body(&url).await?;
+        serde_json::from_slice(&body).map_err(|err| {
+            let html = body
+                .iter()
+                .find(|byte| !byte.is_ascii_whitespace())
+                .is_some_and(|byte| *byte == b'<');
+            let message = if html {
+                "the Hansard API returned an HTML page instead of JSON".to_string()
+            } else {
+                format!("invalid Hansard API response: {err}")
+            };
+            AppError::upstream_with_data(message, json!({"url": redact_url(url.as_str())}))
+        })
+    }
+
+    /// An MP's committee memberships, from the Committees API, and government and
+    /// opposition posts, from their Members API biography, current first. If one source
+    /// fails the other's roles are returned with a note, and the result is not cached.
+    pub async fn fetch_member_roles(
+        &self,
+        args: FetchMemberRolesArgs,
+    ) -> Result<MemberRolesResult, AppError> {
+        let FetchMemberRolesArgs {
+            mp_id,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!("member_roles:{mp_id}");
+        if policy.reads() {
+            if let Some(cached) = read_cache::<MemberRolesResult>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.members,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("the roles of member {mp_id}"))?;
+
+        let biography_url = build_url(MEMBERS_BASE, &[&mp_id.to_string(), "Biography"])?;
+        let mut committees_url = Url::parse(COMMITTEE_MEMBERS)
+            .map_err(|err| AppError::internal(format!("invalid committees api url: {err}")))?;
+        committees_url
+            .query_pairs_mut()
+            .append_pair("Members", &mp_id.to_string())
+            .append_pair("MembershipStatus", "All");
+        let (biography, committees) =
+            futures::join!(self.get_json(biography_url), self.get_json(committees_url));
+
+        let today = chrono::DateTime::from_timestamp(self.clock.wall_secs() as i64, 0)
+            .unwrap_or_default()
+            .date_naive();
+        let (mut roles, note) = match (biography, committees) {
+            (Ok(biography), Ok(committees)) => {
+                let mut roles = parse_committee_roles(&committees, today);
+                roles.extend(parse_posts(&biography, today));
+                (roles, None)
+            }
+            (Ok(biography), Err(error)) => {
+                tracing::warn!(mp_id, %error, "committee memberships failed");
+                (
+                    parse_posts(&biography, today),
+                    Some("Could not fetch committee memberships from Parliament.".to_string()),
+                )
+            }
+            (Err(error), Ok(committees)) => {
+                tracing::warn!(mp_id, %error, "member biography failed");
+                (
+                    parse_committee_roles(&committees, today),
+                    Some(
+                        "Could not fetch government and opposition posts from Parliament."
+                            .to_string(),
+                    ),
+                )
+            }
+            (Err(error), Err(_)) => return Err(error),
+        };
+        sort_roles(&mut roles);
+        let result = MemberRolesResult { mp_id, roles, note };
+
+        if result.note.is_none() && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
+        }
+
+        Ok(result)
+    }
+
+    /// Current committees matching the filters, or one committee by id with its current
+    /// members. With `includeInquiries` the first [`MAX_COMMITTEE_INQUIRY_LOOKUPS`]
+    /// committees also list their open inquiries, one request each; if any of those fail
+    /// the result says so and is not cached.
+    pub async fn fetch_committees(
+        &self,
+        args: FetchCommitteesArgs,
+    ) -> Result<FetchCommitteesResult, AppError> {
+        let FetchCommitteesArgs {
+            committee_id,
+            search_term,
+            house,
+            include_inquiries,
+            limit,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let search_term = sanitise_optional_text(search_term);
+        if committee_id.is_some() && (search_term.is_some() || house.is_some()) {
+            return Err(AppError::bad_request(
+                "committeeId cannot be combined with searchTerm or house".to_string(),
+            ));
+        }
+        let max_items = limit.unwrap_or(20).clamp(1, 50) as usize;
+        let include_inquiries = include_inquiries.unwrap_or(false);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!(
+            "committees:{}:{}:{}:{max_items}:{include_inquiries}",
+            committee_id.map(|id| id.to_string()).unwrap_or_default(),
+            search_term
+                .as_ref()
+                .map(|term| term.to_lowercase())
+                .unwrap_or_default(),
+            house.map(CommitteeHouse::as_str).unwrap_or_default()
+        );
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<FetchCommitteesResult>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.committees,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch("these committees")?;
+
+        let (mut committees, total_results) = match committee_id {
+            Some(id) => {
+                let committee_url = Url::parse(&format!("{COMMITTEES}/{id}"))
+                    .map_err(|err| AppError::internal(format!("invalid committee url: {err}")))?;
+                let mut members_url = Url::parse(&format!("{COMMITTEES}/{id}/Members"))
+                    .map_err(|err| AppError::internal(format!("invalid committee url: {err}")))?;
+                members_url
+                    .query_pairs_mut()
+                    .append_pair("MembershipStatus", "Current");
+                let (committee, members) =
+                    futures::try_join!(self.get_json(committee_url), self.get_json(members_url))?;
+                let mut committee = parse_committee(&committee).ok_or_else(|| {
+                    AppError::internal(format!("unexpected Committees API response for {id}"))
+                })?;
+                committee.members = Some(parse_committee_members(&members));
+                (vec![committee], Some(1))
+            }
+            None => {
+                let mut url = Url::parse(COMMITTEES).map_err(|err| {
+                    AppError::internal(format!("invalid committees api url: {err}"))
+                })?;
+                {
+                    let mut pairs = url.query_pairs_mut();
+                    pairs
+                        .append_pair("CommitteeStatus", "Current")
+                        .append_pair("Take", &max_items.to_string());
+                    if let Some(term) = &search_term {
+                        pairs.append_pair("SearchTerm", term);
+                    }
+                    if let Some(house) = house {
+                        pairs.append_pair("House", house.as_str());
+                    }
+                }
+                let payload = self.get_json(url).await?;
+                let mut committees = parse_committees(&payload);
+                committees.truncate(max_items);
+                (committees, committees::total_results(&payload))
+            }
+        };
+
+        let mut notes = Vec::new();
+        let mut failed = 0;
+        if include_inquiries {
+            let today = chrono::DateTime::from_timestamp(self.clock.wall_secs() as i64, 0)
+                .unwrap_or_default()
+                .date_naive();
+            let ids: Vec<u32> = committees
+                .iter()
+                .take(MAX_COMMITTEE_INQUIRY_LOOKUPS)
+                .map(|committee| committee.id)
+                .collect();
+            let lookups = ids.into_iter().map(|id| async move {
+                let mut url = Url::parse(COMMITTEE_BUSINESS).map_err(|err| {
+                    AppError::internal(format!("invalid committee business url: {err}"))
+                })?;
+                url.query_pairs_mut()
+                    .append_pair("CommitteeId", &id.to_string())
+                    .append_pair("Status", "Open");
+                self.get_json(url).await
+            });
+            for (committee, outcome) in committees.iter_mut().zip(join_all(lookups).await) {
+                match outcome {
+                    Ok(payload) => committee.inquiries = Some(parse_inquiries(&payload, today)),
+                    Err(error) => {
+                        tracing::warn!(committee_id = committee.id, %error, "committee inquiries failed");
+                        failed += 1;
+                    }
+                }
+            }
+            if committees.len() > MAX_COMMITTEE_INQUIRY_LOOKUPS {
+                notes.push(format!(
+                    "Inquiries are listed for the first {MAX_COMMITTEE_INQUIRY_LOOKUPS} committees only."
+                ));
+            }
+            if failed > 0 {
+                notes.push(format!(
+                    "Could not fetch the inquiries of {failed} committee(s) from Parliament."
+                ));
+            }
+        }
+        let result = FetchCommitteesResult {
+            committees,
+            total_results,
+            note: (!notes.is_empty()).then(|| notes.join(" ")),
+        };
+
+        if failed == 0 && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
+        }
+
+        Ok(result)
+    }
+
+    /// One division with every member's vote, from the house's Votes API. Published
+    /// divisions do not change, so they are cached on the long `division_detail` TTL.
+    pub async fn fetch_division_detail(
+        &self,
+        args: FetchDivisionDetailArgs,
+    ) -> Result<DivisionDetail, AppError> {
+        let FetchDivisionDetailArgs {
+            division_id,
+            house,
+            enable_cache,
+            cache_only,
+            ..
+        } = args;
+
+        let house = house.unwrap_or(DivisionHouse::Commons);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let cache_key = format!("division:{}:{division_id}", house.as_str());
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<DivisionDetail>(
+                &self.cache_tree,
+                &self.clock,
+                &cache_key,
+                self.config.cache_ttl.division_detail,
+            )
+            .await?
+            {
+                return Ok(cached);
+            }
+        }
+        policy.allow_fetch(&format!("{} division {division_id}", house.as_str()))?;
+
+        let url = match house {
+            DivisionHouse::Commons => format!("{COMMONS_DIVISION}/{division_id}.json"),
+            DivisionHouse::Lords => format!("{LORDS_DIVISION}/{division_id}"),
+        };
+        let url = Url::parse(&url)
+            .map_err(|err| AppError::internal(format!("invalid division url: {err}")))?;
+        let payload = self.get_json(url).await?;
+        let detail = parse_division_detail(house, &payload).ok_or_else(|| {
+            AppError::internal(format!(
+                "unexpected {} Votes API response for division {division_id}",
+                house.as_str()
+            ))
+        })?;
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &detail).await?;
+        }
+
+        Ok(detail)
+    }
+
+    /// Early day motions matching the filters, newest tabled first. With
+    /// `includeSignatories` the first [`MAX_EDM_SIGNATORY_LOOKUPS`] motions also list who
+    /// signed, one request each; if any of those fail the result says so and is not cached.
+    pub async fn fetch_edms(&self, args: FetchEdmsArgs) -> Result<FetchEdmsResult, AppError> {
+        let FetchEdmsArgs {
+            search_term,
+            sponsor_id,
+            status,
+            tabled_from,
+            tabled_to,
+            limit,
+            include_signatories,
+            enable_cache,
+            cache_only,
+        } = args;
+
+        let max_items = limit.unwrap_or(20).clamp(1, 50) as usize;
+        let tabled_from = parse_optional_date("tabledFrom", tabled_from.as_deref())?;
+        let tabled_to = parse_optional_date("tabledTo", tabled_to.as_deref())?;
+        let search_term = sanitise_optional_text(search_term);
+        let include_signatories = include_signatories.unwrap_or(false);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let text = |value: Option<String>| value.unwrap_or_default();
+        let cache_key = format!(
+            "edms:{}:{}:{}:{}:{}:{max_items}:{include_signatories}",
+            text(search_term.as_ref().map(|term| term.to_lowercase())),
+            text(sponsor_id.map(|id| id.to_string())),
+            status.map(EdmStatus::as_str).unwrap_or_default(),
+            text(tabled_from.map(|date| date.to_string())),
+            text(tabled_to.map(|date| date.to_string()))
+        );
 
-        if cache_enabled {
-            if let Some(cached) = read_cache::<Vec<MpVoteRecord>>(
+        if policy.reads() {
+            if let Some(cached) = read_cache::<FetchEdmsResult>(
                 &self.cache_tree,
+                &self.clock,
                 &cache_key,
-                self.config.cache_ttl.votes,
+                self.config.cache_ttl.edms,
             )
             .await?
             {
-                let filtered = filter_votes(
-                    cached.clone(),
-                    from_date.as_deref(),
-                    to_date.as_deref(),
-                    bill_id.as_deref(),
-                    max_items,
-                );
-                return Ok(filtered);
+                return Ok(cached);
             }
         }
+        policy.allow_fetch("these early day motions")?;
 
-        // Use Commons divisions API instead of the non-existent /Voting endpoint
-        let mut entries = Vec::new();
-
-        // Get recent divisions and create mock voting records
-        let divisions_url = "https://lda.data.parliament.uk/commonsdivisions.json?_pageSize=10";
-        let url = Url::parse(divisions_url)
-            .map_err(|err| AppError::internal(format!("invalid divisions url: {err}")))?;
+        let mut url = Url::parse(EARLY_DAY_MOTIONS)
+            .map_err(|err| AppError::internal(format!("invalid edm api url: {err}")))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs
+                .append_pair("parameters.orderBy", "DateTabledDesc")
+                .append_pair("parameters.take", &max_items.to_string());
+            if let Some(term) = &search_term {
+                pairs.append_pair("parameters.searchTerm", term);
+            }
+            if let Some(member_id) = sponsor_id {
+                pairs
+                    .append_pair("parameters.memberId", &member_id.to_string())
+                    .append_pair("parameters.includeSponsoredByMember", "true");
+            }
+            if let Some(status) = status {
+                pairs.append_pair("parameters.statuses", status.as_str());
+            }
+            if let Some(date) = tabled_from {
+                pairs.append_pair("parameters.tabledStartDate", &date.to_string());
+            }
+            if let Some(date) = tabled_to {
+                pairs.append_pair("parameters.tabledEndDate", &date.to_string());
+            }
+        }
 
-        match self.get_json(url).await {
-            Ok(payload) => {
-                if let Some(items) = payload.get("result").and_then(|r| r.get("items")) {
-                    if let Some(items_array) = items.as_array() {
-                        for (index, item) in items_array.iter().enumerate().take(max_items) {
-                            let title = item
-                                .get("title")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("Unknown Division");
-
-                            let date_str = chrono::Utc::now().to_rfc3339();
-                            let date = item
-                                .get("date")
-                                .and_then(|v| v.get("_value"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or(&date_str);
-
-                            let vote_record = MpVoteRecord {
-                                division_id: Some(format!("div_{index}")),
-                                title: Some(title.to_string()),
-                                date: Some(date.to_string()),
-                                vote: Some("Aye".to_string()), // Mock vote
-                                majority: Some("Government".to_string()),
-                            };
-                            entries.push(vote_record);
-                        }
+        let payload = self.get_json(url).await?;
+        let mut motions = parse_motions(&payload);
+        motions.truncate(max_items);
+
+        let mut notes = Vec::new();
+        let mut failed = 0;
+        if include_signatories {
+            let ids: Vec<u64> = motions
+                .iter()
+                .take(MAX_EDM_SIGNATORY_LOOKUPS)
+                .map(|motion| motion.id)
+                .collect();
+            let lookups = ids.into_iter().map(|id| async move {
+                let url = Url::parse(&format!("{EARLY_DAY_MOTION}/{id}"))
+                    .map_err(|err| AppError::internal(format!("invalid edm url: {err}")))?;
+                self.get_json(url).await
+            });
+            for (motion, outcome) in motions.iter_mut().zip(join_all(lookups).await) {
+                match outcome {
+                    Ok(detail) => motion.signatories = Some(parse_signatories(&detail)),
+                    Err(error) => {
+                        tracing::warn!(edm_id = motion.id, %error, "edm signatories failed");
+                        failed += 1;
                     }
                 }
             }
-            Err(_) => {
-                // If divisions API fails, create a mock record
-                let vote_record = MpVoteRecord {
-                    division_id: Some("mock_1".to_string()),
-                    title: Some("Sample Division".to_string()),
-                    date: Some(chrono::Utc::now().to_rfc3339()),
-                    vote: Some("Aye".to_string()),
-                    majority: Some("Government".to_string()),
-                };
-                entries.push(vote_record);
+            if motions.len() > MAX_EDM_SIGNATORY_LOOKUPS {
+                notes.push(format!(
+                    "Signatories are listed for the first {MAX_EDM_SIGNATORY_LOOKUPS} motions only."
+                ));
+            }
+            if failed > 0 {
+                notes.push(format!(
+                    "Could not fetch the signatories of {failed} motion(s) from Parliament."
+                ));
             }
         }
+        let result = FetchEdmsResult {
+            motions,
+            total_results: edms::total_results(&payload),
+            note: (!notes.is_empty()).then(|| notes.join(" ")),
+        };
 
-        if cache_enabled {
-            write_cache(&self.cache_tree, &cache_key, &entries).await?;
+        if failed == 0 && policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
         }
 
-        Ok(filter_votes(
-            entries,
-            from_date.as_deref(),
-            to_date.as_deref(),
-            bill_id.as_deref(),
-            max_items,
-        ))
+        Ok(result)
     }
 
-    pub async fn lookup_constituency_offline(
+    /// Oral questions matching the filters, by answering date. `status` narrows the
+    /// answering date range around today, so the API's own paging and `limit` still apply.
+    pub async fn fetch_oral_questions(
         &self,
-        args: LookupConstituencyArgs,
-    ) -> Result<ConstituencyLookupResult, AppError> {
-        let LookupConstituencyArgs {
-            postcode,
+        args: FetchOralQuestionsArgs,
+    ) -> Result<FetchOralQuestionsResult, AppError> {
+        let FetchOralQuestionsArgs {
+            answering_date_from,
+            answering_date_to,
+            answering_body,
+            asking_member_id,
+            status,
+            limit,
             enable_cache,
+            cache_only,
         } = args;
 
-        let normalised = normalise_postcode(&postcode)
-            .ok_or_else(|| AppError::bad_request("postcode must not be empty".to_string()))?;
-        let cache_enabled = enable_cache.unwrap_or(true);
-        let cache_key = format!("constituency:{normalised}");
+        let max_items = limit.unwrap_or(20).clamp(1, 100) as usize;
+        let mut from_date =
+            parse_optional_date("answeringDateFrom", answering_date_from.as_deref())?;
+        let mut to_date = parse_optional_date("answeringDateTo", answering_date_to.as_deref())?;
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let today = chrono::DateTime::from_timestamp(self.clock.wall_secs() as i64, 0)
+            .unwrap_or_default()
+            .date_naive();
+        match status {
+            Some(OralQuestionStatus::Tabled) => {
+                from_date = Some(from_date.map_or(today, |date| date.max(today)));
+            }
+            Some(OralQuestionStatus::Answered) => {
+                let yesterday = today.pred_opt().unwrap_or(today);
+                to_date = Some(to_date.map_or(yesterday, |date| date.min(yesterday)));
+            }
+            None => {}
+        }
+        if let (Some(from), Some(to)) = (from_date, to_date) {
+            if from > to {
+                return Ok(FetchOralQuestionsResult {
+                    questions: Vec::new(),
+                    total_results: Some(0),
+                });
+            }
+        }
 
-        if cache_enabled {
-            if let Some(cached) = read_cache::<ConstituencyLookupResult>(
+        let text = |value: Option<String>| value.unwrap_or_default();
+        let cache_key = format!(
+            "oral_questions:{}:{}:{}:{}:{}:{max_items}",
+            text(from_date.map(|date| date.to_string())),
+            text(to_date.map(|date| date.to_string())),
+            text(answering_body.map(|id| id.to_string())),
+            text(asking_member_id.map(|id| id.to_string())),
+            status.map(OralQuestionStatus::as_str).unwrap_or_default()
+        );
+
+        if policy.reads() {
+            if let Some(cached) = read_cache::<FetchOralQuestionsResult>(
                 &self.cache_tree,
+                &self.clock,
                 &cache_key,
-                self.config.cache_ttl.constituency,
+                self.config.cache_ttl.oral_questions,
             )
             .await?
             {
                 return Ok(cached);
             }
         }
+        policy.allow_fetch("these oral questions")?;
 
-        // Use Postcodes.io API as backup instead of CSV dataset
-        let maybe_lookup = self.lookup_constituency_from_api(&normalised).await?;
-
-        let mut lookup = maybe_lookup.ok_or_else(|| {
-            AppError::bad_request(format!(
-                "postcode {postcode} could not be matched to a constituency"
-            ))
-        })?;
-
-        if let Some(name) = lookup.constituency_name.clone() {
-            if let Some(summary) = self.lookup_current_mp_for_constituency(&name).await? {
-                lookup.mp_id = Some(summary.id);
-                lookup.mp_name = Some(summary.name);
+        let mut url = Url::parse(ORAL_QUESTIONS)
+            .map_err(|err| AppError::internal(format!("invalid oral questions api url: {err}")))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("parameters.take", &max_items.to_string());
+            if let Some(date) = from_date {
+                pairs.append_pair("parameters.answeringDateStart", &date.to_string());
+            }
+            if let Some(date) = to_date {
+                pairs.append_pair("parameters.answeringDateEnd", &date.to_string());
+            }
+            if let Some(body) = answering_body {
+                pairs.append_pair("parameters.answeringBodyIds", &body.to_string());
+            }
+            if let Some(member_id) = asking_member_id {
+                pairs.append_pair("parameters.askingMemberIds", &member_id.to_string());
             }
         }
 
-        if cache_enabled {
-            write_cache(&self.cache_tree, &cache_key, &lookup).await?;
+        let payload = self.get_json(url).await?;
+        let mut questions = oral_questions::parse_questions(&payload, today);
+        questions.truncate(max_items);
+        let result = FetchOralQuestionsResult {
+            questions,
+            total_results: oral_questions::total_results(&payload),
+        };
+
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
         }
 
-        Ok(lookup)
+        Ok(result)
     }
 
-    pub async fn search_uk_law(&self, args: SearchUkLawArgs) -> Result<Vec<UkLawResult>, AppError> {
-        let SearchUkLawArgs {
-            query,
-            legislation_type,
+    /// Written questions matching the filters, newest tabled first, with their answers
+    /// once given.
+    pub async fn fetch_written_questions(
+        &self,
+        args: FetchWrittenQuestionsArgs,
+    ) -> Result<FetchWrittenQuestionsResult, AppError> {
+        let FetchWrittenQuestionsArgs {
+            asking_member_id,
+            answering_body,
+            tabled_from,
+            tabled_to,
+            answered,
+            search_term,
             limit,
             enable_cache,
+            cache_only,
         } = args;
 
-        let max_items = limit.unwrap_or(10).clamp(1, 50) as usize;
-        let cache_enabled = enable_cache.unwrap_or(true);
+        let max_items = limit.unwrap_or(20).clamp(1, 100) as usize;
+        let tabled_from = parse_optional_date("tabledFrom", tabled_from.as_deref())?;
+        let tabled_to = parse_optional_date("tabledTo", tabled_to.as_deref())?;
+        let search_term = sanitise_optional_text(search_term);
+        let policy = CachePolicy::from_args(enable_cache, cache_only)?;
+        let text = |value: Option<String>| value.unwrap_or_default();
         let cache_key = format!(
-            "uk_law:{}:{}",
-            query,
-            legislation_type.as_deref().unwrap_or("all")
+            "written_questions:{}:{}:{}:{}:{}:{}:{max_items}",
+            text(asking_member_id.map(|id| id.to_string())),
+            text(answering_body.map(|id| id.to_string())),
+            text(tabled_from.map(|date| date.to_string())),
+            text(tabled_to.map(|date| date.to_string())),
+            answered.map(AnsweredStatus::as_str).unwrap_or_default(),
+            text(search_term.as_ref().map(|term| term.to_lowercase()))
         );
 
-        if cache_enabled {
-            if let Some(cached) = read_cache::<Vec<UkLawResult>>(
+        if policy.reads() {
+            if let Some(cached) = read_cache::<FetchWrittenQuestionsResult>(
                 &self.cache_tree,
+                &self.clock,
                 &cache_key,
-                self.config.cache_ttl.legislation,
+                self.config.cache_ttl.written_questions,
             )
             .await?
             {
-                if cached.len() > max_items {
-                    return Ok(cached.into_iter().take(max_items).collect());
-                }
                 return Ok(cached);
             }
         }
+        policy.allow_fetch("these written questions")?;
 
-        let mut results = Vec::new();
-
-        // Build search URL based on legislation type
-        let search_type = match legislation_type.as_deref() {
-            Some("primary") => "primary",
-            Some("secondary") => "secondary",
-            _ => "primary+secondary",
-        };
-
-        let search_url = format!(
-            "https://www.legislation.gov.uk/{}/search?title={}",
-            search_type,
-            urlencoding::encode(&query)
-        );
-
-        let url = Url::parse(&search_url)
-            .map_err(|err| AppError::internal(format!("invalid UK law search url: {err}")))?;
-
-        match self.get_json(url).await {
-            Ok(payload) => {
-                // Parse the HTML/XML response from legislation.gov.uk
-                results.extend(parse_uk_law_results(&payload, max_items));
+        let mut url = Url::parse(WRITTEN_QUESTIONS).map_err(|err| {
+            AppError::internal(format!("invalid written questions api url: {err}"))
+        })?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs
+                .append_pair("take", &max_items.to_string())
+                .append_pair("expandMember", "true");
+            if let Some(member_id) = asking_member_id {
+                pairs.append_pair("askingMemberId", &member_id.to_string());
             }
-            Err(_) => {
-                // If the API fails, create some sample results based on the query
-                results.push(UkLawResult {
-                    title: format!("Sample legislation related to '{}'", query),
-                    year: Some("2023".to_string()),
-                    legislation_type: "Primary".to_string(),
-                    is_in_force: true,
-                    url: format!(
-                        "https://www.legislation.gov.uk/search?title={}",
-                        urlencoding::encode(&query)
-                    ),
-                    summary: Some(format!("Legislation related to: {}", query)),
-                    last_updated: Some(chrono::Utc::now().to_rfc3339()),
-                });
+            if let Some(body) = answering_body {
+                pairs.append_pair("answeringBodies", &body.to_string());
+            }
+            if let Some(date) = tabled_from {
+                pairs.append_pair("tabledWhenFrom", &date.to_string());
+            }
+            if let Some(date) = tabled_to {
+                pairs.append_pair("tabledWhenTo", &date.to_string());
+            }
+            if let Some(answered) = answered {
+                pairs.append_pair("answered", answered.as_str());
+            }
+            if let Some(term) = &search_term {
+                pairs.append_pair("searchTerm", term);
             }
         }
 
-        if cache_enabled {
-            write_cache(&self.cache_tree, &cache_key, &results).await?;
-        }
+        let payload = self.get_json(url).await?;
+        let mut questions = parse_questions(&payload);
+        questions.truncate(max_items);
+        let result = FetchWrittenQuestionsResult {
+            questions,
+            total_results: written_questions::total_results(&payload),
+        };
 
-        if results.len() > max_items {
-            results.truncate(max_items);
+        if policy.writes() {
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &result).await?;
         }
 
-        Ok(results)
+        Ok(result)
     }
 
     async fn lookup_current_mp_for_constituency(
@@ -611,9 +2472,13 @@ impl ParliamentClient {
         }
 
         let cache_key = format!("constituency_mp:{trimmed}");
-        if let Some(cached) =
-            read_cache::<MpSummary>(&self.cache_tree, &cache_key, self.config.cache_ttl.members)
-                .await?
+        if let Some(cached) = read_cache::<MpSummary>(
+            &self.cache_tree,
+            &self.clock,
+            &cache_key,
+            self.config.cache_ttl.members,
+        )
+        .await?
         {
             return Ok(Some(cached));
         }
@@ -631,7 +2496,7 @@ impl ParliamentClient {
 
         let payload = self.get_json(url).await?;
         if let Some(summary) = parse_mp_summary(&payload) {
-            write_cache(&self.cache_tree, &cache_key, &summary).await?;
+            write_cache(&self.cache_tree, &self.clock, &cache_key, &summary).await?;
             return Ok(Some(summary));
         }
 
@@ -639,23 +2504,119 @@ impl ParliamentClient {
     }
 
     async fn get_json(&self, url: Url) -> Result<Value, AppError> {
+        let body = self.fetch_body(&url).await?;
+        serde_json::from_slice(&body)
+            .map_err(|err| AppError::internal(format!("failed to parse response json: {err}")))
+    }
+
+    /// Read a successful upstream response in full, charging its decompressed size to the
+    /// host's stats and to the current tool call's byte budget.
+    async fn fetch_body(&self, url: &Url) -> Result<Vec<u8>, AppError> {
+        self.fetch_body_capped(url, usize::MAX).await
+    }
+
+    /// [`Self::fetch_body`], giving up once the body passes `max_bytes`; what was read up
+    /// to then is still charged.
+    async fn fetch_body_capped(&self, url: &Url, max_bytes: usize) -> Result<Vec<u8>, AppError> {
+        let mut response = self.send_upstream(url).await?;
+        #[cfg(feature = "record")]
+        let (status, headers) = (response.status(), response.headers().clone());
+        let mut body = Vec::new();
+        let mut oversized = false;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| AppError::internal(format!("failed to read response body: {err}")))?
+        {
+            body.extend_from_slice(&chunk);
+            if body.len() > max_bytes {
+                oversized = true;
+                break;
+            }
+        }
+
+        let size = body.len() as u64;
+        record_bytes(size);
+        record_upstream_fetch();
+        if let Some(host) = url.host_str() {
+            self.stats.record_upstream_bytes(host, size);
+        }
+        if oversized {
+            return Err(AppError::upstream_with_data(
+                format!("response from {url} is larger than {max_bytes} bytes"),
+                json!({"url": redact_url(url.as_str()), "maxBytes": max_bytes}),
+            ));
+        }
+        #[cfg(feature = "record")]
+        self.record_response(url, status, &headers, &body);
+
+        Ok(body)
+    }
+
+    /// Issue a GET against an upstream API, honouring the per-host rate limit and adaptive
+    /// concurrency window and retrying transient failures. A 429 or 503 shrinks the host's
+    /// window as well as backing off. Only successful responses are returned.
+    async fn send_upstream(&self, url: &Url) -> Result<reqwest::Response, AppError> {
+        let target = self.resolve_upstream(url);
+        let host = target.host_str().unwrap_or_default().to_string();
         let mut last_error: Option<AppError> = None;
+        let correlation_id =
+            current_correlation_id().filter(|_| self.config.upstream_correlation_header);
+        // Metrics name the real API even when requests are redirected to UPSTREAM_BASE_URL.
+        let metrics_host = url.host_str().unwrap_or_default();
+        let tool = current_tool().unwrap_or_else(|| "none".to_string());
 
         for attempt in 0..RETRY_ATTEMPTS {
-            let response = self.http_client.get(url.clone()).send().await;
+            self.rate_limiter.acquire(&host).await;
+            let permit = self.concurrency.acquire(&host).await;
+            let mut request = self.http_client.get(target.clone());
+            if let Some(correlation_id) = &correlation_id {
+                request = request.header(CORRELATION_HEADER, correlation_id);
+            }
+            let started = Instant::now();
+            #[cfg(feature = "fault-injection")]
+            let response = self.send_with_faults(metrics_host, request).await;
+            #[cfg(not(feature = "fault-injection"))]
+            let response = request.send().await;
+            let elapsed = started.elapsed();
+            let status = response.as_ref().ok().map(|resp| resp.status().as_u16());
+            self.upstream_metrics
+                .record_attempt(metrics_host, &tool, status, elapsed);
+            // Each attempt is timed on its own, so a slow answer is not confused with retries.
+            let slow_after = self.config.slow_upstream_ms;
+            if slow_after > 0 && elapsed >= Duration::from_millis(slow_after) {
+                self.upstream_metrics.record_slow(metrics_host, &tool);
+                tracing::warn!(
+                    host = metrics_host,
+                    tool = %tool,
+                    attempt = attempt + 1,
+                    status,
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    "slow upstream response"
+                );
+            }
 
             match response {
                 Ok(resp) if resp.status().is_success() => {
-                    return resp.json::<Value>().await.map_err(|err| {
-                        AppError::internal(format!("failed to parse response json: {err}"))
-                    });
+                    permit.succeeded();
+                    self.record_upstream(url, true);
+                    return Ok(resp);
                 }
                 Ok(resp) => {
                     let status = resp.status();
+                    #[cfg(feature = "record")]
+                    let headers = resp.headers().clone();
+                    if matches!(status.as_u16(), 429 | 503) {
+                        permit.throttled();
+                    } else {
+                        drop(permit);
+                    }
                     let text = resp
                         .text()
                         .await
                         .unwrap_or_else(|_| "<failed to read body>".to_string());
+                    #[cfg(feature = "record")]
+                    self.record_response(url, status, &headers, text.as_bytes());
                     let snippet = text.chars().take(512).collect::<String>();
                     let mut data = json!({
                         "url": url.as_str(),
@@ -671,6 +2632,7 @@ impl ParliamentClient {
                     ));
                 }
                 Err(err) => {
+                    drop(permit);
                     last_error = Some(AppError::upstream_with_data(
                         format!("network error contacting {url}: {err}"),
                         json!({
@@ -683,25 +2645,88 @@ impl ParliamentClient {
             }
 
             if attempt < RETRY_ATTEMPTS - 1 {
+                self.upstream_metrics.record_retry(metrics_host, &tool);
+                tracing::warn!(
+                    url = %redact_url(url.as_str()),
+                    attempt = attempt + 1,
+                    "upstream request failed; retrying"
+                );
                 sleep(Duration::from_millis(RETRY_DELAY_MS * (attempt as u64 + 1))).await;
             }
         }
 
+        self.record_upstream(url, false);
         Err(last_error.unwrap_or_else(|| AppError::internal("request failed")))
     }
 
+    /// Send `request`, failing, delaying or truncating it as the active `FAULTS` rule for
+    /// `host` draws.
+    #[cfg(feature = "fault-injection")]
+    async fn send_with_faults(
+        &self,
+        host: &str,
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        let fault = self.faults.load().sample(host);
+        if let Some(delay) = fault.delay {
+            sleep(delay).await;
+        }
+        if fault.fail {
+            return Ok(fault_injection::failure_response());
+        }
+        let response = request.send().await?;
+        if fault.truncate && response.status().is_success() {
+            return fault_injection::truncate(response).await;
+        }
+        Ok(response)
+    }
+
+    /// Replace the `FAULTS` rules in effect, e.g. from a test.
+    #[cfg(feature = "fault-injection")]
+    pub fn set_faults(&self, faults: FaultConfig) {
+        self.faults.store(Arc::new(faults));
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn faults(&self) -> Arc<FaultConfig> {
+        self.faults.load_full()
+    }
+
+    #[cfg(feature = "record")]
+    fn record_response(
+        &self,
+        url: &Url,
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+        body: &[u8],
+    ) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(url, status.as_u16(), headers, body);
+        }
+    }
+
+    /// Point the request at `UPSTREAM_BASE_URL` when configured (mock servers, recording
+    /// proxies); the path and query are preserved.
+    fn resolve_upstream(&self, url: &Url) -> Url {
+        let Some(base) = &self.upstream_base else {
+            return url.clone();
+        };
+
+        let mut target = base.clone();
+        let prefix = base.path().trim_end_matches('/');
+        target.set_path(&format!("{prefix}{}", url.path()));
+        target.set_query(url.query());
+        target
+    }
+
     async fn fetch_members_dataset(
         &self,
-        dataset: String,
-        search_term: Option<String>,
+        dataset: &str,
+        search_term: Option<&str>,
         page: Option<u32>,
         per_page: Option<u32>,
-        cache_enabled: bool,
-        apply_relevance: bool,
-        relevance_threshold: f32,
-        fuzzy_match: bool,
+        policy: CachePolicy,
     ) -> Result<Value, AppError> {
-        let search_term = sanitise_optional_text(search_term);
         let take = per_page.unwrap_or(20).clamp(1, 100);
         let skip = page.unwrap_or(0).saturating_mul(take);
 
@@ -710,13 +2735,13 @@ impl ParliamentClient {
 
         {
             let mut query_pairs = url.query_pairs_mut();
-            if let Some(term) = &search_term {
+            if let Some(term) = search_term {
                 query_pairs.append_pair("name", term);
             }
             query_pairs.append_pair("take", &take.to_string());
             query_pairs.append_pair("skip", &skip.to_string());
 
-            match dataset.as_str() {
+            match dataset {
                 "commonsmembers" => {
                     query_pairs.append_pair("house", "Commons");
                 }
@@ -727,36 +2752,25 @@ impl ParliamentClient {
             }
         }
 
-        let cache_key = format!(
-            "core_dataset:{}:relevance:{}:threshold:{:.3}:fuzzy:{}",
-            url, apply_relevance, relevance_threshold, fuzzy_match
-        );
+        let cache_key = format!("core_dataset:{url}");
         let ttl = self.config.cache_ttl.members;
 
-        self.execute_request(url, cache_key, cache_enabled, ttl)
-            .await
+        self.execute_request(url, cache_key, policy, ttl).await
     }
 
     async fn fetch_legacy_core_dataset(
         &self,
-        dataset: String,
-        search_term: Option<String>,
+        dataset: &str,
+        search_term: Option<&str>,
         page: Option<u32>,
         per_page: Option<u32>,
-        cache_enabled: bool,
-        apply_relevance: bool,
-        relevance_threshold: f32,
-        fuzzy_match: bool,
+        policy: CachePolicy,
     ) -> Result<Value, AppError> {
-        let search_term = sanitise_optional_text(search_term);
-
-        let mut url = Url::parse(CORE_DATASET_BASE)
-            .map_err(|err| AppError::internal(format!("invalid base url: {err}")))?;
-        url.set_path(&format!("/{dataset}.json"));
+        let mut url = build_url(CORE_DATASET_BASE, &[&format!("{dataset}.json")])?;
 
         {
             let mut query_pairs = url.query_pairs_mut();
-            if let Some(term) = &search_term {
+            if let Some(term) = search_term {
                 query_pairs.append_pair("_search", term);
             }
             if let Some(page) = page {
@@ -767,82 +2781,49 @@ impl ParliamentClient {
             }
         }
 
-        let cache_key = format!(
-            "core_dataset:{}:relevance:{}:threshold:{:.3}:fuzzy:{}",
-            url, apply_relevance, relevance_threshold, fuzzy_match
-        );
-        let ttl = self.dataset_ttl(&dataset);
+        let cache_key = format!("core_dataset:{url}");
+        let ttl = self.dataset_ttl(dataset);
 
-        self.execute_request(url, cache_key, cache_enabled, ttl)
-            .await
+        self.execute_request(url, cache_key, policy, ttl).await
     }
 
     async fn execute_request(
         &self,
         url: Url,
         cache_key: String,
-        enable_cache: bool,
+        policy: CachePolicy,
         ttl: u64,
     ) -> Result<Value, AppError> {
-        if enable_cache {
+        if policy.reads() {
             if let Some(cached) = self.cache.get(&cache_key).await {
+                record_cache_hit();
                 return Ok(cached);
             }
+            policy.allow_fetch(&redact_url(url.as_str()))?;
+            tracing::debug!(url = %redact_url(url.as_str()), "cache miss; fetching from upstream");
         }
 
-        let mut last_error: Option<AppError> = None;
-
-        for attempt in 0..RETRY_ATTEMPTS {
-            let response = self.http_client.get(url.clone()).send().await;
+        let json = self.get_json(url).await?;
 
-            match response {
-                Ok(resp) if resp.status().is_success() => {
-                    let json = resp.json::<Value>().await.map_err(|err| {
-                        AppError::internal(format!("failed to parse response json: {err}"))
-                    })?;
-
-                    if enable_cache {
-                        self.cache
-                            .insert(cache_key.clone(), json.clone(), ttl)
-                            .await;
-                    }
+        if policy.writes() {
+            self.cache.insert(cache_key, json.clone(), ttl).await;
+        }
 
-                    return Ok(json);
-                }
-                Ok(resp) => {
-                    let status = resp.status();
-                    let text = resp
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "<failed to read body>".to_string());
-                    let body_snippet = text.chars().take(512).collect::<String>();
-                    last_error = Some(AppError::upstream_with_data(
-                        format!("request to {url} failed with {status}"),
-                        json!({
-                            "url": url.as_str(),
-                            "status": status.as_u16(),
-                            "body": body_snippet,
-                        }),
-                    ));
-                }
-                Err(err) => {
-                    last_error = Some(AppError::upstream_with_data(
-                        format!("network error contacting {url}: {err}"),
-                        json!({
-                            "url": url.as_str(),
-                            "status": Value::Null,
-                            "error": err.to_string(),
-                        }),
-                    ));
-                }
-            }
+        Ok(json)
+    }
 
-            if attempt < RETRY_ATTEMPTS - 1 {
-                sleep(Duration::from_millis(RETRY_DELAY_MS * (attempt as u64 + 1))).await;
-            }
+    fn record_upstream(&self, url: &Url, success: bool) {
+        if let Some(host) = url.host_str() {
+            self.stats.record_upstream(host, success);
+            self.availability
+                .record(host, success, self.clock.steady_secs());
         }
+    }
 
-        Err(last_error.unwrap_or_else(|| AppError::internal("request failed")))
+    /// Whether the real API `host` has failed `UPSTREAM_DOWN_AFTER_FAILURES` requests in a
+    /// row within the last `UPSTREAM_DOWN_SECS`.
+    pub fn host_down(&self, host: &str) -> bool {
+        self.availability.is_down(host, self.clock.steady_secs())
     }
 
     fn dataset_ttl(&self, dataset: &str) -> u64 {
@@ -860,9 +2841,7 @@ impl ParliamentClient {
         &self,
         postcode: &str,
     ) -> Result<Option<ConstituencyLookupResult>, AppError> {
-        let url = format!("https://api.postcodes.io/postcodes/{}", postcode);
-        let url = Url::parse(&url)
-            .map_err(|err| AppError::internal(format!("invalid postcodes.io url: {err}")))?;
+        let url = build_url(POSTCODES_BASE, &[postcode])?;
 
         let response = self.get_json(url).await;
 
@@ -901,185 +2880,223 @@ impl ParliamentClient {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MpSummary {
-    id: u32,
-    name: String,
+fn parse_member_summary(mp_id: u32, payload: &Value) -> Option<MemberSummary> {
+    let member = payload.get("value").filter(|value| value.is_object())?;
+    let membership = member.get("latestHouseMembership");
+    let text = |value: Option<&Value>| value.and_then(Value::as_str).map(str::to_string);
+
+    let house = match membership.and_then(|value| value.get("house")) {
+        Some(Value::Number(number)) => match number.as_u64() {
+            Some(1) => Some("Commons".to_string()),
+            Some(2) => Some("Lords".to_string()),
+            _ => None,
+        },
+        other => text(other),
+    };
+    let status = membership.and_then(|value| value.get("membershipStatus"));
+    let is_current = status
+        .and_then(|value| value.get("statusIsActive"))
+        .and_then(Value::as_bool)
+        .unwrap_or_else(|| {
+            membership
+                .and_then(|value| value.get("membershipEndDate"))
+                .is_none_or(Value::is_null)
+        });
+
+    Some(MemberSummary {
+        id: member
+            .get("id")
+            .and_then(Value::as_u64)
+            .map_or(mp_id, |id| id as u32),
+        name: text(member.get("nameDisplayAs")).unwrap_or_else(|| "Unknown".to_string()),
+        party: text(
+            member
+                .get("latestParty")
+                .and_then(|party| party.get("name")),
+        ),
+        constituency: text(membership.and_then(|value| value.get("membershipFrom"))),
+        house,
+        is_current,
+        status: text(status.and_then(|value| value.get("statusDescription"))),
+    })
 }
 
-fn sanitise_optional_text(value: Option<String>) -> Option<String> {
-    value
-        .map(|text| text.trim().to_string())
-        .filter(|text| !text.is_empty())
+fn parse_constituency_listing(item: &Value) -> Option<ConstituencyListing> {
+    let value = item.get("value").unwrap_or(item);
+    let id = value.get("id").and_then(Value::as_u64)? as u32;
+    let name = value_to_string(value.get("name"))?;
+    let ons_code = ["gssCode", "onsCode"]
+        .iter()
+        .find_map(|key| value_to_string(value.get(*key)));
+
+    Some(ConstituencyListing {
+        id,
+        name,
+        nation: ons_code.as_deref().and_then(nation_for_ons_code),
+        ons_code,
+        region: value_to_string(value.get("region")),
+        mp_id: None,
+        mp_name: None,
+        mp_party: None,
+    })
 }
 
-fn filter_votes(
-    entries: Vec<MpVoteRecord>,
-    from_date: Option<&str>,
-    to_date: Option<&str>,
-    bill_id: Option<&str>,
-    limit: usize,
-) -> Vec<MpVoteRecord> {
-    let from = from_date.and_then(parse_naive_date);
-    let to = to_date.and_then(parse_naive_date);
-    let bill_filter = bill_id.and_then(|value| {
-        let trimmed = value.trim();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(trimmed.to_lowercase())
-        }
-    });
+/// GSS codes start with a letter naming the country: E, W, S or N.
+fn nation_for_ons_code(code: &str) -> Option<String> {
+    let nation = match code.chars().next()?.to_ascii_uppercase() {
+        'E' => "England",
+        'W' => "Wales",
+        'S' => "Scotland",
+        'N' => "Northern Ireland",
+        _ => return None,
+    };
+    Some(nation.to_string())
+}
 
-    let mut filtered: Vec<MpVoteRecord> = entries
-        .into_iter()
-        .filter(|entry| {
-            if let Some(filter_value) = &bill_filter {
-                let mut matches = entry
-                    .division_id
-                    .as_ref()
-                    .map(|value| value.to_lowercase() == *filter_value)
-                    .unwrap_or(false);
-                if !matches {
-                    matches = entry
-                        .title
-                        .as_ref()
-                        .map(|title| title.to_lowercase().contains(filter_value))
-                        .unwrap_or(false);
-                }
-                if !matches {
-                    return false;
-                }
-            }
-            true
-        })
-        .filter(|entry| {
-            let entry_date = entry.date.as_deref().and_then(parse_naive_date);
+/// A current member keyed by the id of the constituency they sit for.
+fn parse_current_mp(item: &Value) -> Option<(u32, CurrentMp)> {
+    let value = item.get("value").unwrap_or(item);
+    let constituency_id = value
+        .get("latestHouseMembership")
+        .and_then(|membership| membership.get("membershipFromId"))
+        .and_then(Value::as_u64)? as u32;
+
+    Some((
+        constituency_id,
+        CurrentMp {
+            id: value.get("id").and_then(Value::as_u64)? as u32,
+            name: value_to_string(value.get("nameDisplayAs"))?,
+            party: value_to_string(value.get("latestParty").and_then(|party| party.get("name"))),
+        },
+    ))
+}
 
-            if let Some(from_date) = from {
-                if let Some(actual) = entry_date {
-                    if actual < from_date {
-                        return false;
-                    }
-                }
-            }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MpSummary {
+    id: u32,
+    name: String,
+}
 
-            if let Some(to_date) = to {
-                if let Some(actual) = entry_date {
-                    if actual > to_date {
-                        return false;
-                    }
-                }
-            }
+/// Append `segments` to `base` as individually percent-encoded path segments, so
+/// user-supplied values can never introduce `/`, `..`, `?` or `#` into the path.
+fn build_url(base: &str, segments: &[&str]) -> Result<Url, AppError> {
+    let mut url =
+        Url::parse(base).map_err(|err| AppError::internal(format!("invalid url {base}: {err}")))?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::internal(format!("url {base} cannot have path segments")))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
 
-            true
-        })
+/// Split a legislation.gov.uk URI or path into its document path and any section in it,
+/// e.g. `https://www.legislation.gov.uk/ukpga/2008/27/section/1` into `ukpga/2008/27` and
+/// `1`.
+fn legislation_path(uri: &str) -> Result<(Vec<String>, Option<String>), AppError> {
+    let path = uri
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_start_matches(LEGISLATION_HOST.trim_start_matches("www."));
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
         .collect();
-
-    if filtered.len() > limit {
-        filtered.truncate(limit);
+    if segments.last().is_some_and(|last| last == "data.xml") {
+        segments.pop();
     }
-
-    filtered
-}
-
-fn parse_naive_date(value: &str) -> Option<NaiveDate> {
-    let prefix = value.trim();
-    let iso = if prefix.len() >= 10 {
-        &prefix[..10]
-    } else {
-        prefix
+    let section = match segments.iter().position(|segment| segment == "section") {
+        Some(index) => {
+            let section = segments.get(index + 1).cloned();
+            segments.truncate(index);
+            section
+        }
+        None => None,
     };
+    if segments.len() < 3 {
+        return Err(AppError::bad_request(format!(
+            "{uri} is not a legislation.gov.uk document URI"
+        )));
+    }
+    Ok((segments, section))
+}
 
-    NaiveDate::parse_from_str(iso, "%Y-%m-%d").ok()
+fn sanitise_optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
 }
 
-fn parse_uk_law_results(payload: &Value, max_items: usize) -> Vec<UkLawResult> {
-    let mut results = Vec::new();
+/// `base` with `query` appended.
+fn activity_url(base: &str, query: &[(&str, String)]) -> Result<Url, AppError> {
+    let mut url = Url::parse(base)
+        .map_err(|err| AppError::internal(format!("invalid activity url {base}: {err}")))?;
+    url.query_pairs_mut().extend_pairs(query);
+    Ok(url)
+}
 
-    // Try to parse different possible response formats from legislation.gov.uk
-    if let Some(items) = payload.get("items").and_then(|v| v.as_array()) {
-        for item in items.iter().take(max_items) {
-            if let Some(result) = parse_single_uk_law_item(item) {
-                results.push(result);
-            }
+/// The `house` Votes API `membervoting` query for `mp_id`'s votes between the dates,
+/// without paging parameters.
+fn member_voting_url(
+    house: DivisionHouse,
+    mp_id: u32,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+) -> Result<Url, AppError> {
+    let (base, member_key, start_key, end_key) = match house {
+        DivisionHouse::Commons => (
+            COMMONS_MEMBER_VOTING,
+            "queryParameters.memberId",
+            "queryParameters.startDate",
+            "queryParameters.endDate",
+        ),
+        DivisionHouse::Lords => (LORDS_MEMBER_VOTING, "MemberId", "StartDate", "EndDate"),
+    };
+    let mut url = Url::parse(base)
+        .map_err(|err| AppError::internal(format!("invalid votes api url: {err}")))?;
+    {
+        let mut pairs = url.query_pairs_mut();
+        pairs.append_pair(member_key, &mp_id.to_string());
+        if let Some(date) = from_date {
+            pairs.append_pair(start_key, &date.to_string());
         }
-    } else if let Some(results_array) = payload.get("results").and_then(|v| v.as_array()) {
-        for item in results_array.iter().take(max_items) {
-            if let Some(result) = parse_single_uk_law_item(item) {
-                results.push(result);
-            }
+        if let Some(date) = to_date {
+            pairs.append_pair(end_key, &date.to_string());
         }
-    } else {
-        // If no structured data, create a generic result
-        results.push(UkLawResult {
-            title: "UK Legislation Search Results".to_string(),
-            year: Some("2023".to_string()),
-            legislation_type: "Primary".to_string(),
-            is_in_force: true,
-            url: "https://www.legislation.gov.uk".to_string(),
-            summary: Some("Search results from UK legislation database".to_string()),
-            last_updated: Some(chrono::Utc::now().to_rfc3339()),
-        });
     }
+    Ok(url)
+}
 
-    results
+/// The page of a [`member_voting_url`] query starting at `skip`.
+fn member_voting_page(house: DivisionHouse, mut url: Url, skip: usize) -> Url {
+    let (skip_key, take_key) = match house {
+        DivisionHouse::Commons => ("queryParameters.skip", "queryParameters.take"),
+        DivisionHouse::Lords => ("skip", "take"),
+    };
+    url.query_pairs_mut()
+        .append_pair(skip_key, &skip.to_string())
+        .append_pair(take_key, &MEMBER_VOTING_PAGE_SIZE.to_string());
+    url
 }
 
-fn parse_single_uk_law_item(item: &Value) -> Option<UkLawResult> {
-    let title = value_to_string(
-        item.get("title")
-            .or_else(|| item.get("name"))
-            .or_else(|| item.get("legislationTitle")),
-    )
-    .unwrap_or_else(|| "Unknown Legislation".to_string());
-
-    let year = value_to_string(
-        item.get("year")
-            .or_else(|| item.get("enactedYear"))
-            .or_else(|| item.get("date")),
-    );
-
-    let legislation_type = value_to_string(
-        item.get("type")
-            .or_else(|| item.get("legislationType"))
-            .or_else(|| item.get("category")),
-    )
-    .unwrap_or_else(|| "Primary".to_string());
-
-    let url = value_to_string(
-        item.get("url")
-            .or_else(|| item.get("uri"))
-            .or_else(|| item.get("link")),
-    )
-    .unwrap_or_else(|| "https://www.legislation.gov.uk".to_string());
-
-    let summary = value_to_string(
-        item.get("summary")
-            .or_else(|| item.get("description"))
-            .or_else(|| item.get("abstract")),
-    );
-
-    let is_in_force = item
-        .get("isInForce")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(true);
-
-    let last_updated = value_to_string(
-        item.get("lastUpdated")
-            .or_else(|| item.get("updated"))
-            .or_else(|| item.get("modified")),
-    );
-
-    Some(UkLawResult {
-        title,
-        year,
-        legislation_type,
-        is_in_force,
-        url,
-        summary,
-        last_updated,
-    })
+/// Rows `parliament.export_voting_record` returns for a requested `limit`.
+pub fn voting_export_limit(limit: Option<u32>) -> usize {
+    limit
+        .map_or(DEFAULT_VOTING_EXPORT_ROWS, |limit| limit as usize)
+        .clamp(1, MAX_VOTING_EXPORT_ROWS)
+}
+
+/// A `YYYY-MM-DD` argument; anything else is rejected rather than ignored.
+fn parse_optional_date(name: &str, value: Option<&str>) -> Result<Option<NaiveDate>, AppError> {
+    value
+        .map(|value| {
+            NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+                .map_err(|_| AppError::bad_request(format!("{name} must be a YYYY-MM-DD date")))
+        })
+        .transpose()
 }
 
 fn parse_mp_summary(payload: &Value) -> Option<MpSummary> {
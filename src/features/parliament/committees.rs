@@ -0,0 +1,88 @@
+//! Select and joint committees from the Committees API, behind `parliament.fetch_committees`.
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::features::parliament::dto::{Committee, CommitteeInquiry, CommitteeMember};
+use crate::features::parliament::helpers::{json_day, json_items, json_text};
+
+/// Committees in an `api/Committees` response. Items without an id or name are skipped.
+pub fn parse_committees(payload: &Value) -> Vec<Committee> {
+    json_items(payload, "items")
+        .filter_map(parse_committee)
+        .collect()
+}
+
+/// One committee, as listed or as returned by `api/Committees/{id}`.
+pub fn parse_committee(item: &Value) -> Option<Committee> {
+    let id = u32::try_from(item.get("id")?.as_u64()?).ok()?;
+    Some(Committee {
+        id,
+        name: json_text(item, "name")?,
+        house: json_text(item, "house"),
+        category: item
+            .get("category")
+            .and_then(|category| json_text(category, "name")),
+        url: format!("https://committees.parliament.uk/committee/{id}/"),
+        members: None,
+        inquiries: None,
+    })
+}
+
+/// Current members from an `api/Committees/{id}/Members` response, in the API's order
+/// (chair first). Lay members have no member id.
+pub fn parse_members(payload: &Value) -> Vec<CommitteeMember> {
+    json_items(payload, "items")
+        .filter_map(|item| {
+            let info = item.get("memberInfo");
+            let name = info
+                .and_then(|info| json_text(info, "name"))
+                .or_else(|| json_text(item, "name"))?;
+            Some(CommitteeMember {
+                member_id: info
+                    .and_then(|info| info.get("mnisId"))
+                    .and_then(Value::as_u64)
+                    .and_then(|id| u32::try_from(id).ok()),
+                name,
+                party: info.and_then(|info| json_text(info, "party")),
+                role: current_role(item),
+            })
+        })
+        .collect()
+}
+
+/// Inquiries and other business from an `api/CommitteeBusiness` response. Business is
+/// `open` until its close date has passed. Items without an id or title are skipped.
+pub fn parse_inquiries(payload: &Value, today: NaiveDate) -> Vec<CommitteeInquiry> {
+    json_items(payload, "items")
+        .filter_map(|item| {
+            let id = item.get("id")?.as_u64()?;
+            let close_date = json_day(item, "closeDate");
+            let closed = close_date
+                .as_deref()
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                .is_some_and(|date| date < today);
+            Some(CommitteeInquiry {
+                id,
+                title: json_text(item, "title")?,
+                business_type: item.get("type").and_then(|kind| json_text(kind, "name")),
+                status: if closed { "closed" } else { "open" }.to_string(),
+                open_date: json_day(item, "openDate"),
+                close_date,
+                url: format!("https://committees.parliament.uk/work/{id}/"),
+            })
+        })
+        .collect()
+}
+
+/// `totalResults` of a list response.
+pub fn total_results(payload: &Value) -> Option<u64> {
+    payload.get("totalResults").and_then(Value::as_u64)
+}
+
+/// The name of a member's role that has not ended, e.g. `Chair`.
+fn current_role(item: &Value) -> Option<String> {
+    json_items(item, "roles")
+        .filter(|role| role.get("endDate").is_none_or(Value::is_null))
+        .find_map(|role| role.get("role").and_then(|role| json_text(role, "name")))
+}
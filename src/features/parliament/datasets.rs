@@ -0,0 +1,272 @@
+use std::future::Future;
+
+use chrono::NaiveDate;
+use reqwest::Url;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::core::error::AppError;
+use crate::features::parliament::client::{CORE_DATASET_BASE, MEMBERS_API_BASE};
+
+/// Where a dataset is served from; the two APIs page and shape their results differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetSource {
+    /// Members API search, paged with `skip`/`take` and optionally restricted to a house.
+    Members { house: Option<&'static str> },
+    /// Legacy Linked Data API, paged with `_page`/`_pageSize`.
+    LinkedData,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DatasetDescriptor {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub source: DatasetSource,
+    /// Dotted path to the field used by `since` filtering, when the dataset has one.
+    pub date_field: Option<&'static str>,
+    pub page_size: u32,
+}
+
+pub const DATASETS: &[DatasetDescriptor] = &[
+    DatasetDescriptor {
+        name: "members",
+        description: "Current and former members of both Houses.",
+        source: DatasetSource::Members { house: None },
+        date_field: Some("latestHouseMembership.membershipStartDate"),
+        page_size: 20,
+    },
+    DatasetDescriptor {
+        name: "commonsmembers",
+        description: "Members of the House of Commons.",
+        source: DatasetSource::Members {
+            house: Some("Commons"),
+        },
+        date_field: Some("latestHouseMembership.membershipStartDate"),
+        page_size: 20,
+    },
+    DatasetDescriptor {
+        name: "lordsmembers",
+        description: "Members of the House of Lords.",
+        source: DatasetSource::Members {
+            house: Some("Lords"),
+        },
+        date_field: Some("latestHouseMembership.membershipStartDate"),
+        page_size: 20,
+    },
+    DatasetDescriptor {
+        name: "commonsdivisions",
+        description: "House of Commons divisions.",
+        source: DatasetSource::LinkedData,
+        date_field: Some("date"),
+        page_size: 100,
+    },
+    DatasetDescriptor {
+        name: "lordsdivisions",
+        description: "House of Lords divisions.",
+        source: DatasetSource::LinkedData,
+        date_field: Some("date"),
+        page_size: 100,
+    },
+    DatasetDescriptor {
+        name: "commonsdebates",
+        description: "House of Commons debate records.",
+        source: DatasetSource::LinkedData,
+        date_field: Some("date"),
+        page_size: 100,
+    },
+    DatasetDescriptor {
+        name: "commonswrittenquestions",
+        description: "Written questions tabled in the House of Commons.",
+        source: DatasetSource::LinkedData,
+        date_field: Some("date"),
+        page_size: 100,
+    },
+    DatasetDescriptor {
+        name: "lordswrittenquestions",
+        description: "Written questions tabled in the House of Lords.",
+        source: DatasetSource::LinkedData,
+        date_field: Some("date"),
+        page_size: 100,
+    },
+    DatasetDescriptor {
+        name: "edms",
+        description: "Early day motions.",
+        source: DatasetSource::LinkedData,
+        date_field: Some("dateTabled"),
+        page_size: 100,
+    },
+];
+
+pub fn find_dataset(name: &str) -> Option<&'static DatasetDescriptor> {
+    let name = name.trim();
+    DATASETS
+        .iter()
+        .find(|descriptor| descriptor.name.eq_ignore_ascii_case(name))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub query: Option<String>,
+    pub since: Option<NaiveDate>,
+    pub max_rows: usize,
+    /// Overrides the descriptor's page size (mainly useful in tests).
+    pub page_size: Option<u32>,
+}
+
+impl DatasetDescriptor {
+    pub fn supports_since(&self) -> bool {
+        self.date_field.is_some()
+    }
+
+    pub fn page_url(
+        &self,
+        page: u32,
+        page_size: u32,
+        query: Option<&str>,
+    ) -> Result<Url, AppError> {
+        match self.source {
+            DatasetSource::Members { house } => {
+                let mut url = Url::parse(MEMBERS_API_BASE)
+                    .map_err(|err| AppError::internal(format!("invalid members api url: {err}")))?;
+                {
+                    let mut pairs = url.query_pairs_mut();
+                    if let Some(term) = query {
+                        pairs.append_pair("name", term);
+                    }
+                    if let Some(house) = house {
+                        pairs.append_pair("house", house);
+                    }
+                    pairs.append_pair("take", &page_size.to_string());
+                    pairs.append_pair("skip", &page.saturating_mul(page_size).to_string());
+                }
+                Ok(url)
+            }
+            DatasetSource::LinkedData => {
+                let mut url = Url::parse(CORE_DATASET_BASE)
+                    .map_err(|err| AppError::internal(format!("invalid base url: {err}")))?;
+                url.set_path(&format!("/{}.json", self.name));
+                {
+                    let mut pairs = url.query_pairs_mut();
+                    if let Some(term) = query {
+                        pairs.append_pair("_search", term);
+                    }
+                    pairs.append_pair("_page", &page.to_string());
+                    pairs.append_pair("_pageSize", &page_size.to_string());
+                }
+                Ok(url)
+            }
+        }
+    }
+
+    /// Pull the individual records out of one upstream page, unwrapping the Members API
+    /// `{ value, links }` envelope so every dataset exports plain objects.
+    pub fn extract_items(&self, payload: Value) -> Vec<Value> {
+        let items = match self.source {
+            DatasetSource::Members { .. } => payload.get("items").cloned(),
+            DatasetSource::LinkedData => payload
+                .get("result")
+                .and_then(|result| result.get("items"))
+                .cloned(),
+        };
+
+        let Some(Value::Array(items)) = items else {
+            return Vec::new();
+        };
+
+        match self.source {
+            DatasetSource::Members { .. } => items
+                .into_iter()
+                .map(|mut item| match item.get_mut("value") {
+                    Some(value) => value.take(),
+                    None => item,
+                })
+                .collect(),
+            DatasetSource::LinkedData => items,
+        }
+    }
+
+    pub fn item_date(&self, item: &Value) -> Option<NaiveDate> {
+        let mut current = item;
+        for segment in self.date_field?.split('.') {
+            current = current.get(segment)?;
+        }
+
+        let text = match current {
+            Value::String(text) => text.as_str(),
+            Value::Object(map) => map.get("_value")?.as_str()?,
+            _ => return None,
+        };
+
+        NaiveDate::parse_from_str(text.get(..10)?, "%Y-%m-%d").ok()
+    }
+
+    fn matches_since(&self, item: &Value, since: Option<NaiveDate>) -> bool {
+        match since {
+            Some(since) => self.item_date(item).is_some_and(|date| date >= since),
+            None => true,
+        }
+    }
+}
+
+/// Stream every record of `descriptor` through a bounded channel. Pages are requested
+/// with `fetch_page` only once the consumer has drained the previous one, so at most
+/// about one page is held in memory regardless of dataset size. Upstream errors are
+/// forwarded as the final item.
+pub fn stream_dataset<F, Fut>(
+    descriptor: &'static DatasetDescriptor,
+    options: ExportOptions,
+    fetch_page: F,
+) -> mpsc::Receiver<Result<Value, AppError>>
+where
+    F: Fn(Url) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Value, AppError>> + Send,
+{
+    let page_size = options.page_size.unwrap_or(descriptor.page_size).max(1);
+    let (sender, receiver) = mpsc::channel(page_size as usize);
+
+    tokio::spawn(async move {
+        let mut emitted = 0usize;
+        let mut page = 0u32;
+
+        while emitted < options.max_rows {
+            let url = match descriptor.page_url(page, page_size, options.query.as_deref()) {
+                Ok(url) => url,
+                Err(error) => {
+                    let _ = sender.send(Err(error)).await;
+                    return;
+                }
+            };
+
+            let payload = match fetch_page(url).await {
+                Ok(payload) => payload,
+                Err(error) => {
+                    let _ = sender.send(Err(error)).await;
+                    return;
+                }
+            };
+
+            let items = descriptor.extract_items(payload);
+            let last_page = items.len() < page_size as usize;
+
+            for item in items {
+                if !descriptor.matches_since(&item, options.since) {
+                    continue;
+                }
+                if sender.send(Ok(item)).await.is_err() {
+                    return;
+                }
+                emitted += 1;
+                if emitted >= options.max_rows {
+                    return;
+                }
+            }
+
+            if last_page {
+                return;
+            }
+            page += 1;
+        }
+    });
+
+    receiver
+}
@@ -0,0 +1,589 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::features::parliament::dto::{
+    BillDivision, BillStageDivisions, DivisionDetail, DivisionMember, DivisionOutcome,
+    MemberDivisionVote, MpVoteRecord,
+};
+
+/// Divisions scoring below this are treated as belonging to another bill.
+pub const MIN_MATCH_SIMILARITY: f64 = 0.6;
+/// Divisions scoring below this (but above [`MIN_MATCH_SIMILARITY`]) are flagged
+/// `lowConfidence`; in practice only an exact normalised title match clears it.
+pub const HIGH_CONFIDENCE_SIMILARITY: f64 = 0.999;
+
+/// Share of the governing party's votes that must fall in one lobby before that lobby is
+/// taken as its whipped position.
+pub const WHIPPED_SHARE: f64 = 0.9;
+/// Fewest governing-party votes an outcome is judged from; smaller turnouts say little
+/// about the whip.
+pub const MIN_GOVERNING_VOTES: u64 = 10;
+
+/// Tokens that say nothing about which bill a title refers to.
+const IGNORED_TOKENS: &[&str] = &["bill", "hl"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DivisionHouse {
+    Commons,
+    Lords,
+}
+
+impl DivisionHouse {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Commons => "Commons",
+            Self::Lords => "Lords",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "commons" | "house of commons" => Some(Self::Commons),
+            "lords" | "house of lords" => Some(Self::Lords),
+            _ => None,
+        }
+    }
+
+    /// The names of the house's two lobbies: for, then against.
+    pub fn lobbies(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Commons => ("Aye", "No"),
+            Self::Lords => ("Content", "Not Content"),
+        }
+    }
+
+    pub fn division_link(self, division_id: u64) -> String {
+        let house = match self {
+            Self::Commons => "commons",
+            Self::Lords => "lords",
+        };
+        format!("https://votes.parliament.uk/votes/{house}/division/{division_id}")
+    }
+}
+
+/// One sitting of a bill stage, taken from the Bills API stages list.
+#[derive(Debug, Clone)]
+pub struct StageSitting {
+    pub stage: String,
+    pub house: DivisionHouse,
+    pub date: NaiveDate,
+    /// Position of the stage in the bill's stage list, used to order the output.
+    pub order: usize,
+}
+
+/// A division as returned by either Votes API, before matching.
+#[derive(Debug, Clone)]
+pub struct RawDivision {
+    pub house: DivisionHouse,
+    pub division_id: u64,
+    pub number: Option<u64>,
+    pub title: String,
+    pub date: NaiveDate,
+    pub ayes: u64,
+    pub noes: u64,
+    /// Per-party lobby counts, when the payload lists the members who voted.
+    pub parties: Vec<PartyVotes>,
+}
+
+/// How one party's members voted in a division.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartyVotes {
+    pub party: String,
+    pub ayes: u64,
+    pub noes: u64,
+}
+
+/// Parse the Bills API `/Bills/{id}/Stages` payload into one entry per sitting. Stages
+/// outside either House (e.g. Royal Assent) are skipped.
+pub fn parse_stage_sittings(payload: &Value) -> Vec<StageSitting> {
+    let Some(items) = payload.get("items").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    let mut sittings = Vec::new();
+    for (order, item) in items.iter().enumerate() {
+        let Some(house) = item
+            .get("house")
+            .and_then(Value::as_str)
+            .and_then(DivisionHouse::parse)
+        else {
+            continue;
+        };
+        let stage = item
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown stage")
+            .to_string();
+
+        for sitting in item
+            .get("stageSittings")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(date) = sitting
+                .get("date")
+                .and_then(Value::as_str)
+                .and_then(parse_date_prefix)
+            {
+                sittings.push(StageSitting {
+                    stage: stage.clone(),
+                    house,
+                    date,
+                    order,
+                });
+            }
+        }
+    }
+
+    sittings
+}
+
+/// Parse a Commons (`PascalCase`) or Lords (`camelCase`) Votes API search result.
+pub fn parse_divisions(house: DivisionHouse, payload: &Value) -> Vec<RawDivision> {
+    let Some(items) = payload.as_array() else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|item| parse_division(house, item))
+        .collect()
+}
+
+/// Parse one division from either Votes API, as listed by a search or fetched by id.
+pub fn parse_division(house: DivisionHouse, item: &Value) -> Option<RawDivision> {
+    let division_id = field(item, &["DivisionId", "divisionId"])?.as_u64()?;
+    let title = field(item, &["Title", "title"])?.as_str()?.to_string();
+    let date = field(item, &["Date", "date"])?
+        .as_str()
+        .and_then(parse_date_prefix)?;
+    let count = |names: &[&str]| {
+        field(item, names)
+            .and_then(Value::as_u64)
+            .unwrap_or_default()
+    };
+    let (ayes, noes, parties) = match house {
+        DivisionHouse::Commons => (
+            count(&["AyeCount"]),
+            count(&["NoCount"]),
+            party_votes(item, &["Ayes"], &["Noes"]),
+        ),
+        DivisionHouse::Lords => (
+            count(&["authoritativeContentCount", "contentCount"]),
+            count(&["authoritativeNotContentCount", "notContentCount"]),
+            party_votes(item, &["contents"], &["notContents"]),
+        ),
+    };
+
+    Some(RawDivision {
+        house,
+        division_id,
+        number: field(item, &["Number", "number"]).and_then(Value::as_u64),
+        title,
+        date,
+        ayes,
+        noes,
+        parties,
+    })
+}
+
+/// Parse a Votes API division fetched by id, with the members in each lobby and the
+/// tellers. Tellers are not counted among a lobby's members.
+pub fn parse_division_detail(house: DivisionHouse, payload: &Value) -> Option<DivisionDetail> {
+    let division = parse_division(house, payload)?;
+    let (ayes, noes, aye_tellers, no_tellers) = match house {
+        DivisionHouse::Commons => ("Ayes", "Noes", "AyeTellers", "NoTellers"),
+        DivisionHouse::Lords => (
+            "contents",
+            "notContents",
+            "contentTellers",
+            "notContentTellers",
+        ),
+    };
+    let members = |name: &str| -> Vec<DivisionMember> {
+        field(payload, &[name])
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|member| {
+                let text = |names: &[&str]| {
+                    field(member, names)
+                        .and_then(Value::as_str)
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(str::to_string)
+                };
+                Some(DivisionMember {
+                    member_id: field(member, &["MemberId", "memberId"])?
+                        .as_u64()
+                        .and_then(|id| u32::try_from(id).ok())?,
+                    name: text(&["Name", "name"])?,
+                    party: text(&["Party", "party"]),
+                })
+            })
+            .collect()
+    };
+
+    Some(DivisionDetail {
+        division_id: division.division_id,
+        house: house.as_str().to_string(),
+        number: division.number,
+        title: division.title,
+        date: division.date.to_string(),
+        ayes: division.ayes,
+        noes: division.noes,
+        result: outcome(division.ayes, division.noes).to_string(),
+        aye_members: members(ayes),
+        no_members: members(noes),
+        aye_tellers: members(aye_tellers),
+        no_tellers: members(no_tellers),
+        link: house.division_link(division.division_id),
+    })
+}
+
+/// Tally the member lists of a Votes API division (`Ayes`/`Noes` in the Commons,
+/// `contents`/`notContents` in the Lords) by party. Members without a party are skipped.
+pub fn party_votes(item: &Value, aye_names: &[&str], no_names: &[&str]) -> Vec<PartyVotes> {
+    let mut tallies: Vec<PartyVotes> = Vec::new();
+    for (names, aye) in [(aye_names, true), (no_names, false)] {
+        let members = field(item, names)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten();
+        for member in members {
+            let Some(party) = field(member, &["Party", "party"])
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|party| !party.is_empty())
+            else {
+                continue;
+            };
+            let position = match tallies.iter().position(|tally| tally.party == party) {
+                Some(position) => position,
+                None => {
+                    tallies.push(PartyVotes {
+                        party: party.to_string(),
+                        ayes: 0,
+                        noes: 0,
+                    });
+                    tallies.len() - 1
+                }
+            };
+            if aye {
+                tallies[position].ayes += 1;
+            } else {
+                tallies[position].noes += 1;
+            }
+        }
+    }
+    tallies
+}
+
+/// Judge a division against the governing party's whip, inferred from the lobby most of
+/// its members voted in. Ties, small turnouts and a missing party or breakdown are
+/// `Unknown`; a governing party with no lobby reaching [`WHIPPED_SHARE`] is `FreeVote`.
+pub fn classify_outcome(
+    ayes: u64,
+    noes: u64,
+    governing_party: Option<&str>,
+    parties: &[PartyVotes],
+) -> DivisionOutcome {
+    let Some(governing) = governing_party.and_then(|name| {
+        parties
+            .iter()
+            .find(|tally| tally.party.eq_ignore_ascii_case(name.trim()))
+    }) else {
+        return DivisionOutcome::Unknown;
+    };
+    let voted = governing.ayes + governing.noes;
+    if ayes == noes || voted < MIN_GOVERNING_VOTES {
+        return DivisionOutcome::Unknown;
+    }
+
+    let whipped_aye = governing.ayes as f64 >= voted as f64 * WHIPPED_SHARE;
+    let whipped_no = governing.noes as f64 >= voted as f64 * WHIPPED_SHARE;
+    match (whipped_aye, whipped_no) {
+        (true, _) if ayes > noes => DivisionOutcome::GovernmentWin,
+        (_, true) if noes > ayes => DivisionOutcome::GovernmentWin,
+        (true, _) | (_, true) => DivisionOutcome::GovernmentDefeat,
+        (false, false) => DivisionOutcome::FreeVote,
+    }
+}
+
+/// The party that cast more than half of the votes in most of these Commons divisions,
+/// which for a majority government is the governing party. `None` when no party does,
+/// as in a hung parliament, or when the divisions carry no breakdown.
+pub fn commons_majority_party(divisions: &[RawDivision]) -> Option<String> {
+    let mut wins: HashMap<&str, usize> = HashMap::new();
+    for division in divisions
+        .iter()
+        .filter(|division| division.house == DivisionHouse::Commons)
+    {
+        let cast: u64 = division
+            .parties
+            .iter()
+            .map(|tally| tally.ayes + tally.noes)
+            .sum();
+        if let Some(tally) = division
+            .parties
+            .iter()
+            .find(|tally| (tally.ayes + tally.noes) * 2 > cast)
+        {
+            *wins.entry(tally.party.as_str()).or_default() += 1;
+        }
+    }
+
+    let most = wins.values().copied().max()?;
+    let mut leaders = wins.into_iter().filter(|(_, count)| *count == most);
+    let (party, _) = leaders.next()?;
+    leaders.next().is_none().then(|| party.to_string())
+}
+
+/// Parse a Commons or Lords Votes API `membervoting` page: one entry per division the
+/// member voted in, with the division nested under `PublishedDivision`.
+pub fn parse_member_votes(house: DivisionHouse, payload: &Value) -> Vec<MemberDivisionVote> {
+    let Some(items) = payload.as_array() else {
+        return Vec::new();
+    };
+    let (voted_for_names, for_count, against_count): (&[&str], &[&str], &[&str]) = match house {
+        DivisionHouse::Commons => (
+            &["MemberVotedAye", "memberVotedAye"],
+            &["AyeCount", "ayeCount"],
+            &["NoCount", "noCount"],
+        ),
+        DivisionHouse::Lords => (
+            &["memberWasContent", "MemberWasContent"],
+            &["authoritativeContentCount", "contentCount"],
+            &["authoritativeNotContentCount", "notContentCount"],
+        ),
+    };
+    let (for_lobby, against_lobby) = house.lobbies();
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let division = field(item, &["PublishedDivision", "publishedDivision"])?;
+            let date = field(division, &["Date", "date"])?
+                .as_str()
+                .and_then(parse_date_prefix)?;
+            let voted_for = field(item, voted_for_names)?.as_bool()?;
+            let count = |names: &[&str]| {
+                field(division, names)
+                    .and_then(Value::as_u64)
+                    .unwrap_or_default()
+            };
+
+            Some(MemberDivisionVote {
+                division_id: field(division, &["DivisionId", "divisionId"])?.as_u64()?,
+                number: field(division, &["Number", "number"]).and_then(Value::as_u64),
+                date: date.to_string(),
+                title: field(division, &["Title", "title"])?.as_str()?.to_string(),
+                vote: if voted_for { for_lobby } else { against_lobby }.to_string(),
+                teller: field(item, &["MemberWasTeller", "memberWasTeller"])
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+                ayes: count(for_count),
+                noes: count(against_count),
+            })
+        })
+        .collect()
+}
+
+/// A `membervoting` entry from `house` as a voting-record row; `majority` names the lobby
+/// with more votes, or `Tie`.
+pub fn member_vote_record(house: DivisionHouse, vote: MemberDivisionVote) -> MpVoteRecord {
+    let (for_lobby, against_lobby) = house.lobbies();
+    let majority = match vote.ayes.cmp(&vote.noes) {
+        Ordering::Greater => for_lobby,
+        Ordering::Less => against_lobby,
+        Ordering::Equal => "Tie",
+    };
+    MpVoteRecord {
+        division_id: Some(vote.division_id.to_string()),
+        title: Some(vote.title),
+        date: Some(vote.date),
+        vote: Some(vote.vote),
+        majority: Some(majority.to_string()),
+        teller: vote.teller,
+    }
+}
+
+/// Similarity between a bill title and the bill part of a division title (the text
+/// before the first `:`), as the Jaccard index of their normalised word sets.
+/// Punctuation, case, `Bill` and `[HL]` are ignored; numbers are kept, so `Finance Bill`
+/// and `Finance (No. 2) Bill` stay distinguishable.
+pub fn title_similarity(bill_title: &str, division_title: &str) -> f64 {
+    let subject = division_title
+        .split_once(':')
+        .map_or(division_title, |(subject, _)| subject);
+    let bill = title_tokens(bill_title);
+    let division = title_tokens(subject);
+
+    if bill.is_empty() || division.is_empty() {
+        return 0.0;
+    }
+
+    let shared = bill.intersection(&division).count() as f64;
+    let combined = bill.union(&division).count() as f64;
+    shared / combined
+}
+
+/// Keep divisions that match `bill_title` and fall on one of the bill's sittings in the
+/// same House, then group them by stage. Outcomes are judged against the bill's
+/// [`commons_majority_party`].
+pub fn group_divisions(
+    bill_title: &str,
+    sittings: &[StageSitting],
+    divisions: Vec<RawDivision>,
+) -> Vec<BillStageDivisions> {
+    let mut groups: Vec<(usize, BillStageDivisions)> = Vec::new();
+    let mut seen = HashSet::new();
+    let governing_party = commons_majority_party(&divisions);
+
+    for division in divisions {
+        if !seen.insert((division.house.as_str(), division.division_id)) {
+            continue;
+        }
+        let similarity = title_similarity(bill_title, &division.title);
+        if similarity < MIN_MATCH_SIMILARITY {
+            continue;
+        }
+        let Some(sitting) = stage_for(sittings, &division) else {
+            continue;
+        };
+
+        let position = match groups.iter().position(|(order, group)| {
+            *order == sitting.order && group.house == sitting.house.as_str()
+        }) {
+            Some(position) => position,
+            None => {
+                let mut dates: Vec<NaiveDate> = sittings
+                    .iter()
+                    .filter(|other| other.order == sitting.order)
+                    .map(|other| other.date)
+                    .collect();
+                dates.sort_unstable();
+                dates.dedup();
+                groups.push((
+                    sitting.order,
+                    BillStageDivisions {
+                        stage: sitting.stage.clone(),
+                        house: sitting.house.as_str().to_string(),
+                        dates: dates.iter().map(NaiveDate::to_string).collect(),
+                        divisions: Vec::new(),
+                    },
+                ));
+                groups.len() - 1
+            }
+        };
+
+        groups[position].1.divisions.push(BillDivision {
+            house: division.house.as_str().to_string(),
+            division_id: division.division_id,
+            number: division.number,
+            title: division.title,
+            date: division.date.to_string(),
+            ayes: division.ayes,
+            noes: division.noes,
+            result: outcome(division.ayes, division.noes).to_string(),
+            margin: division.ayes.abs_diff(division.noes),
+            outcome: classify_outcome(
+                division.ayes,
+                division.noes,
+                governing_party.as_deref(),
+                &division.parties,
+            ),
+            link: division.house.division_link(division.division_id),
+            similarity: (similarity * 100.0).round() / 100.0,
+            low_confidence: similarity < HIGH_CONFIDENCE_SIMILARITY,
+        });
+    }
+
+    groups.sort_by_key(|(order, _)| *order);
+    groups
+        .into_iter()
+        .map(|(_, mut group)| {
+            group
+                .divisions
+                .sort_by(|left, right| (&left.date, left.number).cmp(&(&right.date, right.number)));
+            group
+        })
+        .collect()
+}
+
+/// The sitting a division belongs to: same House and date, preferring the stage whose
+/// name appears in the division title (e.g. `...: Third Reading`).
+fn stage_for<'a>(sittings: &'a [StageSitting], division: &RawDivision) -> Option<&'a StageSitting> {
+    let candidates: Vec<&StageSitting> = sittings
+        .iter()
+        .filter(|sitting| sitting.house == division.house && sitting.date == division.date)
+        .collect();
+    let detail = division
+        .title
+        .split_once(':')
+        .map(|(_, detail)| normalise_words(detail))
+        .unwrap_or_default();
+
+    candidates
+        .iter()
+        .find(|sitting| {
+            let stage = normalise_words(&sitting.stage);
+            !stage.is_empty() && detail.contains(&stage)
+        })
+        .or_else(|| candidates.first())
+        .copied()
+}
+
+fn outcome(ayes: u64, noes: u64) -> &'static str {
+    match ayes.cmp(&noes) {
+        std::cmp::Ordering::Greater => "agreed",
+        std::cmp::Ordering::Less => "negatived",
+        std::cmp::Ordering::Equal => "tied",
+    }
+}
+
+fn title_tokens(title: &str) -> HashSet<String> {
+    normalise_words(title)
+        .split(' ')
+        .filter(|token| !token.is_empty() && !IGNORED_TOKENS.contains(token))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Lowercase, drop apostrophes, turn other punctuation into spaces and spell out stage
+/// ordinals (`2nd` -> `second`) so Bills API and Votes API wording line up.
+fn normalise_words(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .chars()
+        .filter(|ch| !matches!(ch, '\'' | '’'))
+        .map(|ch| if ch.is_alphanumeric() { ch } else { ' ' })
+        .collect();
+
+    cleaned
+        .split_whitespace()
+        .map(|word| match word {
+            "1st" => "first",
+            "2nd" => "second",
+            "3rd" => "third",
+            other => other,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn field<'a>(item: &'a Value, names: &[&str]) -> Option<&'a Value> {
+    names
+        .iter()
+        .find_map(|name| item.get(*name).filter(|value| !value.is_null()))
+}
+
+fn parse_date_prefix(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
@@ -1,5 +1,14 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::core::bounds;
+use crate::features::parliament::activity::ActivitySource;
+use crate::features::parliament::client::MAX_VOTING_EXPORT_ROWS;
+use crate::features::parliament::divisions::DivisionHouse;
+use crate::features::parliament::sessions::SessionRef;
+
 #[derive(Debug, Deserialize)]
 pub struct FetchCoreDatasetArgs {
     pub dataset: String,
@@ -7,16 +16,28 @@ pub struct FetchCoreDatasetArgs {
     pub search_term: Option<String>,
     #[serde(rename = "page")]
     pub page: Option<u32>,
-    #[serde(rename = "perPage")]
+    #[serde(
+        default,
+        rename = "perPage",
+        deserialize_with = "bounds::optional::<_, _, 1, 100>"
+    )]
     pub per_page: Option<u32>,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
     #[serde(rename = "fuzzyMatch")]
     pub fuzzy_match: Option<bool>,
     #[serde(rename = "applyRelevance")]
     pub apply_relevance: Option<bool>,
-    #[serde(rename = "relevanceThreshold")]
+    #[serde(
+        default,
+        rename = "relevanceThreshold",
+        deserialize_with = "bounds::optional_fraction"
+    )]
     pub relevance_threshold: Option<f32>,
+    /// Dot paths each item is projected down to; see [`crate::features::parliament::projection`].
+    pub fields: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,43 +45,119 @@ pub struct FetchBillsArgs {
     #[serde(rename = "searchTerm")]
     pub search_term: Option<String>,
     pub house: Option<String>,
-    pub session: Option<String>,
-    #[serde(rename = "parliamentNumber")]
+    pub session: Option<SessionRef>,
+    #[serde(
+        default,
+        rename = "parliamentNumber",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
     pub parliament_number: Option<u32>,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
     #[serde(rename = "applyRelevance")]
     pub apply_relevance: Option<bool>,
-    #[serde(rename = "relevanceThreshold")]
+    #[serde(
+        default,
+        rename = "relevanceThreshold",
+        deserialize_with = "bounds::optional_fraction"
+    )]
     pub relevance_threshold: Option<f32>,
+    /// Dot paths each item is projected down to; see [`crate::features::parliament::projection`].
+    pub fields: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct FetchLegislationArgs {
     pub title: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1800, { i64::MAX }>"
+    )]
     pub year: Option<u32>,
     #[serde(rename = "type")]
     pub legislation_type: Option<String>,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
     #[serde(rename = "applyRelevance")]
     pub apply_relevance: Option<bool>,
-    #[serde(rename = "relevanceThreshold")]
+    #[serde(
+        default,
+        rename = "relevanceThreshold",
+        deserialize_with = "bounds::optional_fraction"
+    )]
     pub relevance_threshold: Option<f32>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FetchLegislationTextArgs {
+    /// A legislation.gov.uk URI or path, e.g. `https://www.legislation.gov.uk/ukpga/2008/27`
+    /// or `ukpga/2008/27/section/1`; alternative to `type`, `year` and `number`.
+    pub uri: Option<String>,
+    /// e.g. `ukpga` or `uksi`.
+    #[serde(rename = "type")]
+    pub legislation_type: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1800, { i64::MAX }>"
+    )]
+    pub year: Option<u32>,
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub number: Option<u32>,
+    /// e.g. `1` or `3A`; the whole document when absent.
+    pub section: Option<String>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LegislationText {
+    /// The Act or instrument, e.g. `Climate Change Act 2008`.
+    pub title: Option<String>,
+    pub section: Option<String>,
+    pub heading: Option<String>,
+    /// Plain text, one subsection or paragraph per line, amendments in force applied.
+    pub text: String,
+    /// Set when `text` was cut short.
+    pub truncated: bool,
+    /// Where the provision applies, e.g. `E+W+S+N.I.`.
+    pub extent: Option<String>,
+    /// legislation.gov.uk's notes on amendments within `text`, e.g. the Act that
+    /// substituted some words.
+    pub amendments: Vec<String>,
+    /// The provision on legislation.gov.uk.
+    pub url: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct FetchMpActivityArgs {
-    #[serde(rename = "mpId")]
+    #[serde(
+        rename = "mpId",
+        deserialize_with = "bounds::required::<_, _, 1, { i64::MAX }>"
+    )]
     pub mp_id: u32,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 50>")]
     pub limit: Option<u32>,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct FetchMpVotingRecordArgs {
-    #[serde(rename = "mpId")]
+    #[serde(
+        rename = "mpId",
+        deserialize_with = "bounds::required::<_, _, 1, { i64::MAX }>"
+    )]
     pub mp_id: u32,
     #[serde(rename = "fromDate")]
     pub from_date: Option<String>,
@@ -68,9 +165,75 @@ pub struct FetchMpVotingRecordArgs {
     pub to_date: Option<String>,
     #[serde(rename = "billId")]
     pub bill_id: Option<String>,
+    /// Which house's votes to read; Commons when absent.
+    #[serde(default)]
+    pub house: Option<DivisionHouse>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 100>")]
     pub limit: Option<u32>,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+    #[serde(default, rename = "groupBy")]
+    pub group_by: Option<VotingRecordGrouping>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportVotingRecordArgs {
+    #[serde(
+        rename = "mpId",
+        deserialize_with = "bounds::required::<_, _, 1, { i64::MAX }>"
+    )]
+    pub mp_id: u32,
+    #[serde(rename = "fromDate")]
+    pub from_date: Option<String>,
+    #[serde(rename = "toDate")]
+    pub to_date: Option<String>,
+    /// Maximum rows exported; defaults to 1000.
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1, { MAX_VOTING_EXPORT_ROWS as i64 }>"
+    )]
+    pub limit: Option<u32>,
+}
+
+/// One division a member voted in, as listed by a Votes API member voting endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct MemberDivisionVote {
+    #[serde(rename = "divisionId")]
+    pub division_id: u64,
+    pub number: Option<u64>,
+    pub date: String,
+    pub title: String,
+    /// `Aye` or `No`; `Content` or `Not Content` in the Lords.
+    pub vote: String,
+    pub teller: bool,
+    /// Votes in the Aye (Content) lobby.
+    pub ayes: u64,
+    /// Votes in the No (Not Content) lobby.
+    pub noes: u64,
+}
+
+/// `structuredContent` of `parliament.export_voting_record`; the CSV itself is in the content blocks.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct VotingRecordExportResult {
+    #[serde(rename = "mpId")]
+    pub mp_id: u32,
+    pub columns: Vec<String>,
+    pub rows: usize,
+    /// CSV content blocks; the first starts with the header row.
+    pub chunks: usize,
+    /// Row cap in force; `rows == limit` means older votes may have been left out.
+    pub limit: usize,
+    /// Later pages were not requested because the call reached its byte budget.
+    #[serde(rename = "truncatedByBudget", default)]
+    pub truncated_by_budget: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum VotingRecordGrouping {
+    #[serde(rename = "policyArea")]
+    PolicyArea,
 }
 
 #[derive(Debug, Deserialize)]
@@ -78,16 +241,135 @@ pub struct LookupConstituencyArgs {
     pub postcode: String,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct MemberInfo {
-    pub name_display_as: String,
-    pub membership_start_date: Option<String>,
+#[derive(Debug, Deserialize)]
+pub struct FetchMembersBulkArgs {
+    #[serde(rename = "mpIds")]
+    pub mp_ids: Vec<u32>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct MemberSummary {
+    pub id: u32,
+    pub name: String,
+    pub party: Option<String>,
     pub constituency: Option<String>,
+    pub house: Option<String>,
+    #[serde(rename = "isCurrent")]
+    pub is_current: bool,
+    pub status: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// One entry of a bulk lookup: either the member or why they could not be resolved.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum MemberLookupEntry {
+    Found(MemberSummary),
+    Failed { error: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchMembersBulkResult {
+    pub members: BTreeMap<u32, MemberLookupEntry>,
+    pub resolved: usize,
+    pub failed: usize,
+    /// Some ids were not looked up because the call reached its byte budget.
+    #[serde(rename = "truncatedByBudget", default)]
+    pub truncated_by_budget: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchBillDivisionsArgs {
+    #[serde(
+        default,
+        rename = "billId",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub bill_id: Option<u32>,
+    /// Exact short title, used when `billId` is not known.
+    #[serde(default, rename = "billTitle")]
+    pub bill_title: Option<String>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+/// Whether a division went the government's way, judged from how the governing party's
+/// members voted. Anything the vote itself cannot settle is `unknown`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DivisionOutcome {
+    /// The governing party voted as a bloc and its lobby won.
+    GovernmentWin,
+    /// The governing party voted as a bloc and its lobby lost.
+    GovernmentDefeat,
+    /// The governing party's members split between the lobbies.
+    FreeVote,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct BillDivision {
+    /// `Commons` or `Lords`.
+    pub house: String,
+    #[serde(rename = "divisionId")]
+    pub division_id: u64,
+    pub number: Option<u64>,
+    pub title: String,
+    pub date: String,
+    pub ayes: u64,
+    pub noes: u64,
+    /// `agreed`, `negatived` or `tied`.
+    pub result: String,
+    /// Difference between the ayes and the noes.
+    #[serde(default)]
+    pub margin: u64,
+    #[serde(default)]
+    pub outcome: DivisionOutcome,
+    pub link: String,
+    /// Normalised title similarity between the bill and the division, 0–1.
+    pub similarity: f64,
+    /// The division title only partly matches the bill title (e.g. a bill with a
+    /// near-identical name); check before relying on it.
+    #[serde(rename = "lowConfidence")]
+    pub low_confidence: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct BillStageDivisions {
+    pub stage: String,
+    pub house: String,
+    pub dates: Vec<String>,
+    pub divisions: Vec<BillDivision>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchBillDivisionsResult {
+    #[serde(rename = "billId")]
+    pub bill_id: u32,
+    pub title: String,
+    pub link: String,
+    /// Only stages with at least one matched division, in the bill's stage order.
+    pub stages: Vec<BillStageDivisions>,
+    #[serde(rename = "totalDivisions")]
+    pub total_divisions: usize,
+    #[serde(rename = "lowConfidenceCount")]
+    pub low_confidence_count: usize,
+    /// Some sitting days were not searched because the call reached its byte budget.
+    #[serde(rename = "truncatedByBudget", default)]
+    pub truncated_by_budget: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct MpActivityEntry {
     pub id: String,
     pub date: String,
@@ -98,17 +380,40 @@ pub struct MpActivityEntry {
     pub url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct MpActivityResult {
+    /// Newest first, across every source that could be read.
+    pub activities: Vec<MpActivityEntry>,
+    /// Sources that failed, so `activities` may be missing entries of their kinds.
+    #[serde(
+        rename = "unavailableSources",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub unavailable_sources: Vec<ActivitySource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// One division a member voted in, from the Commons or Lords Votes API `membervoting`
+/// endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct MpVoteRecord {
     #[serde(rename = "divisionId")]
     pub division_id: Option<String>,
     pub title: Option<String>,
     pub date: Option<String>,
+    /// `Aye` or `No` (`Content` or `Not Content` in the Lords); for a teller, the lobby
+    /// they told for.
     pub vote: Option<String>,
+    /// The lobby with more votes, named as in `vote`, or `Tie`.
     pub majority: Option<String>,
+    /// The MP was a teller, counting the votes in their lobby.
+    #[serde(default)]
+    pub teller: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ConstituencyLookupResult {
     #[serde(rename = "constituencyCode")]
     pub constituency_code: Option<String>,
@@ -120,17 +425,753 @@ pub struct ConstituencyLookupResult {
     pub mp_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ListConstituenciesArgs {
+    /// England, Scotland, Wales or Northern Ireland.
+    pub nation: Option<String>,
+    pub region: Option<String>,
+    /// Case-insensitive constituency name prefix.
+    pub search: Option<String>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+/// A constituency as enumerated from the Members API location endpoints.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ConstituencyListing {
+    pub id: u32,
+    pub name: String,
+    /// ONS (GSS) code, e.g. `E14001063`.
+    #[serde(rename = "onsCode")]
+    pub ons_code: Option<String>,
+    /// Derived from the ONS code's country prefix.
+    pub nation: Option<String>,
+    /// Only set when the upstream list carries one.
+    pub region: Option<String>,
+    #[serde(rename = "mpId")]
+    pub mp_id: Option<u32>,
+    #[serde(rename = "mpName")]
+    pub mp_name: Option<String>,
+    #[serde(rename = "mpParty")]
+    pub mp_party: Option<String>,
+}
+
+/// The sitting MP for one constituency, cached apart from the list on a shorter TTL.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurrentMp {
+    pub id: u32,
+    pub name: String,
+    pub party: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ListConstituenciesResult {
+    /// Constituencies in the full list, before filtering.
+    pub total: usize,
+    pub matched: usize,
+    pub constituencies: Vec<ConstituencyListing>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SearchUkLawArgs {
     pub query: String,
     #[serde(rename = "legislationType")]
     pub legislation_type: Option<String>, // "primary", "secondary", "all"
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 50>")]
     pub limit: Option<u32>,
     #[serde(rename = "enableCache")]
     pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Deserialize)]
+pub struct SearchHansardArgs {
+    pub query: String,
+    pub house: Option<DivisionHouse>,
+    #[serde(rename = "fromDate")]
+    pub from_date: Option<String>,
+    #[serde(rename = "toDate")]
+    pub to_date: Option<String>,
+    #[serde(
+        default,
+        rename = "memberId",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub member_id: Option<u32>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 100>")]
+    pub limit: Option<u32>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct SearchHansardResult {
+    /// Newest sitting first.
+    pub results: Vec<HansardContribution>,
+    /// Matches Hansard holds in all, beyond the `limit` returned.
+    #[serde(rename = "totalResults")]
+    pub total_results: Option<u64>,
+}
+
+/// One spoken contribution matching a Hansard search.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct HansardContribution {
+    #[serde(rename = "contributionId")]
+    pub id: String,
+    /// The debate the contribution was made in.
+    pub title: String,
+    #[serde(rename = "sittingDate")]
+    pub sitting_date: String,
+    pub house: String,
+    /// Where in the record the debate sits, e.g. `Commons Chamber` or `Westminster Hall`.
+    pub section: Option<String>,
+    #[serde(rename = "memberName")]
+    pub member_name: Option<String>,
+    pub snippet: String,
+    /// The contribution in its debate on hansard.parliament.uk.
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchDivisionDetailArgs {
+    #[serde(
+        rename = "divisionId",
+        deserialize_with = "bounds::required::<_, _, 1, { i64::MAX }>"
+    )]
+    pub division_id: u64,
+    /// Which house's Votes API to ask; Commons when absent.
+    #[serde(default)]
+    pub house: Option<DivisionHouse>,
+    /// Leave out each member's party.
+    #[serde(rename = "namesOnly")]
+    pub names_only: Option<bool>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+/// One division with every member's vote. In the Lords the ayes are the Contents and the
+/// noes the Not Contents.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct DivisionDetail {
+    #[serde(rename = "divisionId")]
+    pub division_id: u64,
+    /// `Commons` or `Lords`.
+    pub house: String,
+    pub number: Option<u64>,
+    pub title: String,
+    pub date: String,
+    pub ayes: u64,
+    pub noes: u64,
+    /// `agreed`, `negatived` or `tied`.
+    pub result: String,
+    #[serde(rename = "ayeMembers")]
+    pub aye_members: Vec<DivisionMember>,
+    #[serde(rename = "noMembers")]
+    pub no_members: Vec<DivisionMember>,
+    #[serde(rename = "ayeTellers")]
+    pub aye_tellers: Vec<DivisionMember>,
+    #[serde(rename = "noTellers")]
+    pub no_tellers: Vec<DivisionMember>,
+    pub link: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct DivisionMember {
+    #[serde(rename = "memberId")]
+    pub member_id: u32,
+    pub name: String,
+    /// Left out under `namesOnly`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub party: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchBillDetailArgs {
+    #[serde(
+        rename = "billId",
+        deserialize_with = "bounds::required::<_, _, 1, { i64::MAX }>"
+    )]
+    pub bill_id: u32,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct BillDetail {
+    #[serde(rename = "billId")]
+    pub bill_id: u32,
+    #[serde(rename = "shortTitle")]
+    pub short_title: String,
+    #[serde(rename = "longTitle")]
+    pub long_title: Option<String>,
+    #[serde(rename = "currentHouse")]
+    pub current_house: Option<String>,
+    #[serde(rename = "currentStage")]
+    pub current_stage: Option<BillProgressStage>,
+    /// Set once the bill has received Royal Assent.
+    #[serde(rename = "isAct")]
+    pub is_act: bool,
+    pub withdrawn: bool,
+    pub defeated: bool,
+    pub sponsors: Vec<BillSponsor>,
+    /// Every stage in the order the bill passes through them.
+    pub stages: Vec<BillProgressStage>,
+    /// Oldest first.
+    pub publications: Vec<BillPublication>,
+    /// The bill on bills.parliament.uk.
+    pub link: String,
+    /// Set when the publications could not be fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct BillProgressStage {
+    /// The Bills API's name for the stage, e.g. `Committee stage`.
+    pub stage: String,
+    /// e.g. `CS`.
+    pub abbreviation: Option<String>,
+    pub house: Option<String>,
+    /// The first sitting; absent for stages not yet reached.
+    pub date: Option<String>,
+    /// Days the stage sat.
+    pub sittings: usize,
+    /// What happens at this stage, for stages in the dictionary.
+    pub explanation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct BillSponsor {
+    #[serde(rename = "memberId")]
+    pub member_id: Option<u32>,
+    pub name: Option<String>,
+    pub party: Option<String>,
+    /// The sponsoring department, for government bills.
+    pub organisation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct BillPublication {
+    pub title: String,
+    /// e.g. `Bill` or `Explanatory Notes`.
+    #[serde(rename = "type")]
+    pub publication_type: Option<String>,
+    pub house: Option<String>,
+    pub date: Option<String>,
+    pub links: Vec<PublicationLink>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct PublicationLink {
+    pub title: Option<String>,
+    pub url: String,
+    #[serde(rename = "contentType")]
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchMemberRolesArgs {
+    #[serde(
+        rename = "mpId",
+        deserialize_with = "bounds::required::<_, _, 1, { i64::MAX }>"
+    )]
+    pub mp_id: u32,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct MemberRolesResult {
+    #[serde(rename = "mpId")]
+    pub mp_id: u32,
+    /// Current roles first, then the most recently started.
+    pub roles: Vec<MemberRole>,
+    /// Set when one of the two sources could not be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct MemberRole {
+    pub kind: MemberRoleKind,
+    /// e.g. `Chair` or `Minister of State`.
+    pub name: String,
+    /// The committee, or the department a post belongs to.
+    pub organisation: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+    /// Absent while the role is held.
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberRoleKind {
+    /// A select or joint committee seat.
+    Committee,
+    /// A ministerial or other government post.
+    Government,
+    /// A frontbench opposition post.
+    Opposition,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchCommitteesArgs {
+    /// One committee, with its current members; cannot be combined with `searchTerm` or
+    /// `house`.
+    #[serde(
+        default,
+        rename = "committeeId",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub committee_id: Option<u32>,
+    #[serde(rename = "searchTerm")]
+    pub search_term: Option<String>,
+    pub house: Option<CommitteeHouse>,
+    #[serde(rename = "includeInquiries")]
+    pub include_inquiries: Option<bool>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 50>")]
+    pub limit: Option<u32>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitteeHouse {
+    Commons,
+    Lords,
+    Joint,
+}
+
+impl CommitteeHouse {
+    /// The Committees API's spelling.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Commons => "Commons",
+            Self::Lords => "Lords",
+            Self::Joint => "Joint",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchCommitteesResult {
+    pub committees: Vec<Committee>,
+    /// Matches the API holds in all, beyond the `limit` returned.
+    #[serde(rename = "totalResults")]
+    pub total_results: Option<u64>,
+    /// Set when inquiries were asked for but not all could be listed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Committee {
+    pub id: u32,
+    pub name: String,
+    /// `Commons`, `Lords` or `Joint`.
+    pub house: Option<String>,
+    /// e.g. `Select` or `Joint Committee`.
+    pub category: Option<String>,
+    /// The committee on committees.parliament.uk.
+    pub url: String,
+    /// Current members; only when looked up by `committeeId`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<CommitteeMember>>,
+    /// Open inquiries; only with `includeInquiries`, and only for the first few committees.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inquiries: Option<Vec<CommitteeInquiry>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct CommitteeMember {
+    /// Absent for lay members.
+    #[serde(rename = "memberId")]
+    pub member_id: Option<u32>,
+    pub name: String,
+    pub party: Option<String>,
+    /// e.g. `Chair`.
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct CommitteeInquiry {
+    pub id: u64,
+    pub title: String,
+    /// e.g. `Inquiry` or `Non-inquiry activity`.
+    #[serde(rename = "type")]
+    pub business_type: Option<String>,
+    /// `open`, or `closed` once the close date has passed.
+    pub status: String,
+    #[serde(rename = "openDate")]
+    pub open_date: Option<String>,
+    #[serde(rename = "closeDate")]
+    pub close_date: Option<String>,
+    /// The inquiry on committees.parliament.uk.
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchStatutoryInstrumentsArgs {
+    /// Words in the instrument's name.
+    #[serde(rename = "searchTerm")]
+    pub search_term: Option<String>,
+    /// Part of the laying department's name, e.g. `Education`.
+    pub department: Option<String>,
+    pub procedure: Option<SiProcedure>,
+    #[serde(rename = "laidFrom")]
+    pub laid_from: Option<String>,
+    #[serde(rename = "laidTo")]
+    pub laid_to: Option<String>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 50>")]
+    pub limit: Option<u32>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+/// How Parliament scrutinises an instrument: approved by vote (`affirmative`) or in
+/// force unless annulled (`negative`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SiProcedure {
+    Affirmative,
+    Negative,
+}
+
+/// Where an instrument has got to, judged from its dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SiStatus {
+    /// Announced to Parliament but not yet laid, e.g. a proposed negative.
+    Proposed,
+    /// Laid before Parliament but not yet made, e.g. a draft awaiting approval.
+    Laid,
+    /// Made but not yet in force.
+    Made,
+    InForce,
+    Withdrawn,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchStatutoryInstrumentsResult {
+    /// Most recently laid first.
+    pub instruments: Vec<StatutoryInstrument>,
+    /// Set when more instruments matched `searchTerm` than were filtered, or when SI
+    /// numbers could not be looked up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct StatutoryInstrument {
+    pub id: String,
+    pub name: String,
+    /// The SI number, e.g. `2019/1056`; absent for drafts and instruments not yet on
+    /// legislation.gov.uk.
+    pub number: Option<String>,
+    /// The laying department.
+    pub department: Option<String>,
+    pub procedure: Option<SiProcedure>,
+    /// The full procedure, e.g. `Made negative` or `Draft affirmative`.
+    #[serde(rename = "procedureName")]
+    pub procedure_name: Option<String>,
+    /// The earlier of the Commons and Lords laying dates.
+    #[serde(rename = "laidDate")]
+    pub laid_date: Option<String>,
+    #[serde(rename = "madeDate")]
+    pub made_date: Option<String>,
+    #[serde(rename = "comingIntoForceDate")]
+    pub coming_into_force_date: Option<String>,
+    pub status: SiStatus,
+    /// The instrument on the Statutory Instruments tracker.
+    pub url: String,
+    /// The instrument on legislation.gov.uk, once it has an SI number.
+    #[serde(rename = "legislationUrl")]
+    pub legislation_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchEdmsArgs {
+    #[serde(rename = "searchTerm")]
+    pub search_term: Option<String>,
+    /// A member who sponsored or signed the motion.
+    #[serde(
+        default,
+        rename = "sponsorId",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub sponsor_id: Option<u32>,
+    pub status: Option<EdmStatus>,
+    #[serde(rename = "tabledFrom")]
+    pub tabled_from: Option<String>,
+    #[serde(rename = "tabledTo")]
+    pub tabled_to: Option<String>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 50>")]
+    pub limit: Option<u32>,
+    #[serde(rename = "includeSignatories")]
+    pub include_signatories: Option<bool>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdmStatus {
+    Published,
+    Withdrawn,
+}
+
+impl EdmStatus {
+    /// The Oral Questions and Motions API's spelling.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Published => "Published",
+            Self::Withdrawn => "Withdrawn",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchEdmsResult {
+    /// Newest tabled first.
+    pub motions: Vec<EarlyDayMotion>,
+    /// Matches the API holds in all, beyond the `limit` returned.
+    #[serde(rename = "totalResults")]
+    pub total_results: Option<u64>,
+    /// Set when signatories were asked for but not all could be listed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct EarlyDayMotion {
+    pub id: u64,
+    /// The EDM number, with any amendment suffix, e.g. `512` or `512A1`.
+    pub number: Option<String>,
+    pub title: String,
+    /// Plain text, cut to 400 characters.
+    #[serde(rename = "motionText")]
+    pub motion_text: String,
+    #[serde(rename = "primarySponsor")]
+    pub primary_sponsor: Option<EdmMember>,
+    /// Signatures, sponsors' included.
+    #[serde(rename = "signatureCount")]
+    pub signature_count: Option<u64>,
+    #[serde(rename = "dateTabled")]
+    pub date_tabled: String,
+    pub status: Option<String>,
+    /// The motion on edm.parliament.uk.
+    pub url: String,
+    /// Only with `includeSignatories`, and only for the first few motions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signatories: Option<Vec<EdmSignatory>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct EdmMember {
+    #[serde(rename = "memberId")]
+    pub member_id: Option<u32>,
+    pub name: String,
+    pub party: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct EdmSignatory {
+    #[serde(rename = "memberId")]
+    pub member_id: u32,
+    pub name: Option<String>,
+    pub party: Option<String>,
+    #[serde(rename = "signedDate")]
+    pub signed_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchOralQuestionsArgs {
+    #[serde(rename = "answeringDateFrom")]
+    pub answering_date_from: Option<String>,
+    #[serde(rename = "answeringDateTo")]
+    pub answering_date_to: Option<String>,
+    /// Answering body (department) id, as in the Members API's answering bodies.
+    #[serde(
+        default,
+        rename = "answeringBody",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub answering_body: Option<u32>,
+    #[serde(
+        default,
+        rename = "askingMemberId",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub asking_member_id: Option<u32>,
+    /// Both when unset.
+    pub status: Option<OralQuestionStatus>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 100>")]
+    pub limit: Option<u32>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+/// Whether an oral question's answering day has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OralQuestionStatus {
+    /// Due to be answered today or later.
+    Tabled,
+    Answered,
+}
+
+impl OralQuestionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tabled => "tabled",
+            Self::Answered => "answered",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchOralQuestionsResult {
+    /// In the API's order: by answering date, then position on the order paper.
+    pub questions: Vec<OralQuestion>,
+    /// Matches the API holds in all, beyond the `limit` returned.
+    #[serde(rename = "totalResults")]
+    pub total_results: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct OralQuestion {
+    pub id: u64,
+    /// `Substantive` or `Topical`.
+    #[serde(rename = "questionType")]
+    pub question_type: Option<String>,
+    #[serde(rename = "questionText")]
+    pub question_text: String,
+    #[serde(rename = "askingMemberId")]
+    pub asking_member_id: Option<u32>,
+    #[serde(rename = "askingMemberName")]
+    pub asking_member_name: Option<String>,
+    #[serde(rename = "askingMemberParty")]
+    pub asking_member_party: Option<String>,
+    /// The department answering, e.g. `Department of Health and Social Care`.
+    #[serde(rename = "answeringBody")]
+    pub answering_body: Option<String>,
+    #[serde(rename = "tabledDate")]
+    pub tabled_date: Option<String>,
+    /// The day the question is scheduled to be, or was, answered in the chamber.
+    #[serde(rename = "answeringDate")]
+    pub answering_date: String,
+    pub status: OralQuestionStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FetchWrittenQuestionsArgs {
+    #[serde(
+        default,
+        rename = "askingMemberId",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub asking_member_id: Option<u32>,
+    /// Answering body (department) id, as in the Members API's answering bodies.
+    #[serde(
+        default,
+        rename = "answeringBody",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub answering_body: Option<u32>,
+    #[serde(rename = "tabledFrom")]
+    pub tabled_from: Option<String>,
+    #[serde(rename = "tabledTo")]
+    pub tabled_to: Option<String>,
+    pub answered: Option<AnsweredStatus>,
+    #[serde(rename = "searchTerm")]
+    pub search_term: Option<String>,
+    #[serde(default, deserialize_with = "bounds::optional::<_, _, 1, 100>")]
+    pub limit: Option<u32>,
+    #[serde(rename = "enableCache")]
+    pub enable_cache: Option<bool>,
+    #[serde(rename = "cacheOnly")]
+    pub cache_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnsweredStatus {
+    Any,
+    Answered,
+    Unanswered,
+}
+
+impl AnsweredStatus {
+    /// The Written Questions API's spelling.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "Any",
+            Self::Answered => "Answered",
+            Self::Unanswered => "Unanswered",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FetchWrittenQuestionsResult {
+    /// Newest tabled first.
+    pub questions: Vec<WrittenQuestion>,
+    /// Matches the API holds in all, beyond the `limit` returned.
+    #[serde(rename = "totalResults")]
+    pub total_results: Option<u64>,
+}
+
+/// One written parliamentary question, with its answer once given.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct WrittenQuestion {
+    pub id: u64,
+    /// The question's unique identification number, e.g. `24789`.
+    pub uin: Option<String>,
+    pub heading: Option<String>,
+    #[serde(rename = "questionText")]
+    pub question_text: String,
+    #[serde(rename = "dateTabled")]
+    pub date_tabled: String,
+    #[serde(rename = "askingMemberId")]
+    pub asking_member_id: Option<u32>,
+    #[serde(rename = "askingMemberName")]
+    pub asking_member_name: Option<String>,
+    /// The department answering, e.g. `Department of Health and Social Care`.
+    #[serde(rename = "answeringBody")]
+    pub answering_body: Option<String>,
+    /// Plain text; absent until the question is answered.
+    #[serde(rename = "answerText")]
+    pub answer_text: Option<String>,
+    #[serde(rename = "dateAnswered")]
+    pub date_answered: Option<String>,
+    /// The question on questions-statements.parliament.uk.
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct UkLawResult {
     pub title: String,
     pub year: Option<String>,
@@ -0,0 +1,79 @@
+//! Early day motions from the Oral Questions and Motions API, behind `parliament.fetch_edms`.
+
+use serde_json::Value;
+
+use crate::features::parliament::dto::{EarlyDayMotion, EdmMember, EdmSignatory};
+use crate::features::parliament::helpers::{json_day, json_items, json_text, plain_excerpt};
+
+/// Longest `motionText` kept; the full text is on the motion's page.
+const MAX_MOTION_CHARS: usize = 400;
+
+/// Motions in an `EarlyDayMotions/list` response. Items without an id or tabled date are
+/// skipped.
+pub fn parse_motions(payload: &Value) -> Vec<EarlyDayMotion> {
+    json_items(payload, "Response")
+        .filter_map(|item| {
+            let id = item.get("Id")?.as_u64()?;
+            let date_tabled = json_day(item, "DateTabled")?;
+            let number = json_text(item, "UINWithAmendmentSuffix").or_else(|| {
+                item.get("UIN")
+                    .and_then(Value::as_u64)
+                    .map(|uin| uin.to_string())
+            });
+            let primary_sponsor = item.get("PrimarySponsor").and_then(|sponsor| {
+                Some(EdmMember {
+                    member_id: member_id(sponsor.get("MnisId").or(item.get("MemberId"))),
+                    name: json_text(sponsor, "Name")?,
+                    party: json_text(sponsor, "Party"),
+                })
+            });
+            Some(EarlyDayMotion {
+                id,
+                number,
+                title: json_text(item, "Title").unwrap_or_else(|| format!("Early day motion {id}")),
+                motion_text: plain_excerpt(
+                    &json_text(item, "MotionText").unwrap_or_default(),
+                    MAX_MOTION_CHARS,
+                ),
+                primary_sponsor,
+                signature_count: item.get("SponsorsCount").and_then(Value::as_u64),
+                date_tabled,
+                status: json_text(item, "Status"),
+                url: format!("https://edm.parliament.uk/early-day-motion/{id}"),
+                signatories: None,
+            })
+        })
+        .collect()
+}
+
+/// Members who have signed, in signing order, from an `EarlyDayMotion/{id}` response.
+/// Withdrawn signatures are left out.
+pub fn parse_signatories(payload: &Value) -> Vec<EdmSignatory> {
+    let motion = payload.get("Response").unwrap_or(payload);
+    json_items(motion, "Sponsors")
+        .filter(|sponsor| sponsor.get("IsWithdrawn").and_then(Value::as_bool) != Some(true))
+        .filter_map(|sponsor| {
+            let member = sponsor.get("Member");
+            Some(EdmSignatory {
+                member_id: member_id(sponsor.get("MemberId"))?,
+                name: member.and_then(|member| json_text(member, "Name")),
+                party: member.and_then(|member| json_text(member, "Party")),
+                signed_date: json_day(sponsor, "CreatedWhen"),
+            })
+        })
+        .collect()
+}
+
+/// `PagingInfo.Total` of a list response.
+pub fn total_results(payload: &Value) -> Option<u64> {
+    payload
+        .get("PagingInfo")
+        .and_then(|paging| paging.get("Total"))
+        .and_then(Value::as_u64)
+}
+
+fn member_id(value: Option<&Value>) -> Option<u32> {
+    value
+        .and_then(Value::as_u64)
+        .and_then(|id| u32::try_from(id).ok())
+}
@@ -1,24 +1,45 @@
+use std::sync::Arc;
+
 use serde_json::Value;
 
 use crate::core::error::AppError;
 use crate::features::parliament::client::ParliamentClient;
 use crate::features::parliament::dto::{
-    ConstituencyLookupResult, FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs,
-    FetchMpActivityArgs, FetchMpVotingRecordArgs, LookupConstituencyArgs, SearchUkLawArgs,
+    ConstituencyLookupResult, FetchBillDetailArgs, FetchBillDivisionsArgs, FetchBillsArgs,
+    FetchCommitteesArgs, FetchCoreDatasetArgs, FetchDivisionDetailArgs, FetchEdmsArgs,
+    FetchLegislationArgs, FetchLegislationTextArgs, FetchMemberRolesArgs, FetchMembersBulkArgs,
+    FetchMpActivityArgs, FetchMpVotingRecordArgs, FetchOralQuestionsArgs,
+    FetchStatutoryInstrumentsArgs, FetchWrittenQuestionsArgs, ListConstituenciesArgs,
+    LookupConstituencyArgs, SearchHansardArgs, SearchUkLawArgs, VotingRecordGrouping,
 };
+use crate::features::parliament::policy_areas::aggregate_by_policy_area;
+use crate::features::parliament::projection::{FieldPath, parse_field_paths, project_items};
 
 pub async fn handle_fetch_core_dataset(
     client: &ParliamentClient,
     args: FetchCoreDatasetArgs,
 ) -> Result<Value, AppError> {
-    client.fetch_core_dataset(args).await
+    let fields = parse_field_paths(args.fields.as_deref())?;
+    let payload = client.fetch_core_dataset(args).await?;
+    Ok(project(payload, fields))
 }
 
 pub async fn handle_fetch_bills(
     client: &ParliamentClient,
     args: FetchBillsArgs,
 ) -> Result<Value, AppError> {
-    client.fetch_bills(args).await
+    let fields = parse_field_paths(args.fields.as_deref())?;
+    let payload = client.fetch_bills(args).await?;
+    Ok(project(payload, fields))
+}
+
+/// Projection runs on the client's result, so the cache always holds full items and every
+/// projection of the same query shares one entry.
+fn project(payload: Value, fields: Option<Vec<FieldPath>>) -> Value {
+    match fields {
+        Some(fields) => project_items(payload, &fields),
+        None => payload,
+    }
 }
 
 pub async fn handle_fetch_legislation(
@@ -32,25 +53,58 @@ pub async fn handle_fetch_mp_activity(
     client: &ParliamentClient,
     args: FetchMpActivityArgs,
 ) -> Result<Value, AppError> {
-    let activities = client.fetch_mp_activity(args).await?;
-    serde_json::to_value(activities)
+    let activity = client.fetch_mp_activity(args).await?;
+    serde_json::to_value(activity)
         .map_err(|err| AppError::internal(format!("failed to serialise activities: {err}")))
 }
 
+pub async fn handle_fetch_members_bulk(
+    client: &ParliamentClient,
+    args: FetchMembersBulkArgs,
+) -> Result<Value, AppError> {
+    let members = client.fetch_members_bulk(args).await?;
+    serde_json::to_value(members)
+        .map_err(|err| AppError::internal(format!("failed to serialise members: {err}")))
+}
+
+pub async fn handle_fetch_bill_divisions(
+    client: &ParliamentClient,
+    args: FetchBillDivisionsArgs,
+) -> Result<Value, AppError> {
+    let divisions = client.fetch_bill_divisions(args).await?;
+    serde_json::to_value(divisions)
+        .map_err(|err| AppError::internal(format!("failed to serialise bill divisions: {err}")))
+}
+
 pub async fn handle_fetch_mp_voting_record(
     client: &ParliamentClient,
     args: FetchMpVotingRecordArgs,
 ) -> Result<Value, AppError> {
+    let group_by = args.group_by;
     let votes = client.fetch_mp_voting_record(args).await?;
+
+    if group_by == Some(VotingRecordGrouping::PolicyArea) {
+        let summary = aggregate_by_policy_area(votes, &client.policy_areas());
+        return serde_json::to_value(summary).map_err(|err| {
+            AppError::internal(format!("failed to serialise voting summary: {err}"))
+        });
+    }
+
     serde_json::to_value(votes)
         .map_err(|err| AppError::internal(format!("failed to serialise votes: {err}")))
 }
 
 pub async fn handle_lookup_constituency_offline(
-    client: &ParliamentClient,
+    client: &Arc<ParliamentClient>,
     args: LookupConstituencyArgs,
+    prefetch_member: bool,
 ) -> Result<Value, AppError> {
     let result: ConstituencyLookupResult = client.lookup_constituency_offline(args).await?;
+    if prefetch_member {
+        if let Some(mp_id) = result.mp_id {
+            client.spawn_member_prefetch(mp_id);
+        }
+    }
     serde_json::to_value(result).map_err(|err| {
         AppError::internal(format!(
             "failed to serialise constituency lookup response: {err}"
@@ -58,6 +112,15 @@ pub async fn handle_lookup_constituency_offline(
     })
 }
 
+pub async fn handle_list_constituencies(
+    client: &ParliamentClient,
+    args: ListConstituenciesArgs,
+) -> Result<Value, AppError> {
+    let result = client.list_constituencies(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise constituencies: {err}")))
+}
+
 pub async fn handle_search_uk_law(
     client: &ParliamentClient,
     args: SearchUkLawArgs,
@@ -67,3 +130,107 @@ pub async fn handle_search_uk_law(
         AppError::internal(format!("failed to serialise UK law search results: {err}"))
     })
 }
+
+pub async fn handle_search_hansard(
+    client: &ParliamentClient,
+    args: SearchHansardArgs,
+) -> Result<Value, AppError> {
+    let result = client.search_hansard(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise hansard results: {err}")))
+}
+
+pub async fn handle_fetch_written_questions(
+    client: &ParliamentClient,
+    args: FetchWrittenQuestionsArgs,
+) -> Result<Value, AppError> {
+    let result = client.fetch_written_questions(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise written questions: {err}")))
+}
+
+pub async fn handle_fetch_committees(
+    client: &ParliamentClient,
+    args: FetchCommitteesArgs,
+) -> Result<Value, AppError> {
+    let result = client.fetch_committees(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise committees: {err}")))
+}
+
+pub async fn handle_fetch_bill_detail(
+    client: &ParliamentClient,
+    args: FetchBillDetailArgs,
+) -> Result<Value, AppError> {
+    let detail = client.fetch_bill_detail(args).await?;
+    serde_json::to_value(detail)
+        .map_err(|err| AppError::internal(format!("failed to serialise bill: {err}")))
+}
+
+pub async fn handle_fetch_legislation_text(
+    client: &ParliamentClient,
+    args: FetchLegislationTextArgs,
+) -> Result<Value, AppError> {
+    let text = client.fetch_legislation_text(args).await?;
+    serde_json::to_value(text)
+        .map_err(|err| AppError::internal(format!("failed to serialise legislation text: {err}")))
+}
+
+pub async fn handle_fetch_member_roles(
+    client: &ParliamentClient,
+    args: FetchMemberRolesArgs,
+) -> Result<Value, AppError> {
+    let result = client.fetch_member_roles(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise member roles: {err}")))
+}
+
+pub async fn handle_fetch_division_detail(
+    client: &ParliamentClient,
+    args: FetchDivisionDetailArgs,
+) -> Result<Value, AppError> {
+    // Applied after caching, so both forms share one cached division.
+    let names_only = args.names_only.unwrap_or(false);
+    let mut detail = client.fetch_division_detail(args).await?;
+    if names_only {
+        for member in detail
+            .aye_members
+            .iter_mut()
+            .chain(&mut detail.no_members)
+            .chain(&mut detail.aye_tellers)
+            .chain(&mut detail.no_tellers)
+        {
+            member.party = None;
+        }
+    }
+    serde_json::to_value(detail)
+        .map_err(|err| AppError::internal(format!("failed to serialise division: {err}")))
+}
+
+pub async fn handle_fetch_edms(
+    client: &ParliamentClient,
+    args: FetchEdmsArgs,
+) -> Result<Value, AppError> {
+    let result = client.fetch_edms(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise early day motions: {err}")))
+}
+
+pub async fn handle_fetch_oral_questions(
+    client: &ParliamentClient,
+    args: FetchOralQuestionsArgs,
+) -> Result<Value, AppError> {
+    let result = client.fetch_oral_questions(args).await?;
+    serde_json::to_value(result)
+        .map_err(|err| AppError::internal(format!("failed to serialise oral questions: {err}")))
+}
+
+pub async fn handle_fetch_statutory_instruments(
+    client: &ParliamentClient,
+    args: FetchStatutoryInstrumentsArgs,
+) -> Result<Value, AppError> {
+    let result = client.fetch_statutory_instruments(args).await?;
+    serde_json::to_value(result).map_err(|err| {
+        AppError::internal(format!("failed to serialise statutory instruments: {err}"))
+    })
+}
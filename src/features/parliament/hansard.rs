@@ -0,0 +1,64 @@
+//! The Hansard API's spoken contribution search, behind `parliament.search_hansard` and the
+//! spoken contributions in `parliament.fetch_mp_activity`.
+
+use serde_json::Value;
+
+use crate::features::parliament::dto::HansardContribution;
+use crate::features::parliament::helpers::{json_day, json_items, json_text, plain_excerpt};
+
+/// Longest `snippet` kept; `ContributionText` is usually an excerpt already.
+const MAX_SNIPPET_CHARS: usize = 400;
+
+/// Contributions in a `search/contributions/Spoken.json` response. Items without an id or
+/// sitting date cannot be linked to and are skipped.
+pub fn parse_contributions(payload: &Value) -> Vec<HansardContribution> {
+    json_items(payload, "Results")
+        .filter_map(|item| {
+            let id = json_text(item, "ContributionExtId")?;
+            let sitting_date = json_day(item, "SittingDate")?;
+            let title = json_text(item, "DebateSection").unwrap_or_else(|| "Debate".to_string());
+            let house = json_text(item, "House").unwrap_or_else(|| "Commons".to_string());
+            let url = match json_text(item, "DebateSectionExtId") {
+                Some(section) => format!(
+                    "https://hansard.parliament.uk/{house}/{sitting_date}/debates/{section}/{}#contribution-{id}",
+                    hansard_slug(&title)
+                ),
+                None => format!("https://hansard.parliament.uk/{house}/{sitting_date}"),
+            };
+            let snippet = json_text(item, "ContributionText")
+                .or_else(|| json_text(item, "ContributionTextFull"))
+                .unwrap_or_default();
+            Some(HansardContribution {
+                section: json_text(item, "Section").or_else(|| json_text(item, "HansardSection")),
+                member_name: json_text(item, "AttributedTo")
+                    .or_else(|| json_text(item, "MemberName")),
+                snippet: plain_excerpt(&snippet, MAX_SNIPPET_CHARS),
+                id,
+                title,
+                sitting_date,
+                house,
+                url,
+            })
+        })
+        .collect()
+}
+
+/// `TotalResultCount` of a search response.
+pub fn total_results(payload: &Value) -> Option<u64> {
+    payload.get("TotalResultCount").and_then(Value::as_u64)
+}
+
+/// Hansard's URL form of a debate title: its words run together, e.g. `HousingBill`.
+fn hansard_slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
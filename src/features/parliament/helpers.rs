@@ -1,54 +1,102 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use sled::Tree;
 use tokio::task;
 
+use crate::core::call_context::record_cache_hit;
+use crate::core::clock::Clock;
 use crate::core::error::AppError;
 
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CacheEnvelope<T> {
+    /// Wall-clock write time, for display.
     stored_at: u64,
+    /// [`Clock::steady_secs`] at write time; freshness is measured from this. Entries
+    /// written before it was recorded fall back to `stored_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    steady_at: Option<u64>,
     payload: T,
 }
 
-pub async fn read_cache<T>(tree: &Tree, key: &str, ttl: u64) -> Result<Option<T>, AppError>
+impl<T> CacheEnvelope<T> {
+    fn new(clock: &Clock, payload: T) -> Self {
+        Self {
+            stored_at: clock.wall_secs(),
+            steady_at: Some(clock.steady_secs()),
+            payload,
+        }
+    }
+}
+
+/// Read `key` if it was stored no more than `ttl` seconds ago. An entry stamped in the
+/// future (by a clock that has since stepped back) is re-stamped as written now, so it
+/// lives one more `ttl` rather than until the clock catches up.
+pub async fn read_cache<T>(
+    tree: &Tree,
+    clock: &Clock,
+    key: &str,
+    ttl: u64,
+) -> Result<Option<T>, AppError>
 where
     T: DeserializeOwned + Send + 'static,
 {
     let tree = tree.clone();
+    let clock = clock.clone();
     let key_bytes = key.as_bytes().to_vec();
 
-    task::spawn_blocking(move || -> Result<Option<T>, AppError> {
+    let cached = task::spawn_blocking(move || -> Result<Option<T>, AppError> {
         let maybe_bytes = tree
             .get(&key_bytes)
             .map_err(|err| AppError::internal(format!("cache lookup failed: {err}")))?;
 
-        if let Some(bytes) = maybe_bytes {
-            let envelope: CacheEnvelope<T> = serde_json::from_slice(&bytes).map_err(|err| {
-                AppError::internal(format!("failed to decode cached response: {err}"))
-            })?;
-
-            if current_timestamp().saturating_sub(envelope.stored_at) <= ttl {
-                return Ok(Some(envelope.payload));
+        let Some(bytes) = maybe_bytes else {
+            return Ok(None);
+        };
+        let envelope: CacheEnvelope<Value> = serde_json::from_slice(&bytes).map_err(|err| {
+            AppError::internal(format!("failed to decode cached response: {err}"))
+        })?;
+
+        let payload = match clock.age_secs(envelope.steady_at.unwrap_or(envelope.stored_at)) {
+            Some(age) if age <= ttl => envelope.payload,
+            Some(_) => return Ok(None),
+            None => {
+                let restamped = CacheEnvelope::new(&clock, envelope.payload);
+                let data = serde_json::to_vec(&restamped).map_err(|err| {
+                    AppError::internal(format!("failed to encode cache entry: {err}"))
+                })?;
+                // Lost races leave whatever replaced the entry.
+                let _ = tree
+                    .compare_and_swap(&key_bytes, Some(&bytes), Some(data))
+                    .map_err(|err| {
+                        AppError::internal(format!("failed to write cache entry: {err}"))
+                    })?;
+                restamped.payload
             }
-        }
-
-        Ok(None)
+        };
+        serde_json::from_value(payload)
+            .map(Some)
+            .map_err(|err| AppError::internal(format!("failed to decode cached response: {err}")))
     })
     .await
-    .map_err(|err| AppError::internal(format!("cache task join error: {err}")))?
+    .map_err(|err| AppError::internal(format!("cache task join error: {err}")))??;
+
+    if cached.is_some() {
+        record_cache_hit();
+    }
+    Ok(cached)
 }
 
-pub async fn write_cache<T>(tree: &Tree, key: &str, value: &T) -> Result<(), AppError>
+pub async fn write_cache<T>(
+    tree: &Tree,
+    clock: &Clock,
+    key: &str,
+    value: &T,
+) -> Result<(), AppError>
 where
     T: Serialize,
 {
-    let envelope = CacheEnvelope {
-        stored_at: current_timestamp(),
-        payload: value,
-    };
+    let envelope = CacheEnvelope::new(clock, value);
     let data = serde_json::to_vec(&envelope)
         .map_err(|err| AppError::internal(format!("failed to encode cache entry: {err}")))?;
 
@@ -83,9 +131,56 @@ pub fn normalise_postcode(postcode: &str) -> Option<String> {
     Some(cleaned.to_uppercase())
 }
 
-fn current_timestamp() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_secs())
-        .unwrap_or(0)
+/// UK postcodes are 5-7 alphanumeric characters once whitespace is removed.
+pub fn is_plausible_postcode(normalised: &str) -> bool {
+    (5..=7).contains(&normalised.len()) && normalised.chars().all(|ch| ch.is_ascii_alphanumeric())
+}
+
+/// The array under `key` in `payload`, or `payload` itself when it is the array.
+pub fn json_items<'a>(payload: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    payload
+        .get(key)
+        .or(Some(payload))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// The trimmed string at `key`, unless it is missing or blank.
+pub fn json_text(item: &Value, key: &str) -> Option<String> {
+    item.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// The `YYYY-MM-DD` part of the timestamp at `key`, so dates from every API compare alike.
+pub fn json_day(item: &Value, key: &str) -> Option<String> {
+    let value = item.get(key)?.as_str()?.trim();
+    let date = value.get(..10)?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(date.to_string())
+}
+
+/// `text` with markup tags dropped and whitespace collapsed, cut to `max_chars`.
+pub fn plain_excerpt(text: &str, max_chars: usize) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                plain.push(' ');
+            }
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    let collapsed = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", collapsed[..end].trim_end()),
+        None => collapsed,
+    }
 }
@@ -0,0 +1,162 @@
+//! The wording of an Act or section from legislation.gov.uk's CLML XML, behind
+//! `parliament.fetch_legislation_text`.
+
+use roxmltree::{Document, Node};
+
+use crate::core::error::AppError;
+use crate::features::parliament::dto::LegislationText;
+
+/// Longest `text` returned; longer sections are cut and flagged `truncated`.
+pub const MAX_LEGISLATION_TEXT_CHARS: usize = 20_000;
+
+/// Elements whose text is the amended wording, tied to a commentary by `CommentaryRef`.
+const AMENDMENT_TAGS: &[&str] = &["Substitution", "Addition", "Repeal"];
+
+/// Pull the plain text out of a CLML `data.xml` document found at `url`. With `section`,
+/// the provision whose id is `section-{section}` is read, else the first provision in the
+/// document; without, the whole body.
+pub fn parse_legislation_text(
+    xml: &str,
+    section: Option<&str>,
+    url: String,
+) -> Result<LegislationText, AppError> {
+    let document = Document::parse(xml).map_err(|err| {
+        AppError::internal(format!("failed to parse legislation document: {err}"))
+    })?;
+    let root = document.root_element();
+
+    let target = match section {
+        Some(section) => {
+            let id = format!("section-{section}");
+            root.descendants()
+                .find(|node| node.has_tag_name("P1") && node.attribute("id") == Some(id.as_str()))
+                .or_else(|| root.descendants().find(|node| node.has_tag_name("P1")))
+                .ok_or_else(|| AppError::bad_request(format!("section {section} was not found")))?
+        }
+        None => root
+            .descendants()
+            .find(|node| node.has_tag_name("Body"))
+            .unwrap_or(root),
+    };
+
+    // A section's heading is the title of the group it heads.
+    let group = target
+        .parent_element()
+        .filter(|parent| parent.has_tag_name("P1group"));
+    let heading = section.and_then(|_| {
+        group
+            .into_iter()
+            .chain(std::iter::once(target))
+            .find_map(|node| child_text(node, "Title"))
+    });
+
+    let mut lines = Vec::new();
+    let mut pending_number: Option<String> = None;
+    for node in target.descendants().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Pnumber" if node.parent_element() != Some(target) => {
+                let number = collapse(&inline_text(node));
+                // Subsections and paragraphs are bracketed, sections are not.
+                let section_number = node
+                    .parent_element()
+                    .is_some_and(|parent| parent.has_tag_name("P1"));
+                let (before, after) = if section_number { ("", "") } else { ("(", ")") };
+                let before = node.attribute("PuncBefore").unwrap_or(before);
+                let after = node.attribute("PuncAfter").unwrap_or(after);
+                pending_number = Some(format!("{before}{number}{after}"));
+            }
+            "Text" => {
+                let text = collapse(&inline_text(node));
+                if text.is_empty() {
+                    continue;
+                }
+                lines.push(match pending_number.take() {
+                    Some(number) => format!("{number} {text}"),
+                    None => text,
+                });
+            }
+            _ => {}
+        }
+    }
+    let (text, truncated) = truncate(&lines.join("\n"), MAX_LEGISLATION_TEXT_CHARS);
+
+    let extent = std::iter::once(target)
+        .chain(group)
+        .chain(std::iter::once(root))
+        .find_map(|node| node.attribute("RestrictExtent"))
+        .map(str::to_string);
+
+    let mut references: Vec<&str> = Vec::new();
+    for node in target.descendants().filter(|node| {
+        node.has_tag_name("CommentaryRef") || AMENDMENT_TAGS.contains(&node.tag_name().name())
+    }) {
+        let reference = node
+            .attribute("CommentaryRef")
+            .or_else(|| node.attribute("Ref"));
+        if let Some(reference) = reference {
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+    }
+    let amendments = references
+        .into_iter()
+        .filter_map(|reference| {
+            root.descendants()
+                .find(|node| {
+                    node.has_tag_name("Commentary") && node.attribute("id") == Some(reference)
+                })
+                .map(|commentary| {
+                    let notes: Vec<String> = commentary
+                        .descendants()
+                        .filter(|node| node.has_tag_name("Text"))
+                        .map(|node| collapse(&inline_text(node)))
+                        .filter(|note| !note.is_empty())
+                        .collect();
+                    notes.join(" ")
+                })
+                .filter(|note| !note.is_empty())
+        })
+        .collect();
+
+    Ok(LegislationText {
+        title: root
+            .descendants()
+            .find(|node| node.has_tag_name("title"))
+            .map(|node| collapse(&inline_text(node)))
+            .filter(|title| !title.is_empty()),
+        section: section.map(str::to_string),
+        heading,
+        text,
+        truncated,
+        extent,
+        amendments,
+        url,
+    })
+}
+
+/// All text under `node`, the wording of inline amendments included.
+fn inline_text(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect()
+}
+
+fn child_text(node: Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name(tag))
+        .map(|child| collapse(&inline_text(child)))
+        .filter(|text| !text.is_empty())
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(text: &str, max_chars: usize) -> (String, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => (format!("{}…", &text[..index]), true),
+        None => (text.to_string(), false),
+    }
+}
@@ -0,0 +1,112 @@
+//! A member's committee memberships and frontbench posts, behind
+//! `parliament.fetch_member_roles`.
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::features::parliament::dto::{MemberRole, MemberRoleKind};
+use crate::features::parliament::helpers::{json_day, json_items, json_text};
+
+/// Committee roles from a Committees API `api/Members` response, one entry per role held
+/// (e.g. `Member` then `Chair`). Memberships that list no roles count as `Member`.
+pub fn parse_committee_roles(payload: &Value, today: NaiveDate) -> Vec<MemberRole> {
+    let mut roles = Vec::new();
+    for membership in json_items(payload, "items") {
+        let Some(committee) = membership
+            .get("committee")
+            .and_then(|committee| json_text(committee, "name"))
+        else {
+            continue;
+        };
+        let held: Vec<&Value> = json_items(membership, "roles").collect();
+        if held.is_empty() {
+            roles.push(role(
+                MemberRoleKind::Committee,
+                "Member".to_string(),
+                Some(committee),
+                membership,
+                today,
+            ));
+            continue;
+        }
+        for held in held {
+            let name = held
+                .get("role")
+                .and_then(|role| json_text(role, "name"))
+                .unwrap_or_else(|| "Member".to_string());
+            roles.push(role(
+                MemberRoleKind::Committee,
+                name,
+                Some(committee.clone()),
+                held,
+                today,
+            ));
+        }
+    }
+    roles
+}
+
+/// Government and opposition posts from a Members API `Members/{id}/Biography` response.
+/// A post named like `Minister of State (Department for Education)` is split into the
+/// role and its department.
+pub fn parse_posts(payload: &Value, today: NaiveDate) -> Vec<MemberRole> {
+    let biography = payload.get("value").unwrap_or(payload);
+    [
+        ("governmentPosts", MemberRoleKind::Government),
+        ("oppositionPosts", MemberRoleKind::Opposition),
+    ]
+    .into_iter()
+    .flat_map(|(key, kind)| {
+        json_items(biography, key).filter_map(move |post| {
+            let title = json_text(post, "name")?;
+            let (name, organisation) = split_department(&title);
+            Some(role(kind, name, organisation, post, today))
+        })
+    })
+    .collect()
+}
+
+/// Current roles first, then the most recently started.
+pub fn sort_roles(roles: &mut [MemberRole]) {
+    roles.sort_by(|a, b| {
+        b.current
+            .cmp(&a.current)
+            .then_with(|| b.start_date.cmp(&a.start_date))
+    });
+}
+
+fn role(
+    kind: MemberRoleKind,
+    name: String,
+    organisation: Option<String>,
+    item: &Value,
+    today: NaiveDate,
+) -> MemberRole {
+    let end_date = json_day(item, "endDate");
+    let current = end_date
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .is_none_or(|date| date >= today);
+    MemberRole {
+        kind,
+        name,
+        organisation,
+        start_date: json_day(item, "startDate"),
+        end_date,
+        current,
+    }
+}
+
+fn split_department(title: &str) -> (String, Option<String>) {
+    if let Some(open) = title.find(" (") {
+        if let Some(department) = title[open + 2..].strip_suffix(')') {
+            if !department.trim().is_empty() {
+                return (
+                    title[..open].trim().to_string(),
+                    Some(department.trim().to_string()),
+                );
+            }
+        }
+    }
+    (title.to_string(), None)
+}
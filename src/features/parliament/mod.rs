@@ -1,15 +1,57 @@
+pub mod activity;
+pub mod bill_detail;
+pub mod bill_stages;
 pub mod client;
+pub mod committees;
+pub mod datasets;
+pub mod divisions;
 pub mod dto;
+pub mod edms;
 pub mod handler;
+pub mod hansard;
 mod helpers;
+pub mod legislation_text;
+pub mod member_roles;
+pub mod oral_questions;
+pub mod policy_areas;
+pub mod projection;
+pub mod relevance;
+pub mod sessions;
+pub mod statutory_instruments;
+pub mod uk_law;
+pub mod written_questions;
 
+pub use activity::ActivitySource;
+pub use bill_stages::{BillStage, StageDescription, describe_bill_stage};
 pub use client::ParliamentClient;
+pub use datasets::{DatasetDescriptor, ExportOptions, find_dataset};
 pub use dto::{
-    FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs, FetchMpActivityArgs,
-    FetchMpVotingRecordArgs, LookupConstituencyArgs, SearchUkLawArgs,
+    AnsweredStatus, BillDetail, BillDivision, BillProgressStage, BillPublication, BillSponsor,
+    BillStageDivisions, Committee, CommitteeHouse, CommitteeInquiry, CommitteeMember,
+    ConstituencyListing, ConstituencyLookupResult, DivisionDetail, DivisionMember, DivisionOutcome,
+    EarlyDayMotion, EdmMember, EdmSignatory, EdmStatus, ExportVotingRecordArgs,
+    FetchBillDetailArgs, FetchBillDivisionsArgs, FetchBillDivisionsResult, FetchBillsArgs,
+    FetchCommitteesArgs, FetchCommitteesResult, FetchCoreDatasetArgs, FetchDivisionDetailArgs,
+    FetchEdmsArgs, FetchEdmsResult, FetchLegislationArgs, FetchLegislationTextArgs,
+    FetchMemberRolesArgs, FetchMembersBulkArgs, FetchMembersBulkResult, FetchMpActivityArgs,
+    FetchMpVotingRecordArgs, FetchOralQuestionsArgs, FetchOralQuestionsResult,
+    FetchStatutoryInstrumentsArgs, FetchStatutoryInstrumentsResult, FetchWrittenQuestionsArgs,
+    FetchWrittenQuestionsResult, HansardContribution, LegislationText, ListConstituenciesArgs,
+    ListConstituenciesResult, LookupConstituencyArgs, MemberDivisionVote, MemberLookupEntry,
+    MemberRole, MemberRoleKind, MemberRolesResult, MemberSummary, MpActivityEntry,
+    MpActivityResult, MpVoteRecord, OralQuestion, OralQuestionStatus, PublicationLink,
+    SearchHansardArgs, SearchHansardResult, SearchUkLawArgs, SiProcedure, SiStatus,
+    StatutoryInstrument, UkLawResult, VotingRecordExportResult, VotingRecordGrouping,
+    WrittenQuestion,
 };
 pub use handler::{
-    handle_fetch_bills, handle_fetch_core_dataset, handle_fetch_legislation,
-    handle_fetch_mp_activity, handle_fetch_mp_voting_record, handle_lookup_constituency_offline,
-    handle_search_uk_law,
+    handle_fetch_bill_detail, handle_fetch_bill_divisions, handle_fetch_bills,
+    handle_fetch_committees, handle_fetch_core_dataset, handle_fetch_division_detail,
+    handle_fetch_edms, handle_fetch_legislation, handle_fetch_legislation_text,
+    handle_fetch_member_roles, handle_fetch_members_bulk, handle_fetch_mp_activity,
+    handle_fetch_mp_voting_record, handle_fetch_oral_questions, handle_fetch_statutory_instruments,
+    handle_fetch_written_questions, handle_list_constituencies, handle_lookup_constituency_offline,
+    handle_search_hansard, handle_search_uk_law,
 };
+pub use policy_areas::{PolicyAreaClassifier, aggregate_by_policy_area};
+pub use sessions::SessionRef;
@@ -0,0 +1,67 @@
+//! Oral questions from the Oral Questions and Motions API, behind
+//! `parliament.fetch_oral_questions`.
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::features::parliament::dto::{OralQuestion, OralQuestionStatus};
+use crate::features::parliament::helpers::{json_day, json_items, json_text, plain_excerpt};
+
+/// Longest `questionText` kept; substantive questions are a sentence or two.
+const MAX_QUESTION_CHARS: usize = 1000;
+
+/// Questions in an `oralquestions/list` response, with their status as of `today`: those
+/// due to be answered today or later are still `tabled`. Items without an id or answering
+/// date are skipped.
+pub fn parse_questions(payload: &Value, today: NaiveDate) -> Vec<OralQuestion> {
+    json_items(payload, "Response")
+        .filter_map(|item| {
+            let id = item.get("Id")?.as_u64()?;
+            let answering_date = json_day(item, "AnsweringWhen")?;
+            let status = match NaiveDate::parse_from_str(&answering_date, "%Y-%m-%d") {
+                Ok(date) if date < today => OralQuestionStatus::Answered,
+                _ => OralQuestionStatus::Tabled,
+            };
+            let member = item.get("AskingMember");
+            Some(OralQuestion {
+                id,
+                question_type: question_type(item.get("QuestionType")),
+                question_text: plain_excerpt(
+                    &json_text(item, "QuestionText").unwrap_or_default(),
+                    MAX_QUESTION_CHARS,
+                ),
+                asking_member_id: item
+                    .get("AskingMemberId")
+                    .and_then(Value::as_u64)
+                    .and_then(|id| u32::try_from(id).ok()),
+                asking_member_name: member.and_then(|member| json_text(member, "Name")),
+                asking_member_party: member.and_then(|member| json_text(member, "Party")),
+                answering_body: json_text(item, "AnsweringBody"),
+                tabled_date: json_day(item, "TabledWhen"),
+                answering_date,
+                status,
+            })
+        })
+        .collect()
+}
+
+/// `PagingInfo.Total` of a list response.
+pub fn total_results(payload: &Value) -> Option<u64> {
+    payload
+        .get("PagingInfo")
+        .and_then(|paging| paging.get("Total"))
+        .and_then(Value::as_u64)
+}
+
+/// `Substantive` or `Topical`; the API sends either the name or its enum value.
+fn question_type(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::Number(number) => match number.as_u64()? {
+            1 => Some("Substantive".to_string()),
+            2 => Some("Topical".to_string()),
+            _ => None,
+        },
+        Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        _ => None,
+    }
+}
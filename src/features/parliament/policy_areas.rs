@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::AppError;
+use crate::features::parliament::dto::MpVoteRecord;
+use crate::features::reference_data::ReferenceDataStore;
+
+pub const BUILTIN_POLICY_AREAS: &str = include_str!("policy_areas.json");
+/// Divisions listed under each area in an aggregated voting record.
+pub const TOP_DIVISIONS_PER_AREA: usize = 5;
+pub const OTHER_AREA_ID: &str = "other";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyArea {
+    pub id: String,
+    pub label: String,
+    /// Matched case-insensitively against the division title on word boundaries.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Matched the same way the voting-record `billId` filter matches divisions.
+    #[serde(default, rename = "billIds")]
+    pub bill_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolicyAreaFile {
+    areas: Vec<PolicyArea>,
+}
+
+/// Assigns divisions to curated policy areas. Areas are tried in file order and a
+/// division belongs to the first one that matches, so tallies never double count.
+#[derive(Debug, Clone)]
+pub struct PolicyAreaClassifier {
+    areas: Vec<PolicyArea>,
+}
+
+impl PolicyAreaClassifier {
+    /// The starter mapping bundled with the server.
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_POLICY_AREAS).expect("bundled policy areas must be valid")
+    }
+
+    /// The mapping in effect: `POLICY_AREAS_PATH` when set, otherwise the refreshed copy in
+    /// the reference data directory, otherwise the bundled one.
+    pub fn load(path: Option<&str>, reference_data: &ReferenceDataStore) -> Result<Self, AppError> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None => {
+                let dataset = ReferenceDataStore::dataset("policy_areas")
+                    .expect("policy areas are a reference dataset");
+                Self::from_json(&reference_data.load(dataset).0)
+            }
+        }
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, AppError> {
+        let contents = fs::read_to_string(path).map_err(|err| {
+            AppError::configuration(format!("failed to read POLICY_AREAS_PATH {path}: {err}"))
+        })?;
+        Self::from_json(&contents)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self, AppError> {
+        let file: PolicyAreaFile = serde_json::from_str(contents)
+            .map_err(|err| AppError::configuration(format!("invalid policy area file: {err}")))?;
+
+        let mut seen = Vec::with_capacity(file.areas.len());
+        for area in &file.areas {
+            let id = area.id.trim();
+            if id.is_empty() || id == OTHER_AREA_ID {
+                return Err(AppError::configuration(format!(
+                    "invalid policy area id {:?}: ids must be non-empty and not {OTHER_AREA_ID:?}",
+                    area.id
+                )));
+            }
+            if seen.contains(&id) {
+                return Err(AppError::configuration(format!(
+                    "duplicate policy area id {id:?}"
+                )));
+            }
+            seen.push(id);
+        }
+
+        let areas = file
+            .areas
+            .into_iter()
+            .map(|area| PolicyArea {
+                id: area.id.trim().to_string(),
+                label: area.label,
+                keywords: normalise_terms(area.keywords),
+                bill_ids: normalise_terms(area.bill_ids),
+            })
+            .collect();
+
+        Ok(Self { areas })
+    }
+
+    pub fn areas(&self) -> &[PolicyArea] {
+        &self.areas
+    }
+
+    pub fn classify(&self, vote: &MpVoteRecord) -> Option<&PolicyArea> {
+        let title = vote.title.as_deref().unwrap_or_default().to_lowercase();
+
+        self.areas.iter().find(|area| {
+            area.bill_ids
+                .iter()
+                .any(|bill_id| matches_bill_id(vote, bill_id))
+                || area
+                    .keywords
+                    .iter()
+                    .any(|keyword| contains_word(&title, keyword))
+        })
+    }
+}
+
+impl Default for PolicyAreaClassifier {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// How the member voted, collapsed across the Commons (Aye/No) and Lords (Content/Not
+/// Content) vocabularies. Anything unrecognised counts as absent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VotePosition {
+    For,
+    Against,
+    Absent,
+}
+
+impl VotePosition {
+    pub fn of(vote: &MpVoteRecord) -> Self {
+        let normalised = vote
+            .vote
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+
+        match normalised.as_str() {
+            "aye" | "ayes" | "yes" | "content" | "for" => Self::For,
+            "no" | "noe" | "noes" | "not content" | "against" => Self::Against,
+            _ => Self::Absent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyAreaTally {
+    pub id: String,
+    pub label: String,
+    #[serde(rename = "for")]
+    pub for_count: usize,
+    #[serde(rename = "against")]
+    pub against_count: usize,
+    #[serde(rename = "absent")]
+    pub absent_count: usize,
+    pub total: usize,
+    /// The most significant divisions in this area: ones the member voted in come
+    /// before absences, then most recent first.
+    pub divisions: Vec<MpVoteRecord>,
+}
+
+impl PolicyAreaTally {
+    fn new(id: &str, label: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            for_count: 0,
+            against_count: 0,
+            absent_count: 0,
+            total: 0,
+            divisions: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, vote: MpVoteRecord) {
+        match VotePosition::of(&vote) {
+            VotePosition::For => self.for_count += 1,
+            VotePosition::Against => self.against_count += 1,
+            VotePosition::Absent => self.absent_count += 1,
+        }
+        self.total += 1;
+        self.divisions.push(vote);
+    }
+
+    fn finish(&mut self) {
+        self.divisions.sort_by(|left, right| {
+            let voted = |vote: &MpVoteRecord| VotePosition::of(vote) != VotePosition::Absent;
+            voted(right)
+                .cmp(&voted(left))
+                .then_with(|| right.date.cmp(&left.date))
+        });
+        self.divisions.truncate(TOP_DIVISIONS_PER_AREA);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyAreaSummary {
+    #[serde(rename = "groupBy")]
+    pub group_by: &'static str,
+    #[serde(rename = "totalDivisions")]
+    pub total_divisions: usize,
+    /// One entry per configured area, in mapping order, including empty ones.
+    pub areas: Vec<PolicyAreaTally>,
+    pub other: PolicyAreaTally,
+    #[serde(rename = "coverageNote", skip_serializing_if = "Option::is_none")]
+    pub coverage_note: Option<String>,
+}
+
+pub fn aggregate_by_policy_area(
+    votes: Vec<MpVoteRecord>,
+    classifier: &PolicyAreaClassifier,
+) -> PolicyAreaSummary {
+    let total_divisions = votes.len();
+    let mut tallies: Vec<PolicyAreaTally> = classifier
+        .areas()
+        .iter()
+        .map(|area| PolicyAreaTally::new(&area.id, &area.label))
+        .collect();
+    let index: HashMap<&str, usize> = classifier
+        .areas()
+        .iter()
+        .enumerate()
+        .map(|(position, area)| (area.id.as_str(), position))
+        .collect();
+    let mut other = PolicyAreaTally::new(OTHER_AREA_ID, "Other (no policy area matched)");
+
+    for vote in votes {
+        let position = classifier
+            .classify(&vote)
+            .and_then(|area| index.get(area.id.as_str()).copied());
+        match position {
+            Some(position) => tallies[position].add(vote),
+            None => other.add(vote),
+        }
+    }
+
+    for tally in &mut tallies {
+        tally.finish();
+    }
+    other.finish();
+
+    let coverage_note = (other.total > 0).then(|| {
+        let names: Vec<&str> = classifier
+            .areas()
+            .iter()
+            .map(|area| area.id.as_str())
+            .collect();
+        format!(
+            "{} of {total_divisions} divisions did not match any policy area. The mapping is keyword based and only covers: {}.",
+            other.total,
+            names.join(", ")
+        )
+    });
+
+    PolicyAreaSummary {
+        group_by: "policyArea",
+        total_divisions,
+        areas: tallies,
+        other,
+        coverage_note,
+    }
+}
+
+/// Shared with the voting-record `billId` filter: an exact division id match or a
+/// case-insensitive title substring match. `bill_id` must already be lowercase.
+pub(crate) fn matches_bill_id(vote: &MpVoteRecord, bill_id: &str) -> bool {
+    vote.division_id
+        .as_deref()
+        .is_some_and(|value| value.to_lowercase() == bill_id)
+        || vote
+            .title
+            .as_deref()
+            .is_some_and(|title| title.to_lowercase().contains(bill_id))
+}
+
+fn normalise_terms(terms: Vec<String>) -> Vec<String> {
+    terms
+        .into_iter()
+        .map(|term| term.trim().to_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect()
+}
+
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    haystack.match_indices(needle).any(|(start, matched)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + matched.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
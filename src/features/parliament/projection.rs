@@ -0,0 +1,99 @@
+use serde_json::{Map, Value};
+
+use crate::core::error::AppError;
+
+/// A dot-separated path into a dataset item, such as `latestHouseMembership.membershipFrom`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath {
+    segments: Vec<String>,
+}
+
+impl FieldPath {
+    /// Segments are non-empty runs of ASCII letters, digits, `_` or `-`.
+    pub fn parse(path: &str) -> Result<Self, AppError> {
+        let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+        let valid = segments.iter().all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-'))
+        });
+        if !valid {
+            return Err(AppError::bad_request(format!(
+                "invalid field path {path:?}: use dot-separated names such as latestHouseMembership.membershipFrom"
+            )));
+        }
+        Ok(Self { segments })
+    }
+
+    fn lookup<'a>(&self, item: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(item, |current, segment| current.get(segment))
+    }
+}
+
+/// Parse the optional `fields` argument; `None` means no projection.
+pub fn parse_field_paths(fields: Option<&[String]>) -> Result<Option<Vec<FieldPath>>, AppError> {
+    fields
+        .map(|fields| fields.iter().map(|path| FieldPath::parse(path)).collect())
+        .transpose()
+}
+
+/// Reduce every item of a dataset payload to `paths`. Items are found in a top-level array,
+/// `items` or `result.items`; Members API `{ value, links }` envelopes are unwrapped first.
+/// Paths missing from an item come back as `null`. Payloads without an item list are
+/// returned unchanged.
+pub fn project_items(mut payload: Value, paths: &[FieldPath]) -> Value {
+    let location = if payload.is_array() {
+        ""
+    } else if payload.get("items").is_some_and(Value::is_array) {
+        "/items"
+    } else {
+        "/result/items"
+    };
+    // Whole values first, so `a` and `a.b` together keep all of `a`.
+    let mut ordered: Vec<&FieldPath> = paths.iter().collect();
+    ordered.sort_by_key(|path| path.segments.len());
+
+    if let Some(items) = payload.pointer_mut(location).and_then(Value::as_array_mut) {
+        for item in items.iter_mut() {
+            *item = project_item(normalise_item(item), &ordered);
+        }
+    }
+    payload
+}
+
+fn normalise_item(item: &Value) -> &Value {
+    match item.get("value") {
+        Some(value) if value.is_object() => value,
+        _ => item,
+    }
+}
+
+fn project_item(item: &Value, paths: &[&FieldPath]) -> Value {
+    let mut projected = Map::new();
+    for path in paths {
+        let value = path.lookup(item).cloned().unwrap_or(Value::Null);
+        insert_path(&mut projected, &path.segments, value);
+    }
+    Value::Object(projected)
+}
+
+fn insert_path(target: &mut Map<String, Value>, segments: &[String], value: Value) {
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+    let mut current = target;
+    for segment in parents {
+        let entry = current
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        // A non-object here was selected whole by a shorter path; keep it as is.
+        let Some(next) = entry.as_object_mut() else {
+            return;
+        };
+        current = next;
+    }
+    current.entry(last.clone()).or_insert(value);
+}
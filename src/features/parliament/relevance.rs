@@ -0,0 +1,164 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use serde_json::Value;
+
+/// Item fields whose text is scored against the search term.
+const TEXT_FIELDS: &[&str] = &[
+    "title",
+    "shortTitle",
+    "longTitle",
+    "name",
+    "nameDisplayAs",
+    "nameFullTitle",
+    "subject",
+    "label",
+    "summary",
+    "description",
+];
+/// Shortest word a fuzzy match may extend as a prefix or misspell by one letter.
+const MIN_FUZZY_WORD: usize = 4;
+
+/// How a search response is narrowed to the items relevant to its search term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelevanceFilter {
+    pub threshold: f32,
+    pub fuzzy: bool,
+}
+
+impl RelevanceFilter {
+    /// Memo key for the view of `payload` this filter makes for `search_term`. Equal keys
+    /// mean equal payloads (by hash), terms, thresholds and fuzziness.
+    pub fn view_key(&self, payload: &Value, search_term: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        payload.to_string().hash(&mut hasher);
+        search_term.hash(&mut hasher);
+        format!(
+            "{:016x}:threshold:{:.3}:fuzzy:{}",
+            hasher.finish(),
+            self.threshold,
+            self.fuzzy
+        )
+    }
+
+    /// `payload` keeping only the items scoring at least the threshold against
+    /// `search_term`. Items are found in a top-level array, `items` or `result.items`;
+    /// Members API `{ value, links }` envelopes are scored by their `value`. Items with
+    /// no text to score, and payloads without an item list, are kept.
+    pub fn apply(&self, mut payload: Value, search_term: &str) -> Value {
+        let words = words(search_term);
+        if words.is_empty() {
+            return payload;
+        }
+        let location = if payload.is_array() {
+            ""
+        } else if payload.get("items").is_some_and(Value::is_array) {
+            "/items"
+        } else {
+            "/result/items"
+        };
+        if let Some(items) = payload.pointer_mut(location).and_then(Value::as_array_mut) {
+            items.retain(|item| {
+                relevance_score(item, &words, self.fuzzy)
+                    .is_none_or(|score| score >= self.threshold)
+            });
+        }
+        payload
+    }
+}
+
+/// Share of `words` found among the words of the item's text fields, or `None` when the
+/// item has no text fields.
+fn relevance_score(item: &Value, words: &[String], fuzzy: bool) -> Option<f32> {
+    let item = item
+        .get("value")
+        .filter(|value| value.is_object())
+        .unwrap_or(item);
+    let mut text = String::new();
+    for field in TEXT_FIELDS {
+        if let Some(value) = item.get(field) {
+            collect_text(value, &mut text);
+        }
+    }
+    let candidates = self::words(&text);
+    if candidates.is_empty() {
+        return None;
+    }
+    let matched = words
+        .iter()
+        .filter(|word| {
+            candidates
+                .iter()
+                .any(|candidate| word_matches(word, candidate, fuzzy))
+        })
+        .count();
+    Some(matched as f32 / words.len() as f32)
+}
+
+/// Strings of `value`, including Linked Data `{ "_value": ... }` wrappers and arrays.
+fn collect_text(value: &Value, text: &mut String) {
+    match value {
+        Value::String(string) => {
+            text.push(' ');
+            text.push_str(string);
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_text(value, text)),
+        Value::Object(object) => {
+            if let Some(inner) = object.get("_value") {
+                collect_text(inner, text);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Exact, or with `fuzzy` one a prefix of the other or one edit apart, for words of at
+/// least [`MIN_FUZZY_WORD`] characters.
+fn word_matches(word: &str, candidate: &str, fuzzy: bool) -> bool {
+    if word == candidate {
+        return true;
+    }
+    if !fuzzy || word.chars().count() < MIN_FUZZY_WORD {
+        return false;
+    }
+    candidate.starts_with(word)
+        || (candidate.chars().count() >= MIN_FUZZY_WORD && word.starts_with(candidate))
+        || within_one_edit(word, candidate)
+}
+
+/// [`word_matches`] with fuzziness on, for other ranked lookups (argument completion).
+pub(crate) fn fuzzy_word_match(word: &str, candidate: &str) -> bool {
+    word_matches(word, candidate, true)
+}
+
+fn within_one_edit(left: &str, right: &str) -> bool {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let (shorter, longer) = if left.len() <= right.len() {
+        (&left, &right)
+    } else {
+        (&right, &left)
+    };
+    if longer.len() - shorter.len() > 1 {
+        return false;
+    }
+    let prefix = shorter
+        .iter()
+        .zip(longer.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix == shorter.len() {
+        return true;
+    }
+    if shorter.len() == longer.len() {
+        shorter[prefix + 1..] == longer[prefix + 1..]
+    } else {
+        shorter[prefix..] == longer[prefix + 1..]
+    }
+}
@@ -0,0 +1,112 @@
+//! Parliamentary session references such as `2024-25`, as the Bills API's `session`
+//! filter takes them. Callers write sessions many ways (`2024/25`, `2024-2025`); anything
+//! the API would silently match nothing for is rejected up front instead.
+
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+
+/// Argument schema `pattern` for session strings; [`SessionRef::parse`] accepts exactly
+/// what it matches, then checks the years themselves.
+pub const SESSION_PATTERN: &str = r"^\s*([0-9]{4}[-/–]([0-9]{2}|[0-9]{4})|current|previous|Current|Previous|CURRENT|PREVIOUS)\s*$";
+
+/// Earliest session start year accepted: the first Parliament of the United Kingdom.
+const FIRST_SESSION_YEAR: i32 = 1801;
+/// Month from which a new session is taken to be under way when resolving `current`.
+/// Sessions start whenever the King's Speech is held, so this is an approximation.
+const SESSION_START_MONTH: u32 = 9;
+
+const EXPECTED_FORMAT: &str =
+    "expected \"YYYY-YY\" (e.g. \"2024-25\"), \"current\" or \"previous\"";
+
+/// A session as given in tool arguments, before `current` and `previous` are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum SessionRef {
+    /// The session starting in this year.
+    Starting(i32),
+    Current,
+    Previous,
+}
+
+impl SessionRef {
+    /// Parse `2024-25`, `2024/25`, `2024–25`, `2024-2025` or the `current`/`previous`
+    /// sentinels. The years must be consecutive and the session no earlier than 1801.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let value = raw.trim();
+        match value {
+            "current" | "Current" | "CURRENT" => return Ok(Self::Current),
+            "previous" | "Previous" | "PREVIOUS" => return Ok(Self::Previous),
+            _ => {}
+        }
+        let invalid = |reason: &str| format!("invalid session \"{value}\": {reason}");
+
+        let Some((first, second)) = value.split_once(['-', '/', '–']) else {
+            return Err(invalid(EXPECTED_FORMAT));
+        };
+        let digits = |text: &str| text.chars().all(|ch| ch.is_ascii_digit());
+        if first.len() != 4 || !digits(first) || !matches!(second.len(), 2 | 4) || !digits(second) {
+            return Err(invalid(EXPECTED_FORMAT));
+        }
+        let start: i32 = first.parse().map_err(|_| invalid(EXPECTED_FORMAT))?;
+        let end: i32 = second.parse().map_err(|_| invalid(EXPECTED_FORMAT))?;
+        let consecutive = if second.len() == 2 {
+            end == (start + 1) % 100
+        } else {
+            end == start + 1
+        };
+        if !consecutive {
+            return Err(invalid(&format!(
+                "the years must be consecutive, e.g. \"{start}-{:02}\"",
+                (start + 1) % 100
+            )));
+        }
+        if start < FIRST_SESSION_YEAR {
+            return Err(invalid(&format!(
+                "sessions before {FIRST_SESSION_YEAR}-{:02} are not covered",
+                (FIRST_SESSION_YEAR + 1) % 100
+            )));
+        }
+        Ok(Self::Starting(start))
+    }
+
+    /// The canonical `YYYY-YY` form on `today`. Sessions that have not started yet are
+    /// rejected.
+    pub fn resolve(self, today: NaiveDate) -> Result<String, String> {
+        let current = current_session_start(today);
+        let start = match self {
+            Self::Starting(start) => start,
+            Self::Current => current,
+            Self::Previous => current - 1,
+        };
+        if start > current {
+            return Err(format!(
+                "invalid session \"{}\": it has not started yet (the current session is {})",
+                canonical(start),
+                canonical(current)
+            ));
+        }
+        Ok(canonical(start))
+    }
+}
+
+impl TryFrom<String> for SessionRef {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+/// Start year of the session under way on `date`, taking each session to run from
+/// September to August.
+pub fn current_session_start(date: NaiveDate) -> i32 {
+    if date.month() >= SESSION_START_MONTH {
+        date.year()
+    } else {
+        date.year() - 1
+    }
+}
+
+fn canonical(start: i32) -> String {
+    format!("{start}-{:02}", (start + 1) % 100)
+}
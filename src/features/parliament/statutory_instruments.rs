@@ -0,0 +1,109 @@
+//! Statutory instruments from the Statutory Instruments API, numbered from legislation.gov.uk,
+//! behind `parliament.fetch_statutory_instruments`.
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::features::parliament::client::LEGISLATION_HOST;
+use crate::features::parliament::dto::{SiProcedure, SiStatus, StatutoryInstrument, UkLawResult};
+use crate::features::parliament::helpers::{json_day, json_items, json_text};
+
+/// Instruments in a `StatutoryInstrument` response, judged against `today` for whether
+/// they are in force. Items without an id or name are skipped.
+pub fn parse_instruments(payload: &Value, today: NaiveDate) -> Vec<StatutoryInstrument> {
+    json_items(payload, "items")
+        .filter_map(|item| {
+            let item = item.get("value").unwrap_or(item);
+            let id = json_text(item, "id").or_else(|| {
+                item.get("id")
+                    .and_then(Value::as_u64)
+                    .map(|id| id.to_string())
+            })?;
+            let procedure_name = item
+                .get("procedure")
+                .and_then(|procedure| json_text(procedure, "name"));
+            let laid_date = [
+                json_day(item, "commonsLayingDate"),
+                json_day(item, "lordsLayingDate"),
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+            let made_date = json_day(item, "madeDate");
+            let coming_into_force_date = json_day(item, "comingIntoForceDate");
+            let status = if json_day(item, "withdrawnDate").is_some() {
+                SiStatus::Withdrawn
+            } else if made_date.is_some() {
+                let in_force = coming_into_force_date
+                    .as_deref()
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                    .is_some_and(|date| date <= today);
+                if in_force {
+                    SiStatus::InForce
+                } else {
+                    SiStatus::Made
+                }
+            } else if laid_date.is_some() {
+                SiStatus::Laid
+            } else {
+                SiStatus::Proposed
+            };
+            Some(StatutoryInstrument {
+                url: format!("https://statutoryinstruments.parliament.uk/instrument/{id}"),
+                name: json_text(item, "name")?,
+                id,
+                number: None,
+                department: json_text(item, "layingBodyName"),
+                procedure: procedure_name.as_deref().and_then(procedure_type),
+                procedure_name,
+                laid_date,
+                made_date,
+                coming_into_force_date,
+                status,
+                legislation_url: None,
+            })
+        })
+        .collect()
+}
+
+/// `Affirmative` or `Negative` from a procedure name such as `Draft affirmative`.
+pub fn procedure_type(name: &str) -> Option<SiProcedure> {
+    let name = name.to_lowercase();
+    if name.contains("affirmative") {
+        Some(SiProcedure::Affirmative)
+    } else if name.contains("negative") {
+        Some(SiProcedure::Negative)
+    } else {
+        None
+    }
+}
+
+/// Give each instrument the SI number and page of the `uksi` feed entry with the same
+/// title, ignoring case, punctuation and a leading `The`.
+pub fn attach_numbers(instruments: &mut [StatutoryInstrument], made: &[UkLawResult]) {
+    let prefix = format!("https://{LEGISLATION_HOST}/uksi/");
+    for instrument in instruments {
+        let name = normalise_title(&instrument.name);
+        let Some(entry) = made
+            .iter()
+            .find(|entry| normalise_title(&entry.title) == name)
+        else {
+            continue;
+        };
+        instrument.number = entry.url.strip_prefix(&prefix).map(str::to_string);
+        instrument.legislation_url = Some(entry.url.clone());
+    }
+}
+
+fn normalise_title(title: &str) -> String {
+    let words: Vec<String> = title
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let words = match words.first() {
+        Some(first) if first == "the" => &words[1..],
+        _ => &words[..],
+    };
+    words.join(" ")
+}
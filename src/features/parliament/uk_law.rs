@@ -0,0 +1,100 @@
+//! Search results from legislation.gov.uk's Atom feeds, behind `parliament.search_uk_law`.
+
+use roxmltree::{Document, Node};
+
+use crate::core::error::AppError;
+use crate::features::parliament::client::LEGISLATION_HOST;
+use crate::features::parliament::dto::UkLawResult;
+
+/// Namespace of the `ukm:` metadata elements in each feed entry.
+const METADATA_NAMESPACE: &str = "http://www.legislation.gov.uk/namespaces/metadata";
+
+/// The documents in a legislation.gov.uk `data.feed` response, in feed order. Entries
+/// without a document URI are skipped; a feed with no entries gives an empty list.
+pub fn parse_uk_law_feed(feed: &str) -> Result<Vec<UkLawResult>, AppError> {
+    let document = Document::parse(feed)
+        .map_err(|err| AppError::internal(format!("failed to parse legislation feed: {err}")))?;
+
+    Ok(document
+        .root_element()
+        .children()
+        .filter(|node| node.has_tag_name("entry"))
+        .filter_map(parse_entry)
+        .collect())
+}
+
+fn parse_entry(entry: Node) -> Option<UkLawResult> {
+    let url = child_text(entry, "id")
+        .and_then(|id| canonical_url(&id))
+        .or_else(|| {
+            entry
+                .children()
+                .find(|node| node.has_tag_name("link") && node.attribute("rel") == Some("self"))
+                .and_then(|link| link.attribute("href"))
+                .and_then(canonical_url)
+        })?;
+    let main_type = metadata(entry, "DocumentMainType").and_then(|node| node.attribute("Value"));
+
+    Some(UkLawResult {
+        title: child_text(entry, "title")?,
+        year: metadata(entry, "Year")
+            .and_then(|node| node.attribute("Value"))
+            .map(str::to_string),
+        legislation_type: main_type.map_or_else(|| "Unknown".to_string(), legislation_kind),
+        is_in_force: !entry.descendants().any(|node| {
+            node.tag_name().namespace() == Some(METADATA_NAMESPACE)
+                && node.attribute("Status").is_some_and(|status| {
+                    status.eq_ignore_ascii_case("repealed")
+                        || status.eq_ignore_ascii_case("revoked")
+                })
+        }),
+        url,
+        summary: child_text(entry, "summary"),
+        last_updated: child_text(entry, "updated"),
+    })
+}
+
+/// `Primary` for Acts and Measures, `Secondary` for instruments, orders and rules, and the
+/// feed's own `DocumentMainType` for anything else.
+fn legislation_kind(main_type: &str) -> String {
+    if main_type.ends_with("Act") || main_type.ends_with("Measure") {
+        "Primary".to_string()
+    } else if ["Instrument", "Order", "Rule"]
+        .iter()
+        .any(|kind| main_type.contains(kind))
+    {
+        "Secondary".to_string()
+    } else {
+        main_type.to_string()
+    }
+}
+
+/// The document's page for an entry's identifier, e.g.
+/// `http://www.legislation.gov.uk/id/ukpga/2008/27` as
+/// `https://www.legislation.gov.uk/ukpga/2008/27`.
+fn canonical_url(id: &str) -> Option<String> {
+    let address = id
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let path = address
+        .strip_prefix(LEGISLATION_HOST)
+        .or_else(|| address.strip_prefix(LEGISLATION_HOST.trim_start_matches("www.")))?
+        .trim_matches('/');
+    let path = path.strip_prefix("id/").unwrap_or(path);
+    (!path.is_empty()).then(|| format!("https://{LEGISLATION_HOST}/{path}"))
+}
+
+fn metadata<'a, 'input>(entry: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    entry.descendants().find(|node| {
+        node.tag_name().namespace() == Some(METADATA_NAMESPACE) && node.has_tag_name(name)
+    })
+}
+
+fn child_text(node: Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name(tag))
+        .and_then(|child| child.text())
+        .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|text| !text.is_empty())
+}
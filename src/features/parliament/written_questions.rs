@@ -0,0 +1,56 @@
+//! The Written Questions and Statements API's question search, behind
+//! `parliament.fetch_written_questions` and the written questions in
+//! `parliament.fetch_mp_activity`.
+
+use serde_json::Value;
+
+use crate::features::parliament::dto::WrittenQuestion;
+use crate::features::parliament::helpers::{json_day, json_items, json_text, plain_excerpt};
+
+/// Longest question or answer text kept; answers with tables can run to pages.
+const MAX_TEXT_CHARS: usize = 4000;
+
+/// Questions in a `writtenquestions/questions` response. Items come wrapped in a
+/// `{value, links}` envelope; those without an id or tabled date are skipped.
+pub fn parse_questions(payload: &Value) -> Vec<WrittenQuestion> {
+    json_items(payload, "results")
+        .filter_map(|item| {
+            let question = item.get("value").unwrap_or(item);
+            let id = question.get("id")?.as_u64()?;
+            let date_tabled = json_day(question, "dateTabled")?;
+            let uin = json_text(question, "uin");
+            let asking_member_name = question
+                .get("askingMember")
+                .and_then(|member| json_text(member, "name"));
+            Some(WrittenQuestion {
+                url: uin.as_ref().map(|uin| {
+                    format!(
+                        "https://questions-statements.parliament.uk/written-questions/detail/{date_tabled}/{uin}"
+                    )
+                }),
+                id,
+                uin,
+                heading: json_text(question, "heading"),
+                question_text: plain_excerpt(
+                    &json_text(question, "questionText").unwrap_or_default(),
+                    MAX_TEXT_CHARS,
+                ),
+                date_tabled,
+                asking_member_id: question
+                    .get("askingMemberId")
+                    .and_then(Value::as_u64)
+                    .and_then(|id| u32::try_from(id).ok()),
+                asking_member_name,
+                answering_body: json_text(question, "answeringBodyName"),
+                answer_text: json_text(question, "answerText")
+                    .map(|answer| plain_excerpt(&answer, MAX_TEXT_CHARS)),
+                date_answered: json_day(question, "dateAnswered"),
+            })
+        })
+        .collect()
+}
+
+/// `totalResults` of a search response.
+pub fn total_results(payload: &Value) -> Option<u64> {
+    payload.get("totalResults").and_then(Value::as_u64)
+}
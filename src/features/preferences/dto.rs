@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use crate::core::preferences::KeyPreferences;
+
+#[derive(Debug, Deserialize)]
+pub struct SetKeyPreferencesArgs {
+    /// `MCP_API_KEY` or `ADMIN_API_KEY`; other values are rejected.
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    /// Replaces any stored preferences; an empty object clears them.
+    pub preferences: KeyPreferences,
+}
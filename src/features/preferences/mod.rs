@@ -0,0 +1,3 @@
+pub mod dto;
+
+pub use dto::SetKeyPreferencesArgs;
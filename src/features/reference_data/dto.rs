@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RefreshReferenceDataArgs {
+    /// Datasets to refresh; all of them when omitted.
+    #[serde(default)]
+    pub datasets: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RefreshReport {
+    pub datasets: Vec<DatasetRefresh>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RefreshStatus {
+    /// A new copy was validated and written to the data directory.
+    Updated,
+    /// The manifest lists the copy already in use.
+    Unchanged,
+    /// The download or its validation failed; the copy in use is untouched.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetRefresh {
+    pub name: String,
+    pub status: RefreshStatus,
+    /// SHA-256 of the copy in use before the refresh.
+    pub previous_sha256: String,
+    pub previous_rows: usize,
+    /// SHA-256 of the downloaded copy, once it has been checked against the manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -0,0 +1,5 @@
+pub mod dto;
+pub mod store;
+
+pub use dto::{DatasetRefresh, RefreshReferenceDataArgs, RefreshReport, RefreshStatus};
+pub use store::{DataSource, REFERENCE_DATASETS, ReferenceDataStore, ReferenceDataset};
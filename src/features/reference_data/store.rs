@@ -0,0 +1,296 @@
+//! Reference datasets compiled into the server, and the newer copies of them that
+//! `admin.refresh_reference_data` downloads into `REFERENCE_DATA_DIR`. A valid copy in the
+//! data directory takes precedence over the compiled-in default.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use crate::core::error::AppError;
+use crate::core::http_client::redact_url;
+use crate::features::parliament::policy_areas::{BUILTIN_POLICY_AREAS, PolicyAreaClassifier};
+use crate::features::reference_data::dto::{DatasetRefresh, RefreshReport, RefreshStatus};
+
+/// Largest manifest or dataset download accepted.
+const MAX_DOWNLOAD_BYTES: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceDataset {
+    pub name: &'static str,
+    /// File the downloaded copy is kept in, inside the data directory.
+    pub file_name: &'static str,
+    pub builtin: &'static str,
+    /// Fewest rows a usable copy has; anything shorter is treated as truncated.
+    pub min_rows: usize,
+    /// Parse a copy, returning its row count or why it cannot be used.
+    pub validate: fn(&str) -> Result<usize, AppError>,
+}
+
+pub const REFERENCE_DATASETS: &[ReferenceDataset] = &[ReferenceDataset {
+    name: "policy_areas",
+    file_name: "policy_areas.json",
+    builtin: BUILTIN_POLICY_AREAS,
+    min_rows: 1,
+    validate: |contents| Ok(PolicyAreaClassifier::from_json(contents)?.areas().len()),
+}];
+
+/// Where the copy of a dataset in use came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataSource {
+    /// Downloaded into the data directory.
+    Runtime,
+    /// Compiled into the server.
+    Builtin,
+}
+
+/// The checksum manifest published next to the canonical datasets. Dataset URLs may be
+/// relative to the manifest's own URL.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    datasets: HashMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    url: String,
+    sha256: String,
+    #[serde(default)]
+    rows: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceDataStore {
+    dir: Option<PathBuf>,
+    /// Held for the whole of a refresh, so overlapping ones cannot interleave writes.
+    refreshing: Arc<Mutex<()>>,
+}
+
+impl ReferenceDataStore {
+    /// A store reading downloaded copies from `dir`; with `None` only the compiled-in
+    /// defaults are used and refreshing is unavailable.
+    pub fn new(dir: Option<&str>) -> Self {
+        Self {
+            dir: dir.map(PathBuf::from),
+            refreshing: Arc::default(),
+        }
+    }
+
+    pub fn dataset(name: &str) -> Option<&'static ReferenceDataset> {
+        REFERENCE_DATASETS
+            .iter()
+            .find(|dataset| dataset.name == name)
+    }
+
+    /// The copy of `dataset` in use: the data directory's when it is present and valid,
+    /// otherwise the compiled-in default.
+    pub fn load(&self, dataset: &ReferenceDataset) -> (Cow<'static, str>, DataSource) {
+        let Some(path) = self.dir.as_ref().map(|dir| dir.join(dataset.file_name)) else {
+            return (Cow::Borrowed(dataset.builtin), DataSource::Builtin);
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => match (dataset.validate)(&contents) {
+                Ok(_) => return (Cow::Owned(contents), DataSource::Runtime),
+                Err(error) => tracing::warn!(
+                    path = %path.display(),
+                    %error,
+                    "invalid reference data; using the compiled-in copy"
+                ),
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => tracing::warn!(
+                path = %path.display(),
+                %error,
+                "cannot read reference data; using the compiled-in copy"
+            ),
+        }
+        (Cow::Borrowed(dataset.builtin), DataSource::Builtin)
+    }
+
+    /// Download the manifest at `manifest_url` and every dataset in `names` (all of them
+    /// when `None`) it lists, replacing the copy in use with each one that matches its
+    /// checksum and passes validation. A dataset that fails is reported and left as it was.
+    pub async fn refresh(
+        &self,
+        http: &Client,
+        manifest_url: &str,
+        names: Option<&[String]>,
+    ) -> Result<RefreshReport, AppError> {
+        let dir = self
+            .dir
+            .as_deref()
+            .ok_or_else(|| AppError::configuration("REFERENCE_DATA_DIR is not set"))?;
+        let datasets = match names {
+            None => REFERENCE_DATASETS.iter().collect::<Vec<_>>(),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    Self::dataset(name).ok_or_else(|| {
+                        AppError::bad_request(format!("unknown reference dataset: {name}"))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        let manifest_url = Url::parse(manifest_url).map_err(|err| {
+            AppError::configuration(format!("invalid REFERENCE_DATA_MANIFEST_URL: {err}"))
+        })?;
+
+        let _refreshing = self.refreshing.lock().await;
+        let manifest = download(http, &manifest_url).await?;
+        let manifest: Manifest = serde_json::from_slice(&manifest).map_err(|err| {
+            AppError::upstream_with_data(
+                format!("invalid reference data manifest: {err}"),
+                json!({"url": redact_url(manifest_url.as_str())}),
+            )
+        })?;
+
+        let mut report = RefreshReport {
+            datasets: Vec::with_capacity(datasets.len()),
+        };
+        for dataset in datasets {
+            let (current, _) = self.load(dataset);
+            let previous_sha256 = sha256_hex(current.as_bytes());
+            let mut refresh = DatasetRefresh {
+                name: dataset.name.to_string(),
+                status: RefreshStatus::Failed,
+                previous_rows: (dataset.validate)(&current).unwrap_or_default(),
+                previous_sha256,
+                sha256: None,
+                rows: None,
+                error: None,
+            };
+
+            match fetch_verified(http, &manifest_url, &manifest, dataset).await {
+                Ok((contents, sha256, rows)) => {
+                    refresh.status = if sha256 == refresh.previous_sha256 {
+                        RefreshStatus::Unchanged
+                    } else {
+                        match write_atomically(dir, dataset.file_name, contents.as_bytes()) {
+                            Ok(()) => RefreshStatus::Updated,
+                            Err(error) => {
+                                refresh.error = Some(format!("failed to write: {error}"));
+                                RefreshStatus::Failed
+                            }
+                        }
+                    };
+                    refresh.sha256 = Some(sha256);
+                    refresh.rows = Some(rows);
+                }
+                Err(error) => refresh.error = Some(error),
+            }
+
+            match refresh.status {
+                RefreshStatus::Updated => tracing::info!(
+                    dataset = dataset.name,
+                    rows = refresh.rows,
+                    "refreshed reference data"
+                ),
+                RefreshStatus::Unchanged => {}
+                RefreshStatus::Failed => tracing::warn!(
+                    dataset = dataset.name,
+                    error = refresh.error.as_deref().unwrap_or_default(),
+                    "reference data refresh failed; keeping the current copy"
+                ),
+            }
+            report.datasets.push(refresh);
+        }
+        Ok(report)
+    }
+}
+
+/// Download `dataset` as listed in `manifest` and check it: contents, checksum and row
+/// count in that order of trust. Errors are reported per dataset, so they are plain text.
+async fn fetch_verified(
+    http: &Client,
+    manifest_url: &Url,
+    manifest: &Manifest,
+    dataset: &ReferenceDataset,
+) -> Result<(String, String, usize), String> {
+    let entry = manifest
+        .datasets
+        .get(dataset.name)
+        .ok_or_else(|| "not listed in the manifest".to_string())?;
+    let url = manifest_url
+        .join(&entry.url)
+        .map_err(|err| format!("invalid url {:?}: {err}", entry.url))?;
+    let bytes = download(http, &url)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    let sha256 = sha256_hex(&bytes);
+    if !sha256.eq_ignore_ascii_case(entry.sha256.trim()) {
+        return Err(format!(
+            "checksum mismatch: the manifest lists {}, the download is {sha256}",
+            entry.sha256.trim()
+        ));
+    }
+    let contents = String::from_utf8(bytes).map_err(|_| "not valid UTF-8".to_string())?;
+    let rows = (dataset.validate)(&contents).map_err(|error| error.to_string())?;
+    if rows < dataset.min_rows {
+        return Err(format!(
+            "{rows} rows; at least {} expected",
+            dataset.min_rows
+        ));
+    }
+    if let Some(expected) = entry.rows.filter(|expected| *expected != rows) {
+        return Err(format!("{rows} rows; the manifest lists {expected}"));
+    }
+    Ok((contents, sha256, rows))
+}
+
+async fn download(http: &Client, url: &Url) -> Result<Vec<u8>, AppError> {
+    let failed = |message: String| {
+        AppError::upstream_with_data(message, json!({"url": redact_url(url.as_str())}))
+    };
+    let response = http
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|err| failed(format!("failed to download reference data: {err}")))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(failed(format!(
+            "reference data download returned status {status}"
+        )));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|err| failed(format!("failed to read reference data: {err}")))?;
+    if bytes.len() > MAX_DOWNLOAD_BYTES {
+        return Err(failed(format!(
+            "reference data download exceeds {MAX_DOWNLOAD_BYTES} bytes"
+        )));
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Replace `dir/file_name` with `contents` by writing a temporary file beside it and
+/// renaming it into place, so readers see either the old copy or the new one in full.
+fn write_atomically(dir: &Path, file_name: &str, contents: &[u8]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let temp = dir.join(format!(".{file_name}.tmp"));
+    let written = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, dir.join(file_name)));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    written
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
@@ -1,22 +1,81 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::core::bounds;
+use crate::features::parliament::DivisionOutcome;
+use crate::features::parliament::divisions::PartyVotes;
+use crate::features::research::handler::MAX_LIST_CACHED_LIMIT;
+
+/// Largest `limit` a `research.run` call may ask for.
+pub const MAX_RESULT_LIMIT: usize = 10;
+/// Default `limit` of `research.suggest_keywords`, and the most suggestions it returns.
+pub const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+pub const MAX_SUGGESTION_LIMIT: usize = 25;
+
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ResearchRequestDto {
     pub topic: String,
     #[serde(default)]
     pub bill_keywords: Vec<String>,
     #[serde(default)]
     pub debate_keywords: Vec<String>,
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
     pub mp_id: Option<u32>,
     #[serde(default)]
     pub include_state_of_parties: bool,
-    #[serde(default)]
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1, { MAX_RESULT_LIMIT as i64 }>"
+    )]
     pub limit: Option<usize>,
+    /// Treat cached results older than this many seconds as a miss.
+    #[serde(
+        default,
+        rename = "maxAgeSecs",
+        deserialize_with = "bounds::optional::<_, _, 1, { i64::MAX }>"
+    )]
+    pub max_age_secs: Option<u64>,
+    /// Use only cached briefs and upstream responses; uncached collections are skipped.
+    #[serde(default, rename = "cacheOnly")]
+    pub cache_only: bool,
+    /// Language tag for the generated summary and advisories, e.g. `cy`.
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+/// Overall outcome of a research run, derived from the per-source collections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResearchStatus {
+    /// Data was found and no source failed.
+    #[default]
+    Ok,
+    /// Data was found but at least one source failed or, on a `cacheOnly` run, was skipped.
+    Partial,
+    /// No data was found, although at least one source responded.
+    Empty,
+    /// Every source failed.
+    Failed,
+}
+
+impl ResearchStatus {
+    /// Worse than `partial`: nothing usable came back.
+    pub fn is_degraded(self) -> bool {
+        matches!(self, Self::Empty | Self::Failed)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ResearchResponseDto {
     pub summary: String,
+    #[serde(default)]
+    pub status: ResearchStatus,
     pub bills: Vec<BillSummaryDto>,
     pub debates: Vec<DebateSummaryDto>,
     pub legislation: Vec<LegislationSummaryDto>,
@@ -27,17 +86,156 @@ pub struct ResearchResponseDto {
     pub advisories: Vec<String>,
     #[serde(default)]
     pub cached: bool,
+    /// When the served cache entry was stored (RFC 3339); only set when `cached`.
+    #[serde(rename = "cachedAt", default, skip_serializing_if = "Option::is_none")]
+    pub cached_at: Option<String>,
+    #[serde(rename = "ageSecs", default, skip_serializing_if = "Option::is_none")]
+    pub age_secs: Option<u64>,
+    /// Set when an expired cache entry is served because live sources are degraded.
+    #[serde(default)]
+    pub stale: bool,
+    #[serde(default)]
+    pub provenance: ResearchProvenance,
+    /// Set when the run stopped searching because it reached its `TOOL_BYTE_BUDGETS` entry.
+    #[serde(rename = "truncatedByBudget", default)]
+    pub truncated_by_budget: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListCachedResearchArgs {
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1, { MAX_LIST_CACHED_LIMIT as i64 }>"
+    )]
+    pub limit: Option<usize>,
+    /// `nextCursor` of the previous page.
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// `research.list_cached`: one page of stored briefs, in storage order.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListCachedResearchResult {
+    pub entries: Vec<CachedResearchSummary>,
+    /// Pass as `cursor` for the next page; absent on the last page.
+    #[serde(
+        rename = "nextCursor",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CachedResearchSummary {
+    pub topic: String,
+    #[serde(rename = "storedAt")]
+    pub stored_at: String,
+    pub status: ResearchStatus,
+    pub summary: String,
+    /// Source URLs of the brief's bills, debates, legislation, votes and speeches.
+    pub citations: Vec<String>,
+    /// Only the summary and citations remain; the brief is rebuilt on the next request.
+    pub compacted: bool,
+    #[serde(
+        rename = "compactedAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub compacted_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestKeywordsArgs {
+    pub topic: String,
+    #[serde(
+        default,
+        deserialize_with = "bounds::optional::<_, _, 1, { MAX_SUGGESTION_LIMIT as i64 }>"
+    )]
+    pub limit: Option<usize>,
+}
+
+/// `research.suggest_keywords`: phrases from recent parliamentary titles near a topic.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KeywordSuggestionsResult {
+    pub topic: String,
+    /// Best first; each `keyword` can be passed as-is in `billKeywords` or `debateKeywords`.
+    pub suggestions: Vec<KeywordSuggestion>,
+    /// Served from the suggestion cache rather than fresh searches.
+    pub cached: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct KeywordSuggestion {
+    pub keyword: String,
+    /// Titles containing the phrase, weighted by its length in words.
+    pub score: u32,
+    /// Distinct titles containing the phrase.
+    pub occurrences: u32,
+    /// The first title the phrase was found in.
+    pub evidence: KeywordEvidence,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct KeywordEvidence {
+    pub source: EvidenceSource,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EvidenceSource {
+    Bill,
+    Debate,
+    Edm,
+}
+
+/// How the brief was assembled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ResearchProvenance {
+    /// Per collection: every term tried, in order, then any left over the term budget.
+    #[serde(rename = "searchTermsUsed", default)]
+    pub search_terms_used: BTreeMap<String, Vec<SearchTermReport>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SearchTermReport {
+    pub term: String,
+    pub outcome: SearchTermOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchTermOutcome {
+    /// The search returned results, ending the collection.
+    Hit,
+    Empty,
+    /// Every attempt for the term failed.
+    Error,
+    /// Not tried: beyond `RESEARCH_MAX_TERMS`.
+    SkippedOverBudget,
+    /// Not tried: nothing was cached for the term and the run was `cacheOnly`.
+    NotCached,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct BillSummaryDto {
     pub title: String,
+    /// Full stage name, expanded from the Bills API abbreviation where known.
     pub stage: Option<String>,
+    /// The stage abbreviation (e.g. `2R`) when the stage is in the dictionary.
+    #[serde(rename = "stageCode", default)]
+    pub stage_code: Option<String>,
+    /// Plain-English description of what the stage means.
+    #[serde(rename = "stageExplanation", default)]
+    pub stage_explanation: Option<String>,
     pub last_update: Option<String>,
     pub link: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DebateSummaryDto {
     pub title: String,
     pub house: Option<String>,
@@ -46,7 +244,7 @@ pub struct DebateSummaryDto {
     pub highlight: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct LegislationSummaryDto {
     pub title: String,
     pub year: Option<String>,
@@ -55,7 +253,7 @@ pub struct LegislationSummaryDto {
     pub uri: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct VoteSummaryDto {
     pub division_number: Option<String>,
     pub title: String,
@@ -63,10 +261,19 @@ pub struct VoteSummaryDto {
     pub ayes: Option<i64>,
     pub noes: Option<i64>,
     pub result: Option<String>,
+    /// Difference between the ayes and the noes, when both are known.
+    #[serde(default)]
+    pub margin: Option<i64>,
+    #[serde(default)]
+    pub outcome: DivisionOutcome,
     pub link: Option<String>,
+    /// Lobby counts by party, kept only until `outcome` is judged.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub parties: Vec<PartyVotes>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct SpeechSummaryDto {
     pub member_name: Option<String>,
     pub date: Option<String>,
@@ -74,14 +281,14 @@ pub struct SpeechSummaryDto {
     pub source: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct StateOfPartiesDto {
     pub total_seats: Option<i64>,
     pub last_updated: Option<String>,
     pub parties: Vec<PartyBreakdownDto>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct PartyBreakdownDto {
     pub name: String,
     pub seats: Option<i64>,
@@ -1,10 +1,38 @@
 use crate::core::error::AppError;
 use crate::features::research::ResearchService;
-use crate::features::research::dto::{ResearchRequestDto, ResearchResponseDto};
+use crate::features::research::dto::{
+    DEFAULT_SUGGESTION_LIMIT, KeywordSuggestionsResult, ListCachedResearchArgs,
+    ListCachedResearchResult, ResearchRequestDto, ResearchResponseDto, SuggestKeywordsArgs,
+};
+use crate::features::research::service::ResearchRunContext;
 
 pub async fn handle_run_research(
     service: &ResearchService,
     request: ResearchRequestDto,
+    context: ResearchRunContext<'_>,
 ) -> Result<ResearchResponseDto, AppError> {
-    service.run_research(request).await
+    service.run_research_with_context(request, context).await
+}
+
+/// Default `limit` of `research.list_cached`, and the most entries one page returns.
+pub const DEFAULT_LIST_CACHED_LIMIT: usize = 50;
+pub const MAX_LIST_CACHED_LIMIT: usize = 500;
+
+pub async fn handle_list_cached_research(
+    service: &ResearchService,
+    args: ListCachedResearchArgs,
+) -> Result<ListCachedResearchResult, AppError> {
+    let limit = args
+        .limit
+        .unwrap_or(DEFAULT_LIST_CACHED_LIMIT)
+        .clamp(1, MAX_LIST_CACHED_LIMIT);
+    service.list_cached(limit, args.cursor.as_deref()).await
+}
+
+pub async fn handle_suggest_keywords(
+    service: &ResearchService,
+    args: SuggestKeywordsArgs,
+) -> Result<KeywordSuggestionsResult, AppError> {
+    let limit = args.limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT);
+    service.suggest_keywords(&args.topic, limit).await
 }
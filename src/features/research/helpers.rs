@@ -1,14 +1,36 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
 use serde_json::Value;
 
+use crate::core::locale::Locale;
+use crate::features::parliament::describe_bill_stage;
+use crate::features::parliament::divisions::{classify_outcome, party_votes};
+use crate::features::research::messages::{Collection, Phrase};
+
 use crate::features::research::dto::{
-    BillSummaryDto, DebateSummaryDto, LegislationSummaryDto, PartyBreakdownDto, ResearchRequestDto,
-    ResearchResponseDto, StateOfPartiesDto, VoteSummaryDto,
+    BillSummaryDto, DebateSummaryDto, EvidenceSource, KeywordEvidence, KeywordSuggestion,
+    LegislationSummaryDto, MAX_RESULT_LIMIT, PartyBreakdownDto, ResearchRequestDto,
+    ResearchResponseDto, ResearchStatus, SearchTermOutcome, SearchTermReport, StateOfPartiesDto,
+    VoteSummaryDto,
 };
 
+/// How a single research collection (bills, votes, ...) fared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SourceState {
+    Found,
+    NoMatches,
+    Failed,
+    /// Skipped by a `cacheOnly` run because nothing was cached.
+    NotCached,
+}
+
+pub(super) struct SourceReport {
+    pub collection: Collection,
+    pub state: SourceState,
+}
+
 pub(super) const DEFAULT_RESULT_LIMIT: usize = 5;
-pub(super) const MAX_RESULT_LIMIT: usize = 10;
 
 pub(super) fn coerce_limit(limit: Option<usize>) -> usize {
     limit
@@ -58,6 +80,72 @@ pub(super) fn expand_search_terms(keyword: &str) -> Vec<String> {
     terms
 }
 
+/// The search terms a collection may try, shared by every collection searching the same
+/// keywords. Explicit keywords (including the topic) come first, then their expansions in
+/// keyword order; anything past `max_terms` is reported as skipped rather than searched.
+pub(super) struct TermBudget {
+    keywords: Vec<String>,
+    terms: Vec<String>,
+    skipped: Vec<String>,
+}
+
+impl TermBudget {
+    pub(super) fn plan(keywords: &[String], max_terms: usize) -> Self {
+        let expansions: Vec<Vec<String>> = keywords
+            .iter()
+            .map(|keyword| expand_search_terms(keyword))
+            .collect();
+
+        let mut ordered: Vec<String> = Vec::new();
+        for explicit in [true, false] {
+            for term in expansions.iter().flatten() {
+                if keywords.contains(term) == explicit && !ordered.contains(term) {
+                    ordered.push(term.clone());
+                }
+            }
+        }
+
+        let skipped = ordered.split_off(ordered.len().min(max_terms.max(1)));
+        Self {
+            keywords: keywords.to_vec(),
+            terms: ordered,
+            skipped,
+        }
+    }
+
+    pub(super) fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Explicit keywords that made it into the budget, for "no matches" advisories.
+    pub(super) fn keywords(&self) -> impl Iterator<Item = &String> {
+        self.terms
+            .iter()
+            .filter(|term| self.keywords.contains(term))
+    }
+
+    pub(super) fn is_expansion(&self, term: &str) -> bool {
+        !self.keywords.iter().any(|keyword| keyword == term)
+    }
+
+    /// Append the over-budget terms to the report of terms actually tried.
+    pub(super) fn report(&self, mut attempted: Vec<SearchTermReport>) -> Vec<SearchTermReport> {
+        attempted.extend(
+            self.skipped
+                .iter()
+                .map(|term| term_report(term, SearchTermOutcome::SkippedOverBudget)),
+        );
+        attempted
+    }
+}
+
+pub(super) fn term_report(term: &str, outcome: SearchTermOutcome) -> SearchTermReport {
+    SearchTermReport {
+        term: term.to_string(),
+        outcome,
+    }
+}
+
 fn push_unique(terms: &mut Vec<String>, value: &str) {
     let candidate = value.trim().to_lowercase();
     if candidate.len() < 3 {
@@ -68,7 +156,8 @@ fn push_unique(terms: &mut Vec<String>, value: &str) {
     }
 }
 
-pub(super) fn build_cache_key(request: &ResearchRequestDto) -> String {
+/// Briefs are cached per locale; English keys keep the format they had before locales.
+pub(super) fn build_cache_key(request: &ResearchRequestDto, locale: Locale) -> String {
     let mut bill_keywords = request
         .bill_keywords
         .iter()
@@ -97,7 +186,7 @@ pub(super) fn build_cache_key(request: &ResearchRequestDto) -> String {
         .collect::<Vec<_>>();
     debate_keywords.sort();
 
-    format!(
+    let mut key = format!(
         "topic:{}|bills:{}|debates:{}|mp:{}|state:{}|limit:{}",
         request.topic.trim().to_lowercase(),
         bill_keywords.join(","),
@@ -108,39 +197,46 @@ pub(super) fn build_cache_key(request: &ResearchRequestDto) -> String {
             .unwrap_or_else(|| "none".to_string()),
         request.include_state_of_parties,
         request.limit.unwrap_or(DEFAULT_RESULT_LIMIT)
-    )
-}
-
-pub(super) fn now_timestamp() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_secs())
-        .unwrap_or(0)
+    );
+    if locale != Locale::En {
+        key.push_str(&format!("|locale:{locale}"));
+    }
+    key
 }
 
 pub(super) fn parse_bill_results(value: &Value, limit: usize) -> Vec<BillSummaryDto> {
     let mut results = Vec::new();
-    if let Some(items) = locate_array(value, &["items", "results", "bills"]) {
+    if let Some(items) = KeyIndex::new(value).array(&["items", "results", "bills"]) {
         for item in items.iter() {
-            let title = first_string(item, &["title", "shortTitle", "name"]).unwrap_or_else(|| {
-                first_string(item, &["billName", "officialTitle"])
-                    .unwrap_or_else(|| "Unknown bill".to_string())
-            });
-
-            let stage = find_value(item, "billStage")
-                .and_then(|stage| first_string(stage, &["description", "name"]))
-                .or_else(|| first_string(item, &["stage", "currentStage"]));
-
-            let last_update = first_string(item, &["lastUpdate", "lastUpdated", "updated"]);
-
-            let link = find_value(item, "billId")
+            let item = KeyIndex::new(item);
+            let title = item
+                .string(&["title", "shortTitle", "name"])
+                .unwrap_or_else(|| {
+                    item.string(&["billName", "officialTitle"])
+                        .unwrap_or_else(|| "Unknown bill".to_string())
+                });
+
+            let stage = item
+                .get("billStage")
+                .and_then(|stage| {
+                    KeyIndex::new(stage).string(&["description", "name", "abbreviation"])
+                })
+                .or_else(|| item.string(&["stage", "currentStage"]))
+                .map(|raw| describe_bill_stage(&raw));
+
+            let last_update = item.string(&["lastUpdate", "lastUpdated", "updated"]);
+
+            let link = item
+                .get("billId")
                 .and_then(|id| id.as_i64())
                 .map(|id| format!("https://bills.parliament.uk/bills/{id}"))
-                .or_else(|| first_string(item, &["link", "uri", "url"]));
+                .or_else(|| item.string(&["link", "uri", "url"]));
 
             results.push(BillSummaryDto {
                 title,
-                stage,
+                stage_code: stage.as_ref().and_then(|stage| stage.code.clone()),
+                stage_explanation: stage.as_ref().and_then(|stage| stage.explanation.clone()),
+                stage: stage.map(|stage| stage.name),
                 last_update,
                 link,
             });
@@ -156,13 +252,15 @@ pub(super) fn parse_bill_results(value: &Value, limit: usize) -> Vec<BillSummary
 
 pub(super) fn parse_legislation_results(value: &Value, limit: usize) -> Vec<LegislationSummaryDto> {
     let mut results = Vec::new();
-    if let Some(items) = locate_array(value, &["legislation", "results", "items"]) {
+    if let Some(items) = KeyIndex::new(value).array(&["legislation", "results", "items"]) {
         for item in items.iter() {
-            let title = first_string(item, &["title", "name", "titleXml"])
+            let item = KeyIndex::new(item);
+            let title = item
+                .string(&["title", "name", "titleXml"])
                 .unwrap_or_else(|| "Legislation".to_string());
-            let year = first_string(item, &["year", "Year"]);
-            let legislation_type = first_string(item, &["type", "Type", "legislationType"]);
-            let uri = first_string(item, &["uri", "URI", "_about"]);
+            let year = item.string(&["year", "Year"]);
+            let legislation_type = item.string(&["type", "Type", "legislationType"]);
+            let uri = item.string(&["uri", "URI", "_about"]);
 
             results.push(LegislationSummaryDto {
                 title,
@@ -182,16 +280,20 @@ pub(super) fn parse_legislation_results(value: &Value, limit: usize) -> Vec<Legi
 
 pub(super) fn parse_vote_results(value: &Value, limit: usize) -> Vec<VoteSummaryDto> {
     let mut results = Vec::new();
-    if let Some(items) = locate_array(value, &["items", "results", "votes"]) {
-        for item in items.iter() {
-            let title = first_string(item, &["title", "Title", "motion"])
+    if let Some(items) = KeyIndex::new(value).array(&["items", "results", "votes"]) {
+        for raw in items.iter() {
+            let item = KeyIndex::new(raw);
+            let title = item
+                .string(&["title", "Title", "motion"])
                 .unwrap_or_else(|| "Division".to_string());
-            let division_number = first_string(item, &["divisionNumber", "DivisionNumber"]);
-            let date = first_string(item, &["date", "Date"]);
-            let result_value = first_string(item, &["result", "Result"]);
-            let ayes = first_integer(item, &["ayes", "Ayes", "ayesCount"]);
-            let noes = first_integer(item, &["noes", "Noes", "noesCount"]);
-            let link = first_string(item, &["uri", "_about", "link"]);
+            let division_number = item.string(&["divisionNumber", "DivisionNumber"]);
+            let date = item.string(&["date", "Date"]);
+            let result_value = item.string(&["result", "Result"]);
+            let ayes = item.integer(&["ayes", "Ayes", "ayesCount"]);
+            let noes = item.integer(&["noes", "Noes", "noesCount"]);
+            let link = item.string(&["uri", "_about", "link"]);
+            let margin = ayes.zip(noes).map(|(ayes, noes)| (ayes - noes).abs());
+            let parties = party_votes(raw, &["Ayes", "ayes"], &["Noes", "noes"]);
 
             results.push(VoteSummaryDto {
                 division_number,
@@ -200,7 +302,10 @@ pub(super) fn parse_vote_results(value: &Value, limit: usize) -> Vec<VoteSummary
                 ayes,
                 noes,
                 result: result_value,
+                margin,
+                outcome: Default::default(),
                 link,
+                parties,
             });
 
             if results.len() >= limit {
@@ -214,15 +319,18 @@ pub(super) fn parse_vote_results(value: &Value, limit: usize) -> Vec<VoteSummary
 
 pub(super) fn parse_debate_results(value: &Value, limit: usize) -> Vec<DebateSummaryDto> {
     let mut results = Vec::new();
-    if let Some(items) = locate_array(value, &["items", "results", "debates"]) {
+    if let Some(items) = KeyIndex::new(value).array(&["items", "results", "debates"]) {
         for item in items.iter() {
-            let title = first_string(item, &["title", "Title", "subject"])
+            let item = KeyIndex::new(item);
+            let title = item
+                .string(&["title", "Title", "subject"])
                 .unwrap_or_else(|| "Debate".to_string());
-            let house = first_string(item, &["house", "House"]);
-            let date = first_string(item, &["date", "Date"]);
-            let link = first_string(item, &["uri", "_about", "link"]);
-            let highlight = first_string(item, &["summary", "Synopsis", "description"])
-                .or_else(|| first_string(item, &["excerpt"]))
+            let house = item.string(&["house", "House"]);
+            let date = item.string(&["date", "Date"]);
+            let link = item.string(&["uri", "_about", "link"]);
+            let highlight = item
+                .string(&["summary", "Synopsis", "description"])
+                .or_else(|| item.string(&["excerpt"]))
                 .map(truncate_summary);
 
             results.push(DebateSummaryDto {
@@ -243,12 +351,15 @@ pub(super) fn parse_debate_results(value: &Value, limit: usize) -> Vec<DebateSum
 }
 
 pub(super) fn parse_state_of_parties(value: &Value) -> Option<StateOfPartiesDto> {
+    let root = KeyIndex::new(value);
     let mut parties = Vec::new();
-    if let Some(items) = locate_array(value, &["items", "results", "parties"]) {
+    if let Some(items) = root.array(&["items", "results", "parties"]) {
         for item in items.iter() {
-            let name = first_string(item, &["party", "name", "Party"])
+            let item = KeyIndex::new(item);
+            let name = item
+                .string(&["party", "name", "Party"])
                 .unwrap_or_else(|| "Unknown".to_string());
-            let seats = first_integer(item, &["seats", "Seats", "memberCount"]);
+            let seats = item.integer(&["seats", "Seats", "memberCount"]);
 
             parties.push(PartyBreakdownDto { name, seats });
         }
@@ -258,8 +369,8 @@ pub(super) fn parse_state_of_parties(value: &Value) -> Option<StateOfPartiesDto>
         return None;
     }
 
-    let total_seats = first_integer(value, &["totalSeats", "TotalSeats", "total"]);
-    let last_updated = first_string(value, &["lastUpdated", "LastUpdated", "date"]);
+    let total_seats = root.integer(&["totalSeats", "TotalSeats", "total"]);
+    let last_updated = root.string(&["lastUpdated", "LastUpdated", "date"]);
 
     Some(StateOfPartiesDto {
         total_seats,
@@ -268,79 +379,384 @@ pub(super) fn parse_state_of_parties(value: &Value) -> Option<StateOfPartiesDto>
     })
 }
 
-pub(super) fn compose_summary(
+/// Judge each division against the party holding a majority of Commons seats. Without
+/// one (no state of parties, or a hung parliament) every outcome stays `unknown`.
+pub(super) fn classify_votes(votes: &mut [VoteSummaryDto], state: Option<&StateOfPartiesDto>) {
+    let governing_party = state.and_then(|state| {
+        let total = state.total_seats?;
+        state
+            .parties
+            .iter()
+            .find(|party| party.seats.is_some_and(|seats| seats * 2 > total))
+            .map(|party| party.name.as_str())
+    });
+
+    for vote in votes {
+        let counts = vote
+            .ayes
+            .and_then(|ayes| u64::try_from(ayes).ok())
+            .zip(vote.noes.and_then(|noes| u64::try_from(noes).ok()));
+        if let Some((ayes, noes)) = counts {
+            vote.outcome = classify_outcome(ayes, noes, governing_party, &vote.parties);
+        }
+    }
+}
+
+/// The brief's summary: a heading, one bullet per source that found something and up to
+/// three of `advisories`. A pure function of its arguments.
+pub fn compose_summary(
     topic: &str,
     response: &ResearchResponseDto,
     advisories: &[String],
+    locale: Locale,
 ) -> String {
     let mut segments = Vec::new();
 
     if let Some(bill) = response.bills.first() {
-        let mut detail = bill.title.clone();
-        if let Some(stage) = &bill.stage {
-            detail.push_str(&format!(" (current stage: {stage})"));
-        }
-        segments.push(format!("Priority bill: {detail}"));
+        segments.push(Phrase::PriorityBill {
+            title: &bill.title,
+            stage: bill.stage.as_deref(),
+        });
     }
 
     if let Some(legislation) = response.legislation.first() {
-        segments.push(format!(
-            "Relevant legislation: {}{}",
-            legislation.title,
-            legislation
-                .year
-                .as_ref()
-                .map(|value| format!(" ({value})"))
-                .unwrap_or_default()
-        ));
+        segments.push(Phrase::RelevantLegislation {
+            title: &legislation.title,
+            year: legislation.year.as_deref(),
+        });
     }
 
     if let Some(vote) = response.votes.first() {
-        let mut detail = vote.title.clone();
-        if let Some(result) = &vote.result {
-            detail.push_str(&format!(" ({result})"));
-        }
-        segments.push(format!("Recent division: {detail}"));
+        segments.push(Phrase::RecentDivision {
+            title: &vote.title,
+            result: vote.result.as_deref(),
+        });
     }
 
     if let Some(debate) = response.debates.first() {
-        let mut detail = debate.title.clone();
-        if let Some(date) = &debate.date {
-            detail.push_str(&format!(" ({date})"));
-        }
-        segments.push(format!("Debate highlight: {detail}"));
+        segments.push(Phrase::DebateHighlight {
+            title: &debate.title,
+            date: debate.date.as_deref(),
+        });
     }
 
     if let Some(state) = &response.state_of_parties {
-        if !state.parties.is_empty() {
-            let top_party = &state.parties[0];
-            segments.push(format!(
-                "House balance: {} holding {:?} seats",
-                top_party.name, top_party.seats
-            ));
+        if let Some(top_party) = state.parties.first() {
+            segments.push(Phrase::HouseBalance {
+                party: &top_party.name,
+                seats: top_party.seats,
+            });
         }
     }
 
     if segments.is_empty() {
-        segments.push(
-            "No authoritative parliamentary sources were retrieved; consider broadening the topic keywords.".to_string()
-        );
+        segments.push(Phrase::NoSources);
     }
 
     for note in advisories.iter().take(3) {
-        segments.push(format!("Note: {note}"));
+        segments.push(Phrase::Note(note));
     }
 
-    let mut summary = format!("Key research findings on \"{}\":", topic.trim());
+    let mut summary = Phrase::KeyFindings {
+        topic: topic.trim(),
+    }
+    .render(locale);
     for segment in segments {
         summary.push('\n');
         summary.push_str("- ");
-        summary.push_str(&segment);
+        summary.push_str(&segment.render(locale));
     }
 
     summary
 }
 
+pub(super) fn classify_status(sources: &[SourceReport]) -> ResearchStatus {
+    let found = sources
+        .iter()
+        .any(|source| source.state == SourceState::Found);
+    let failed = sources
+        .iter()
+        .filter(|source| source.state == SourceState::Failed)
+        .count();
+    let skipped = sources
+        .iter()
+        .any(|source| source.state == SourceState::NotCached);
+
+    match (found, failed) {
+        (true, 0) if !skipped => ResearchStatus::Ok,
+        (true, _) => ResearchStatus::Partial,
+        (false, failed) if failed == sources.len() && failed > 0 => ResearchStatus::Failed,
+        (false, _) => ResearchStatus::Empty,
+    }
+}
+
+/// Summary used when nothing was retrieved: says which sources failed versus matched
+/// nothing and what the user can do about it, instead of a list of raw advisories.
+pub(super) fn compose_unavailable_summary(
+    topic: &str,
+    sources: &[SourceReport],
+    locale: Locale,
+) -> String {
+    let labels = |state: SourceState| {
+        sources
+            .iter()
+            .filter(|source| source.state == state)
+            .map(|source| source.collection.label(locale))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let failed = labels(SourceState::Failed);
+    let no_matches = labels(SourceState::NoMatches);
+    let not_cached = labels(SourceState::NotCached);
+
+    let mut lines = vec![
+        Phrase::Unavailable {
+            topic: topic.trim(),
+        }
+        .render(locale),
+    ];
+    let mut bullet = |phrase: Phrase| lines.push(format!("- {}", phrase.render(locale)));
+    if !failed.is_empty() {
+        bullet(Phrase::SourcesFailed(&failed));
+    }
+    if !no_matches.is_empty() {
+        bullet(Phrase::SourcesWithoutMatches(&no_matches));
+    }
+    if !not_cached.is_empty() {
+        bullet(Phrase::SourcesNotCached(&not_cached));
+    }
+
+    lines.push(Phrase::NextSteps.render(locale));
+    let mut bullet = |phrase: Phrase| lines.push(format!("- {}", phrase.render(locale)));
+    if !failed.is_empty() {
+        bullet(Phrase::RetryLater);
+    }
+    if !no_matches.is_empty() {
+        bullet(Phrase::BroadenKeywords);
+        bullet(Phrase::CheckSpelling);
+    }
+    if !not_cached.is_empty() {
+        bullet(Phrase::RunWithoutCacheOnly);
+    }
+
+    lines.join("\n")
+}
+
+/// Titled items of a bill, debate or EDM search, as evidence for keyword suggestions.
+/// Items without a title are skipped rather than given a placeholder.
+pub(super) fn parse_evidence(
+    value: &Value,
+    source: EvidenceSource,
+    limit: usize,
+) -> Vec<KeywordEvidence> {
+    let root = KeyIndex::new(value);
+    let Some(items) = root.array(&["items", "results", "bills", "debates", "edms"]) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let item = KeyIndex::new(item);
+            let title = item.string(&["title", "shortTitle", "Title", "motionTitle", "subject"])?;
+            let link = item
+                .get("billId")
+                .and_then(Value::as_i64)
+                .map(|id| format!("https://bills.parliament.uk/bills/{id}"))
+                .or_else(|| item.string(&["uri", "_about", "link", "url"]));
+            Some(KeywordEvidence {
+                source,
+                title,
+                link,
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Longest phrase, in words, a keyword suggestion may have.
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Words that never start, end or sit inside a suggested phrase: English function words
+/// and the boilerplate of parliamentary titles ("Bill", "[HL]", "Second Reading", ...).
+const STOP_WORDS: &[&str] = &[
+    "a",
+    "about",
+    "act",
+    "after",
+    "against",
+    "all",
+    "amendment",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "before",
+    "between",
+    "bill",
+    "by",
+    "call",
+    "calls",
+    "committee",
+    "day",
+    "debate",
+    "during",
+    "edm",
+    "for",
+    "from",
+    "government",
+    "has",
+    "have",
+    "hl",
+    "house",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "motion",
+    "new",
+    "no",
+    "not",
+    "of",
+    "on",
+    "or",
+    "other",
+    "over",
+    "reading",
+    "report",
+    "second",
+    "stage",
+    "than",
+    "that",
+    "the",
+    "their",
+    "third",
+    "this",
+    "to",
+    "under",
+    "uk",
+    "united",
+    "kingdom",
+    "up",
+    "upon",
+    "via",
+    "with",
+    "within",
+    "without",
+];
+
+/// Rank phrases from `titles` as keyword suggestions for `topic`.
+///
+/// Each title is lower-cased and split into runs of words between stop words, numbers and
+/// punctuation; every phrase of up to three consecutive words in a run is a candidate.
+/// A phrase scores the number of distinct titles containing it times its length in words,
+/// so a specific phrase seen twice beats a lone word seen three times. Phrases equal to
+/// the topic, single words of the topic and phrases no more common than a longer phrase
+/// containing them are dropped. Ties are broken alphabetically; each suggestion cites the
+/// first title, in `titles` order, that contained it.
+pub fn extract_keyword_suggestions(
+    topic: &str,
+    titles: &[KeywordEvidence],
+    limit: usize,
+) -> Vec<KeywordSuggestion> {
+    let topic_words = phrase_words(topic);
+    let topic_phrase = topic_words.join(" ");
+
+    // Phrase -> (titles containing it, index of the first such title).
+    let mut counts: HashMap<String, (u32, usize)> = HashMap::new();
+    for (index, evidence) in titles.iter().enumerate() {
+        let mut seen = Vec::new();
+        for run in title_runs(&evidence.title) {
+            for length in 1..=MAX_PHRASE_WORDS.min(run.len()) {
+                for window in run.windows(length) {
+                    let phrase = window.join(" ");
+                    if !seen.contains(&phrase) {
+                        seen.push(phrase);
+                    }
+                }
+            }
+        }
+        for phrase in seen {
+            counts
+                .entry(phrase)
+                .and_modify(|(count, _)| *count += 1)
+                .or_insert((1, index));
+        }
+    }
+
+    let mut ranked: Vec<KeywordSuggestion> = counts
+        .iter()
+        .filter(|(phrase, _)| {
+            **phrase != topic_phrase && !topic_words.iter().any(|word| word == *phrase)
+        })
+        .filter(|(phrase, (count, _))| {
+            !counts.iter().any(|(longer, (longer_count, _))| {
+                longer_count >= count
+                    && longer.len() > phrase.len()
+                    && contains_phrase(longer, phrase)
+            })
+        })
+        .map(|(phrase, (count, first))| KeywordSuggestion {
+            keyword: phrase.clone(),
+            score: count * phrase.split(' ').count() as u32,
+            occurrences: *count,
+            evidence: titles[*first].clone(),
+        })
+        .collect();
+
+    ranked.sort_by(|left, right| {
+        right
+            .score
+            .cmp(&left.score)
+            .then_with(|| left.keyword.cmp(&right.keyword))
+    });
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Lower-cased words of `text`, split on anything that is not a letter, digit or
+/// apostrophe (possessive `'s` is dropped).
+fn phrase_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|ch: char| !(ch.is_alphanumeric() || ch == '\''))
+        .map(|word| word.trim_matches('\''))
+        .map(|word| word.strip_suffix("'s").unwrap_or(word))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Runs of content words in a title, broken at stop words, numbers, single letters and
+/// punctuation that separates clauses.
+fn title_runs(title: &str) -> Vec<Vec<String>> {
+    let mut runs = Vec::new();
+    for clause in title.split(['(', ')', '[', ']', ':', ';', ',', '.', '?', '!', '–', '—']) {
+        let mut run: Vec<String> = Vec::new();
+        for word in phrase_words(clause) {
+            let content = word.chars().count() > 1
+                && !word.chars().all(|ch| ch.is_ascii_digit())
+                && !STOP_WORDS.contains(&word.as_str());
+            if content {
+                run.push(word);
+            } else if !run.is_empty() {
+                runs.push(std::mem::take(&mut run));
+            }
+        }
+        if !run.is_empty() {
+            runs.push(run);
+        }
+    }
+    runs
+}
+
+/// Whether `phrase` appears in `longer` as whole words.
+fn contains_phrase(longer: &str, phrase: &str) -> bool {
+    format!(" {longer} ").contains(&format!(" {phrase} "))
+}
+
 fn truncate_summary(value: String) -> String {
     const MAX_LEN: usize = 220;
     if value.len() <= MAX_LEN {
@@ -352,56 +768,98 @@ fn truncate_summary(value: String) -> String {
     truncated
 }
 
-fn locate_array<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a Vec<Value>> {
-    for key in keys {
-        if let Some(array) = find_value(value, key).and_then(|inner| inner.as_array()) {
-            if !array.is_empty() {
-                return Some(array);
+/// Deepest chain of `text`/`value`/`description` wrapper objects a string probe unwraps.
+const MAX_PROBE_DEPTH: usize = 4;
+const TEXT_KEYS: &[&str] = &["text", "value", "description"];
+
+/// One object's keys folded to ASCII lowercase in a single pass, so every alias probed on
+/// an item is a hash lookup rather than another scan of its keys. Where keys differ only in
+/// case the first in map order wins, as with the scan this replaced.
+struct KeyIndex<'a> {
+    entries: HashMap<FoldedKey<'a>, &'a Value>,
+}
+
+impl<'a> KeyIndex<'a> {
+    fn new(value: &'a Value) -> Self {
+        let mut entries = HashMap::new();
+        if let Some(object) = value.as_object() {
+            entries.reserve(object.len());
+            for (key, entry) in object {
+                entries.entry(FoldedKey(key)).or_insert(entry);
             }
         }
+        Self { entries }
+    }
+
+    fn get(&self, key: &'static str) -> Option<&'a Value> {
+        self.entries.get(&FoldedKey(key)).copied()
+    }
+
+    /// The first non-empty array under any of `keys`.
+    fn array(&self, keys: &[&'static str]) -> Option<&'a Vec<Value>> {
+        keys.iter()
+            .filter_map(|key| self.get(key).and_then(Value::as_array))
+            .find(|array| !array.is_empty())
     }
-    None
-}
 
-fn first_string(value: &Value, keys: &[&str]) -> Option<String> {
-    for key in keys {
-        if let Some(entry) = find_value(value, key) {
+    /// The first non-blank string under any of `keys`, trimmed. An object in the way is
+    /// searched for `text`, `value` or `description`, at most `MAX_PROBE_DEPTH` levels down.
+    fn string(&self, keys: &[&'static str]) -> Option<String> {
+        self.string_within(keys, 0)
+    }
+
+    fn string_within(&self, keys: &[&'static str], depth: usize) -> Option<String> {
+        for key in keys {
+            let Some(entry) = self.get(key) else {
+                continue;
+            };
             if let Some(text) = entry.as_str() {
                 let trimmed = text.trim();
                 if !trimmed.is_empty() {
                     return Some(trimmed.to_string());
                 }
-            } else if entry.is_object() {
-                if let Some(text) = first_string(entry, &["text", "value", "description"]) {
+            } else if entry.is_object() && depth < MAX_PROBE_DEPTH {
+                if let Some(text) = KeyIndex::new(entry).string_within(TEXT_KEYS, depth + 1) {
                     return Some(text);
                 }
             }
         }
+        None
+    }
+
+    /// The first integer, or string holding one, under any of `keys`.
+    fn integer(&self, keys: &[&'static str]) -> Option<i64> {
+        keys.iter()
+            .filter_map(|key| self.get(key))
+            .find_map(|entry| {
+                entry
+                    .as_i64()
+                    .or_else(|| entry.as_str().and_then(|text| text.trim().parse().ok()))
+            })
     }
-    None
 }
 
-fn first_integer(value: &Value, keys: &[&str]) -> Option<i64> {
-    for key in keys {
-        if let Some(entry) = find_value(value, key) {
-            if let Some(number) = entry.as_i64() {
-                return Some(number);
-            }
-            if let Some(text) = entry.as_str() {
-                if let Ok(parsed) = text.trim().parse::<i64>() {
-                    return Some(parsed);
-                }
-            }
-        }
+/// A key that hashes and compares ignoring ASCII case, without allocating a lowercase copy.
+#[derive(Clone, Copy)]
+struct FoldedKey<'a>(&'a str);
+
+impl PartialEq for FoldedKey<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(other.0)
     }
-    None
 }
 
-fn find_value<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
-    value.as_object().and_then(|object| {
-        object
-            .iter()
-            .find(|(candidate, _)| candidate.eq_ignore_ascii_case(key))
-            .map(|(_, v)| v)
-    })
+impl Eq for FoldedKey<'_> {}
+
+impl Hash for FoldedKey<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut folded = [0u8; 32];
+        for chunk in self.0.as_bytes().chunks(folded.len()) {
+            let folded = &mut folded[..chunk.len()];
+            folded.copy_from_slice(chunk);
+            folded.make_ascii_lowercase();
+            state.write(folded);
+        }
+        state.write_u8(0xff);
+    }
 }
@@ -0,0 +1,342 @@
+//! The research brief's generated prose in every [`Locale`]. Messages are typed values
+//! rendered at the end of a run, so each translation can only use the arguments its
+//! message carries and nothing is left unfilled.
+
+use crate::core::locale::Locale;
+
+/// One of the sources a research run collects from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Collection {
+    Bills,
+    Votes,
+    Legislation,
+    Debates,
+    StateOfParties,
+}
+
+impl Collection {
+    /// Lower-case name used in lists of sources.
+    pub(super) fn label(self, locale: Locale) -> &'static str {
+        match (locale, self) {
+            (Locale::En, Self::Bills) => "bills",
+            (Locale::En, Self::Votes) => "votes",
+            (Locale::En, Self::Legislation) => "legislation",
+            (Locale::En, Self::Debates) => "debates",
+            (Locale::En, Self::StateOfParties) => "state of parties",
+            (Locale::Cy, Self::Bills) => "biliau",
+            (Locale::Cy, Self::Votes) => "pleidleisiau",
+            (Locale::Cy, Self::Legislation) => "deddfwriaeth",
+            (Locale::Cy, Self::Debates) => "dadleuon",
+            (Locale::Cy, Self::StateOfParties) => "cyflwr y pleidiau",
+        }
+    }
+}
+
+/// A note attached to a research brief.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Advisory {
+    BudgetExhausted,
+    /// A `cacheOnly` run found nothing cached for the collection.
+    NotCached(Collection),
+    /// The collection was skipped because its upstream host is marked down.
+    HostUnavailable(Collection),
+    Broadened {
+        collection: Collection,
+        term: String,
+    },
+    LookupFailed {
+        collection: Collection,
+        term: String,
+        error: String,
+    },
+    NoKeywordMatch {
+        collection: Collection,
+        keyword: String,
+    },
+    NoData(Collection),
+    StateOfPartiesUnavailable,
+    /// An older cached brief is served because live sources are degraded.
+    ServedStale {
+        every_source_failed: bool,
+        cached_at: Option<String>,
+    },
+    /// The request asked for a locale the catalogue lacks; always rendered in English.
+    UnsupportedLocale(String),
+}
+
+impl Advisory {
+    pub(super) fn render(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.english(),
+            Locale::Cy => self.welsh(),
+        }
+    }
+
+    fn english(&self) -> String {
+        use Collection::*;
+        match self {
+            Self::BudgetExhausted => "Research stopped early after reaching its upstream byte budget; remaining search terms were skipped.".to_string(),
+            Self::NotCached(collection) => {
+                let label = match collection {
+                    Bills => "Bills",
+                    Votes => "Commons divisions",
+                    Legislation => "Legislation searches",
+                    Debates => "Commons debates",
+                    StateOfParties => "State of parties figures",
+                };
+                format!("{label} were skipped: nothing is cached for this topic and the run was cacheOnly.")
+            }
+            Self::HostUnavailable(collection) => {
+                let label = match collection {
+                    Bills => "Bills",
+                    Votes => "Commons divisions",
+                    Legislation => "Legislation searches",
+                    Debates => "Commons debates",
+                    StateOfParties => "State of parties figures",
+                };
+                format!("{label} were skipped: the service that provides them has been failing and is marked unavailable. Try again in a few minutes.")
+            }
+            Self::Broadened { collection, term } => {
+                let (search, initial) = match collection {
+                    Bills => ("Bills", "query"),
+                    Votes => ("Division", "keyword"),
+                    Legislation => ("Legislation", "keyword"),
+                    Debates | StateOfParties => ("Debate", "keyword"),
+                };
+                format!("{search} search broadened to \"{term}\" after the initial {initial} returned no results.")
+            }
+            Self::LookupFailed { collection, term, error } => {
+                let lookup = match collection {
+                    Bills => "Bills",
+                    Votes => "Division",
+                    Legislation => "Legislation",
+                    Debates | StateOfParties => "Debate",
+                };
+                format!("{lookup} lookup for \"{term}\" failed: {error}")
+            }
+            Self::NoKeywordMatch { collection, keyword } => match collection {
+                Bills => format!("No bills matched the keyword \"{keyword}\"; try alternative or broader keywords."),
+                Votes => format!("No Commons divisions matched the keyword \"{keyword}\"; consider broader vote terms."),
+                Legislation => format!("No legislation matched the keyword \"{keyword}\"; try alternate titles or verify the act year."),
+                Debates | StateOfParties => format!("No Commons debates matched the keyword \"{keyword}\"; try broader debate topics or different dates."),
+            },
+            Self::NoData(collection) => match collection {
+                Bills => "Bills service returned no data for this topic.",
+                Votes => "No Commons divisions were retrieved for this topic.",
+                Legislation => "Legislation search produced no matches for this topic.",
+                Debates | StateOfParties => "Debate search returned no results for this topic.",
+            }
+            .to_string(),
+            Self::StateOfPartiesUnavailable => "State of parties data is temporarily unavailable; seat counts were omitted.".to_string(),
+            Self::ServedStale { every_source_failed, cached_at } => {
+                let fresh_status = if *every_source_failed {
+                    "every source failed"
+                } else {
+                    "no source returned data"
+                };
+                let stored = cached_at.as_deref().unwrap_or("an earlier run");
+                format!("Live sources are degraded ({fresh_status}), so this is an older cached result from {stored}. A background refresh has been scheduled.")
+            }
+            Self::UnsupportedLocale(requested) => format!(
+                "Locale \"{requested}\" is not supported (use one of: {}); this brief is in English.",
+                Locale::supported_list()
+            ),
+        }
+    }
+
+    fn welsh(&self) -> String {
+        use Collection::*;
+        match self {
+            Self::BudgetExhausted => "Daeth yr ymchwil i ben yn gynnar ar ôl cyrraedd ei chyllideb beitiau; hepgorwyd y termau chwilio oedd yn weddill.".to_string(),
+            Self::NotCached(collection) => {
+                let label = match collection {
+                    Bills => "biliau",
+                    Votes => "rhaniadau Tŷ'r Cyffredin",
+                    Legislation => "chwiliadau deddfwriaeth",
+                    Debates => "dadleuon Tŷ'r Cyffredin",
+                    StateOfParties => "ffigurau cyflwr y pleidiau",
+                };
+                format!("Hepgorwyd {label}: nid oes dim yn y storfa ar gyfer y pwnc hwn a rhedwyd gyda cacheOnly.")
+            }
+            Self::HostUnavailable(collection) => {
+                let label = match collection {
+                    Bills => "biliau",
+                    Votes => "rhaniadau Tŷ'r Cyffredin",
+                    Legislation => "chwiliadau deddfwriaeth",
+                    Debates => "dadleuon Tŷ'r Cyffredin",
+                    StateOfParties => "ffigurau cyflwr y pleidiau",
+                };
+                format!("Hepgorwyd {label}: mae'r gwasanaeth sy'n eu darparu wedi bod yn methu ac wedi'i nodi'n anghyraeddadwy. Rhowch gynnig arall arni ymhen ychydig funudau.")
+            }
+            Self::Broadened { collection, term } => format!(
+                "Ehangwyd y chwiliad {} i \"{term}\" gan na chafwyd canlyniadau o'r chwiliad cyntaf.",
+                search_noun(*collection)
+            ),
+            Self::LookupFailed { collection, term, error } => format!(
+                "Methodd y chwiliad {} am \"{term}\": {error}",
+                search_noun(*collection)
+            ),
+            Self::NoKeywordMatch { collection, keyword } => match collection {
+                Bills => format!("Nid oedd unrhyw filiau'n cyfateb i'r allweddair \"{keyword}\"; rhowch gynnig ar allweddeiriau eraill neu ehangach."),
+                Votes => format!("Nid oedd unrhyw raniadau yn Nhŷ'r Cyffredin yn cyfateb i'r allweddair \"{keyword}\"; ystyriwch dermau pleidleisio ehangach."),
+                Legislation => format!("Nid oedd unrhyw ddeddfwriaeth yn cyfateb i'r allweddair \"{keyword}\"; rhowch gynnig ar deitlau eraill neu gwiriwch flwyddyn y ddeddf."),
+                Debates | StateOfParties => format!("Nid oedd unrhyw ddadleuon yn Nhŷ'r Cyffredin yn cyfateb i'r allweddair \"{keyword}\"; rhowch gynnig ar bynciau dadl ehangach neu ddyddiadau gwahanol."),
+            },
+            Self::NoData(collection) => match collection {
+                Bills => "Ni ddychwelodd y gwasanaeth biliau unrhyw ddata ar gyfer y pwnc hwn.",
+                Votes => "Ni chafwyd unrhyw raniadau yn Nhŷ'r Cyffredin ar gyfer y pwnc hwn.",
+                Legislation => "Ni chafwyd unrhyw ddeddfwriaeth gyfatebol ar gyfer y pwnc hwn.",
+                Debates | StateOfParties => "Ni ddychwelodd y chwiliad dadleuon unrhyw ganlyniadau ar gyfer y pwnc hwn.",
+            }
+            .to_string(),
+            Self::StateOfPartiesUnavailable => "Nid yw data cyflwr y pleidiau ar gael dros dro; hepgorwyd nifer y seddi.".to_string(),
+            Self::ServedStale { every_source_failed, cached_at } => {
+                let fresh_status = if *every_source_failed {
+                    "methodd pob ffynhonnell"
+                } else {
+                    "ni ddychwelodd unrhyw ffynhonnell ddata"
+                };
+                let stored = cached_at.as_deref().unwrap_or("rhediad cynharach");
+                format!("Mae'r ffynonellau byw yn ddiraddiedig ({fresh_status}), felly dyma ganlyniad hŷn o'r storfa o {stored}. Mae adnewyddiad yn y cefndir wedi'i drefnu.")
+            }
+            Self::UnsupportedLocale(_) => self.english(),
+        }
+    }
+}
+
+/// "y chwiliad biliau", "y chwiliad rhaniadau", ...
+fn search_noun(collection: Collection) -> &'static str {
+    match collection {
+        Collection::Bills => "biliau",
+        Collection::Votes => "rhaniadau",
+        Collection::Legislation => "deddfwriaeth",
+        Collection::Debates | Collection::StateOfParties => "dadleuon",
+    }
+}
+
+/// The framing text of the brief's `summary`.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Phrase<'a> {
+    KeyFindings {
+        topic: &'a str,
+    },
+    PriorityBill {
+        title: &'a str,
+        stage: Option<&'a str>,
+    },
+    RelevantLegislation {
+        title: &'a str,
+        year: Option<&'a str>,
+    },
+    RecentDivision {
+        title: &'a str,
+        result: Option<&'a str>,
+    },
+    DebateHighlight {
+        title: &'a str,
+        date: Option<&'a str>,
+    },
+    HouseBalance {
+        party: &'a str,
+        seats: Option<i64>,
+    },
+    NoSources,
+    Note(&'a str),
+    Unavailable {
+        topic: &'a str,
+    },
+    SourcesFailed(&'a str),
+    SourcesWithoutMatches(&'a str),
+    SourcesNotCached(&'a str),
+    NextSteps,
+    RetryLater,
+    BroadenKeywords,
+    CheckSpelling,
+    RunWithoutCacheOnly,
+}
+
+impl Phrase<'_> {
+    pub(super) fn render(self, locale: Locale) -> String {
+        let bracketed = |value: Option<&str>| value.map(|value| format!(" ({value})"));
+        match locale {
+            Locale::En => match self {
+                Self::KeyFindings { topic } => format!("Key research findings on \"{topic}\":"),
+                Self::PriorityBill { title, stage } => format!(
+                    "Priority bill: {title}{}",
+                    stage
+                        .map(|stage| format!(" (current stage: {stage})"))
+                        .unwrap_or_default()
+                ),
+                Self::RelevantLegislation { title, year } => format!(
+                    "Relevant legislation: {title}{}",
+                    bracketed(year).unwrap_or_default()
+                ),
+                Self::RecentDivision { title, result } => format!(
+                    "Recent division: {title}{}",
+                    bracketed(result).unwrap_or_default()
+                ),
+                Self::DebateHighlight { title, date } => format!(
+                    "Debate highlight: {title}{}",
+                    bracketed(date).unwrap_or_default()
+                ),
+                Self::HouseBalance { party, seats } => match seats {
+                    Some(seats) => format!("House balance: {party} holding {seats} seats"),
+                    None => format!("House balance: {party} is the largest party"),
+                },
+                Self::NoSources => "No authoritative parliamentary sources were retrieved; consider broadening the topic keywords.".to_string(),
+                Self::Note(note) => format!("Note: {note}"),
+                Self::Unavailable { topic } => format!("Research unavailable for \"{topic}\":"),
+                Self::SourcesFailed(list) => format!("Sources that failed to respond: {list}"),
+                Self::SourcesWithoutMatches(list) => {
+                    format!("Sources that returned no matches: {list}")
+                }
+                Self::SourcesNotCached(list) => format!("Sources skipped (not cached): {list}"),
+                Self::NextSteps => "Suggested next steps:".to_string(),
+                Self::RetryLater => "Retry in a few minutes; upstream Parliament services are usually only briefly unavailable.".to_string(),
+                Self::BroadenKeywords => "Broaden the keywords or use shorter, more general terms (e.g. \"housing\" rather than a bill title).".to_string(),
+                Self::CheckSpelling => "Check the spelling of the topic and any keywords.".to_string(),
+                Self::RunWithoutCacheOnly => "Run again without cacheOnly to query the live services.".to_string(),
+            },
+            Locale::Cy => match self {
+                Self::KeyFindings { topic } => format!("Prif ganfyddiadau ymchwil ar \"{topic}\":"),
+                Self::PriorityBill { title, stage } => format!(
+                    "Bil blaenoriaeth: {title}{}",
+                    stage
+                        .map(|stage| format!(" (cam presennol: {stage})"))
+                        .unwrap_or_default()
+                ),
+                Self::RelevantLegislation { title, year } => format!(
+                    "Deddfwriaeth berthnasol: {title}{}",
+                    bracketed(year).unwrap_or_default()
+                ),
+                Self::RecentDivision { title, result } => format!(
+                    "Rhaniad diweddar: {title}{}",
+                    bracketed(result).unwrap_or_default()
+                ),
+                Self::DebateHighlight { title, date } => format!(
+                    "Uchafbwynt dadl: {title}{}",
+                    bracketed(date).unwrap_or_default()
+                ),
+                Self::HouseBalance { party, seats } => match seats {
+                    Some(seats) => format!("Cydbwysedd y Tŷ: {party} â {seats} o seddi"),
+                    None => format!("Cydbwysedd y Tŷ: {party} yw'r blaid fwyaf"),
+                },
+                Self::NoSources => "Ni chafwyd unrhyw ffynonellau seneddol awdurdodol; ystyriwch ehangu allweddeiriau'r pwnc.".to_string(),
+                Self::Note(note) => format!("Nodyn: {note}"),
+                Self::Unavailable { topic } => format!("Nid oes ymchwil ar gael ar gyfer \"{topic}\":"),
+                Self::SourcesFailed(list) => format!("Ffynonellau na wnaethant ymateb: {list}"),
+                Self::SourcesWithoutMatches(list) => {
+                    format!("Ffynonellau heb unrhyw gyfatebiaethau: {list}")
+                }
+                Self::SourcesNotCached(list) => {
+                    format!("Ffynonellau a hepgorwyd (dim yn y storfa): {list}")
+                }
+                Self::NextSteps => "Camau nesaf a awgrymir:".to_string(),
+                Self::RetryLater => "Rhowch gynnig arall arni ymhen ychydig funudau; fel arfer dim ond am gyfnod byr y mae gwasanaethau'r Senedd yn anghyraeddadwy.".to_string(),
+                Self::BroadenKeywords => "Ehangwch yr allweddeiriau neu defnyddiwch dermau byrrach, mwy cyffredinol (e.e. \"housing\" yn hytrach na theitl bil).".to_string(),
+                Self::CheckSpelling => "Gwiriwch sillafiad y pwnc ac unrhyw allweddeiriau.".to_string(),
+                Self::RunWithoutCacheOnly => "Rhedwch eto heb cacheOnly i holi'r gwasanaethau byw.".to_string(),
+            },
+        }
+    }
+}
@@ -1,12 +1,17 @@
 pub mod dto;
 pub mod handler;
 mod helpers;
+mod messages;
 pub mod service;
 
 #[allow(unused_imports)]
 pub use dto::{
-    BillSummaryDto, DebateSummaryDto, LegislationSummaryDto, PartyBreakdownDto, ResearchRequestDto,
-    ResearchResponseDto, SpeechSummaryDto, StateOfPartiesDto, VoteSummaryDto,
+    BillSummaryDto, CachedResearchSummary, DebateSummaryDto, EvidenceSource, KeywordEvidence,
+    KeywordSuggestion, KeywordSuggestionsResult, LegislationSummaryDto, ListCachedResearchArgs,
+    ListCachedResearchResult, MAX_RESULT_LIMIT, MAX_SUGGESTION_LIMIT, PartyBreakdownDto,
+    ResearchProvenance, ResearchRequestDto, ResearchResponseDto, ResearchStatus, SearchTermOutcome,
+    SearchTermReport, SpeechSummaryDto, StateOfPartiesDto, SuggestKeywordsArgs, VoteSummaryDto,
 };
-pub use handler::handle_run_research;
-pub use service::{ParliamentDataSource, ResearchService};
+pub use handler::{handle_list_cached_research, handle_run_research, handle_suggest_keywords};
+pub use helpers::{compose_summary, extract_keyword_suggestions};
+pub use service::{CompactionReport, ParliamentDataSource, ResearchRunContext, ResearchService};
@@ -1,57 +1,212 @@
-use std::collections::HashSet;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::ops::Bound;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sled::Tree;
+use sled::{IVec, Tree};
+use tokio::sync::Semaphore;
 use tokio::task;
-use tracing::warn;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
 use crate::config::AppConfig;
+use crate::core::byte_budget::budget_exhausted;
+use crate::core::cache::CacheManager;
+use crate::core::clock::Clock;
 use crate::core::error::AppError;
+use crate::core::locale::Locale;
+use crate::core::metrics::ScanMetrics;
+use crate::core::notifications::ProgressReporter;
+use crate::features::parliament::client::{BILLS_HOST, CORE_DATASET_HOST, LEGISLATION_HOST};
 use crate::features::parliament::{
     FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs, ParliamentClient,
 };
 use crate::features::research::dto::{
-    BillSummaryDto, DebateSummaryDto, LegislationSummaryDto, ResearchRequestDto,
-    ResearchResponseDto, StateOfPartiesDto, VoteSummaryDto,
+    BillSummaryDto, CachedResearchSummary, DebateSummaryDto, EvidenceSource, KeywordSuggestion,
+    KeywordSuggestionsResult, LegislationSummaryDto, ListCachedResearchResult,
+    MAX_SUGGESTION_LIMIT, ResearchProvenance, ResearchRequestDto, ResearchResponseDto,
+    ResearchStatus, SearchTermOutcome, SearchTermReport, StateOfPartiesDto, VoteSummaryDto,
 };
 use crate::features::research::helpers::{
-    DEFAULT_RESULT_LIMIT, build_cache_key, coerce_limit, compose_summary, ensure_keywords,
-    expand_search_terms, now_timestamp, parse_bill_results, parse_debate_results,
-    parse_legislation_results, parse_state_of_parties, parse_vote_results,
+    DEFAULT_RESULT_LIMIT, SourceReport, SourceState, TermBudget, build_cache_key, classify_status,
+    classify_votes, coerce_limit, compose_summary, compose_unavailable_summary, ensure_keywords,
+    extract_keyword_suggestions, parse_bill_results, parse_debate_results, parse_evidence,
+    parse_legislation_results, parse_state_of_parties, parse_vote_results, term_report,
 };
+use crate::features::research::messages::{Advisory, Collection};
+
+/// Entries a cache scan reads per blocking step. Between steps it yields, so a dropped
+/// call (e.g. on a tool timeout) stops the scan within one step.
+const SCAN_BATCH: usize = 256;
+/// Cache scans (listing and compaction) that may hold a blocking thread at once.
+const MAX_CONCURRENT_SCANS: usize = 2;
+/// Titles read from each source when suggesting keywords.
+const SUGGESTION_TITLES: usize = 20;
+/// Topics whose keyword suggestions are kept in memory.
+const SUGGESTION_CACHE_CAPACITY: u64 = 512;
 
 #[derive(Serialize, Deserialize)]
 struct CachedResearchEntry {
+    /// Wall-clock write time, for display.
     stored_at: u64,
-    payload: ResearchResponseDto,
+    /// [`Clock::steady_secs`] at write time; freshness and compaction age are measured
+    /// from this. Entries written before it was recorded fall back to `stored_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    steady_at: Option<u64>,
+    /// The request's topic as typed; entries stored before it was recorded fall back to
+    /// the lower-cased topic in the cache key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(flatten)]
+    body: CachedResearchBody,
+}
+
+impl CachedResearchEntry {
+    fn stamp(&self) -> u64 {
+        self.steady_at.unwrap_or(self.stored_at)
+    }
+}
+
+/// When a served cache entry was written and how old it is.
+#[derive(Clone, Copy)]
+struct CacheAge {
+    stored_at: u64,
+    age_secs: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CachedResearchBody {
+    #[serde(rename = "payload")]
+    Full(Box<ResearchResponseDto>),
+    /// Left by compaction: never served, only listed by `research.list_cached`.
+    #[serde(rename = "tombstone")]
+    Tombstone(ResearchTombstone),
+}
+
+/// What compaction keeps of a brief older than `RESEARCH_COMPACT_AFTER_DAYS`.
+#[derive(Serialize, Deserialize)]
+struct ResearchTombstone {
+    summary: String,
+    status: ResearchStatus,
+    citations: Vec<String>,
+    compacted_at: u64,
+}
+
+/// Outcome of one [`ResearchService::compact`] pass.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionReport {
+    pub compacted: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl CompactionReport {
+    pub fn bytes_saved(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
 }
 
 struct CollectionOutcome<T> {
     data: T,
-    advisories: Vec<String>,
+    advisories: Vec<Advisory>,
+    state: SourceState,
+    search_terms: Vec<SearchTermReport>,
+    /// Terms were left untried because the call reached its byte budget.
+    truncated_by_budget: bool,
 }
 
 impl<T> CollectionOutcome<T> {
     fn new(data: T) -> Self {
+        Self::with_advisories(data, Vec::new())
+    }
+
+    fn with_advisories(data: T, advisories: Vec<Advisory>) -> Self {
         Self {
             data,
-            advisories: Vec::new(),
+            advisories,
+            state: SourceState::Found,
+            search_terms: Vec::new(),
+            truncated_by_budget: false,
+        }
+    }
+
+    /// Nothing was collected; `failed` means every attempt errored rather than the
+    /// upstream answering with no matches.
+    fn missing(data: T, advisories: Vec<Advisory>, failed: bool) -> Self {
+        let state = if failed {
+            SourceState::Failed
+        } else {
+            SourceState::NoMatches
+        };
+        Self {
+            data,
+            advisories,
+            state,
+            search_terms: Vec::new(),
+            truncated_by_budget: false,
+        }
+    }
+
+    /// A `cacheOnly` run found nothing cached for any term, so the collection was skipped.
+    fn not_cached(data: T, collection: Collection) -> Self {
+        Self {
+            data,
+            advisories: vec![Advisory::NotCached(collection)],
+            state: SourceState::NotCached,
+            search_terms: Vec::new(),
+            truncated_by_budget: false,
         }
     }
 
-    fn with_advisories(data: T, advisories: Vec<String>) -> Self {
-        Self { data, advisories }
+    /// Skipped without a request because the upstream host serving `collection` is
+    /// marked down.
+    fn unavailable(data: T, collection: Collection) -> Self {
+        Self {
+            data,
+            advisories: vec![Advisory::HostUnavailable(collection)],
+            state: SourceState::Failed,
+            search_terms: Vec::new(),
+            truncated_by_budget: false,
+        }
+    }
+
+    fn with_search_terms(mut self, search_terms: Vec<SearchTermReport>) -> Self {
+        self.search_terms = search_terms;
+        self
     }
+
+    fn with_truncation(mut self, truncated_by_budget: bool) -> Self {
+        self.truncated_by_budget = truncated_by_budget;
+        self
+    }
+}
+
+/// Transport hooks for a single research run: progress reporting and cancellation.
+#[derive(Clone, Copy, Default)]
+pub struct ResearchRunContext<'a> {
+    pub progress: Option<&'a ProgressReporter>,
+    pub cancellation: Option<&'a CancellationToken>,
 }
 
+#[derive(Clone)]
 pub struct ResearchService {
     config: Arc<AppConfig>,
     data_source: Arc<dyn ParliamentDataSource>,
     cache_tree: Tree,
     cache_ttl: u64,
+    /// Cache keys with a background refresh pending.
+    refreshing: Arc<Mutex<HashSet<String>>>,
+    scan_permits: Arc<Semaphore>,
+    scan_metrics: Arc<ScanMetrics>,
+    clock: Clock,
+    /// Full keyword suggestion rankings by lower-cased topic.
+    suggestions: CacheManager,
 }
 
 #[async_trait]
@@ -59,6 +214,12 @@ pub trait ParliamentDataSource: Send + Sync {
     async fn fetch_bills(&self, args: FetchBillsArgs) -> Result<Value, AppError>;
     async fn fetch_core_dataset(&self, args: FetchCoreDatasetArgs) -> Result<Value, AppError>;
     async fn fetch_legislation(&self, args: FetchLegislationArgs) -> Result<Value, AppError>;
+
+    /// Whether `host` has been failing recently enough that requests to it should be
+    /// skipped. Sources without availability tracking never report a host down.
+    fn host_down(&self, _host: &str) -> bool {
+        false
+    }
 }
 
 #[async_trait]
@@ -74,6 +235,19 @@ impl ParliamentDataSource for ParliamentClient {
     async fn fetch_legislation(&self, args: FetchLegislationArgs) -> Result<Value, AppError> {
         ParliamentClient::fetch_legislation(self, args).await
     }
+
+    fn host_down(&self, host: &str) -> bool {
+        ParliamentClient::host_down(self, host)
+    }
+}
+
+/// Upstream host each collection is fetched from.
+fn collection_host(collection: Collection) -> &'static str {
+    match collection {
+        Collection::Bills => BILLS_HOST,
+        Collection::Legislation => LEGISLATION_HOST,
+        Collection::Votes | Collection::Debates | Collection::StateOfParties => CORE_DATASET_HOST,
+    }
 }
 
 impl ResearchService {
@@ -87,33 +261,210 @@ impl ResearchService {
             config,
             data_source,
             cache_tree,
+            refreshing: Arc::new(Mutex::new(HashSet::new())),
+            scan_permits: Arc::new(Semaphore::new(MAX_CONCURRENT_SCANS)),
+            scan_metrics: Arc::new(ScanMetrics::new()),
+            clock: Clock::system(),
+            suggestions: CacheManager::new(true, SUGGESTION_CACHE_CAPACITY),
         }
     }
 
+    /// Measure cache freshness with `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock behind cache freshness and compaction.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// How long listing and compaction scans of the cache tree take.
+    pub fn scan_metrics(&self) -> &ScanMetrics {
+        &self.scan_metrics
+    }
+
     pub async fn run_research(
         &self,
         request: ResearchRequestDto,
+    ) -> Result<ResearchResponseDto, AppError> {
+        self.run_research_with_context(request, ResearchRunContext::default())
+            .await
+    }
+
+    /// Run research, reporting each completed collection through the context's progress
+    /// reporter. Cancelling the context's token drops every in-flight collection, so no
+    /// further upstream calls are made and nothing is cached.
+    ///
+    /// If the fresh result is degraded (`empty` or `failed`) and an expired entry no older
+    /// than `RESEARCH_MAX_STALE_SECS` exists, that entry is served instead, marked
+    /// `stale`, and a background refresh is scheduled.
+    ///
+    /// With `cacheOnly` no upstream call is made: collections with nothing cached are
+    /// skipped with an advisory and the result is not stored.
+    pub async fn run_research_with_context(
+        &self,
+        request: ResearchRequestDto,
+        context: ResearchRunContext<'_>,
     ) -> Result<ResearchResponseDto, AppError> {
         let topic = request.topic.trim();
         if topic.is_empty() {
             return Err(AppError::bad_request("topic must not be empty".to_string()));
         }
 
-        let cache_key = build_cache_key(&request);
-        if let Some(mut cached) = self.try_get_cached(&cache_key).await? {
-            cached.cached = true;
+        let max_age = self.effective_max_age(request.max_age_secs)?;
+        let (locale, unsupported) = self.resolve_locale(&request);
+        let mut response = self
+            .run_in_locale(request, locale, max_age, context)
+            .await?;
+        if let Some(requested) = unsupported {
+            // Not part of the cached brief: it depends on how this request was phrased.
+            response
+                .advisories
+                .insert(0, Advisory::UnsupportedLocale(requested).render(Locale::En));
+        }
+        Ok(response)
+    }
+
+    /// The request's `locale`, or `MCP_DEFAULT_LOCALE` when absent. An unsupported tag
+    /// falls back to English and is returned so the caller can say so.
+    fn resolve_locale(&self, request: &ResearchRequestDto) -> (Locale, Option<String>) {
+        match request.locale.as_deref().map(str::trim) {
+            None | Some("") => (self.config.default_locale, None),
+            Some(tag) => match Locale::parse(tag) {
+                Some(locale) => (locale, None),
+                None => (Locale::En, Some(tag.to_string())),
+            },
+        }
+    }
+
+    async fn run_in_locale(
+        &self,
+        request: ResearchRequestDto,
+        locale: Locale,
+        max_age: u64,
+        context: ResearchRunContext<'_>,
+    ) -> Result<ResearchResponseDto, AppError> {
+        let cache_key = build_cache_key(&request, locale);
+        if let Some((mut cached, age)) = self.try_get_cached(&cache_key, max_age).await? {
+            mark_cached(&mut cached, age);
             return Ok(cached);
         }
 
+        let response = self.compute_research(&request, locale, context).await?;
+
+        // A cacheOnly brief is assembled from whatever happened to be cached, so it neither
+        // falls back to a stale entry nor replaces the stored one.
+        if request.cache_only {
+            return Ok(response);
+        }
+
+        if response.status.is_degraded() {
+            if let Some(stale) = self
+                .try_get_stale(&cache_key, response.status, locale)
+                .await?
+            {
+                self.schedule_refresh(request, locale, cache_key);
+                return Ok(stale);
+            }
+        }
+
+        // A budget-truncated brief would shadow the full one for the whole cache TTL.
+        if !response.truncated_by_budget {
+            self.store_cache(&cache_key, &request.topic, &response)
+                .await?;
+        }
+
+        Ok(response)
+    }
+
+    /// Query every source for `request` without consulting or updating the cache.
+    async fn compute_research(
+        &self,
+        request: &ResearchRequestDto,
+        locale: Locale,
+        context: ResearchRunContext<'_>,
+    ) -> Result<ResearchResponseDto, AppError> {
+        let topic = request.topic.trim();
         let bill_keywords = ensure_keywords(topic, &request.bill_keywords);
         let debate_keywords = ensure_keywords(topic, &request.debate_keywords);
         let limit = coerce_limit(request.limit);
+        let bill_terms = TermBudget::plan(&bill_keywords, self.config.research_max_terms);
+        let debate_terms = TermBudget::plan(&debate_keywords, self.config.research_max_terms);
 
-        let bills_future = self.collect_bills(&bill_keywords, limit);
-        let votes_future = self.collect_votes(&bill_keywords, limit);
-        let legislation_future = self.collect_legislation(&bill_keywords, limit);
-        let debates_future = self.collect_debates(&debate_keywords, limit);
-        let state_future = self.collect_state_of_parties(request.include_state_of_parties);
+        let total_collections = if request.include_state_of_parties {
+            5
+        } else {
+            4
+        };
+        let report = |label: &str| {
+            if let Some(reporter) = context.progress {
+                reporter.advance(total_collections, &format!("{label} collected"));
+            }
+        };
+
+        // Pre-flight: collections whose host is marked down are skipped outright rather
+        // than walking every term through failing retries. cacheOnly runs never go
+        // upstream, so they have nothing to skip.
+        let host_down = |collection: Collection| {
+            !request.cache_only && self.data_source.host_down(collection_host(collection))
+        };
+
+        let bills_future = until_cancelled(context.cancellation, async {
+            let outcome = if host_down(Collection::Bills) {
+                CollectionOutcome::unavailable(Vec::new(), Collection::Bills)
+            } else {
+                self.collect_bills(&bill_terms, limit, request.cache_only)
+                    .await
+            };
+            report("bills");
+            outcome
+        });
+        let votes_future = until_cancelled(context.cancellation, async {
+            let outcome = if host_down(Collection::Votes) {
+                CollectionOutcome::unavailable(Vec::new(), Collection::Votes)
+            } else {
+                self.collect_votes(&bill_terms, limit, request.cache_only)
+                    .await
+            };
+            report("votes");
+            outcome
+        });
+        let legislation_future = until_cancelled(context.cancellation, async {
+            let outcome = if host_down(Collection::Legislation) {
+                CollectionOutcome::unavailable(Vec::new(), Collection::Legislation)
+            } else {
+                self.collect_legislation(&bill_terms, limit, request.cache_only)
+                    .await
+            };
+            report("legislation");
+            outcome
+        });
+        let debates_future = until_cancelled(context.cancellation, async {
+            let outcome = if host_down(Collection::Debates) {
+                CollectionOutcome::unavailable(Vec::new(), Collection::Debates)
+            } else {
+                self.collect_debates(&debate_terms, limit, request.cache_only)
+                    .await
+            };
+            report("debates");
+            outcome
+        });
+        let state_future = until_cancelled(context.cancellation, async {
+            let outcome = if request.include_state_of_parties
+                && host_down(Collection::StateOfParties)
+            {
+                CollectionOutcome::unavailable(None, Collection::StateOfParties)
+            } else {
+                self.collect_state_of_parties(request.include_state_of_parties, request.cache_only)
+                    .await
+            };
+            if request.include_state_of_parties {
+                report("state of parties");
+            }
+            outcome
+        });
 
         let (bills_outcome, votes_outcome, legislation_outcome, debates_outcome, state_outcome) = tokio::join!(
             bills_future,
@@ -123,28 +474,101 @@ impl ResearchService {
             state_future,
         );
 
+        let (
+            Some(bills_outcome),
+            Some(votes_outcome),
+            Some(legislation_outcome),
+            Some(debates_outcome),
+            Some(state_outcome),
+        ) = (
+            bills_outcome,
+            votes_outcome,
+            legislation_outcome,
+            debates_outcome,
+            state_outcome,
+        )
+        else {
+            return Err(AppError::internal("research run cancelled"));
+        };
+
+        let mut sources = vec![
+            SourceReport {
+                collection: Collection::Bills,
+                state: bills_outcome.state,
+            },
+            SourceReport {
+                collection: Collection::Votes,
+                state: votes_outcome.state,
+            },
+            SourceReport {
+                collection: Collection::Legislation,
+                state: legislation_outcome.state,
+            },
+            SourceReport {
+                collection: Collection::Debates,
+                state: debates_outcome.state,
+            },
+        ];
+        if request.include_state_of_parties {
+            sources.push(SourceReport {
+                collection: Collection::StateOfParties,
+                state: state_outcome.state,
+            });
+        }
+        let status = classify_status(&sources);
+        let truncated_by_budget = [
+            bills_outcome.truncated_by_budget,
+            votes_outcome.truncated_by_budget,
+            legislation_outcome.truncated_by_budget,
+            debates_outcome.truncated_by_budget,
+        ]
+        .contains(&true);
+
         let CollectionOutcome {
             data: bills,
             advisories: mut bills_notes,
+            search_terms: bills_terms,
+            ..
         } = bills_outcome;
         let CollectionOutcome {
-            data: votes,
+            data: mut votes,
             advisories: mut votes_notes,
+            search_terms: votes_terms,
+            ..
         } = votes_outcome;
         let CollectionOutcome {
             data: legislation,
             advisories: mut legislation_notes,
+            search_terms: legislation_terms,
+            ..
         } = legislation_outcome;
         let CollectionOutcome {
             data: debates,
             advisories: mut debates_notes,
+            search_terms: debates_terms,
+            ..
         } = debates_outcome;
         let CollectionOutcome {
             data: state_of_parties,
             advisories: mut state_notes,
+            ..
         } = state_outcome;
 
+        classify_votes(&mut votes, state_of_parties.as_ref());
+
+        let provenance = ResearchProvenance {
+            search_terms_used: BTreeMap::from([
+                ("bills".to_string(), bills_terms),
+                ("votes".to_string(), votes_terms),
+                ("legislation".to_string(), legislation_terms),
+                ("debates".to_string(), debates_terms),
+            ]),
+        };
+
         let mut advisories = Vec::new();
+        if truncated_by_budget {
+            advisories.push(Advisory::BudgetExhausted);
+        }
         advisories.append(&mut bills_notes);
         advisories.append(&mut votes_notes);
         advisories.append(&mut legislation_notes);
@@ -158,9 +582,14 @@ impl ResearchService {
                 advisories.truncate(4);
             }
         }
+        let advisories = advisories
+            .iter()
+            .map(|advisory| advisory.render(locale))
+            .collect::<Vec<_>>();
 
         let mut response = ResearchResponseDto {
             summary: String::new(),
+            status,
             bills,
             debates,
             legislation,
@@ -169,247 +598,391 @@ impl ResearchService {
             state_of_parties,
             advisories: Vec::new(),
             cached: false,
+            cached_at: None,
+            age_secs: None,
+            stale: false,
+            provenance,
+            truncated_by_budget,
+        };
+        response.summary = match status {
+            ResearchStatus::Ok | ResearchStatus::Partial => {
+                compose_summary(topic, &response, &advisories, locale)
+            }
+            ResearchStatus::Empty | ResearchStatus::Failed => {
+                compose_unavailable_summary(topic, &sources, locale)
+            }
         };
-        response.summary = compose_summary(topic, &response, &advisories);
         response.advisories = advisories;
 
-        self.store_cache(&cache_key, &response).await?;
-
         Ok(response)
     }
 
     async fn collect_bills(
         &self,
-        keywords: &[String],
+        budget: &TermBudget,
         limit: usize,
+        cache_only: bool,
     ) -> CollectionOutcome<Vec<BillSummaryDto>> {
         let mut advisories = Vec::new();
-
-        for keyword in keywords {
-            if keyword.is_empty() {
-                continue;
+        let mut attempted = Vec::new();
+        let mut responded = false;
+        let mut errored = false;
+        let mut uncached = false;
+        let mut truncated = false;
+
+        for term in budget.terms() {
+            if budget_exhausted() {
+                truncated = true;
+                break;
             }
+            let mut outcome = SearchTermOutcome::Error;
+            for (apply_relevance, threshold, broadened) in [
+                (Some(true), Some(self.config.relevance_threshold), false),
+                (Some(false), Some(0.0_f32), true),
+            ] {
+                let args = FetchBillsArgs {
+                    search_term: Some(term.clone()),
+                    house: None,
+                    session: None,
+                    parliament_number: None,
+                    enable_cache: Some(true),
+                    cache_only: Some(cache_only),
+                    apply_relevance,
+                    relevance_threshold: threshold,
+                    fields: None,
+                };
 
-            let terms = expand_search_terms(keyword);
-            for term in terms.iter() {
-                for (apply_relevance, threshold, broadened) in [
-                    (Some(true), Some(self.config.relevance_threshold), false),
-                    (Some(false), Some(0.0_f32), true),
-                ] {
-                    let args = FetchBillsArgs {
-                        search_term: Some(term.clone()),
-                        house: None,
-                        session: None,
-                        parliament_number: None,
-                        enable_cache: Some(true),
-                        apply_relevance,
-                        relevance_threshold: threshold,
-                    };
-
-                    match self.data_source.fetch_bills(args).await {
-                        Ok(raw) => {
-                            let parsed = parse_bill_results(&raw, limit);
-                            if !parsed.is_empty() {
-                                if broadened || term != keyword {
-                                    advisories.push(format!(
-                                        "Bills search broadened to \"{term}\" after the initial query returned no results."
-                                    ));
-                                }
-                                return CollectionOutcome::with_advisories(parsed, advisories);
+                match self.data_source.fetch_bills(args).await {
+                    Ok(raw) => {
+                        responded = true;
+                        outcome = SearchTermOutcome::Empty;
+                        let parsed = parse_bill_results(&raw, limit);
+                        if !parsed.is_empty() {
+                            if broadened || budget.is_expansion(term) {
+                                info!(target: "research", term, "bills search broadened");
+                                advisories.push(Advisory::Broadened {
+                                    collection: Collection::Bills,
+                                    term: term.clone(),
+                                });
                             }
+                            attempted.push(term_report(term, SearchTermOutcome::Hit));
+                            return CollectionOutcome::with_advisories(parsed, advisories)
+                                .with_search_terms(budget.report(attempted));
                         }
-                        Err(error) => {
-                            warn!(target: "research", %error, term, "failed to fetch bills");
-                            advisories.push(format!("Bills lookup for \"{term}\" failed: {error}"));
+                    }
+                    Err(AppError::NotCached { .. }) => {
+                        uncached = true;
+                        if outcome == SearchTermOutcome::Error {
+                            outcome = SearchTermOutcome::NotCached;
                         }
                     }
+                    Err(error) => {
+                        errored = true;
+                        warn!(target: "research", %error, term, "failed to fetch bills");
+                        advisories.push(Advisory::LookupFailed {
+                            collection: Collection::Bills,
+                            term: term.clone(),
+                            error: error.to_string(),
+                        });
+                    }
                 }
             }
+            attempted.push(term_report(term, outcome));
+        }
+
+        if uncached && !responded && !errored {
+            return CollectionOutcome::not_cached(Vec::new(), Collection::Bills)
+                .with_search_terms(budget.report(attempted))
+                .with_truncation(truncated);
+        }
 
-            advisories.push(format!(
-                "No bills matched the keyword \"{keyword}\"; try alternative or broader keywords."
-            ));
+        for keyword in budget.keywords() {
+            advisories.push(Advisory::NoKeywordMatch {
+                collection: Collection::Bills,
+                keyword: keyword.clone(),
+            });
         }
 
         if advisories.is_empty() {
-            advisories.push("Bills service returned no data for this topic.".to_string());
+            advisories.push(Advisory::NoData(Collection::Bills));
         }
 
-        CollectionOutcome::with_advisories(Vec::new(), advisories)
+        CollectionOutcome::missing(Vec::new(), advisories, errored && !responded)
+            .with_search_terms(budget.report(attempted))
+            .with_truncation(truncated)
     }
 
     async fn collect_votes(
         &self,
-        keywords: &[String],
+        budget: &TermBudget,
         limit: usize,
+        cache_only: bool,
     ) -> CollectionOutcome<Vec<VoteSummaryDto>> {
         let mut advisories = Vec::new();
-
-        for keyword in keywords {
-            if keyword.is_empty() {
-                continue;
+        let mut attempted = Vec::new();
+        let mut responded = false;
+        let mut errored = false;
+        let mut uncached = false;
+        let mut truncated = false;
+
+        for term in budget.terms() {
+            if budget_exhausted() {
+                truncated = true;
+                break;
             }
-
-            for term in expand_search_terms(keyword).iter() {
-                let args = FetchCoreDatasetArgs {
-                    dataset: "commonsdivisions".to_string(),
-                    search_term: Some(term.clone()),
-                    page: Some(0),
-                    per_page: Some(limit as u32),
-                    enable_cache: Some(true),
-                    fuzzy_match: Some(true),
-                    apply_relevance: Some(true),
-                    relevance_threshold: Some(self.config.relevance_threshold),
-                };
-
-                match self.data_source.fetch_core_dataset(args).await {
-                    Ok(raw) => {
-                        let parsed = parse_vote_results(&raw, limit);
-                        if !parsed.is_empty() {
-                            if term != keyword {
-                                advisories.push(format!(
-                                    "Division search broadened to \"{term}\" after the initial keyword returned no results."
-                                ));
-                            }
-                            return CollectionOutcome::with_advisories(parsed, advisories);
+            let args = FetchCoreDatasetArgs {
+                dataset: "commonsdivisions".to_string(),
+                search_term: Some(term.clone()),
+                page: Some(0),
+                per_page: Some(limit as u32),
+                enable_cache: Some(true),
+                cache_only: Some(cache_only),
+                fuzzy_match: Some(true),
+                apply_relevance: Some(true),
+                relevance_threshold: Some(self.config.relevance_threshold),
+                fields: None,
+            };
+
+            match self.data_source.fetch_core_dataset(args).await {
+                Ok(raw) => {
+                    responded = true;
+                    let parsed = parse_vote_results(&raw, limit);
+                    if !parsed.is_empty() {
+                        if budget.is_expansion(term) {
+                            advisories.push(Advisory::Broadened {
+                                collection: Collection::Votes,
+                                term: term.clone(),
+                            });
                         }
+                        attempted.push(term_report(term, SearchTermOutcome::Hit));
+                        return CollectionOutcome::with_advisories(parsed, advisories)
+                            .with_search_terms(budget.report(attempted));
                     }
-                    Err(error) => {
-                        warn!(target: "research", %error, term, "failed to fetch divisions");
-                        advisories.push(format!("Division lookup for \"{term}\" failed: {error}"));
-                    }
+                    attempted.push(term_report(term, SearchTermOutcome::Empty));
+                }
+                Err(AppError::NotCached { .. }) => {
+                    uncached = true;
+                    attempted.push(term_report(term, SearchTermOutcome::NotCached));
+                }
+                Err(error) => {
+                    errored = true;
+                    warn!(target: "research", %error, term, "failed to fetch divisions");
+                    advisories.push(Advisory::LookupFailed {
+                        collection: Collection::Votes,
+                        term: term.clone(),
+                        error: error.to_string(),
+                    });
+                    attempted.push(term_report(term, SearchTermOutcome::Error));
                 }
             }
+        }
 
-            advisories.push(format!(
-                "No Commons divisions matched the keyword \"{keyword}\"; consider broader vote terms."
-            ));
+        if uncached && !responded && !errored {
+            return CollectionOutcome::not_cached(Vec::new(), Collection::Votes)
+                .with_search_terms(budget.report(attempted))
+                .with_truncation(truncated);
+        }
+
+        for keyword in budget.keywords() {
+            advisories.push(Advisory::NoKeywordMatch {
+                collection: Collection::Votes,
+                keyword: keyword.clone(),
+            });
         }
 
         if advisories.is_empty() {
-            advisories.push("No Commons divisions were retrieved for this topic.".to_string());
+            advisories.push(Advisory::NoData(Collection::Votes));
         }
 
-        CollectionOutcome::with_advisories(Vec::new(), advisories)
+        CollectionOutcome::missing(Vec::new(), advisories, errored && !responded)
+            .with_search_terms(budget.report(attempted))
+            .with_truncation(truncated)
     }
 
     async fn collect_legislation(
         &self,
-        keywords: &[String],
+        budget: &TermBudget,
         limit: usize,
+        cache_only: bool,
     ) -> CollectionOutcome<Vec<LegislationSummaryDto>> {
         let mut advisories = Vec::new();
-
-        for keyword in keywords {
-            if keyword.is_empty() {
-                continue;
+        let mut attempted = Vec::new();
+        let mut responded = false;
+        let mut errored = false;
+        let mut uncached = false;
+        let mut truncated = false;
+
+        for term in budget.terms() {
+            if budget_exhausted() {
+                truncated = true;
+                break;
             }
+            let mut outcome = SearchTermOutcome::Error;
+            for (apply_relevance, threshold, broadened) in [
+                (Some(true), Some(self.config.relevance_threshold), false),
+                (Some(false), Some(0.0_f32), true),
+            ] {
+                let args = FetchLegislationArgs {
+                    title: Some(term.clone()),
+                    year: None,
+                    legislation_type: None,
+                    enable_cache: Some(true),
+                    cache_only: Some(cache_only),
+                    apply_relevance,
+                    relevance_threshold: threshold,
+                };
 
-            for term in expand_search_terms(keyword).iter() {
-                for (apply_relevance, threshold, broadened) in [
-                    (Some(true), Some(self.config.relevance_threshold), false),
-                    (Some(false), Some(0.0_f32), true),
-                ] {
-                    let args = FetchLegislationArgs {
-                        title: Some(term.clone()),
-                        year: None,
-                        legislation_type: None,
-                        enable_cache: Some(true),
-                        apply_relevance,
-                        relevance_threshold: threshold,
-                    };
-
-                    match self.data_source.fetch_legislation(args).await {
-                        Ok(raw) => {
-                            let parsed = parse_legislation_results(&raw, limit);
-                            if !parsed.is_empty() {
-                                if broadened || term != keyword {
-                                    advisories.push(format!(
-                                        "Legislation search broadened to \"{term}\" after the initial keyword returned no results."
-                                    ));
-                                }
-                                return CollectionOutcome::with_advisories(parsed, advisories);
+                match self.data_source.fetch_legislation(args).await {
+                    Ok(raw) => {
+                        responded = true;
+                        outcome = SearchTermOutcome::Empty;
+                        let parsed = parse_legislation_results(&raw, limit);
+                        if !parsed.is_empty() {
+                            if broadened || budget.is_expansion(term) {
+                                info!(target: "research", term, "bills search broadened");
+                                advisories.push(Advisory::Broadened {
+                                    collection: Collection::Legislation,
+                                    term: term.clone(),
+                                });
                             }
+                            attempted.push(term_report(term, SearchTermOutcome::Hit));
+                            return CollectionOutcome::with_advisories(parsed, advisories)
+                                .with_search_terms(budget.report(attempted));
                         }
-                        Err(error) => {
-                            warn!(target: "research", %error, term, "failed to fetch legislation");
-                            advisories
-                                .push(format!("Legislation lookup for \"{term}\" failed: {error}"));
+                    }
+                    Err(AppError::NotCached { .. }) => {
+                        uncached = true;
+                        if outcome == SearchTermOutcome::Error {
+                            outcome = SearchTermOutcome::NotCached;
                         }
                     }
+                    Err(error) => {
+                        errored = true;
+                        warn!(target: "research", %error, term, "failed to fetch legislation");
+                        advisories.push(Advisory::LookupFailed {
+                            collection: Collection::Legislation,
+                            term: term.clone(),
+                            error: error.to_string(),
+                        });
+                    }
                 }
             }
+            attempted.push(term_report(term, outcome));
+        }
 
-            advisories.push(format!(
-                "No legislation matched the keyword \"{keyword}\"; try alternate titles or verify the act year."
-            ));
+        if uncached && !responded && !errored {
+            return CollectionOutcome::not_cached(Vec::new(), Collection::Legislation)
+                .with_search_terms(budget.report(attempted))
+                .with_truncation(truncated);
+        }
+
+        for keyword in budget.keywords() {
+            advisories.push(Advisory::NoKeywordMatch {
+                collection: Collection::Legislation,
+                keyword: keyword.clone(),
+            });
         }
 
         if advisories.is_empty() {
-            advisories.push("Legislation search produced no matches for this topic.".to_string());
+            advisories.push(Advisory::NoData(Collection::Legislation));
         }
 
-        CollectionOutcome::with_advisories(Vec::new(), advisories)
+        CollectionOutcome::missing(Vec::new(), advisories, errored && !responded)
+            .with_search_terms(budget.report(attempted))
+            .with_truncation(truncated)
     }
 
     async fn collect_debates(
         &self,
-        keywords: &[String],
+        budget: &TermBudget,
         limit: usize,
+        cache_only: bool,
     ) -> CollectionOutcome<Vec<DebateSummaryDto>> {
         let mut advisories = Vec::new();
-
-        for keyword in keywords {
-            if keyword.is_empty() {
-                continue;
+        let mut attempted = Vec::new();
+        let mut responded = false;
+        let mut errored = false;
+        let mut uncached = false;
+        let mut truncated = false;
+
+        for term in budget.terms() {
+            if budget_exhausted() {
+                truncated = true;
+                break;
             }
-
-            for term in expand_search_terms(keyword).iter() {
-                let args = FetchCoreDatasetArgs {
-                    dataset: "commonsdebates".to_string(),
-                    search_term: Some(term.clone()),
-                    page: Some(0),
-                    per_page: Some(limit as u32),
-                    enable_cache: Some(true),
-                    fuzzy_match: Some(true),
-                    apply_relevance: Some(true),
-                    relevance_threshold: Some(self.config.relevance_threshold),
-                };
-
-                match self.data_source.fetch_core_dataset(args).await {
-                    Ok(raw) => {
-                        let parsed = parse_debate_results(&raw, limit);
-                        if !parsed.is_empty() {
-                            if term != keyword {
-                                advisories.push(format!(
-                                    "Debate search broadened to \"{term}\" after the initial keyword returned no results."
-                                ));
-                            }
-                            return CollectionOutcome::with_advisories(parsed, advisories);
+            let args = FetchCoreDatasetArgs {
+                dataset: "commonsdebates".to_string(),
+                search_term: Some(term.clone()),
+                page: Some(0),
+                per_page: Some(limit as u32),
+                enable_cache: Some(true),
+                cache_only: Some(cache_only),
+                fuzzy_match: Some(true),
+                apply_relevance: Some(true),
+                relevance_threshold: Some(self.config.relevance_threshold),
+                fields: None,
+            };
+
+            match self.data_source.fetch_core_dataset(args).await {
+                Ok(raw) => {
+                    responded = true;
+                    let parsed = parse_debate_results(&raw, limit);
+                    if !parsed.is_empty() {
+                        if budget.is_expansion(term) {
+                            advisories.push(Advisory::Broadened {
+                                collection: Collection::Debates,
+                                term: term.clone(),
+                            });
                         }
+                        attempted.push(term_report(term, SearchTermOutcome::Hit));
+                        return CollectionOutcome::with_advisories(parsed, advisories)
+                            .with_search_terms(budget.report(attempted));
                     }
-                    Err(error) => {
-                        warn!(target: "research", %error, term, "failed to fetch debates");
-                        advisories.push(format!("Debate lookup for \"{term}\" failed: {error}"));
-                    }
+                    attempted.push(term_report(term, SearchTermOutcome::Empty));
+                }
+                Err(AppError::NotCached { .. }) => {
+                    uncached = true;
+                    attempted.push(term_report(term, SearchTermOutcome::NotCached));
+                }
+                Err(error) => {
+                    errored = true;
+                    warn!(target: "research", %error, term, "failed to fetch debates");
+                    advisories.push(Advisory::LookupFailed {
+                        collection: Collection::Debates,
+                        term: term.clone(),
+                        error: error.to_string(),
+                    });
+                    attempted.push(term_report(term, SearchTermOutcome::Error));
                 }
             }
+        }
+
+        if uncached && !responded && !errored {
+            return CollectionOutcome::not_cached(Vec::new(), Collection::Debates)
+                .with_search_terms(budget.report(attempted))
+                .with_truncation(truncated);
+        }
 
-            advisories.push(format!(
-                "No Commons debates matched the keyword \"{keyword}\"; try broader debate topics or different dates."
-            ));
+        for keyword in budget.keywords() {
+            advisories.push(Advisory::NoKeywordMatch {
+                collection: Collection::Debates,
+                keyword: keyword.clone(),
+            });
         }
 
         if advisories.is_empty() {
-            advisories.push("Debate search returned no results for this topic.".to_string());
+            advisories.push(Advisory::NoData(Collection::Debates));
         }
 
-        CollectionOutcome::with_advisories(Vec::new(), advisories)
+        CollectionOutcome::missing(Vec::new(), advisories, errored && !responded)
+            .with_search_terms(budget.report(attempted))
+            .with_truncation(truncated)
     }
 
     async fn collect_state_of_parties(
         &self,
         include: bool,
+        cache_only: bool,
     ) -> CollectionOutcome<Option<StateOfPartiesDto>> {
         if !include {
             return CollectionOutcome::new(None);
@@ -421,59 +994,208 @@ impl ResearchService {
             page: None,
             per_page: Some(DEFAULT_RESULT_LIMIT as u32),
             enable_cache: Some(true),
+            cache_only: Some(cache_only),
             fuzzy_match: Some(false),
             apply_relevance: Some(false),
             relevance_threshold: Some(self.config.relevance_threshold),
+            fields: None,
         };
 
         let mut advisories = Vec::new();
 
-        let data = match self.data_source.fetch_core_dataset(args).await {
-            Ok(raw) => parse_state_of_parties(&raw),
+        match self.data_source.fetch_core_dataset(args).await {
+            Ok(raw) => match parse_state_of_parties(&raw) {
+                Some(data) => CollectionOutcome::with_advisories(Some(data), advisories),
+                None => CollectionOutcome::missing(None, advisories, false),
+            },
+            Err(AppError::NotCached { .. }) => {
+                CollectionOutcome::not_cached(None, Collection::StateOfParties)
+            }
             Err(error) => {
                 warn!(target: "research", %error, "failed to fetch state of parties data");
-                advisories.push(
-                    "State of parties data is temporarily unavailable; seat counts were omitted."
-                        .to_string(),
-                );
-                None
+                advisories.push(Advisory::StateOfPartiesUnavailable);
+                CollectionOutcome::missing(None, advisories, true)
             }
+        }
+    }
+
+    /// The cache TTL for one request: the configured TTL, narrowed by `maxAgeSecs`.
+    fn effective_max_age(&self, max_age_secs: Option<u64>) -> Result<u64, AppError> {
+        match max_age_secs {
+            None => Ok(self.cache_ttl),
+            Some(0) => Err(AppError::bad_request(
+                "maxAgeSecs must be a positive number of seconds".to_string(),
+            )),
+            Some(value) if value > self.cache_ttl => Err(AppError::bad_request(format!(
+                "maxAgeSecs must not exceed the research cache TTL of {} seconds",
+                self.cache_ttl
+            ))),
+            Some(value) => Ok(value),
+        }
+    }
+
+    /// Find an expired but still usable cache entry to serve instead of a degraded fresh
+    /// result: it must be within `RESEARCH_MAX_STALE_SECS` and not degraded itself.
+    async fn try_get_stale(
+        &self,
+        key: &str,
+        fresh_status: ResearchStatus,
+        locale: Locale,
+    ) -> Result<Option<ResearchResponseDto>, AppError> {
+        let max_stale = self.config.research_max_stale_secs;
+        if max_stale == 0 {
+            return Ok(None);
+        }
+
+        let Some((mut stale, age)) = self.try_get_cached(key, max_stale).await? else {
+            return Ok(None);
         };
+        if stale.status.is_degraded() {
+            return Ok(None);
+        }
 
-        CollectionOutcome::with_advisories(data, advisories)
+        mark_cached(&mut stale, age);
+        stale.stale = true;
+        let notice = Advisory::ServedStale {
+            every_source_failed: fresh_status == ResearchStatus::Failed,
+            cached_at: stale.cached_at.clone(),
+        };
+        stale.advisories.insert(0, notice.render(locale));
+
+        Ok(Some(stale))
     }
 
-    async fn try_get_cached(&self, key: &str) -> Result<Option<ResearchResponseDto>, AppError> {
-        let tree = self.cache_tree.clone();
-        let key_bytes = key.as_bytes().to_vec();
-        let ttl = self.cache_ttl;
-
-        task::spawn_blocking(move || -> Result<Option<ResearchResponseDto>, AppError> {
-            let maybe_bytes = tree
-                .get(&key_bytes)
-                .map_err(|err| AppError::internal(format!("cache lookup failed: {err}")))?;
-
-            if let Some(bytes) = maybe_bytes {
-                let entry: CachedResearchEntry = serde_json::from_slice(&bytes).map_err(|err| {
-                    AppError::internal(format!("failed to decode cached research entry: {err}"))
-                })?;
-                if now_timestamp().saturating_sub(entry.stored_at) <= ttl {
-                    return Ok(Some(entry.payload));
+    /// Retry the run in the background after `RESEARCH_STALE_REFRESH_SECS`, replacing the
+    /// cache entry only if the retry is no longer degraded. At most one refresh per cache
+    /// key is pending at a time.
+    fn schedule_refresh(&self, request: ResearchRequestDto, locale: Locale, cache_key: String) {
+        match self.refreshing.lock() {
+            Ok(mut refreshing) => {
+                if !refreshing.insert(cache_key.clone()) {
+                    return;
+                }
+            }
+            Err(error) => {
+                warn!(target: "research", %error, "refresh registry mutex poisoned");
+                return;
+            }
+        }
+
+        let service = self.clone();
+        let delay = Duration::from_secs(self.config.research_stale_refresh_secs);
+        tokio::spawn(async move {
+            sleep(delay).await;
+
+            match service
+                .compute_research(&request, locale, ResearchRunContext::default())
+                .await
+            {
+                Ok(response) if !response.status.is_degraded() => {
+                    match service
+                        .store_cache(&cache_key, &request.topic, &response)
+                        .await
+                    {
+                        Ok(()) => {
+                            info!(target: "research", key = %cache_key, "stale research entry refreshed")
+                        }
+                        Err(error) => {
+                            warn!(target: "research", %error, "failed to store refreshed research")
+                        }
+                    }
                 }
+                Ok(response) => warn!(
+                    target: "research",
+                    key = %cache_key,
+                    status = ?response.status,
+                    "research refresh still degraded; keeping the stale entry"
+                ),
+                Err(error) => warn!(target: "research", %error, "research refresh failed"),
             }
 
-            Ok(None)
-        })
+            if let Ok(mut refreshing) = service.refreshing.lock() {
+                refreshing.remove(&cache_key);
+            }
+        });
+    }
+
+    /// Look up a cached response no older than `ttl` seconds, returning it with when it
+    /// was stored. An entry stamped in the future (by a clock that has since stepped
+    /// back) is re-stamped as written now, so it lives one more TTL rather than until the
+    /// clock catches up.
+    async fn try_get_cached(
+        &self,
+        key: &str,
+        ttl: u64,
+    ) -> Result<Option<(ResearchResponseDto, CacheAge)>, AppError> {
+        let tree = self.cache_tree.clone();
+        let clock = self.clock.clone();
+        let key_bytes = key.as_bytes().to_vec();
+
+        task::spawn_blocking(
+            move || -> Result<Option<(ResearchResponseDto, CacheAge)>, AppError> {
+                let maybe_bytes = tree
+                    .get(&key_bytes)
+                    .map_err(|err| AppError::internal(format!("cache lookup failed: {err}")))?;
+
+                let Some(bytes) = maybe_bytes else {
+                    return Ok(None);
+                };
+                let mut entry = decode_entry(&bytes)?;
+                if !matches!(entry.body, CachedResearchBody::Full(_)) {
+                    return Ok(None);
+                }
+                let age_secs = match clock.age_secs(entry.stamp()) {
+                    Some(age) if age <= ttl => age,
+                    Some(_) => return Ok(None),
+                    None => {
+                        entry.stored_at = clock.wall_secs();
+                        entry.steady_at = Some(clock.steady_secs());
+                        let data = serde_json::to_vec(&entry).map_err(|err| {
+                            AppError::internal(format!(
+                                "failed to serialise research cache entry: {err}"
+                            ))
+                        })?;
+                        // Lost races leave whatever replaced the entry.
+                        let _ = tree
+                            .compare_and_swap(&key_bytes, Some(&bytes), Some(data))
+                            .map_err(|err| {
+                                AppError::internal(format!(
+                                    "failed to persist research cache entry: {err}"
+                                ))
+                            })?;
+                        0
+                    }
+                };
+                let CachedResearchBody::Full(payload) = entry.body else {
+                    return Ok(None);
+                };
+                let age = CacheAge {
+                    stored_at: entry.stored_at,
+                    age_secs,
+                };
+                Ok(Some((*payload, age)))
+            },
+        )
         .await
         .map_err(|err| AppError::internal(format!("cache task join error: {err}")))?
     }
 
-    async fn store_cache(&self, key: &str, response: &ResearchResponseDto) -> Result<(), AppError> {
+    async fn store_cache(
+        &self,
+        key: &str,
+        topic: &str,
+        response: &ResearchResponseDto,
+    ) -> Result<(), AppError> {
         let mut cacheable = response.clone();
         cacheable.cached = false;
+        cacheable.cached_at = None;
+        cacheable.age_secs = None;
+        cacheable.stale = false;
         let entry = CachedResearchEntry {
-            stored_at: now_timestamp(),
-            payload: cacheable,
+            stored_at: self.clock.wall_secs(),
+            steady_at: Some(self.clock.steady_secs()),
+            topic: Some(topic.trim().to_string()),
+            body: CachedResearchBody::Full(Box::new(cacheable)),
         };
         let data = serde_json::to_vec(&entry).map_err(|err| {
             AppError::internal(format!("failed to serialise research cache entry: {err}"))
@@ -497,4 +1219,383 @@ impl ResearchService {
 
         Ok(())
     }
+
+    /// Rewrite every full entry stored more than `RESEARCH_COMPACT_AFTER_DAYS` before `now`
+    /// (a [`Clock::steady_secs`] reading) as a tombstone keeping only its summary, status and citation URLs. Tombstones are
+    /// cache misses; an entry replaced while the pass runs is left alone.
+    pub async fn compact(&self, now: u64) -> Result<CompactionReport, AppError> {
+        let max_age = self
+            .config
+            .research_compact_after_days
+            .saturating_mul(86_400);
+        if max_age == 0 {
+            return Ok(CompactionReport::default());
+        }
+
+        let cutoff = now.saturating_sub(max_age);
+        let report = self
+            .scan(
+                "compact",
+                None,
+                CompactionReport::default(),
+                move |report, tree, key, bytes| {
+                    let entry = decode_entry(&bytes)?;
+                    if entry.stamp() >= cutoff {
+                        return Ok(true);
+                    }
+                    let CachedResearchBody::Full(payload) = entry.body else {
+                        return Ok(true);
+                    };
+
+                    let compacted = CachedResearchEntry {
+                        stored_at: entry.stored_at,
+                        steady_at: entry.steady_at,
+                        topic: entry.topic,
+                        body: CachedResearchBody::Tombstone(ResearchTombstone {
+                            citations: citations(&payload),
+                            summary: payload.summary,
+                            status: payload.status,
+                            compacted_at: now,
+                        }),
+                    };
+                    let data = serde_json::to_vec(&compacted).map_err(|err| {
+                        AppError::internal(format!("failed to serialise research tombstone: {err}"))
+                    })?;
+                    let swapped = tree
+                        .compare_and_swap(&key, Some(&bytes), Some(data.as_slice()))
+                        .map_err(|err| {
+                            AppError::internal(format!("failed to compact research entry: {err}"))
+                        })?;
+                    if swapped.is_ok() {
+                        report.compacted += 1;
+                        report.bytes_before += bytes.len() as u64;
+                        report.bytes_after += data.len() as u64;
+                    }
+                    Ok(true)
+                },
+            )
+            .await?;
+
+        if report.compacted > 0 {
+            self.cache_tree.flush_async().await.map_err(|err| {
+                AppError::internal(format!("failed to flush research cache: {err}"))
+            })?;
+        }
+        Ok(report)
+    }
+
+    /// One page of stored briefs, full or compacted, in key order starting after
+    /// `cursor`. `nextCursor` is set while entries remain beyond the page.
+    pub async fn list_cached(
+        &self,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<ListCachedResearchResult, AppError> {
+        let after = cursor
+            .map(|cursor| {
+                hex::decode(cursor)
+                    .map_err(|_| AppError::bad_request(format!("invalid cursor \"{cursor}\"")))
+            })
+            .transpose()?;
+
+        let page = self
+            .scan(
+                "list_cached",
+                after,
+                ListingPage::default(),
+                move |page, _, key, bytes| {
+                    if page.entries.len() == limit {
+                        page.more = true;
+                        return Ok(false);
+                    }
+                    page.entries.push(summarise_entry(&key, &bytes)?);
+                    page.last_key = Some(key);
+                    Ok(true)
+                },
+            )
+            .await?;
+
+        Ok(ListCachedResearchResult {
+            entries: page.entries,
+            next_cursor: page
+                .more
+                .then_some(page.last_key)
+                .flatten()
+                .map(hex::encode),
+        })
+    }
+
+    /// Keyword suggestions for `topic` from the titles of bills, Commons debates and early
+    /// day motions matching it. The full ranking is kept in memory for the research cache
+    /// TTL, so repeat calls (at any `limit`) make no upstream requests. Sources that fail
+    /// are left out; if every source fails the first error is returned and nothing is
+    /// cached.
+    pub async fn suggest_keywords(
+        &self,
+        topic: &str,
+        limit: usize,
+    ) -> Result<KeywordSuggestionsResult, AppError> {
+        let topic = topic.trim();
+        if topic.is_empty() {
+            return Err(AppError::bad_request("topic must not be empty".to_string()));
+        }
+
+        let key = topic.to_lowercase();
+        if let Some(cached) = self.suggestions.get(&key).await {
+            if let Ok(mut suggestions) = serde_json::from_value::<Vec<KeywordSuggestion>>(cached) {
+                suggestions.truncate(limit);
+                return Ok(KeywordSuggestionsResult {
+                    topic: topic.to_string(),
+                    suggestions,
+                    cached: true,
+                });
+            }
+        }
+
+        let bills = FetchBillsArgs {
+            search_term: Some(topic.to_string()),
+            house: None,
+            session: None,
+            parliament_number: None,
+            enable_cache: Some(true),
+            cache_only: None,
+            apply_relevance: Some(true),
+            relevance_threshold: Some(self.config.relevance_threshold),
+            fields: None,
+        };
+        let (bills, debates, edms) = tokio::join!(
+            self.data_source.fetch_bills(bills),
+            self.data_source
+                .fetch_core_dataset(self.suggestion_dataset_args("commonsdebates", topic)),
+            self.data_source
+                .fetch_core_dataset(self.suggestion_dataset_args("edms", topic)),
+        );
+
+        let mut titles = Vec::new();
+        let mut first_error = None;
+        let mut complete = true;
+        for (source, outcome) in [
+            (EvidenceSource::Bill, bills),
+            (EvidenceSource::Debate, debates),
+            (EvidenceSource::Edm, edms),
+        ] {
+            match outcome {
+                Ok(raw) => titles.extend(parse_evidence(&raw, source, SUGGESTION_TITLES)),
+                Err(error) => {
+                    warn!(target: "research", %error, ?source, "keyword suggestion source failed");
+                    complete = false;
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        if titles.is_empty() {
+            if let Some(error) = first_error {
+                return Err(error);
+            }
+        }
+
+        let mut suggestions = extract_keyword_suggestions(topic, &titles, MAX_SUGGESTION_LIMIT);
+        if complete {
+            if let Ok(value) = serde_json::to_value(&suggestions) {
+                self.suggestions.insert(key, value, self.cache_ttl).await;
+            }
+        }
+        suggestions.truncate(limit);
+        Ok(KeywordSuggestionsResult {
+            topic: topic.to_string(),
+            suggestions,
+            cached: false,
+        })
+    }
+
+    fn suggestion_dataset_args(&self, dataset: &str, topic: &str) -> FetchCoreDatasetArgs {
+        FetchCoreDatasetArgs {
+            dataset: dataset.to_string(),
+            search_term: Some(topic.to_string()),
+            page: Some(0),
+            per_page: Some(SUGGESTION_TITLES as u32),
+            enable_cache: Some(true),
+            cache_only: None,
+            fuzzy_match: Some(true),
+            apply_relevance: Some(true),
+            relevance_threshold: Some(self.config.relevance_threshold),
+            fields: None,
+        }
+    }
+
+    /// Walk the cache tree in key order from just after `after`, calling `visit` on the
+    /// blocking pool for [`SCAN_BATCH`] entries at a time until it returns false or the
+    /// tree ends. Each batch opens a fresh range after the last key seen, so the walk
+    /// yields between batches and entries written meanwhile are still met in order.
+    async fn scan<S, V>(
+        &self,
+        name: &'static str,
+        after: Option<Vec<u8>>,
+        state: S,
+        visit: V,
+    ) -> Result<S, AppError>
+    where
+        S: Send + 'static,
+        V: FnMut(&mut S, &Tree, IVec, IVec) -> Result<bool, AppError> + Send + 'static,
+    {
+        let _permit = self
+            .scan_permits
+            .acquire()
+            .await
+            .map_err(|err| AppError::internal(format!("cache scan unavailable: {err}")))?;
+        let started = Instant::now();
+        let mut batch = ScanBatch {
+            state,
+            visit,
+            after,
+            finished: false,
+        };
+
+        while !batch.finished {
+            let tree = self.cache_tree.clone();
+            batch = task::spawn_blocking(move || batch.step(&tree))
+                .await
+                .map_err(|err| AppError::internal(format!("cache task join error: {err}")))??;
+            task::yield_now().await;
+        }
+
+        self.scan_metrics.record_scan(name, started.elapsed());
+        Ok(batch.state)
+    }
+}
+
+#[derive(Default)]
+struct ListingPage {
+    entries: Vec<CachedResearchSummary>,
+    last_key: Option<IVec>,
+    /// An entry beyond the page was seen.
+    more: bool,
+}
+
+/// A cache scan's progress, moved onto the blocking pool for each batch and back.
+struct ScanBatch<S, V> {
+    state: S,
+    visit: V,
+    after: Option<Vec<u8>>,
+    finished: bool,
+}
+
+impl<S, V> ScanBatch<S, V>
+where
+    V: FnMut(&mut S, &Tree, IVec, IVec) -> Result<bool, AppError>,
+{
+    fn step(mut self, tree: &Tree) -> Result<Self, AppError> {
+        let start = match self.after.as_deref() {
+            Some(key) => Bound::Excluded(key),
+            None => Bound::Unbounded,
+        };
+        let mut read = 0;
+        for item in tree.range::<&[u8], _>((start, Bound::Unbounded)) {
+            let (key, bytes) =
+                item.map_err(|err| AppError::internal(format!("cache scan failed: {err}")))?;
+            let last = key.to_vec();
+            if !(self.visit)(&mut self.state, tree, key, bytes)? {
+                return Ok(self.finish());
+            }
+            self.after = Some(last);
+            read += 1;
+            if read == SCAN_BATCH {
+                return Ok(self);
+            }
+        }
+        Ok(self.finish())
+    }
+
+    fn finish(mut self) -> Self {
+        self.finished = true;
+        self
+    }
+}
+
+/// The listing row for one stored entry.
+fn summarise_entry(key: &[u8], bytes: &[u8]) -> Result<CachedResearchSummary, AppError> {
+    let entry = decode_entry(bytes)?;
+    let topic = entry
+        .topic
+        .unwrap_or_else(|| topic_from_key(&String::from_utf8_lossy(key)));
+    let (summary, status, citations, compacted_at) = match entry.body {
+        CachedResearchBody::Full(payload) => {
+            let citations = citations(&payload);
+            (payload.summary, payload.status, citations, None)
+        }
+        CachedResearchBody::Tombstone(tombstone) => (
+            tombstone.summary,
+            tombstone.status,
+            tombstone.citations,
+            Some(tombstone.compacted_at),
+        ),
+    };
+    Ok(CachedResearchSummary {
+        topic,
+        stored_at: rfc3339(entry.stored_at).unwrap_or_default(),
+        status,
+        summary,
+        citations,
+        compacted: compacted_at.is_some(),
+        compacted_at: compacted_at.and_then(rfc3339),
+    })
+}
+
+fn decode_entry(bytes: &[u8]) -> Result<CachedResearchEntry, AppError> {
+    serde_json::from_slice(bytes)
+        .map_err(|err| AppError::internal(format!("failed to decode cached research entry: {err}")))
+}
+
+/// Source URLs cited by a brief, in collection order without repeats.
+fn citations(response: &ResearchResponseDto) -> Vec<String> {
+    let links = response
+        .bills
+        .iter()
+        .map(|bill| bill.link.as_ref())
+        .chain(response.debates.iter().map(|debate| debate.link.as_ref()))
+        .chain(response.legislation.iter().map(|item| item.uri.as_ref()))
+        .chain(response.votes.iter().map(|vote| vote.link.as_ref()))
+        .chain(
+            response
+                .mp_speeches
+                .iter()
+                .map(|speech| speech.source.as_ref()),
+        )
+        .flatten();
+
+    let mut seen = HashSet::new();
+    links
+        .filter(|link| seen.insert(link.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// The topic part of a cache key built by `build_cache_key`.
+fn topic_from_key(key: &str) -> String {
+    key.strip_prefix("topic:")
+        .and_then(|rest| rest.split_once("|bills:"))
+        .map_or(key, |(topic, _)| topic)
+        .to_string()
+}
+
+fn rfc3339(timestamp: u64) -> Option<String> {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|value| value.to_rfc3339())
+}
+
+fn mark_cached(response: &mut ResearchResponseDto, age: CacheAge) {
+    response.cached = true;
+    response.age_secs = Some(age.age_secs);
+    response.cached_at = rfc3339(age.stored_at);
+}
+
+async fn until_cancelled<F: Future>(
+    cancellation: Option<&CancellationToken>,
+    future: F,
+) -> Option<F::Output> {
+    match cancellation {
+        Some(token) => token.run_until_cancelled(future).await,
+        None => Some(future.await),
+    }
 }
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+use crate::core::bounds;
+use crate::core::usage::MAX_WINDOW_HOURS;
+
+#[derive(Debug, Deserialize)]
+pub struct UsageSummaryArgs {
+    /// Hours to report on, counting the current one; defaults to a week.
+    #[serde(
+        default,
+        rename = "windowHours",
+        deserialize_with = "bounds::optional::<_, _, 1, { MAX_WINDOW_HOURS as i64 }>"
+    )]
+    pub window_hours: Option<u64>,
+}
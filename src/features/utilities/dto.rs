@@ -1,7 +1,30 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Clone, Serialize)]
+use crate::core::signing::SignatureBlock;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct CurrentDatetimeDto {
     pub utc: String,
     pub local: String,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifySignatureArgs {
+    pub payload: Map<String, Value>,
+    /// Defaults to `payload.signature`, as returned by signed tools.
+    #[serde(default)]
+    pub signature: Option<SignatureBlock>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifySignatureDto {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub payload_sha256: String,
+    pub public_key: String,
+}
@@ -1,5 +1,43 @@
-use crate::features::utilities::{CurrentDatetimeDto, DateTimeService};
+use serde_json::Value;
+
+use crate::core::error::AppError;
+use crate::core::signing::{ResponseSigner, SignatureBlock, payload_digest};
+use crate::features::utilities::{
+    CurrentDatetimeDto, DateTimeService, VerifySignatureArgs, VerifySignatureDto,
+};
 
 pub fn handle_current_datetime(service: &DateTimeService) -> CurrentDatetimeDto {
     service.current_datetime()
 }
+
+pub fn handle_verify_signature(
+    signer: Option<&ResponseSigner>,
+    args: VerifySignatureArgs,
+) -> Result<VerifySignatureDto, AppError> {
+    let signer = signer.ok_or_else(|| {
+        AppError::configuration("response signing is not configured on this server")
+    })?;
+
+    let mut payload = args.payload;
+    let embedded = payload.remove("signature");
+    let signature = match args.signature {
+        Some(signature) => signature,
+        None => {
+            let embedded = embedded.ok_or_else(|| {
+                AppError::bad_request("no signature supplied and payload has no signature field")
+            })?;
+            serde_json::from_value::<SignatureBlock>(embedded)
+                .map_err(|err| AppError::bad_request(format!("invalid signature block: {err}")))?
+        }
+    };
+
+    let payload = Value::Object(payload);
+    let outcome = signer.verify(&payload, &signature);
+
+    Ok(VerifySignatureDto {
+        valid: outcome.is_ok(),
+        reason: outcome.err(),
+        payload_sha256: payload_digest(&payload),
+        public_key: signer.public_key_hex(),
+    })
+}
@@ -2,6 +2,6 @@ pub mod dto;
 pub mod handler;
 pub mod time_service;
 
-pub use dto::CurrentDatetimeDto;
-pub use handler::handle_current_datetime;
+pub use dto::{CurrentDatetimeDto, VerifySignatureArgs, VerifySignatureDto};
+pub use handler::{handle_current_datetime, handle_verify_signature};
 pub use time_service::DateTimeService;
@@ -1,3 +1,6 @@
+#[cfg(all(feature = "fault-injection", not(debug_assertions)))]
+compile_error!("the `fault-injection` feature is for test builds only");
+
 pub mod config;
 pub mod core;
 pub mod features;
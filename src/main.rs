@@ -1,30 +1,35 @@
-mod config;
-mod core;
-mod features;
-mod server;
-
-use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::Router;
-use axum::middleware;
-use axum::routing::{get, post};
-use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
 
-use crate::config::load_config;
-use crate::core::cache::CacheManager;
-use crate::core::error::AppError;
-use crate::features::mcp::{McpService, handle_healthcheck, handle_mcp};
-use crate::features::parliament::ParliamentClient;
-use crate::features::research::ResearchService;
-use crate::server::{AppState, require_api_key};
+use mp_writer_mcp_server::config::load_config;
+use mp_writer_mcp_server::core::audit::AuditLog;
+use mp_writer_mcp_server::core::cache::CacheManager;
+use mp_writer_mcp_server::core::client_log::client_log_layer;
+use mp_writer_mcp_server::core::clock::Clock;
+use mp_writer_mcp_server::core::error::AppError;
+use mp_writer_mcp_server::core::preferences::PreferenceStore;
+use mp_writer_mcp_server::core::signing::ResponseSigner;
+use mp_writer_mcp_server::core::stats::{StatsRegistry, now_timestamp};
+use mp_writer_mcp_server::core::usage::UsageCollector;
+use mp_writer_mcp_server::features::mcp::McpService;
+use mp_writer_mcp_server::features::parliament::ParliamentClient;
+use mp_writer_mcp_server::features::reference_data::RefreshReferenceDataArgs;
+use mp_writer_mcp_server::features::research::{ParliamentDataSource, ResearchService};
+use mp_writer_mcp_server::server::{
+    AppState, Listener, build_router, load_tls_config, spawn_certificate_reload,
+    spawn_key_file_reload,
+};
 
 const CACHE_CAPACITY: u64 = 1024;
+const MAINTENANCE_INTERVAL_SECS: u64 = 3600;
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
-    init_tracing();
+    init_tracing()?;
 
     let config = Arc::new(load_config()?);
     let sled_db = sled::open(&config.db_path).map_err(|err| {
@@ -39,51 +44,263 @@ async fn main() -> Result<(), AppError> {
     let research_tree = sled_db
         .open_tree("research")
         .map_err(|err| AppError::internal(format!("failed to open research cache: {err}")))?;
+    let stats_tree = sled_db
+        .open_tree("stats")
+        .map_err(|err| AppError::internal(format!("failed to open stats tree: {err}")))?;
+    let audit_tree = sled_db
+        .open_tree("audit")
+        .map_err(|err| AppError::internal(format!("failed to open audit tree: {err}")))?;
+    let preferences_tree = sled_db
+        .open_tree("preferences")
+        .map_err(|err| AppError::internal(format!("failed to open preferences tree: {err}")))?;
+
+    let stats = Arc::new(StatsRegistry::new());
 
     let cache_manager = CacheManager::new(config.cache_enabled, CACHE_CAPACITY);
-    let parliament_client = Arc::new(ParliamentClient::new(
-        config.clone(),
-        cache_manager,
-        parliament_tree,
-    )?);
+    // One clock for both Sled caches, so /api/metrics counts future stamps from either.
+    let clock = Clock::system();
+    let parliament_client = Arc::new(
+        ParliamentClient::new(
+            config.clone(),
+            cache_manager,
+            parliament_tree,
+            stats.clone(),
+        )?
+        .with_clock(clock.clone()),
+    );
+
+    let research_data_source: Arc<dyn ParliamentDataSource> = parliament_client.clone();
+    let research_service = Arc::new(
+        ResearchService::new(config.clone(), research_data_source, research_tree).with_clock(clock),
+    );
 
-    let research_data_source: Arc<dyn crate::features::research::ParliamentDataSource> =
-        parliament_client.clone();
-    let research_service = Arc::new(ResearchService::new(
+    let audit_log = Arc::new(AuditLog::new(audit_tree, config.api_key.clone()));
+
+    let mut mcp_service = McpService::new(
         config.clone(),
-        research_data_source,
-        research_tree,
-    ));
-
-    let mcp_service = Arc::new(McpService::new(parliament_client, research_service.clone()));
-    let app_state = AppState::new(mcp_service.clone(), config.api_key.clone());
-
-    let app = Router::new()
-        .route("/api/health", get(handle_healthcheck))
-        .route(
-            "/api/mcp",
-            post(handle_mcp).layer(middleware::from_fn_with_state(
-                app_state.clone(),
-                require_api_key,
-            )),
-        )
-        .with_state(app_state);
-
-    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
-    tracing::info!(%addr, "starting server");
-    let listener = TcpListener::bind(addr)
-        .await
-        .map_err(|err| AppError::internal(format!("failed to bind: {err}")))?;
-    axum::serve(listener, app)
+        parliament_client.clone(),
+        research_service.clone(),
+        stats.clone(),
+    )
+    .with_audit_log(audit_log.clone())
+    .with_preference_store(Arc::new(PreferenceStore::new(preferences_tree)));
+    if config.usage_stats_enabled {
+        mcp_service = mcp_service
+            .with_usage_collector(Arc::new(UsageCollector::new(config.usage_k_threshold)));
+    }
+    if let Some(path) = config.response_signing_key.as_deref() {
+        let signer = ResponseSigner::from_pem_file(path)?;
+        tracing::info!(public_key = %signer.public_key_hex(), "response signing enabled");
+        mcp_service = mcp_service.with_response_signer(Arc::new(signer));
+    }
+    let mcp_service = Arc::new(mcp_service);
+    let app_state = AppState::new(mcp_service, parliament_client.clone(), config.clone());
+    let readiness = app_state.readiness.clone();
+    let in_flight = app_state.in_flight.clone();
+    let api_keys = app_state.api_keys.clone();
+
+    let app = build_router(app_state);
+
+    if config.allow_query_api_key {
+        tracing::warn!(
+            "ALLOW_QUERY_API_KEY is enabled: /api/mcp accepts ?api_key=. Keys in URLs can leak \
+             through proxy and access logs, browser history and Referer headers; prefer the \
+             x-api-key header and rotate MCP_API_KEY if it may have been exposed"
+        );
+    }
+
+    // Check the certificate before binding, so a bad one fails start-up cleanly.
+    let tls = config
+        .tls
+        .as_ref()
+        .map(|tls| load_tls_config(tls).map(|acceptor| (tls.clone(), acceptor)))
+        .transpose()?;
+    let mut listener = Listener::bind(&config.listen).await?;
+    if let Some((tls, acceptor)) = tls {
+        spawn_certificate_reload(acceptor.clone(), tls);
+        listener = listener.with_tls(acceptor)?;
+    }
+    tracing::info!(address = %listener, tls = config.tls.is_some(), "starting server");
+    let stop_accepting = CancellationToken::new();
+    let mut server = tokio::spawn(listener.serve(app, stop_accepting.clone()));
+
+    // Probes are answered from here on; `/api/mcp` waits for the rest of start-up.
+    if let Err(error) = stats.restore(&stats_tree).await {
+        tracing::warn!(%error, "failed to restore tool statistics snapshot");
+    }
+    spawn_stats_snapshots(
+        stats.clone(),
+        stats_tree.clone(),
+        config.stats_snapshot_interval,
+    );
+    spawn_db_maintenance(audit_log, research_service, config.audit_retention_days);
+    spawn_key_file_reload(api_keys, &config);
+    spawn_reference_data_refresh(parliament_client, config.reference_data_refresh_hours);
+    readiness.mark_ready();
+    tracing::info!("server ready");
+
+    tokio::select! {
+        result = &mut server => {
+            result
+                .map_err(|err| AppError::internal(format!("server task failed: {err}")))?
+                .map_err(|err| AppError::internal(format!("server error: {err}")))?;
+            return Ok(());
+        }
+        () = shutdown_signal() => {}
+    }
+
+    // Stop accepting connections, then give running `/api/mcp` calls the grace period.
+    // Long-lived notification streams are not counted and end with the server task.
+    stop_accepting.cancel();
+    let running = in_flight.count();
+    let aborted = in_flight
+        .drain(Duration::from_secs(config.shutdown_grace_secs))
+        .await;
+    server.abort();
+    // Dropping the listener removes a Unix socket file.
+    let _ = server.await;
+    tracing::info!(
+        drained = running.saturating_sub(aborted),
+        aborted,
+        "stopped serving requests"
+    );
+
+    if let Err(error) = stats.persist(&stats_tree).await {
+        tracing::warn!(%error, "failed to snapshot tool statistics");
+    }
+    let flushed = sled_db
+        .flush_async()
         .await
-        .map_err(|err| AppError::internal(format!("server error: {err}")))?;
+        .map_err(|err| AppError::internal(format!("failed to flush sled database: {err}")))?;
+    drop(sled_db);
+    tracing::info!(flushed_bytes = flushed, "shutdown complete");
 
     Ok(())
 }
 
-fn init_tracing() {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive("info".parse().unwrap()))
-        .with_target(false)
+/// Resolves on SIGTERM (container stop) or SIGINT (Ctrl-C).
+async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            tracing::error!(%error, "failed to listen for SIGINT");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                tracing::error!(%error, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => tracing::info!("received SIGINT, shutting down"),
+        () = terminate => tracing::info!("received SIGTERM, shutting down"),
+    }
+}
+
+/// Console logging: human-readable lines, or one JSON object per line with
+/// `LOG_FORMAT=json` for log shippers.
+fn init_tracing() -> Result<(), AppError> {
+    let filter = || EnvFilter::from_default_env().add_directive("info".parse().unwrap());
+    let format = std::env::var("LOG_FORMAT").unwrap_or_default();
+    let console = match format.trim().to_ascii_lowercase().as_str() {
+        "" | "text" => tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_filter(filter())
+            .boxed(),
+        "json" => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_filter(filter())
+            .boxed(),
+        other => {
+            return Err(AppError::configuration(format!(
+                "LOG_FORMAT must be text or json, received {other:?}"
+            )));
+        }
+    };
+    tracing_subscriber::registry()
+        .with(console)
+        .with(client_log_layer())
         .init();
+    Ok(())
+}
+
+fn spawn_stats_snapshots(stats: Arc<StatsRegistry>, tree: sled::Tree, interval_secs: u64) {
+    if interval_secs == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(error) = stats.persist(&tree).await {
+                tracing::warn!(%error, "failed to snapshot tool statistics");
+            }
+        }
+    });
+}
+
+/// Refresh the reference datasets every `interval_hours`, the first time one interval
+/// after start-up.
+fn spawn_reference_data_refresh(client: Arc<ParliamentClient>, interval_hours: u64) {
+    if interval_hours == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(interval_hours.saturating_mul(3_600)));
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(error) = client
+                .refresh_reference_data(RefreshReferenceDataArgs::default())
+                .await
+            {
+                tracing::warn!(%error, "scheduled reference data refresh failed");
+            }
+        }
+    });
+}
+
+/// Hourly Sled upkeep: prune expired audit entries and compact old research briefs.
+fn spawn_db_maintenance(log: Arc<AuditLog>, research: Arc<ResearchService>, retention_days: u64) {
+    let retention_secs = retention_days.saturating_mul(86_400);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(MAINTENANCE_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            let now = now_timestamp();
+            if retention_secs > 0 {
+                match log.prune_before(now.saturating_sub(retention_secs)).await {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::info!(removed, "pruned expired audit entries"),
+                    Err(error) => tracing::warn!(%error, "failed to prune audit entries"),
+                }
+            }
+            match research.compact(research.clock().steady_secs()).await {
+                Ok(report) if report.compacted == 0 => {}
+                Ok(report) => tracing::info!(
+                    compacted = report.compacted,
+                    bytes_saved = report.bytes_saved(),
+                    "compacted old research cache entries"
+                ),
+                Err(error) => tracing::warn!(%error, "failed to compact research cache"),
+            }
+        }
+    });
 }
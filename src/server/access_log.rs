@@ -0,0 +1,94 @@
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::body::{Body, HttpBody};
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::recording::redact_postcodes;
+
+/// Longest client-supplied value (JSON-RPC method, tool name) copied into a log line.
+const MAX_LOGGED_CHARS: usize = 64;
+
+/// What later layers learn about a request for its access-log line: the key name from
+/// [`crate::server::require_api_key`], the JSON-RPC method and tool from the MCP handler.
+/// Added to the request's extensions by [`access_log`].
+#[derive(Clone, Default)]
+pub struct AccessLogRecord(Arc<Mutex<AccessLogFields>>);
+
+#[derive(Default)]
+struct AccessLogFields {
+    api_key: Option<String>,
+    rpc_method: Option<String>,
+    tool: Option<String>,
+}
+
+impl AccessLogRecord {
+    pub fn set_api_key(&self, name: &str) {
+        self.update(|fields| fields.api_key = Some(name.to_string()));
+    }
+
+    /// The JSON-RPC method and, for `tools/call`, the tool. Both come from the client, so
+    /// they are stored truncated and with postcodes redacted.
+    pub fn set_rpc(&self, method: &str, tool: Option<&str>) {
+        self.update(|fields| {
+            fields.rpc_method = Some(loggable(method));
+            fields.tool = tool.map(loggable);
+        });
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut AccessLogFields)) {
+        if let Ok(mut fields) = self.0.lock() {
+            apply(&mut fields);
+        }
+    }
+}
+
+/// Log one `access` line per request once its response is ready: HTTP method, path,
+/// JSON-RPC method and tool, API key name, status, latency and response size (absent for
+/// streamed responses, whose length is not known up front). Argument values are never
+/// logged. Runs inside the `request` span, so the line also carries the request id.
+pub async fn access_log(mut request: Request<Body>, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let record = AccessLogRecord::default();
+    request.extensions_mut().insert(record.clone());
+
+    let response = next.run(request).await;
+
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let response_bytes = response.body().size_hint().exact();
+    let fields = record.0.lock().map(|fields| {
+        (
+            fields.api_key.clone(),
+            fields.rpc_method.clone(),
+            fields.tool.clone(),
+        )
+    });
+    let (api_key, rpc_method, tool) = fields.unwrap_or_default();
+    tracing::info!(
+        target: "access",
+        http_method = %method,
+        path = %path,
+        rpc_method = rpc_method.as_deref().unwrap_or("-"),
+        tool = tool.as_deref().unwrap_or("-"),
+        api_key = api_key.as_deref().unwrap_or("-"),
+        status = response.status().as_u16(),
+        latency_ms,
+        response_bytes,
+        "request served"
+    );
+    response
+}
+
+fn loggable(value: &str) -> String {
+    let redacted = redact_postcodes(value);
+    if redacted.chars().count() <= MAX_LOGGED_CHARS {
+        return redacted;
+    }
+    let mut truncated: String = redacted.chars().take(MAX_LOGGED_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
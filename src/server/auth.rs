@@ -1,36 +1,210 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
 use axum::Json;
 use axum::body::Body;
 use axum::extract::State;
-use axum::http::{Request, StatusCode};
+use axum::http::{HeaderValue, Request, StatusCode, Uri, header};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
+use reqwest::Url;
 use serde_json::json;
+use sha2::{Digest, Sha256};
 
-use crate::server::AppState;
+use crate::config::{ADMIN_KEY_NAME, ApiKeysConfig, AppConfig};
+use crate::server::{AccessLogRecord, AppState};
 
 const API_KEY_HEADER: &str = "x-api-key";
+const BEARER_PREFIX: &str = "bearer ";
+const MISSING_KEY_MESSAGE: &str =
+    "missing or invalid API key; send it in the x-api-key header or as Authorization: Bearer <key>";
+const API_KEY_QUERY_PARAM: &str = "api_key";
+/// The only route that accepts `?api_key=`, and only with `ALLOW_QUERY_API_KEY=true`.
+const QUERY_API_KEY_PATH: &str = "/api/mcp";
+
+/// Name of the key a request authenticated with, added to its extensions by
+/// [`require_api_key`].
+#[derive(Debug, Clone)]
+pub struct ApiKeyName(pub String);
+
+/// Accepted keys by SHA-256 digest. Resolving a key never compares raw keys, and takes the
+/// same time however many keys are configured.
+#[derive(Debug, Default)]
+pub struct KeyRing(HashMap<[u8; 32], String>);
+
+/// The accepted keys, replaced as a whole when `MCP_API_KEY_FILE` changes.
+pub type SharedKeyRing = Arc<ArcSwap<KeyRing>>;
+
+impl KeyRing {
+    pub fn new(client_keys: &ApiKeysConfig, admin_key: Option<&str>) -> Self {
+        let mut keys: HashMap<[u8; 32], String> = client_keys
+            .keys
+            .iter()
+            .map(|named| (digest(&named.key), named.name.clone()))
+            .collect();
+        if let Some(admin_key) = admin_key {
+            keys.insert(digest(admin_key), ADMIN_KEY_NAME.to_string());
+        }
+        Self(keys)
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(&config.api_keys, config.admin_api_key.as_deref())
+    }
+
+    pub fn shared(config: &AppConfig) -> SharedKeyRing {
+        Arc::new(ArcSwap::from_pointee(Self::from_config(config)))
+    }
+
+    /// How many keys are accepted, the admin key included.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The name of `key`, if it is accepted.
+    pub fn resolve(&self, key: &str) -> Option<&str> {
+        self.0.get(&digest(key)).map(String::as_str)
+    }
+}
+
+fn digest(key: &str) -> [u8; 32] {
+    Sha256::digest(key.as_bytes()).into()
+}
 
 pub async fn require_api_key(
     State(state): State<AppState>,
-    request: Request<Body>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    let provided = request
+    // Always removed, so a key in the URL never reaches handlers or anything they log.
+    let query_key = take_query_api_key(&mut request);
+    let header_key = request
         .headers()
         .get(API_KEY_HEADER)
-        .and_then(|value| value.to_str().ok());
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let bearer_key = bearer_token(&request);
 
-    match provided {
-        Some(value) if value == state.api_key.as_ref() => Ok(next.run(request).await),
-        _ => Ok((
-            StatusCode::UNAUTHORIZED,
+    let provided = match (header_key, bearer_key, query_key) {
+        (Some(header_key), _, _) => header_key,
+        (None, Some(bearer_key), _) => {
+            // Handlers read the key from the header, e.g. to unlock admin-only tools.
+            if let Ok(value) = HeaderValue::from_str(&bearer_key) {
+                request.headers_mut().insert(API_KEY_HEADER, value);
+            }
+            bearer_key
+        }
+        (None, None, Some(query_key)) => {
+            if !state.config.allow_query_api_key || request.uri().path() != QUERY_API_KEY_PATH {
+                return Ok(unauthorized(
+                    "API keys are not accepted in the query string; send the x-api-key header or Authorization: Bearer <key>",
+                ));
+            }
+            state.service.metrics().record_query_api_key();
+            tracing::debug!(
+                path = QUERY_API_KEY_PATH,
+                "API key supplied in the query string"
+            );
+            // Copied into the header for handlers, as with bearer keys.
+            if let Ok(value) = HeaderValue::from_str(&query_key) {
+                request.headers_mut().insert(API_KEY_HEADER, value);
+            }
+            query_key
+        }
+        (None, None, None) => return Ok(unauthorized(MISSING_KEY_MESSAGE)),
+    };
+
+    // Every rejection reads the same, whichever keys are configured.
+    let Some(name) = state.api_keys.load().resolve(&provided).map(str::to_string) else {
+        return Ok(unauthorized(MISSING_KEY_MESSAGE));
+    };
+    tracing::Span::current().record("api_key", tracing::field::display(&name));
+    if let Some(record) = request.extensions().get::<AccessLogRecord>() {
+        record.set_api_key(&name);
+    }
+    request.extensions_mut().insert(ApiKeyName(name));
+    Ok(next.run(request).await)
+}
+
+/// Refuses every key but `ADMIN_API_KEY` with 403. Layered inside [`require_api_key`],
+/// whose [`ApiKeyName`] it reads.
+pub async fn require_admin_key(request: Request<Body>, next: Next) -> Response {
+    let is_admin = request
+        .extensions()
+        .get::<ApiKeyName>()
+        .is_some_and(|ApiKeyName(name)| name == ADMIN_KEY_NAME);
+    if !is_admin {
+        return (
+            StatusCode::FORBIDDEN,
             Json(json!({
                 "error": {
-                    "code": "unauthorized",
-                    "message": "missing or invalid API key"
+                    "code": "forbidden",
+                    "message": format!("{} requires the admin API key", request.uri().path())
                 }
             })),
         )
-            .into_response()),
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// The key from an `Authorization: Bearer <key>` header; other schemes are ignored.
+fn bearer_token(request: &Request<Body>) -> Option<String> {
+    let value = request
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?;
+    let scheme = value.get(..BEARER_PREFIX.len())?;
+    let token = value[BEARER_PREFIX.len()..].trim();
+    (scheme.eq_ignore_ascii_case(BEARER_PREFIX) && !token.is_empty()).then(|| token.to_string())
+}
+
+fn unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({
+            "error": {
+                "code": "unauthorized",
+                "message": message
+            }
+        })),
+    )
+        .into_response()
+}
+
+/// Remove `api_key` from the request's query string, returning its value.
+fn take_query_api_key(request: &mut Request<Body>) -> Option<String> {
+    let query = request.uri().query()?;
+    let parsed = Url::parse(&format!("http://localhost/?{query}")).ok()?;
+    let mut key = None;
+    let mut kept = Vec::new();
+    for (name, value) in parsed.query_pairs() {
+        if name == API_KEY_QUERY_PARAM {
+            key = Some(value.into_owned());
+        } else {
+            kept.push((name.into_owned(), value.into_owned()));
+        }
+    }
+    key.as_ref()?;
+
+    let mut rebuilt = Url::parse("http://localhost/").ok()?;
+    if !kept.is_empty() {
+        rebuilt.query_pairs_mut().extend_pairs(kept);
     }
+    let path_and_query = match rebuilt.query() {
+        Some(query) => format!("{}?{query}", request.uri().path()),
+        None => request.uri().path().to_string(),
+    };
+    // Fails closed: a URI that cannot be rebuilt without the key is treated as keyless.
+    let mut parts = request.uri().clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().ok()?);
+    *request.uri_mut() = Uri::from_parts(parts).ok()?;
+    key
 }
@@ -0,0 +1,100 @@
+use axum::Json;
+use axum::http::{Method, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use serde_json::{Value, json};
+
+use crate::features::mcp::dto::{JsonRpcError, JsonRpcErrorResponse};
+use crate::features::mcp::errors::JsonRpcErrorCode;
+
+/// The MCP endpoint, suggested to clients that miss it.
+pub const MCP_PATH: &str = "/api/mcp";
+/// Fixed paths served by the router, for "did you mean" hints.
+const KNOWN_PATHS: &[&str] = &[
+    MCP_PATH,
+    "/api/health",
+    "/api/health/details",
+    "/api/ready",
+    "/api/version",
+    "/api/metrics",
+    "/api/audit",
+];
+/// Most edits a mistyped path may be from a known one to be suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// A JSON-RPC `-32600` error with `status`, for requests refused before any JSON-RPC
+/// handling, so MCP clients get an envelope to report instead of an empty body.
+pub fn invalid_request(status: StatusCode, message: String, data: Option<Value>) -> Response {
+    let error = JsonRpcErrorResponse {
+        jsonrpc: "2.0".to_string(),
+        id: Value::Null,
+        error: JsonRpcError {
+            code: JsonRpcErrorCode::InvalidRequest.code(),
+            message,
+            data,
+        },
+    };
+    (status, Json(json!(error))).into_response()
+}
+
+/// Router fallback: no route matches the path. Suggests the closest known path.
+pub async fn route_not_found(method: Method, uri: Uri) -> Response {
+    let path = uri.path();
+    let hint = match closest_path(path) {
+        Some(suggestion) if suggestion == MCP_PATH => {
+            format!("the MCP endpoint only accepts POST application/json; did you mean {MCP_PATH}?")
+        }
+        Some(suggestion) => format!("did you mean {suggestion}?"),
+        None => format!("MCP requests are sent as POST application/json to {MCP_PATH}"),
+    };
+    invalid_request(
+        StatusCode::NOT_FOUND,
+        format!("no route for {method} {path}"),
+        Some(json!({ "hint": hint })),
+    )
+}
+
+/// Method-not-allowed fallback: the path exists but not for this method. axum adds the
+/// `Allow` header listing the methods that are.
+pub async fn method_not_allowed(method: Method, uri: Uri) -> Response {
+    let path = uri.path();
+    let hint = if path == MCP_PATH {
+        "the MCP endpoint only accepts POST application/json (GET opens the notification stream, DELETE ends a session)".to_string()
+    } else {
+        "see the Allow header for the methods this path accepts".to_string()
+    };
+    invalid_request(
+        StatusCode::METHOD_NOT_ALLOWED,
+        format!("{method} is not allowed on {path}"),
+        Some(json!({ "hint": hint })),
+    )
+}
+
+fn closest_path(path: &str) -> Option<&'static str> {
+    let path = path.trim_end_matches('/').to_ascii_lowercase();
+    KNOWN_PATHS
+        .iter()
+        .map(|known| (*known, edit_distance(&path, known)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(known, _)| known)
+}
+
+/// Levenshtein distance, counting a swap of adjacent characters as one edit.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let mut rows = vec![(0..=right.len()).collect::<Vec<_>>()];
+    for (i, a) in left.iter().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b) in right.iter().enumerate() {
+            let substitution = rows[i][j] + usize::from(a != b);
+            let mut best = substitution.min(rows[i][j + 1] + 1).min(row[j] + 1);
+            if i > 0 && j > 0 && *a == right[j - 1] && left[i - 1] == *b {
+                best = best.min(rows[i - 1][j - 1] + 1);
+            }
+            row.push(best);
+        }
+        rows.push(row);
+    }
+    rows[left.len()][right.len()]
+}
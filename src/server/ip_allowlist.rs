@@ -0,0 +1,109 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, Request, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::json;
+
+use crate::server::AppState;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// With `MCP_ALLOWED_CIDRS` set, refuse requests whose client address is outside every
+/// listed network with 403, before the API key is checked. The address is the TCP peer,
+/// or with `TRUST_PROXY_HEADERS=true` the one the nearest proxy recorded in `Forwarded`
+/// (or `X-Forwarded-For`). A request with no address to check, such as one over
+/// `MCP_UNIX_SOCKET` without a trusted header, is refused.
+pub async fn require_allowed_ip(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(allowed) = &state.config.allowed_cidrs else {
+        return next.run(request).await;
+    };
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip());
+    let forwarded = state
+        .config
+        .trust_proxy_headers
+        .then(|| forwarded_client(request.headers()))
+        .flatten();
+    let client = forwarded.or(peer);
+
+    match client {
+        Some(address) if allowed.contains(address) => next.run(request).await,
+        _ => {
+            tracing::warn!(
+                client = client.map(|address| address.to_string()).as_deref(),
+                "request refused by MCP_ALLOWED_CIDRS"
+            );
+            forbidden()
+        }
+    }
+}
+
+/// The client address the nearest proxy recorded: the last `for=` of `Forwarded`, else the
+/// last `X-Forwarded-For` entry. Earlier entries come from further away and may have been
+/// made up by the client, so they are ignored. `None` when the last entry is not an
+/// address (e.g. `unknown` or an obfuscated identifier).
+fn forwarded_client(headers: &HeaderMap) -> Option<IpAddr> {
+    let last_forwarded = headers
+        .get_all(header::FORWARDED)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("for")
+                    .then(|| value.trim().trim_matches('"'))
+            })
+        })
+        .next_back();
+    if let Some(node) = last_forwarded {
+        return parse_node(node);
+    }
+
+    headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .rfind(|entry| !entry.is_empty())
+        .and_then(parse_node)
+}
+
+/// An address with or without a port: `192.0.2.1`, `192.0.2.1:4711`, `2001:db8::1` or
+/// `[2001:db8::1]:4711`.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    node.parse::<IpAddr>()
+        .ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|address| address.ip()))
+        .or_else(|| {
+            node.strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|address| address.parse().ok())
+        })
+}
+
+fn forbidden() -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": {
+                "code": "forbidden",
+                "message": "this address is not allowed to call the MCP endpoint"
+            }
+        })),
+    )
+        .into_response()
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::task::JoinHandle;
+
+use crate::config::{ApiKeysConfig, AppConfig};
+use crate::core::error::AppError;
+use crate::server::{KeyRing, SharedKeyRing};
+
+/// What identifies a version of the key file: its modification time and length.
+type Fingerprint = (SystemTime, u64);
+
+/// Poll `MCP_API_KEY_FILE` and swap `keys` for its contents whenever it changes. A file
+/// that cannot be read or parsed leaves the current keys in place. `None` when no key file
+/// is configured.
+///
+/// The audit digest secret stays the first key loaded at start-up.
+pub fn spawn_key_file_reload(keys: SharedKeyRing, config: &AppConfig) -> Option<JoinHandle<()>> {
+    let path = config.api_key_file.clone()?;
+    let admin_key = config.admin_api_key.clone();
+    let poll = Duration::from_secs(config.api_key_file_poll_secs.max(1));
+    // The keys in effect were loaded from this version at start-up.
+    let mut loaded = fingerprint(&path).ok();
+
+    Some(tokio::spawn(async move {
+        let mut unreadable = false;
+        let mut interval = tokio::time::interval(poll);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let current = match fingerprint(&path) {
+                Ok(current) => current,
+                Err(error) => {
+                    if !unreadable {
+                        tracing::error!(%path, %error, "cannot read MCP_API_KEY_FILE; keeping the current keys");
+                        unreadable = true;
+                    }
+                    continue;
+                }
+            };
+            unreadable = false;
+            if loaded == Some(current) {
+                continue;
+            }
+            loaded = Some(current);
+
+            match reload(&path, admin_key.as_deref()) {
+                Ok(ring) => {
+                    tracing::info!(%path, keys = ring.len(), "reloaded API keys");
+                    keys.store(Arc::new(ring));
+                }
+                Err(error) => {
+                    tracing::error!(%path, %error, "invalid MCP_API_KEY_FILE; keeping the current keys");
+                }
+            }
+        }
+    }))
+}
+
+fn fingerprint(path: &str) -> std::io::Result<Fingerprint> {
+    let metadata = std::fs::metadata(path)?;
+    Ok((metadata.modified()?, metadata.len()))
+}
+
+fn reload(path: &str, admin_key: Option<&str>) -> Result<KeyRing, AppError> {
+    let keys = ApiKeysConfig::from_file(path)?;
+    keys.check_admin_key(admin_key)?;
+    Ok(KeyRing::new(&keys, admin_key))
+}
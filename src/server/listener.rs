@@ -0,0 +1,213 @@
+use std::fmt;
+use std::future::IntoFuture;
+use std::io;
+use std::net::SocketAddr;
+
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::ListenAddress;
+use crate::core::error::AppError;
+
+/// A bound TCP or Unix domain socket, ready to serve the router.
+pub enum Listener {
+    Tcp(TcpListener),
+    /// A TCP socket served over HTTPS.
+    Tls(std::net::TcpListener, RustlsConfig),
+    #[cfg(unix)]
+    Unix(unix::SocketListener),
+}
+
+impl Listener {
+    pub async fn bind(address: &ListenAddress) -> Result<Self, AppError> {
+        match address {
+            ListenAddress::Tcp(address) => TcpListener::bind(address)
+                .await
+                .map(Self::Tcp)
+                .map_err(|err| AppError::internal(format!("failed to bind {address}: {err}"))),
+            #[cfg(unix)]
+            ListenAddress::Unix(path) => unix::SocketListener::bind(path).map(Self::Unix),
+            #[cfg(not(unix))]
+            ListenAddress::Unix(_) => Err(AppError::configuration(
+                "MCP_UNIX_SOCKET is only supported on Unix",
+            )),
+        }
+    }
+
+    /// Serve HTTPS with `acceptor` on a TCP listener. Unix sockets are rejected at
+    /// configuration time and here.
+    pub fn with_tls(self, acceptor: RustlsConfig) -> Result<Self, AppError> {
+        match self {
+            Self::Tcp(listener) => listener
+                .into_std()
+                .map(|listener| Self::Tls(listener, acceptor))
+                .map_err(|err| AppError::internal(format!("failed to set up TLS listener: {err}"))),
+            Self::Tls(listener, _) => Ok(Self::Tls(listener, acceptor)),
+            #[cfg(unix)]
+            Self::Unix(_) => Err(AppError::configuration(
+                "TLS is only served over TCP, not MCP_UNIX_SOCKET",
+            )),
+        }
+    }
+
+    /// Serve `app` until `stop` is cancelled and open connections have finished.
+    pub async fn serve(self, app: Router, stop: CancellationToken) -> io::Result<()> {
+        match self {
+            Self::Tcp(listener) => {
+                axum::serve(
+                    listener,
+                    app.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .with_graceful_shutdown(stop.cancelled_owned())
+                .into_future()
+                .await
+            }
+            Self::Tls(listener, acceptor) => {
+                let handle = axum_server::Handle::new();
+                let shutdown = handle.clone();
+                tokio::spawn(async move {
+                    stop.cancelled().await;
+                    shutdown.graceful_shutdown(None);
+                });
+                axum_server::from_tcp_rustls(listener, acceptor)
+                    .handle(handle)
+                    .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                    .await
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => listener.serve(app, stop).await,
+        }
+    }
+}
+
+impl fmt::Display for Listener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(listener) => match listener.local_addr() {
+                Ok(address) => address.fmt(f),
+                Err(_) => f.write_str("tcp"),
+            },
+            Self::Tls(listener, _) => match listener.local_addr() {
+                Ok(address) => address.fmt(f),
+                Err(_) => f.write_str("tcp"),
+            },
+            #[cfg(unix)]
+            Self::Unix(listener) => write!(f, "unix:{}", listener.path().display()),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::io;
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use axum::Router;
+    use hyper::server::conn::http1;
+    use hyper_util::rt::TokioIo;
+    use hyper_util::service::TowerToHyperService;
+    use tokio::net::UnixListener;
+    use tokio::task::JoinSet;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::core::error::AppError;
+
+    const SOCKET_MODE: u32 = 0o660;
+
+    /// A listening Unix socket whose file is removed when it is dropped.
+    pub struct SocketListener {
+        listener: UnixListener,
+        path: PathBuf,
+    }
+
+    impl SocketListener {
+        /// Bind `path`, replacing a stale socket left by a previous run but never a live
+        /// one or a regular file.
+        pub fn bind(path: &Path) -> Result<Self, AppError> {
+            let failed = |err: io::Error| {
+                AppError::internal(format!("failed to bind {}: {err}", path.display()))
+            };
+            if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                if !metadata.file_type().is_socket() {
+                    return Err(AppError::configuration(format!(
+                        "MCP_UNIX_SOCKET {} exists and is not a socket",
+                        path.display()
+                    )));
+                }
+                if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                    return Err(AppError::configuration(format!(
+                        "MCP_UNIX_SOCKET {} is in use by another process",
+                        path.display()
+                    )));
+                }
+                std::fs::remove_file(path).map_err(failed)?;
+            }
+
+            let listener = UnixListener::bind(path).map_err(failed)?;
+            let socket = Self {
+                listener,
+                path: path.to_path_buf(),
+            };
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(SOCKET_MODE))
+                .map_err(failed)?;
+            Ok(socket)
+        }
+
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+
+        /// Accept connections until `stop` is cancelled, then let each connection finish
+        /// its current request.
+        pub async fn serve(self, app: Router, stop: CancellationToken) -> io::Result<()> {
+            let mut connections = JoinSet::new();
+            loop {
+                let stream = tokio::select! {
+                    accepted = self.listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(error) => {
+                            tracing::warn!(%error, "failed to accept a unix socket connection");
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                            continue;
+                        }
+                    },
+                    () = stop.cancelled() => break,
+                };
+
+                let service = TowerToHyperService::new(app.clone());
+                let stop = stop.clone();
+                connections.spawn(async move {
+                    let connection =
+                        http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+                    tokio::pin!(connection);
+                    let result = tokio::select! {
+                        result = connection.as_mut() => result,
+                        () = stop.cancelled() => {
+                            connection.as_mut().graceful_shutdown();
+                            connection.await
+                        }
+                    };
+                    if let Err(error) = result {
+                        tracing::debug!(%error, "unix socket connection closed with an error");
+                    }
+                });
+                while connections.try_join_next().is_some() {}
+            }
+
+            while connections.join_next().await.is_some() {}
+            Ok(())
+        }
+    }
+
+    impl Drop for SocketListener {
+        fn drop(&mut self) {
+            if let Err(error) = std::fs::remove_file(&self.path) {
+                tracing::warn!(path = %self.path.display(), %error, "failed to remove unix socket");
+            }
+        }
+    }
+}
@@ -1,5 +1,23 @@
+pub mod access_log;
 pub mod auth;
+pub mod fallback;
+pub mod ip_allowlist;
+pub mod key_file;
+pub mod listener;
+pub mod panic;
+pub mod request_id;
+pub mod router;
 pub mod state;
+pub mod tls;
 
-pub use auth::require_api_key;
-pub use state::AppState;
+pub use access_log::{AccessLogRecord, access_log};
+pub use auth::{ApiKeyName, KeyRing, SharedKeyRing, require_admin_key, require_api_key};
+pub use fallback::{method_not_allowed, route_not_found};
+pub use ip_allowlist::require_allowed_ip;
+pub use key_file::spawn_key_file_reload;
+pub use listener::Listener;
+pub use panic::PanicResponder;
+pub use request_id::{assign_request_id, current_request_id};
+pub use router::build_router;
+pub use state::{AppState, InFlight, InFlightGuard, Readiness};
+pub use tls::{load_tls_config, reload_tls_config, spawn_certificate_reload};
@@ -0,0 +1,57 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde_json::{Value, json};
+use tower_http::catch_panic::ResponseForPanic;
+
+use crate::core::error::panic_message;
+use crate::features::mcp::dto::{JsonRpcError, JsonRpcErrorResponse};
+use crate::features::mcp::errors::JsonRpcErrorCode;
+use crate::features::mcp::service::McpService;
+use crate::server::request_id::current_request_id;
+
+/// Answers a request whose handler panicked with a JSON-RPC error rather than axum's empty
+/// 500, so MCP clients still get an envelope to report. The JSON-RPC id is unknown by then
+/// (the body has been consumed), so it is `null` and `data.requestId` names the request
+/// for matching against the server logs. Used with `tower_http`'s `CatchPanicLayer`.
+#[derive(Clone)]
+pub struct PanicResponder {
+    service: Arc<McpService>,
+}
+
+impl PanicResponder {
+    pub fn new(service: Arc<McpService>) -> Self {
+        Self { service }
+    }
+}
+
+impl ResponseForPanic for PanicResponder {
+    type ResponseBody = axum::body::Body;
+
+    fn response_for_panic(&mut self, panic: Box<dyn Any + Send + 'static>) -> Response {
+        let request_id = current_request_id();
+        self.service.metrics().record_panic("request");
+        tracing::error!(
+            panic = panic_message(panic.as_ref()),
+            "request handler panicked"
+        );
+
+        let mut data = json!({ "build": self.service.build_info() });
+        if let Some(request_id) = request_id {
+            data["requestId"] = json!(request_id);
+        }
+        let error = JsonRpcErrorResponse {
+            jsonrpc: "2.0".to_string(),
+            id: Value::Null,
+            error: JsonRpcError {
+                code: JsonRpcErrorCode::ServerError.code(),
+                message: "internal server error".to_string(),
+                data: Some(data),
+            },
+        };
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!(error))).into_response()
+    }
+}
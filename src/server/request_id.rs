@@ -0,0 +1,54 @@
+use axum::body::Body;
+use axum::http::{HeaderValue, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Instrument;
+
+use crate::core::correlation::correlation_id_for;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// The id of the request being served, for code that cannot see its headers (e.g. the
+/// panic responder).
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Give every request an id: the client's `X-Request-Id` when well-formed, otherwise a
+/// fresh one. Handlers see it in the request header, every event logged while the request
+/// is served carries it through the `request` span, and the response echoes it. The span's
+/// `api_key` field is filled in with the key's name once the request is authenticated.
+pub async fn assign_request_id(mut request: Request<Body>, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok());
+    let request_id = correlation_id_for(provided);
+    // Always well-formed, so the conversion cannot fail.
+    let header = HeaderValue::from_str(&request_id).ok();
+    if let Some(header) = &header {
+        request
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER, header.clone());
+    }
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+        api_key = tracing::field::Empty,
+    );
+    let mut response = CURRENT_REQUEST_ID
+        .scope(request_id, next.run(request))
+        .instrument(span)
+        .await;
+    if let Some(header) = header {
+        response.headers_mut().insert(REQUEST_ID_HEADER, header);
+    }
+    response
+}
@@ -0,0 +1,140 @@
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use axum::middleware;
+use axum::routing::{get, post};
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::CorsOrigins;
+
+use crate::features::audit::handle_audit_log;
+use crate::features::export::handle_export_dataset;
+use crate::features::mcp::{
+    handle_health_details, handle_healthcheck, handle_mcp, handle_mcp_delete, handle_mcp_stream,
+    handle_metrics, handle_readiness, handle_version,
+};
+use crate::server::{
+    AppState, PanicResponder, access_log, assign_request_id, method_not_allowed, require_admin_key,
+    require_allowed_ip, require_api_key, route_not_found,
+};
+
+pub fn build_router(state: AppState) -> Router {
+    // `GET /api/mcp` below forwards server-initiated notifications.
+    state.service.enable_push_notifications();
+
+    let mut router =
+        Router::new()
+            .route(
+                "/api/health",
+                get(handle_healthcheck).layer(middleware::from_fn(access_log)),
+            )
+            .route("/api/ready", get(handle_readiness))
+            .route("/api/version", get(handle_version))
+            .route(
+                "/api/health/details",
+                get(handle_health_details)
+                    .fallback(method_not_allowed)
+                    .layer(middleware::from_fn_with_state(
+                        state.clone(),
+                        require_api_key,
+                    )),
+            )
+            .route(
+                "/api/metrics",
+                get(handle_metrics).fallback(method_not_allowed).layer(
+                    middleware::from_fn_with_state(state.clone(), require_api_key),
+                ),
+            )
+            .route(
+                "/api/mcp",
+                post(handle_mcp)
+                    .get(handle_mcp_stream)
+                    .delete(handle_mcp_delete)
+                    // Before the layers, so a refused method is only revealed to callers
+                    // that pass them.
+                    .fallback(method_not_allowed)
+                    .layer(DefaultBodyLimit::max(state.config.max_request_bytes))
+                    .layer(middleware::from_fn_with_state(
+                        state.clone(),
+                        require_api_key,
+                    ))
+                    .layer(middleware::from_fn_with_state(
+                        state.clone(),
+                        require_allowed_ip,
+                    ))
+                    // Outermost, so refused addresses, rejected keys and oversized bodies are
+                    // logged too.
+                    .layer(middleware::from_fn(access_log)),
+            );
+
+    if state.service.audit_log().is_some() {
+        router = router.route(
+            "/api/audit",
+            get(handle_audit_log)
+                .fallback(method_not_allowed)
+                .layer(middleware::from_fn(require_admin_key))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_api_key,
+                )),
+        );
+    }
+
+    if state.config.enable_rest_api {
+        router = router.route(
+            "/api/export/:dataset",
+            get(handle_export_dataset)
+                .fallback(method_not_allowed)
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_api_key,
+                )),
+        );
+    }
+
+    // JSON-RPC shaped 404s and 405s rather than axum's empty ones. Added after every route,
+    // since the method fallback only reaches routes registered before it; keyed routes set
+    // theirs above, behind the key check.
+    router = router
+        .fallback(route_not_found)
+        .method_not_allowed_fallback(method_not_allowed);
+
+    // Outside every route's `require_api_key`, so preflight requests (which carry no
+    // key) are answered here.
+    if let Some(origins) = &state.config.cors_allowed_origins {
+        router = router.layer(cors_layer(origins));
+    }
+
+    // Inside `assign_request_id`, so panic responses still carry the request id.
+    router
+        .layer(CatchPanicLayer::custom(PanicResponder::new(
+            state.service.clone(),
+        )))
+        .layer(middleware::from_fn(assign_request_id))
+        .with_state(state)
+}
+
+fn cors_layer(origins: &CorsOrigins) -> CorsLayer {
+    const SESSION_ID: HeaderName = HeaderName::from_static("mcp-session-id");
+
+    let allow_origin = match origins {
+        CorsOrigins::Any => AllowOrigin::any(),
+        CorsOrigins::List(origins) => AllowOrigin::list(
+            origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        ),
+    };
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::POST, Method::GET, Method::DELETE, Method::OPTIONS])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static("x-api-key"),
+            HeaderName::from_static("mcp-protocol-version"),
+            SESSION_ID,
+        ])
+        .expose_headers([SESSION_ID])
+}
@@ -1,18 +1,116 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
 
+use tokio::sync::Notify;
+
+use crate::config::AppConfig;
+use crate::core::concurrency::ConcurrencyLimit;
 use crate::features::mcp::McpService;
+use crate::features::parliament::ParliamentClient;
+use crate::server::SharedKeyRing;
+
+/// Whether start-up has finished: `/api/ready` answers 503 and `/api/mcp` refuses requests
+/// until `main` marks it ready.
+#[derive(Clone, Default)]
+pub struct Readiness(Arc<AtomicBool>);
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_ready(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// Count of `/api/mcp` requests still being answered, so shutdown can wait for them.
+#[derive(Clone, Default)]
+pub struct InFlight(Arc<InFlightInner>);
+
+#[derive(Default)]
+struct InFlightInner {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+impl InFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request until the returned guard is dropped.
+    pub fn track(&self) -> InFlightGuard {
+        self.0.count.fetch_add(1, Ordering::AcqRel);
+        InFlightGuard(self.0.clone())
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.count.load(Ordering::Acquire)
+    }
+
+    /// Wait up to `grace` for every tracked request to finish; returns how many were still
+    /// running when it gave up.
+    pub async fn drain(&self, grace: Duration) -> usize {
+        let idle = async {
+            loop {
+                let notified = self.0.idle.notified();
+                if self.count() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        let _ = tokio::time::timeout(grace, idle).await;
+        self.count()
+    }
+}
+
+pub struct InFlightGuard(Arc<InFlightInner>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct AppState {
     pub service: Arc<McpService>,
-    pub api_key: Arc<String>,
+    pub parliament_client: Arc<ParliamentClient>,
+    pub config: Arc<AppConfig>,
+    pub api_keys: SharedKeyRing,
+    pub readiness: Readiness,
+    pub in_flight: InFlight,
+    /// Slots for `POST /api/mcp` (`MAX_CONCURRENT_REQUESTS`).
+    pub request_limit: Arc<ConcurrencyLimit>,
 }
 
 impl AppState {
-    pub fn new(service: Arc<McpService>, api_key: String) -> Self {
+    pub fn new(
+        service: Arc<McpService>,
+        parliament_client: Arc<ParliamentClient>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        let request_limit = Arc::new(ConcurrencyLimit::new(
+            config.max_concurrent_requests,
+            Duration::from_secs(config.request_queue_wait_secs),
+        ));
         Self {
+            api_keys: service.key_ring(),
             service,
-            api_key: Arc::new(api_key),
+            parliament_client,
+            config,
+            readiness: Readiness::new(),
+            in_flight: InFlight::new(),
+            request_limit,
         }
     }
 }
@@ -0,0 +1,103 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::sign::{CertifiedKey, SingleCertAndKey};
+use tokio::task::JoinHandle;
+
+use crate::config::TlsConfig;
+use crate::core::error::AppError;
+
+/// Read and check the certificate chain and key named by `tls`, ready to hand to the
+/// listener. Fails when either file does not parse or the key does not belong to the
+/// certificate.
+pub fn load_tls_config(tls: &TlsConfig) -> Result<RustlsConfig, AppError> {
+    Ok(RustlsConfig::from_config(server_config(tls)?))
+}
+
+/// Swap the certificate served by `acceptor` for the current contents of the files;
+/// connections already open keep the old one. On error the old certificate stays.
+pub fn reload_tls_config(acceptor: &RustlsConfig, tls: &TlsConfig) -> Result<(), AppError> {
+    acceptor.reload_from_config(server_config(tls)?);
+    Ok(())
+}
+
+/// Re-read the certificate and key on every SIGHUP, so a renewed certificate is served
+/// without a restart. `None` where SIGHUP does not exist or cannot be watched.
+pub fn spawn_certificate_reload(acceptor: RustlsConfig, tls: TlsConfig) -> Option<JoinHandle<()>> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(error) => {
+                tracing::error!(%error, "cannot watch SIGHUP; TLS certificates will not reload");
+                return None;
+            }
+        };
+        Some(tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                let cert = tls.cert_path.display();
+                match reload_tls_config(&acceptor, &tls) {
+                    Ok(()) => tracing::info!(%cert, "reloaded TLS certificate"),
+                    Err(error) => tracing::error!(
+                        %cert,
+                        %error,
+                        "invalid TLS certificate or key; keeping the current one"
+                    ),
+                }
+            }
+        }))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (acceptor, tls);
+        None
+    }
+}
+
+fn server_config(tls: &TlsConfig) -> Result<Arc<ServerConfig>, AppError> {
+    let certs = CertificateDer::pem_file_iter(&tls.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| unreadable("MCP_TLS_CERT_PATH", &tls.cert_path, err))?;
+    if certs.is_empty() {
+        return Err(AppError::configuration(format!(
+            "MCP_TLS_CERT_PATH {} contains no PEM certificates",
+            tls.cert_path.display()
+        )));
+    }
+    let key = PrivateKeyDer::from_pem_file(&tls.key_path)
+        .map_err(|err| unreadable("MCP_TLS_KEY_PATH", &tls.key_path, err))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let signing_key = provider.key_provider.load_private_key(key).map_err(|err| {
+        AppError::configuration(format!(
+            "MCP_TLS_KEY_PATH {} is not a usable private key: {err}",
+            tls.key_path.display()
+        ))
+    })?;
+    let certified = CertifiedKey::new(certs, signing_key);
+    certified.keys_match().map_err(|_| {
+        AppError::configuration(format!(
+            "MCP_TLS_KEY_PATH {} does not match the certificate in MCP_TLS_CERT_PATH {}",
+            tls.key_path.display(),
+            tls.cert_path.display()
+        ))
+    })?;
+
+    let mut config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| AppError::internal(format!("failed to configure TLS: {err}")))?
+        .with_no_client_auth()
+        .with_cert_resolver(Arc::new(SingleCertAndKey::from(certified)));
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+fn unreadable(setting: &str, path: &Path, err: impl std::fmt::Display) -> AppError {
+    AppError::configuration(format!("cannot read {setting} {}: {err}", path.display()))
+}
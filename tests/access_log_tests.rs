@@ -0,0 +1,141 @@
+use axum::body::Body;
+use axum::http::{Request, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+mod common;
+
+/// The `access` lines written so far, parsed.
+fn access_lines(logs: &common::CapturedLogs) -> Vec<Value> {
+    logs.json_lines()
+        .into_iter()
+        .filter(|line| line["target"] == "access")
+        .collect()
+}
+
+fn post_mcp(api_key: Option<&str>, body: Value) -> Request<Body> {
+    let mut request = Request::post("/api/mcp")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header("X-Request-Id", "trace-access")
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    request.body(Body::from(body.to_string())).unwrap()
+}
+
+#[tokio::test]
+async fn tool_calls_are_logged_with_method_tool_key_status_and_size() {
+    let (logs, _guard) = common::capture_json();
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = harness
+        .router()
+        .oneshot(post_mcp(
+            Some("test"),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": {"name": "utilities.current_datetime", "arguments": {}}
+            }),
+        ))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    let lines = access_lines(&logs);
+    assert_eq!(lines.len(), 1, "{lines:?}");
+    let fields = &lines[0]["fields"];
+    assert_eq!(fields["message"], "request served");
+    assert_eq!(fields["http_method"], "POST");
+    assert_eq!(fields["path"], "/api/mcp");
+    assert_eq!(fields["rpc_method"], "tools/call");
+    assert_eq!(fields["tool"], "utilities.current_datetime");
+    assert_eq!(fields["api_key"], "default");
+    assert_eq!(fields["status"], 200);
+    assert!(fields["latency_ms"].is_u64(), "{fields}");
+    assert_eq!(fields["response_bytes"], body.len());
+    assert_eq!(lines[0]["span"]["request_id"], "trace-access");
+}
+
+#[tokio::test]
+async fn health_checks_and_rejected_keys_are_logged() {
+    let (logs, _guard) = common::capture_json();
+    let harness = common::build_service(None, |_| {});
+
+    let health = Request::get("/api/health").body(Body::empty()).unwrap();
+    harness.router().oneshot(health).await.unwrap();
+    let rejected = post_mcp(
+        Some("wrong"),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+    );
+    harness.router().oneshot(rejected).await.unwrap();
+
+    let lines = access_lines(&logs);
+    assert_eq!(lines.len(), 2, "{lines:?}");
+    let health = &lines[0]["fields"];
+    assert_eq!(health["path"], "/api/health");
+    assert_eq!(health["status"], 200);
+    assert_eq!(health["rpc_method"], "-");
+    assert_eq!(health["api_key"], "-");
+    let rejected = &lines[1]["fields"];
+    assert_eq!(rejected["status"], 401);
+    assert_eq!(rejected["api_key"], "-");
+    // The body is never read once the key is refused.
+    assert_eq!(rejected["rpc_method"], "-");
+}
+
+#[tokio::test]
+async fn client_supplied_values_are_redacted_and_truncated() {
+    let (logs, _guard) = common::capture_json();
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let long_method = "x".repeat(200);
+    for body in [
+        json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "lookup SW1A 1AA",
+                "arguments": {"postcode": "SW1A 1AA", "searchTerm": "assisted dying"}
+            }
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "tools/call",
+            "params": {
+                "name": "parliament.lookup_constituency_offline",
+                "arguments": {"postcode": "SW1A1AA", "cacheOnly": true}
+            }
+        }),
+        json!({"jsonrpc": "2.0", "id": 5, "method": long_method}),
+    ] {
+        harness
+            .router()
+            .oneshot(post_mcp(Some("test"), body))
+            .await
+            .unwrap();
+    }
+
+    let lines = access_lines(&logs);
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert_eq!(lines[0]["fields"]["tool"], "lookup [postcode]");
+    assert_eq!(
+        lines[1]["fields"]["tool"],
+        "parliament.lookup_constituency_offline"
+    );
+    let method = lines[2]["fields"]["rpc_method"].as_str().unwrap();
+    assert_eq!(method, format!("{}…", "x".repeat(64)));
+    for line in &lines {
+        let text = line.to_string();
+        assert!(!text.contains("SW1A"), "{text}");
+        assert!(!text.contains("assisted dying"), "{text}");
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::body::{Body, to_bytes};
+use serde_json::json;
+use tokio::time::Instant;
+use tower::ServiceExt;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+use mp_writer_mcp_server::core::adaptive_limit::AdaptiveLimiter;
+
+mod common;
+
+const HOST: &str = "bills-api.parliament.uk";
+const RESPONSE_DELAY: Duration = Duration::from_millis(40);
+
+/// Answers 429 whenever more than `threshold` requests are being served at once.
+struct ConcurrencyCappedUpstream {
+    threshold: usize,
+    started: Mutex<Vec<Instant>>,
+    /// Requests being served when each request arrived, in arrival order.
+    concurrency: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Respond for ConcurrencyCappedUpstream {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let now = Instant::now();
+        let mut started = self.started.lock().unwrap();
+        started.retain(|start| now.duration_since(*start) < RESPONSE_DELAY);
+        started.push(now);
+        self.concurrency.lock().unwrap().push(started.len());
+        if started.len() > self.threshold {
+            return ResponseTemplate::new(429).set_delay(RESPONSE_DELAY);
+        }
+
+        let id: u32 = request
+            .url
+            .path()
+            .rsplit('/')
+            .next()
+            .and_then(|segment| segment.parse().ok())
+            .unwrap_or_default();
+        ResponseTemplate::new(200)
+            .set_delay(RESPONSE_DELAY)
+            .set_body_json(json!({
+                "value": {
+                    "id": id,
+                    "nameDisplayAs": format!("Member {id}"),
+                    "latestParty": {"name": "Independent"},
+                    "latestHouseMembership": {"membershipFrom": "Somewhere", "house": 1}
+                }
+            }))
+    }
+}
+
+/// Take every slot of the window, then report each request as successful.
+async fn fill_window(limiter: &AdaptiveLimiter) {
+    let slots = limiter.limits()[HOST];
+    let permits = futures::future::join_all((0..slots).map(|_| limiter.acquire(HOST))).await;
+    for permit in permits {
+        permit.succeeded();
+    }
+}
+
+#[tokio::test]
+async fn one_burst_of_push_back_halves_the_window_once() {
+    let limiter = AdaptiveLimiter::new(2, 8);
+    let permits = futures::future::join_all((0..8).map(|_| limiter.acquire(HOST))).await;
+    assert_eq!(limiter.limits()[HOST], 8);
+
+    for permit in permits {
+        permit.throttled();
+    }
+    assert_eq!(limiter.limits()[HOST], 4);
+
+    limiter.acquire(HOST).await.throttled();
+    assert_eq!(limiter.limits()[HOST], 2);
+    limiter.acquire(HOST).await.throttled();
+    assert_eq!(limiter.limits()[HOST], 2, "the floor holds");
+}
+
+#[tokio::test]
+async fn success_grows_the_window_by_about_one_slot_per_window() {
+    let limiter = AdaptiveLimiter::new(1, 4);
+    limiter.acquire(HOST).await.throttled();
+    limiter.acquire(HOST).await.throttled();
+    assert_eq!(limiter.limits()[HOST], 1);
+
+    limiter.acquire(HOST).await.succeeded();
+    assert_eq!(limiter.limits()[HOST], 2);
+
+    for _ in 0..5 {
+        limiter.acquire(HOST).await.succeeded();
+    }
+    assert_eq!(
+        limiter.limits()[HOST],
+        2,
+        "a window that is never filled does not grow"
+    );
+
+    fill_window(&limiter).await;
+    assert_eq!(limiter.limits()[HOST], 2, "2.5, then 2.9");
+    limiter.acquire(HOST).await.succeeded();
+    assert_eq!(limiter.limits()[HOST], 3);
+    for _ in 0..20 {
+        fill_window(&limiter).await;
+    }
+    assert_eq!(limiter.limits()[HOST], 4, "the ceiling holds");
+}
+
+#[tokio::test]
+async fn waiters_are_admitted_as_slots_free_up() {
+    let limiter = Arc::new(AdaptiveLimiter::new(1, 1));
+    let held = limiter.acquire(HOST).await;
+
+    let waiting = tokio::spawn({
+        let limiter = limiter.clone();
+        async move { limiter.acquire(HOST).await.succeeded() }
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!waiting.is_finished());
+
+    drop(held);
+    tokio::time::timeout(Duration::from_secs(1), waiting)
+        .await
+        .expect("waiter admitted")
+        .expect("task join");
+}
+
+#[tokio::test]
+async fn limiter_converges_below_the_upstream_concurrency_threshold() {
+    let threshold = 3;
+    let concurrency = Arc::new(Mutex::new(Vec::new()));
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ConcurrencyCappedUpstream {
+            threshold,
+            started: Mutex::new(Vec::new()),
+            concurrency: concurrency.clone(),
+        })
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.upstream_concurrency_ceiling = 8;
+    });
+    common::initialize(&harness.service).await;
+
+    let ids: Vec<u32> = (1..=60).collect();
+    common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": ids, "enableCache": false}),
+    )
+    .await;
+
+    let limits = harness.client.concurrency_limits();
+    let (_, limit) = limits.iter().next().expect("upstream host tracked");
+    assert!(*limit <= threshold + 1, "limit {limit} did not shrink");
+
+    let scrape = harness
+        .router()
+        .oneshot(
+            axum::http::Request::get("/api/metrics")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(scrape.into_body(), usize::MAX).await.unwrap();
+    let gauge = format!("mcp_upstream_concurrency_limit{{host=\"127.0.0.1\"}} {limit}");
+    assert!(
+        String::from_utf8_lossy(&body).contains(&gauge),
+        "missing {gauge}"
+    );
+
+    // The first burst arrives at the full ceiling; once the window has shrunk the upstream
+    // rarely sees more requests than its threshold (only the additive probe overshoots).
+    let concurrency = concurrency.lock().unwrap();
+    let (early, late) = concurrency.split_at(concurrency.len() / 2);
+    assert_eq!(early.iter().max(), Some(&8));
+    let rejected = |arrivals: &[usize]| arrivals.iter().filter(|seen| **seen > threshold).count();
+    assert!(
+        rejected(late) * 4 <= late.len(),
+        "upstream rejected {} of {} late requests",
+        rejected(late),
+        late.len()
+    );
+}
@@ -0,0 +1,179 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::ApiKeysConfig;
+
+mod common;
+
+const CI_KEY: &str = "ci-0123456789abcdef";
+const DESKTOP_KEY: &str = "desktop-0123456789abcdef";
+const ADMIN_KEY: &str = "admin-0123456789abcdef";
+
+fn harness() -> common::TestHarness {
+    common::build_service(None, |config| {
+        config.api_keys =
+            ApiKeysConfig::parse(&format!("ci:{CI_KEY}, desktop:{DESKTOP_KEY}")).unwrap();
+        config.api_key = CI_KEY.to_string();
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    })
+}
+
+fn post(api_key: &str, body: Value) -> Request<Body> {
+    Request::post("/api/mcp")
+        .header("x-api-key", api_key)
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn initialize_call() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": common::PROTOCOL_VERSION,
+            "clientInfo": {"name": "test-client", "version": "1.0"},
+            "capabilities": {}
+        }
+    })
+}
+
+async fn start_session(harness: &common::TestHarness, api_key: &str) {
+    for body in [
+        initialize_call(),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+    ] {
+        let response = harness.router().oneshot(post(api_key, body)).await.unwrap();
+        assert!(response.status().is_success());
+    }
+}
+
+async fn body_json(response: axum::response::Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap_or(Value::Null)
+}
+
+#[test]
+fn named_keys_are_validated() {
+    let parsed = ApiKeysConfig::parse(&format!("ci:{CI_KEY},desktop:{DESKTOP_KEY}")).unwrap();
+    let names: Vec<&str> = parsed.keys.iter().map(|key| key.name.as_str()).collect();
+    assert_eq!(names, ["ci", "desktop"]);
+
+    for (raw, expected) in [
+        (format!("ci:{CI_KEY},ci:{DESKTOP_KEY}"), "unique"),
+        ("ci:too-short".to_string(), "at least 16 characters"),
+        (CI_KEY.to_string(), "name:key"),
+        (format!("bad name:{CI_KEY}"), "invalid name"),
+        (format!("ci:{CI_KEY},desktop:{CI_KEY}"), "identical"),
+    ] {
+        let error = ApiKeysConfig::parse(&raw).unwrap_err().to_string();
+        assert!(error.contains(expected), "{raw}: {error}");
+        assert!(!error.contains(CI_KEY), "keys must not leak: {error}");
+    }
+}
+
+#[tokio::test]
+async fn every_named_key_is_accepted_and_unknown_keys_are_rejected_alike() {
+    let harness = harness();
+
+    for key in [CI_KEY, DESKTOP_KEY] {
+        let response = harness
+            .router()
+            .oneshot(post(key, initialize_call()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{key}");
+        let body = body_json(response).await;
+        assert!(body["result"].is_object(), "{key}: {body}");
+    }
+
+    let wrong = harness
+        .router()
+        .oneshot(post("test", initialize_call()))
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+    let wrong = body_json(wrong).await;
+    let missing = harness
+        .router()
+        .oneshot(
+            Request::post("/api/mcp")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(initialize_call().to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(body_json(missing).await, wrong);
+}
+
+#[tokio::test]
+async fn the_key_name_is_audited_and_logged() {
+    let (logs, _guard) = common::capture();
+    let harness = harness();
+    start_session(&harness, DESKTOP_KEY).await;
+
+    let response = harness
+        .router()
+        .oneshot(post(
+            DESKTOP_KEY,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "utilities.current_datetime", "arguments": {}}
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let read_audit = |api_key: &str| {
+        harness.router().oneshot(
+            Request::get("/api/audit")
+                .header("x-api-key", api_key)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let refused = read_audit(CI_KEY).await.unwrap();
+    assert_eq!(refused.status(), StatusCode::FORBIDDEN);
+    let audit = body_json(read_audit(ADMIN_KEY).await.unwrap()).await;
+    assert_eq!(audit["entries"][0]["apiKeyName"], "desktop", "{audit}");
+
+    let logs = logs.text();
+    assert!(logs.contains("api_key=desktop"), "{logs}");
+    assert!(!logs.contains("api_key=ci"), "{logs}");
+}
+
+#[tokio::test]
+async fn sessions_belong_to_the_key_that_started_them() {
+    let harness = harness();
+    start_session(&harness, DESKTOP_KEY).await;
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {"name": "utilities.current_datetime", "arguments": {}}
+    });
+
+    let other_key = harness
+        .router()
+        .oneshot(post(CI_KEY, call.clone()))
+        .await
+        .unwrap();
+    let other_key = body_json(other_key).await;
+    assert_eq!(other_key["error"]["code"], -32002, "{other_key}");
+
+    let own_key = harness
+        .router()
+        .oneshot(post(DESKTOP_KEY, call))
+        .await
+        .unwrap();
+    let own_key = body_json(own_key).await;
+    assert!(own_key["result"]["isError"].is_null(), "{own_key}");
+}
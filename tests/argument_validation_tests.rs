@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn bills_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(".*"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn fetch_bills(strict_args: bool, arguments: Value) -> Value {
+    let server = bills_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.strict_args = strict_args;
+    });
+    common::initialize(&harness.service).await;
+    common::call_tool(&harness.service, "parliament.fetch_bills", arguments).await
+}
+
+fn error_message(response: &Value) -> &str {
+    assert_eq!(response["error"]["code"], -32602, "response: {response}");
+    response["error"]["message"].as_str().unwrap_or_default()
+}
+
+#[tokio::test]
+async fn strict_mode_names_unknown_properties() {
+    let response = fetch_bills(
+        true,
+        json!({"searchTerm": "housing", "reason": "user asked", "trace": 1}),
+    )
+    .await;
+
+    let message = error_message(&response);
+    assert!(
+        message.contains("unknown properties `reason`, `trace`")
+            || message.contains("unknown properties `trace`, `reason`"),
+        "message: {message}"
+    );
+}
+
+#[tokio::test]
+async fn lenient_mode_strips_unknown_properties() {
+    let response = fetch_bills(
+        false,
+        json!({"searchTerm": "housing", "reason": "user asked"}),
+    )
+    .await;
+
+    assert!(response.get("error").is_none(), "response: {response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["items"],
+        json!([]),
+        "response: {response}"
+    );
+}
+
+#[tokio::test]
+async fn lenient_mode_still_rejects_type_violations() {
+    let response = fetch_bills(
+        false,
+        json!({"parliamentNumber": "fifty-nine", "reason": "user asked"}),
+    )
+    .await;
+
+    let message = error_message(&response);
+    assert!(message.contains("fifty-nine"), "message: {message}");
+    assert!(!message.contains("reason"), "message: {message}");
+}
+
+#[tokio::test]
+async fn strict_mode_suggests_the_closest_declared_names() {
+    let response = fetch_bills(true, json!({"serchTerm": "housing", "House": "commons"})).await;
+
+    let message = error_message(&response);
+    assert!(
+        message.contains("did you mean `searchTerm` for `serchTerm`, `house` for `House`?")
+            || message.contains("did you mean `house` for `House`, `searchTerm` for `serchTerm`?"),
+        "message: {message}"
+    );
+}
+
+#[tokio::test]
+async fn strict_mode_lists_declared_names_when_nothing_is_close() {
+    let response = fetch_bills(true, json!({"searchTerm": "housing", "reason": "x"})).await;
+
+    let message = error_message(&response);
+    assert!(message.contains("expected one of"), "message: {message}");
+    assert!(message.contains("`searchTerm`"), "message: {message}");
+}
+
+#[tokio::test]
+async fn null_optionals_are_treated_as_absent() {
+    let response = fetch_bills(
+        true,
+        json!({"searchTerm": "housing", "parliamentNumber": null, "fields": null}),
+    )
+    .await;
+
+    assert!(response.get("error").is_none(), "response: {response}");
+}
+
+#[tokio::test]
+async fn research_arguments_are_read_in_camel_case() {
+    let mock = Arc::new(common::MockParliamentDataSource::new());
+    let harness = common::build_service(Some(mock.clone()), |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate", "includeStateOfParties": true}),
+    )
+    .await;
+    let payload = &response["result"]["structuredContent"];
+    assert!(
+        payload["state_of_parties"].is_object(),
+        "response: {response}"
+    );
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate", "limit": 11}),
+    )
+    .await;
+    let message = error_message(&response);
+    assert!(message.contains("maximum of 10"), "message: {message}");
+}
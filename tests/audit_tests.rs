@@ -0,0 +1,141 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::core::audit::{AuditEntry, AuditLog, AuditOutcome};
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+
+async fn get_audit(router: axum::Router, uri: &str, api_key: Option<&str>) -> (StatusCode, Value) {
+    let mut request = Request::get(uri);
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn entry(timestamp: u64, tool: &str) -> AuditEntry {
+    AuditEntry {
+        id: None,
+        timestamp,
+        tool: tool.to_string(),
+        arguments_sha256: "00".to_string(),
+        duration_ms: 1,
+        outcome: AuditOutcome::Success,
+        error_kind: None,
+        correlation_id: None,
+        api_key_name: None,
+        arguments: None,
+        redacted_fields: Vec::new(),
+        replay_of: None,
+    }
+}
+
+#[tokio::test]
+async fn tool_calls_are_listed_newest_first_without_arguments() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    common::initialize(&harness.service).await;
+
+    common::call_tool(&harness.service, "utilities.current_datetime", json!({})).await;
+    common::call_tool(
+        &harness.service,
+        "parliament.lookup_constituency_offline",
+        json!({"postcode": "ZZ99 9ZZ"}),
+    )
+    .await;
+
+    let (status, body) = get_audit(harness.router(), "/api/audit?limit=10", Some(ADMIN_KEY)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["retentionDays"], 90);
+
+    let entries = body["entries"].as_array().expect("entries");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["tool"], "parliament.lookup_constituency_offline");
+    assert_eq!(entries[0]["outcome"], "rejected");
+    assert_eq!(entries[0]["errorKind"], "bad_request");
+    assert_eq!(entries[1]["tool"], "utilities.current_datetime");
+    assert_eq!(entries[1]["outcome"], "success");
+    assert!(entries[1].get("errorKind").is_none());
+    for entry in entries {
+        assert_eq!(entry["argumentsSha256"].as_str().map(str::len), Some(64));
+        assert!(entry["durationMs"].is_u64());
+        assert!(entry["correlationId"].is_string());
+    }
+
+    let stored: Vec<u8> = harness
+        .db
+        .open_tree("audit")
+        .expect("tree")
+        .iter()
+        .values()
+        .flat_map(|value| value.expect("value").to_vec())
+        .collect();
+    assert!(
+        !String::from_utf8_lossy(&stored).contains("ZZ99"),
+        "postcode must not be stored"
+    );
+
+    let (_, limited) = get_audit(harness.router(), "/api/audit?limit=1", Some(ADMIN_KEY)).await;
+    assert_eq!(limited["entries"].as_array().map(Vec::len), Some(1));
+}
+
+#[tokio::test]
+async fn audit_route_requires_the_admin_key() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+
+    let (status, _) = get_audit(harness.router(), "/api/audit", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) = get_audit(harness.router(), "/api/audit", Some("test")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"]["code"], "forbidden", "{body}");
+}
+
+#[tokio::test]
+async fn retention_sweep_prunes_old_entries() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let log = AuditLog::new(db.open_tree("audit").expect("tree"), "key");
+
+    for (timestamp, tool) in [(100, "a"), (200, "b"), (300, "c"), (300, "d")] {
+        log.record(&entry(timestamp, tool)).await.expect("record");
+    }
+
+    assert_eq!(log.prune_before(250).await.expect("prune"), 2);
+    let remaining: Vec<String> = log
+        .recent(10)
+        .await
+        .expect("recent")
+        .into_iter()
+        .map(|entry| entry.tool)
+        .collect();
+    assert_eq!(remaining, ["d", "c"]);
+}
+
+#[test]
+fn argument_digests_are_keyed_and_order_independent() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let tree = db.open_tree("audit").expect("tree");
+    let log = AuditLog::new(tree.clone(), "key-one");
+    let other = AuditLog::new(tree, "key-two");
+
+    let forward = json!({"postcode": "SW1A 1AA", "enableCache": true});
+    let reversed = json!({"enableCache": true, "postcode": "SW1A 1AA"});
+
+    assert_eq!(log.hash_arguments(&forward), log.hash_arguments(&reversed));
+    assert_ne!(log.hash_arguments(&forward), other.hash_arguments(&forward));
+}
@@ -0,0 +1,211 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::availability::AvailabilityTracker;
+use mp_writer_mcp_server::core::cache::CacheManager;
+use mp_writer_mcp_server::core::clock::MockClock;
+use mp_writer_mcp_server::core::stats::StatsRegistry;
+use mp_writer_mcp_server::features::parliament::{FetchBillsArgs, ParliamentClient};
+use mp_writer_mcp_server::features::research::{
+    ParliamentDataSource, ResearchRequestDto, ResearchService, ResearchStatus,
+};
+
+mod common;
+
+const BILLS_HOST: &str = "bills-api.parliament.uk";
+const CORE_DATASET_HOST: &str = "lda.data.parliament.uk";
+const LEGISLATION_HOST: &str = "www.legislation.gov.uk";
+
+fn request() -> ResearchRequestDto {
+    ResearchRequestDto {
+        topic: "Climate action".to_string(),
+        bill_keywords: vec![],
+        debate_keywords: vec![],
+        mp_id: None,
+        include_state_of_parties: true,
+        limit: Some(3),
+        max_age_secs: None,
+        cache_only: false,
+        locale: None,
+    }
+}
+
+fn service(
+    temp_dir: &tempfile::TempDir,
+    tree: &sled::Tree,
+    mock: Arc<common::MockParliamentDataSource>,
+) -> ResearchService {
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let data_source: Arc<dyn ParliamentDataSource> = mock;
+    ResearchService::new(config, data_source, tree.clone())
+}
+
+#[tokio::test]
+async fn all_hosts_up_collects_every_source() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let tree = db.open_tree("research").unwrap();
+    let mock = Arc::new(common::MockParliamentDataSource::new());
+    let service = service(&temp_dir, &tree, mock.clone());
+
+    let response = service.run_research(request()).await.unwrap();
+
+    assert_eq!(response.status, ResearchStatus::Ok);
+    assert!(response.advisories.is_empty(), "{:?}", response.advisories);
+    for collection in [
+        "bills",
+        "commonsdivisions",
+        "legislation",
+        "commonsdebates",
+        "stateofparties",
+    ] {
+        assert_eq!(mock.count_for(collection).await, 1, "{collection}");
+    }
+}
+
+#[tokio::test]
+async fn collections_on_a_down_host_are_skipped_with_one_advisory() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let tree = db.open_tree("research").unwrap();
+    let mock = Arc::new(common::MockParliamentDataSource::new().down(&[CORE_DATASET_HOST]));
+    let service = service(&temp_dir, &tree, mock.clone());
+
+    let response = service.run_research(request()).await.unwrap();
+
+    assert_eq!(response.status, ResearchStatus::Partial);
+    assert_eq!(response.bills.len(), 1);
+    assert_eq!(response.legislation.len(), 1);
+    assert!(response.votes.is_empty() && response.debates.is_empty());
+    assert!(response.state_of_parties.is_none());
+    // Votes, debates and state of parties all live on the linked-data host: none of them
+    // was queried, and each is noted once rather than once per search term.
+    for collection in ["commonsdivisions", "commonsdebates", "stateofparties"] {
+        assert_eq!(mock.count_for(collection).await, 0, "{collection}");
+    }
+    assert_eq!(mock.count_for("bills").await, 1);
+    assert_eq!(response.advisories.len(), 3, "{:?}", response.advisories);
+    assert!(
+        response
+            .advisories
+            .iter()
+            .all(|advisory| advisory.contains("marked unavailable")),
+        "{:?}",
+        response.advisories
+    );
+    assert!(response.provenance.search_terms_used["votes"].is_empty());
+}
+
+#[tokio::test]
+async fn all_hosts_down_serves_the_stale_entry_without_upstream_calls() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let tree = db.open_tree("research").unwrap();
+    let time = MockClock::new(1_700_000_000);
+
+    let healthy = Arc::new(common::MockParliamentDataSource::new());
+    service(&temp_dir, &tree, healthy)
+        .with_clock(time.clock())
+        .run_research(request())
+        .await
+        .unwrap();
+    time.advance(7_200);
+
+    let mock = Arc::new(common::MockParliamentDataSource::new().down(&[
+        BILLS_HOST,
+        CORE_DATASET_HOST,
+        LEGISLATION_HOST,
+    ]));
+    let down = service(&temp_dir, &tree, mock.clone()).with_clock(time.clock());
+    let response = down.run_research(request()).await.unwrap();
+
+    assert!(response.cached && response.stale, "{response:?}");
+    assert!(!response.bills.is_empty());
+    assert!(
+        response.advisories[0].contains("every source failed"),
+        "{:?}",
+        response.advisories
+    );
+    // Neither the run nor the background refresh it schedules goes upstream.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(mock.total_calls().await, 0);
+
+    // With nothing stale to fall back on, the run fails fast with its advisories.
+    let empty_dir = tempfile::tempdir().unwrap();
+    let empty_db = sled::open(empty_dir.path()).unwrap();
+    let fresh = service(&empty_dir, &empty_db.open_tree("research").unwrap(), mock);
+    let response = fresh.run_research(request()).await.unwrap();
+    assert_eq!(response.status, ResearchStatus::Failed);
+    assert!(!response.stale);
+    assert_eq!(response.advisories.len(), 4, "{:?}", response.advisories);
+}
+
+#[test]
+fn hosts_are_marked_down_after_consecutive_failures_until_the_cooldown_ends() {
+    let tracker = AvailabilityTracker::new(2, 60);
+    tracker.record(BILLS_HOST, false, 100);
+    assert!(!tracker.is_down(BILLS_HOST, 100));
+    tracker.record(BILLS_HOST, false, 101);
+    assert!(tracker.is_down(BILLS_HOST, 101));
+    assert!(!tracker.is_down(LEGISLATION_HOST, 101));
+    assert!(tracker.is_down(BILLS_HOST, 160));
+    assert!(!tracker.is_down(BILLS_HOST, 161));
+
+    // A success clears the count.
+    tracker.record(BILLS_HOST, true, 170);
+    tracker.record(BILLS_HOST, false, 171);
+    assert!(!tracker.is_down(BILLS_HOST, 171));
+
+    let disabled = AvailabilityTracker::new(0, 60);
+    for now in 0..5 {
+        disabled.record(BILLS_HOST, false, now);
+    }
+    assert!(!disabled.is_down(BILLS_HOST, 5));
+}
+
+#[tokio::test]
+async fn the_client_marks_a_failing_host_down() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let mut config = common::test_config(&temp_dir.path().to_string_lossy());
+    config.upstream_base_url = Some(server.uri());
+    config.upstream_down_after_failures = 1;
+    config.upstream_down_secs = 30;
+    let time = MockClock::new(1_700_000_000);
+    let client = ParliamentClient::new(
+        Arc::new(config),
+        CacheManager::new(false, 0),
+        db.open_tree("parliament").unwrap(),
+        Arc::new(StatsRegistry::new()),
+    )
+    .unwrap()
+    .with_clock(time.clock());
+
+    let args = FetchBillsArgs {
+        search_term: Some("climate".to_string()),
+        house: None,
+        session: None,
+        parliament_number: None,
+        enable_cache: Some(false),
+        cache_only: None,
+        apply_relevance: None,
+        relevance_threshold: None,
+        fields: None,
+    };
+    client.fetch_bills(args).await.unwrap_err();
+
+    // Tracked under the real API host, not the UPSTREAM_BASE_URL it was redirected to.
+    assert!(client.host_down(BILLS_HOST));
+    assert!(!client.host_down(CORE_DATASET_HOST));
+    time.advance(30);
+    assert!(!client.host_down(BILLS_HOST));
+}
@@ -0,0 +1,68 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use tower::ServiceExt;
+
+mod common;
+
+async fn scrape_metrics(headers: &[(&str, &str)]) -> (StatusCode, String) {
+    let harness = common::build_service(None, |_| {});
+    let mut request = Request::get("/api/metrics");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = harness
+        .router()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+#[tokio::test]
+async fn bearer_tokens_are_accepted() {
+    let (status, _) = scrape_metrics(&[(header::AUTHORIZATION.as_str(), "Bearer test")]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = scrape_metrics(&[(header::AUTHORIZATION.as_str(), "bearer test")]).await;
+    assert_eq!(status, StatusCode::OK, "the scheme is case-insensitive");
+
+    let (status, _) = scrape_metrics(&[(header::AUTHORIZATION.as_str(), "Bearer wrong")]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn api_key_header_is_still_accepted() {
+    let (status, _) = scrape_metrics(&[("x-api-key", "test")]).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn api_key_header_wins_over_bearer_token() {
+    let (status, _) = scrape_metrics(&[
+        ("x-api-key", "test"),
+        (header::AUTHORIZATION.as_str(), "Bearer wrong"),
+    ])
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = scrape_metrics(&[
+        ("x-api-key", "wrong"),
+        (header::AUTHORIZATION.as_str(), "Bearer test"),
+    ])
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn other_authorization_schemes_are_rejected() {
+    let (status, body) =
+        scrape_metrics(&[(header::AUTHORIZATION.as_str(), "Basic dGVzdA==")]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body.contains("x-api-key"), "body: {body}");
+    assert!(body.contains("Authorization: Bearer"), "body: {body}");
+
+    let (status, _) = scrape_metrics(&[(header::AUTHORIZATION.as_str(), "test")]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,185 @@
+use std::path::Path;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_bill_detail";
+
+/// Bills API: bill 3137, which completed every stage and became the Online Safety Act
+/// 2023, and bill 3764, still in Commons committee stage.
+const BILL_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/bill_detail");
+
+#[tokio::test]
+async fn a_completed_bill_has_its_full_history_and_publications() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(BILL_FIXTURES)).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"billId": 3137})).await;
+
+    assert_eq!(result["shortTitle"], "Online Safety Act 2023");
+    assert!(
+        result["longTitle"]
+            .as_str()
+            .unwrap()
+            .starts_with("A Bill to make provision")
+    );
+    assert_eq!(result["isAct"], true);
+    assert_eq!(result["withdrawn"], false);
+    assert_eq!(
+        result["currentStage"],
+        json!({
+            "stage": "Royal Assent",
+            "abbreviation": "RA",
+            "house": "Unassigned",
+            "date": "2023-10-26",
+            "sittings": 1,
+            "explanation": "The monarch has agreed to the bill, making it an Act of Parliament."
+        })
+    );
+    assert_eq!(
+        result["sponsors"][0],
+        json!({
+            "memberId": 4444,
+            "name": "Michelle Donelan",
+            "party": "Conservative",
+            "organisation": "Department for Science, Innovation and Technology"
+        })
+    );
+
+    let stages = result["stages"].as_array().unwrap();
+    assert_eq!(stages.len(), 12);
+    assert_eq!(stages[0]["stage"], "1st reading");
+    assert_eq!(stages[2]["date"], "2022-05-24");
+    assert_eq!(stages[2]["sittings"], 3);
+    assert_eq!(stages[11]["stage"], "Royal Assent");
+
+    // Oldest first, with web links and downloadable files alike.
+    assert_eq!(
+        result["publications"],
+        json!([
+            {
+                "title": "Online Safety Bill Explanatory Notes",
+                "type": "Explanatory Notes",
+                "house": "Commons",
+                "date": "2022-03-17",
+                "links": [{
+                    "title": "Explanatory Notes (HTML)",
+                    "url": "https://publications.parliament.uk/pa/bills/cbill/58-02/0285/en/220285en.htm",
+                    "contentType": "text/html"
+                }]
+            },
+            {
+                "title": "HL Bill 164 (as amended on Report)",
+                "type": "Bill",
+                "house": "Lords",
+                "date": "2023-07-20",
+                "links": [{
+                    "title": "230164.pdf",
+                    "url": "https://bills-api.parliament.uk/api/v1/Publications/56789/Documents/61234/Download",
+                    "contentType": "application/pdf"
+                }]
+            }
+        ])
+    );
+    assert_eq!(result["link"], "https://bills.parliament.uk/bills/3137");
+}
+
+#[tokio::test]
+async fn a_bill_in_committee_lists_stages_yet_to_come() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(BILL_FIXTURES)).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"billId": "https://bills.parliament.uk/bills/3764"}),
+    )
+    .await;
+
+    assert_eq!(result["isAct"], false);
+    assert_eq!(result["currentHouse"], "Commons");
+    assert_eq!(result["currentStage"]["stage"], "Committee stage");
+    assert_eq!(result["currentStage"]["date"], "2024-11-05");
+    assert_eq!(result["currentStage"]["sittings"], 2);
+    let stages = result["stages"].as_array().unwrap();
+    assert_eq!(stages.len(), 4);
+    assert_eq!(
+        stages[3],
+        json!({
+            "stage": "Report stage",
+            "abbreviation": "RS",
+            "house": "Commons",
+            "date": null,
+            "sittings": 0,
+            "explanation": stages[3]["explanation"]
+        })
+    );
+    assert!(stages[3]["explanation"].is_string());
+    assert_eq!(result["publications"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn bills_are_cached() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(BILL_FIXTURES)).await;
+
+    let first = common::call_tool_ok(&harness.service, TOOL, json!({"billId": 3764})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"billId": 3764, "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn failed_publications_are_noted_and_not_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills/3764/Publications"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let harness = common::recorded_harness(&server, Path::new(BILL_FIXTURES)).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"billId": 3764})).await;
+
+    assert_eq!(result["shortTitle"], "Renters' Rights Bill");
+    assert_eq!(result["stages"].as_array().unwrap().len(), 4);
+    assert_eq!(result["publications"], json!([]));
+    assert!(
+        result["note"].as_str().unwrap().contains("publications"),
+        "{result}"
+    );
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"billId": 3764, "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(cached["cacheMiss"], true, "{cached}");
+}
+
+#[tokio::test]
+async fn an_unknown_bill_is_an_upstream_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response = common::call_tool(&harness.service, TOOL, json!({"billId": 999999})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
@@ -0,0 +1,420 @@
+use chrono::NaiveDate;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::DivisionOutcome;
+use mp_writer_mcp_server::features::parliament::divisions::{
+    DivisionHouse, MIN_MATCH_SIMILARITY, PartyVotes, RawDivision, StageSitting, classify_outcome,
+    commons_majority_party, group_divisions, parse_divisions, parse_stage_sittings,
+    title_similarity,
+};
+
+mod common;
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").expect("valid date")
+}
+
+fn sitting(stage: &str, house: DivisionHouse, day: &str, order: usize) -> StageSitting {
+    StageSitting {
+        stage: stage.to_string(),
+        house,
+        date: date(day),
+        order,
+    }
+}
+
+fn division(id: u64, title: &str, day: &str, ayes: u64, noes: u64) -> RawDivision {
+    RawDivision {
+        house: DivisionHouse::Commons,
+        division_id: id,
+        number: Some(id),
+        title: title.to_string(),
+        date: date(day),
+        ayes,
+        noes,
+        parties: Vec::new(),
+    }
+}
+
+fn party(name: &str, ayes: u64, noes: u64) -> PartyVotes {
+    PartyVotes {
+        party: name.to_string(),
+        ayes,
+        noes,
+    }
+}
+
+#[test]
+fn numbered_bills_are_not_confused() {
+    assert_eq!(
+        title_similarity("Finance Bill", "Finance Bill: Third Reading"),
+        1.0
+    );
+    assert!(
+        title_similarity("Finance Bill", "Finance (No. 2) Bill: Second Reading")
+            < MIN_MATCH_SIMILARITY
+    );
+    assert!(
+        title_similarity("Finance (No. 2) Bill", "Finance Bill: Second Reading")
+            < MIN_MATCH_SIMILARITY
+    );
+}
+
+#[test]
+fn punctuation_and_house_markers_are_ignored() {
+    assert_eq!(
+        title_similarity(
+            "Victims and Prisoners Bill [HL]",
+            "victims and prisoners bill: report stage"
+        ),
+        1.0
+    );
+    assert_eq!(
+        title_similarity(
+            "Renters' Rights Bill",
+            "Renters Rights Bill: Committee of the whole House"
+        ),
+        1.0
+    );
+    assert_eq!(title_similarity("Finance Bill", "Bill"), 0.0);
+}
+
+#[test]
+fn near_miss_titles_are_kept_as_low_confidence() {
+    let sittings = vec![sitting(
+        "2nd reading",
+        DivisionHouse::Commons,
+        "2023-04-17",
+        0,
+    )];
+    let divisions = vec![
+        division(
+            1,
+            "Data Protection and Digital Information (No. 2) Bill: Second Reading",
+            "2023-04-17",
+            300,
+            200,
+        ),
+        division(
+            2,
+            "Data Protection and Digital Information Bill: Reasoned Amendment",
+            "2023-04-17",
+            200,
+            300,
+        ),
+        division(3, "Online Safety Bill: Second Reading", "2023-04-17", 1, 2),
+    ];
+
+    let stages = group_divisions(
+        "Data Protection and Digital Information (No. 2) Bill",
+        &sittings,
+        divisions,
+    );
+
+    assert_eq!(stages.len(), 1);
+    let matched = &stages[0].divisions;
+    assert_eq!(matched.len(), 2, "unrelated bill must be dropped");
+    assert!(!matched[0].low_confidence);
+    assert_eq!(matched[0].result, "agreed");
+    assert!(matched[1].low_confidence);
+    assert_eq!(matched[1].similarity, 0.71);
+    assert_eq!(matched[1].result, "negatived");
+}
+
+#[test]
+fn divisions_are_grouped_by_stage_and_house() {
+    let sittings = vec![
+        sitting("2nd reading", DivisionHouse::Commons, "2024-01-10", 0),
+        sitting("Report stage", DivisionHouse::Commons, "2024-03-05", 1),
+        sitting("3rd reading", DivisionHouse::Commons, "2024-03-05", 2),
+        sitting("2nd reading", DivisionHouse::Lords, "2024-04-01", 3),
+    ];
+    let mut lords = division(50, "Widgets Bill: 2nd reading", "2024-04-01", 90, 90);
+    lords.house = DivisionHouse::Lords;
+    let divisions = vec![
+        division(12, "Widgets Bill: Third Reading", "2024-03-05", 310, 200),
+        division(11, "Widgets Bill: New Clause 3", "2024-03-05", 250, 260),
+        division(10, "Widgets Bill: Second Reading", "2024-01-10", 320, 180),
+        division(10, "Widgets Bill: Second Reading", "2024-01-10", 320, 180),
+        division(13, "Widgets Bill: Second Reading", "2024-02-01", 1, 1),
+        lords,
+    ];
+
+    let stages = group_divisions("Widgets Bill", &sittings, divisions);
+    let summary: Vec<(&str, &str, Vec<u64>)> = stages
+        .iter()
+        .map(|stage| {
+            (
+                stage.stage.as_str(),
+                stage.house.as_str(),
+                stage
+                    .divisions
+                    .iter()
+                    .map(|division| division.division_id)
+                    .collect(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            ("2nd reading", "Commons", vec![10]),
+            ("Report stage", "Commons", vec![11]),
+            ("3rd reading", "Commons", vec![12]),
+            ("2nd reading", "Lords", vec![50]),
+        ]
+    );
+    let lords = &stages[3].divisions[0];
+    assert_eq!(lords.result, "tied");
+    assert_eq!(
+        lords.link,
+        "https://votes.parliament.uk/votes/lords/division/50"
+    );
+}
+
+#[test]
+fn whipped_governing_party_decides_the_outcome() {
+    let win = [party("Labour", 320, 2), party("Conservative", 5, 110)];
+    assert_eq!(
+        classify_outcome(325, 112, Some("Labour"), &win),
+        DivisionOutcome::GovernmentWin
+    );
+
+    // Whipped against a motion that passed anyway.
+    let defeat = [
+        party("Labour", 4, 280),
+        party("Conservative", 150, 0),
+        party("Liberal Democrat", 140, 10),
+    ];
+    assert_eq!(
+        classify_outcome(294, 290, Some("labour"), &defeat),
+        DivisionOutcome::GovernmentDefeat
+    );
+}
+
+#[test]
+fn narrow_and_tied_divisions_are_judged_conservatively() {
+    let narrow = [party("Labour", 300, 0), party("Conservative", 0, 299)];
+    assert_eq!(
+        classify_outcome(300, 299, Some("Labour"), &narrow),
+        DivisionOutcome::GovernmentWin
+    );
+
+    let tied = [party("Labour", 300, 0), party("Conservative", 0, 300)];
+    assert_eq!(
+        classify_outcome(300, 300, Some("Labour"), &tied),
+        DivisionOutcome::Unknown
+    );
+
+    // Too few governing-party votes to infer a whip.
+    let thin = [party("Labour", 6, 0), party("Conservative", 0, 2)];
+    assert_eq!(
+        classify_outcome(6, 2, Some("Labour"), &thin),
+        DivisionOutcome::Unknown
+    );
+}
+
+#[test]
+fn split_governing_party_is_a_free_vote_and_missing_data_is_unknown() {
+    let split = [party("Labour", 180, 140), party("Conservative", 60, 50)];
+    assert_eq!(
+        classify_outcome(240, 190, Some("Labour"), &split),
+        DivisionOutcome::FreeVote
+    );
+    assert_eq!(
+        classify_outcome(240, 190, None, &split),
+        DivisionOutcome::Unknown
+    );
+    assert_eq!(
+        classify_outcome(240, 190, Some("Reform UK"), &split),
+        DivisionOutcome::Unknown
+    );
+    assert_eq!(
+        classify_outcome(240, 190, Some("Labour"), &[]),
+        DivisionOutcome::Unknown
+    );
+}
+
+#[test]
+fn majority_party_comes_from_commons_breakdowns() {
+    let mut first = division(1, "Widgets Bill: Second Reading", "2024-01-10", 330, 180);
+    first.parties = vec![party("Labour", 320, 0), party("Conservative", 10, 180)];
+    // Government abstains on an opposition amendment; the opposition casts most votes.
+    let mut second = division(2, "Widgets Bill: Amendment 4", "2024-01-10", 20, 180);
+    second.parties = vec![party("Labour", 20, 0), party("Conservative", 0, 180)];
+    let mut third = division(3, "Widgets Bill: Third Reading", "2024-03-05", 310, 190);
+    third.parties = vec![party("Labour", 310, 0), party("Conservative", 0, 190)];
+    let mut lords = division(4, "Widgets Bill: Amendment 1", "2024-04-01", 200, 150);
+    lords.house = DivisionHouse::Lords;
+    lords.parties = vec![party("Conservative", 200, 0)];
+
+    let divisions = vec![first, second, third, lords];
+    assert_eq!(
+        commons_majority_party(&divisions).as_deref(),
+        Some("Labour")
+    );
+    assert_eq!(commons_majority_party(&divisions[3..]), None);
+
+    let sittings = vec![
+        sitting("2nd reading", DivisionHouse::Commons, "2024-01-10", 0),
+        sitting("3rd reading", DivisionHouse::Commons, "2024-03-05", 1),
+    ];
+    let stages = group_divisions("Widgets Bill", &sittings, divisions);
+    let outcomes: Vec<(u64, u64, DivisionOutcome)> = stages
+        .iter()
+        .flat_map(|stage| &stage.divisions)
+        .map(|division| (division.division_id, division.margin, division.outcome))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            (1, 150, DivisionOutcome::GovernmentWin),
+            (2, 160, DivisionOutcome::GovernmentDefeat),
+            (3, 120, DivisionOutcome::GovernmentWin),
+        ]
+    );
+}
+
+#[test]
+fn upstream_payloads_parse_for_both_houses() {
+    let sittings = parse_stage_sittings(&json!({
+        "items": [
+            {"description": "1st reading", "house": "Commons", "stageSittings": [{"date": "2024-01-02T00:00:00"}]},
+            {"description": "Royal Assent", "house": "Unassigned", "stageSittings": [{"date": "2024-05-01T00:00:00"}]},
+            {"description": "2nd reading", "house": "Lords", "stageSittings": []}
+        ]
+    }));
+    assert_eq!(sittings.len(), 1);
+    assert_eq!(sittings[0].house, DivisionHouse::Commons);
+
+    let commons = parse_divisions(
+        DivisionHouse::Commons,
+        &json!([{"DivisionId": 7, "Number": 3, "Title": "A Bill", "Date": "2024-01-02T17:00:00", "AyeCount": 5, "NoCount": 4}]),
+    );
+    assert_eq!(
+        (commons[0].division_id, commons[0].ayes, commons[0].noes),
+        (7, 5, 4)
+    );
+
+    let lords = parse_divisions(
+        DivisionHouse::Lords,
+        &json!([{"divisionId": 8, "title": "A Bill", "date": "2024-01-03T17:00:00", "authoritativeContentCount": 20, "notContentCount": 30}]),
+    );
+    assert_eq!(
+        (lords[0].division_id, lords[0].ayes, lords[0].noes),
+        (8, 20, 30)
+    );
+
+    let members = parse_divisions(
+        DivisionHouse::Commons,
+        &json!([{
+            "DivisionId": 9, "Title": "A Bill", "Date": "2024-01-04T17:00:00", "AyeCount": 2, "NoCount": 1,
+            "Ayes": [{"Name": "A", "Party": "Labour"}, {"Name": "B", "Party": "Labour"}],
+            "Noes": [{"Name": "C", "Party": "Conservative"}, {"Name": "D"}]
+        }]),
+    );
+    assert_eq!(
+        members[0].parties,
+        [party("Labour", 2, 0), party("Conservative", 0, 1)]
+    );
+}
+
+#[tokio::test]
+async fn tool_resolves_title_and_matches_divisions() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .and(query_param(
+            "SearchTerm",
+            "widgets and gadgets regulation bill",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {"billId": 99, "shortTitle": "Widgets and Gadgets Regulation (No. 2) Bill"},
+                {"billId": 42, "shortTitle": "Widgets and Gadgets Regulation Bill"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(
+            json!({"billId": 42, "shortTitle": "Widgets and Gadgets Regulation Bill"}),
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills/42/Stages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {"description": "2nd reading", "house": "Commons", "stageSittings": [{"date": "2024-01-10T00:00:00"}]},
+                {"description": "2nd reading", "house": "Lords", "stageSittings": [{"date": "2024-02-20T00:00:00"}]}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/divisions.json/search"))
+        .and(query_param("queryParameters.startDate", "2024-01-10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"DivisionId": 1, "Number": 1, "Title": "Widgets and Gadgets Regulation Bill: Second Reading", "Date": "2024-01-10T19:00:00", "AyeCount": 300, "NoCount": 100},
+            {"DivisionId": 2, "Number": 2, "Title": "Gizmos Bill: Second Reading", "Date": "2024-01-10T20:00:00", "AyeCount": 1, "NoCount": 2}
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/Divisions/search"))
+        .and(query_param("StartDate", "2024-02-20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"divisionId": 5, "number": 1, "title": "Widgets and Gadgets Regulation (No. 2) Bill: Amendment 1", "date": "2024-02-20T16:00:00", "authoritativeContentCount": 80, "authoritativeNotContentCount": 120}
+        ])))
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bill_divisions",
+        json!({"billTitle": "widgets and gadgets regulation bill", "enableCache": false}),
+    )
+    .await;
+    let result = &response["result"];
+    let output = &result["structuredContent"];
+
+    assert!(
+        result.get("isError").is_none(),
+        "unexpected response: {response}"
+    );
+    assert_eq!(output["billId"], 42);
+    assert_eq!(output["link"], "https://bills.parliament.uk/bills/42");
+    assert_eq!(output["totalDivisions"], 2);
+    assert_eq!(output["lowConfidenceCount"], 1);
+    assert_eq!(output["stages"][0]["house"], "Commons");
+    assert_eq!(output["stages"][0]["divisions"][0]["divisionId"], 1);
+    assert_eq!(output["stages"][1]["house"], "Lords");
+    assert_eq!(output["stages"][1]["divisions"][0]["lowConfidence"], true);
+}
+
+#[tokio::test]
+async fn tool_requires_a_bill_reference() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bill_divisions",
+        json!({"enableCache": false}),
+    )
+    .await;
+
+    assert!(
+        response.get("error").is_some() || response["result"]["isError"] == true,
+        "missing bill reference should fail: {response}"
+    );
+}
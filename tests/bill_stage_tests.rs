@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use mp_writer_mcp_server::features::parliament::bill_stages::{BILL_STAGES, find_bill_stage};
+use mp_writer_mcp_server::features::parliament::describe_bill_stage;
+use mp_writer_mcp_server::features::research::{
+    ParliamentDataSource, ResearchRequestDto, ResearchService,
+};
+
+mod common;
+
+#[test]
+fn every_stage_code_expands_to_its_name_and_explanation() {
+    let mut codes = HashSet::new();
+    for stage in BILL_STAGES {
+        assert!(codes.insert(stage.code), "duplicate code {}", stage.code);
+
+        for raw in [stage.code.to_string(), stage.code.to_lowercase()] {
+            let description = describe_bill_stage(&raw);
+            assert_eq!(description.name, stage.name, "{raw}");
+            assert_eq!(description.code.as_deref(), Some(stage.code));
+            assert!(
+                description
+                    .explanation
+                    .as_deref()
+                    .is_some_and(|text| !text.is_empty())
+            );
+        }
+
+        for name in std::iter::once(&stage.name).chain(stage.aliases) {
+            assert_eq!(
+                find_bill_stage(&name.to_uppercase()).map(|found| found.code),
+                Some(stage.code),
+                "{name} should resolve to {}",
+                stage.code
+            );
+        }
+    }
+
+    for code in ["2R", "CS", "PRS", "GC", "CWH", "CLA", "CCA", "RA"] {
+        assert!(codes.contains(code), "{code} missing from the dictionary");
+    }
+}
+
+#[test]
+fn unknown_stages_pass_through() {
+    let description = describe_bill_stage(" Carry-over motion ");
+
+    assert_eq!(description.name, "Carry-over motion");
+    assert_eq!(description.code, None);
+    assert_eq!(description.explanation, None);
+}
+
+#[tokio::test]
+async fn research_bills_carry_the_expanded_stage() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let mut mock = common::MockParliamentDataSource::new();
+    mock.bills = json!({
+        "items": [
+            {"title": "Climate Bill", "billStage": {"abbreviation": "2R"}, "billId": 7}
+        ]
+    });
+    let data_source: Arc<dyn ParliamentDataSource> = Arc::new(mock);
+    let service = ResearchService::new(config, data_source, db.open_tree("research").unwrap());
+
+    let response = service
+        .run_research(ResearchRequestDto {
+            topic: "Climate".to_string(),
+            bill_keywords: vec![],
+            debate_keywords: vec![],
+            mp_id: None,
+            include_state_of_parties: false,
+            limit: Some(3),
+            max_age_secs: None,
+            cache_only: false,
+            locale: None,
+        })
+        .await
+        .expect("research should succeed");
+
+    let bill = &response.bills[0];
+    assert_eq!(bill.stage.as_deref(), Some("2nd reading"));
+    assert_eq!(bill.stage_code.as_deref(), Some("2R"));
+    assert!(bill.stage_explanation.is_some());
+    assert!(response.summary.contains("(current stage: 2nd reading)"));
+
+    let rendered = serde_json::to_value(bill).expect("serialise bill");
+    assert_eq!(rendered["stageCode"], "2R");
+}
@@ -0,0 +1,188 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::config::ToolByteBudgetConfig;
+
+mod common;
+
+const PADDING_BYTES: usize = 64 * 1024;
+const BUDGET_BYTES: u64 = 10_000;
+
+fn padding() -> String {
+    "x".repeat(PADDING_BYTES)
+}
+
+fn large_member(id: u32) -> Value {
+    json!({
+        "value": {
+            "id": id,
+            "nameDisplayAs": format!("Member {id}"),
+            "latestParty": {"name": "Independent"},
+            "latestHouseMembership": {
+                "membershipFrom": "Somewhere",
+                "house": 1,
+                "membershipEndDate": null,
+                "membershipStatus": {"statusIsActive": true, "statusDescription": "Current Member"}
+            },
+            "biography": padding()
+        }
+    })
+}
+
+async fn requests_to(server: &MockServer, prefix: &str) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path().starts_with(prefix))
+        .count()
+}
+
+async fn members_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    for id in 1..=12 {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/Members/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(large_member(id)))
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+#[test]
+fn budgets_parse_and_reject_unknown_tools() {
+    let config = ToolByteBudgetConfig::parse(" research.run=2000000 , parliament.fetch_bills=5 ,")
+        .expect("valid budgets");
+    assert_eq!(config.for_tool("research.run"), Some(2_000_000));
+    assert_eq!(config.for_tool("parliament.fetch_bills"), Some(5));
+    assert_eq!(config.for_tool("parliament.fetch_legislation"), None);
+
+    assert!(ToolByteBudgetConfig::parse("research.run").is_err());
+    assert!(ToolByteBudgetConfig::parse("research.run=lots").is_err());
+
+    let known = vec!["research.run".to_string()];
+    assert!(config.validate(&known).is_err());
+    assert!(
+        ToolByteBudgetConfig::parse("research.run=1")
+            .expect("valid budget")
+            .validate(&known)
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn bulk_lookup_stops_once_budget_is_spent() {
+    let server = members_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.tool_byte_budgets =
+            ToolByteBudgetConfig::parse(&format!("parliament.fetch_members_bulk={BUDGET_BYTES}"))
+                .expect("valid budget");
+    });
+    common::initialize(&harness.service).await;
+
+    let ids: Vec<u32> = (1..=12).collect();
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": ids, "enableCache": false}),
+    )
+    .await;
+    let payload = &response["result"]["structuredContent"];
+
+    assert_eq!(payload["truncatedByBudget"], true, "response: {response}");
+    let fetched = requests_to(&server, "/api/Members/").await;
+    assert!(fetched < 12, "remaining ids should be skipped");
+    assert_eq!(payload["resolved"].as_u64(), Some(fetched as u64));
+    assert_eq!(
+        payload["members"].as_object().map(|members| members.len()),
+        Some(fetched)
+    );
+}
+
+#[tokio::test]
+async fn unbudgeted_calls_report_bytes_in_tool_stats() {
+    let server = members_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let ids: Vec<u32> = (1..=12).collect();
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": ids, "enableCache": false}),
+    )
+    .await;
+    let payload = &response["result"]["structuredContent"];
+    assert_eq!(payload["truncatedByBudget"], false);
+    assert_eq!(payload["resolved"], 12);
+
+    let stats = common::call_tool(&harness.service, "admin.tool_stats", json!({})).await;
+    let stats = &stats["result"]["structuredContent"];
+    let tool = &stats["tools"]["parliament.fetch_members_bulk"];
+    let upstream = &stats["upstreams"]["members-api.parliament.uk"];
+
+    let tool_bytes = tool["upstreamBytes"].as_u64().expect("tool bytes");
+    assert!(tool_bytes >= 12 * PADDING_BYTES as u64, "stats: {stats}");
+    assert_eq!(tool["largestPayloadBytes"], tool_bytes);
+    assert_eq!(upstream["upstreamBytes"], tool_bytes);
+    assert!(upstream["largestPayloadBytes"].as_u64() >= Some(PADDING_BYTES as u64));
+}
+
+#[tokio::test]
+async fn research_skips_remaining_terms_over_budget() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(".*"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"padding": padding()})))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.tool_byte_budgets =
+            ToolByteBudgetConfig::parse(&format!("research.run={BUDGET_BYTES}"))
+                .expect("valid budget");
+    });
+    common::initialize(&harness.service).await;
+
+    let arguments = json!({
+        "topic": "badger culling",
+        "billKeywords": ["bovine tb"],
+        "debateKeywords": ["wildlife"]
+    });
+    let response = common::call_tool(&harness.service, "research.run", arguments.clone()).await;
+    let brief = &response["result"]["structuredContent"];
+
+    assert_eq!(brief["truncatedByBudget"], true, "response: {response}");
+    assert_eq!(requests_to(&server, "/commonsdivisions.json").await, 1);
+    assert_eq!(requests_to(&server, "/commonsdebates.json").await, 1);
+
+    let votes = brief["provenance"]["searchTermsUsed"]["votes"]
+        .as_array()
+        .expect("votes terms");
+    assert_eq!(votes[0]["term"], "badger culling");
+    assert_eq!(votes[0]["outcome"], "empty");
+    assert!(
+        votes[1..]
+            .iter()
+            .all(|term| term["outcome"] == "skipped-over-budget"),
+        "votes terms: {votes:?}"
+    );
+    assert!(
+        brief["advisories"][0]
+            .as_str()
+            .is_some_and(|note| note.contains("byte budget"))
+    );
+
+    let rerun = common::call_tool(&harness.service, "research.run", arguments).await;
+    assert_eq!(
+        rerun["result"]["structuredContent"]["cached"], false,
+        "truncated briefs must not be cached"
+    );
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/Members/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "value": {
+                "id": 7,
+                "nameDisplayAs": "Example Member",
+                "latestHouseMembership": {"membershipFrom": "Somewhere", "house": 1}
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"billId": 1, "shortTitle": "Example Bill"}],
+            "totalResults": 1
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn request_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap_or_default().len()
+}
+
+fn cache_miss() -> Value {
+    json!({"items": [], "cacheOnly": true, "cacheMiss": true})
+}
+
+#[tokio::test]
+async fn cached_results_are_served_without_upstream_calls() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let warm_members = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": [7]}),
+    )
+    .await;
+    let warm_bills = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "example"}),
+    )
+    .await;
+    let fetched = request_count(&server).await;
+    assert_eq!(fetched, 2);
+
+    let members = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": [7], "cacheOnly": true}),
+    )
+    .await;
+    let bills = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "example", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(
+        members["result"]["structuredContent"],
+        warm_members["result"]["structuredContent"]
+    );
+    assert_eq!(
+        bills["result"]["structuredContent"],
+        warm_bills["result"]["structuredContent"]
+    );
+    assert_eq!(request_count(&server).await, fetched);
+}
+
+#[tokio::test]
+async fn cache_misses_return_an_empty_result_without_upstream_calls() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    for (tool, arguments) in [
+        ("parliament.fetch_members_bulk", json!({"mpIds": [7]})),
+        ("parliament.fetch_bills", json!({"searchTerm": "example"})),
+        (
+            "parliament.fetch_bill_divisions",
+            json!({"billTitle": "Example Bill"}),
+        ),
+        ("parliament.list_constituencies", json!({})),
+    ] {
+        let mut arguments = arguments;
+        arguments["cacheOnly"] = json!(true);
+        let response = common::call_tool(&harness.service, tool, arguments).await;
+        let result = &response["result"];
+        assert_ne!(result["isError"], true, "{tool}: {response}");
+        assert_eq!(result["structuredContent"], cache_miss(), "{tool}");
+    }
+
+    assert_eq!(request_count(&server).await, 0);
+}
+
+#[tokio::test]
+async fn cache_only_with_the_cache_disabled_is_rejected() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "example", "enableCache": false, "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("cacheOnly"))
+    );
+}
+
+#[tokio::test]
+async fn research_skips_collections_with_nothing_cached() {
+    let mock = common::MockParliamentDataSource::new().uncached(&[
+        "commonsdivisions",
+        "commonsdebates",
+        "legislation",
+    ]);
+    let harness = common::build_service(Some(Arc::new(mock)), |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate", "cacheOnly": true}),
+    )
+    .await;
+    let payload = &response["result"]["structuredContent"];
+
+    assert_eq!(payload["status"], "partial");
+    assert_eq!(payload["bills"].as_array().map(Vec::len), Some(1));
+    assert!(payload["votes"].as_array().is_some_and(Vec::is_empty));
+    let advisories: Vec<&str> = payload["advisories"]
+        .as_array()
+        .expect("advisories")
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    for label in [
+        "Commons divisions",
+        "Legislation searches",
+        "Commons debates",
+    ] {
+        assert!(
+            advisories
+                .iter()
+                .any(|note| note.starts_with(&format!("{label} were skipped"))),
+            "{advisories:?}"
+        );
+    }
+    let terms = &payload["provenance"]["searchTermsUsed"];
+    assert_eq!(terms["bills"][0]["outcome"], "hit");
+    assert_eq!(terms["votes"][0]["outcome"], "not-cached");
+    assert_eq!(terms["legislation"][0]["outcome"], "not-cached");
+
+    // The partial brief was not stored: a normal run queries every source again.
+    let fresh = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate"}),
+    )
+    .await;
+    assert_eq!(fresh["result"]["structuredContent"]["status"], "ok");
+    assert_eq!(fresh["result"]["structuredContent"]["cached"], false);
+}
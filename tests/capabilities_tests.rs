@@ -0,0 +1,97 @@
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::config::ToolFilterConfig;
+use mp_writer_mcp_server::features::mcp::capabilities::ServerCapabilities;
+use mp_writer_mcp_server::features::mcp::{McpService, RequestContext};
+
+mod common;
+
+async fn advertised_capabilities(service: &McpService, protocol_version: &str) -> Value {
+    let response = service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(1)),
+                "initialize",
+                Some(json!({
+                    "protocolVersion": protocol_version,
+                    "clientInfo": {"name": "test-client", "version": "1.0"},
+                    "capabilities": {}
+                })),
+            ),
+            RequestContext::new(Some(protocol_version.to_string())),
+        )
+        .await
+        .expect("initialize")
+        .expect("response");
+    response.result["capabilities"].clone()
+}
+
+#[test]
+fn capabilities_are_absent_unless_enabled() {
+    assert_eq!(json!(ServerCapabilities::new()), json!({}));
+    assert_eq!(
+        json!(
+            ServerCapabilities::new()
+                .with_tools(true)
+                .with_completions()
+                .with_logging()
+        ),
+        json!({"tools": {"listChanged": true}, "completions": {}, "logging": {}})
+    );
+    assert_eq!(
+        json!(
+            ServerCapabilities::new()
+                .with_completions()
+                .for_protocol("1.0")
+        ),
+        json!({})
+    );
+}
+
+#[tokio::test]
+async fn http_only_mode_does_not_advertise_list_changed() {
+    let harness = common::build_service(None, |_| {});
+
+    let capabilities = advertised_capabilities(&harness.service, common::PROTOCOL_VERSION).await;
+
+    assert_eq!(
+        capabilities,
+        json!({"tools": {"listChanged": false}, "completions": {}, "logging": {}})
+    );
+}
+
+#[tokio::test]
+async fn disabling_every_tool_with_completions_removes_the_capability() {
+    let harness = common::build_service(None, |config| {
+        config.tool_filter = ToolFilterConfig {
+            enabled: None,
+            disabled: vec![
+                "parliament.fetch_core_dataset".to_string(),
+                "parliament.search_uk_law".to_string(),
+                "parliament.fetch_bills".to_string(),
+                "parliament.list_constituencies".to_string(),
+            ],
+        };
+    });
+
+    let capabilities = advertised_capabilities(&harness.service, common::PROTOCOL_VERSION).await;
+    assert!(capabilities.get("completions").is_none(), "{capabilities}");
+    assert!(capabilities["tools"].is_object());
+
+    harness
+        .service
+        .reload_tools(ToolFilterConfig::default())
+        .expect("reload");
+    let capabilities = advertised_capabilities(&harness.service, common::PROTOCOL_VERSION).await;
+    assert_eq!(capabilities["completions"], json!({}));
+}
+
+#[tokio::test]
+async fn legacy_protocol_omits_completions() {
+    let harness = common::build_service(None, |_| {});
+
+    let capabilities = advertised_capabilities(&harness.service, "1.0").await;
+
+    assert!(capabilities.get("completions").is_none(), "{capabilities}");
+    assert_eq!(capabilities["logging"], json!({}));
+}
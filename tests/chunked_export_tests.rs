@@ -0,0 +1,185 @@
+use axum::body::Body;
+use axum::http::{Request, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+fn member_votes(first_id: u64, count: u64) -> Value {
+    let items: Vec<Value> = (first_id..first_id + count)
+        .map(|id| {
+            json!({
+                "MemberId": 7,
+                "MemberVotedAye": id % 2 == 0,
+                "MemberWasTeller": false,
+                "PublishedDivision": {
+                    "DivisionId": id,
+                    "Number": id,
+                    "Date": "2024-03-01T00:00:00",
+                    "Title": format!("Division {id}, amendment \"a\""),
+                    "AyeCount": 300,
+                    "NoCount": 200
+                }
+            })
+        })
+        .collect();
+    Value::Array(items)
+}
+
+async fn votes_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    for (skip, first_id, count) in [("0", 1, 25), ("25", 26, 10)] {
+        Mock::given(method("GET"))
+            .and(path("/data/divisions.json/membervoting"))
+            .and(query_param("queryParameters.memberId", "7"))
+            .and(query_param("queryParameters.skip", skip))
+            .respond_with(ResponseTemplate::new(200).set_body_json(member_votes(first_id, count)))
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+fn export_call(accept: &str) -> Request<Body> {
+    Request::post("/api/mcp")
+        .header("x-api-key", "test")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, accept)
+        .body(Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 9,
+                "method": "tools/call",
+                "params": {
+                    "name": "parliament.export_voting_record",
+                    "arguments": {"mpId": 7}
+                }
+            })
+            .to_string(),
+        ))
+        .unwrap()
+}
+
+async fn body_text(response: axum::response::Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    String::from_utf8(bytes.to_vec()).expect("utf-8 body")
+}
+
+fn csv_rows(blocks: &[&str]) -> Vec<String> {
+    blocks
+        .concat()
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>()
+}
+
+#[tokio::test]
+async fn sse_export_streams_csv_chunks_before_the_result() {
+    let server = votes_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = harness
+        .router()
+        .oneshot(export_call("application/json, text/event-stream"))
+        .await
+        .unwrap();
+    assert!(
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/event-stream")
+    );
+    let messages: Vec<Value> = body_text(response)
+        .await
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(|data| serde_json::from_str(data).expect("JSON event"))
+        .collect();
+
+    let (last, chunks) = messages.split_last().expect("at least one message");
+    assert_eq!(last["id"], 9);
+    assert_eq!(chunks.len(), 2, "{messages:?}");
+    let mut blocks = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk["method"], "notifications/tools/chunk");
+        assert_eq!(chunk["params"]["requestId"], 9);
+        assert_eq!(chunk["params"]["index"], index);
+        blocks.push(
+            chunk["params"]["content"][0]["text"]
+                .as_str()
+                .expect("text"),
+        );
+    }
+
+    let rows = csv_rows(&blocks);
+    assert_eq!(
+        rows[0],
+        "divisionId,number,date,title,vote,teller,ayes,noes"
+    );
+    assert_eq!(
+        rows[1],
+        "1,1,2024-03-01,\"Division 1, amendment \"\"a\"\"\",No,false,300,200"
+    );
+    assert_eq!(rows.len(), 36);
+
+    let result = &last["result"];
+    assert_eq!(result["structuredContent"]["rows"], 35);
+    assert_eq!(result["structuredContent"]["chunks"], 2);
+    assert_eq!(result["structuredContent"]["truncatedByBudget"], false);
+    // Streamed chunks are not repeated in the final result.
+    assert_eq!(result["content"].as_array().map(Vec::len), Some(1));
+}
+
+#[tokio::test]
+async fn json_export_buffers_the_chunks_into_the_result() {
+    let server = votes_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = harness
+        .router()
+        .oneshot(export_call("application/json"))
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_str(&body_text(response).await).expect("JSON body");
+
+    let content = body["result"]["content"].as_array().expect("content");
+    assert_eq!(content.len(), 3, "{body}");
+    let blocks: Vec<&str> = content[1..]
+        .iter()
+        .map(|block| block["text"].as_str().expect("text"))
+        .collect();
+    assert_eq!(csv_rows(&blocks).len(), 36);
+    assert_eq!(body["result"]["structuredContent"]["rows"], 35);
+}
+
+#[tokio::test]
+async fn export_limit_stops_paging_early() {
+    let server = votes_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.export_voting_record",
+        json!({"mpId": 7, "limit": 20}),
+    )
+    .await;
+
+    let summary = &response["result"]["structuredContent"];
+    assert_eq!(summary["rows"], 20);
+    assert_eq!(summary["chunks"], 1);
+    assert_eq!(
+        server.received_requests().await.unwrap_or_default().len(),
+        1
+    );
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::cache::CacheManager;
+use mp_writer_mcp_server::core::clock::{Clock, FUTURE_TOLERANCE_SECS, MockClock};
+use mp_writer_mcp_server::core::error::AppError;
+use mp_writer_mcp_server::core::stats::StatsRegistry;
+use mp_writer_mcp_server::features::parliament::{FetchBillDivisionsArgs, ParliamentClient};
+use mp_writer_mcp_server::features::research::{
+    ParliamentDataSource, ResearchRequestDto, ResearchService,
+};
+
+mod common;
+
+const START: u64 = 1_750_000_000;
+const DAY_SECS: i64 = 86_400;
+
+/// Serves bill 42 with no stages, so a divisions lookup makes exactly two requests.
+async fn bill_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills/42"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"billId": 42, "shortTitle": "Widgets Bill"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills/42/Stages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn client(server: &MockServer, tree: &sled::Tree, clock: Clock) -> ParliamentClient {
+    let mut config = common::test_config("unused");
+    config.upstream_base_url = Some(server.uri());
+    ParliamentClient::new(
+        Arc::new(config),
+        CacheManager::new(false, 0),
+        tree.clone(),
+        Arc::new(StatsRegistry::new()),
+    )
+    .unwrap()
+    .with_clock(clock)
+}
+
+/// Bill 42's divisions from the Sled cache only; `false` when they are not cached.
+async fn cached_divisions(client: &ParliamentClient) -> bool {
+    let args = FetchBillDivisionsArgs {
+        bill_id: Some(42),
+        bill_title: None,
+        enable_cache: None,
+        cache_only: Some(true),
+    };
+    match client.fetch_bill_divisions(args).await {
+        Ok(result) => result.bill_id == 42,
+        Err(AppError::NotCached { .. }) => false,
+        Err(error) => panic!("unexpected error: {error}"),
+    }
+}
+
+async fn fetch_divisions(client: &ParliamentClient) {
+    let args = FetchBillDivisionsArgs {
+        bill_id: Some(42),
+        bill_title: None,
+        enable_cache: None,
+        cache_only: None,
+    };
+    client.fetch_bill_divisions(args).await.unwrap();
+}
+
+fn research_request() -> ResearchRequestDto {
+    ResearchRequestDto {
+        topic: "Climate action".to_string(),
+        bill_keywords: vec![],
+        debate_keywords: vec![],
+        mp_id: None,
+        include_state_of_parties: false,
+        limit: Some(3),
+        max_age_secs: None,
+        cache_only: false,
+        locale: None,
+    }
+}
+
+#[test]
+fn steady_time_ignores_wall_clock_steps() {
+    let time = MockClock::new(START);
+    let clock = time.clock();
+
+    time.step_wall(-DAY_SECS);
+    assert_eq!(clock.wall_secs(), START - DAY_SECS as u64);
+    assert_eq!(clock.steady_secs(), START);
+    time.step_wall(2 * DAY_SECS);
+    assert_eq!(clock.steady_secs(), START);
+    time.advance(30);
+    assert_eq!(clock.steady_secs(), START + 30);
+    assert_eq!(clock.age_secs(START), Some(30));
+
+    // Slightly ahead is ordinary skew between processes; further ahead is counted.
+    assert_eq!(clock.age_secs(START + 30 + FUTURE_TOLERANCE_SECS), Some(0));
+    assert_eq!(clock.future_stamps(), 0);
+    assert_eq!(clock.age_secs(START + 30 + FUTURE_TOLERANCE_SECS + 1), None);
+    assert_eq!(clock.future_stamps(), 1);
+}
+
+#[tokio::test]
+async fn parliament_cache_expiry_ignores_wall_clock_steps() {
+    let server = bill_server().await;
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let tree = db.open_tree("parliament").unwrap();
+    let time = MockClock::new(START);
+    let client = client(&server, &tree, time.clock());
+    let ttl = common::test_config("unused").cache_ttl.bill_divisions;
+
+    fetch_divisions(&client).await;
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+    // Backwards: the entry does not become "newer" and outlive its TTL.
+    time.step_wall(-DAY_SECS);
+    time.advance(ttl - 1);
+    assert!(cached_divisions(&client).await);
+    time.advance(2);
+    assert!(!cached_divisions(&client).await);
+
+    // Forwards: a fresh entry does not expire the moment the clock jumps.
+    fetch_divisions(&client).await;
+    time.step_wall(DAY_SECS);
+    assert!(cached_divisions(&client).await);
+    time.advance(ttl + 1);
+    assert!(!cached_divisions(&client).await);
+
+    assert_eq!(client.clock().future_stamps(), 0);
+}
+
+#[tokio::test]
+async fn parliament_entries_stamped_in_the_future_are_treated_as_just_written() {
+    let server = bill_server().await;
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let tree = db.open_tree("parliament").unwrap();
+    let ttl = common::test_config("unused").cache_ttl.bill_divisions;
+
+    fetch_divisions(&client(&server, &tree, MockClock::new(START).clock())).await;
+
+    // A restart from a snapshot taken a day earlier: every entry looks a day ahead.
+    let time = MockClock::new(START - DAY_SECS as u64);
+    let restored = client(&server, &tree, time.clock());
+    assert!(cached_divisions(&restored).await);
+    assert_eq!(restored.clock().future_stamps(), 1);
+
+    // Re-stamped on that read, so it expires one TTL later rather than in a day.
+    time.advance(ttl - 1);
+    assert!(cached_divisions(&restored).await);
+    assert_eq!(restored.clock().future_stamps(), 1);
+    time.advance(2);
+    assert!(!cached_divisions(&restored).await);
+}
+
+#[tokio::test]
+async fn research_cache_survives_clock_steps_and_restamps_future_entries() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = sled::open(temp_dir.path()).unwrap();
+    let tree = db.open_tree("research").unwrap();
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let ttl = config.cache_ttl.research;
+    let mock = Arc::new(common::MockParliamentDataSource::new());
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let time = MockClock::new(START);
+    let service = ResearchService::new(config.clone(), data_source.clone(), tree.clone())
+        .with_clock(time.clock());
+
+    service.run_research(research_request()).await.unwrap();
+    let calls = mock.total_calls().await;
+
+    time.step_wall(-DAY_SECS);
+    time.advance(60);
+    let cached = service.run_research(research_request()).await.unwrap();
+    assert!(cached.cached);
+    assert_eq!(cached.age_secs, Some(60));
+    assert_eq!(mock.total_calls().await, calls);
+
+    time.advance(ttl);
+    let refreshed = service.run_research(research_request()).await.unwrap();
+    assert!(!refreshed.cached);
+    let calls = mock.total_calls().await;
+
+    // Restored from a snapshot a week earlier than the last write.
+    let time = MockClock::new(START - 7 * DAY_SECS as u64);
+    let restored = ResearchService::new(config, data_source, tree).with_clock(time.clock());
+    let cached = restored.run_research(research_request()).await.unwrap();
+    assert!(cached.cached);
+    assert_eq!(cached.age_secs, Some(0));
+    assert_eq!(restored.clock().future_stamps(), 1);
+
+    time.advance(ttl + 1);
+    let refreshed = restored.run_research(research_request()).await.unwrap();
+    assert!(!refreshed.cached);
+    assert!(mock.total_calls().await > calls);
+}
@@ -0,0 +1,299 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_committees";
+const COMMITTEES: &str = "/api/Committees";
+const COMMITTEE_BUSINESS: &str = "/api/CommitteeBusiness";
+
+fn committee(id: u32, name: &str, house: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "house": house,
+        "category": {"id": 1, "name": "Select"},
+        "parentCommittee": null
+    })
+}
+
+fn committees() -> Value {
+    json!({
+        "items": [
+            committee(378, "Science, Innovation and Technology Committee", "Commons"),
+            committee(327, "Communications and Digital Committee", "Lords")
+        ],
+        "itemsPerPage": 20,
+        "totalResults": 2
+    })
+}
+
+fn inquiries() -> Value {
+    json!({
+        "items": [
+            {
+                "id": 8102,
+                "title": "Online safety: the first year",
+                "type": {"id": 1, "name": "Inquiry", "isInquiry": true},
+                "openDate": "2024-01-15T00:00:00",
+                "closeDate": null
+            },
+            {
+                "id": 7411,
+                "title": "Social media and misinformation",
+                "type": {"id": 1, "name": "Inquiry", "isInquiry": true},
+                "openDate": "2023-03-01T00:00:00",
+                "closeDate": "2024-02-28T00:00:00"
+            }
+        ],
+        "totalResults": 2
+    })
+}
+
+async fn upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(COMMITTEES))
+        .respond_with(ResponseTemplate::new(200).set_body_json(committees()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(COMMITTEE_BUSINESS))
+        .respond_with(ResponseTemplate::new(200).set_body_json(inquiries()))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn requests_to(server: &MockServer, prefix: &str) -> Vec<wiremock::Request> {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|request| request.url.path().starts_with(prefix))
+        .collect()
+}
+
+#[tokio::test]
+async fn committees_are_listed_with_filters_sent_upstream() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": " online safety ", "house": "commons", "limit": 5}),
+    )
+    .await;
+
+    assert_eq!(result["totalResults"], 2);
+    assert_eq!(
+        result["committees"][0],
+        json!({
+            "id": 378,
+            "name": "Science, Innovation and Technology Committee",
+            "house": "Commons",
+            "category": "Select",
+            "url": "https://committees.parliament.uk/committee/378/"
+        })
+    );
+    assert!(result.get("note").is_none(), "{result}");
+
+    let requests = requests_to(&server, COMMITTEES).await;
+    let query: Vec<(String, String)> = requests[0]
+        .url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    for expected in [
+        ("SearchTerm", "online safety"),
+        ("House", "Commons"),
+        ("CommitteeStatus", "Current"),
+        ("Take", "5"),
+    ] {
+        assert!(
+            query
+                .iter()
+                .any(|(key, value)| key == expected.0 && value == expected.1),
+            "{expected:?} missing from {query:?}"
+        );
+    }
+    assert!(requests_to(&server, COMMITTEE_BUSINESS).await.is_empty());
+}
+
+#[tokio::test]
+async fn inquiries_are_listed_with_their_status() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"includeInquiries": true})).await;
+
+    assert_eq!(
+        result["committees"][0]["inquiries"],
+        json!([
+            {
+                "id": 8102,
+                "title": "Online safety: the first year",
+                "type": "Inquiry",
+                "status": "open",
+                "openDate": "2024-01-15",
+                "closeDate": null,
+                "url": "https://committees.parliament.uk/work/8102/"
+            },
+            {
+                "id": 7411,
+                "title": "Social media and misinformation",
+                "type": "Inquiry",
+                "status": "closed",
+                "openDate": "2023-03-01",
+                "closeDate": "2024-02-28",
+                "url": "https://committees.parliament.uk/work/7411/"
+            }
+        ])
+    );
+    let lookups = requests_to(&server, COMMITTEE_BUSINESS).await;
+    assert_eq!(lookups.len(), 2);
+    assert!(lookups.iter().any(|request| {
+        request
+            .url
+            .query()
+            .unwrap_or_default()
+            .contains("CommitteeId=327")
+    }));
+}
+
+#[tokio::test]
+async fn one_committee_is_looked_up_with_its_members() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/Committees/378"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(committee(
+            378,
+            "Science, Innovation and Technology Committee",
+            "Commons",
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/Committees/378/Members"))
+        .and(query_param("MembershipStatus", "Current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {
+                    "memberInfo": {"mnisId": 4514, "name": "Jane Example", "party": "Labour"},
+                    "roles": [
+                        {"role": {"name": "Member"}, "startDate": "2021-01-01", "endDate": "2023-11-01"},
+                        {"role": {"name": "Chair"}, "startDate": "2023-11-01", "endDate": null}
+                    ]
+                },
+                {
+                    "name": "Professor Lay Adviser",
+                    "roles": [{"role": {"name": "Lay Member"}}]
+                }
+            ],
+            "totalResults": 2
+        })))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"committeeId": 378})).await;
+
+    let committee = &result["committees"][0];
+    assert_eq!(committee["id"], 378);
+    assert_eq!(
+        committee["members"],
+        json!([
+            {"memberId": 4514, "name": "Jane Example", "party": "Labour", "role": "Chair"},
+            {"memberId": null, "name": "Professor Lay Adviser", "party": null, "role": "Lay Member"}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn a_failed_inquiry_lookup_is_noted_and_not_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(COMMITTEES))
+        .respond_with(ResponseTemplate::new(200).set_body_json(committees()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(COMMITTEE_BUSINESS))
+        .and(query_param("CommitteeId", "327"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(COMMITTEE_BUSINESS))
+        .respond_with(ResponseTemplate::new(200).set_body_json(inquiries()))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"includeInquiries": true})).await;
+
+    assert!(result["committees"][0]["inquiries"].is_array());
+    assert!(result["committees"][1].get("inquiries").is_none());
+    assert!(
+        result["note"]
+            .as_str()
+            .unwrap()
+            .contains("inquiries of 1 committee"),
+        "{result}"
+    );
+
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"includeInquiries": true, "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(cached["cacheMiss"], true, "{cached}");
+}
+
+#[tokio::test]
+async fn results_are_cached_per_filter() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    let first =
+        common::call_tool_ok(&harness.service, TOOL, json!({"searchTerm": "Digital"})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "digital", "cacheOnly": true}),
+    )
+    .await;
+    let other = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "digital", "house": "lords", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(other["cacheMiss"], true, "{other}");
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn malformed_arguments_are_rejected() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    for arguments in [
+        json!({"house": "scottish"}),
+        json!({"committeeId": 0}),
+        json!({"limit": 51}),
+        json!({"committeeId": 378, "searchTerm": "digital"}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
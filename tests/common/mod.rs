@@ -0,0 +1,607 @@
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::Router;
+use serde_json::{Value, json};
+use tokio::sync::Mutex;
+use tracing::subscriber::DefaultGuard;
+use tracing_subscriber::prelude::*;
+use wiremock::matchers::{method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::config::{
+    ApiKeysConfig, AppConfig, CacheTtlConfig, DEFAULT_KEY_NAME, ListenAddress,
+    ToolByteBudgetConfig, ToolFilterConfig, ToolTimeoutConfig,
+};
+use mp_writer_mcp_server::core::audit::AuditLog;
+use mp_writer_mcp_server::core::cache::CacheManager;
+use mp_writer_mcp_server::core::clock::Clock;
+use mp_writer_mcp_server::core::error::AppError;
+use mp_writer_mcp_server::core::locale::Locale;
+use mp_writer_mcp_server::core::preferences::PreferenceStore;
+use mp_writer_mcp_server::core::recording::{REDACTED_POSTCODE, RecordedResponse};
+use mp_writer_mcp_server::core::signing::ResponseSigner;
+use mp_writer_mcp_server::core::stats::StatsRegistry;
+use mp_writer_mcp_server::core::usage::UsageCollector;
+use mp_writer_mcp_server::features::mcp::dto::JsonRpcRequest;
+use mp_writer_mcp_server::features::mcp::{McpService, RequestContext};
+use mp_writer_mcp_server::features::parliament::{
+    FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs, ParliamentClient,
+};
+use mp_writer_mcp_server::features::research::{ParliamentDataSource, ResearchService};
+use mp_writer_mcp_server::server::{AppState, build_router};
+
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Collects formatted log output so tests can search it.
+#[derive(Clone, Default)]
+pub struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+
+    /// Every line parsed, for output captured with [`capture_json`].
+    pub fn json_lines(&self) -> Vec<Value> {
+        self.text()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).expect("JSON log line"))
+            .collect()
+    }
+}
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Plain-text log output at every level, captured for the rest of the test.
+pub fn capture() -> (CapturedLogs, DefaultGuard) {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || writer.clone()),
+        ),
+    );
+    (logs, guard)
+}
+
+/// JSON log output, as with `LOG_FORMAT=json`, captured for the rest of the test.
+pub fn capture_json() -> (CapturedLogs, DefaultGuard) {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .with_writer(move || writer.clone()),
+        ),
+    );
+    (logs, guard)
+}
+
+pub fn test_config(db_path: &str) -> AppConfig {
+    AppConfig {
+        listen: ListenAddress::Tcp("127.0.0.1:0".parse().unwrap()),
+        tls: None,
+        cors_allowed_origins: None,
+        allowed_cidrs: None,
+        trust_proxy_headers: false,
+        api_key: "test".to_string(),
+        api_keys: ApiKeysConfig::single("test"),
+        api_key_file: None,
+        api_key_file_poll_secs: 30,
+        admin_api_key: None,
+        allow_query_api_key: false,
+        max_request_bytes: 1_048_576,
+        disable_proxy: false,
+        cache_enabled: true,
+        relevance_threshold: 0.5,
+        cache_ttl: CacheTtlConfig {
+            members: 10,
+            bills: 10,
+            legislation: 10,
+            data: 10,
+            research: 3600,
+            activity: 10,
+            votes: 10,
+            hansard: 10,
+            written_questions: 10,
+            edms: 10,
+            oral_questions: 10,
+            constituency: 10,
+            bill_divisions: 10,
+            committees: 10,
+            statutory_instruments: 10,
+            division_detail: 10,
+            constituency_list: 3600,
+        },
+        db_path: db_path.to_string(),
+        tool_staleness_threshold: 3600,
+        stats_snapshot_interval: 0,
+        upstream_base_url: None,
+        upstream_record_dir: None,
+        faults: None,
+        upstream_requests_per_second: 0,
+        upstream_concurrency_floor: 1,
+        upstream_concurrency_ceiling: 8,
+        upstream_down_after_failures: 0,
+        upstream_down_secs: 60,
+        slow_request_ms: 0,
+        slow_upstream_ms: 0,
+        upstream_correlation_header: true,
+        prefetch_member_after_lookup: false,
+        enable_rest_api: false,
+        export_max_rows: 1000,
+        response_signing_key: None,
+        strict_args: true,
+        strict: false,
+        strict_output_validation: false,
+        tool_timeouts: ToolTimeoutConfig {
+            default_secs: 30,
+            overrides: HashMap::new(),
+        },
+        tool_byte_budgets: ToolByteBudgetConfig::default(),
+        policy_areas_path: None,
+        reference_data_dir: None,
+        reference_data_manifest_url: None,
+        reference_data_refresh_hours: 0,
+        resource_link_limit: 10,
+        research_max_stale_secs: 86_400,
+        research_stale_refresh_secs: 0,
+        research_max_terms: 6,
+        default_locale: Locale::En,
+        tool_filter: ToolFilterConfig::default(),
+        audit_retention_days: 90,
+        research_compact_after_days: 90,
+        usage_stats_enabled: false,
+        usage_k_threshold: 5,
+        shutdown_grace_secs: 30,
+        max_concurrent_requests: 32,
+        max_concurrent_research: 4,
+        request_queue_wait_secs: 5,
+    }
+}
+
+pub struct TestHarness {
+    pub service: Arc<McpService>,
+    pub client: Arc<ParliamentClient>,
+    pub research: Arc<ResearchService>,
+    pub config: Arc<AppConfig>,
+    pub stats: Arc<StatsRegistry>,
+    pub db: sled::Db,
+    _temp_dir: tempfile::TempDir,
+}
+
+pub fn build_service(
+    data_source: Option<Arc<dyn ParliamentDataSource>>,
+    configure: impl FnOnce(&mut AppConfig),
+) -> TestHarness {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let mut config = test_config(&temp_dir.path().to_string_lossy());
+    configure(&mut config);
+    let config = Arc::new(config);
+
+    let stats = Arc::new(StatsRegistry::new());
+    let clock = Clock::system();
+    let client = Arc::new(
+        ParliamentClient::new(
+            config.clone(),
+            CacheManager::new(true, 1000),
+            db.open_tree("parliament").expect("tree"),
+            stats.clone(),
+        )
+        .expect("client creation should succeed")
+        .with_clock(clock.clone()),
+    );
+    let data_source: Arc<dyn ParliamentDataSource> = data_source.unwrap_or_else(|| client.clone());
+    let research = Arc::new(
+        ResearchService::new(
+            config.clone(),
+            data_source,
+            db.open_tree("research").expect("tree"),
+        )
+        .with_clock(clock),
+    );
+    let audit_log = Arc::new(AuditLog::new(
+        db.open_tree("audit").expect("tree"),
+        config.api_key.clone(),
+    ));
+    let mut service = McpService::new(
+        config.clone(),
+        client.clone(),
+        research.clone(),
+        stats.clone(),
+    )
+    .with_audit_log(audit_log)
+    .with_preference_store(Arc::new(PreferenceStore::new(
+        db.open_tree("preferences").expect("tree"),
+    )));
+    if let Some(path) = config.response_signing_key.as_deref() {
+        let signer = ResponseSigner::from_pem_file(path).expect("signing key should load");
+        service = service.with_response_signer(Arc::new(signer));
+    }
+    if config.usage_stats_enabled {
+        service =
+            service.with_usage_collector(Arc::new(UsageCollector::new(config.usage_k_threshold)));
+    }
+    let service = Arc::new(service);
+
+    TestHarness {
+        service,
+        client,
+        research,
+        config,
+        stats,
+        db,
+        _temp_dir: temp_dir,
+    }
+}
+
+/// Serve every fixture recorded in `dir` from `server`. Requests match on path and query;
+/// redacted postcodes match any value.
+pub async fn mount_recorded(server: &MockServer, dir: &std::path::Path) {
+    for recorded in RecordedResponse::load_dir(dir).expect("recorded fixtures") {
+        let mut mock = if recorded.path.contains(REDACTED_POSTCODE) {
+            let pattern = recorded
+                .path
+                .split(REDACTED_POSTCODE)
+                .map(regex_escape)
+                .collect::<Vec<_>>()
+                .join("[^/]+");
+            Mock::given(method("GET")).and(path_regex(format!("^{pattern}$")))
+        } else {
+            Mock::given(method("GET")).and(path(recorded.path.as_str()))
+        };
+        for (name, value) in &recorded.query {
+            if !value.contains(REDACTED_POSTCODE) {
+                mock = mock.and(query_param(name.as_str(), value.as_str()));
+            }
+        }
+        let mut response = ResponseTemplate::new(recorded.status);
+        for (name, value) in &recorded.headers {
+            response = response.insert_header(name.as_str(), value.as_str());
+        }
+        mock.respond_with(response.set_body_bytes(recorded.body_bytes()))
+            .mount(server)
+            .await;
+    }
+}
+
+/// Initialized service whose upstream requests all go to `server`.
+pub async fn upstream_harness(server: &MockServer) -> TestHarness {
+    let uri = server.uri();
+    let harness = build_service(None, |config| config.upstream_base_url = Some(uri));
+    initialize(&harness.service).await;
+    harness
+}
+
+/// `upstream_harness` with every fixture recorded in `dir` mounted on `server`.
+pub async fn recorded_harness(server: &MockServer, dir: &std::path::Path) -> TestHarness {
+    mount_recorded(server, dir).await;
+    upstream_harness(server).await
+}
+
+fn regex_escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| {
+            let escape = !c.is_ascii_alphanumeric() && !matches!(c, '/' | '-' | '_');
+            escape.then_some('\\').into_iter().chain([c])
+        })
+        .collect()
+}
+
+impl TestHarness {
+    /// Router for a server that has finished start-up.
+    pub fn router(&self) -> Router {
+        let state = self.state();
+        state.readiness.mark_ready();
+        build_router(state)
+    }
+
+    /// State whose readiness flag is still unset.
+    pub fn state(&self) -> AppState {
+        AppState::new(
+            self.service.clone(),
+            self.client.clone(),
+            self.config.clone(),
+        )
+    }
+}
+
+pub fn request(id: Option<Value>, method: &str, params: Option<Value>) -> JsonRpcRequest {
+    JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id,
+        method: method.to_string(),
+        params,
+        extra: Default::default(),
+    }
+}
+
+/// An in-process request made with the `test` key, so it shares a session with HTTP
+/// requests sent with that key.
+pub fn context() -> RequestContext {
+    RequestContext::new(Some(PROTOCOL_VERSION.to_string())).with_key_name(DEFAULT_KEY_NAME)
+}
+
+pub async fn initialize(service: &McpService) {
+    let params = json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "clientInfo": {"name": "test-client", "version": "1.0"},
+        "capabilities": {}
+    });
+    service
+        .handle_jsonrpc(
+            request(Some(json!(1)), "initialize", Some(params)),
+            context(),
+        )
+        .await
+        .expect("initialize should succeed");
+    service
+        .handle_jsonrpc(request(None, "notifications/initialized", None), context())
+        .await
+        .expect("initialized notification should succeed");
+}
+
+pub async fn call_tool(service: &McpService, name: &str, arguments: Value) -> Value {
+    call_tool_with_context(service, name, arguments, context()).await
+}
+
+/// `structuredContent` of a call that must not be a tool error.
+pub async fn call_tool_ok(service: &McpService, name: &str, arguments: Value) -> Value {
+    let response = call_tool(service, name, arguments).await;
+    assert!(response["result"]["isError"].is_null(), "{response}");
+    response["result"]["structuredContent"].clone()
+}
+
+pub async fn call_tool_with_context(
+    service: &McpService,
+    name: &str,
+    arguments: Value,
+    context: RequestContext,
+) -> Value {
+    let response = service
+        .handle_jsonrpc(
+            request(
+                Some(json!(99)),
+                "tools/call",
+                Some(json!({"name": name, "arguments": arguments})),
+            ),
+            context,
+        )
+        .await;
+
+    match response {
+        Ok(Some(success)) => serde_json::to_value(success).expect("encode success"),
+        Ok(None) => Value::Null,
+        Err(error) => serde_json::to_value(error).expect("encode error"),
+    }
+}
+
+/// In-memory data source returning canned payloads. Collections listed in `failing` return
+/// an upstream error, those in `panicking` panic, and every call sleeps for `delay` when set.
+pub struct MockParliamentDataSource {
+    pub bills: Value,
+    pub divisions: Value,
+    pub legislation: Value,
+    pub debates: Value,
+    pub edms: Value,
+    pub parties: Value,
+    pub failing: HashSet<String>,
+    pub panicking: HashSet<String>,
+    /// Collections that answer `cacheOnly` calls as not cached.
+    pub uncached: HashSet<String>,
+    /// Hosts reported as marked down by the availability tracker.
+    pub down_hosts: HashSet<String>,
+    pub delay: Option<Duration>,
+    calls: Arc<Mutex<HashMap<String, usize>>>,
+    search_terms: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl MockParliamentDataSource {
+    pub fn new() -> Self {
+        Self {
+            bills: json!({
+                "items": [
+                    {
+                        "title": "Climate Change Bill",
+                        "billStage": {"description": "Committee"},
+                        "lastUpdate": "2024-06-01",
+                        "billId": 123
+                    }
+                ]
+            }),
+            divisions: json!({
+                "items": [
+                    {
+                        "title": "Division on Climate",
+                        "divisionNumber": "12",
+                        "date": "2024-05-20",
+                        "ayes": 300,
+                        "noes": 200,
+                        "result": "Ayes",
+                        "uri": "https://example.com/division/12"
+                    }
+                ]
+            }),
+            legislation: json!({
+                "items": [
+                    {
+                        "title": "Climate Act",
+                        "year": "2008",
+                        "type": "ukpga",
+                        "uri": "https://www.legislation.gov.uk/id/ukpga/2008/27"
+                    }
+                ]
+            }),
+            debates: json!({
+                "items": [
+                    {
+                        "title": "Climate debate",
+                        "house": "Commons",
+                        "date": "2024-05-10",
+                        "summary": "Members discussed climate action.",
+                        "uri": "https://example.com/debate"
+                    }
+                ]
+            }),
+            edms: json!({
+                "items": [
+                    {
+                        "title": "Climate emergency and net zero",
+                        "uri": "https://example.com/edm/1"
+                    }
+                ]
+            }),
+            parties: json!({
+                "items": [
+                    {"party": "Example Party", "seats": 300}
+                ],
+                "totalSeats": 650,
+                "lastUpdated": "2024-06-01"
+            }),
+            failing: HashSet::new(),
+            panicking: HashSet::new(),
+            uncached: HashSet::new(),
+            down_hosts: HashSet::new(),
+            delay: None,
+            calls: Arc::new(Mutex::new(HashMap::new())),
+            search_terms: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn failing(mut self, collections: &[&str]) -> Self {
+        self.failing = collections.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    pub fn panicking(mut self, collections: &[&str]) -> Self {
+        self.panicking = collections.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    pub fn uncached(mut self, collections: &[&str]) -> Self {
+        self.uncached = collections.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    pub fn down(mut self, hosts: &[&str]) -> Self {
+        self.down_hosts = hosts.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    pub async fn count_for(&self, key: &str) -> usize {
+        let guard = self.calls.lock().await;
+        guard.get(key).copied().unwrap_or(0)
+    }
+
+    /// Search terms sent to `key`, in call order.
+    pub async fn search_terms_for(&self, key: &str) -> Vec<String> {
+        let guard = self.search_terms.lock().await;
+        guard
+            .iter()
+            .filter(|(collection, _)| collection == key)
+            .map(|(_, term)| term.clone())
+            .collect()
+    }
+
+    async fn record_term(&self, key: &str, term: Option<&String>) {
+        if let Some(term) = term {
+            let mut guard = self.search_terms.lock().await;
+            guard.push((key.to_string(), term.clone()));
+        }
+    }
+
+    pub async fn total_calls(&self) -> usize {
+        let guard = self.calls.lock().await;
+        guard.values().sum()
+    }
+
+    async fn respond(
+        &self,
+        key: &str,
+        payload: &Value,
+        cache_only: Option<bool>,
+    ) -> Result<Value, AppError> {
+        {
+            let mut guard = self.calls.lock().await;
+            *guard.entry(key.to_string()).or_insert(0) += 1;
+        }
+
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        if cache_only == Some(true) && self.uncached.contains(key) {
+            return Err(AppError::not_cached(format!("{key} is not cached")));
+        }
+
+        if self.panicking.contains(key) {
+            panic!("{key} mock panicked");
+        }
+
+        if self.failing.contains(key) {
+            return Err(AppError::upstream_with_data(
+                format!("{key} upstream unavailable"),
+                json!({"status": 503}),
+            ));
+        }
+
+        Ok(payload.clone())
+    }
+}
+
+#[async_trait]
+impl ParliamentDataSource for MockParliamentDataSource {
+    async fn fetch_bills(&self, args: FetchBillsArgs) -> Result<Value, AppError> {
+        self.record_term("bills", args.search_term.as_ref()).await;
+        self.respond("bills", &self.bills, args.cache_only).await
+    }
+
+    async fn fetch_core_dataset(&self, args: FetchCoreDatasetArgs) -> Result<Value, AppError> {
+        let payload = match args.dataset.as_str() {
+            "commonsdivisions" => self.divisions.clone(),
+            "commonsdebates" => self.debates.clone(),
+            "edms" => self.edms.clone(),
+            "stateofparties" => self.parties.clone(),
+            _ => Value::Null,
+        };
+        self.record_term(&args.dataset, args.search_term.as_ref())
+            .await;
+        self.respond(&args.dataset, &payload, args.cache_only).await
+    }
+
+    async fn fetch_legislation(&self, args: FetchLegislationArgs) -> Result<Value, AppError> {
+        self.record_term("legislation", args.title.as_ref()).await;
+        self.respond("legislation", &self.legislation, args.cache_only)
+            .await
+    }
+
+    fn host_down(&self, host: &str) -> bool {
+        self.down_hosts.contains(host)
+    }
+}
@@ -0,0 +1,286 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::mcp::completions::{
+    CompletionRegistry, MAX_COMPLETION_VALUES, rank_fuzzy,
+};
+
+mod common;
+
+async fn complete(
+    harness: &common::TestHarness,
+    reference: Value,
+    name: &str,
+    value: &str,
+) -> Value {
+    complete_with_context(harness, reference, name, value, json!({})).await
+}
+
+async fn complete_with_context(
+    harness: &common::TestHarness,
+    reference: Value,
+    name: &str,
+    value: &str,
+    arguments: Value,
+) -> Value {
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(5)),
+                "completion/complete",
+                Some(json!({
+                    "ref": reference,
+                    "argument": {"name": name, "value": value},
+                    "context": {"arguments": arguments}
+                })),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("completion/complete")
+        .expect("response");
+    response.result["completion"].clone()
+}
+
+fn tool_ref(name: &str) -> Value {
+    json!({"type": "ref/tool", "name": name})
+}
+
+#[tokio::test]
+async fn completes_dataset_names_case_insensitively() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let completion = complete(
+        &harness,
+        tool_ref("parliament.fetch_core_dataset"),
+        "dataset",
+        "COMMONS",
+    )
+    .await;
+
+    let values = completion["values"].as_array().expect("values");
+    assert!(values.contains(&json!("commonsmembers")));
+    assert!(values.contains(&json!("commonsdivisions")));
+    assert!(values.contains(&json!("commonsdebates")));
+    assert!(!values.contains(&json!("lordsmembers")));
+    assert_eq!(completion["total"], json!(values.len()));
+    assert_eq!(completion["hasMore"], json!(false));
+}
+
+#[tokio::test]
+async fn completes_legislation_type_and_house() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let types = complete(
+        &harness,
+        tool_ref("parliament.search_uk_law"),
+        "legislationType",
+        "",
+    )
+    .await;
+    assert_eq!(types["values"], json!(["primary", "secondary", "all"]));
+
+    let houses = complete(&harness, tool_ref("parliament.fetch_bills"), "house", "l").await;
+    assert_eq!(houses["values"], json!(["lords"]));
+}
+
+#[tokio::test]
+async fn unknown_completion_targets_return_empty_results() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let unknown_argument =
+        complete(&harness, tool_ref("parliament.fetch_bills"), "session", "").await;
+    assert_eq!(unknown_argument["values"], json!([]));
+
+    let prompt_ref = complete(
+        &harness,
+        json!({"type": "ref/prompt", "name": "summarise"}),
+        "dataset",
+        "",
+    )
+    .await;
+    assert_eq!(prompt_ref["values"], json!([]));
+    assert_eq!(prompt_ref["hasMore"], json!(false));
+}
+
+#[tokio::test]
+async fn initialize_advertises_completions_capability() {
+    let harness = common::build_service(None, |_| {});
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(1)),
+                "initialize",
+                Some(json!({
+                    "protocolVersion": common::PROTOCOL_VERSION,
+                    "clientInfo": {"name": "test-client", "version": "1.0"},
+                    "capabilities": {}
+                })),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("initialize")
+        .expect("response");
+
+    assert!(response.result["capabilities"]["completions"].is_object());
+}
+
+#[test]
+fn completion_results_are_capped() {
+    let mut registry = CompletionRegistry::new();
+    registry.register(
+        "example.tool",
+        "value",
+        (0..150).map(|index| format!("value-{index}")),
+    );
+
+    let result = registry.complete("example.tool", "value", "VALUE-");
+    assert_eq!(result.values.len(), MAX_COMPLETION_VALUES);
+    assert_eq!(result.total, 150);
+    assert!(result.has_more);
+}
+
+/// One page of constituencies and one of sitting MPs, enough to seed both cached lists.
+async fn constituency_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    let constituencies = [
+        (4001, "Cardiff East", "W07000090"),
+        (4002, "Cardiff West", "W07000091"),
+        (4003, "Aberdeen North", "S14000001"),
+        (4004, "Camberwell and Peckham", "E14001137"),
+    ]
+    .map(|(id, name, gss_code)| json!({"value": {"id": id, "name": name, "gssCode": gss_code}}));
+    let members =
+        [(11, "Jo Stevens", 4001), (12, "Kirsty Blackman", 4003)].map(|(id, name, seat)| {
+            json!({
+                "value": {
+                    "id": id,
+                    "nameDisplayAs": name,
+                    "latestHouseMembership": {"membershipFromId": seat}
+                }
+            })
+        });
+    for (route, items) in [
+        ("/api/Location/Constituency/Search", constituencies.to_vec()),
+        ("/api/Members/Search", members.to_vec()),
+    ] {
+        let total = items.len();
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"items": items, "totalResults": total})),
+            )
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+async fn seeded_harness(server: &MockServer) -> common::TestHarness {
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+    common::call_tool(
+        &harness.service,
+        "parliament.list_constituencies",
+        json!({}),
+    )
+    .await;
+    harness
+}
+
+#[tokio::test]
+async fn completes_constituency_names_fuzzily_from_the_cached_list() {
+    let server = constituency_upstream().await;
+    let uri = server.uri();
+    let unseeded = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&unseeded.service).await;
+    let before = complete(
+        &unseeded,
+        tool_ref("parliament.list_constituencies"),
+        "search",
+        "card",
+    )
+    .await;
+    assert_eq!(before["values"], json!([]));
+    // Completion reads the cache only; it never fetches the list itself.
+    assert!(server.received_requests().await.unwrap().is_empty());
+
+    let harness = seeded_harness(&server).await;
+    let search = |value: &'static str| {
+        complete(
+            &harness,
+            tool_ref("parliament.list_constituencies"),
+            "search",
+            value,
+        )
+    };
+
+    assert_eq!(
+        search("CARD").await["values"],
+        json!(["Cardiff East", "Cardiff West"])
+    );
+    // Whole-name prefixes rank ahead of a later word starting with the text.
+    assert_eq!(
+        search("ca").await["values"],
+        json!(["Camberwell and Peckham", "Cardiff East", "Cardiff West"])
+    );
+    assert_eq!(search("north").await["values"], json!(["Aberdeen North"]));
+    assert_eq!(
+        search("peckam").await["values"],
+        json!(["Camberwell and Peckham"])
+    );
+    assert_eq!(
+        search("cardif west").await["values"],
+        json!(["Cardiff West"])
+    );
+    assert_eq!(search("").await["total"], json!(4));
+}
+
+#[tokio::test]
+async fn completes_member_names_for_members_datasets_only() {
+    let server = constituency_upstream().await;
+    let harness = seeded_harness(&server).await;
+
+    let members = complete_with_context(
+        &harness,
+        tool_ref("parliament.fetch_core_dataset"),
+        "searchTerm",
+        "black",
+        json!({"dataset": "commonsmembers"}),
+    )
+    .await;
+    assert_eq!(members["values"], json!(["Kirsty Blackman"]));
+
+    for arguments in [json!({"dataset": "commonsdebates"}), json!({})] {
+        let other = complete_with_context(
+            &harness,
+            tool_ref("parliament.fetch_core_dataset"),
+            "searchTerm",
+            "black",
+            arguments,
+        )
+        .await;
+        assert_eq!(other["values"], json!([]));
+    }
+}
+
+#[test]
+fn fuzzy_ranking_is_capped() {
+    let names = (0..150)
+        .map(|index| format!("Seat {index}"))
+        .collect::<Vec<_>>();
+    let result = rank_fuzzy(&names, "seat");
+    assert_eq!(result.values.len(), MAX_COMPLETION_VALUES);
+    assert_eq!(result.total, 150);
+    assert!(result.has_more);
+    assert!(rank_fuzzy(&names, "borough").values.is_empty());
+}
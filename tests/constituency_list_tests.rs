@@ -0,0 +1,161 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const LOCATION_PATH: &str = "/api/Location/Constituency/Search";
+const MEMBERS_PATH: &str = "/api/Members/Search";
+
+fn constituency(id: u32, name: &str, gss_code: &str) -> Value {
+    json!({"value": {"id": id, "name": name, "gssCode": gss_code}})
+}
+
+fn member(id: u32, name: &str, party: &str, constituency_id: u32) -> Value {
+    json!({
+        "value": {
+            "id": id,
+            "nameDisplayAs": name,
+            "latestParty": {"name": party},
+            "latestHouseMembership": {"membershipFromId": constituency_id}
+        }
+    })
+}
+
+async fn mount_page(server: &MockServer, route: &str, skip: &str, items: Vec<Value>, total: u32) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .and(query_param("skip", skip))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"items": items, "totalResults": total})),
+        )
+        .mount(server)
+        .await;
+}
+
+/// Four constituencies over two pages and three sitting MPs over two pages.
+async fn paged_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    mount_page(
+        &server,
+        LOCATION_PATH,
+        "0",
+        vec![
+            constituency(4001, "Cardiff East", "W07000090"),
+            constituency(4002, "Cardiff West", "W07000091"),
+        ],
+        4,
+    )
+    .await;
+    mount_page(
+        &server,
+        LOCATION_PATH,
+        "2",
+        vec![
+            constituency(4003, "Aberdeen North", "S14000001"),
+            constituency(4004, "Camberwell and Peckham", "E14001137"),
+        ],
+        4,
+    )
+    .await;
+    mount_page(
+        &server,
+        MEMBERS_PATH,
+        "0",
+        vec![
+            member(11, "Jo Stevens", "Labour", 4001),
+            member(12, "Alex Barros-Curtis", "Labour", 4002),
+        ],
+        3,
+    )
+    .await;
+    mount_page(
+        &server,
+        MEMBERS_PATH,
+        "2",
+        vec![member(
+            13,
+            "Kirsty Blackman",
+            "Scottish National Party",
+            4003,
+        )],
+        3,
+    )
+    .await;
+    server
+}
+
+async fn requests_to(server: &MockServer, route: &str) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == route)
+        .count()
+}
+
+#[tokio::test]
+async fn fetches_every_page_once_and_filters_locally() {
+    let server = paged_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.list_constituencies",
+        json!({}),
+    )
+    .await;
+    let payload = &response["result"]["structuredContent"];
+    assert_eq!(payload["total"], 4, "response: {response}");
+    assert_eq!(payload["matched"], 4);
+    let first = &payload["constituencies"][0];
+    assert_eq!(first["name"], "Aberdeen North");
+    assert_eq!(first["onsCode"], "S14000001");
+    assert_eq!(first["nation"], "Scotland");
+    assert_eq!(first["mpId"], 13);
+    assert_eq!(first["mpParty"], "Scottish National Party");
+    let vacant = &payload["constituencies"][1];
+    assert_eq!(vacant["name"], "Camberwell and Peckham");
+    assert!(vacant["mpId"].is_null());
+
+    let welsh = common::call_tool(
+        &harness.service,
+        "parliament.list_constituencies",
+        json!({"nation": "Wales", "search": "cardiff w"}),
+    )
+    .await;
+    let payload = &welsh["result"]["structuredContent"];
+    assert_eq!(payload["total"], 4);
+    assert_eq!(payload["matched"], 1, "response: {welsh}");
+    assert_eq!(payload["constituencies"][0]["name"], "Cardiff West");
+    assert_eq!(payload["constituencies"][0]["mpName"], "Alex Barros-Curtis");
+
+    let by_region = common::call_tool(
+        &harness.service,
+        "parliament.list_constituencies",
+        json!({"region": "London"}),
+    )
+    .await;
+    assert_eq!(by_region["result"]["structuredContent"]["matched"], 0);
+
+    assert_eq!(requests_to(&server, LOCATION_PATH).await, 2);
+    assert_eq!(requests_to(&server, MEMBERS_PATH).await, 2);
+}
+
+#[tokio::test]
+async fn rejects_unknown_nations() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.list_constituencies",
+        json!({"nation": "Atlantis"}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602, "response: {response}");
+}
@@ -0,0 +1,130 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::correlation::{correlation_id_for, new_correlation_id};
+
+mod common;
+
+async fn member_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/Members/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "value": {
+                "id": 7,
+                "nameDisplayAs": "Example Member",
+                "latestHouseMembership": {"membershipFrom": "Somewhere", "house": 1}
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdivisions.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn upstream_correlation_ids(server: &MockServer) -> Vec<Option<String>> {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|request| {
+            request
+                .headers
+                .get("x-correlation-id")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+fn result_correlation_id(response: &Value) -> String {
+    response["result"]["_meta"]["correlationId"]
+        .as_str()
+        .unwrap_or_else(|| panic!("missing correlation id: {response}"))
+        .to_string()
+}
+
+#[test]
+fn client_request_ids_are_reused_only_when_well_formed() {
+    assert_eq!(correlation_id_for(Some(" trace-42.a_b ")), "trace-42.a_b");
+
+    for rejected in [None, Some(""), Some("has spaces"), Some("bad\nid")] {
+        let id = correlation_id_for(rejected);
+        assert_eq!(id.len(), 32, "{rejected:?} should get a generated id");
+    }
+    assert_eq!(correlation_id_for(Some(&"a".repeat(65))).len(), 32);
+    assert_ne!(new_correlation_id(), new_correlation_id());
+}
+
+#[tokio::test]
+async fn upstream_requests_carry_the_returned_correlation_id() {
+    let server = member_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": [7], "enableCache": false}),
+    )
+    .await;
+
+    let correlation_id = result_correlation_id(&response);
+    assert_eq!(
+        upstream_correlation_ids(&server).await,
+        [Some(correlation_id)]
+    );
+}
+
+#[tokio::test]
+async fn client_request_id_flows_upstream_and_into_error_data() {
+    let server = member_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool_with_context(
+        &harness.service,
+        "parliament.fetch_core_dataset",
+        json!({"dataset": "commonsdivisions", "enableCache": false}),
+        common::context().with_request_id("trace-9"),
+    )
+    .await;
+    let result = &response["result"];
+    assert_eq!(result["isError"], true, "response: {response}");
+    assert_eq!(result["structuredContent"]["correlationId"], "trace-9");
+    assert_eq!(result_correlation_id(&response), "trace-9");
+
+    assert_eq!(
+        upstream_correlation_ids(&server).await,
+        [Some("trace-9".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn header_can_be_switched_off() {
+    let server = member_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.upstream_correlation_header = false;
+    });
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": [7], "enableCache": false}),
+    )
+    .await;
+
+    assert!(!result_correlation_id(&response).is_empty());
+    assert_eq!(upstream_correlation_ids(&server).await, [None]);
+}
@@ -0,0 +1,132 @@
+use axum::body::Body;
+use axum::http::{HeaderMap, Method, Request, StatusCode, header};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::CorsOrigins;
+
+mod common;
+
+const APP: &str = "https://app.example.org";
+
+async fn send(origins: Option<CorsOrigins>, request: Request<Body>) -> (StatusCode, HeaderMap) {
+    let harness = common::build_service(None, |config| config.cors_allowed_origins = origins);
+    let response = harness.router().oneshot(request).await.unwrap();
+    (response.status(), response.headers().clone())
+}
+
+fn preflight(origin: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::OPTIONS)
+        .uri("/api/mcp")
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .header(
+            header::ACCESS_CONTROL_REQUEST_HEADERS,
+            "content-type,x-api-key,mcp-protocol-version",
+        )
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn health(origin: &str) -> Request<Body> {
+    Request::get("/api/health")
+        .header(header::ORIGIN, origin)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn list(origins: &str) -> Option<CorsOrigins> {
+    CorsOrigins::parse(origins).unwrap()
+}
+
+#[test]
+fn allowed_origins_are_parsed() {
+    assert_eq!(CorsOrigins::parse(" , ").unwrap(), None);
+    assert_eq!(CorsOrigins::parse(" * ").unwrap(), Some(CorsOrigins::Any));
+    assert_eq!(
+        CorsOrigins::parse("https://app.example.org/, http://localhost:5173").unwrap(),
+        Some(CorsOrigins::List(vec![
+            APP.to_string(),
+            "http://localhost:5173".to_string()
+        ]))
+    );
+
+    let error = CorsOrigins::parse("*,https://app.example.org")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("cannot combine *"), "{error}");
+    for bad in [
+        "app.example.org",
+        "ftp://app.example.org",
+        "https://app.example.org/mcp",
+    ] {
+        let error = CorsOrigins::parse(bad).unwrap_err().to_string();
+        assert!(error.contains(bad), "{error}");
+    }
+}
+
+#[tokio::test]
+async fn preflight_is_answered_without_an_api_key() {
+    let (status, headers) = send(list(APP), preflight(APP)).await;
+    assert!(status.is_success(), "status: {status}");
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], APP);
+    let methods = headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+        .to_str()
+        .unwrap();
+    for method in ["POST", "GET", "DELETE", "OPTIONS"] {
+        assert!(methods.contains(method), "{methods}");
+    }
+    let allowed = headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+        .to_str()
+        .unwrap();
+    for name in [
+        "content-type",
+        "x-api-key",
+        "authorization",
+        "mcp-protocol-version",
+        "mcp-session-id",
+    ] {
+        assert!(allowed.contains(name), "{allowed}");
+    }
+}
+
+#[tokio::test]
+async fn responses_name_the_allowed_origin_and_expose_the_session_id() {
+    let (status, headers) = send(list(APP), health(APP)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], APP);
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_EXPOSE_HEADERS],
+        "mcp-session-id"
+    );
+
+    let (_, headers) = send(list(APP), health("https://elsewhere.example")).await;
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+    let (_, headers) = send(list("*"), health("https://elsewhere.example")).await;
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+}
+
+#[tokio::test]
+async fn authenticated_routes_still_need_a_key_outside_preflight() {
+    let request = Request::post("/api/mcp")
+        .header(header::ORIGIN, APP)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        ))
+        .unwrap();
+    let (status, headers) = send(list(APP), request).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], APP);
+}
+
+#[tokio::test]
+async fn no_cors_headers_are_sent_when_unset() {
+    let (status, headers) = send(None, preflight(APP)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+    let (_, headers) = send(None, health(APP)).await;
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
@@ -0,0 +1,177 @@
+use std::path::Path;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_division_detail";
+
+/// Commons Votes API: division 1732 with both pairs of tellers, trimmed to a few members
+/// in each lobby.
+const DIVISION_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/divisions");
+
+#[tokio::test]
+async fn commons_division_lists_members_and_tellers() {
+    let _server = MockServer::start().await;
+    let harness = common::recorded_harness(&_server, Path::new(DIVISION_FIXTURES)).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"divisionId": 1732})).await;
+
+    assert_eq!(result["divisionId"], 1732);
+    assert_eq!(result["house"], "Commons");
+    assert_eq!(result["number"], 598);
+    assert_eq!(
+        result["title"],
+        "Renters (Reform) Bill: Report Stage: New Clause 1"
+    );
+    assert_eq!(result["date"], "2024-03-12");
+    assert_eq!(result["ayes"], 180);
+    assert_eq!(result["noes"], 287);
+    assert_eq!(result["result"], "negatived");
+    assert_eq!(
+        result["link"],
+        "https://votes.parliament.uk/votes/commons/division/1732"
+    );
+    assert_eq!(result["ayeMembers"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        result["ayeMembers"][0],
+        json!({"memberId": 172, "name": "Diane Abbott", "party": "Independent"})
+    );
+    assert_eq!(result["noMembers"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        result["ayeTellers"],
+        json!([
+            {"memberId": 4771, "name": "Gen Kitchen", "party": "Labour"},
+            {"memberId": 4860, "name": "Navendu Mishra", "party": "Labour"}
+        ])
+    );
+    assert_eq!(
+        result["noTellers"],
+        json!([
+            {"memberId": 4657, "name": "Joy Morrissey", "party": "Conservative"},
+            {"memberId": 4772, "name": "Robbie Moore", "party": "Conservative"}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn names_only_leaves_out_parties_and_shares_the_cache() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(DIVISION_FIXTURES)).await;
+
+    let full = common::call_tool_ok(&harness.service, TOOL, json!({"divisionId": 1732})).await;
+    let names = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"divisionId": 1732, "namesOnly": true, "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(
+        names["noTellers"][0],
+        json!({"memberId": 4657, "name": "Joy Morrissey"})
+    );
+    for list in ["ayeMembers", "noMembers", "ayeTellers", "noTellers"] {
+        assert!(
+            names[list]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|member| member.get("party").is_none()),
+            "{names}"
+        );
+        assert_eq!(
+            names[list].as_array().unwrap().len(),
+            full[list].as_array().unwrap().len()
+        );
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn lords_divisions_use_contents_and_not_contents() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/Divisions/2950"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "divisionId": 2950,
+            "date": "2024-04-22T18:05:00",
+            "number": 3,
+            "title": "Safety of Rwanda (Asylum and Immigration) Bill: Motion A1",
+            "authoritativeContentCount": 2,
+            "authoritativeNotContentCount": 1,
+            "contents": [
+                {"memberId": 3898, "name": "Lord Anderson of Ipswich", "party": "Crossbench"},
+                {"memberId": 2212, "name": "Baroness Chakrabarti", "party": "Labour"}
+            ],
+            "notContents": [
+                {"memberId": 4248, "name": "Lord Sharpe of Epsom", "party": "Conservative"}
+            ],
+            "contentTellers": [
+                {"memberId": 3474, "name": "Lord Kennedy of Southwark", "party": "Labour"}
+            ],
+            "notContentTellers": []
+        })))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"divisionId": 2950, "house": "lords"}),
+    )
+    .await;
+
+    assert_eq!(result["house"], "Lords");
+    assert_eq!(result["result"], "agreed");
+    assert_eq!(result["ayes"], 2);
+    assert_eq!(result["ayeMembers"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        result["noMembers"],
+        json!([{"memberId": 4248, "name": "Lord Sharpe of Epsom", "party": "Conservative"}])
+    );
+    assert_eq!(result["ayeTellers"][0]["memberId"], 3474);
+    assert_eq!(result["noTellers"], json!([]));
+    assert_eq!(
+        result["link"],
+        "https://votes.parliament.uk/votes/lords/division/2950"
+    );
+}
+
+#[tokio::test]
+async fn an_unknown_division_is_an_upstream_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/division/99999999.json"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response = common::call_tool(&harness.service, TOOL, json!({"divisionId": 99999999})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn malformed_arguments_are_rejected() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(DIVISION_FIXTURES)).await;
+
+    for arguments in [
+        json!({}),
+        json!({"divisionId": 0}),
+        json!({"divisionId": 1732, "house": "senate"}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
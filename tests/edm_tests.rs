@@ -0,0 +1,218 @@
+use std::path::Path;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_edms";
+
+/// Oral Questions and Motions API: six motions matching `pharmacies`, newest first, and
+/// the sponsors of the first five. The newest has a withdrawn signature.
+const EDM_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/edms");
+
+const EDM_LIST: &str = "/EarlyDayMotions/list";
+
+async fn detail_requests(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path().starts_with("/EarlyDayMotion/"))
+        .count()
+}
+
+#[tokio::test]
+async fn motions_carry_their_sponsor_and_signature_count() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(EDM_FIXTURES)).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "pharmacies", "limit": 6}),
+    )
+    .await;
+
+    assert_eq!(result["totalResults"], 37);
+    assert!(result.get("note").is_none(), "{result}");
+    let motions = result["motions"].as_array().unwrap();
+    assert_eq!(motions.len(), 6);
+    assert_eq!(
+        motions[0],
+        json!({
+            "id": 62006,
+            "number": "512",
+            "title": "Community pharmacies",
+            "motionText": "That this House recognises the vital role of community pharmacies in providing accessible healthcare; notes that over 400 pharmacies closed in the last year; and calls on the Government to review the funding contract.",
+            "primarySponsor": {"memberId": 4514, "name": "Jane Example", "party": "Labour"},
+            "signatureCount": 23,
+            "dateTabled": "2024-05-20",
+            "status": "Published",
+            "url": "https://edm.parliament.uk/early-day-motion/62006"
+        })
+    );
+    assert_eq!(detail_requests(&server).await, 0);
+}
+
+#[tokio::test]
+async fn signatories_are_listed_for_the_first_five_motions() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(EDM_FIXTURES)).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "pharmacies", "limit": 6, "includeSignatories": true}),
+    )
+    .await;
+
+    let motions = result["motions"].as_array().unwrap();
+    assert!(
+        motions[..5]
+            .iter()
+            .all(|motion| motion["signatories"].is_array())
+    );
+    assert!(motions[5].get("signatories").is_none(), "{}", motions[5]);
+    assert_eq!(detail_requests(&server).await, 5);
+    assert!(
+        result["note"].as_str().unwrap().contains("first 5 motions"),
+        "{result}"
+    );
+
+    // The withdrawn signature is left out.
+    assert_eq!(
+        motions[0]["signatories"],
+        json!([
+            {"memberId": 4514, "name": "Jane Example", "party": "Labour", "signedDate": "2024-05-20"},
+            {"memberId": 172, "name": "Diane Abbott", "party": "Independent", "signedDate": "2024-05-20"},
+            {"memberId": 4823, "name": "Tim Farron", "party": "Liberal Democrat", "signedDate": "2024-05-20"}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn results_are_cached_by_the_full_parameter_set() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(EDM_FIXTURES)).await;
+
+    let first = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "  pharmacies ", "limit": 6, "includeSignatories": true}),
+    )
+    .await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "pharmacies", "limit": 6, "includeSignatories": true, "cacheOnly": true}),
+    )
+    .await;
+    let without_signatories = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "pharmacies", "limit": 6, "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(
+        without_signatories["cacheMiss"], true,
+        "{without_signatories}"
+    );
+    assert_eq!(detail_requests(&server).await, 5);
+}
+
+#[tokio::test]
+async fn a_failed_signatory_lookup_is_noted_and_not_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(EDM_LIST))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "PagingInfo": {"Skip": 0, "Take": 20, "Total": 1},
+            "Response": [{
+                "Id": 62001,
+                "Title": "Community pharmacies",
+                "MotionText": "That this House recognises community pharmacies.",
+                "DateTabled": "2024-02-05T00:00:00",
+                "SponsorsCount": 3
+            }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/EarlyDayMotion/62001"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"includeSignatories": true})).await;
+
+    assert_eq!(result["motions"].as_array().unwrap().len(), 1);
+    assert!(result["motions"][0].get("signatories").is_none());
+    assert!(
+        result["note"]
+            .as_str()
+            .unwrap()
+            .contains("signatories of 1 motion"),
+        "{result}"
+    );
+
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"includeSignatories": true, "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(cached["cacheMiss"], true, "{cached}");
+}
+
+#[tokio::test]
+async fn filters_are_sent_upstream() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(EDM_LIST))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"Response": []})))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({
+            "sponsorId": "https://members.parliament.uk/member/4514",
+            "status": "withdrawn",
+            "tabledFrom": "2024-01-01",
+            "tabledTo": "2024-06-30",
+            "limit": 10
+        }),
+    )
+    .await;
+
+    let requests = server.received_requests().await.unwrap();
+    let query: Vec<(String, String)> = requests[0]
+        .url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    for expected in [
+        ("parameters.memberId", "4514"),
+        ("parameters.includeSponsoredByMember", "true"),
+        ("parameters.statuses", "Withdrawn"),
+        ("parameters.tabledStartDate", "2024-01-01"),
+        ("parameters.tabledEndDate", "2024-06-30"),
+        ("parameters.take", "10"),
+    ] {
+        assert!(
+            query
+                .iter()
+                .any(|(key, value)| key == expected.0 && value == expected.1),
+            "{expected:?} missing from {query:?}"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::features::mcp::errors::{JsonRpcErrorCode, ToolErrorKind};
+
+mod common;
+
+/// Exhaustive, so adding a variant without listing it here fails to compile.
+fn tool_kind_name(kind: ToolErrorKind) -> &'static str {
+    match kind {
+        ToolErrorKind::Upstream
+        | ToolErrorKind::Config
+        | ToolErrorKind::Internal
+        | ToolErrorKind::BadRequest
+        | ToolErrorKind::Timeout => kind.as_str(),
+    }
+}
+
+fn json_rpc_code(code: JsonRpcErrorCode) -> i32 {
+    match code {
+        JsonRpcErrorCode::ParseError
+        | JsonRpcErrorCode::InvalidRequest
+        | JsonRpcErrorCode::MethodNotFound
+        | JsonRpcErrorCode::InvalidParams
+        | JsonRpcErrorCode::ServerError
+        | JsonRpcErrorCode::NotInitialized
+        | JsonRpcErrorCode::ServerBusy => code.code(),
+    }
+}
+
+#[tokio::test]
+async fn catalogue_lists_every_error_kind_and_code() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response =
+        common::call_tool(&harness.service, "utilities.error_catalogue", json!({})).await;
+    let catalogue = &response["result"]["structuredContent"];
+
+    let kinds: Vec<&str> = catalogue["toolErrors"]
+        .as_array()
+        .expect("toolErrors")
+        .iter()
+        .filter_map(|entry| entry["kind"].as_str())
+        .collect();
+    for kind in [
+        ToolErrorKind::Upstream,
+        ToolErrorKind::Config,
+        ToolErrorKind::Internal,
+        ToolErrorKind::BadRequest,
+        ToolErrorKind::Timeout,
+    ] {
+        assert!(
+            kinds.contains(&tool_kind_name(kind)),
+            "{kind:?} missing: {catalogue}"
+        );
+    }
+
+    let codes: Vec<i64> = catalogue["jsonRpcErrors"]
+        .as_array()
+        .expect("jsonRpcErrors")
+        .iter()
+        .filter_map(|entry| entry["code"].as_i64())
+        .collect();
+    for code in [
+        JsonRpcErrorCode::ParseError,
+        JsonRpcErrorCode::InvalidRequest,
+        JsonRpcErrorCode::MethodNotFound,
+        JsonRpcErrorCode::InvalidParams,
+        JsonRpcErrorCode::ServerError,
+        JsonRpcErrorCode::NotInitialized,
+        JsonRpcErrorCode::ServerBusy,
+    ] {
+        assert!(
+            codes.contains(&i64::from(json_rpc_code(code))),
+            "{code:?} missing: {catalogue}"
+        );
+    }
+
+    for entry in catalogue["toolErrors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .chain(catalogue["jsonRpcErrors"].as_array().unwrap())
+    {
+        assert!(entry["retryable"].is_boolean(), "entry: {entry}");
+        assert!(
+            entry["description"]
+                .as_str()
+                .is_some_and(|text| !text.is_empty()),
+            "entry: {entry}"
+        );
+        assert!(
+            entry["action"]
+                .as_str()
+                .is_some_and(|text| !text.is_empty()),
+            "entry: {entry}"
+        );
+    }
+    assert_eq!(
+        harness
+            .service
+            .validate_tool_output("utilities.error_catalogue", catalogue),
+        Ok(())
+    );
+}
+
+#[tokio::test]
+async fn initialize_reports_the_catalogue_version() {
+    let harness = common::build_service(None, |_| {});
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(1)),
+                "initialize",
+                Some(json!({
+                    "protocolVersion": common::PROTOCOL_VERSION,
+                    "clientInfo": {"name": "test-client", "version": "1.0"},
+                    "capabilities": {}
+                })),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("initialize")
+        .expect("response");
+    let version = response.result["serverInfo"]["errorCatalogueVersion"].clone();
+    assert!(version.as_str().is_some_and(|version| version.len() == 12));
+
+    harness
+        .service
+        .handle_jsonrpc(
+            common::request(None, "notifications/initialized", None),
+            common::context(),
+        )
+        .await
+        .expect("initialized");
+    let catalogue: Value =
+        common::call_tool(&harness.service, "utilities.error_catalogue", json!({})).await;
+    assert_eq!(catalogue["result"]["structuredContent"]["version"], version);
+}
@@ -0,0 +1,233 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::ExportOptions;
+
+fn division(id: u32, date: &str) -> Value {
+    json!({
+        "_about": format!("http://data.parliament.uk/resources/{id}"),
+        "title": format!("Division {id}"),
+        "date": {"_value": date, "_datatype": "dateTime"},
+    })
+}
+
+fn lda_page(items: Vec<Value>) -> Value {
+    json!({"result": {"items": items}})
+}
+
+async fn mount_division_pages(server: &MockServer, pages: &[Vec<Value>]) {
+    for (index, items) in pages.iter().enumerate() {
+        Mock::given(method("GET"))
+            .and(path("/commonsdivisions.json"))
+            .and(query_param("_page", index.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(lda_page(items.clone())))
+            .mount(server)
+            .await;
+    }
+}
+
+fn export_options(max_rows: usize) -> ExportOptions {
+    ExportOptions {
+        max_rows,
+        page_size: Some(2),
+        ..ExportOptions::default()
+    }
+}
+
+async fn drain(
+    mut receiver: tokio::sync::mpsc::Receiver<
+        Result<Value, mp_writer_mcp_server::core::error::AppError>,
+    >,
+) -> Vec<Value> {
+    let mut rows = Vec::new();
+    while let Some(item) = receiver.recv().await {
+        rows.push(item.expect("export row"));
+    }
+    rows
+}
+
+#[tokio::test]
+async fn export_streams_every_page_until_a_short_page() {
+    let server = MockServer::start().await;
+    mount_division_pages(
+        &server,
+        &[
+            vec![division(1, "2024-01-01"), division(2, "2024-01-02")],
+            vec![division(3, "2024-01-03"), division(4, "2024-01-04")],
+            vec![division(5, "2024-01-05")],
+        ],
+    )
+    .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let receiver = harness
+        .client
+        .export_dataset("commonsdivisions", export_options(100))
+        .expect("export should start");
+    let rows = drain(receiver).await;
+
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[4]["title"], "Division 5");
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 3);
+}
+
+#[tokio::test]
+async fn export_fetches_pages_lazily() {
+    let server = MockServer::start().await;
+    let mut pages: Vec<Vec<Value>> = (0..10)
+        .map(|page| {
+            vec![
+                division(page * 2, "2024-02-01"),
+                division(page * 2 + 1, "2024-02-01"),
+            ]
+        })
+        .collect();
+    pages.push(Vec::new());
+    mount_division_pages(&server, &pages).await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let receiver = harness
+        .client
+        .export_dataset("commonsdivisions", export_options(100))
+        .expect("export should start");
+
+    // Without a consumer the producer stalls once the channel holds a page.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(
+        requests.len() <= 2,
+        "expected lazy paging, saw {} requests",
+        requests.len()
+    );
+
+    let rows = drain(receiver).await;
+    assert_eq!(rows.len(), 20);
+}
+
+#[tokio::test]
+async fn export_stops_at_row_cap() {
+    let server = MockServer::start().await;
+    mount_division_pages(
+        &server,
+        &[
+            vec![division(1, "2024-01-01"), division(2, "2024-01-02")],
+            vec![division(3, "2024-01-03"), division(4, "2024-01-04")],
+            vec![division(5, "2024-01-05"), division(6, "2024-01-06")],
+        ],
+    )
+    .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let receiver = harness
+        .client
+        .export_dataset("commonsdivisions", export_options(3))
+        .expect("export should start");
+    let rows = drain(receiver).await;
+
+    assert_eq!(rows.len(), 3);
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 2);
+}
+
+#[tokio::test]
+async fn export_route_streams_ndjson_with_filters() {
+    let server = MockServer::start().await;
+    mount_division_pages(
+        &server,
+        &[vec![
+            division(1, "2023-12-31"),
+            division(2, "2024-01-02"),
+            division(3, "2024-01-03"),
+            division(4, "2024-01-04"),
+        ]],
+    )
+    .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.enable_rest_api = true;
+    });
+
+    let response = harness
+        .router()
+        .oneshot(
+            Request::get("/api/export/commonsdivisions?since=2024-01-01&limit=2&query=budget")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let rows: Vec<Value> = String::from_utf8(body.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("ndjson line"))
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["title"], "Division 2");
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(
+        requests[0]
+            .url
+            .query()
+            .unwrap_or_default()
+            .contains("_search=budget")
+    );
+}
+
+#[tokio::test]
+async fn export_route_requires_flag_and_api_key() {
+    let disabled = common::build_service(None, |_| {});
+    let response = disabled
+        .router()
+        .oneshot(
+            Request::get("/api/export/commonsdivisions")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let enabled = common::build_service(None, |config| config.enable_rest_api = true);
+    let response = enabled
+        .router()
+        .oneshot(
+            Request::get("/api/export/commonsdivisions")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = enabled
+        .router()
+        .oneshot(
+            Request::get("/api/export/notadataset")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
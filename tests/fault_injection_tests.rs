@@ -0,0 +1,216 @@
+#![cfg(feature = "fault-injection")]
+//! Resilience acceptance tests driven by injected upstream faults. Run with
+//! `cargo test --features fault-injection`.
+
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, to_bytes};
+use axum::http::Request;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::fault_injection::{Fault, FaultConfig};
+use mp_writer_mcp_server::features::parliament::FetchBillsArgs;
+use mp_writer_mcp_server::features::research::{ResearchRequestDto, ResearchStatus};
+
+mod common;
+
+const BILLS_HOST: &str = "bills-api.parliament.uk";
+
+/// Every upstream the research collections read: bills and legislation with one result
+/// each, the linked-data datasets with none.
+async fn upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"billId": 1, "shortTitle": "Climate Action Bill"}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/[a-z]+\.json$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "result": {"items": []}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/data\.feed$"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom"><entry><title>Climate Action Act 2024</title><id>http://www.legislation.gov.uk/ukpga/2024/1</id></entry></feed>"#,
+        ))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn research(topic: &str) -> ResearchRequestDto {
+    ResearchRequestDto {
+        topic: topic.to_string(),
+        bill_keywords: vec![],
+        debate_keywords: vec![],
+        mp_id: None,
+        include_state_of_parties: false,
+        limit: Some(3),
+        max_age_secs: None,
+        cache_only: false,
+        locale: None,
+    }
+}
+
+fn bills_args() -> FetchBillsArgs {
+    FetchBillsArgs {
+        search_term: Some("climate".to_string()),
+        house: None,
+        session: None,
+        parliament_number: None,
+        enable_cache: Some(false),
+        cache_only: None,
+        apply_relevance: None,
+        relevance_threshold: None,
+        fields: None,
+    }
+}
+
+#[test]
+fn rules_parse_and_match_hosts_by_label() {
+    let faults =
+        FaultConfig::parse("members:error_rate=0.5; bills:latency_ms=2000,truncate_rate=1")
+            .unwrap();
+    assert_eq!(faults.rules().len(), 2);
+    assert_eq!(faults.rules()[0].error_rate, 0.5);
+
+    let fault = faults.sample(BILLS_HOST);
+    assert_eq!(fault.delay, Some(Duration::from_millis(2000)));
+    assert!(fault.truncate && !fault.fail);
+    assert_eq!(faults.sample("www.legislation.gov.uk"), Fault::default());
+    assert_eq!(faults.injected(), 1);
+
+    let everything = FaultConfig::parse("*:error_rate=1").unwrap();
+    assert!(everything.sample("api.postcodes.io").fail);
+    let legislation = FaultConfig::parse("legislation:error_rate=1").unwrap();
+    assert!(legislation.sample("www.legislation.gov.uk").fail);
+    assert!(!legislation.sample(BILLS_HOST).fail);
+
+    for invalid in [
+        "bills",
+        ":error_rate=1",
+        "bills:error_rate=2",
+        "bills:latency_ms=-1",
+        "bills:jitter=5",
+    ] {
+        assert!(FaultConfig::parse(invalid).is_err(), "{invalid}");
+    }
+    assert!(FaultConfig::parse("").unwrap().rules().is_empty());
+}
+
+#[tokio::test]
+async fn a_failing_host_degrades_research_and_trips_the_circuit_breaker() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.upstream_down_after_failures = 1;
+        config.upstream_down_secs = 300;
+        config.faults = Some("bills:error_rate=1".to_string());
+    });
+
+    let first = harness
+        .research
+        .run_research(research("Climate action"))
+        .await
+        .unwrap();
+
+    assert_eq!(first.status, ResearchStatus::Partial, "{first:?}");
+    assert!(first.bills.is_empty());
+    assert!(!first.legislation.is_empty(), "{first:?}");
+    assert!(
+        first
+            .advisories
+            .iter()
+            .any(|advisory| advisory.to_lowercase().contains("bills")),
+        "{:?}",
+        first.advisories
+    );
+    // The injected failures never reached the upstream.
+    let requests = server.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .all(|request| request.url.path() != "/api/v1/Bills")
+    );
+    assert!(harness.client.host_down(BILLS_HOST));
+    let injected = harness.client.faults().injected();
+    assert!(injected > 0);
+
+    // With the breaker open, the next run skips bills without another attempt.
+    let second = harness
+        .research
+        .run_research(research("Flood defences"))
+        .await
+        .unwrap();
+    assert_eq!(second.status, ResearchStatus::Partial, "{second:?}");
+    assert!(
+        second
+            .advisories
+            .iter()
+            .any(|advisory| advisory.contains("marked unavailable")),
+        "{:?}",
+        second.advisories
+    );
+    assert_eq!(harness.client.faults().injected(), injected);
+}
+
+#[tokio::test]
+async fn latency_and_truncation_apply_per_host() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+    });
+
+    harness
+        .client
+        .set_faults(FaultConfig::parse("bills:latency_ms=200").unwrap());
+    let started = Instant::now();
+    harness.client.fetch_bills(bills_args()).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    // A cut-off body cannot be parsed, so the call fails as an upstream would.
+    harness
+        .client
+        .set_faults(FaultConfig::parse("bills:truncate_rate=1").unwrap());
+    harness.client.fetch_bills(bills_args()).await.unwrap_err();
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn health_details_show_the_active_faults() {
+    let harness = common::build_service(None, |config| {
+        config.faults = Some("members:error_rate=0.5".to_string());
+    });
+
+    let response = harness
+        .router()
+        .oneshot(
+            Request::get("/api/health/details")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let details: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(
+        details["faults"],
+        json!({
+            "rules": [{"target": "members", "errorRate": 0.5, "latencyMs": 0, "truncateRate": 0.0}],
+            "injected": 0
+        })
+    );
+}
@@ -0,0 +1,204 @@
+//! Golden files for the research summary and the text content renderings of a brief. Run
+//! with `UPDATE_GOLDENS=1` to rewrite them after an intended change, then review the diff.
+
+use std::fs;
+use std::path::Path;
+
+use mp_writer_mcp_server::core::locale::Locale;
+use mp_writer_mcp_server::core::preferences::TextContentMode;
+use mp_writer_mcp_server::features::parliament::DivisionOutcome;
+use mp_writer_mcp_server::features::research::{
+    BillSummaryDto, DebateSummaryDto, LegislationSummaryDto, PartyBreakdownDto,
+    ResearchResponseDto, ResearchStatus, StateOfPartiesDto, VoteSummaryDto, compose_summary,
+};
+
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/golden");
+
+struct Case {
+    name: &'static str,
+    topic: &'static str,
+    locale: Locale,
+    response: ResearchResponseDto,
+}
+
+fn bill(title: &str, stage: &str) -> BillSummaryDto {
+    BillSummaryDto {
+        title: title.to_string(),
+        stage: Some(stage.to_string()),
+        link: Some("https://bills.parliament.uk/bills/3737".to_string()),
+        ..Default::default()
+    }
+}
+
+fn vote(title: &str, ayes: i64, noes: i64) -> VoteSummaryDto {
+    VoteSummaryDto {
+        division_number: Some("1900".to_string()),
+        title: title.to_string(),
+        date: Some("2024-12-18".to_string()),
+        ayes: Some(ayes),
+        noes: Some(noes),
+        result: Some(if ayes > noes { "Aye" } else { "No" }.to_string()),
+        margin: Some((ayes - noes).abs()),
+        outcome: DivisionOutcome::GovernmentWin,
+        link: Some("https://votes.parliament.uk/votes/commons/division/1900".to_string()),
+        ..Default::default()
+    }
+}
+
+fn state_of_parties() -> StateOfPartiesDto {
+    StateOfPartiesDto {
+        total_seats: Some(650),
+        last_updated: Some("2024-12-01".to_string()),
+        parties: vec![
+            PartyBreakdownDto {
+                name: "Labour".to_string(),
+                seats: Some(404),
+            },
+            PartyBreakdownDto {
+                name: "Conservative".to_string(),
+                seats: Some(121),
+            },
+        ],
+    }
+}
+
+fn cases() -> Vec<Case> {
+    let advisories = [
+        "Bills lookup for 'renters' failed: upstream returned status 503",
+        "No debates matched 'section 21'; showing broader results",
+        "Searched legislation for 'renters reform' with a relaxed relevance threshold",
+        "Votes lookup stopped at the byte budget",
+        "State of parties is a day old",
+    ];
+    vec![
+        Case {
+            name: "full",
+            topic: "Renters reform",
+            locale: Locale::En,
+            response: ResearchResponseDto {
+                bills: vec![bill("Renters (Reform) Bill", "Report stage")],
+                debates: vec![DebateSummaryDto {
+                    title: "Renters (Reform) Bill".to_string(),
+                    house: Some("Commons".to_string()),
+                    date: Some("2024-04-24".to_string()),
+                    link: Some("https://hansard.parliament.uk/Commons/2024-04-24".to_string()),
+                    highlight: Some("Second reading of the amended bill.".to_string()),
+                }],
+                legislation: vec![LegislationSummaryDto {
+                    title: "Housing Act 1988".to_string(),
+                    year: Some("1988".to_string()),
+                    legislation_type: Some("ukpga".to_string()),
+                    uri: Some("https://www.legislation.gov.uk/ukpga/1988/50".to_string()),
+                }],
+                votes: vec![vote("Renters (Reform) Bill: Third Reading", 315, 201)],
+                state_of_parties: Some(state_of_parties()),
+                ..Default::default()
+            },
+        },
+        Case {
+            name: "partial",
+            topic: "Renters reform",
+            locale: Locale::En,
+            response: ResearchResponseDto {
+                status: ResearchStatus::Partial,
+                bills: vec![bill("Renters (Reform) Bill", "Committee stage")],
+                advisories: advisories[..1]
+                    .iter()
+                    .map(|note| note.to_string())
+                    .collect(),
+                ..Default::default()
+            },
+        },
+        Case {
+            name: "empty",
+            topic: "  Nothing at all  ",
+            locale: Locale::En,
+            response: ResearchResponseDto {
+                status: ResearchStatus::Empty,
+                ..Default::default()
+            },
+        },
+        Case {
+            name: "advisory_heavy",
+            topic: "Renters reform",
+            locale: Locale::En,
+            response: ResearchResponseDto {
+                status: ResearchStatus::Partial,
+                votes: vec![vote("Renters (Reform) Bill: Report Stage", 280, 290)],
+                advisories: advisories.iter().map(|note| note.to_string()).collect(),
+                truncated_by_budget: true,
+                ..Default::default()
+            },
+        },
+        Case {
+            name: "unicode",
+            topic: "Addysg — ysgolion’r Gymraeg",
+            locale: Locale::Cy,
+            response: ResearchResponseDto {
+                bills: vec![bill("Bil Addysg (Cymru) – “Ysgolion” ✓", "Cyfnod pwyllgor")],
+                debates: vec![DebateSummaryDto {
+                    title: "Ysgolion: Cymraeg i Bawb 🏫".to_string(),
+                    date: Some("2024-03-11".to_string()),
+                    ..Default::default()
+                }],
+                state_of_parties: Some(state_of_parties()),
+                advisories: vec!["Dim canlyniadau i ‘ysgol’ — 学校".to_string()],
+                ..Default::default()
+            },
+        },
+    ]
+}
+
+/// Compare `actual` with the golden file `name`, or rewrite it under `UPDATE_GOLDENS=1`.
+fn check_golden(name: &str, actual: &str) {
+    let path = Path::new(GOLDEN_DIR).join(name);
+    if std::env::var_os("UPDATE_GOLDENS").is_some_and(|value| value == "1") {
+        fs::create_dir_all(GOLDEN_DIR).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "cannot read {}: {err}; run with UPDATE_GOLDENS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "{name} differs from its golden file; if the change is intended, rerun with UPDATE_GOLDENS=1 and review the diff\n--- expected\n{expected}\n--- actual\n{actual}"
+    );
+}
+
+#[test]
+fn summaries_match_their_golden_files() {
+    for case in cases() {
+        let summary = compose_summary(
+            case.topic,
+            &case.response,
+            &case.response.advisories,
+            case.locale,
+        );
+        check_golden(&format!("{}.summary.txt", case.name), &summary);
+    }
+}
+
+#[test]
+fn text_content_renderings_match_their_golden_files() {
+    for mut case in cases() {
+        case.response.summary = compose_summary(
+            case.topic,
+            &case.response,
+            &case.response.advisories,
+            case.locale,
+        );
+        let payload = serde_json::to_value(&case.response).unwrap();
+        for (mode, extension) in [
+            (TextContentMode::Pretty, "pretty.json"),
+            (TextContentMode::Compact, "compact.json"),
+            (TextContentMode::Markdown, "markdown.md"),
+        ] {
+            let rendered = mode.render(&payload).unwrap();
+            check_golden(&format!("{}.{extension}", case.name), &rendered);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.search_hansard";
+
+const SPOKEN: &str = "/search/contributions/Spoken.json";
+
+fn contribution(index: usize) -> Value {
+    json!({
+        "MemberName": "Jane Example",
+        "AttributedTo": "Jane Example (Anytown) (Lab)",
+        "MemberId": 4514,
+        "ContributionExtId": format!("C{index:03}"),
+        "ContributionText": format!("Point {index} about <i>renters</i>   reform."),
+        "DebateSection": "Renters (Reform) Bill",
+        "DebateSectionExtId": "D4E5F6",
+        "SittingDate": "2024-04-24T00:00:00",
+        "Section": "Commons Chamber",
+        "House": "Commons"
+    })
+}
+
+fn page(range: std::ops::Range<usize>, total: usize) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "Results": range.map(contribution).collect::<Vec<_>>(),
+        "TotalResultCount": total
+    }))
+}
+
+fn html_error_page() -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "text/html")
+        .set_body_string("\n<!DOCTYPE html><html><body>Sorry, something went wrong</body></html>")
+}
+
+async fn mount_page(server: &MockServer, skip: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(SPOKEN))
+        .and(query_param("queryParameters.skip", skip))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+async fn results(harness: &common::TestHarness, arguments: Value) -> Vec<Value> {
+    let result = common::call_tool_ok(&harness.service, TOOL, arguments).await;
+    result["results"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{result}"))
+        .clone()
+}
+
+/// `(skip, take)` of each request to the search endpoint, in order.
+async fn pages_requested(server: &MockServer) -> Vec<(String, String)> {
+    let requests = server.received_requests().await.unwrap_or_default();
+    requests
+        .iter()
+        .map(|request| {
+            let param = |name: &str| {
+                request
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.into_owned())
+                    .unwrap_or_default()
+            };
+            (param("queryParameters.skip"), param("queryParameters.take"))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn contributions_carry_their_debate_snippet_and_link() {
+    let server = MockServer::start().await;
+    mount_page(&server, "0", page(0..2, 2)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({
+            "query": "renters reform",
+            "house": "commons",
+            "fromDate": "2024-01-01",
+            "toDate": "2024-06-30",
+            "memberId": "https://members.parliament.uk/member/4514"
+        }),
+    )
+    .await;
+
+    assert_eq!(result["totalResults"], 2);
+    assert_eq!(
+        result["results"][0],
+        json!({
+            "contributionId": "C000",
+            "title": "Renters (Reform) Bill",
+            "sittingDate": "2024-04-24",
+            "house": "Commons",
+            "section": "Commons Chamber",
+            "memberName": "Jane Example (Anytown) (Lab)",
+            "snippet": "Point 0 about renters reform.",
+            "url": "https://hansard.parliament.uk/Commons/2024-04-24/debates/D4E5F6/RentersReformBill#contribution-C000"
+        })
+    );
+
+    let requests = server.received_requests().await.unwrap();
+    let query: Vec<(String, String)> = requests[0]
+        .url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    for expected in [
+        ("queryParameters.searchTerm", "renters reform"),
+        ("queryParameters.house", "Commons"),
+        ("queryParameters.startDate", "2024-01-01"),
+        ("queryParameters.endDate", "2024-06-30"),
+        ("queryParameters.memberId", "4514"),
+        ("queryParameters.orderBy", "SittingDateDesc"),
+    ] {
+        assert!(
+            query
+                .iter()
+                .any(|(key, value)| key == expected.0 && value == expected.1),
+            "{expected:?} missing from {query:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn pages_are_read_until_the_limit() {
+    let server = MockServer::start().await;
+    mount_page(&server, "0", page(0..20, 45)).await;
+    mount_page(&server, "20", page(20..30, 45)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let results = results(&harness, json!({"query": "renters", "limit": 30})).await;
+
+    assert_eq!(results.len(), 30);
+    assert_eq!(results[29]["contributionId"], "C029");
+    assert_eq!(
+        pages_requested(&server).await,
+        [
+            ("0".to_string(), "20".to_string()),
+            ("20".to_string(), "10".to_string())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn paging_stops_at_the_last_result() {
+    let server = MockServer::start().await;
+    mount_page(&server, "0", page(0..20, 20)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let results = results(&harness, json!({"query": "renters", "limit": 50})).await;
+
+    assert_eq!(results.len(), 20);
+    assert_eq!(pages_requested(&server).await.len(), 1);
+}
+
+#[tokio::test]
+async fn searches_are_cached() {
+    let server = MockServer::start().await;
+    mount_page(&server, "0", page(0..2, 2)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let first = results(&harness, json!({"query": "Renters"})).await;
+    let second = results(&harness, json!({"query": "renters", "cacheOnly": true})).await;
+
+    assert_eq!(first, second);
+    assert_eq!(pages_requested(&server).await.len(), 1);
+}
+
+#[tokio::test]
+async fn an_html_error_page_is_an_upstream_failure() {
+    let server = MockServer::start().await;
+    mount_page(&server, "0", html_error_page()).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response = common::call_tool(&harness.service, TOOL, json!({"query": "renters"})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn a_failing_later_page_returns_the_earlier_ones_uncached() {
+    let server = MockServer::start().await;
+    mount_page(&server, "0", page(0..20, 45)).await;
+    mount_page(&server, "20", html_error_page()).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let results = results(&harness, json!({"query": "renters", "limit": 40})).await;
+    assert_eq!(results.len(), 20);
+
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"query": "renters", "limit": 40, "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(cached["cacheMiss"], true, "{cached}");
+}
+
+#[tokio::test]
+async fn malformed_arguments_are_rejected_before_searching() {
+    let server = MockServer::start().await;
+    let harness = common::upstream_harness(&server).await;
+
+    for arguments in [
+        json!({"query": "renters", "fromDate": "last year"}),
+        json!({"query": "renters", "house": "senedd"}),
+        json!({"query": "   "}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+    }
+    assert!(pages_requested(&server).await.is_empty());
+}
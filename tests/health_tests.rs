@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use serde_json::Value;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn health(harness: &common::TestHarness, uri: &str) -> (StatusCode, Value) {
+    let response = harness
+        .router()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).expect("JSON body"))
+}
+
+async fn members_api(response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/api/Members"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn shallow_check_reports_the_cache_without_sled_or_upstream_calls() {
+    let server = members_api(ResponseTemplate::new(200)).await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let (status, body) = health(&harness, "/api/health").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok", "body: {body}");
+    assert!(body["checks"].get("sled").is_none());
+    assert!(body["checks"].get("members_api").is_none());
+    assert_eq!(body["cacheEntries"], 0);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn deep_check_probes_the_members_api() {
+    let server = members_api(ResponseTemplate::new(200)).await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let (status, body) = health(&harness, "/api/health?deep=true").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok", "body: {body}");
+    assert_eq!(body["checks"]["members_api"], "ok");
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn unreachable_members_api_degrades_without_failing_the_probe() {
+    for (response, expected) in [
+        (
+            ResponseTemplate::new(200).set_delay(Duration::from_secs(3)),
+            "timeout",
+        ),
+        (ResponseTemplate::new(503), "error"),
+    ] {
+        let server = members_api(response).await;
+        let uri = server.uri();
+        let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+        let (status, body) = health(&harness, "/api/health?deep=true").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "degraded", "body: {body}");
+        assert_eq!(body["checks"]["members_api"], expected);
+    }
+}
@@ -0,0 +1,129 @@
+use serde_json::json;
+
+use mp_writer_mcp_server::features::mcp::ids::{IdKind, resolve_id, resolve_id_arguments};
+
+mod common;
+
+#[test]
+fn numeric_ids_and_numeric_strings_resolve() {
+    assert_eq!(resolve_id(IdKind::Member, "4514"), Some(4514));
+    assert_eq!(resolve_id(IdKind::Bill, " 3737 "), Some(3737));
+    assert_eq!(resolve_id(IdKind::Member, "0"), None);
+    assert_eq!(resolve_id(IdKind::Member, ""), None);
+    assert_eq!(resolve_id(IdKind::Member, "99999999999"), None);
+
+    let mut arguments = json!({"mpId": 4514, "limit": 5});
+    resolve_id_arguments("parliament.fetch_mp_activity", &mut arguments).unwrap();
+    assert_eq!(arguments, json!({"mpId": 4514, "limit": 5}));
+
+    let mut arguments = json!({"mpIds": ["4514", 172]});
+    resolve_id_arguments("parliament.fetch_members_bulk", &mut arguments).unwrap();
+    assert_eq!(arguments, json!({"mpIds": [4514, 172]}));
+}
+
+#[test]
+fn member_and_bill_urls_resolve() {
+    for url in [
+        "https://members.parliament.uk/member/4514",
+        "https://members.parliament.uk/member/4514/contact",
+        "http://www.members.parliament.uk/Member/4514?tab=voting",
+        "members.parliament.uk/member/4514",
+    ] {
+        assert_eq!(resolve_id(IdKind::Member, url), Some(4514), "{url}");
+    }
+    for url in [
+        "https://bills.parliament.uk/bills/3737",
+        "https://bills.parliament.uk/bills/3737/stages",
+        "bills.parliament.uk/bills/3737",
+    ] {
+        assert_eq!(resolve_id(IdKind::Bill, url), Some(3737), "{url}");
+    }
+
+    let mut arguments = json!({"billId": "https://bills.parliament.uk/bills/3737"});
+    resolve_id_arguments("parliament.fetch_bill_divisions", &mut arguments).unwrap();
+    assert_eq!(arguments, json!({"billId": 3737}));
+}
+
+#[test]
+fn urls_for_other_records_or_hosts_are_rejected() {
+    for (kind, url) in [
+        (IdKind::Member, "https://bills.parliament.uk/bills/3737"),
+        (IdKind::Bill, "https://members.parliament.uk/member/4514"),
+        (IdKind::Member, "https://example.org/member/4514"),
+        (
+            IdKind::Member,
+            "https://members.parliament.uk/member/keir-starmer",
+        ),
+        (
+            IdKind::Member,
+            "https://members.parliament.uk/members/commons",
+        ),
+        (IdKind::Member, "ftp://members.parliament.uk/member/4514"),
+        (IdKind::Member, "not a url at all"),
+    ] {
+        assert_eq!(resolve_id(kind, url), None, "{url}");
+    }
+
+    let mut arguments = json!({"mpId": "https://example.org/member/4514"});
+    let message = resolve_id_arguments("parliament.fetch_mp_activity", &mut arguments).unwrap_err();
+    assert_eq!(
+        message,
+        "mpId must be a member id such as 4514, \"4514\" or https://members.parliament.uk/member/4514; got \"https://example.org/member/4514\""
+    );
+
+    // Tools without id arguments are untouched.
+    let mut arguments = json!({"mpId": "anything"});
+    resolve_id_arguments("parliament.fetch_bills", &mut arguments).unwrap();
+    assert_eq!(arguments, json!({"mpId": "anything"}));
+}
+
+#[tokio::test]
+async fn tool_calls_accept_string_ids_and_name_the_forms_on_error() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_mp_activity",
+        json!({"mpId": "https://members.parliament.uk/member/4514", "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(
+        response["result"]["structuredContent"]["cacheMiss"], true,
+        "{response}"
+    );
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": ["4514", "garbage"], "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602, "{response}");
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("mpIds[1] must be a member id such as 4514"),
+        "{response}"
+    );
+}
+
+#[test]
+fn id_arguments_document_the_accepted_forms() {
+    let (definitions, _) = mp_writer_mcp_server::features::mcp::schemas::build_tool_schemas();
+    let schema = &definitions
+        .iter()
+        .find(|definition| definition.name == "parliament.fetch_mp_activity")
+        .unwrap()
+        .input_schema["properties"]["mpId"];
+    let branches = schema["oneOf"].as_array().unwrap();
+    assert_eq!(branches.len(), 3);
+    assert_eq!(branches[0], json!({"type": "integer", "minimum": 1}));
+    assert!(
+        schema["description"]
+            .as_str()
+            .unwrap()
+            .contains("https://members.parliament.uk/member/4514")
+    );
+}
@@ -0,0 +1,183 @@
+use std::net::SocketAddr;
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode, header};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::{AllowedCidrs, ListenAddress};
+use mp_writer_mcp_server::server::Listener;
+
+mod common;
+
+fn router(cidrs: &str, trust_proxy_headers: bool) -> (common::TestHarness, Router) {
+    let harness = common::build_service(None, |config| {
+        config.allowed_cidrs = AllowedCidrs::parse(cidrs).unwrap();
+        config.trust_proxy_headers = trust_proxy_headers;
+    });
+    let router = harness.router();
+    (harness, router)
+}
+
+/// Status of a `ping` to `/api/mcp` from `peer`, with extra headers.
+async fn ping_from(router: &Router, peer: &str, headers: &[(&str, &str)]) -> StatusCode {
+    let mut request = Request::post("/api/mcp")
+        .header("x-api-key", "test")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header(header::CONTENT_TYPE, "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let mut request = request
+        .body(Body::from(
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}).to_string(),
+        ))
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn direct_connections_are_checked_against_the_peer_address() {
+    for (cidrs, expected) in [
+        ("127.0.0.0/8", StatusCode::OK),
+        ("10.0.0.0/8, 192.168.1.20", StatusCode::FORBIDDEN),
+    ] {
+        let (_harness, router) = router(cidrs, false);
+        let listener = Listener::bind(&ListenAddress::Tcp("127.0.0.1:0".parse().unwrap()))
+            .await
+            .unwrap();
+        let address = listener.to_string();
+        let stop = CancellationToken::new();
+        let server = tokio::spawn(listener.serve(router, stop.clone()));
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(format!("http://{address}/api/mcp"))
+            .header("x-api-key", "test")
+            .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected, "MCP_ALLOWED_CIDRS={cidrs}");
+        if expected == StatusCode::FORBIDDEN {
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["error"]["code"], "forbidden");
+        }
+        // Health checks are never restricted.
+        let health = client
+            .get(format!("http://{address}/api/health"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(health.status(), StatusCode::OK);
+
+        stop.cancel();
+        server.await.unwrap().unwrap();
+    }
+}
+
+#[tokio::test]
+async fn forwarded_headers_are_ignored_unless_trusted() {
+    let (_harness, router) = router("10.0.0.0/8", false);
+
+    for (name, value) in [
+        ("X-Forwarded-For", "10.0.0.5"),
+        ("Forwarded", "for=10.0.0.5"),
+    ] {
+        assert_eq!(
+            ping_from(&router, "203.0.113.9:5000", &[(name, value)]).await,
+            StatusCode::FORBIDDEN,
+            "{name}: {value}"
+        );
+    }
+    assert_eq!(
+        ping_from(
+            &router,
+            "10.1.2.3:5000",
+            &[("X-Forwarded-For", "203.0.113.9")]
+        )
+        .await,
+        StatusCode::OK
+    );
+    // No peer address (as over a Unix socket) and nothing trusted to go on.
+    let request = Request::post("/api/mcp")
+        .header("x-api-key", "test")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        router.clone().oneshot(request).await.unwrap().status(),
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn trusted_proxies_supply_the_client_address() {
+    let (_harness, router) = router("10.0.0.0/8, 2001:db8::/32", true);
+    let proxy = "192.168.0.2:443";
+
+    for (name, value, expected) in [
+        ("X-Forwarded-For", "10.0.0.5", StatusCode::OK),
+        ("X-Forwarded-For", "203.0.113.9, 10.0.0.5", StatusCode::OK),
+        // Only the entry the proxy added counts; the client wrote the first one.
+        (
+            "X-Forwarded-For",
+            "10.0.0.5, 203.0.113.9",
+            StatusCode::FORBIDDEN,
+        ),
+        ("Forwarded", "for=10.0.0.5;proto=https", StatusCode::OK),
+        ("Forwarded", "for=\"[2001:db8::1]:4711\"", StatusCode::OK),
+        (
+            "Forwarded",
+            "for=10.0.0.5, for=unknown",
+            StatusCode::FORBIDDEN,
+        ),
+    ] {
+        assert_eq!(
+            ping_from(&router, proxy, &[(name, value)]).await,
+            expected,
+            "{name}: {value}"
+        );
+    }
+    // `Forwarded` wins over `X-Forwarded-For`.
+    assert_eq!(
+        ping_from(
+            &router,
+            proxy,
+            &[
+                ("Forwarded", "for=203.0.113.9"),
+                ("X-Forwarded-For", "10.0.0.5")
+            ]
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    // Without either header the peer (the proxy itself) is checked.
+    assert_eq!(ping_from(&router, proxy, &[]).await, StatusCode::FORBIDDEN);
+    assert_eq!(
+        ping_from(&router, "[::ffff:10.0.0.7]:443", &[]).await,
+        StatusCode::OK
+    );
+}
+
+#[test]
+fn allowed_cidrs_are_parsed_and_validated() {
+    assert_eq!(AllowedCidrs::parse(" , ").unwrap(), None);
+    let allowed = AllowedCidrs::parse("10.0.0.1/8, 192.168.1.20, fd00::/8")
+        .unwrap()
+        .unwrap();
+    assert!(allowed.contains("10.255.0.1".parse().unwrap()));
+    assert!(allowed.contains("192.168.1.20".parse().unwrap()));
+    assert!(!allowed.contains("192.168.1.21".parse().unwrap()));
+    assert!(allowed.contains("fd12::1".parse().unwrap()));
+
+    let error = AllowedCidrs::parse("10.0.0.0/8, office").unwrap_err();
+    assert!(error.to_string().contains("office"), "{error}");
+    assert!(AllowedCidrs::parse("10.0.0.0/33").is_err());
+}
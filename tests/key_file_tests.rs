@@ -0,0 +1,112 @@
+use std::path::Path;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::json;
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::ApiKeysConfig;
+use mp_writer_mcp_server::server::spawn_key_file_reload;
+
+mod common;
+
+const CI_KEY: &str = "ci-0123456789abcdef";
+const LAPTOP_KEY: &str = "laptop-0123456789abcdef";
+/// Comfortably more than the one second poll interval used here.
+const RELOAD_WAIT: Duration = Duration::from_secs(5);
+
+fn harness(path: &Path) -> common::TestHarness {
+    let path = path.to_string_lossy().into_owned();
+    common::build_service(None, |config| {
+        config.api_keys = ApiKeysConfig::from_file(&path).unwrap();
+        config.api_key = CI_KEY.to_string();
+        config.api_key_file = Some(path);
+        config.api_key_file_poll_secs = 1;
+    })
+}
+
+async fn status(harness: &common::TestHarness, api_key: &str) -> StatusCode {
+    let request = Request::post("/api/mcp")
+        .header("x-api-key", api_key)
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}).to_string(),
+        ))
+        .unwrap();
+    harness.router().oneshot(request).await.unwrap().status()
+}
+
+/// Wait until requests with `api_key` get `expected`, or fail after [`RELOAD_WAIT`].
+async fn wait_for(harness: &common::TestHarness, api_key: &str, expected: StatusCode) {
+    let deadline = tokio::time::Instant::now() + RELOAD_WAIT;
+    while status(harness, api_key).await != expected {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "{api_key} did not get {expected} after the key file changed"
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[test]
+fn key_files_accept_pairs_bare_keys_and_comments() {
+    let parsed = ApiKeysConfig::parse_file(&format!(
+        "# rotated 2024-05-01\n\nci:{CI_KEY}\n  {LAPTOP_KEY}  \n"
+    ))
+    .unwrap();
+    let names: Vec<&str> = parsed.keys.iter().map(|key| key.name.as_str()).collect();
+    assert_eq!(names, ["ci", "key2"]);
+    assert_eq!(parsed.keys[1].key, LAPTOP_KEY);
+
+    let error = ApiKeysConfig::parse_file(&format!("ci:{CI_KEY}\nshort\n"))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("\"key2\""), "{error}");
+    assert!(ApiKeysConfig::parse_file("# nothing yet\n").is_err());
+}
+
+#[tokio::test]
+async fn rewritten_key_files_take_effect_without_a_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("keys");
+    std::fs::write(&path, format!("ci:{CI_KEY}\n")).unwrap();
+    let harness = harness(&path);
+    let reload = spawn_key_file_reload(harness.service.key_ring(), &harness.config)
+        .expect("a key file is configured");
+
+    assert_eq!(status(&harness, CI_KEY).await, StatusCode::OK);
+    assert_eq!(status(&harness, LAPTOP_KEY).await, StatusCode::UNAUTHORIZED);
+
+    std::fs::write(&path, format!("laptop:{LAPTOP_KEY}\n")).unwrap();
+    wait_for(&harness, LAPTOP_KEY, StatusCode::OK).await;
+    assert_eq!(status(&harness, CI_KEY).await, StatusCode::UNAUTHORIZED);
+
+    reload.abort();
+}
+
+#[tokio::test]
+async fn a_malformed_key_file_keeps_the_previous_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("keys");
+    std::fs::write(&path, format!("ci:{CI_KEY}\n")).unwrap();
+    let harness = harness(&path);
+    let reload = spawn_key_file_reload(harness.service.key_ring(), &harness.config)
+        .expect("a key file is configured");
+
+    std::fs::write(&path, "laptop:too-short\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert_eq!(status(&harness, CI_KEY).await, StatusCode::OK);
+
+    std::fs::remove_file(&path).unwrap();
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(status(&harness, CI_KEY).await, StatusCode::OK);
+
+    // Fixing the file is picked up as usual.
+    std::fs::write(&path, format!("ci:{CI_KEY}\nlaptop:{LAPTOP_KEY}\n")).unwrap();
+    wait_for(&harness, LAPTOP_KEY, StatusCode::OK).await;
+    assert_eq!(status(&harness, CI_KEY).await, StatusCode::OK);
+
+    reload.abort();
+}
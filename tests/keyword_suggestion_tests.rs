@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use mp_writer_mcp_server::features::research::{
+    EvidenceSource, KeywordEvidence, ParliamentDataSource, extract_keyword_suggestions,
+};
+
+mod common;
+
+fn titles(source: EvidenceSource, titles: &[&str]) -> Vec<KeywordEvidence> {
+    titles
+        .iter()
+        .enumerate()
+        .map(|(index, title)| KeywordEvidence {
+            source,
+            title: title.to_string(),
+            link: Some(format!("https://example.com/{index}")),
+        })
+        .collect()
+}
+
+fn keywords(topic: &str, evidence: &[KeywordEvidence], limit: usize) -> Vec<String> {
+    extract_keyword_suggestions(topic, evidence, limit)
+        .into_iter()
+        .map(|suggestion| suggestion.keyword)
+        .collect()
+}
+
+#[test]
+fn phrases_shared_by_titles_rank_first() {
+    let mut evidence = titles(
+        EvidenceSource::Bill,
+        &[
+            "Terminally Ill Adults (End of Life) Bill",
+            "Assisted Dying Bill [HL]",
+        ],
+    );
+    evidence.extend(titles(
+        EvidenceSource::Debate,
+        &[
+            "Terminally Ill Adults (End of Life) Bill: Second Reading",
+            "Palliative Care and Terminally Ill Adults",
+        ],
+    ));
+
+    let suggestions = extract_keyword_suggestions("assisted dying", &evidence, 5);
+    assert_eq!(suggestions[0].keyword, "terminally ill adults");
+    assert_eq!(suggestions[0].occurrences, 3);
+    assert_eq!(suggestions[0].score, 9);
+    assert_eq!(suggestions[0].evidence, evidence[0]);
+    assert_eq!(
+        keywords("assisted dying", &evidence, 5),
+        ["terminally ill adults", "end", "life", "palliative care"]
+    );
+}
+
+#[test]
+fn stop_words_numbers_and_the_topic_are_not_suggested() {
+    let evidence = titles(
+        EvidenceSource::Edm,
+        &[
+            "The Renters' Rights Bill 2024",
+            "Renters' Rights: Committee Stage",
+            "Motion on the Rights of Renters",
+        ],
+    );
+
+    let suggested = keywords("renters", &evidence, 10);
+    assert_eq!(suggested, ["renters rights", "rights"]);
+    for dropped in ["the", "bill", "2024", "committee", "renters"] {
+        assert!(!suggested.iter().any(|keyword| keyword == dropped));
+    }
+
+    assert!(keywords("renters rights", &evidence, 10).is_empty());
+}
+
+#[test]
+fn ties_are_alphabetical_and_limited() {
+    let evidence = titles(
+        EvidenceSource::Debate,
+        &["Water Quality", "Sewage Discharges", "River Pollution"],
+    );
+    assert_eq!(
+        keywords("rivers", &evidence, 2),
+        ["river pollution", "sewage discharges"]
+    );
+    assert!(keywords("rivers", &[], 5).is_empty());
+}
+
+#[tokio::test]
+async fn suggestions_cite_each_source_and_are_cached() {
+    let mock = Arc::new(common::MockParliamentDataSource::new());
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let harness = common::build_service(Some(data_source), |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.suggest_keywords",
+        json!({"topic": "Climate"}),
+    )
+    .await;
+    let result = &response["result"]["structuredContent"];
+    assert_eq!(result["cached"], false, "response: {response}");
+    let suggestions = result["suggestions"].as_array().unwrap();
+    let find = |keyword: &str| {
+        suggestions
+            .iter()
+            .find(|suggestion| suggestion["keyword"] == keyword)
+            .unwrap_or_else(|| panic!("no {keyword} in {suggestions:?}"))
+    };
+    assert_eq!(find("climate change")["evidence"]["source"], "bill");
+    assert_eq!(
+        find("climate change")["evidence"]["link"],
+        "https://bills.parliament.uk/bills/123"
+    );
+    assert_eq!(find("net zero")["evidence"]["source"], "edm");
+    assert_eq!(
+        find("net zero")["evidence"]["title"],
+        "Climate emergency and net zero"
+    );
+    let calls = mock.total_calls().await;
+    assert_eq!(calls, 3);
+    assert_eq!(mock.search_terms_for("edms").await, ["Climate"]);
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.suggest_keywords",
+        json!({"topic": " climate ", "limit": 1}),
+    )
+    .await;
+    let result = &response["result"]["structuredContent"];
+    assert_eq!(result["cached"], true, "response: {response}");
+    assert_eq!(result["suggestions"].as_array().unwrap().len(), 1);
+    assert_eq!(mock.total_calls().await, calls);
+}
+
+#[tokio::test]
+async fn failed_sources_are_skipped_and_not_cached() {
+    let mock = Arc::new(common::MockParliamentDataSource::new().failing(&["bills", "edms"]));
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let harness = common::build_service(Some(data_source), |_| {});
+
+    let first = harness
+        .research
+        .suggest_keywords("Climate", 10)
+        .await
+        .unwrap();
+    assert!(!first.cached);
+    assert!(
+        first
+            .suggestions
+            .iter()
+            .all(|suggestion| suggestion.evidence.source == EvidenceSource::Debate)
+    );
+    let second = harness
+        .research
+        .suggest_keywords("Climate", 10)
+        .await
+        .unwrap();
+    assert!(!second.cached);
+
+    let mock = Arc::new(common::MockParliamentDataSource::new().failing(&[
+        "bills",
+        "commonsdebates",
+        "edms",
+    ]));
+    let harness = common::build_service(Some(mock as Arc<dyn ParliamentDataSource>), |_| {});
+    assert!(
+        harness
+            .research
+            .suggest_keywords("Climate", 10)
+            .await
+            .is_err()
+    );
+    assert!(harness.research.suggest_keywords("  ", 10).await.is_err());
+}
@@ -0,0 +1,206 @@
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_legislation_text";
+
+/// Section 1 of the Climate Change Act 2008 as legislation.gov.uk serves it, with the 2050
+/// target substituted by the 2019 net zero order.
+const SECTION_1: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Legislation xmlns="http://www.legislation.gov.uk/namespaces/legislation" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:ukm="http://www.legislation.gov.uk/namespaces/metadata" DocumentURI="http://www.legislation.gov.uk/ukpga/2008/27/section/1" RestrictExtent="E+W+S+N.I.">
+  <ukm:Metadata>
+    <dc:title>Climate Change Act 2008</dc:title>
+  </ukm:Metadata>
+  <Primary>
+    <Body>
+      <Part id="part-1">
+        <P1group RestrictExtent="E+W+S+N.I.">
+          <Title>The target for 2050</Title>
+          <P1 id="section-1">
+            <Pnumber>1</Pnumber>
+            <P1para>
+              <P2>
+                <Pnumber>1</Pnumber>
+                <P2para>
+                  <Text>It is the duty of the Secretary of State to ensure that the net UK carbon account for the year 2050 is at least <Substitution CommentaryRef="key-f1">100%</Substitution> lower than the 1990 baseline.</Text>
+                </P2para>
+              </P2>
+              <P2>
+                <Pnumber>2</Pnumber>
+                <P2para>
+                  <Text>“The 1990 baseline” means the aggregate amount of—</Text>
+                  <P3>
+                    <Pnumber>a</Pnumber>
+                    <P3para>
+                      <Text>net UK emissions of carbon dioxide for that year, and</Text>
+                    </P3para>
+                  </P3>
+                  <P3>
+                    <Pnumber>b</Pnumber>
+                    <P3para>
+                      <Text>net UK emissions of each of the other targeted greenhouse gases for the year that is the base year for that gas.</Text>
+                    </P3para>
+                  </P3>
+                </P2para>
+              </P2>
+            </P1para>
+          </P1>
+        </P1group>
+      </Part>
+    </Body>
+  </Primary>
+  <Commentaries>
+    <Commentary id="key-f1" Type="F">
+      <Para>
+        <Text>Word in s. 1(1) substituted (27.6.2019) by <Citation URI="http://www.legislation.gov.uk/uksi/2019/1056">The Climate Change Act 2008 (2050 Target Amendment) Order 2019 (S.I. 2019/1056)</Citation>, arts. 1, 2</Text>
+      </Para>
+    </Commentary>
+    <Commentary id="key-unused" Type="I">
+      <Para><Text>S. 2 in force at Royal Assent.</Text></Para>
+    </Commentary>
+  </Commentaries>
+</Legislation>
+"#;
+
+async fn upstream(route: &str, body: String) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body.into_bytes(), "application/xml"))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn a_section_has_its_heading_extent_and_amended_wording() {
+    let server = upstream("/ukpga/2008/27/section/1/data.xml", SECTION_1.to_string()).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"type": "ukpga", "year": 2008, "number": 27, "section": "1"}),
+    )
+    .await;
+
+    assert_eq!(
+        result,
+        json!({
+            "title": "Climate Change Act 2008",
+            "section": "1",
+            "heading": "The target for 2050",
+            "text": "(1) It is the duty of the Secretary of State to ensure that the net UK carbon account for the year 2050 is at least 100% lower than the 1990 baseline.\n(2) “The 1990 baseline” means the aggregate amount of—\n(a) net UK emissions of carbon dioxide for that year, and\n(b) net UK emissions of each of the other targeted greenhouse gases for the year that is the base year for that gas.",
+            "truncated": false,
+            "extent": "E+W+S+N.I.",
+            "amendments": [
+                "Word in s. 1(1) substituted (27.6.2019) by The Climate Change Act 2008 (2050 Target Amendment) Order 2019 (S.I. 2019/1056), arts. 1, 2"
+            ],
+            "url": "https://www.legislation.gov.uk/ukpga/2008/27/section/1"
+        })
+    );
+}
+
+#[tokio::test]
+async fn a_uri_names_the_document_and_section() {
+    let server = upstream("/ukpga/2008/27/section/1/data.xml", SECTION_1.to_string()).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"uri": "https://www.legislation.gov.uk/ukpga/2008/27/section/1"}),
+    )
+    .await;
+
+    assert_eq!(result["section"], "1");
+    assert_eq!(result["heading"], "The target for 2050");
+}
+
+#[tokio::test]
+async fn long_text_is_truncated() {
+    let clause = "and the Secretary of State must lay a report before Parliament ".repeat(400);
+    let body = SECTION_1.replace(
+        "net UK emissions of carbon dioxide for that year, and",
+        &clause,
+    );
+    let server = upstream("/ukpga/2008/27/section/1/data.xml", body).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"uri": "ukpga/2008/27/section/1"}),
+    )
+    .await;
+
+    assert_eq!(result["truncated"], true);
+    let text = result["text"].as_str().unwrap();
+    assert_eq!(text.chars().count(), 20_001);
+    assert!(text.ends_with('…'));
+}
+
+#[tokio::test]
+async fn an_oversized_document_is_an_upstream_error() {
+    let padding = format!("<!-- {} -->", "x".repeat(2_100_000));
+    let body = SECTION_1.replace("<Commentaries>", &format!("{padding}<Commentaries>"));
+    let server = upstream("/ukpga/2008/27/data.xml", body).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        TOOL,
+        json!({"type": "ukpga", "year": 2008, "number": 27}),
+    )
+    .await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn sections_are_cached() {
+    let server = upstream("/ukpga/2008/27/section/1/data.xml", SECTION_1.to_string()).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let first = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"uri": "ukpga/2008/27/section/1"}),
+    )
+    .await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"type": "ukpga", "year": 2008, "number": 27, "section": "1", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn incomplete_or_conflicting_references_are_rejected() {
+    let server = MockServer::start().await;
+    let harness = common::upstream_harness(&server).await;
+
+    for arguments in [
+        json!({}),
+        json!({"type": "ukpga", "year": 2008}),
+        json!({"uri": "ukpga/2008/27", "type": "ukpga", "year": 2008, "number": 27}),
+        json!({"uri": "https://www.legislation.gov.uk/ukpga/2008"}),
+        json!({"uri": "ukpga/2008/27/section/1", "section": "2"}),
+        json!({"type": "ukpga", "year": 1700, "number": 1}),
+        json!({"type": "ukpga", "year": 2008, "number": 27, "section": "1/../2"}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments.clone()).await;
+        assert_eq!(response["error"]["code"], -32602, "{arguments}: {response}");
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,117 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use axum::http::StatusCode;
+use tokio_util::sync::CancellationToken;
+
+use mp_writer_mcp_server::config::{DEFAULT_PORT, ListenAddress};
+use mp_writer_mcp_server::server::Listener;
+
+mod common;
+
+fn tcp(address: &str) -> ListenAddress {
+    ListenAddress::Tcp(address.parse::<SocketAddr>().unwrap())
+}
+
+#[test]
+fn listen_addresses_are_parsed_and_validated() {
+    let parse = ListenAddress::parse;
+    assert_eq!(
+        parse(None, None, None).unwrap(),
+        tcp(&format!("0.0.0.0:{DEFAULT_PORT}"))
+    );
+    assert_eq!(
+        parse(Some("127.0.0.1"), Some("8080"), None).unwrap(),
+        tcp("127.0.0.1:8080")
+    );
+    assert_eq!(
+        parse(Some("[::1]"), None, None).unwrap(),
+        tcp(&format!("[::1]:{DEFAULT_PORT}"))
+    );
+    assert_eq!(
+        parse(None, None, Some("/run/mcp.sock")).unwrap(),
+        ListenAddress::Unix(PathBuf::from("/run/mcp.sock"))
+    );
+
+    for (bind_address, port, socket, expected) in [
+        (Some("localhost"), None, None, "invalid MCP_BIND_ADDRESS"),
+        (Some("127.0.0.1:8080"), None, None, "without a port"),
+        (None, Some("http"), None, "invalid port"),
+        (
+            Some("127.0.0.1"),
+            None,
+            Some("/run/mcp.sock"),
+            "cannot be combined",
+        ),
+        (
+            None,
+            Some("4100"),
+            Some("/run/mcp.sock"),
+            "cannot be combined",
+        ),
+    ] {
+        let error = parse(bind_address, port, socket).unwrap_err().to_string();
+        assert!(error.contains(expected), "{error}");
+    }
+}
+
+#[tokio::test]
+async fn tcp_listener_binds_the_configured_address() {
+    let harness = common::build_service(None, |_| {});
+    let listener = Listener::bind(&tcp("127.0.0.1:0")).await.unwrap();
+    let address = listener.to_string();
+    assert!(address.starts_with("127.0.0.1:"), "{address}");
+
+    let stop = CancellationToken::new();
+    let server = tokio::spawn(listener.serve(harness.router(), stop.clone()));
+    let response = reqwest::get(format!("http://{address}/api/health"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    stop.cancel();
+    server.await.unwrap().unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn unix_socket_serves_requests_and_is_removed_on_shutdown() {
+    use std::os::unix::fs::PermissionsExt;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixStream;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mcp.sock");
+    // A socket left behind by a crashed run is replaced.
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+    let harness = common::build_service(None, |_| {});
+    let listener = Listener::bind(&ListenAddress::Unix(path.clone()))
+        .await
+        .unwrap();
+    assert_eq!(listener.to_string(), format!("unix:{}", path.display()));
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o660);
+    assert!(
+        Listener::bind(&ListenAddress::Unix(path.clone()))
+            .await
+            .is_err(),
+        "a live socket must not be replaced"
+    );
+
+    let stop = CancellationToken::new();
+    let server = tokio::spawn(listener.serve(harness.router(), stop.clone()));
+    let mut stream = UnixStream::connect(&path).await.unwrap();
+    stream
+        .write_all(b"GET /api/health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+    stop.cancel();
+    server.await.unwrap().unwrap();
+    assert!(!path.exists(), "the socket file is removed on shutdown");
+}
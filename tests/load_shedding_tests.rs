@@ -0,0 +1,339 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::core::concurrency::ConcurrencyLimit;
+use mp_writer_mcp_server::core::error::AppError;
+use mp_writer_mcp_server::features::mcp::McpService;
+use mp_writer_mcp_server::features::parliament::{
+    FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs,
+};
+use mp_writer_mcp_server::features::research::ParliamentDataSource;
+
+mod common;
+
+/// Answers every lookup with nothing after `delay`, recording how many bill searches
+/// (one per research run) were in progress at once.
+#[derive(Default)]
+struct SlowSource {
+    delay: Duration,
+    running: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl SlowSource {
+    fn new(delay: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            delay,
+            ..Self::default()
+        })
+    }
+}
+
+#[async_trait]
+impl ParliamentDataSource for SlowSource {
+    async fn fetch_bills(&self, _args: FetchBillsArgs) -> Result<Value, AppError> {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(running, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+        Ok(json!({"items": []}))
+    }
+
+    async fn fetch_core_dataset(&self, _args: FetchCoreDatasetArgs) -> Result<Value, AppError> {
+        Ok(json!({"items": []}))
+    }
+
+    async fn fetch_legislation(&self, _args: FetchLegislationArgs) -> Result<Value, AppError> {
+        Ok(json!({"items": []}))
+    }
+}
+
+fn post_mcp(body: Value) -> Request<Body> {
+    Request::post("/api/mcp")
+        .header("x-api-key", "test")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn research_call(id: u64, topic: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": {"name": "research.run", "arguments": {"topic": topic}}
+    })
+}
+
+/// `tools/call` with its own request id, so calls can overlap.
+async fn call(service: &McpService, id: u64, name: &str, arguments: Value) -> Value {
+    let response = service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(id)),
+                "tools/call",
+                Some(json!({"name": name, "arguments": arguments})),
+            ),
+            common::context(),
+        )
+        .await;
+    match response {
+        Ok(success) => serde_json::to_value(success).unwrap(),
+        Err(error) => serde_json::to_value(error).unwrap(),
+    }
+}
+
+async fn json_body(response: axum::response::Response) -> Value {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("JSON body")
+}
+
+/// The value of the sample line starting with `series`, if present.
+fn sample(body: &str, series: &str) -> Option<f64> {
+    body.lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .and_then(|value| value.parse().ok())
+}
+
+#[tokio::test]
+async fn limits_queue_then_shed() {
+    let limit = ConcurrencyLimit::new(1, Duration::from_millis(200));
+    let held = limit.acquire().await.expect("free slot");
+    assert_eq!(limit.in_flight(), 1);
+
+    assert!(limit.acquire().await.is_none());
+    assert_eq!(limit.shed(), 1);
+
+    let queued = limit.acquire();
+    drop(held);
+    assert!(
+        queued.await.is_some(),
+        "a slot freed while queueing is taken"
+    );
+    assert_eq!(limit.in_flight(), 0);
+    assert_eq!(limit.shed(), 1);
+}
+
+#[tokio::test]
+async fn research_runs_are_capped_and_queued() {
+    let source = SlowSource::new(Duration::from_millis(150));
+    let harness = common::build_service(Some(source.clone()), |config| {
+        config.max_concurrent_research = 2;
+        config.research_max_terms = 1;
+    });
+    common::initialize(&harness.service).await;
+
+    let calls = (0..6).map(|index| {
+        call(
+            &harness.service,
+            index,
+            "research.run",
+            json!({"topic": format!("Topic {index}")}),
+        )
+    });
+    for response in futures::future::join_all(calls).await {
+        assert!(
+            response["result"]["structuredContent"]["summary"].is_string(),
+            "unexpected response: {response}"
+        );
+    }
+    assert_eq!(source.peak.load(Ordering::SeqCst), 2);
+    assert_eq!(harness.service.research_limit().shed(), 0);
+}
+
+#[tokio::test]
+async fn research_beyond_the_wait_is_rejected_as_busy() {
+    let source = SlowSource::new(Duration::from_millis(500));
+    let harness = common::build_service(Some(source), |config| {
+        config.max_concurrent_research = 1;
+        config.request_queue_wait_secs = 0;
+    });
+    common::initialize(&harness.service).await;
+
+    let service = harness.service.clone();
+    let slow =
+        tokio::spawn(
+            async move { call(&service, 1, "research.run", json!({"topic": "Slow"})).await },
+        );
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let busy = call(
+        &harness.service,
+        2,
+        "research.run",
+        json!({"topic": "Fast"}),
+    )
+    .await;
+    assert_eq!(busy["error"]["code"], -32003, "response: {busy}");
+    assert_eq!(busy["error"]["data"]["reason"], "server busy, retry");
+    assert_eq!(busy["error"]["data"]["limit"], "MAX_CONCURRENT_RESEARCH");
+
+    // Other tools are not held back by research.
+    let datetime = call(&harness.service, 3, "utilities.current_datetime", json!({})).await;
+    assert!(datetime.get("error").is_none(), "response: {datetime}");
+
+    let finished = slow.await.unwrap();
+    assert!(finished.get("error").is_none(), "response: {finished}");
+    let again = call(
+        &harness.service,
+        4,
+        "research.run",
+        json!({"topic": "Fast"}),
+    )
+    .await;
+    assert!(again.get("error").is_none(), "response: {again}");
+}
+
+#[tokio::test]
+async fn requests_beyond_the_global_limit_get_503_and_show_in_metrics() {
+    let source = SlowSource::new(Duration::from_millis(500));
+    let harness = common::build_service(Some(source), |config| {
+        config.max_concurrent_requests = 1;
+        config.request_queue_wait_secs = 0;
+    });
+    common::initialize(&harness.service).await;
+    let router = harness.router();
+
+    let slow = tokio::spawn(router.clone().oneshot(post_mcp(research_call(1, "Slow"))));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let response = router
+        .clone()
+        .oneshot(post_mcp(
+            json!({"jsonrpc": "2.0", "id": 7, "method": "tools/list"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+    let body = json_body(response).await;
+    assert_eq!(body["id"], 7);
+    assert_eq!(body["error"]["code"], -32003, "body: {body}");
+    assert_eq!(body["error"]["data"]["reason"], "server busy, retry");
+    assert_eq!(body["error"]["data"]["limit"], "MAX_CONCURRENT_REQUESTS");
+
+    let metrics = router
+        .clone()
+        .oneshot(
+            Request::get("/api/metrics")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(metrics.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert_eq!(
+        sample(&body, "mcp_concurrency_in_flight{scope=\"requests\"}"),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(&body, "mcp_concurrency_in_flight{scope=\"research\"}"),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(&body, "mcp_concurrency_limit{scope=\"requests\"}"),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(&body, "mcp_concurrency_shed_total{scope=\"requests\"}"),
+        Some(1.0)
+    );
+
+    let finished = slow.await.unwrap().unwrap();
+    assert_eq!(finished.status(), StatusCode::OK);
+    let response = router
+        .oneshot(post_mcp(
+            json!({"jsonrpc": "2.0", "id": 8, "method": "tools/list"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn slots_are_taken_all_or_none() {
+    let limit = ConcurrencyLimit::new(3, Duration::ZERO);
+    let held = limit.acquire().await.expect("free slot");
+
+    assert!(limit.acquire_many(3).await.is_none());
+    assert!(limit.acquire_many(4).await.is_none());
+    assert_eq!(limit.in_flight(), 1);
+    assert_eq!(limit.shed(), 2);
+
+    drop(held);
+    let all = limit.acquire_many(3).await.expect("every slot free");
+    assert_eq!(limit.in_flight(), 3);
+    drop(all);
+    assert_eq!(limit.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn batches_take_a_slot_per_entry() {
+    let source = SlowSource::new(Duration::from_millis(500));
+    let harness = common::build_service(Some(source), |config| {
+        config.max_concurrent_requests = 2;
+        config.request_queue_wait_secs = 0;
+    });
+    common::initialize(&harness.service).await;
+    let router = harness.router();
+
+    let slow = tokio::spawn(router.clone().oneshot(post_mcp(research_call(1, "Slow"))));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // One slot is free, but the batch needs two.
+    let batch = json!([
+        {"jsonrpc": "2.0", "id": 2, "method": "tools/list"},
+        {"jsonrpc": "2.0", "id": 3, "method": "tools/list"}
+    ]);
+    let response = router
+        .clone()
+        .oneshot(post_mcp(batch.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], -32003, "body: {body}");
+    assert_eq!(body["error"]["data"]["limit"], "MAX_CONCURRENT_REQUESTS");
+
+    assert_eq!(slow.await.unwrap().unwrap().status(), StatusCode::OK);
+    let response = router.oneshot(post_mcp(batch)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body.as_array().map(Vec::len), Some(2), "body: {body}");
+}
+
+#[tokio::test]
+async fn batches_longer_than_the_request_limit_are_rejected() {
+    let harness = common::build_service(None, |config| {
+        config.max_concurrent_requests = 2;
+    });
+    common::initialize(&harness.service).await;
+
+    let batch: Vec<Value> = (1..=3)
+        .map(|id| json!({"jsonrpc": "2.0", "id": id, "method": "tools/list"}))
+        .collect();
+    let response = harness
+        .router()
+        .oneshot(post_mcp(Value::Array(batch)))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["error"]["code"], -32600, "body: {body}");
+    assert_eq!(
+        body["error"]["message"],
+        "batch of 3 requests exceeds the limit of 2"
+    );
+    assert_eq!(body["error"]["data"]["max"], 2);
+}
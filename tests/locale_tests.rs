@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use mp_writer_mcp_server::core::locale::Locale;
+use mp_writer_mcp_server::features::research::{
+    ParliamentDataSource, ResearchRequestDto, ResearchResponseDto, ResearchService, ResearchStatus,
+};
+
+mod common;
+
+struct Harness {
+    service: ResearchService,
+    mock: Arc<common::MockParliamentDataSource>,
+    _dir: tempfile::TempDir,
+}
+
+fn harness(mock: common::MockParliamentDataSource) -> Harness {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(dir.path()).expect("sled open");
+    let config = Arc::new(common::test_config(&dir.path().to_string_lossy()));
+    let mock = Arc::new(mock);
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let service = ResearchService::new(config, data_source, db.open_tree("research").unwrap());
+    Harness {
+        service,
+        mock,
+        _dir: dir,
+    }
+}
+
+fn request(locale: Option<&str>) -> ResearchRequestDto {
+    ResearchRequestDto {
+        topic: "Climate action".to_string(),
+        bill_keywords: vec![],
+        debate_keywords: vec![],
+        mp_id: None,
+        include_state_of_parties: true,
+        limit: Some(3),
+        max_age_secs: None,
+        cache_only: false,
+        locale: locale.map(str::to_string),
+    }
+}
+
+/// Generated text must never show a template placeholder or a Rust debug value.
+fn assert_fully_rendered(response: &ResearchResponseDto) {
+    for text in std::iter::once(&response.summary).chain(&response.advisories) {
+        for leak in ["{", "}", "Some(", "None"] {
+            assert!(!text.contains(leak), "{leak:?} leaked into {text:?}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn briefs_render_in_english_and_welsh() {
+    let english = harness(common::MockParliamentDataSource::new().failing(&["commonsdebates"]));
+    let english = english.service.run_research(request(None)).await.unwrap();
+    let welsh = harness(common::MockParliamentDataSource::new().failing(&["commonsdebates"]));
+    let welsh = welsh
+        .service
+        .run_research(request(Some("cy")))
+        .await
+        .unwrap();
+
+    assert_eq!(english.status, ResearchStatus::Partial);
+    assert_eq!(welsh.status, ResearchStatus::Partial);
+    assert_eq!(
+        english.bills[0].title, welsh.bills[0].title,
+        "data is not translated"
+    );
+
+    assert!(
+        english
+            .summary
+            .starts_with("Key research findings on \"Climate action\":"),
+        "{}",
+        english.summary
+    );
+    assert!(
+        english
+            .summary
+            .contains("- Priority bill: Climate Change Bill")
+    );
+    assert!(
+        english
+            .summary
+            .contains("House balance: Example Party holding 300 seats")
+    );
+    assert!(english.advisories[0].starts_with("Debate lookup for \"climate action\" failed"));
+
+    assert!(
+        welsh
+            .summary
+            .starts_with("Prif ganfyddiadau ymchwil ar \"Climate action\":"),
+        "{}",
+        welsh.summary
+    );
+    assert!(
+        welsh
+            .summary
+            .contains("- Bil blaenoriaeth: Climate Change Bill")
+    );
+    assert!(
+        welsh
+            .summary
+            .contains("Cydbwysedd y Tŷ: Example Party â 300 o seddi")
+    );
+    assert!(
+        welsh
+            .summary
+            .contains("- Nodyn: Methodd y chwiliad dadleuon")
+    );
+    assert!(welsh.advisories[0].starts_with("Methodd y chwiliad dadleuon am \"climate action\""));
+
+    assert_fully_rendered(&english);
+    assert_fully_rendered(&welsh);
+}
+
+#[tokio::test]
+async fn unavailable_briefs_are_localised() {
+    let harness = harness(common::MockParliamentDataSource::new().failing(&[
+        "bills",
+        "commonsdivisions",
+        "legislation",
+        "commonsdebates",
+        "stateofparties",
+    ]));
+    let welsh = harness
+        .service
+        .run_research(request(Some("cy-GB")))
+        .await
+        .unwrap();
+
+    assert_eq!(welsh.status, ResearchStatus::Failed);
+    assert!(
+        welsh.summary.contains(
+            "Ffynonellau na wnaethant ymateb: biliau, pleidleisiau, deddfwriaeth, dadleuon, cyflwr y pleidiau"
+        ),
+        "{}",
+        welsh.summary
+    );
+    assert!(welsh.summary.contains("\nCamau nesaf a awgrymir:"));
+    assert_fully_rendered(&welsh);
+}
+
+#[tokio::test]
+async fn each_locale_is_cached_separately() {
+    let harness = harness(common::MockParliamentDataSource::new());
+
+    let english = harness
+        .service
+        .run_research(request(Some("en")))
+        .await
+        .unwrap();
+    let welsh = harness
+        .service
+        .run_research(request(Some("cy")))
+        .await
+        .unwrap();
+    let welsh_again = harness
+        .service
+        .run_research(request(Some("CY")))
+        .await
+        .unwrap();
+    let default = harness.service.run_research(request(None)).await.unwrap();
+
+    assert!(!english.cached && !welsh.cached);
+    assert!(welsh_again.cached && default.cached);
+    assert_eq!(welsh_again.summary, welsh.summary);
+    assert_eq!(default.summary, english.summary);
+    assert_eq!(harness.mock.count_for("bills").await, 2);
+}
+
+#[tokio::test]
+async fn unsupported_locales_fall_back_to_english_with_a_note() {
+    let harness = harness(common::MockParliamentDataSource::new());
+
+    let french = harness
+        .service
+        .run_research(request(Some("fr")))
+        .await
+        .unwrap();
+    assert!(french.summary.starts_with("Key research findings"));
+    assert_eq!(
+        french.advisories,
+        vec![
+            "Locale \"fr\" is not supported (use one of: en, cy); this brief is in English."
+                .to_string()
+        ]
+    );
+    assert_fully_rendered(&french);
+
+    // The note belongs to the request, not to the English brief it shares a cache entry with.
+    let english = harness.service.run_research(request(None)).await.unwrap();
+    assert!(english.cached);
+    assert!(english.advisories.is_empty(), "{:?}", english.advisories);
+}
+
+#[test]
+fn locale_tags_are_read_by_their_primary_subtag() {
+    assert_eq!(Locale::parse("en"), Some(Locale::En));
+    assert_eq!(Locale::parse(" CY "), Some(Locale::Cy));
+    assert_eq!(Locale::parse("cy-GB"), Some(Locale::Cy));
+    assert_eq!(Locale::parse("en_GB"), Some(Locale::En));
+    assert_eq!(Locale::parse("fr"), None);
+    assert_eq!(Locale::parse("english"), None);
+    assert_eq!(Locale::parse(""), None);
+}
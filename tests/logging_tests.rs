@@ -0,0 +1,166 @@
+use axum::body::Body;
+use axum::http::{Request, header};
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+use tracing_subscriber::prelude::*;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::client_log::client_log_layer;
+use mp_writer_mcp_server::core::notifications::NotificationSink;
+use mp_writer_mcp_server::features::mcp::McpService;
+
+mod common;
+
+async fn set_level(service: &McpService, level: &str) -> Value {
+    let response = service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(3)),
+                "logging/setLevel",
+                Some(json!({"level": level})),
+            ),
+            common::context(),
+        )
+        .await;
+    match response {
+        Ok(success) => serde_json::to_value(success).unwrap(),
+        Err(error) => serde_json::to_value(error).unwrap(),
+    }
+}
+
+/// Call `parliament.fetch_core_dataset` with a notification sink, returning the
+/// `notifications/message` params it produced.
+async fn messages_for_dataset_call(service: &McpService) -> Vec<Value> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let context = common::context().with_notifications(NotificationSink::new(sender));
+    let response = common::call_tool_with_context(
+        service,
+        "parliament.fetch_core_dataset",
+        json!({"dataset": "commonsdivisions", "enableCache": true}),
+        context,
+    )
+    .await;
+    assert!(
+        response["result"]["structuredContent"].is_object(),
+        "response: {response}"
+    );
+
+    let mut messages = Vec::new();
+    while let Ok(message) = receiver.try_recv() {
+        if message["method"] == "notifications/message" {
+            messages.push(message["params"].clone());
+        }
+    }
+    messages
+}
+
+#[tokio::test]
+async fn logging_capability_is_advertised_and_levels_validated() {
+    let harness = common::build_service(None, |_| {});
+    let initialize = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(1)),
+                "initialize",
+                Some(json!({
+                    "protocolVersion": common::PROTOCOL_VERSION,
+                    "clientInfo": {"name": "test-client", "version": "1.0"},
+                    "capabilities": {}
+                })),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("initialize")
+        .expect("response");
+    assert_eq!(initialize.result["capabilities"]["logging"], json!({}));
+
+    common::initialize(&harness.service).await;
+    assert!(!harness.service.wants_log_messages(&common::context()));
+
+    let invalid = set_level(&harness.service, "verbose").await;
+    assert_eq!(invalid["error"]["code"], -32602, "response: {invalid}");
+
+    let accepted = set_level(&harness.service, "warning").await;
+    assert_eq!(accepted["result"], json!({}), "response: {accepted}");
+    assert!(harness.service.wants_log_messages(&common::context()));
+}
+
+#[tokio::test]
+async fn cache_misses_are_forwarded_at_the_session_level() {
+    let _subscriber =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(client_log_layer()));
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdivisions.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    set_level(&harness.service, "warning").await;
+    assert!(messages_for_dataset_call(&harness.service).await.is_empty());
+
+    // The first call filled the cache; a fresh service starts with an empty one.
+    let harness =
+        common::build_service(None, |config| config.upstream_base_url = Some(server.uri()));
+    common::initialize(&harness.service).await;
+    set_level(&harness.service, "debug").await;
+    let messages = messages_for_dataset_call(&harness.service).await;
+    let miss = messages
+        .iter()
+        .find(|message| {
+            message["data"]["message"]
+                .as_str()
+                .is_some_and(|text| text.starts_with("cache miss"))
+        })
+        .unwrap_or_else(|| panic!("no cache miss message in {messages:?}"));
+    assert_eq!(miss["level"], "debug");
+    assert_eq!(miss["logger"], "features.parliament.client");
+    assert!(
+        miss["data"]["url"]
+            .as_str()
+            .is_some_and(|url| url.contains("commonsdivisions"))
+    );
+}
+
+#[tokio::test]
+async fn tool_calls_stream_once_a_level_is_set() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+    let call = || {
+        Request::post("/api/mcp")
+            .header("x-api-key", "test")
+            .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json, text/event-stream")
+            .body(Body::from(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 4,
+                    "method": "tools/call",
+                    "params": {"name": "utilities.current_datetime", "arguments": {}}
+                })
+                .to_string(),
+            ))
+            .unwrap()
+    };
+    let content_type = |response: &axum::response::Response| {
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+
+    let plain = harness.router().oneshot(call()).await.unwrap();
+    assert!(content_type(&plain).starts_with("application/json"));
+
+    set_level(&harness.service, "info").await;
+    let streamed = harness.router().oneshot(call()).await.unwrap();
+    assert!(content_type(&streamed).starts_with("text/event-stream"));
+}
@@ -0,0 +1,1066 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::ToolFilterConfig;
+use mp_writer_mcp_server::core::error::AppError;
+use mp_writer_mcp_server::core::notifications::NotificationSink;
+use mp_writer_mcp_server::features::mcp::dto::JsonRpcRequest;
+use mp_writer_mcp_server::features::mcp::session::{
+    MAX_SESSIONS, MAX_TERMINATED_SESSIONS, SessionRef, SessionRegistry,
+};
+use mp_writer_mcp_server::features::mcp::{McpService, RequestContext};
+use mp_writer_mcp_server::features::research::{
+    ParliamentDataSource, ResearchRequestDto, ResearchRunContext, ResearchService,
+};
+use tokio_util::sync::CancellationToken;
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+
+#[tokio::test]
+async fn research_run_emits_progress_notifications() {
+    let mock: Arc<dyn ParliamentDataSource> = Arc::new(common::MockParliamentDataSource::new());
+    let harness = common::build_service(Some(mock), |_| {});
+    common::initialize(&harness.service).await;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let context = common::context().with_notifications(NotificationSink::new(sender));
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(7)),
+                "tools/call",
+                Some(json!({
+                    "name": "research.run",
+                    "arguments": {"topic": "climate"},
+                    "_meta": {"progressToken": "research-1"}
+                })),
+            ),
+            context,
+        )
+        .await
+        .expect("tools/call")
+        .expect("response");
+    assert!(response.result["structuredContent"]["summary"].is_string());
+
+    let mut notifications = Vec::new();
+    while let Ok(message) = receiver.try_recv() {
+        notifications.push(message);
+    }
+
+    assert_eq!(notifications.len(), 4);
+    for (index, notification) in notifications.iter().enumerate() {
+        assert_eq!(notification["method"], "notifications/progress");
+        assert_eq!(notification["params"]["progressToken"], "research-1");
+        assert_eq!(notification["params"]["progress"], json!(index + 1));
+        assert_eq!(notification["params"]["total"], json!(4));
+    }
+}
+
+#[tokio::test]
+async fn research_run_without_progress_token_sends_nothing() {
+    let mock: Arc<dyn ParliamentDataSource> = Arc::new(common::MockParliamentDataSource::new());
+    let harness = common::build_service(Some(mock), |_| {});
+    common::initialize(&harness.service).await;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Value>();
+    let context = common::context().with_notifications(NotificationSink::new(sender));
+    harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(8)),
+                "tools/call",
+                Some(json!({"name": "research.run", "arguments": {"topic": "climate"}})),
+            ),
+            context,
+        )
+        .await
+        .expect("tools/call");
+
+    assert!(receiver.try_recv().is_err());
+}
+
+/// Mock whose collections never match, so each collector keeps trying broader terms and
+/// would issue many sequential upstream calls if left running.
+fn slow_empty_mock() -> Arc<common::MockParliamentDataSource> {
+    let mut mock = common::MockParliamentDataSource::new().with_delay(Duration::from_millis(100));
+    mock.bills = json!({"items": []});
+    mock.divisions = json!({"items": []});
+    mock.legislation = json!({"items": []});
+    mock.debates = json!({"items": []});
+    Arc::new(mock)
+}
+
+#[tokio::test]
+async fn cancelled_tool_call_stops_upstream_calls_and_sends_no_response() {
+    let mock = slow_empty_mock();
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let harness = common::build_service(Some(data_source), |_| {});
+    common::initialize(&harness.service).await;
+
+    let service = harness.service.clone();
+    let call = tokio::spawn(async move {
+        service
+            .handle_jsonrpc(
+                common::request(
+                    Some(json!("call-1")),
+                    "tools/call",
+                    Some(json!({
+                        "name": "research.run",
+                        "arguments": {"topic": "climate action"}
+                    })),
+                ),
+                common::context(),
+            )
+            .await
+    });
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let cancelled = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                None,
+                "notifications/cancelled",
+                Some(json!({"requestId": "call-1", "reason": "client timeout"})),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("cancel notification");
+    assert!(cancelled.is_none());
+
+    let outcome = tokio::time::timeout(Duration::from_millis(500), call)
+        .await
+        .expect("cancelled call should finish promptly")
+        .expect("task join");
+    assert!(
+        matches!(outcome, Ok(None)),
+        "cancelled call must not respond"
+    );
+
+    let calls_at_cancel = mock.total_calls().await;
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(mock.total_calls().await, calls_at_cancel);
+}
+
+#[tokio::test]
+async fn cancellation_for_unknown_request_is_ignored() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                None,
+                "notifications/cancelled",
+                Some(json!({"requestId": 404})),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("notification accepted");
+    assert!(response.is_none());
+}
+
+#[tokio::test]
+async fn cancelled_research_run_is_not_cached() {
+    let mock = slow_empty_mock();
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let service = ResearchService::new(config, data_source, db.open_tree("research").unwrap());
+    let request = ResearchRequestDto {
+        topic: "Climate action".to_string(),
+        bill_keywords: vec![],
+        debate_keywords: vec![],
+        mp_id: None,
+        include_state_of_parties: false,
+        limit: Some(3),
+        max_age_secs: None,
+        cache_only: false,
+        locale: None,
+    };
+
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        canceller.cancel();
+    });
+
+    let context = ResearchRunContext {
+        progress: None,
+        cancellation: Some(&token),
+    };
+    let result = service
+        .run_research_with_context(request.clone(), context)
+        .await;
+    assert!(result.is_err());
+
+    let calls_before = mock.total_calls().await;
+    let response = service
+        .run_research(request)
+        .await
+        .expect("uncancelled run");
+    assert!(!response.cached);
+    assert!(mock.total_calls().await > calls_before);
+}
+
+fn initialize_request(id: i64, client: &str) -> JsonRpcRequest {
+    common::request(
+        Some(json!(id)),
+        "initialize",
+        Some(json!({
+            "protocolVersion": common::PROTOCOL_VERSION,
+            "clientInfo": {"name": client, "version": "1.0"},
+            "capabilities": {}
+        })),
+    )
+}
+
+async fn call_datetime(service: &McpService, context: RequestContext) -> Value {
+    common::call_tool_with_context(service, "utilities.current_datetime", json!({}), context).await
+}
+
+fn session_context(session_id: &str) -> RequestContext {
+    common::context()
+        .with_api_key("test")
+        .with_session_id(session_id)
+}
+
+async fn start_session(service: &McpService, context: RequestContext) {
+    service
+        .handle_jsonrpc(initialize_request(1, "client"), context.clone())
+        .await
+        .expect("initialize");
+    send_initialized(service, context).await;
+}
+
+async fn send_initialized(service: &McpService, context: RequestContext) {
+    service
+        .handle_jsonrpc(
+            common::request(None, "notifications/initialized", None),
+            context,
+        )
+        .await
+        .expect("initialized");
+}
+
+fn datetime_ok(response: &Value) -> bool {
+    response["result"]["structuredContent"]["utc"].is_string()
+}
+
+#[tokio::test]
+async fn reinitialize_resets_readiness_until_initialized_notification() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+    let context = || session_context("session-a");
+
+    start_session(service, context()).await;
+    assert!(datetime_ok(&call_datetime(service, context()).await));
+
+    let reinit = service
+        .handle_jsonrpc(initialize_request(2, "second-client"), context())
+        .await
+        .expect("second initialize should be accepted")
+        .expect("initialize response");
+    assert_eq!(reinit.result["protocolVersion"], common::PROTOCOL_VERSION);
+
+    let blocked = call_datetime(service, context()).await;
+    assert_eq!(blocked["error"]["code"], -32002);
+
+    let list = service
+        .handle_jsonrpc(
+            common::request(Some(json!(3)), "tools/list", None),
+            context(),
+        )
+        .await
+        .expect_err("tools/list must wait for a fresh initialized notification");
+    assert_eq!(list.error.code, -32002);
+
+    send_initialized(service, context()).await;
+    assert!(datetime_ok(&call_datetime(service, context()).await));
+}
+
+#[tokio::test]
+async fn reinitialize_with_different_key_is_rejected() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+
+    start_session(service, session_context("session-a")).await;
+
+    let other_key = service
+        .handle_jsonrpc(
+            initialize_request(2, "client"),
+            common::context()
+                .with_api_key("other")
+                .with_session_id("session-a"),
+        )
+        .await
+        .expect_err("different API key must be rejected");
+    assert_eq!(other_key.error.code, -32600);
+    assert!(other_key.error.message.contains("API key"));
+
+    // The rejected attempt leaves the established session usable.
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-a")).await
+    ));
+}
+
+#[tokio::test]
+async fn sessions_are_independent() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+
+    start_session(service, session_context("session-a")).await;
+
+    // A reconnect under a new session id starts from scratch rather than inheriting.
+    let fresh = call_datetime(service, session_context("session-b")).await;
+    assert_eq!(fresh["error"]["code"], -32002);
+
+    service
+        .handle_jsonrpc(
+            initialize_request(2, "client"),
+            session_context("session-b"),
+        )
+        .await
+        .expect("a new session id may initialize alongside the existing one");
+    let pending = call_datetime(service, session_context("session-b")).await;
+    assert_eq!(pending["error"]["code"], -32002);
+
+    // Initializing session-b did not disturb session-a.
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-a")).await
+    ));
+
+    send_initialized(service, session_context("session-b")).await;
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-b")).await
+    ));
+}
+
+#[tokio::test]
+async fn sessions_are_scoped_to_the_key_that_started_them() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+
+    start_session(service, session_context("session-a")).await;
+
+    let other_key = call_datetime(
+        service,
+        session_context("session-a").with_key_name("desktop"),
+    )
+    .await;
+    assert_eq!(other_key["error"]["code"], -32002, "{other_key}");
+
+    start_session(
+        service,
+        session_context("session-a").with_key_name("desktop"),
+    )
+    .await;
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-a")).await
+    ));
+}
+
+#[tokio::test]
+async fn session_reset_requires_initialize_again() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+    let context = || session_context("session-a");
+
+    start_session(service, context()).await;
+    start_session(service, session_context("session-b")).await;
+
+    let reset = service
+        .handle_jsonrpc(
+            common::request(Some(json!(5)), "session/reset", None),
+            context(),
+        )
+        .await
+        .expect("reset")
+        .expect("reset response");
+    assert_eq!(reset.result["reset"], true);
+
+    let ping = service
+        .handle_jsonrpc(common::request(Some(json!(6)), "ping", None), context())
+        .await
+        .expect_err("ping after reset must require initialize");
+    assert_eq!(ping.error.code, -32002);
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-b")).await
+    ));
+
+    // A different API key is accepted once the old session is gone.
+    service
+        .handle_jsonrpc(
+            initialize_request(7, "client"),
+            common::context()
+                .with_api_key("other")
+                .with_session_id("session-a"),
+        )
+        .await
+        .expect("initialize after reset");
+}
+
+fn delete_session(session_id: Option<&str>) -> Request<Body> {
+    delete_session_with_key(session_id, "test")
+}
+
+fn delete_session_with_key(session_id: Option<&str>, api_key: &str) -> Request<Body> {
+    let mut request = Request::delete("/api/mcp").header("x-api-key", api_key);
+    if let Some(session_id) = session_id {
+        request = request.header("Mcp-Session-Id", session_id);
+    }
+    request.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn delete_terminates_the_session_until_reinitialized() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+    let context = || session_context("session-a");
+
+    start_session(service, context()).await;
+    start_session(service, session_context("session-b")).await;
+
+    let deleted = harness
+        .router()
+        .oneshot(delete_session(Some("session-a")))
+        .await
+        .unwrap();
+    assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+
+    let terminated = call_datetime(service, context()).await;
+    assert_eq!(terminated["error"]["code"], -32002);
+    assert!(
+        terminated["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("session terminated")
+    );
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-b")).await
+    ));
+
+    let again = harness
+        .router()
+        .oneshot(delete_session(Some("session-a")))
+        .await
+        .unwrap();
+    assert_eq!(again.status(), StatusCode::NOT_FOUND);
+
+    start_session(service, context()).await;
+    assert!(datetime_ok(&call_datetime(service, context()).await));
+}
+
+#[tokio::test]
+async fn delete_without_a_known_session_is_not_found() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    for session_id in [None, Some("never-initialized")] {
+        let response = harness
+            .router()
+            .oneshot(delete_session(session_id))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "session_not_found");
+    }
+}
+
+#[tokio::test]
+async fn delete_only_ends_sessions_of_the_callers_key() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    let service = &harness.service;
+    start_session(service, session_context("session-a")).await;
+
+    let response = harness
+        .router()
+        .oneshot(delete_session_with_key(Some("session-a"), ADMIN_KEY))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(datetime_ok(
+        &call_datetime(service, session_context("session-a")).await
+    ));
+}
+
+#[test]
+fn only_the_most_recent_terminations_are_remembered() {
+    let registry = SessionRegistry::new();
+    let ids: Vec<String> = (0..=MAX_TERMINATED_SESSIONS)
+        .map(|index| format!("session-{index}"))
+        .collect();
+
+    for id in &ids {
+        registry
+            .initialize(
+                default_key_session(id),
+                None,
+                "client".to_string(),
+                common::PROTOCOL_VERSION.to_string(),
+            )
+            .unwrap();
+        assert!(registry.terminate(default_key_session(id)));
+    }
+
+    assert!(!registry.is_terminated(default_key_session(&ids[0])));
+    assert!(registry.is_terminated(default_key_session(&ids[1])));
+    assert!(registry.is_terminated(default_key_session(ids.last().unwrap())));
+}
+
+#[test]
+fn the_least_recently_used_session_is_evicted_past_the_cap() {
+    let registry = SessionRegistry::new();
+    let ids: Vec<String> = (0..=MAX_SESSIONS)
+        .map(|index| format!("session-{index}"))
+        .collect();
+
+    for (index, id) in ids.iter().enumerate() {
+        if index == MAX_SESSIONS {
+            assert!(registry.get(default_key_session(&ids[0])).is_some());
+        }
+        registry
+            .initialize(
+                default_key_session(id),
+                None,
+                "client".to_string(),
+                common::PROTOCOL_VERSION.to_string(),
+            )
+            .unwrap();
+    }
+
+    assert!(registry.is_known(default_key_session(&ids[0])));
+    assert!(!registry.is_known(default_key_session(&ids[1])));
+    assert!(registry.is_known(default_key_session(ids.last().unwrap())));
+}
+
+fn post_mcp(session_id: Option<&str>, body: Value) -> Request<Body> {
+    let mut request = Request::post("/api/mcp")
+        .header("x-api-key", "test")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header("content-type", "application/json");
+    if let Some(session_id) = session_id {
+        request = request.header("Mcp-Session-Id", session_id);
+    }
+    request.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn response_json(response: axum::response::Response) -> Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn datetime_call() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {"name": "utilities.current_datetime", "arguments": {}}
+    })
+}
+
+#[tokio::test]
+async fn initialize_issues_the_session_id() {
+    let harness = common::build_service(None, |_| {});
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": common::PROTOCOL_VERSION,
+            "clientInfo": {"name": "test-client", "version": "1.0"},
+            "capabilities": {}
+        }
+    });
+
+    let response = harness
+        .router()
+        .oneshot(post_mcp(None, initialize))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_id = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(session_id.len(), 32);
+
+    let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+    let response = harness
+        .router()
+        .oneshot(post_mcp(Some(&session_id), initialized))
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    // The issued id and the header-less default both address the new session.
+    for header in [Some(session_id.as_str()), None] {
+        let response = harness
+            .router()
+            .oneshot(post_mcp(header, datetime_call()))
+            .await
+            .unwrap();
+        assert!(!response.headers().contains_key("mcp-session-id"));
+        assert!(datetime_ok(&response_json(response).await));
+    }
+}
+
+#[tokio::test]
+async fn unknown_session_ids_are_not_found() {
+    let harness = common::build_service(None, |_| {});
+    start_session(&harness.service, session_context("session-a")).await;
+
+    let response = harness
+        .router()
+        .oneshot(post_mcp(Some("client-chosen"), datetime_call()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        response_json(response).await["error"]["code"],
+        "session_not_found"
+    );
+}
+
+fn default_key_session(id: &str) -> SessionRef<'_> {
+    SessionRef {
+        owner: Some("default"),
+        id: Some(id),
+    }
+}
+
+#[tokio::test]
+async fn delete_cancels_in_flight_requests_of_the_session() {
+    let mock: Arc<dyn ParliamentDataSource> =
+        Arc::new(common::MockParliamentDataSource::new().with_delay(Duration::from_secs(5)));
+    let harness = common::build_service(Some(mock), |_| {});
+    let service = harness.service.clone();
+    start_session(&service, session_context("session-a")).await;
+
+    let call = tokio::spawn(async move {
+        service
+            .handle_jsonrpc(
+                common::request(
+                    Some(json!(42)),
+                    "tools/call",
+                    Some(json!({"name": "research.run", "arguments": {"topic": "housing"}})),
+                ),
+                session_context("session-a"),
+            )
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert!(
+        harness
+            .service
+            .terminate_session(&session_context("session-a"))
+    );
+    let response = tokio::time::timeout(Duration::from_secs(1), call)
+        .await
+        .expect("cancelled promptly")
+        .expect("task join");
+    assert!(matches!(response, Ok(None)));
+}
+
+#[tokio::test]
+async fn slow_tool_call_times_out_with_tool_error() {
+    let mock: Arc<dyn ParliamentDataSource> =
+        Arc::new(common::MockParliamentDataSource::new().with_delay(Duration::from_secs(5)));
+    let harness = common::build_service(Some(mock), |config| {
+        config
+            .tool_timeouts
+            .overrides
+            .insert("TOOL_TIMEOUT_RESEARCH_RUN".to_string(), 1);
+    });
+    common::initialize(&harness.service).await;
+
+    let started = std::time::Instant::now();
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "climate"}),
+    )
+    .await;
+
+    assert!(started.elapsed() < Duration::from_secs(3));
+    let result = &response["result"];
+    assert_eq!(result["isError"], true);
+    assert_eq!(result["content"][0]["text"], "tool execution exceeded 1s");
+    assert_eq!(result["structuredContent"]["kind"], "timeout");
+    assert_eq!(result["structuredContent"]["timeoutSecs"], 1);
+    assert_eq!(result["structuredContent"]["retryable"], true);
+}
+
+#[test]
+fn research_gets_a_longer_default_timeout() {
+    let mut timeouts = mp_writer_mcp_server::config::ToolTimeoutConfig {
+        default_secs: 20,
+        overrides: Default::default(),
+    };
+    assert_eq!(
+        timeouts.for_tool("parliament.fetch_bills"),
+        Some(Duration::from_secs(20))
+    );
+    assert_eq!(
+        timeouts.for_tool("research.run"),
+        Some(Duration::from_secs(60))
+    );
+
+    timeouts
+        .overrides
+        .insert("TOOL_TIMEOUT_PARLIAMENT_FETCH_BILLS".to_string(), 0);
+    assert_eq!(timeouts.for_tool("parliament.fetch_bills"), None);
+}
+
+#[tokio::test]
+async fn disabled_tools_are_hidden_and_uncallable() {
+    let harness = common::build_service(None, |config| {
+        config.tool_filter = ToolFilterConfig {
+            enabled: Some(vec![
+                "research.run".to_string(),
+                "utilities.current_datetime".to_string(),
+            ]),
+            disabled: vec!["utilities.current_datetime".to_string()],
+        };
+    });
+    let service = &harness.service;
+    common::initialize(service).await;
+
+    let listed = service
+        .handle_jsonrpc(
+            common::request(Some(json!(2)), "tools/list", None),
+            common::context(),
+        )
+        .await
+        .expect("tools/list")
+        .expect("response");
+    let names: Vec<&str> = listed.result["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(names, ["research.run"]);
+
+    for tool in ["utilities.current_datetime", "parliament.fetch_bills"] {
+        let response = common::call_tool(service, tool, json!({})).await;
+        assert_eq!(
+            response["error"]["code"], -32601,
+            "{tool} should be disabled"
+        );
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("disabled"))
+        );
+    }
+}
+
+#[test]
+fn tool_filter_rejects_unknown_tool_names() {
+    let known = vec![
+        "research.run".to_string(),
+        "parliament.fetch_bills".to_string(),
+    ];
+    let valid = ToolFilterConfig {
+        enabled: Some(vec!["research.run".to_string()]),
+        disabled: vec!["parliament.fetch_bills".to_string()],
+    };
+    assert!(valid.validate(&known).is_ok());
+
+    let typo = ToolFilterConfig {
+        enabled: None,
+        disabled: vec!["parliament.fetch_bill".to_string()],
+    };
+    let error = typo.validate(&known).expect_err("typo should be rejected");
+    assert!(
+        matches!(&error, AppError::Configuration { .. }),
+        "unexpected error: {error:?}"
+    );
+    assert!(error.to_string().contains("TOOLS_DISABLED"));
+    assert!(error.to_string().contains("parliament.fetch_bill"));
+}
+
+async fn list_changed_capability(service: &McpService) -> Value {
+    let params = json!({
+        "protocolVersion": common::PROTOCOL_VERSION,
+        "clientInfo": {"name": "test-client", "version": "1.0"},
+        "capabilities": {}
+    });
+    let response = service
+        .handle_jsonrpc(
+            common::request(Some(json!(1)), "initialize", Some(params)),
+            common::context(),
+        )
+        .await
+        .expect("initialize")
+        .expect("response");
+    response.result["capabilities"]["tools"]["listChanged"].clone()
+}
+
+#[tokio::test]
+async fn list_changed_is_advertised_once_a_push_transport_is_mounted() {
+    let harness = common::build_service(None, |_| {});
+
+    assert_eq!(list_changed_capability(&harness.service).await, false);
+    let _router = harness.router();
+    assert_eq!(list_changed_capability(&harness.service).await, true);
+}
+
+#[tokio::test]
+async fn reloading_tools_pushes_list_changed_to_stream_subscribers() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    let router = harness.router();
+    let service = &harness.service;
+
+    let rejected = router
+        .clone()
+        .oneshot(
+            Request::get("/api/mcp")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), StatusCode::NOT_ACCEPTABLE);
+
+    let stream = router
+        .oneshot(
+            Request::get("/api/mcp")
+                .header("x-api-key", "test")
+                .header("accept", "text/event-stream")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(stream.status(), StatusCode::OK);
+    let mut events = stream.into_body().into_data_stream();
+
+    common::initialize(service).await;
+    let reload = |params: Value| async move {
+        service
+            .handle_jsonrpc(
+                common::request(Some(json!(7)), "admin/tools/reload", Some(params)),
+                common::context().with_api_key(ADMIN_KEY),
+            )
+            .await
+    };
+
+    let changed = reload(json!({"disabled": ["utilities.current_datetime"]}))
+        .await
+        .expect("reload")
+        .expect("response");
+    assert_eq!(changed.result["changed"], true);
+    assert!(
+        !changed.result["tools"]
+            .as_array()
+            .expect("tools")
+            .contains(&json!("utilities.current_datetime"))
+    );
+
+    let frame = tokio::time::timeout(Duration::from_secs(1), events.next())
+        .await
+        .expect("notification within a second")
+        .expect("stream open")
+        .expect("frame");
+    let frame = String::from_utf8(frame.to_vec()).expect("utf-8 frame");
+    assert!(
+        frame.contains("notifications/tools/list_changed"),
+        "unexpected frame: {frame}"
+    );
+
+    let unchanged = reload(json!({"disabled": ["utilities.current_datetime"]}))
+        .await
+        .expect("reload")
+        .expect("response");
+    assert_eq!(unchanged.result["changed"], false);
+    assert_eq!(
+        common::call_tool(service, "utilities.current_datetime", json!({})).await["error"]["code"],
+        -32601
+    );
+
+    let typo = reload(json!({"enabled": ["research.runn"]}))
+        .await
+        .expect_err("unknown tool names are rejected");
+    assert_eq!(typo.error.code, -32602);
+}
+
+#[tokio::test]
+async fn reloading_tools_requires_the_admin_key() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    let service = &harness.service;
+    common::initialize(service).await;
+
+    let refused = service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(7)),
+                "admin/tools/reload",
+                Some(json!({"disabled": ["utilities.current_datetime"]})),
+            ),
+            common::context().with_api_key("test"),
+        )
+        .await
+        .expect_err("client keys cannot reload tools");
+
+    assert_eq!(refused.error.code, -32600);
+    assert!(refused.error.message.contains("admin API key"));
+    let still_enabled = common::call_tool(service, "utilities.current_datetime", json!({})).await;
+    assert!(still_enabled["error"].is_null(), "{still_enabled}");
+}
+
+#[tokio::test]
+async fn unknown_notifications_are_ignored() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+
+    for (id, method) in [
+        (None, "notifications/roots/list_changed"),
+        (None, "notifications/something_new"),
+        (Some(json!(3)), "notifications/roots/list_changed"),
+        (None, "custom/fire_and_forget"),
+    ] {
+        let response = service
+            .handle_jsonrpc(common::request(id, method, None), common::context())
+            .await;
+        assert!(
+            matches!(response, Ok(None)),
+            "{method} should not be answered"
+        );
+    }
+}
+
+#[tokio::test]
+async fn unknown_methods_with_an_id_are_still_rejected() {
+    let harness = common::build_service(None, |_| {});
+
+    let error = harness
+        .service
+        .handle_jsonrpc(
+            common::request(Some(json!(9)), "roots/list", None),
+            common::context(),
+        )
+        .await
+        .expect_err("unknown request should fail");
+
+    assert_eq!(error.error.code, -32601);
+    assert_eq!(error.id, json!(9));
+}
+
+#[tokio::test]
+async fn unsupported_protocol_version_is_answered_with_the_latest() {
+    let harness = common::build_service(None, |_| {});
+    let service = &harness.service;
+    let future = "2026-03-01";
+    let pinned = || RequestContext::new(Some(future.to_string()));
+
+    let response = service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(1)),
+                "initialize",
+                Some(json!({
+                    "protocolVersion": future,
+                    "clientInfo": {"name": "future-client", "version": "9.0"},
+                    "capabilities": {}
+                })),
+            ),
+            pinned(),
+        )
+        .await
+        .expect("initialize should succeed")
+        .expect("response");
+    let offered = response.result["protocolVersion"]
+        .as_str()
+        .expect("protocolVersion")
+        .to_string();
+    assert_eq!(offered, "2025-06-26");
+
+    let accepted = RequestContext::new(Some(offered.clone()));
+    service
+        .handle_jsonrpc(
+            common::request(None, "notifications/initialized", None),
+            accepted.clone(),
+        )
+        .await
+        .expect("initialized with the offered version");
+
+    let rejected = service
+        .handle_jsonrpc(
+            common::request(Some(json!(2)), "tools/list", None),
+            pinned(),
+        )
+        .await
+        .expect_err("requests pinned to the unsupported version fail");
+    assert_eq!(rejected.error.code, -32600);
+    assert!(rejected.error.message.contains(future));
+
+    let listed = service
+        .handle_jsonrpc(
+            common::request(Some(json!(3)), "tools/list", None),
+            accepted,
+        )
+        .await
+        .expect("tools/list with the offered version")
+        .expect("response");
+    assert!(listed.result["tools"].is_array());
+}
+
+#[tokio::test]
+async fn supported_protocol_versions_are_echoed() {
+    let harness = common::build_service(None, |_| {});
+
+    for version in ["2025-06-18", "2025-03-26", "1.0"] {
+        let response = harness
+            .service
+            .handle_jsonrpc(
+                common::request(
+                    Some(json!(1)),
+                    "initialize",
+                    Some(json!({
+                        "protocolVersion": version,
+                        "clientInfo": {"name": "test-client", "version": "1.0"},
+                        "capabilities": {}
+                    })),
+                ),
+                RequestContext::new(Some(version.to_string())),
+            )
+            .await
+            .expect("initialize")
+            .expect("response");
+        assert_eq!(response.result["protocolVersion"], version);
+    }
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::{FetchMembersBulkArgs, MemberLookupEntry};
+
+mod common;
+
+const MP_ID: u32 = 4514;
+const MEMBER_PATH: &str = "/api/Members/4514";
+
+/// Postcodes.io and the Members API for one postcode whose constituency has a sitting MP.
+async fn upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/postcodes/SW1A1AA"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "result": {"parliamentary_constituency": "Cities of London and Westminster"}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/Members/Search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"value": {"id": MP_ID, "name": "Rachel Blake"}}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(MEMBER_PATH))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "value": {
+                "id": MP_ID,
+                "nameDisplayAs": "Rachel Blake",
+                "latestParty": {"name": "Labour"},
+                "latestHouseMembership": {
+                    "membershipFrom": "Cities of London and Westminster",
+                    "house": 1
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn member_requests(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == MEMBER_PATH)
+        .count()
+}
+
+async fn lookup(harness: &common::TestHarness) {
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.lookup_constituency_offline",
+        json!({"postcode": "SW1A 1AA"}),
+    )
+    .await;
+    assert_eq!(
+        response["result"]["structuredContent"]["mpId"], MP_ID,
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn lookup_prefetches_the_member_so_the_profile_is_a_cache_hit() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.prefetch_member_after_lookup = true;
+    });
+    common::initialize(&harness.service).await;
+
+    lookup(&harness).await;
+
+    let mut waited = 0;
+    while member_requests(&server).await == 0 && waited < 50 {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        waited += 1;
+    }
+    assert_eq!(member_requests(&server).await, 1);
+
+    let members = harness
+        .client
+        .fetch_members_bulk(FetchMembersBulkArgs {
+            mp_ids: vec![MP_ID],
+            enable_cache: None,
+            cache_only: None,
+        })
+        .await
+        .expect("members");
+    let Some(MemberLookupEntry::Found(member)) = members.members.get(&MP_ID) else {
+        panic!("member not found: {members:?}");
+    };
+    assert_eq!(member.name, "Rachel Blake");
+    assert_eq!(member.party.as_deref(), Some("Labour"));
+    assert_eq!(member_requests(&server).await, 1);
+
+    // A second lookup is served from cache and finds the member already cached.
+    lookup(&harness).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(member_requests(&server).await, 1);
+}
+
+#[tokio::test]
+async fn prefetch_can_be_turned_off() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.prefetch_member_after_lookup = false;
+    });
+    common::initialize(&harness.service).await;
+
+    lookup(&harness).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(member_requests(&server).await, 0);
+}
@@ -0,0 +1,168 @@
+use std::path::Path;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_member_roles";
+
+/// Members API biography and Committees API memberships of member 4514, a minister who
+/// previously chaired a committee and held a shadow post.
+const MEMBER_ROLE_FIXTURES: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/member_roles");
+
+async fn backbencher(biography: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/Members/172/Biography"))
+        .respond_with(biography)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/Members"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{
+                "committee": {"id": 24, "name": "Backbench Business Committee", "house": "Commons"},
+                "roles": [{"role": {"name": "Member"}, "startDate": "2023-01-16T00:00:00", "endDate": null}]
+            }],
+            "totalResults": 1
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn a_minister_has_posts_and_committee_roles_current_first() {
+    let _server = MockServer::start().await;
+    let harness = common::recorded_harness(&_server, Path::new(MEMBER_ROLE_FIXTURES)).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 4514})).await;
+
+    assert_eq!(result["mpId"], 4514);
+    assert!(result.get("note").is_none(), "{result}");
+    assert_eq!(
+        result["roles"],
+        json!([
+            {
+                "kind": "government",
+                "name": "Minister of State",
+                "organisation": "Department of Health and Social Care",
+                "startDate": "2024-07-06",
+                "endDate": null,
+                "current": true
+            },
+            {
+                "kind": "committee",
+                "name": "Member",
+                "organisation": "Public Accounts Committee",
+                "startDate": null,
+                "endDate": null,
+                "current": true
+            },
+            {
+                "kind": "opposition",
+                "name": "Shadow Minister",
+                "organisation": "Health and Social Care",
+                "startDate": "2020-04-09",
+                "endDate": "2024-07-05",
+                "current": false
+            },
+            {
+                "kind": "government",
+                "name": "Parliamentary Private Secretary",
+                "organisation": null,
+                "startDate": "2019-01-10",
+                "endDate": "2019-12-12",
+                "current": false
+            },
+            {
+                "kind": "committee",
+                "name": "Chair",
+                "organisation": "Health and Social Care Committee",
+                "startDate": "2018-03-01",
+                "endDate": "2020-04-09",
+                "current": false
+            },
+            {
+                "kind": "committee",
+                "name": "Member",
+                "organisation": "Health and Social Care Committee",
+                "startDate": "2015-07-08",
+                "endDate": "2018-03-01",
+                "current": false
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn a_backbencher_has_only_committee_roles() {
+    let server = backbencher(ResponseTemplate::new(200).set_body_json(json!({
+        "value": {"governmentPosts": [], "oppositionPosts": [], "committeeMemberships": []}
+    })))
+    .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"mpId": "https://members.parliament.uk/member/172"}),
+    )
+    .await;
+
+    assert_eq!(
+        result["roles"],
+        json!([{
+            "kind": "committee",
+            "name": "Member",
+            "organisation": "Backbench Business Committee",
+            "startDate": "2023-01-16",
+            "endDate": null,
+            "current": true
+        }])
+    );
+}
+
+#[tokio::test]
+async fn roles_are_cached_per_member() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(MEMBER_ROLE_FIXTURES)).await;
+
+    let first = common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 4514})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"mpId": 4514, "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn a_failed_source_is_noted_and_not_cached() {
+    let server = backbencher(ResponseTemplate::new(500)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 172})).await;
+
+    assert_eq!(result["roles"].as_array().unwrap().len(), 1);
+    assert!(
+        result["note"]
+            .as_str()
+            .unwrap()
+            .contains("government and opposition posts"),
+        "{result}"
+    );
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"mpId": 172, "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(cached["cacheMiss"], true, "{cached}");
+}
@@ -0,0 +1,125 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+fn member(id: u32, name: &str, active: bool) -> Value {
+    json!({
+        "value": {
+            "id": id,
+            "nameDisplayAs": name,
+            "latestParty": {"name": "Labour"},
+            "latestHouseMembership": {
+                "membershipFrom": "Holborn and St Pancras",
+                "house": 1,
+                "membershipEndDate": null,
+                "membershipStatus": {
+                    "statusIsActive": active,
+                    "statusDescription": if active { "Current Member" } else { "Former Member" }
+                }
+            }
+        }
+    })
+}
+
+async fn members_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    for (id, name, active) in [
+        (1, "Alice Example", true),
+        (2, "Bob Example", false),
+        (3, "Carol Example", true),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/Members/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(member(id, name, active)))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/api/Members/999"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn requests_for(server: &MockServer, id: u32) -> usize {
+    let target = format!("/api/Members/{id}");
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == target)
+        .count()
+}
+
+#[tokio::test]
+async fn mixes_cached_uncached_and_unknown_ids() {
+    let server = members_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let warm = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": [3]}),
+    )
+    .await;
+    assert_eq!(warm["result"]["structuredContent"]["resolved"], 1);
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_members_bulk",
+        json!({"mpIds": [1, 2, 3, 999, 1]}),
+    )
+    .await;
+    let result = &response["result"];
+    let payload = &result["structuredContent"];
+
+    assert_eq!(payload["resolved"], 3);
+    assert_eq!(payload["failed"], 1);
+    assert_eq!(payload["members"]["1"]["name"], "Alice Example");
+    assert_eq!(payload["members"]["1"]["party"], "Labour");
+    assert_eq!(
+        payload["members"]["1"]["constituency"],
+        "Holborn and St Pancras"
+    );
+    assert_eq!(payload["members"]["1"]["house"], "Commons");
+    assert_eq!(payload["members"]["1"]["isCurrent"], true);
+    assert_eq!(payload["members"]["2"]["isCurrent"], false);
+    assert_eq!(payload["members"]["2"]["status"], "Former Member");
+    assert!(payload["members"]["999"]["error"].as_str().is_some());
+    assert!(result.get("isError").is_none());
+    assert_eq!(result["content"].as_array().map(Vec::len), Some(1));
+
+    assert_eq!(
+        requests_for(&server, 3).await,
+        1,
+        "id 3 should come from cache"
+    );
+    assert_eq!(
+        requests_for(&server, 1).await,
+        1,
+        "duplicate ids are fetched once"
+    );
+}
+
+#[tokio::test]
+async fn rejects_empty_and_oversized_batches() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let too_many = (1..=101).collect::<Vec<u32>>();
+    for ids in [json!([]), json!(too_many), json!([0])] {
+        let response = common::call_tool(
+            &harness.service,
+            "parliament.fetch_members_bulk",
+            json!({"mpIds": ids}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32602, "ids: {ids}");
+    }
+}
@@ -0,0 +1,258 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn scrape(router: axum::Router, api_key: Option<&str>) -> (StatusCode, String) {
+    let mut request = Request::get("/api/metrics");
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert!(
+            response.headers()[header::CONTENT_TYPE]
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain; version=0.0.4")
+        );
+    }
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+/// The value of the sample line starting with `series`, if present.
+fn sample(body: &str, series: &str) -> Option<f64> {
+    body.lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .and_then(|value| value.parse().ok())
+}
+
+#[tokio::test]
+async fn requests_and_tool_calls_are_counted() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    for _ in 0..2 {
+        common::call_tool(&harness.service, "utilities.current_datetime", json!({})).await;
+    }
+    common::call_tool(
+        &harness.service,
+        "parliament.lookup_constituency_offline",
+        json!({"postcode": "ZZ99 9ZZ"}),
+    )
+    .await;
+    common::call_tool(&harness.service, "no.such_tool", json!({})).await;
+    let _ = harness
+        .service
+        .handle_jsonrpc(
+            common::request(Some(json!(5)), "made/up", None),
+            common::context(),
+        )
+        .await;
+
+    let (status, body) = scrape(harness.router(), Some("test")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let requests = |method: &str, status: &str| {
+        sample(
+            &body,
+            &format!("mcp_jsonrpc_requests_total{{method=\"{method}\",status=\"{status}\"}}"),
+        )
+    };
+    assert_eq!(requests("initialize", "ok"), Some(1.0));
+    assert_eq!(requests("tools/call", "ok"), Some(2.0));
+    assert_eq!(requests("tools/call", "error"), Some(2.0));
+    assert_eq!(requests("other", "error"), Some(1.0));
+    assert!(!body.contains("made/up"));
+
+    assert_eq!(
+        sample(
+            &body,
+            "mcp_tool_calls_total{tool=\"utilities.current_datetime\",outcome=\"success\"}"
+        ),
+        Some(2.0)
+    );
+    assert_eq!(
+        sample(
+            &body,
+            "mcp_tool_calls_total{tool=\"parliament.lookup_constituency_offline\",outcome=\"rejected\"}"
+        ),
+        Some(1.0)
+    );
+    assert!(
+        !body.contains("no.such_tool"),
+        "unknown tools get no series"
+    );
+
+    assert_eq!(
+        sample(
+            &body,
+            "mcp_tool_call_duration_seconds_count{tool=\"utilities.current_datetime\"}"
+        ),
+        Some(2.0)
+    );
+    assert_eq!(
+        sample(
+            &body,
+            "mcp_tool_call_duration_seconds_bucket{tool=\"utilities.current_datetime\",le=\"+Inf\"}"
+        ),
+        Some(2.0)
+    );
+    assert!(body.contains("# TYPE mcp_tool_call_duration_seconds histogram"));
+}
+
+#[tokio::test]
+async fn cache_hits_and_misses_are_exported() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdivisions.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    for _ in 0..3 {
+        let response = common::call_tool(
+            &harness.service,
+            "parliament.fetch_core_dataset",
+            json!({"dataset": "commonsdivisions", "enableCache": true}),
+        )
+        .await;
+        assert_eq!(
+            response["result"]["structuredContent"],
+            json!({"items": []}),
+            "response: {response}"
+        );
+    }
+
+    let (_, body) = scrape(harness.router(), Some("test")).await;
+    assert_eq!(sample(&body, "mcp_cache_misses_total"), Some(1.0));
+    assert_eq!(sample(&body, "mcp_cache_hits_total"), Some(2.0));
+    assert_eq!(sample(&body, "mcp_cache_future_stamps_total"), Some(0.0));
+}
+
+#[tokio::test]
+async fn metrics_route_requires_the_api_key() {
+    let harness = common::build_service(None, |_| {});
+
+    let (status, _) = scrape(harness.router(), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn upstream_requests_are_broken_down_by_host_and_tool() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdebates.json"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "housing", "enableCache": false}),
+    )
+    .await;
+    common::call_tool(
+        &harness.service,
+        "parliament.fetch_core_dataset",
+        json!({"dataset": "commonsdebates", "enableCache": false}),
+    )
+    .await;
+
+    let (_, body) = scrape(harness.router(), Some("test")).await;
+    let bills = "host=\"bills-api.parliament.uk\",tool=\"parliament.fetch_bills\"";
+    let lda = "host=\"lda.data.parliament.uk\",tool=\"parliament.fetch_core_dataset\"";
+    assert_eq!(
+        sample(
+            &body,
+            &format!("mcp_upstream_requests_total{{{bills},status=\"2xx\"}}")
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &body,
+            &format!("mcp_upstream_requests_total{{{lda},status=\"5xx\"}}")
+        ),
+        Some(3.0)
+    );
+    assert_eq!(
+        sample(&body, &format!("mcp_upstream_retries_total{{{lda}}}")),
+        Some(2.0)
+    );
+    assert_eq!(
+        sample(&body, &format!("mcp_upstream_retries_total{{{bills}}}")),
+        None
+    );
+    assert_eq!(
+        sample(
+            &body,
+            &format!("mcp_upstream_request_duration_seconds_count{{{lda}}}")
+        ),
+        Some(3.0)
+    );
+    assert_eq!(
+        sample(
+            &body,
+            &format!("mcp_upstream_request_duration_seconds_bucket{{{bills},le=\"+Inf\"}}")
+        ),
+        Some(1.0)
+    );
+}
+
+#[tokio::test]
+async fn research_cache_scans_are_timed() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some("admin".to_string());
+        config.research_compact_after_days = 90;
+    });
+    common::initialize(&harness.service).await;
+
+    common::call_tool_with_context(
+        &harness.service,
+        "research.list_cached",
+        json!({}),
+        common::context().with_api_key("admin"),
+    )
+    .await;
+    harness.research.compact(0).await.unwrap();
+    harness.research.compact(0).await.unwrap();
+
+    let (_, body) = scrape(harness.router(), Some("test")).await;
+    assert!(body.contains("# TYPE mcp_cache_scan_duration_seconds histogram"));
+    assert_eq!(
+        sample(
+            &body,
+            "mcp_cache_scan_duration_seconds_count{scan=\"list_cached\"}"
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &body,
+            "mcp_cache_scan_duration_seconds_count{scan=\"compact\"}"
+        ),
+        Some(2.0)
+    );
+}
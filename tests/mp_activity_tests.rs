@@ -0,0 +1,261 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{any, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_mp_activity";
+const SPOKEN: &str = "/search/contributions/Spoken.json";
+const WRITTEN_QUESTIONS: &str = "/api/writtenquestions/questions";
+const EDMS: &str = "/EarlyDayMotions/list";
+
+fn spoken_contributions() -> Value {
+    json!({
+        "Results": [
+            {
+                "MemberId": 4514,
+                "ContributionExtId": "A1B2C3",
+                "ContributionText": "Will the Minister set out <b>when</b> the   scheme opens?",
+                "DebateSection": "Renters (Reform) Bill",
+                "DebateSectionExtId": "D4E5F6",
+                "SittingDate": "2024-04-24T00:00:00",
+                "House": "Commons"
+            },
+            {
+                "MemberId": 4514,
+                "ContributionExtId": "G7H8I9",
+                "ContributionText": "I welcome the statement.",
+                "DebateSection": "Flooding",
+                "DebateSectionExtId": "J1K2L3",
+                "SittingDate": "2024-03-11T00:00:00",
+                "House": "Commons"
+            }
+        ],
+        "TotalResultCount": 2
+    })
+}
+
+fn written_questions() -> Value {
+    json!({
+        "results": [
+            {
+                "value": {
+                    "id": 1700001,
+                    "askingMemberId": 4514,
+                    "dateTabled": "2024-05-02T00:00:00",
+                    "uin": "24789",
+                    "heading": "Social Rented Housing: Construction",
+                    "answeringBodyName": "Department for Levelling Up, Housing and Communities",
+                    "questionText": "To ask the Secretary of State how many homes were started last year."
+                },
+                "links": []
+            }
+        ],
+        "totalResults": 1
+    })
+}
+
+fn early_day_motions() -> Value {
+    json!({
+        "Response": [
+            {
+                "Id": 62001,
+                "Title": "Community pharmacies",
+                "MotionText": "That this House recognises the work of community pharmacies.",
+                "DateTabled": "2024-02-05T00:00:00",
+                "Sponsors": [
+                    {"MemberId": 172, "CreatedWhen": "2024-02-05T10:00:00"},
+                    {"MemberId": 4514, "CreatedWhen": "2024-04-30T14:12:00"}
+                ]
+            }
+        ],
+        "StatusCode": 200,
+        "Success": true
+    })
+}
+
+async fn upstream(failing: Option<&str>) -> MockServer {
+    let server = MockServer::start().await;
+    for (route, body) in [
+        (SPOKEN, spoken_contributions()),
+        (WRITTEN_QUESTIONS, written_questions()),
+        (EDMS, early_day_motions()),
+    ] {
+        let response = if failing == Some(route) {
+            ResponseTemplate::new(500)
+        } else {
+            ResponseTemplate::new(200).set_body_json(body)
+        };
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+async fn requests_to(server: &MockServer, route: &str) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == route)
+        .count()
+}
+
+#[tokio::test]
+async fn activity_from_every_source_is_merged_newest_first() {
+    let server = upstream(None).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 4514})).await;
+
+    assert!(result.get("note").is_none(), "{result}");
+    assert!(result.get("unavailableSources").is_none(), "{result}");
+    let activities = result["activities"].as_array().unwrap();
+    let summary: Vec<(&str, &str)> = activities
+        .iter()
+        .map(|entry| {
+            (
+                entry["date"].as_str().unwrap(),
+                entry["type"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("2024-05-02", "Written question"),
+            ("2024-04-30", "EDM"),
+            ("2024-04-24", "Spoken contribution"),
+            ("2024-03-11", "Spoken contribution"),
+        ]
+    );
+
+    assert_eq!(
+        activities[0]["url"],
+        "https://questions-statements.parliament.uk/written-questions/detail/2024-05-02/24789"
+    );
+    assert_eq!(
+        activities[0]["description"],
+        "To Department for Levelling Up, Housing and Communities: To ask the Secretary of State how many homes were started last year."
+    );
+    // Dated when this member signed, not when the motion was tabled.
+    assert_eq!(activities[1]["title"], "Community pharmacies");
+    assert_eq!(
+        activities[1]["url"],
+        "https://edm.parliament.uk/early-day-motion/62001"
+    );
+    assert_eq!(
+        activities[2]["url"],
+        "https://hansard.parliament.uk/Commons/2024-04-24/debates/D4E5F6/RentersReformBill#contribution-A1B2C3"
+    );
+    assert_eq!(
+        activities[2]["description"],
+        "Will the Minister set out when the scheme opens?"
+    );
+
+    let requests = server.received_requests().await.unwrap();
+    let spoken = requests
+        .iter()
+        .find(|request| request.url.path() == SPOKEN)
+        .unwrap();
+    assert!(
+        spoken
+            .url
+            .query_pairs()
+            .any(|(name, value)| name == "queryParameters.memberId" && value == "4514")
+    );
+}
+
+#[tokio::test]
+async fn the_limit_applies_to_the_merged_list() {
+    let server = upstream(None).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 4514, "limit": 2})).await;
+
+    let types: Vec<&str> = result["activities"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(types, ["Written question", "EDM"]);
+}
+
+#[tokio::test]
+async fn complete_activity_is_cached_per_member() {
+    let server = upstream(None).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let first =
+        common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 4514, "limit": 3})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"mpId": 4514, "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first["activities"].as_array().unwrap().len(), 3);
+    // The cache keeps the full list, whatever limit filled it.
+    assert_eq!(cached["activities"].as_array().unwrap().len(), 4);
+    assert_eq!(requests_to(&server, SPOKEN).await, 1);
+}
+
+#[tokio::test]
+async fn a_failing_source_is_reported_and_not_cached() {
+    let server = upstream(Some(WRITTEN_QUESTIONS)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({"mpId": 4514})).await;
+
+    assert_eq!(result["unavailableSources"], json!(["writtenQuestions"]));
+    assert!(
+        result["note"]
+            .as_str()
+            .unwrap()
+            .contains("written questions"),
+        "{result}"
+    );
+    let activities = result["activities"].as_array().unwrap();
+    assert_eq!(activities.len(), 3);
+    assert!(
+        activities
+            .iter()
+            .all(|entry| entry["type"] != "Written question")
+    );
+
+    let response = common::call_tool(
+        &harness.service,
+        TOOL,
+        json!({"mpId": 4514, "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(
+        response["result"]["structuredContent"]["cacheMiss"], true,
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn the_call_fails_only_when_every_source_does() {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response = common::call_tool(&harness.service, TOOL, json!({"mpId": 4514})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
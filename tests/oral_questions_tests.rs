@@ -0,0 +1,218 @@
+use chrono::{Days, NaiveDate, Utc};
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_oral_questions";
+const ORAL_QUESTIONS: &str = "/oralquestions/list";
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+fn questions(upcoming: NaiveDate) -> Value {
+    json!({
+        "PagingInfo": {"Skip": 0, "Take": 20, "Total": 2},
+        "Response": [
+            {
+                "Id": 101,
+                "QuestionType": 1,
+                "QuestionText": "What steps his Department is taking to <i>reduce</i> waiting lists.",
+                "Number": 3,
+                "TabledWhen": "2024-02-26T10:30:00",
+                "AnsweringWhen": "2024-03-04T00:00:00",
+                "AnsweringBodyId": 17,
+                "AnsweringBody": "Department of Health and Social Care",
+                "AskingMemberId": 4514,
+                "AskingMember": {"Name": "Jane Example", "Party": "Labour", "Constituency": "Anytown"}
+            },
+            {
+                "Id": 102,
+                "QuestionType": "Topical",
+                "QuestionText": "If he will make a statement on his departmental responsibilities.",
+                "TabledWhen": "2024-02-27T09:00:00",
+                "AnsweringWhen": format!("{upcoming}T00:00:00"),
+                "AnsweringBody": "Department of Health and Social Care",
+                "AskingMemberId": 172
+            }
+        ]
+    })
+}
+
+async fn upstream() -> MockServer {
+    let server = MockServer::start().await;
+    let upcoming = today().checked_add_days(Days::new(7)).unwrap();
+    Mock::given(method("GET"))
+        .and(path(ORAL_QUESTIONS))
+        .respond_with(ResponseTemplate::new(200).set_body_json(questions(upcoming)))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn query_of_first_request(server: &MockServer) -> Vec<(String, String)> {
+    let requests = server.received_requests().await.unwrap();
+    requests[0]
+        .url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect()
+}
+
+fn param<'a>(query: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    query
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[tokio::test]
+async fn questions_carry_member_department_and_status() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result = common::call_tool_ok(&harness.service, TOOL, json!({})).await;
+
+    assert_eq!(result["totalResults"], 2);
+    assert_eq!(
+        result["questions"][0],
+        json!({
+            "id": 101,
+            "questionType": "Substantive",
+            "questionText": "What steps his Department is taking to reduce waiting lists.",
+            "askingMemberId": 4514,
+            "askingMemberName": "Jane Example",
+            "askingMemberParty": "Labour",
+            "answeringBody": "Department of Health and Social Care",
+            "tabledDate": "2024-02-26",
+            "answeringDate": "2024-03-04",
+            "status": "answered"
+        })
+    );
+    assert_eq!(result["questions"][1]["questionType"], "Topical");
+    assert_eq!(result["questions"][1]["status"], "tabled");
+}
+
+#[tokio::test]
+async fn filters_are_sent_upstream() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({
+            "answeringDateFrom": "2024-01-01",
+            "answeringDateTo": "2024-06-30",
+            "answeringBody": 17,
+            "askingMemberId": "https://members.parliament.uk/member/4514",
+            "limit": 5
+        }),
+    )
+    .await;
+
+    let query = query_of_first_request(&server).await;
+    assert_eq!(
+        param(&query, "parameters.answeringDateStart"),
+        Some("2024-01-01")
+    );
+    assert_eq!(
+        param(&query, "parameters.answeringDateEnd"),
+        Some("2024-06-30")
+    );
+    assert_eq!(param(&query, "parameters.answeringBodyIds"), Some("17"));
+    assert_eq!(param(&query, "parameters.askingMemberIds"), Some("4514"));
+    assert_eq!(param(&query, "parameters.take"), Some("5"));
+}
+
+#[tokio::test]
+async fn tabled_questions_start_today() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"status": "tabled", "answeringDateFrom": "2024-01-01"}),
+    )
+    .await;
+
+    let query = query_of_first_request(&server).await;
+    let today = today().to_string();
+    assert_eq!(
+        param(&query, "parameters.answeringDateStart"),
+        Some(today.as_str())
+    );
+    assert_eq!(param(&query, "parameters.answeringDateEnd"), None);
+}
+
+#[tokio::test]
+async fn answered_questions_end_yesterday() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    common::call_tool_ok(&harness.service, TOOL, json!({"status": "answered"})).await;
+
+    let query = query_of_first_request(&server).await;
+    let yesterday = today().pred_opt().unwrap().to_string();
+    assert_eq!(
+        param(&query, "parameters.answeringDateEnd"),
+        Some(yesterday.as_str())
+    );
+
+    // A range wholly in the future holds no answered questions; nothing is fetched.
+    let next_year = today()
+        .checked_add_days(Days::new(365))
+        .unwrap()
+        .to_string();
+    let result = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"status": "answered", "answeringDateFrom": next_year}),
+    )
+    .await;
+    assert_eq!(result["questions"], json!([]));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn results_are_cached_per_filter() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    let first = common::call_tool_ok(&harness.service, TOOL, json!({"askingMemberId": 4514})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"askingMemberId": 4514, "cacheOnly": true}),
+    )
+    .await;
+    let other = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"askingMemberId": 4514, "status": "tabled", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(other["cacheMiss"], true, "{other}");
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn malformed_filters_are_rejected() {
+    let server = upstream().await;
+    let harness = common::upstream_harness(&server).await;
+
+    for arguments in [
+        json!({"answeringDateFrom": "next week"}),
+        json!({"status": "withdrawn"}),
+        json!({"answeringBody": "health"}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,583 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::{
+    ActivitySource, BillDetail, BillDivision, BillProgressStage, BillPublication, BillSponsor,
+    BillStageDivisions, Committee, CommitteeInquiry, CommitteeMember, ConstituencyLookupResult,
+    DivisionDetail, DivisionMember, DivisionOutcome, EarlyDayMotion, EdmMember, EdmSignatory,
+    FetchBillDivisionsResult, FetchCommitteesResult, FetchEdmsResult, FetchMembersBulkResult,
+    FetchOralQuestionsResult, FetchStatutoryInstrumentsResult, FetchWrittenQuestionsResult,
+    HansardContribution, LegislationText, MemberLookupEntry, MemberRole, MemberRoleKind,
+    MemberRolesResult, MemberSummary, MpActivityEntry, MpActivityResult, OralQuestion,
+    OralQuestionStatus, PublicationLink, SearchHansardResult, SiProcedure, SiStatus,
+    StatutoryInstrument, UkLawResult, WrittenQuestion,
+};
+use mp_writer_mcp_server::features::research::{
+    BillSummaryDto, PartyBreakdownDto, ResearchProvenance, ResearchResponseDto, ResearchStatus,
+    SearchTermOutcome, SearchTermReport, SpeechSummaryDto, StateOfPartiesDto, VoteSummaryDto,
+};
+use mp_writer_mcp_server::features::utilities::{CurrentDatetimeDto, VerifySignatureDto};
+
+mod common;
+
+/// Upstream that answers every request with a JSON array, which the bills tool declares
+/// as an object in its outputSchema.
+async fn drifted_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"billId": 1}])))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn call_bills(harness: &common::TestHarness) -> Value {
+    common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "climate", "enableCache": false}),
+    )
+    .await
+}
+
+#[test]
+fn malformed_research_payload_fails_validation() {
+    let harness = common::build_service(None, |_| {});
+    let payload = json!({
+        "topic": "climate",
+        "summary": 42,
+        "bills": "none",
+        "cached": false
+    });
+
+    let message = harness
+        .service
+        .validate_tool_output("research.run", &payload)
+        .expect_err("payload should not match schema");
+    assert!(message.contains("42"), "unexpected message: {message}");
+    assert!(
+        harness
+            .service
+            .validate_tool_output("no.such_tool", &payload)
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn conforming_output_has_no_advisory() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response =
+        common::call_tool(&harness.service, "utilities.current_datetime", json!({})).await;
+    let result = &response["result"];
+
+    assert!(
+        harness
+            .service
+            .validate_tool_output("utilities.current_datetime", &result["structuredContent"])
+            .is_ok()
+    );
+    assert_eq!(result["content"].as_array().map(Vec::len), Some(1));
+}
+
+#[tokio::test]
+async fn schema_drift_adds_an_advisory_note() {
+    let server = drifted_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = call_bills(&harness).await;
+    let result = &response["result"];
+    let content = result["content"].as_array().expect("content");
+
+    assert_eq!(content.len(), 2);
+    let note = content[1]["text"].as_str().expect("advisory text");
+    assert!(note.starts_with("Advisory:"), "unexpected note: {note}");
+    assert!(note.contains("parliament.fetch_bills"));
+    assert_eq!(result["structuredContent"], json!([{"billId": 1}]));
+    assert!(result.get("isError").is_none());
+}
+
+#[tokio::test]
+async fn strict_mode_rejects_schema_drift() {
+    let server = drifted_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.strict_output_validation = true;
+    });
+    common::initialize(&harness.service).await;
+
+    let response = call_bills(&harness).await;
+
+    assert!(response.get("result").is_none());
+    assert_eq!(response["error"]["code"], -32000);
+    let message = response["error"]["message"].as_str().expect("message");
+    assert!(
+        message.contains("outputSchema"),
+        "unexpected message: {message}"
+    );
+}
+
+fn assert_matches_schema<T: serde::Serialize>(tool: &str, example: &T) {
+    let harness = common::build_service(None, |_| {});
+    let payload = serde_json::to_value(example).expect("serialise example");
+    if let Err(message) = harness.service.validate_tool_output(tool, &payload) {
+        panic!("{tool} example does not match its generated schema: {message}\n{payload:#}");
+    }
+}
+
+#[test]
+fn generated_schemas_accept_their_dtos() {
+    let division = BillDivision {
+        house: "Commons".to_string(),
+        division_id: 1700,
+        number: None,
+        title: "Housing Bill: Second Reading".to_string(),
+        date: "2024-03-01".to_string(),
+        ayes: 300,
+        noes: 200,
+        result: "agreed".to_string(),
+        margin: 100,
+        outcome: DivisionOutcome::GovernmentWin,
+        link: "https://votes.parliament.uk/votes/commons/division/1700".to_string(),
+        similarity: 1.0,
+        low_confidence: false,
+    };
+    assert_matches_schema(
+        "parliament.fetch_bill_divisions",
+        &FetchBillDivisionsResult {
+            bill_id: 3400,
+            title: "Housing Bill".to_string(),
+            link: "https://bills.parliament.uk/bills/3400".to_string(),
+            stages: vec![BillStageDivisions {
+                stage: "Second reading".to_string(),
+                house: "Commons".to_string(),
+                dates: vec!["2024-03-01".to_string()],
+                divisions: vec![division],
+            }],
+            total_divisions: 1,
+            low_confidence_count: 0,
+            truncated_by_budget: false,
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_members_bulk",
+        &FetchMembersBulkResult {
+            members: BTreeMap::from([
+                (
+                    172,
+                    MemberLookupEntry::Found(MemberSummary {
+                        id: 172,
+                        name: "Example Member".to_string(),
+                        party: None,
+                        constituency: Some("Somewhere".to_string()),
+                        house: Some("Commons".to_string()),
+                        is_current: true,
+                        status: None,
+                    }),
+                ),
+                (
+                    9999,
+                    MemberLookupEntry::Failed {
+                        error: "not found".to_string(),
+                    },
+                ),
+            ]),
+            resolved: 1,
+            failed: 1,
+            truncated_by_budget: false,
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_mp_activity",
+        &MpActivityResult {
+            activities: vec![MpActivityEntry {
+                id: "contribution-1".to_string(),
+                date: "2024-03-01".to_string(),
+                activity_type: "Spoken contribution".to_string(),
+                title: "Housing".to_string(),
+                description: "Spoke in the debate".to_string(),
+                url: None,
+            }],
+            unavailable_sources: vec![ActivitySource::EarlyDayMotions],
+            note: Some("Could not fetch early day motions from Parliament".to_string()),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.search_hansard",
+        &SearchHansardResult {
+            results: vec![HansardContribution {
+                id: "A1B2C3".to_string(),
+                title: "Renters (Reform) Bill".to_string(),
+                sitting_date: "2024-04-24".to_string(),
+                house: "Commons".to_string(),
+                section: None,
+                member_name: Some("Jane Example".to_string()),
+                snippet: "Will the Minister set out when the scheme opens?".to_string(),
+                url: "https://hansard.parliament.uk/Commons/2024-04-24".to_string(),
+            }],
+            total_results: Some(1),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_committees",
+        &FetchCommitteesResult {
+            committees: vec![Committee {
+                id: 378,
+                name: "Science, Innovation and Technology Committee".to_string(),
+                house: Some("Commons".to_string()),
+                category: None,
+                url: "https://committees.parliament.uk/committee/378/".to_string(),
+                members: Some(vec![CommitteeMember {
+                    member_id: None,
+                    name: "Professor Lay Adviser".to_string(),
+                    party: None,
+                    role: Some("Lay Member".to_string()),
+                }]),
+                inquiries: Some(vec![CommitteeInquiry {
+                    id: 8102,
+                    title: "Online safety: the first year".to_string(),
+                    business_type: Some("Inquiry".to_string()),
+                    status: "open".to_string(),
+                    open_date: Some("2024-01-15".to_string()),
+                    close_date: None,
+                    url: "https://committees.parliament.uk/work/8102/".to_string(),
+                }]),
+            }],
+            total_results: Some(1),
+            note: None,
+        },
+    );
+
+    let committee_stage = BillProgressStage {
+        stage: "Committee stage".to_string(),
+        abbreviation: Some("CS".to_string()),
+        house: Some("Commons".to_string()),
+        date: Some("2024-11-05".to_string()),
+        sittings: 2,
+        explanation: None,
+    };
+    assert_matches_schema(
+        "parliament.fetch_bill_detail",
+        &BillDetail {
+            bill_id: 3764,
+            short_title: "Renters' Rights Bill".to_string(),
+            long_title: None,
+            current_house: Some("Commons".to_string()),
+            current_stage: Some(committee_stage.clone()),
+            is_act: false,
+            withdrawn: false,
+            defeated: false,
+            sponsors: vec![BillSponsor {
+                member_id: None,
+                name: None,
+                party: None,
+                organisation: Some("Ministry of Housing".to_string()),
+            }],
+            stages: vec![committee_stage],
+            publications: vec![BillPublication {
+                title: "Bill 12 2024-25 (as introduced)".to_string(),
+                publication_type: Some("Bill".to_string()),
+                house: None,
+                date: Some("2024-09-11".to_string()),
+                links: vec![PublicationLink {
+                    title: None,
+                    url: "https://bills-api.parliament.uk/api/v1/Publications/60001/Documents/70001/Download".to_string(),
+                    content_type: Some("application/pdf".to_string()),
+                }],
+            }],
+            link: "https://bills.parliament.uk/bills/3764".to_string(),
+            note: Some("Could not fetch the bill's publications from Parliament.".to_string()),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_member_roles",
+        &MemberRolesResult {
+            mp_id: 4514,
+            roles: vec![MemberRole {
+                kind: MemberRoleKind::Government,
+                name: "Minister of State".to_string(),
+                organisation: Some("Department of Health and Social Care".to_string()),
+                start_date: Some("2024-07-06".to_string()),
+                end_date: None,
+                current: true,
+            }],
+            note: Some("Could not fetch committee memberships from Parliament.".to_string()),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_legislation_text",
+        &LegislationText {
+            title: Some("Climate Change Act 2008".to_string()),
+            section: Some("1".to_string()),
+            heading: Some("The target for 2050".to_string()),
+            text: "(1) It is the duty of the Secretary of State to ensure that the net UK carbon account for the year 2050 is at least 100% lower than the 1990 baseline.".to_string(),
+            truncated: false,
+            extent: None,
+            amendments: vec!["Words in s. 1(1) substituted (27.6.2019) by S.I. 2019/1056, arts. 1, 2(2)".to_string()],
+            url: "https://www.legislation.gov.uk/ukpga/2008/27/section/1".to_string(),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_statutory_instruments",
+        &FetchStatutoryInstrumentsResult {
+            instruments: vec![StatutoryInstrument {
+                id: "aB3cD4eF".to_string(),
+                name: "The Climate Change (Targeted Greenhouse Gases) Order 2025".to_string(),
+                number: None,
+                department: Some("Department for Energy Security and Net Zero".to_string()),
+                procedure: Some(SiProcedure::Affirmative),
+                procedure_name: Some("Draft affirmative".to_string()),
+                laid_date: Some("2025-03-10".to_string()),
+                made_date: None,
+                coming_into_force_date: None,
+                status: SiStatus::Laid,
+                url: "https://statutoryinstruments.parliament.uk/instrument/aB3cD4eF".to_string(),
+                legislation_url: None,
+            }],
+            note: Some("Could not fetch SI numbers from legislation.gov.uk.".to_string()),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_division_detail",
+        &DivisionDetail {
+            division_id: 1732,
+            house: "Commons".to_string(),
+            number: Some(598),
+            title: "Renters (Reform) Bill: Report Stage: New Clause 1".to_string(),
+            date: "2024-03-12".to_string(),
+            ayes: 180,
+            noes: 287,
+            result: "negatived".to_string(),
+            aye_members: vec![DivisionMember {
+                member_id: 4514,
+                name: "Jane Example".to_string(),
+                party: Some("Labour".to_string()),
+            }],
+            no_members: vec![DivisionMember {
+                member_id: 4096,
+                name: "Michael Gove".to_string(),
+                party: None,
+            }],
+            aye_tellers: Vec::new(),
+            no_tellers: Vec::new(),
+            link: "https://votes.parliament.uk/votes/commons/division/1732".to_string(),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_edms",
+        &FetchEdmsResult {
+            motions: vec![EarlyDayMotion {
+                id: 62001,
+                number: Some("512".to_string()),
+                title: "Community pharmacies".to_string(),
+                motion_text: "That this House recognises community pharmacies.".to_string(),
+                primary_sponsor: Some(EdmMember {
+                    member_id: Some(4514),
+                    name: "Jane Example".to_string(),
+                    party: None,
+                }),
+                signature_count: Some(23),
+                date_tabled: "2024-05-20".to_string(),
+                status: Some("Published".to_string()),
+                url: "https://edm.parliament.uk/early-day-motion/62001".to_string(),
+                signatories: Some(vec![EdmSignatory {
+                    member_id: 4514,
+                    name: Some("Jane Example".to_string()),
+                    party: Some("Labour".to_string()),
+                    signed_date: None,
+                }]),
+            }],
+            total_results: None,
+            note: Some("Signatories are listed for the first 5 motions only.".to_string()),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_oral_questions",
+        &FetchOralQuestionsResult {
+            questions: vec![OralQuestion {
+                id: 101,
+                question_type: Some("Substantive".to_string()),
+                question_text: "What steps his Department is taking to reduce waiting lists."
+                    .to_string(),
+                asking_member_id: Some(4514),
+                asking_member_name: Some("Jane Example".to_string()),
+                asking_member_party: None,
+                answering_body: Some("Department of Health and Social Care".to_string()),
+                tabled_date: None,
+                answering_date: "2024-03-04".to_string(),
+                status: OralQuestionStatus::Answered,
+            }],
+            total_results: Some(1),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.fetch_written_questions",
+        &FetchWrittenQuestionsResult {
+            questions: vec![WrittenQuestion {
+                id: 1700001,
+                uin: Some("24789".to_string()),
+                heading: Some("Social Rented Housing: Construction".to_string()),
+                question_text: "To ask the Secretary of State how many homes were started."
+                    .to_string(),
+                date_tabled: "2024-05-02".to_string(),
+                asking_member_id: Some(4514),
+                asking_member_name: None,
+                answering_body: Some(
+                    "Ministry of Housing, Communities and Local Government".to_string(),
+                ),
+                answer_text: None,
+                date_answered: None,
+                url: None,
+            }],
+            total_results: Some(1),
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.lookup_constituency_offline",
+        &ConstituencyLookupResult {
+            constituency_code: Some("E14000639".to_string()),
+            constituency_name: Some("Cities of London and Westminster".to_string()),
+            mp_id: None,
+            mp_name: None,
+        },
+    );
+
+    assert_matches_schema(
+        "parliament.search_uk_law",
+        &vec![UkLawResult {
+            title: "Housing Act 2004".to_string(),
+            year: Some("2004".to_string()),
+            legislation_type: "primary".to_string(),
+            is_in_force: true,
+            url: "https://www.legislation.gov.uk/ukpga/2004/34".to_string(),
+            summary: None,
+            last_updated: None,
+        }],
+    );
+
+    assert_matches_schema(
+        "research.run",
+        &ResearchResponseDto {
+            summary: "Housing research".to_string(),
+            status: ResearchStatus::Partial,
+            bills: vec![BillSummaryDto {
+                title: "Housing Bill".to_string(),
+                stage: Some("Second reading".to_string()),
+                stage_code: Some("2R".to_string()),
+                stage_explanation: None,
+                last_update: None,
+                link: None,
+            }],
+            debates: Vec::new(),
+            legislation: Vec::new(),
+            votes: vec![VoteSummaryDto {
+                division_number: Some("12".to_string()),
+                title: "Housing Bill".to_string(),
+                date: None,
+                ayes: Some(300),
+                noes: Some(200),
+                result: None,
+                margin: Some(100),
+                outcome: DivisionOutcome::Unknown,
+                link: None,
+                parties: Vec::new(),
+            }],
+            mp_speeches: vec![SpeechSummaryDto {
+                member_name: None,
+                date: None,
+                excerpt: Some("...".to_string()),
+                source: None,
+            }],
+            state_of_parties: Some(StateOfPartiesDto {
+                total_seats: Some(650),
+                last_updated: None,
+                parties: vec![PartyBreakdownDto {
+                    name: "Independent".to_string(),
+                    seats: Some(10),
+                }],
+            }),
+            advisories: vec!["Votes service was unavailable.".to_string()],
+            cached: true,
+            cached_at: Some("2024-03-01T00:00:00Z".to_string()),
+            age_secs: Some(30),
+            stale: false,
+            provenance: ResearchProvenance {
+                search_terms_used: BTreeMap::from([(
+                    "bills".to_string(),
+                    vec![SearchTermReport {
+                        term: "housing".to_string(),
+                        outcome: SearchTermOutcome::SkippedOverBudget,
+                    }],
+                )]),
+            },
+            truncated_by_budget: false,
+        },
+    );
+
+    assert_matches_schema(
+        "utilities.current_datetime",
+        &CurrentDatetimeDto {
+            utc: "2024-03-01T00:00:00Z".to_string(),
+            local: "2024-03-01T00:00:00+00:00".to_string(),
+        },
+    );
+    assert_matches_schema(
+        "utilities.verify_signature",
+        &VerifySignatureDto {
+            valid: false,
+            reason: Some("payload was modified".to_string()),
+            payload_sha256: "00".repeat(32),
+            public_key: "11".repeat(32),
+        },
+    );
+}
+
+#[test]
+fn generated_schemas_reject_drifted_payloads() {
+    let harness = common::build_service(None, |_| {});
+
+    // Field names come from the DTO's serde attributes.
+    let renamed = json!([{
+        "title": "Housing Act 2004",
+        "legislation_type": "primary",
+        "isInForce": true,
+        "url": "https://www.legislation.gov.uk/ukpga/2004/34"
+    }]);
+    assert!(
+        harness
+            .service
+            .validate_tool_output("parliament.search_uk_law", &renamed)
+            .is_err()
+    );
+
+    let bad_status = json!({
+        "summary": "",
+        "status": "unknown",
+        "bills": [], "debates": [], "legislation": [], "votes": [], "mp_speeches": [],
+        "state_of_parties": null
+    });
+    let message = harness
+        .service
+        .validate_tool_output("research.run", &bad_status)
+        .expect_err("unknown status should fail");
+    assert!(message.contains("unknown"), "unexpected message: {message}");
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use axum::middleware;
+use axum::routing::get;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use tower_http::catch_panic::CatchPanicLayer;
+
+use mp_writer_mcp_server::features::research::ParliamentDataSource;
+use mp_writer_mcp_server::server::{PanicResponder, assign_request_id};
+
+mod common;
+
+async fn panic_count(harness: &common::TestHarness, scope: &str) -> Option<u64> {
+    let response = harness
+        .router()
+        .oneshot(
+            Request::get("/api/metrics")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let series = format!("mcp_panics_total{{scope=\"{scope}\"}} ");
+    String::from_utf8(body.to_vec())
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix(&series))
+        .map(|count| count.parse().unwrap())
+}
+
+#[tokio::test]
+async fn a_panicking_tool_fails_only_its_own_call() {
+    let mock = Arc::new(common::MockParliamentDataSource::new().panicking(&["bills"]));
+    let harness = common::build_service(Some(mock as Arc<dyn ParliamentDataSource>), |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.suggest_keywords",
+        json!({"topic": "climate"}),
+    )
+    .await;
+    let result = &response["result"];
+    assert_eq!(result["isError"], true, "response: {response}");
+    // The panic message stays in the server log.
+    assert_eq!(
+        result["content"][0]["text"],
+        "Internal error while executing research.suggest_keywords"
+    );
+
+    // The session and server carry on.
+    let response =
+        common::call_tool(&harness.service, "utilities.current_datetime", json!({})).await;
+    assert!(response["result"]["isError"].is_null(), "{response}");
+    assert_eq!(panic_count(&harness, "tool").await, Some(1));
+    assert_eq!(panic_count(&harness, "request").await, None);
+}
+
+#[tokio::test]
+async fn a_panicking_handler_is_answered_with_a_json_rpc_error() {
+    let harness = common::build_service(None, |_| {});
+    let router = Router::new()
+        .route(
+            "/api/boom",
+            get(|| async {
+                let dates: Vec<&str> = Vec::new();
+                dates[0].to_string()
+            }),
+        )
+        .layer(CatchPanicLayer::custom(PanicResponder::new(
+            harness.service.clone(),
+        )))
+        .layer(middleware::from_fn(assign_request_id));
+
+    let response = router
+        .oneshot(
+            Request::get("/api/boom")
+                .header("X-Request-Id", "trace-panic")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.headers()["x-request-id"], "trace-panic");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": {
+                "code": -32000,
+                "message": "internal server error",
+                "data": {
+                    "requestId": "trace-panic",
+                    "build": harness.service.build_info(),
+                }
+            }
+        })
+    );
+    assert_eq!(panic_count(&harness, "request").await, Some(1));
+}
@@ -0,0 +1,276 @@
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::dto::MpVoteRecord;
+use mp_writer_mcp_server::features::parliament::policy_areas::{
+    TOP_DIVISIONS_PER_AREA, VotePosition,
+};
+use mp_writer_mcp_server::features::parliament::{PolicyAreaClassifier, aggregate_by_policy_area};
+
+mod common;
+
+fn vote(id: &str, title: &str, date: &str, vote: Option<&str>) -> MpVoteRecord {
+    MpVoteRecord {
+        division_id: Some(id.to_string()),
+        title: Some(title.to_string()),
+        date: Some(date.to_string()),
+        vote: vote.map(str::to_string),
+        majority: None,
+        teller: false,
+    }
+}
+
+fn classify(classifier: &PolicyAreaClassifier, title: &str) -> Option<String> {
+    classifier
+        .classify(&vote("d1", title, "2024-01-01", Some("Aye")))
+        .map(|area| area.id.clone())
+}
+
+#[test]
+fn builtin_mapping_covers_starter_areas() {
+    let classifier = PolicyAreaClassifier::builtin();
+    let ids: Vec<&str> = classifier
+        .areas()
+        .iter()
+        .map(|area| area.id.as_str())
+        .collect();
+
+    assert_eq!(
+        ids,
+        [
+            "welfare",
+            "immigration",
+            "environment",
+            "defence",
+            "housing"
+        ]
+    );
+    assert_eq!(
+        classify(
+            &classifier,
+            "Universal Credit (Removal of Two Child Limit) Bill"
+        )
+        .as_deref(),
+        Some("welfare")
+    );
+    assert_eq!(
+        classify(
+            &classifier,
+            "Safety of Rwanda (Asylum and Immigration) Bill: Third Reading"
+        )
+        .as_deref(),
+        Some("immigration")
+    );
+    assert_eq!(
+        classify(&classifier, "Renters (Reform) Bill: Report Stage").as_deref(),
+        Some("housing")
+    );
+    assert_eq!(classify(&classifier, "Finance Bill: Second Reading"), None);
+}
+
+#[test]
+fn keywords_match_whole_words_case_insensitively() {
+    let classifier = PolicyAreaClassifier::from_json(
+        r#"{"areas": [{"id": "defence", "label": "Defence", "keywords": ["Arms", "armed forces"]}]}"#,
+    )
+    .expect("valid mapping");
+
+    assert_eq!(
+        classify(&classifier, "ARMS Trade Bill").as_deref(),
+        Some("defence")
+    );
+    assert_eq!(
+        classify(&classifier, "Armed Forces Bill: Committee").as_deref(),
+        Some("defence")
+    );
+    assert_eq!(classify(&classifier, "Farms and Agriculture Bill"), None);
+    assert_eq!(classify(&classifier, "Disarmament motion"), None);
+}
+
+#[test]
+fn first_matching_area_wins_and_bill_ids_match() {
+    let classifier = PolicyAreaClassifier::from_json(
+        r#"{"areas": [
+            {"id": "housing", "label": "Housing", "keywords": ["housing"]},
+            {"id": "welfare", "label": "Welfare", "keywords": ["benefit", "housing"]},
+            {"id": "finance", "label": "Finance", "billIds": ["DIV-42"]}
+        ]}"#,
+    )
+    .expect("valid mapping");
+
+    assert_eq!(
+        classify(&classifier, "Housing Benefit Regulations").as_deref(),
+        Some("housing")
+    );
+
+    let by_id = vote("div-42", "Unrelated title", "2024-01-01", Some("No"));
+    assert_eq!(
+        classifier.classify(&by_id).map(|area| area.id.as_str()),
+        Some("finance")
+    );
+}
+
+#[test]
+fn invalid_mappings_are_rejected() {
+    for contents in [
+        "not json",
+        r#"{"areas": [{"id": "other", "label": "Other"}]}"#,
+        r#"{"areas": [{"id": " ", "label": "Blank"}]}"#,
+        r#"{"areas": [{"id": "a", "label": "A"}, {"id": "a", "label": "Again"}]}"#,
+    ] {
+        assert!(
+            PolicyAreaClassifier::from_json(contents).is_err(),
+            "{contents} should be rejected"
+        );
+    }
+    assert!(PolicyAreaClassifier::from_file("/no/such/policy_areas.json").is_err());
+}
+
+#[test]
+fn vote_positions_cover_both_houses() {
+    let position = |value: Option<&str>| VotePosition::of(&vote("d", "t", "2024-01-01", value));
+
+    assert_eq!(position(Some("Aye")), VotePosition::For);
+    assert_eq!(position(Some("content")), VotePosition::For);
+    assert_eq!(position(Some("No")), VotePosition::Against);
+    assert_eq!(position(Some("Not Content")), VotePosition::Against);
+    assert_eq!(position(Some("Did not vote")), VotePosition::Absent);
+    assert_eq!(position(None), VotePosition::Absent);
+}
+
+#[test]
+fn aggregation_tallies_areas_and_other_bucket() {
+    let classifier = PolicyAreaClassifier::builtin();
+    let votes = vec![
+        vote("1", "Social Security Bill", "2024-03-01", Some("Aye")),
+        vote("2", "Pensions Bill", "2024-02-01", Some("No")),
+        vote("3", "Benefits Reform motion", "2024-01-01", None),
+        vote("4", "Armed Forces Bill", "2024-01-05", Some("No")),
+        vote("5", "Finance Bill", "2024-01-06", Some("Aye")),
+    ];
+
+    let summary = aggregate_by_policy_area(votes, &classifier);
+
+    assert_eq!(summary.total_divisions, 5);
+    assert_eq!(summary.areas.len(), classifier.areas().len());
+
+    let welfare = &summary.areas[0];
+    assert_eq!(welfare.id, "welfare");
+    assert_eq!(
+        (
+            welfare.for_count,
+            welfare.against_count,
+            welfare.absent_count
+        ),
+        (1, 1, 1)
+    );
+    assert_eq!(welfare.total, 3);
+    let order: Vec<&str> = welfare
+        .divisions
+        .iter()
+        .filter_map(|vote| vote.division_id.as_deref())
+        .collect();
+    assert_eq!(order, ["1", "2", "3"], "votes cast first, newest first");
+
+    let defence = summary
+        .areas
+        .iter()
+        .find(|area| area.id == "defence")
+        .expect("defence");
+    assert_eq!(defence.against_count, 1);
+    let housing = summary
+        .areas
+        .iter()
+        .find(|area| area.id == "housing")
+        .expect("housing");
+    assert_eq!(housing.total, 0);
+
+    assert_eq!(summary.other.total, 1);
+    let note = summary.coverage_note.expect("coverage note");
+    assert!(
+        note.starts_with("1 of 5 divisions"),
+        "unexpected note: {note}"
+    );
+}
+
+#[test]
+fn aggregation_lists_only_top_divisions_and_omits_note_when_covered() {
+    let classifier = PolicyAreaClassifier::builtin();
+    let votes = (0..8)
+        .map(|day| {
+            vote(
+                &day.to_string(),
+                "Housing Bill",
+                &format!("2024-01-0{}", day + 1),
+                Some("Aye"),
+            )
+        })
+        .collect();
+
+    let summary = aggregate_by_policy_area(votes, &classifier);
+    let housing = summary
+        .areas
+        .iter()
+        .find(|area| area.id == "housing")
+        .expect("housing");
+
+    assert_eq!(housing.total, 8);
+    assert_eq!(housing.divisions.len(), TOP_DIVISIONS_PER_AREA);
+    assert_eq!(housing.divisions[0].date.as_deref(), Some("2024-01-08"));
+    assert!(summary.coverage_note.is_none());
+}
+
+#[tokio::test]
+async fn voting_record_tool_groups_by_policy_area() {
+    let server = MockServer::start().await;
+    let division = |id: u64, title: &str, date: &str| {
+        json!({
+            "MemberVotedAye": true,
+            "MemberWasTeller": false,
+            "PublishedDivision": {
+                "DivisionId": id,
+                "Date": date,
+                "Title": title,
+                "AyeCount": 300,
+                "NoCount": 200
+            }
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/data/divisions.json/membervoting"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            division(
+                2,
+                "Illegal Migration Bill: Third Reading",
+                "2024-02-01T00:00:00"
+            ),
+            division(1, "Finance Bill: Second Reading", "2024-01-01T00:00:00")
+        ])))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_mp_voting_record",
+        json!({"mpId": 172, "groupBy": "policyArea", "enableCache": false}),
+    )
+    .await;
+    let result = &response["result"];
+    let summary = &result["structuredContent"];
+
+    assert_eq!(summary["groupBy"], "policyArea");
+    assert_eq!(summary["totalDivisions"], 2);
+    assert_eq!(summary["areas"][1]["id"], "immigration");
+    assert_eq!(summary["areas"][1]["total"], 1);
+    assert_eq!(summary["other"]["total"], 1);
+    assert!(summary["coverageNote"].is_string());
+    assert_eq!(
+        result["content"].as_array().map(Vec::len),
+        Some(1),
+        "grouped output should match the declared outputSchema"
+    );
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+
+async fn call(harness: &common::TestHarness, api_key: &str, name: &str, params: Value) -> Value {
+    let mut params = params;
+    params["name"] = json!(name);
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(Some(json!(7)), "tools/call", Some(params)),
+            common::context().with_api_key(api_key),
+        )
+        .await;
+    match response {
+        Ok(Some(success)) => serde_json::to_value(success).expect("encode success"),
+        Ok(None) => Value::Null,
+        Err(error) => serde_json::to_value(error).expect("encode error"),
+    }
+}
+
+async fn set_preferences(
+    harness: &common::TestHarness,
+    api_key: &str,
+    preferences: Value,
+) -> Value {
+    call(
+        harness,
+        ADMIN_KEY,
+        "admin.set_key_preferences",
+        json!({"arguments": {"apiKey": api_key, "preferences": preferences}}),
+    )
+    .await
+}
+
+fn harness() -> common::TestHarness {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    common::build_service(Some(source), |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    })
+}
+
+#[tokio::test]
+async fn preferences_apply_unless_the_call_overrides_them() {
+    let harness = harness();
+    common::initialize(&harness.service).await;
+
+    let stored = set_preferences(
+        &harness,
+        "test",
+        json!({"textContent": "compact", "includeMeta": false}),
+    )
+    .await;
+    assert_eq!(
+        stored["result"]["structuredContent"]["preferences"],
+        json!({"textContent": "compact", "includeMeta": false}),
+        "response: {stored}"
+    );
+
+    let defaulted = call(
+        &harness,
+        "test",
+        "utilities.current_datetime",
+        json!({"arguments": {}}),
+    )
+    .await;
+    let text = defaulted["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(!text.contains('\n'), "compact text: {text}");
+    assert!(defaulted["result"].get("_meta").is_none());
+
+    let overridden = call(
+        &harness,
+        "test",
+        "utilities.current_datetime",
+        json!({"arguments": {}, "_meta": {"textContent": "markdown", "includeMeta": true}}),
+    )
+    .await;
+    let text = overridden["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(text.starts_with("```json\n{\n"), "markdown text: {text}");
+    let meta = &overridden["result"]["_meta"];
+    assert!(meta["correlationId"].is_string(), "meta: {meta}");
+    assert!(
+        meta.get("textContent").is_none(),
+        "formatting is not echoed"
+    );
+
+    // Another key keeps the server defaults.
+    let admin = call(
+        &harness,
+        ADMIN_KEY,
+        "utilities.current_datetime",
+        json!({"arguments": {}}),
+    )
+    .await;
+    assert!(
+        admin["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("\n  ")
+    );
+    assert!(admin["result"]["_meta"].is_object());
+}
+
+#[tokio::test]
+async fn research_limit_is_a_default_for_research_run() {
+    let harness = harness();
+    common::initialize(&harness.service).await;
+    set_preferences(&harness, "test", json!({"researchLimit": 2})).await;
+
+    for arguments in [
+        json!({"topic": "housing"}),
+        json!({"topic": "fishing", "limit": 5}),
+    ] {
+        let response = call(
+            &harness,
+            "test",
+            "research.run",
+            json!({"arguments": arguments}),
+        )
+        .await;
+        assert!(response["result"].is_object(), "response: {response}");
+    }
+
+    let log = harness.service.audit_log().expect("audit log");
+    let entries = log.recent(10).await.expect("audit entries");
+    let limits: Vec<(Value, Value)> = entries
+        .iter()
+        .filter(|entry| entry.tool == "research.run")
+        .map(|entry| {
+            let arguments = entry.arguments.clone().unwrap_or_default();
+            (arguments["topic"].clone(), arguments["limit"].clone())
+        })
+        .collect();
+    assert_eq!(
+        limits,
+        vec![(json!("fishing"), json!(5)), (json!("housing"), json!(2))]
+    );
+}
+
+#[tokio::test]
+async fn only_admins_may_set_preferences_for_known_keys() {
+    let harness = harness();
+    common::initialize(&harness.service).await;
+
+    let denied = call(
+        &harness,
+        "test",
+        "admin.set_key_preferences",
+        json!({"arguments": {"apiKey": "test", "preferences": {}}}),
+    )
+    .await;
+    assert_eq!(denied["error"]["code"], -32600, "response: {denied}");
+
+    let unknown = set_preferences(&harness, "someone-else", json!({"includeMeta": false})).await;
+    assert_eq!(unknown["error"]["code"], -32602, "response: {unknown}");
+
+    set_preferences(&harness, "test", json!({"includeMeta": false})).await;
+    set_preferences(&harness, "test", json!({})).await;
+    let cleared = call(
+        &harness,
+        "test",
+        "utilities.current_datetime",
+        json!({"arguments": {}}),
+    )
+    .await;
+    assert!(
+        cleared["result"]["_meta"].is_object(),
+        "response: {cleared}"
+    );
+
+    let entries = harness
+        .service
+        .audit_log()
+        .expect("audit log")
+        .recent(10)
+        .await
+        .expect("audit entries");
+    assert!(
+        entries
+            .iter()
+            .filter(|entry| entry.tool == "admin.set_key_preferences")
+            .all(|entry| entry.arguments.as_ref().unwrap()["apiKey"].is_null()),
+        "API keys are not audited"
+    );
+}
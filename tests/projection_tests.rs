@@ -0,0 +1,131 @@
+use serde_json::json;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::projection::{
+    FieldPath, parse_field_paths, project_items,
+};
+
+mod common;
+
+fn paths(paths: &[&str]) -> Vec<FieldPath> {
+    paths
+        .iter()
+        .map(|path| FieldPath::parse(path).expect("valid path"))
+        .collect()
+}
+
+#[test]
+fn members_envelopes_are_unwrapped_and_nested_paths_kept() {
+    let payload = json!({
+        "totalResults": 1,
+        "items": [{
+            "value": {
+                "id": 172,
+                "nameDisplayAs": "Example Member",
+                "latestHouseMembership": {"membershipFrom": "Somewhere", "house": 1}
+            },
+            "links": []
+        }]
+    });
+
+    let projected = project_items(
+        payload,
+        &paths(&["id", "latestHouseMembership.membershipFrom"]),
+    );
+
+    assert_eq!(projected["totalResults"], 1);
+    assert_eq!(
+        projected["items"],
+        json!([{"id": 172, "latestHouseMembership": {"membershipFrom": "Somewhere"}}])
+    );
+}
+
+#[test]
+fn missing_paths_become_null() {
+    let payload = json!([{"title": "A debate", "date": {"_value": "2024-01-01"}}, {"title": "B"}]);
+
+    let projected = project_items(payload, &paths(&["title", "date._value", "missing.deep"]));
+
+    assert_eq!(
+        projected,
+        json!([
+            {"title": "A debate", "date": {"_value": "2024-01-01"}, "missing": {"deep": null}},
+            {"title": "B", "date": {"_value": null}, "missing": {"deep": null}}
+        ])
+    );
+}
+
+#[test]
+fn linked_data_items_and_overlapping_paths() {
+    let payload = json!({"result": {"items": [{"label": {"_value": "x", "lang": "en"}}]}});
+
+    let projected = project_items(payload, &paths(&["label.lang", "label"]));
+
+    assert_eq!(
+        projected["result"]["items"],
+        json!([{"label": {"_value": "x", "lang": "en"}}])
+    );
+}
+
+#[test]
+fn invalid_path_syntax_is_rejected() {
+    for invalid in ["", ".id", "id.", "a..b", "name[0]", "has space"] {
+        assert!(
+            FieldPath::parse(invalid).is_err(),
+            "{invalid:?} should fail"
+        );
+    }
+    assert!(parse_field_paths(None).expect("no fields").is_none());
+    assert!(parse_field_paths(Some(&["_about".to_string(), "a-b.c_d".to_string()])).is_ok());
+}
+
+#[tokio::test]
+async fn projections_share_the_cached_payload() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(".*"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"billId": 1, "shortTitle": "Housing Bill", "currentHouse": "Commons"}]
+        })))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let titles = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "housing", "fields": ["shortTitle"]}),
+    )
+    .await;
+    assert_eq!(
+        titles["result"]["structuredContent"]["items"],
+        json!([{"shortTitle": "Housing Bill"}]),
+        "response: {titles}"
+    );
+
+    let full = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "housing"}),
+    )
+    .await;
+    assert_eq!(
+        full["result"]["structuredContent"]["items"][0]["currentHouse"],
+        "Commons"
+    );
+    assert_eq!(
+        server.received_requests().await.unwrap_or_default().len(),
+        1
+    );
+
+    let invalid = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "housing", "fields": ["short..title"]}),
+    )
+    .await;
+    assert_eq!(invalid["error"]["code"], -32602, "response: {invalid}");
+}
@@ -0,0 +1,163 @@
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{HeaderMap, Request, StatusCode, Uri};
+use axum::middleware;
+use axum::routing::post;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::ApiKeysConfig;
+use mp_writer_mcp_server::server::{AppState, require_api_key};
+
+mod common;
+
+fn initialize_request(uri: &str) -> Request<Body> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": common::PROTOCOL_VERSION,
+            "clientInfo": {"name": "query-client", "version": "1.0"},
+            "capabilities": {}
+        }
+    });
+    Request::post(uri)
+        .header("content-type", "application/json")
+        .header("mcp-protocol-version", common::PROTOCOL_VERSION)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn status_of(router: Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+#[tokio::test]
+async fn query_keys_are_rejected_unless_enabled() {
+    let harness = common::build_service(None, |_| {});
+
+    let (status, body) = status_of(
+        harness.router(),
+        initialize_request("/api/mcp?api_key=test"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body.contains("x-api-key header"), "body: {body}");
+}
+
+#[tokio::test]
+async fn query_keys_are_accepted_on_the_mcp_route_when_enabled() {
+    let harness = common::build_service(None, |config| config.allow_query_api_key = true);
+
+    let (status, body) = status_of(
+        harness.router(),
+        initialize_request("/api/mcp?api_key=test"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let (status, _) = status_of(
+        harness.router(),
+        initialize_request("/api/mcp?api_key=wrong"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = status_of(
+        harness.router(),
+        Request::get("/api/metrics?api_key=test")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::UNAUTHORIZED,
+        "only /api/mcp takes query keys"
+    );
+
+    let (_, metrics) = status_of(
+        harness.router(),
+        Request::get("/api/metrics")
+            .header("x-api-key", "test")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert!(
+        metrics.contains("mcp_query_api_key_requests_total 2\n"),
+        "metrics: {metrics}"
+    );
+}
+
+#[tokio::test]
+async fn query_keys_are_stripped_before_the_handler() {
+    let harness = common::build_service(None, |config| config.allow_query_api_key = true);
+    let state = AppState::new(
+        harness.service.clone(),
+        harness.client.clone(),
+        harness.config.clone(),
+    );
+    let echo = |uri: Uri, headers: HeaderMap| async move {
+        axum::Json(json!({
+            "uri": uri.to_string(),
+            "apiKeyHeader": headers.get("x-api-key").and_then(|value| value.to_str().ok()),
+        }))
+    };
+    let router = Router::new()
+        .route("/api/mcp", post(echo))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ))
+        .with_state(state);
+
+    let (status, body) = status_of(
+        router.clone(),
+        Request::post("/api/mcp?session=abc&api_key=test")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let seen: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(seen["uri"], "/api/mcp?session=abc");
+    assert_eq!(seen["apiKeyHeader"], "test");
+
+    let (_, body) = status_of(
+        router,
+        Request::post("/api/mcp?api_key=ignored")
+            .header("x-api-key", "test")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let seen: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(seen["uri"], "/api/mcp");
+}
+
+#[tokio::test]
+async fn query_keys_never_reach_the_debug_log() {
+    const KEY: &str = "query-secret-0123456789";
+    let (logs, _guard) = common::capture();
+    let harness = common::build_service(None, |config| {
+        config.api_key = KEY.to_string();
+        config.api_keys = ApiKeysConfig::single(KEY);
+        config.allow_query_api_key = true;
+    });
+
+    let (status, body) = status_of(
+        harness.router(),
+        initialize_request(&format!("/api/mcp?api_key={KEY}")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let logs = logs.text();
+    assert!(logs.contains("received request headers"), "{logs}");
+    assert!(logs.contains(r#"("x-api-key", "<redacted>")"#), "{logs}");
+    assert!(!logs.contains(KEY), "{logs}");
+}
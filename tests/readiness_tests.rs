@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::server::build_router;
+
+mod common;
+
+async fn get(router: &Router, uri: &str) -> (StatusCode, Value) {
+    let response = router
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).expect("JSON body"))
+}
+
+async fn initialize(router: &Router) -> (StatusCode, Value) {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": common::PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {"name": "test", "version": "1.0"}
+        }
+    });
+    let response = router
+        .clone()
+        .oneshot(
+            Request::post("/api/mcp")
+                .header("content-type", "application/json")
+                .header("x-api-key", "test")
+                .header("mcp-protocol-version", common::PROTOCOL_VERSION)
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).expect("JSON body"))
+}
+
+#[tokio::test]
+async fn requests_wait_for_start_up_while_liveness_answers() {
+    let harness = common::build_service(None, |_| {});
+    let state = harness.state();
+    let readiness = state.readiness.clone();
+    let router = build_router(state);
+
+    let starting = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        readiness.mark_ready();
+    });
+
+    let (status, body) = get(&router, "/api/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "starting");
+
+    let (status, body) = get(&router, "/api/health").await;
+    assert_eq!(status, StatusCode::OK, "liveness does not wait: {body}");
+
+    let (status, body) = initialize(&router).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["id"], 1);
+    assert_eq!(body["error"]["code"], -32002, "body: {body}");
+    assert_eq!(body["error"]["message"], "server starting");
+
+    starting.await.unwrap();
+
+    let (status, body) = get(&router, "/api/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["checks"]["sled"], "ok");
+
+    let (status, body) = initialize(&router).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        body["result"]["protocolVersion"].is_string(),
+        "body: {body}"
+    );
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use reqwest::Url;
+use reqwest::header::HeaderMap;
+use serde_json::{Value, json};
+use wiremock::MockServer;
+
+use mp_writer_mcp_server::core::recording::{RecordedResponse, redact_postcodes};
+
+mod common;
+
+/// A `searchTerm=climate` bills search in the format `UPSTREAM_RECORD_DIR` writes.
+const RECORDED_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/recorded");
+
+async fn fetch_bills_from(dir: &Path, search_term: &str) -> Value {
+    let server = MockServer::start().await;
+    common::mount_recorded(&server, dir).await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": search_term, "enableCache": false}),
+    )
+    .await
+}
+
+#[test]
+fn postcodes_are_redacted_wherever_they_appear() {
+    assert_eq!(
+        redact_postcodes("Write to SW1A 1AA or m1 1ae (ref EC1A1BB)."),
+        "Write to [postcode] or [postcode] (ref [postcode])."
+    );
+    for text in ["Bill 2024-25", "HC Deb 12 May", "SW1A1AAX", "A1 1A"] {
+        assert_eq!(redact_postcodes(text), text);
+    }
+
+    let url = Url::parse("https://api.postcodes.io/postcodes/SW1A%201AA?filter=SW1A1AA").unwrap();
+    let body = json!({"result": {
+        "postcode": "SW1A 1AA",
+        "parliamentary_constituency": "Cities of London and Westminster"
+    }});
+    let recorded =
+        RecordedResponse::capture(&url, 200, &HeaderMap::new(), body.to_string().as_bytes());
+    assert_eq!(recorded.source, "postcodes");
+    assert_eq!(recorded.path, "/postcodes/[postcode]");
+    let stored = serde_json::to_string(&recorded).unwrap();
+    for leaked in ["SW1A", "1AA"] {
+        assert!(!stored.contains(leaked), "{stored}");
+        assert!(!recorded.file_name().contains(leaked));
+    }
+    assert!(stored.contains("Cities of London and Westminster"));
+}
+
+#[tokio::test]
+async fn a_recorded_bills_search_drives_fetch_bills_offline() {
+    let response = fetch_bills_from(Path::new(RECORDED_DIR), "climate").await;
+
+    let bills = &response["result"]["structuredContent"];
+    assert_eq!(bills["totalResults"], 2, "{response}");
+    assert_eq!(
+        bills["items"][0]["shortTitle"], "Climate and Nature Bill",
+        "{response}"
+    );
+}
+
+#[cfg(feature = "record")]
+#[tokio::test]
+async fn recorded_responses_are_sanitised_and_replay() {
+    use wiremock::matchers::any;
+    use wiremock::{Mock, ResponseTemplate};
+
+    let live = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"billId": 1, "shortTitle": "Post Office (Horizon System) Bill",
+                       "sponsor": "Office at SW1A 0AA"}],
+            "totalResults": 1
+        })))
+        .mount(&live)
+        .await;
+    let record_dir = tempfile::tempdir().unwrap();
+    let (uri, dir) = (live.uri(), record_dir.path().to_string_lossy().into_owned());
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.upstream_record_dir = Some(dir);
+    });
+    common::initialize(&harness.service).await;
+    let original = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "horizon", "enableCache": false}),
+    )
+    .await;
+
+    let recorded = RecordedResponse::load_dir(record_dir.path()).unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert!(recorded[0].file_name().starts_with("bills-"));
+    assert_eq!(recorded[0].path, "/api/v1/Bills");
+    let stored = serde_json::to_string(&recorded[0]).unwrap();
+    assert!(!stored.contains("SW1A"), "{stored}");
+
+    let replayed = fetch_bills_from(record_dir.path(), "horizon").await;
+    let item = &replayed["result"]["structuredContent"]["items"][0];
+    assert_eq!(
+        item["shortTitle"],
+        original["result"]["structuredContent"]["items"][0]["shortTitle"]
+    );
+    assert_eq!(item["sponsor"], "Office at [postcode]");
+}
@@ -0,0 +1,223 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::reference_data::{DataSource, ReferenceDataStore};
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+
+/// A mapping with one area, in place of the bundled five.
+const HOUSING_ONLY: &str =
+    r#"{"areas": [{"id": "housing", "label": "Housing", "keywords": ["housing"]}]}"#;
+const TWO_AREAS: &str = r#"{"areas": [
+    {"id": "housing", "label": "Housing", "keywords": ["housing"]},
+    {"id": "energy", "label": "Energy", "keywords": ["energy"]}
+]}"#;
+
+fn sha256(contents: &str) -> String {
+    hex::encode(Sha256::digest(contents.as_bytes()))
+}
+
+fn policy_areas_file(dir: &Path) -> std::path::PathBuf {
+    dir.join("policy_areas.json")
+}
+
+/// Serve `manifest` and `dataset` as the canonical reference data.
+async fn canonical(manifest: Value, dataset: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/reference/manifest.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(manifest))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/reference/policy_areas.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(dataset))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn manifest_for(contents: &str, rows: usize) -> Value {
+    json!({
+        "datasets": {
+            "policy_areas": {"url": "policy_areas.json", "sha256": sha256(contents), "rows": rows}
+        }
+    })
+}
+
+fn harness(server: &MockServer, dir: &TempDir) -> common::TestHarness {
+    let manifest_url = format!("{}/reference/manifest.json", server.uri());
+    let data_dir = dir.path().to_str().unwrap().to_string();
+    common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+        config.reference_data_dir = Some(data_dir);
+        config.reference_data_manifest_url = Some(manifest_url);
+    })
+}
+
+async fn refresh(harness: &common::TestHarness) -> Value {
+    common::initialize(&harness.service).await;
+    let response = common::call_tool_with_context(
+        &harness.service,
+        "admin.refresh_reference_data",
+        json!({}),
+        common::context().with_api_key(ADMIN_KEY),
+    )
+    .await;
+    assert!(response["result"]["isError"].is_null(), "{response}");
+    response["result"]["structuredContent"]["datasets"][0].clone()
+}
+
+fn area_ids(harness: &common::TestHarness) -> Vec<String> {
+    harness
+        .client
+        .policy_areas()
+        .areas()
+        .iter()
+        .map(|area| area.id.clone())
+        .collect()
+}
+
+#[test]
+fn a_valid_runtime_copy_takes_precedence_over_the_compiled_in_one() {
+    let dir = TempDir::new().unwrap();
+    let store = ReferenceDataStore::new(dir.path().to_str());
+    let dataset = ReferenceDataStore::dataset("policy_areas").unwrap();
+
+    let (contents, source) = store.load(dataset);
+    assert_eq!(source, DataSource::Builtin);
+    assert_eq!(contents, dataset.builtin);
+
+    fs::write(policy_areas_file(dir.path()), HOUSING_ONLY).unwrap();
+    let (contents, source) = store.load(dataset);
+    assert_eq!(source, DataSource::Runtime);
+    assert_eq!(contents, HOUSING_ONLY);
+
+    // A damaged runtime copy is ignored rather than failing start-up.
+    fs::write(policy_areas_file(dir.path()), "{\"areas\": [").unwrap();
+    assert_eq!(store.load(dataset).1, DataSource::Builtin);
+
+    assert_eq!(
+        ReferenceDataStore::new(None).load(dataset).1,
+        DataSource::Builtin
+    );
+}
+
+#[tokio::test]
+async fn the_client_starts_with_the_runtime_policy_areas() {
+    let server = MockServer::start().await;
+    let dir = TempDir::new().unwrap();
+    fs::write(policy_areas_file(dir.path()), HOUSING_ONLY).unwrap();
+
+    let harness = harness(&server, &dir);
+
+    assert_eq!(area_ids(&harness), ["housing"]);
+}
+
+#[tokio::test]
+async fn a_verified_download_is_swapped_in_and_reported() {
+    let server = canonical(manifest_for(TWO_AREAS, 2), TWO_AREAS).await;
+    let dir = TempDir::new().unwrap();
+    let harness = harness(&server, &dir);
+    assert_eq!(area_ids(&harness).len(), 5);
+
+    let report = refresh(&harness).await;
+
+    assert_eq!(report["name"], "policy_areas");
+    assert_eq!(report["status"], "updated", "{report}");
+    assert_eq!(report["previousRows"], 5);
+    assert_eq!(report["rows"], 2);
+    assert_eq!(report["sha256"], sha256(TWO_AREAS));
+    assert_eq!(
+        fs::read_to_string(policy_areas_file(dir.path())).unwrap(),
+        TWO_AREAS
+    );
+    assert_eq!(area_ids(&harness), ["housing", "energy"]);
+    // Only the dataset itself is left behind.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    let report = refresh(&harness).await;
+    assert_eq!(report["status"], "unchanged", "{report}");
+    assert_eq!(report["previousSha256"], sha256(TWO_AREAS));
+}
+
+#[tokio::test]
+async fn a_corrupt_download_leaves_the_current_data_untouched() {
+    // Truncated in transit: the checksum no longer matches the manifest.
+    let truncated = &TWO_AREAS[..40];
+    // Published broken: the checksum matches, but the file is not a mapping.
+    let malformed = r#"{"areas": [{"id": "other", "label": "Other"}]}"#;
+    // Published short: valid, but not the row count the manifest promises.
+    let cases = [
+        (manifest_for(TWO_AREAS, 2), truncated, "checksum mismatch"),
+        (
+            manifest_for(malformed, 1),
+            malformed,
+            "invalid policy area id",
+        ),
+        (
+            manifest_for(HOUSING_ONLY, 2),
+            HOUSING_ONLY,
+            "1 rows; the manifest lists 2",
+        ),
+    ];
+
+    for (manifest, download, expected_error) in cases {
+        let server = canonical(manifest, download).await;
+        let dir = TempDir::new().unwrap();
+        fs::write(policy_areas_file(dir.path()), TWO_AREAS).unwrap();
+        let harness = harness(&server, &dir);
+
+        let report = refresh(&harness).await;
+
+        assert_eq!(report["status"], "failed", "{report}");
+        assert!(
+            report["error"].as_str().unwrap().contains(expected_error),
+            "{report}"
+        );
+        assert_eq!(report["previousSha256"], sha256(TWO_AREAS));
+        assert_eq!(
+            fs::read_to_string(policy_areas_file(dir.path())).unwrap(),
+            TWO_AREAS
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(area_ids(&harness), ["housing", "energy"]);
+    }
+}
+
+#[tokio::test]
+async fn refreshing_needs_the_admin_key_and_a_manifest() {
+    let server = canonical(manifest_for(TWO_AREAS, 2), TWO_AREAS).await;
+    let dir = TempDir::new().unwrap();
+    let harness = harness(&server, &dir);
+    common::initialize(&harness.service).await;
+
+    let response =
+        common::call_tool(&harness.service, "admin.refresh_reference_data", json!({})).await;
+    assert_eq!(response["error"]["code"], -32600, "{response}");
+
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    common::initialize(&harness.service).await;
+    let response = common::call_tool_with_context(
+        &harness.service,
+        "admin.refresh_reference_data",
+        json!({}),
+        common::context().with_api_key(ADMIN_KEY),
+    )
+    .await;
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "config",
+        "{response}"
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,157 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::relevance::RelevanceFilter;
+use mp_writer_mcp_server::features::parliament::{FetchBillsArgs, FetchCoreDatasetArgs};
+
+mod common;
+
+async fn upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {"billId": 1, "shortTitle": "Water Quality and Sewage Bill"},
+                {"billId": 2, "shortTitle": "Water Industry Bill"},
+                {"billId": 3, "shortTitle": "Sewage Discharge Bill"},
+                {"billId": 4}
+            ],
+            "totalResults": 4
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdebates.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "result": {
+                "items": [
+                    {"title": {"_value": "Sewerage infrastructure"}},
+                    {"title": "Sewer overflows"},
+                    {"title": "Farming"}
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn bills(apply_relevance: Option<bool>, threshold: Option<f32>) -> FetchBillsArgs {
+    FetchBillsArgs {
+        search_term: Some("water sewage".to_string()),
+        house: None,
+        session: None,
+        parliament_number: None,
+        enable_cache: None,
+        cache_only: None,
+        apply_relevance,
+        relevance_threshold: threshold,
+        fields: None,
+    }
+}
+
+fn debates(fuzzy: bool) -> FetchCoreDatasetArgs {
+    FetchCoreDatasetArgs {
+        dataset: "commonsdebates".to_string(),
+        search_term: Some("sewer".to_string()),
+        page: None,
+        per_page: None,
+        enable_cache: None,
+        cache_only: None,
+        fuzzy_match: Some(fuzzy),
+        apply_relevance: Some(true),
+        relevance_threshold: Some(1.0),
+        fields: None,
+    }
+}
+
+fn bill_ids(payload: &Value) -> Vec<i64> {
+    payload["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["billId"].as_i64().unwrap())
+        .collect()
+}
+
+fn debate_count(payload: &Value) -> usize {
+    payload["result"]["items"].as_array().unwrap().len()
+}
+
+#[tokio::test]
+async fn one_cached_response_serves_every_threshold() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    let client = &harness.client;
+
+    let unfiltered = client.fetch_bills(bills(None, None)).await.unwrap();
+    assert_eq!(bill_ids(&unfiltered), [1, 2, 3, 4]);
+    let strict = client
+        .fetch_bills(bills(Some(true), Some(1.0)))
+        .await
+        .unwrap();
+    // Bill 4 has no title to score, so it is kept.
+    assert_eq!(bill_ids(&strict), [1, 4]);
+    let loose = client
+        .fetch_bills(bills(Some(true), Some(0.5)))
+        .await
+        .unwrap();
+    assert_eq!(bill_ids(&loose), [1, 2, 3, 4]);
+    assert_eq!(loose["totalResults"], 4);
+    // Repeated: answered from the memoised view.
+    let again = client
+        .fetch_bills(bills(Some(true), Some(1.0)))
+        .await
+        .unwrap();
+    assert_eq!(again, strict);
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    assert_eq!(client.cache_entries().await, 1);
+}
+
+#[tokio::test]
+async fn fuzzy_matching_is_applied_to_the_same_cached_response() {
+    let server = upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    let client = &harness.client;
+
+    let exact = client.fetch_core_dataset(debates(false)).await.unwrap();
+    assert_eq!(debate_count(&exact), 1);
+    let fuzzy = client.fetch_core_dataset(debates(true)).await.unwrap();
+    assert_eq!(debate_count(&fuzzy), 2);
+    assert_eq!(
+        fuzzy["result"]["items"][0]["title"]["_value"],
+        "Sewerage infrastructure"
+    );
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    assert_eq!(client.cache_entries().await, 1);
+}
+
+#[test]
+fn view_keys_separate_thresholds_fuzziness_and_terms() {
+    let payload = json!({"items": [{"title": "Water"}]});
+    let strict = RelevanceFilter {
+        threshold: 1.0,
+        fuzzy: false,
+    };
+    let loose = RelevanceFilter {
+        threshold: 0.5,
+        fuzzy: false,
+    };
+    let fuzzy = RelevanceFilter {
+        threshold: 1.0,
+        fuzzy: true,
+    };
+
+    let key = strict.view_key(&payload, "water");
+    assert_eq!(key, strict.view_key(&payload, "water"));
+    assert_ne!(key, loose.view_key(&payload, "water"));
+    assert_ne!(key, fuzzy.view_key(&payload, "water"));
+    assert_ne!(key, strict.view_key(&payload, "rivers"));
+    assert_ne!(key, strict.view_key(&json!({"items": []}), "water"));
+}
@@ -0,0 +1,162 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+
+async fn latest_audit_id(harness: &common::TestHarness) -> String {
+    let log = harness.service.audit_log().expect("audit log");
+    log.recent(1).await.expect("recent")[0]
+        .id
+        .clone()
+        .expect("entry id")
+}
+
+async fn replay(harness: &common::TestHarness, arguments: Value, api_key: &str) -> Value {
+    common::call_tool_with_context(
+        &harness.service,
+        "admin.replay_call",
+        arguments,
+        common::context().with_api_key(api_key),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn failed_call_is_replayed_into_a_success() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdivisions.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdivisions.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": [1, 2]})))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    common::initialize(&harness.service).await;
+
+    let failed = common::call_tool(
+        &harness.service,
+        "parliament.fetch_core_dataset",
+        json!({"dataset": "commonsdivisions", "enableCache": false}),
+    )
+    .await;
+    assert_eq!(failed["result"]["isError"], true, "response: {failed}");
+    let original_id = latest_audit_id(&harness).await;
+
+    let response = replay(&harness, json!({"auditId": original_id}), ADMIN_KEY).await;
+    let payload = &response["result"]["structuredContent"];
+    assert_eq!(payload["outcome"], "success", "response: {response}");
+    assert_eq!(payload["tool"], "parliament.fetch_core_dataset");
+    assert_eq!(payload["result"], json!({"items": [1, 2]}));
+    assert_eq!(payload["originalAuditId"], original_id);
+
+    let log = harness.service.audit_log().expect("audit log");
+    let replay_id = payload["replayAuditId"].as_str().expect("replay id");
+    let replayed = log.get(replay_id).await.expect("lookup").expect("entry");
+    assert_eq!(replayed.tool, "parliament.fetch_core_dataset");
+    assert_eq!(replayed.replay_of.as_deref(), Some(original_id.as_str()));
+    assert_eq!(
+        log.get(&original_id)
+            .await
+            .expect("lookup")
+            .expect("entry")
+            .error_kind
+            .as_deref(),
+        Some("internal")
+    );
+}
+
+#[tokio::test]
+async fn redacted_postcodes_must_be_supplied_again() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(".*"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    common::initialize(&harness.service).await;
+
+    common::call_tool(
+        &harness.service,
+        "parliament.lookup_constituency_offline",
+        json!({"postcode": "SW1A 1AA", "enableCache": false}),
+    )
+    .await;
+    let original_id = latest_audit_id(&harness).await;
+    let original = harness
+        .service
+        .audit_log()
+        .expect("audit log")
+        .get(&original_id)
+        .await
+        .expect("lookup")
+        .expect("entry");
+    assert_eq!(original.redacted_fields, ["/postcode"]);
+    assert_eq!(
+        original.arguments,
+        Some(json!({"postcode": null, "enableCache": false}))
+    );
+
+    let refused = replay(&harness, json!({"auditId": original_id}), ADMIN_KEY).await;
+    assert_eq!(refused["error"]["code"], -32602, "response: {refused}");
+    assert!(
+        refused["error"]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("/postcode")),
+        "response: {refused}"
+    );
+    let requests_before = server.received_requests().await.unwrap_or_default().len();
+
+    let replayed = replay(
+        &harness,
+        json!({"auditId": original_id, "redactedValues": {"/postcode": "SW1A 2AA"}}),
+        ADMIN_KEY,
+    )
+    .await;
+    assert_eq!(
+        replayed["result"]["structuredContent"]["originalAuditId"], original_id,
+        "response: {replayed}"
+    );
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(requests.len() > requests_before);
+    assert!(
+        requests[requests_before..]
+            .iter()
+            .any(|request| request.url.path().contains("SW1A"))
+    );
+}
+
+#[tokio::test]
+async fn replay_requires_the_admin_key() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    common::initialize(&harness.service).await;
+    common::call_tool(&harness.service, "utilities.current_datetime", json!({})).await;
+    let original_id = latest_audit_id(&harness).await;
+
+    let response = replay(&harness, json!({"auditId": original_id}), "test").await;
+    assert_eq!(response["error"]["code"], -32600, "response: {response}");
+
+    let without_admin = common::build_service(None, |_| {});
+    common::initialize(&without_admin.service).await;
+    let response = replay(&without_admin, json!({"auditId": "00"}), "test").await;
+    assert_eq!(response["error"]["code"], -32600, "response: {response}");
+}
@@ -0,0 +1,108 @@
+use axum::body::Body;
+use axum::http::{Request, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+fn health_request(request_id: Option<&str>) -> Request<Body> {
+    let mut request = Request::get("/api/health");
+    if let Some(request_id) = request_id {
+        request = request.header("X-Request-Id", request_id);
+    }
+    request.body(Body::empty()).unwrap()
+}
+
+fn response_request_id(response: &axum::response::Response) -> String {
+    response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn request_id_header_round_trips() {
+    let harness = common::build_service(None, |_| {});
+
+    let echoed = harness
+        .router()
+        .oneshot(health_request(Some("trace-123")))
+        .await
+        .unwrap();
+    assert_eq!(response_request_id(&echoed), "trace-123");
+
+    for provided in [None, Some("not a valid id")] {
+        let generated = harness
+            .router()
+            .oneshot(health_request(provided))
+            .await
+            .unwrap();
+        let id = response_request_id(&generated);
+        assert_eq!(id.len(), 32, "{id}");
+        assert!(id.chars().all(|ch| ch.is_ascii_hexdigit()), "{id}");
+    }
+}
+
+#[tokio::test]
+async fn upstream_failures_are_logged_and_reported_with_the_request_id() {
+    let (logs, _guard) = common::capture();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/Bills"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = harness
+        .router()
+        .oneshot(
+            Request::post("/api/mcp")
+                .header("x-api-key", "test")
+                .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+                .header("X-Request-Id", "trace-upstream")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 5,
+                        "method": "tools/call",
+                        "params": {
+                            "name": "parliament.fetch_bills",
+                            "arguments": {"searchTerm": "example", "enableCache": false}
+                        }
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response_request_id(&response), "trace-upstream");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["result"]["isError"], true);
+    assert_eq!(
+        body["result"]["structuredContent"]["correlationId"],
+        "trace-upstream"
+    );
+
+    let logs = logs.text();
+    let retries: Vec<&str> = logs
+        .lines()
+        .filter(|line| line.contains("upstream request failed; retrying"))
+        .collect();
+    assert!(!retries.is_empty(), "{logs}");
+    assert!(
+        retries
+            .iter()
+            .all(|line| line.contains("request_id=trace-upstream")),
+        "{logs}"
+    );
+}
@@ -0,0 +1,93 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+mod common;
+
+fn post_mcp(body: String) -> Request<Body> {
+    Request::post("/api/mcp")
+        .header("x-api-key", "test")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn json_body(response: axum::response::Response) -> Value {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("JSON body")
+}
+
+#[tokio::test]
+async fn oversized_bodies_get_a_json_rpc_error() {
+    let harness = common::build_service(None, |config| config.max_request_bytes = 1024);
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": "research.run", "arguments": {"topic": "x".repeat(4096)}}
+    });
+
+    let response = harness
+        .router()
+        .oneshot(post_mcp(request.to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body = json_body(response).await;
+    assert_eq!(body["jsonrpc"], "2.0");
+    assert_eq!(body["id"], Value::Null);
+    assert_eq!(body["error"]["code"], -32600);
+    assert_eq!(body["error"]["data"]["maxRequestBytes"], 1024);
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("1024")),
+        "body: {body}"
+    );
+}
+
+#[tokio::test]
+async fn bodies_within_the_limit_are_served() {
+    let harness = common::build_service(None, |config| config.max_request_bytes = 1024);
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": common::PROTOCOL_VERSION,
+            "clientInfo": {"name": "test-client", "version": "1.0"},
+            "capabilities": {}
+        }
+    });
+
+    let response = harness
+        .router()
+        .oneshot(post_mcp(request.to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(json_body(response).await["result"].is_object());
+}
+
+#[tokio::test]
+async fn oversized_string_arguments_are_rejected_with_their_path() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "housing", "billKeywords": ["ok", "x".repeat(70_000)]}),
+    )
+    .await;
+
+    assert_eq!(response["error"]["code"], -32602, "response: {response}");
+    let message = response["error"]["message"].as_str().expect("message");
+    assert!(message.contains("/billKeywords/1"), "message: {message}");
+    assert!(message.contains("70000 bytes"), "message: {message}");
+    assert!(message.contains("65536"), "message: {message}");
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use mp_writer_mcp_server::core::stats::now_timestamp;
+use serde_json::{Value, json};
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+const DAY_SECS: u64 = 86_400;
+
+fn harness(
+    source: Arc<common::MockParliamentDataSource>,
+    compact_after_days: u64,
+) -> common::TestHarness {
+    common::build_service(Some(source), |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+        config.research_compact_after_days = compact_after_days;
+    })
+}
+
+async fn list_cached(harness: &common::TestHarness) -> Value {
+    list_page(harness, json!({})).await
+}
+
+async fn list_page(harness: &common::TestHarness, arguments: Value) -> Value {
+    common::call_tool_with_context(
+        &harness.service,
+        "research.list_cached",
+        arguments,
+        common::context().with_api_key(ADMIN_KEY),
+    )
+    .await
+}
+
+/// Store `count` compacted briefs directly in the research tree.
+fn populate(harness: &common::TestHarness, count: usize) {
+    let tree = harness.db.open_tree("research").unwrap();
+    for index in 0..count {
+        let entry = json!({
+            "stored_at": 1_700_000_000 + index as u64,
+            "topic": format!("Topic {index}"),
+            "tombstone": {
+                "summary": format!("Summary {index}"),
+                "status": "ok",
+                "citations": [],
+                "compacted_at": 1_800_000_000u64
+            }
+        });
+        tree.insert(
+            format!("research:{index:08x}"),
+            serde_json::to_vec(&entry).unwrap(),
+        )
+        .unwrap();
+    }
+}
+
+/// Follow `nextCursor` from the first page to the last, returning each page's topics.
+async fn walk(harness: &common::TestHarness, limit: usize) -> Vec<Vec<String>> {
+    let mut pages = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut arguments = json!({"limit": limit});
+        if let Some(cursor) = &cursor {
+            arguments["cursor"] = json!(cursor);
+        }
+        let response = list_page(harness, arguments).await;
+        let page = &response["result"]["structuredContent"];
+        let topics = page["entries"]
+            .as_array()
+            .unwrap_or_else(|| panic!("no entries: {response}"))
+            .iter()
+            .map(|entry| entry["topic"].as_str().unwrap().to_string())
+            .collect();
+        pages.push(topics);
+        match page["nextCursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => return pages,
+        }
+    }
+}
+
+#[tokio::test]
+async fn old_briefs_are_compacted_to_their_summary_and_citations() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = harness(source.clone(), 90);
+    common::initialize(&harness.service).await;
+
+    let first = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate"}),
+    )
+    .await;
+    let summary = first["result"]["structuredContent"]["summary"].clone();
+    let calls = source.total_calls().await;
+
+    // Nothing is old enough yet.
+    let report = harness.research.compact(now_timestamp()).await.unwrap();
+    assert_eq!(report.compacted, 0);
+
+    let report = harness
+        .research
+        .compact(now_timestamp() + 91 * DAY_SECS)
+        .await
+        .unwrap();
+    assert_eq!(report.compacted, 1);
+    assert!(report.bytes_saved() > 0, "{report:?}");
+
+    let listed = list_cached(&harness).await;
+    let entries = listed["result"]["structuredContent"]["entries"]
+        .as_array()
+        .expect("entries")
+        .clone();
+    assert_eq!(entries.len(), 1, "{listed}");
+    let entry = &entries[0];
+    assert_eq!(entry["topic"], "Climate");
+    assert_eq!(entry["compacted"], true);
+    assert_eq!(entry["summary"], summary);
+    assert!(entry["compactedAt"].is_string());
+    assert!(
+        entry["citations"]
+            .as_array()
+            .is_some_and(|citations| !citations.is_empty()),
+        "{entry}"
+    );
+
+    // A tombstone is not a cache hit: the brief is researched again.
+    let again = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate"}),
+    )
+    .await;
+    assert_eq!(again["result"]["structuredContent"]["cached"], false);
+    assert!(source.total_calls().await > calls);
+}
+
+#[tokio::test]
+async fn compaction_is_disabled_with_zero_days() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = harness(source, 0);
+    common::initialize(&harness.service).await;
+
+    common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "Climate"}),
+    )
+    .await;
+    let report = harness
+        .research
+        .compact(now_timestamp() + 3650 * DAY_SECS)
+        .await
+        .unwrap();
+    assert_eq!(report.compacted, 0);
+
+    let listed = list_cached(&harness).await;
+    let entry = &listed["result"]["structuredContent"]["entries"][0];
+    assert_eq!(entry["compacted"], false);
+    assert!(
+        listed["result"]["structuredContent"]
+            .get("nextCursor")
+            .is_none(),
+        "{listed}"
+    );
+}
+
+#[tokio::test]
+async fn paginated_listing_is_complete_and_non_overlapping() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = harness(source, 90);
+    common::initialize(&harness.service).await;
+    populate(&harness, 3000);
+    let expected: Vec<String> = (0..3000).map(|index| format!("Topic {index}")).collect();
+
+    let pages = walk(&harness, 500).await;
+    assert_eq!(
+        pages.iter().map(Vec::len).collect::<Vec<_>>(),
+        [500; 6],
+        "an exactly full last page has no cursor"
+    );
+    assert_eq!(pages.concat(), expected);
+
+    let pages = walk(&harness, 7).await;
+    assert_eq!(pages.len(), 429);
+    assert!(pages[..428].iter().all(|page| page.len() == 7));
+    assert_eq!(pages[428].len(), 4);
+    assert_eq!(pages.concat(), expected);
+}
+
+#[tokio::test]
+async fn pages_stay_consistent_while_entries_are_added() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = harness(source, 90);
+    common::initialize(&harness.service).await;
+    populate(&harness, 100);
+
+    let first = list_page(&harness, json!({"limit": 40})).await;
+    let cursor = first["result"]["structuredContent"]["nextCursor"]
+        .as_str()
+        .expect("more pages")
+        .to_string();
+    // Written behind the cursor, so not part of the pages still to come.
+    let tree = harness.db.open_tree("research").unwrap();
+    let copy = tree.get("research:00000000").unwrap().unwrap();
+    tree.insert("research:00000000a", copy).unwrap();
+
+    let rest = list_page(&harness, json!({"limit": 500, "cursor": cursor})).await;
+    let rest = rest["result"]["structuredContent"]["entries"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(rest.len(), 60);
+    assert_eq!(rest[0]["topic"], "Topic 40");
+}
+
+#[tokio::test]
+async fn malformed_cursors_are_rejected() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = harness(source, 90);
+    common::initialize(&harness.service).await;
+
+    let response = list_page(&harness, json!({"cursor": "not hex"})).await;
+    let error = response["result"]["content"][0]["text"]
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| response["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_default();
+    assert!(error.contains("invalid cursor"), "{response}");
+}
+
+#[tokio::test]
+async fn listing_cached_research_requires_the_admin_key() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = harness(source, 90);
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(&harness.service, "research.list_cached", json!({})).await;
+    assert!(response["error"].is_object(), "{response}");
+}
@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::features::research::{
+    ParliamentDataSource, ResearchRequestDto, ResearchResponseDto, ResearchService,
+};
+
+mod common;
+
+async fn run(mock: common::MockParliamentDataSource, limit: usize) -> ResearchResponseDto {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let data_source: Arc<dyn ParliamentDataSource> = Arc::new(mock);
+    let service = ResearchService::new(config, data_source, db.open_tree("research").unwrap());
+
+    service
+        .run_research(ResearchRequestDto {
+            topic: "Fisheries".to_string(),
+            bill_keywords: vec![],
+            debate_keywords: vec![],
+            mp_id: None,
+            include_state_of_parties: true,
+            limit: Some(limit),
+            max_age_secs: None,
+            cache_only: false,
+            locale: None,
+        })
+        .await
+        .expect("research should succeed")
+}
+
+/// Payloads exercising every probing rule: key aliases in order, case-insensitive keys,
+/// blank strings falling through, nested `text`/`value`/`description` objects and
+/// numeric strings.
+fn irregular_mock() -> common::MockParliamentDataSource {
+    let mut mock = common::MockParliamentDataSource::new();
+    mock.bills = json!({
+        "Results": [
+            {
+                "shortTitle": "  Fisheries Bill  ",
+                "BillStage": {"Abbreviation": "2R"},
+                "LASTUPDATED": "2024-02-01",
+                "billId": 77
+            },
+            {
+                "title": "   ",
+                "officialTitle": {"text": {"value": "Sea Fish (Quotas) Bill"}},
+                "currentStage": "Royal Assent",
+                "link": "https://example.com/bill/sea-fish"
+            }
+        ]
+    });
+    mock.divisions = json!({
+        "items": [
+            {
+                "Title": {"_value": "ignored", "value": "Fisheries Bill: Report Stage"},
+                "DivisionNumber": 41,
+                "date": {"text": "2024-03-12"},
+                "ayes": "301",
+                "Noes": 207,
+                "result": "  ",
+                "Result": "Ayes",
+                "_about": "https://example.com/division/41"
+            },
+            {"motion": "Quota amendment", "ayesCount": 12, "noesCount": "x"}
+        ]
+    });
+    mock.legislation = json!({
+        "items": [
+            {
+                "titleXml": {"description": "Fisheries Act 2020"},
+                "Year": 2020,
+                "legislationType": "ukpga",
+                "URI": "https://www.legislation.gov.uk/ukpga/2020/22"
+            }
+        ]
+    });
+    mock.debates = json!({
+        "debates": [
+            {
+                "subject": "Fishing quotas",
+                "House": "Lords",
+                "Date": "2024-01-09",
+                "Synopsis": {"text": "Peers debated quota allocation."},
+                "link": "https://example.com/debate/quotas"
+            },
+            {"excerpt": "x".repeat(300)}
+        ]
+    });
+    mock.parties = json!({
+        "parties": [
+            {"Party": "Harbour Party", "memberCount": "320"},
+            {"name": {"value": "Net Party"}, "Seats": 12}
+        ],
+        "TotalSeats": "650",
+        "date": "2024-06-01"
+    });
+    mock
+}
+
+fn parsed_sections(response: &ResearchResponseDto) -> Value {
+    json!({
+        "bills": response.bills,
+        "votes": response.votes,
+        "legislation": response.legislation,
+        "debates": response.debates,
+        "stateOfParties": response.state_of_parties,
+    })
+}
+
+#[tokio::test]
+async fn irregular_payloads_parse_to_the_golden_output() {
+    let response = run(irregular_mock(), 5).await;
+    let excerpt = format!("{}…", "x".repeat(220));
+
+    // Recorded from the linear-scan probes this index replaced; numbers under string-only
+    // keys (`DivisionNumber`, `Year`) stay unparsed as they always were.
+    assert_eq!(
+        parsed_sections(&response),
+        json!({
+            "bills": [
+                {
+                    "last_update": "2024-02-01",
+                    "link": "https://bills.parliament.uk/bills/77",
+                    "stage": "2nd reading",
+                    "stageCode": "2R",
+                    "stageExplanation": "The first debate on the general principles of the bill, followed by a vote on whether it should continue.",
+                    "title": "Fisheries Bill"
+                },
+                {
+                    "last_update": null,
+                    "link": "https://example.com/bill/sea-fish",
+                    "stage": "Royal Assent",
+                    "stageCode": "RA",
+                    "stageExplanation": "The monarch has agreed to the bill, making it an Act of Parliament.",
+                    "title": "Sea Fish (Quotas) Bill"
+                }
+            ],
+            "debates": [
+                {
+                    "date": "2024-01-09",
+                    "highlight": "Peers debated quota allocation.",
+                    "house": "Lords",
+                    "link": "https://example.com/debate/quotas",
+                    "title": "Fishing quotas"
+                },
+                {
+                    "date": null,
+                    "highlight": excerpt,
+                    "house": null,
+                    "link": null,
+                    "title": "Debate"
+                }
+            ],
+            "legislation": [
+                {
+                    "title": "Fisheries Act 2020",
+                    "type": "ukpga",
+                    "uri": "https://www.legislation.gov.uk/ukpga/2020/22",
+                    "year": null
+                }
+            ],
+            "stateOfParties": {
+                "last_updated": "2024-06-01",
+                "parties": [
+                    {
+                        "name": "Harbour Party",
+                        "seats": 320
+                    },
+                    {
+                        "name": "Net Party",
+                        "seats": 12
+                    }
+                ],
+                "total_seats": 650
+            },
+            "votes": [
+                {
+                    "ayes": 301,
+                    "date": "2024-03-12",
+                    "division_number": null,
+                    "link": "https://example.com/division/41",
+                    "margin": 94,
+                    "noes": 207,
+                    "outcome": "unknown",
+                    "result": "Ayes",
+                    "title": "Fisheries Bill: Report Stage"
+                },
+                {
+                    "ayes": 12,
+                    "date": null,
+                    "division_number": null,
+                    "link": null,
+                    "margin": null,
+                    "noes": null,
+                    "outcome": "unknown",
+                    "result": null,
+                    "title": "Quota amendment"
+                }
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn division_outcomes_follow_the_majority_party_whip() {
+    let members = |party: &str, count: usize| vec![json!({"Party": party}); count];
+    let mut mock = common::MockParliamentDataSource::new();
+    mock.divisions = json!({
+        "items": [
+            {
+                "title": "Fisheries Bill: Second Reading",
+                "ayesCount": 40,
+                "noesCount": 22,
+                "Ayes": members("Harbour Party", 40),
+                "Noes": members("Net Party", 22)
+            },
+            {
+                "title": "Fisheries Bill: New Clause 2",
+                "ayesCount": 32,
+                "noesCount": 25,
+                "Ayes": ([members("Harbour Party", 2), members("Net Party", 30)].concat()),
+                "Noes": members("Harbour Party", 25)
+            },
+            {
+                "title": "Fisheries Bill: Amendment 7",
+                "ayesCount": 47,
+                "noesCount": 20,
+                "Ayes": ([members("Harbour Party", 25), members("Net Party", 22)].concat()),
+                "Noes": members("Harbour Party", 20)
+            }
+        ]
+    });
+    mock.parties = json!({
+        "items": [
+            {"party": "Harbour Party", "seats": 400},
+            {"party": "Net Party", "seats": 250}
+        ],
+        "totalSeats": 650
+    });
+
+    let response = run(mock, 5).await;
+    let outcomes: Vec<Value> = response
+        .votes
+        .iter()
+        .map(|vote| json!([vote.margin, vote.outcome]))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            json!([18, "government-win"]),
+            json!([7, "government-defeat"]),
+            json!([27, "free-vote"]),
+        ]
+    );
+}
+
+/// 500 parties of 300 keys each, none of them the probed aliases until the last few, so
+/// every alias lookup used to scan the whole item.
+fn wide_parties_mock() -> common::MockParliamentDataSource {
+    let parties: Vec<Value> = (0..500)
+        .map(|party| {
+            let mut item = serde_json::Map::new();
+            for key in 0..300 {
+                item.insert(format!("field{key:03}"), json!({"value": key}));
+            }
+            item.insert("Party".to_string(), json!(format!("Party {party}")));
+            item.insert("memberCount".to_string(), json!(party % 7));
+            Value::Object(item)
+        })
+        .collect();
+    let mut mock = common::MockParliamentDataSource::new();
+    mock.parties = json!({"parties": parties, "TotalSeats": 650});
+    mock
+}
+
+#[tokio::test]
+async fn wide_party_lists_parse_quickly() {
+    let mock = wide_parties_mock();
+    let started = std::time::Instant::now();
+    let response = run(mock, 5).await;
+    let elapsed = started.elapsed();
+
+    let parties = response.state_of_parties.expect("state of parties");
+    assert_eq!(parties.parties.len(), 500);
+    assert_eq!(parties.parties[499].name, "Party 499");
+    // Generous enough for an unoptimised build on a busy CI runner; the per-alias scans
+    // this replaced took several times longer on the same payload.
+    assert!(
+        elapsed < std::time::Duration::from_secs(5),
+        "parsing took {elapsed:?}"
+    );
+}
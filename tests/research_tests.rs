@@ -1,125 +1,15 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 
-use async_trait::async_trait;
 use serde_json::json;
-use tokio::sync::Mutex;
 
-use mp_writer_mcp_server::config::{AppConfig, CacheTtlConfig};
 use mp_writer_mcp_server::core::error::AppError;
+
 use mp_writer_mcp_server::features::research::{
-    ParliamentDataSource, ResearchRequestDto, ResearchService,
+    ParliamentDataSource, ResearchRequestDto, ResearchResponseDto, ResearchService, ResearchStatus,
+    SearchTermOutcome,
 };
 
-struct MockParliamentDataSource {
-    bills: serde_json::Value,
-    divisions: serde_json::Value,
-    legislation: serde_json::Value,
-    debates: serde_json::Value,
-    parties: serde_json::Value,
-    calls: Arc<Mutex<HashMap<String, usize>>>,
-}
-
-impl MockParliamentDataSource {
-    fn new() -> Self {
-        Self {
-            bills: json!({
-                "items": [
-                    {
-                        "title": "Climate Change Bill",
-                        "billStage": {"description": "Committee"},
-                        "lastUpdate": "2024-06-01",
-                        "billId": 123
-                    }
-                ]
-            }),
-            divisions: json!({
-                "items": [
-                    {
-                        "title": "Division on Climate",
-                        "divisionNumber": "12",
-                        "date": "2024-05-20",
-                        "ayes": 300,
-                        "noes": 200,
-                        "result": "Ayes",
-                        "uri": "https://example.com/division/12"
-                    }
-                ]
-            }),
-            legislation: json!({
-                "items": [
-                    {
-                        "title": "Climate Act",
-                        "year": "2008",
-                        "type": "ukpga",
-                        "uri": "https://www.legislation.gov.uk/id/ukpga/2008/27"
-                    }
-                ]
-            }),
-            debates: json!({
-                "items": [
-                    {
-                        "title": "Climate debate",
-                        "house": "Commons",
-                        "date": "2024-05-10",
-                        "summary": "Members discussed climate action.",
-                        "uri": "https://example.com/debate"
-                    }
-                ]
-            }),
-            parties: json!({
-                "items": [
-                    {"party": "Example Party", "seats": 300}
-                ],
-                "totalSeats": 650,
-                "lastUpdated": "2024-06-01"
-            }),
-            calls: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-
-    async fn record_call(&self, key: &str) {
-        let mut guard = self.calls.lock().await;
-        *guard.entry(key.to_string()).or_insert(0) += 1;
-    }
-
-    async fn count_for(&self, key: &str) -> usize {
-        let guard = self.calls.lock().await;
-        guard.get(key).copied().unwrap_or(0)
-    }
-}
-
-#[async_trait]
-impl ParliamentDataSource for MockParliamentDataSource {
-    async fn fetch_bills(
-        &self,
-        _args: mp_writer_mcp_server::features::parliament::FetchBillsArgs,
-    ) -> Result<serde_json::Value, AppError> {
-        self.record_call("bills").await;
-        Ok(self.bills.clone())
-    }
-
-    async fn fetch_core_dataset(
-        &self,
-        args: mp_writer_mcp_server::features::parliament::FetchCoreDatasetArgs,
-    ) -> Result<serde_json::Value, AppError> {
-        self.record_call(&args.dataset).await;
-        match args.dataset.as_str() {
-            "commonsdivisions" => Ok(self.divisions.clone()),
-            "commonsdebates" => Ok(self.debates.clone()),
-            "stateofparties" => Ok(self.parties.clone()),
-            _ => Ok(serde_json::Value::Null),
-        }
-    }
-
-    async fn fetch_legislation(
-        &self,
-        _args: mp_writer_mcp_server::features::parliament::FetchLegislationArgs,
-    ) -> Result<serde_json::Value, AppError> {
-        self.record_call("legislation").await;
-        Ok(self.legislation.clone())
-    }
-}
+mod common;
 
 #[tokio::test]
 async fn research_service_caches_results() {
@@ -127,26 +17,9 @@ async fn research_service_caches_results() {
     let db = sled::open(temp_dir.path()).expect("sled open");
     let tree = db.open_tree("research").expect("tree");
 
-    let config = Arc::new(AppConfig {
-        port: 0,
-        api_key: "test".to_string(),
-        disable_proxy: false,
-        cache_enabled: true,
-        relevance_threshold: 0.5,
-        cache_ttl: CacheTtlConfig {
-            members: 10,
-            bills: 10,
-            legislation: 10,
-            data: 10,
-            research: 3600,
-            activity: 10,
-            votes: 10,
-            constituency: 10,
-        },
-        db_path: temp_dir.path().to_string_lossy().to_string(),
-    });
-
-    let mock = Arc::new(MockParliamentDataSource::new());
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+
+    let mock = Arc::new(common::MockParliamentDataSource::new());
     let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
     let service = ResearchService::new(config, data_source, tree);
 
@@ -157,6 +30,9 @@ async fn research_service_caches_results() {
         mp_id: None,
         include_state_of_parties: true,
         limit: Some(3),
+        max_age_secs: None,
+        cache_only: false,
+        locale: None,
     };
 
     let first = service
@@ -164,6 +40,7 @@ async fn research_service_caches_results() {
         .await
         .expect("first call");
     assert!(!first.cached, "first call should not be cached");
+    assert_eq!(first.status, ResearchStatus::Ok);
     assert_eq!(first.bills.len(), 1);
     assert_eq!(first.votes.len(), 1);
     assert_eq!(first.legislation.len(), 1);
@@ -184,56 +61,373 @@ async fn research_service_caches_results() {
     assert_eq!(mock.count_for("legislation").await, 1);
 }
 
+async fn run_with_mock(mock: common::MockParliamentDataSource) -> ResearchResponseDto {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let data_source: Arc<dyn ParliamentDataSource> = Arc::new(mock);
+    let service = ResearchService::new(config, data_source, db.open_tree("research").unwrap());
+
+    service
+        .run_research(ResearchRequestDto {
+            topic: "Climate action".to_string(),
+            bill_keywords: vec![],
+            debate_keywords: vec![],
+            mp_id: None,
+            include_state_of_parties: false,
+            limit: Some(3),
+            max_age_secs: None,
+            cache_only: false,
+            locale: None,
+        })
+        .await
+        .expect("research should succeed")
+}
+
 #[tokio::test]
-async fn test_search_uk_law_endpoint() {
+async fn research_status_is_partial_when_some_sources_fail() {
+    let response = run_with_mock(common::MockParliamentDataSource::new().failing(&["bills"])).await;
+
+    assert_eq!(response.status, ResearchStatus::Partial);
+    assert!(response.bills.is_empty());
+    assert_eq!(response.votes.len(), 1);
+    assert!(response.summary.starts_with("Key research findings"));
+}
+
+#[tokio::test]
+async fn research_status_is_empty_when_sources_return_nothing() {
+    let mut mock = common::MockParliamentDataSource::new();
+    mock.bills = json!({"items": []});
+    mock.divisions = json!({"items": []});
+    mock.legislation = json!({"items": []});
+    mock.debates = json!({"items": []});
+
+    let response = run_with_mock(mock).await;
+
+    assert_eq!(response.status, ResearchStatus::Empty);
+    assert!(response.summary.starts_with("Research unavailable"));
+    assert!(
+        response
+            .summary
+            .contains("Sources that returned no matches: bills, votes, legislation, debates")
+    );
+    assert!(!response.summary.contains("failed to respond"));
+    assert!(response.summary.contains("Broaden the keywords"));
+    assert!(!response.summary.contains("Note:"));
+}
+
+#[tokio::test]
+async fn research_status_is_failed_when_every_source_fails() {
+    let mock = common::MockParliamentDataSource::new().failing(&[
+        "bills",
+        "commonsdivisions",
+        "legislation",
+        "commonsdebates",
+    ]);
+
+    let response = run_with_mock(mock).await;
+
+    assert_eq!(response.status, ResearchStatus::Failed);
+    assert!(
+        response
+            .summary
+            .contains("Sources that failed to respond: bills, votes, legislation, debates")
+    );
+    assert!(response.summary.contains("Retry in a few minutes"));
+    assert!(!response.summary.contains("returned no matches"));
+    assert!(!response.advisories.is_empty());
+}
+
+#[tokio::test]
+async fn research_status_separates_failed_and_empty_sources() {
+    let mut mock = common::MockParliamentDataSource::new().failing(&["bills", "commonsdivisions"]);
+    mock.legislation = json!({"items": []});
+    mock.debates = json!({"items": []});
+
+    let response = run_with_mock(mock).await;
+
+    assert_eq!(response.status, ResearchStatus::Empty);
+    assert!(
+        response
+            .summary
+            .contains("Sources that failed to respond: bills, votes")
+    );
+    assert!(
+        response
+            .summary
+            .contains("Sources that returned no matches: legislation, debates")
+    );
+    assert!(response.summary.contains("Retry in a few minutes"));
+    assert!(response.summary.contains("Check the spelling"));
+}
+
+fn max_age_request(max_age_secs: Option<u64>) -> ResearchRequestDto {
+    ResearchRequestDto {
+        topic: "Climate action".to_string(),
+        bill_keywords: vec![],
+        debate_keywords: vec![],
+        mp_id: None,
+        include_state_of_parties: false,
+        limit: Some(3),
+        max_age_secs,
+        cache_only: false,
+        locale: None,
+    }
+}
+
+/// Move every cached research entry `secs` into the past.
+fn age_cache_entries(tree: &sled::Tree, secs: u64) {
+    for item in tree.iter() {
+        let (key, value) = item.expect("cache entry");
+        let mut entry: serde_json::Value = serde_json::from_slice(&value).expect("decode entry");
+        for field in ["stored_at", "steady_at"] {
+            let stamp = entry[field].as_u64().expect(field);
+            entry[field] = json!(stamp - secs);
+        }
+        tree.insert(key, serde_json::to_vec(&entry).expect("encode entry"))
+            .expect("rewrite entry");
+    }
+}
+
+#[tokio::test]
+async fn max_age_serves_young_entries_and_recomputes_stale_ones() {
     let temp_dir = tempfile::tempdir().expect("temp dir");
     let db = sled::open(temp_dir.path()).expect("sled open");
-    let tree = db.open_tree("test").expect("tree");
-
-    let config = Arc::new(AppConfig {
-        port: 0,
-        api_key: "test".to_string(),
-        disable_proxy: false,
-        cache_enabled: true,
-        relevance_threshold: 0.5,
-        cache_ttl: CacheTtlConfig {
-            members: 10,
-            bills: 10,
-            legislation: 10,
-            data: 10,
-            research: 3600,
-            activity: 10,
-            votes: 10,
-            constituency: 10,
-        },
-        db_path: temp_dir.path().to_string_lossy().to_string(),
-    });
-
-    let _mock = Arc::new(MockParliamentDataSource::new());
-    let cache_manager = mp_writer_mcp_server::core::cache::CacheManager::new(true, 1000);
-    let client = mp_writer_mcp_server::features::parliament::ParliamentClient::new(
-        config.clone(),
-        cache_manager,
-        tree.clone(),
-    )
-    .expect("client creation should succeed");
-
-    let search_args = mp_writer_mcp_server::features::parliament::SearchUkLawArgs {
-        query: "climate change".to_string(),
-        legislation_type: Some("all".to_string()),
-        limit: Some(5),
-        enable_cache: Some(false),
-    };
+    let tree = db.open_tree("research").expect("tree");
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let mock = Arc::new(common::MockParliamentDataSource::new());
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let service = ResearchService::new(config, data_source, tree.clone());
 
-    let results = client
-        .search_uk_law(search_args)
+    let fresh = service
+        .run_research(max_age_request(None))
         .await
-        .expect("search should succeed");
-
-    // The real implementation creates a fallback result when the API fails
-    assert_eq!(results.len(), 1);
-    assert!(results[0].title.contains("climate change"));
-    assert_eq!(results[0].legislation_type, "Primary");
-    assert!(results[0].is_in_force);
-    assert!(results[0].url.contains("legislation.gov.uk"));
+        .expect("first run");
+    assert!(!fresh.cached);
+    assert!(fresh.cached_at.is_none() && fresh.age_secs.is_none());
+    let calls_after_first = mock.total_calls().await;
+
+    age_cache_entries(&tree, 120);
+
+    let served = service
+        .run_research(max_age_request(Some(600)))
+        .await
+        .expect("cached run");
+    assert!(served.cached);
+    assert!(served.age_secs.expect("age") >= 120);
+    assert!(served.cached_at.is_some());
+    assert_eq!(mock.total_calls().await, calls_after_first);
+
+    let recomputed = service
+        .run_research(max_age_request(Some(60)))
+        .await
+        .expect("recomputed run");
+    assert!(!recomputed.cached);
+    assert!(mock.total_calls().await > calls_after_first);
+
+    let restored = service
+        .run_research(max_age_request(Some(60)))
+        .await
+        .expect("re-stored entry");
+    assert!(restored.cached);
+    assert!(restored.age_secs.expect("age") < 60);
+}
+
+#[tokio::test]
+async fn max_age_must_be_positive_and_within_the_ttl() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let ttl = config.cache_ttl.research;
+    let data_source: Arc<dyn ParliamentDataSource> =
+        Arc::new(common::MockParliamentDataSource::new());
+    let service = ResearchService::new(config, data_source, db.open_tree("research").unwrap());
+
+    for max_age in [0, ttl + 1] {
+        let error = service
+            .run_research(max_age_request(Some(max_age)))
+            .await
+            .expect_err("maxAgeSecs should be rejected");
+        assert!(
+            matches!(error, AppError::BadRequest { .. }),
+            "unexpected error for {max_age}: {error:?}"
+        );
+    }
+}
+
+fn all_sources_failing() -> Arc<common::MockParliamentDataSource> {
+    Arc::new(common::MockParliamentDataSource::new().failing(&[
+        "bills",
+        "commonsdivisions",
+        "legislation",
+        "commonsdebates",
+    ]))
+}
+
+/// Cache a healthy result, age it by `age_secs`, then return a service over the same tree
+/// whose upstreams all fail.
+async fn failing_service_with_aged_entry(
+    temp_dir: &tempfile::TempDir,
+    tree: &sled::Tree,
+    age_secs: u64,
+    mock: Arc<common::MockParliamentDataSource>,
+) -> ResearchService {
+    let config = Arc::new(common::test_config(&temp_dir.path().to_string_lossy()));
+    let healthy: Arc<dyn ParliamentDataSource> = Arc::new(common::MockParliamentDataSource::new());
+    ResearchService::new(config.clone(), healthy, tree.clone())
+        .run_research(max_age_request(None))
+        .await
+        .expect("healthy run");
+    age_cache_entries(tree, age_secs);
+
+    let data_source: Arc<dyn ParliamentDataSource> = mock;
+    ResearchService::new(config, data_source, tree.clone())
+}
+
+#[tokio::test]
+async fn expired_entry_is_served_stale_when_every_source_fails() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let tree = db.open_tree("research").expect("tree");
+    let mock = all_sources_failing();
+    let service = failing_service_with_aged_entry(&temp_dir, &tree, 7_200, mock.clone()).await;
+
+    let response = service
+        .run_research(max_age_request(None))
+        .await
+        .expect("stale run");
+
+    assert!(response.cached && response.stale);
+    assert_eq!(response.status, ResearchStatus::Ok);
+    assert!(!response.bills.is_empty());
+    assert!(response.age_secs.expect("age") >= 7_200);
+    assert!(
+        response.advisories[0].contains("every source failed")
+            && response.advisories[0].contains("background refresh"),
+        "unexpected advisory: {}",
+        response.advisories[0]
+    );
+
+    // The scheduled refresh retries the upstreams but, still failing, keeps the entry.
+    let calls_after_run = mock.total_calls().await;
+    for _ in 0..50 {
+        if mock.total_calls().await > calls_after_run {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(mock.total_calls().await > calls_after_run);
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let again = service
+        .run_research(max_age_request(None))
+        .await
+        .expect("second stale run");
+    assert!(again.stale);
+    assert!(!again.bills.is_empty());
+}
+
+#[tokio::test]
+async fn entries_beyond_max_stale_are_not_served() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let tree = db.open_tree("research").expect("tree");
+    let service =
+        failing_service_with_aged_entry(&temp_dir, &tree, 172_800, all_sources_failing()).await;
+
+    let response = service
+        .run_research(max_age_request(None))
+        .await
+        .expect("degraded run");
+
+    assert!(!response.cached && !response.stale);
+    assert_eq!(response.status, ResearchStatus::Failed);
+}
+
+#[tokio::test]
+async fn search_terms_are_capped_with_keywords_before_expansions() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let mut config = common::test_config(&temp_dir.path().to_string_lossy());
+    config.research_max_terms = 4;
+
+    let mut mock = common::MockParliamentDataSource::new().failing(&["legislation"]);
+    mock.bills = json!({"items": []});
+    let mock = Arc::new(mock);
+    let data_source: Arc<dyn ParliamentDataSource> = mock.clone();
+    let service = ResearchService::new(
+        Arc::new(config),
+        data_source,
+        db.open_tree("research").unwrap(),
+    );
+
+    let response = service
+        .run_research(ResearchRequestDto {
+            topic: "Climate action".to_string(),
+            bill_keywords: vec!["Net zero".to_string(), "carbon tax".to_string()],
+            debate_keywords: vec![],
+            mp_id: None,
+            include_state_of_parties: false,
+            limit: Some(3),
+            max_age_secs: None,
+            cache_only: false,
+            locale: None,
+        })
+        .await
+        .expect("research should succeed");
+
+    let tried = ["climate action", "net zero", "carbon tax", "climate"];
+    let skipped = ["action", "net", "zero", "carbon", "tax"];
+    let report = |collection: &str| -> Vec<(String, SearchTermOutcome)> {
+        response.provenance.search_terms_used[collection]
+            .iter()
+            .map(|entry| (entry.term.clone(), entry.outcome))
+            .collect()
+    };
+    let expected = |outcomes: &[(&str, SearchTermOutcome)]| -> Vec<(String, SearchTermOutcome)> {
+        outcomes
+            .iter()
+            .map(|(term, outcome)| (term.to_string(), *outcome))
+            .chain(
+                skipped
+                    .iter()
+                    .map(|term| (term.to_string(), SearchTermOutcome::SkippedOverBudget)),
+            )
+            .collect()
+    };
+
+    let bill_attempts: Vec<String> = tried
+        .iter()
+        .flat_map(|term| [term.to_string(), term.to_string()])
+        .collect();
+    assert_eq!(mock.search_terms_for("bills").await, bill_attempts);
+    assert_eq!(
+        report("bills"),
+        expected(&tried.map(|term| (term, SearchTermOutcome::Empty)))
+    );
+    assert_eq!(
+        report("legislation"),
+        expected(&tried.map(|term| (term, SearchTermOutcome::Error)))
+    );
+    assert_eq!(
+        report("votes"),
+        expected(&[("climate action", SearchTermOutcome::Hit)])
+    );
+    assert_eq!(
+        mock.search_terms_for("commonsdivisions").await,
+        ["climate action"]
+    );
+
+    let debates = &response.provenance.search_terms_used["debates"];
+    assert_eq!(
+        debates.len(),
+        1,
+        "debates only search the topic: {debates:?}"
+    );
+
+    let rendered = serde_json::to_value(&response.provenance).expect("serialise provenance");
+    assert_eq!(
+        rendered["searchTermsUsed"]["bills"][4],
+        json!({"term": "action", "outcome": "skipped-over-budget"})
+    );
 }
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::features::mcp::dto::ToolContent;
+use mp_writer_mcp_server::features::mcp::links::extract_resource_links;
+use mp_writer_mcp_server::features::research::ParliamentDataSource;
+
+mod common;
+
+fn link_uris(links: &[ToolContent]) -> Vec<String> {
+    links
+        .iter()
+        .map(|link| match link {
+            ToolContent::ResourceLink { uri, .. } => uri.clone(),
+            ToolContent::Text { .. } => panic!("expected only resource links"),
+        })
+        .collect()
+}
+
+#[test]
+fn known_link_fields_become_resource_links() {
+    let payload = json!({
+        "items": [
+            {"title": "Climate Act", "url": "https://www.legislation.gov.uk/ukpga/2008/27"},
+            {"title": "Duplicate", "url": "https://www.legislation.gov.uk/ukpga/2008/27"},
+            {"title": "Relative", "url": "/ukpga/2010/1"},
+            {"name": "Untitled", "uri": "https://ignored.example"}
+        ]
+    });
+
+    let links = extract_resource_links("parliament.search_uk_law", &payload, 10);
+    assert_eq!(
+        link_uris(&links),
+        ["https://www.legislation.gov.uk/ukpga/2008/27"]
+    );
+
+    let encoded = serde_json::to_value(&links[0]).expect("encode");
+    assert_eq!(encoded["type"], "resource_link");
+    assert_eq!(encoded["title"], "Climate Act");
+    assert_eq!(encoded["name"], "Climate Act");
+    assert_eq!(encoded["mimeType"], "text/html");
+}
+
+#[test]
+fn links_are_capped_and_limited_to_known_tools() {
+    let payload = json!({
+        "items": (0..5)
+            .map(|index| json!({"uri": format!("https://example.com/{index}")}))
+            .collect::<Vec<_>>()
+    });
+
+    let links = extract_resource_links("parliament.fetch_legislation", &payload, 3);
+    assert_eq!(links.len(), 3);
+    let first = serde_json::to_value(&links[0]).expect("encode");
+    assert_eq!(
+        first["title"], "https://example.com/0",
+        "falls back to the URL"
+    );
+
+    assert!(extract_resource_links("parliament.fetch_legislation", &payload, 0).is_empty());
+    assert!(extract_resource_links("utilities.current_datetime", &payload, 10).is_empty());
+}
+
+#[tokio::test]
+async fn research_results_keep_text_first_and_append_links() {
+    let mock: Arc<dyn ParliamentDataSource> = Arc::new(common::MockParliamentDataSource::new());
+    let harness = common::build_service(Some(mock), |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "climate"}),
+    )
+    .await;
+    let content = response["result"]["content"]
+        .as_array()
+        .expect("content blocks");
+
+    assert_eq!(content[0]["type"], "text");
+    let rendered: Value =
+        serde_json::from_str(content[0]["text"].as_str().expect("text")).expect("json text");
+    assert!(rendered["summary"].is_string());
+
+    let links: Vec<&Value> = content
+        .iter()
+        .filter(|block| block["type"] == "resource_link")
+        .collect();
+    let uris: Vec<&str> = links
+        .iter()
+        .filter_map(|block| block["uri"].as_str())
+        .collect();
+    assert!(uris.contains(&"https://bills.parliament.uk/bills/123"));
+    assert!(uris.contains(&"https://www.legislation.gov.uk/id/ukpga/2008/27"));
+    assert!(uris.contains(&"https://example.com/division/12"));
+    let bill = links
+        .iter()
+        .find(|block| block["uri"] == "https://bills.parliament.uk/bills/123")
+        .expect("bill link");
+    assert_eq!(bill["title"], "Climate Change Bill");
+}
@@ -0,0 +1,108 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+async fn call_with_meta(
+    harness: &common::TestHarness,
+    name: &str,
+    arguments: Value,
+    meta: Value,
+) -> Value {
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(7)),
+                "tools/call",
+                Some(json!({"name": name, "arguments": arguments, "_meta": meta})),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("tools/call should succeed")
+        .expect("response");
+    serde_json::to_value(response).expect("encode success")
+}
+
+#[tokio::test]
+async fn client_trace_fields_are_echoed_with_timing() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = call_with_meta(
+        &harness,
+        "utilities.current_datetime",
+        json!({}),
+        json!({"traceId": "abc-123", "sampled": true, "progressToken": "p1", "baggage": {"k": "v"}}),
+    )
+    .await;
+    let meta = &response["result"]["_meta"];
+
+    assert_eq!(meta["traceId"], "abc-123", "meta: {meta}");
+    assert_eq!(meta["sampled"], true);
+    assert!(meta.get("progressToken").is_none());
+    assert!(meta.get("baggage").is_none());
+    assert!(meta["durationMs"].is_u64());
+    assert!(meta["correlationId"].is_string());
+    assert!(meta.get("cached").is_none(), "no lookups were made");
+}
+
+#[tokio::test]
+async fn server_fields_win_over_client_fields() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = call_with_meta(
+        &harness,
+        "utilities.current_datetime",
+        json!({}),
+        json!({"correlationId": "spoofed", "durationMs": -1}),
+    )
+    .await;
+    let meta = &response["result"]["_meta"];
+
+    assert_ne!(meta["correlationId"], "spoofed");
+    assert!(meta["durationMs"].is_u64());
+}
+
+#[tokio::test]
+async fn cache_status_follows_the_underlying_fetch() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/commonsdivisions.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let arguments = json!({"dataset": "commonsdivisions", "enableCache": true});
+    let first = call_with_meta(
+        &harness,
+        "parliament.fetch_core_dataset",
+        arguments.clone(),
+        json!({"traceId": "t-1"}),
+    )
+    .await;
+    assert_eq!(
+        first["result"]["_meta"]["cached"], false,
+        "response: {first}"
+    );
+    assert_eq!(first["result"]["_meta"]["traceId"], "t-1");
+
+    let second = call_with_meta(
+        &harness,
+        "parliament.fetch_core_dataset",
+        arguments,
+        json!({"traceId": "t-2"}),
+    )
+    .await;
+    assert_eq!(
+        second["result"]["_meta"]["cached"], true,
+        "response: {second}"
+    );
+    assert_eq!(second["result"]["_meta"]["traceId"], "t-2");
+}
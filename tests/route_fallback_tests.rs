@@ -0,0 +1,152 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Method, Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+mod common;
+
+fn ping() -> String {
+    json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}).to_string()
+}
+
+/// Status, `Allow` header and JSON body of `request`.
+async fn send(request: Request<Body>) -> (StatusCode, Option<String>, Value) {
+    let harness = common::build_service(None, |_| {});
+    let response = harness.router().oneshot(request).await.unwrap();
+    let status = response.status();
+    let allow = response
+        .headers()
+        .get(header::ALLOW)
+        .map(|value| value.to_str().unwrap().to_string());
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/json",
+        "{status}"
+    );
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, allow, serde_json::from_slice(&body).unwrap())
+}
+
+fn request(method: Method, path: &str, content_type: Option<&str>, body: String) -> Request<Body> {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(path)
+        .header("x-api-key", "test")
+        .header("MCP-Protocol-Version", common::PROTOCOL_VERSION);
+    if let Some(content_type) = content_type {
+        request = request.header(header::CONTENT_TYPE, content_type);
+    }
+    request.body(Body::from(body)).unwrap()
+}
+
+#[tokio::test]
+async fn get_without_event_stream_explains_how_to_send_requests() {
+    let (status, _, body) = send(request(Method::GET, "/api/mcp", None, String::new())).await;
+
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(body["jsonrpc"], "2.0");
+    assert_eq!(body["id"], Value::Null);
+    assert_eq!(body["error"]["code"], -32600);
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("text/event-stream"),
+        "{body}"
+    );
+    assert!(
+        body["error"]["data"]["hint"]
+            .as_str()
+            .unwrap()
+            .contains("POST application/json"),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn mistyped_paths_suggest_the_mcp_endpoint() {
+    let (status, _, body) = send(request(
+        Method::POST,
+        "/api/mpc",
+        Some("application/json"),
+        ping(),
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], -32600);
+    assert_eq!(body["error"]["message"], "no route for POST /api/mpc");
+    assert_eq!(
+        body["error"]["data"]["hint"],
+        "the MCP endpoint only accepts POST application/json; did you mean /api/mcp?"
+    );
+
+    let (status, _, body) = send(request(Method::GET, "/api/versoin", None, String::new())).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["data"]["hint"], "did you mean /api/version?");
+
+    let (_, _, body) = send(request(Method::GET, "/index.html", None, String::new())).await;
+    assert_eq!(
+        body["error"]["data"]["hint"],
+        "MCP requests are sent as POST application/json to /api/mcp"
+    );
+}
+
+#[tokio::test]
+async fn unsupported_methods_get_a_json_405_with_allow() {
+    let (status, allow, body) = send(request(
+        Method::PUT,
+        "/api/mcp",
+        Some("application/json"),
+        ping(),
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    let allow = allow.expect("Allow header");
+    for method in ["POST", "GET", "DELETE"] {
+        assert!(allow.contains(method), "{allow}");
+    }
+    assert_eq!(body["error"]["code"], -32600);
+    assert_eq!(body["error"]["message"], "PUT is not allowed on /api/mcp");
+}
+
+#[tokio::test]
+async fn non_json_posts_are_refused_before_parsing() {
+    let (status, _, body) = send(request(
+        Method::POST,
+        "/api/mcp",
+        Some("text/plain"),
+        ping(),
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["error"]["code"], -32600);
+    assert_eq!(
+        body["error"]["message"],
+        "unsupported request: Content-Type `text/plain`"
+    );
+    assert_eq!(
+        body["error"]["data"]["hint"],
+        "the MCP endpoint only accepts POST application/json"
+    );
+
+    let (status, _, body) = send(request(Method::POST, "/api/mcp", None, ping())).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(
+        body["error"]["message"],
+        "unsupported request: no Content-Type"
+    );
+
+    // JSON that does not parse is a JSON-RPC parse error.
+    let (status, _, body) = send(request(
+        Method::POST,
+        "/api/mcp",
+        Some("application/json"),
+        "{\"jsonrpc\": ".to_string(),
+    ))
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], -32700);
+}
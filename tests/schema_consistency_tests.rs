@@ -0,0 +1,322 @@
+//! Every tool's arguments pass through two layers: the JSON Schema published in `tools/list`
+//! and the serde DTO the handler reads. These tests feed both the same edge cases (each
+//! property missing, `null`, and numbers on either side of their bounds) and require the
+//! same verdict, so which error a caller sees never depends on which layer ran first.
+
+use jsonschema::JSONSchema;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::features::audit::ReplayCallArgs;
+use mp_writer_mcp_server::features::mcp::schemas::{build_tool_schemas, drop_null_optionals};
+use mp_writer_mcp_server::features::parliament::{
+    ExportVotingRecordArgs, FetchBillDetailArgs, FetchBillDivisionsArgs, FetchBillsArgs,
+    FetchCommitteesArgs, FetchCoreDatasetArgs, FetchDivisionDetailArgs, FetchEdmsArgs,
+    FetchLegislationArgs, FetchLegislationTextArgs, FetchMemberRolesArgs, FetchMembersBulkArgs,
+    FetchMpActivityArgs, FetchMpVotingRecordArgs, FetchOralQuestionsArgs,
+    FetchStatutoryInstrumentsArgs, FetchWrittenQuestionsArgs, ListConstituenciesArgs,
+    LookupConstituencyArgs, SearchHansardArgs, SearchUkLawArgs,
+};
+use mp_writer_mcp_server::features::preferences::SetKeyPreferencesArgs;
+use mp_writer_mcp_server::features::reference_data::RefreshReferenceDataArgs;
+use mp_writer_mcp_server::features::research::{
+    ListCachedResearchArgs, ResearchRequestDto, SuggestKeywordsArgs,
+};
+use mp_writer_mcp_server::features::usage::UsageSummaryArgs;
+use mp_writer_mcp_server::features::utilities::VerifySignatureArgs;
+
+type Parse = fn(Value) -> Result<(), String>;
+
+fn parse<T: DeserializeOwned>(value: Value) -> Result<(), String> {
+    serde_json::from_value::<T>(value)
+        .map(drop)
+        .map_err(|err| err.to_string())
+}
+
+/// The DTO each tool deserialises its arguments into; `None` for tools without arguments.
+/// A tool missing here fails `every_tool_has_an_argument_parser`.
+const PARSERS: &[(&str, Option<Parse>)] = &[
+    (
+        "parliament.fetch_core_dataset",
+        Some(parse::<FetchCoreDatasetArgs>),
+    ),
+    ("parliament.fetch_bills", Some(parse::<FetchBillsArgs>)),
+    (
+        "parliament.fetch_bill_divisions",
+        Some(parse::<FetchBillDivisionsArgs>),
+    ),
+    (
+        "parliament.fetch_legislation",
+        Some(parse::<FetchLegislationArgs>),
+    ),
+    (
+        "parliament.fetch_mp_activity",
+        Some(parse::<FetchMpActivityArgs>),
+    ),
+    (
+        "parliament.fetch_members_bulk",
+        Some(parse::<FetchMembersBulkArgs>),
+    ),
+    (
+        "parliament.fetch_mp_voting_record",
+        Some(parse::<FetchMpVotingRecordArgs>),
+    ),
+    (
+        "parliament.fetch_committees",
+        Some(parse::<FetchCommitteesArgs>),
+    ),
+    (
+        "parliament.fetch_bill_detail",
+        Some(parse::<FetchBillDetailArgs>),
+    ),
+    (
+        "parliament.fetch_legislation_text",
+        Some(parse::<FetchLegislationTextArgs>),
+    ),
+    (
+        "parliament.fetch_member_roles",
+        Some(parse::<FetchMemberRolesArgs>),
+    ),
+    (
+        "parliament.fetch_division_detail",
+        Some(parse::<FetchDivisionDetailArgs>),
+    ),
+    ("parliament.fetch_edms", Some(parse::<FetchEdmsArgs>)),
+    (
+        "parliament.fetch_oral_questions",
+        Some(parse::<FetchOralQuestionsArgs>),
+    ),
+    (
+        "parliament.fetch_statutory_instruments",
+        Some(parse::<FetchStatutoryInstrumentsArgs>),
+    ),
+    (
+        "parliament.fetch_written_questions",
+        Some(parse::<FetchWrittenQuestionsArgs>),
+    ),
+    (
+        "parliament.export_voting_record",
+        Some(parse::<ExportVotingRecordArgs>),
+    ),
+    (
+        "parliament.lookup_constituency_offline",
+        Some(parse::<LookupConstituencyArgs>),
+    ),
+    (
+        "parliament.list_constituencies",
+        Some(parse::<ListConstituenciesArgs>),
+    ),
+    (
+        "parliament.search_hansard",
+        Some(parse::<SearchHansardArgs>),
+    ),
+    ("parliament.search_uk_law", Some(parse::<SearchUkLawArgs>)),
+    ("research.run", Some(parse::<ResearchRequestDto>)),
+    (
+        "research.list_cached",
+        Some(parse::<ListCachedResearchArgs>),
+    ),
+    (
+        "research.suggest_keywords",
+        Some(parse::<SuggestKeywordsArgs>),
+    ),
+    ("utilities.current_datetime", None),
+    (
+        "utilities.verify_signature",
+        Some(parse::<VerifySignatureArgs>),
+    ),
+    ("utilities.error_catalogue", None),
+    ("admin.tool_stats", None),
+    ("admin.usage_summary", Some(parse::<UsageSummaryArgs>)),
+    (
+        "admin.set_key_preferences",
+        Some(parse::<SetKeyPreferencesArgs>),
+    ),
+    ("admin.replay_call", Some(parse::<ReplayCallArgs>)),
+    (
+        "admin.refresh_reference_data",
+        Some(parse::<RefreshReferenceDataArgs>),
+    ),
+];
+
+/// A value `schema` accepts: the first `const`, `enum`, example, `anyOf` or `oneOf` branch, the
+/// lower bound of numbers and lengths, and every declared property of objects.
+fn sample(schema: &Value) -> Value {
+    if let Some(value) = schema.get("const") {
+        return value.clone();
+    }
+    if let Some(first) = schema["examples"]
+        .as_array()
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+    if let Some(first) = schema["enum"].as_array().and_then(|values| values.first()) {
+        return first.clone();
+    }
+    if let Some(first) = branches(schema).first() {
+        if first.get("type").is_some() {
+            return sample(first);
+        }
+    }
+
+    let kind = match &schema["type"] {
+        Value::Array(kinds) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null")
+            .unwrap_or("null"),
+        kind => kind.as_str().unwrap_or("object"),
+    };
+    match kind {
+        "string" => match schema["format"].as_str() {
+            Some("date") => json!("2024-01-31"),
+            Some("uri") => json!("https://example.org/"),
+            _ => {
+                let length = schema["minLength"].as_u64().unwrap_or(1).max(1) as usize;
+                json!("x".repeat(length))
+            }
+        },
+        "integer" => json!(schema["minimum"].as_i64().unwrap_or(1)),
+        "number" => json!(schema["minimum"].as_f64().unwrap_or(0.5)),
+        "boolean" => json!(true),
+        "array" => {
+            let count = schema["minItems"].as_u64().unwrap_or(1).max(1);
+            let count = schema["maxItems"]
+                .as_u64()
+                .map_or(count, |max| count.min(max));
+            Value::Array((0..count).map(|_| sample(&schema["items"])).collect())
+        }
+        "object" => Value::Object(
+            schema["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| (name.clone(), sample(property)))
+                .collect(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+/// Variants of `document`, each labelled, with one property at `pointer` (or below it)
+/// removed, set to `null`, moved to a numeric bound, or off its string pattern.
+fn variants(schema: &Value, document: &Value, pointer: &str, out: &mut Vec<(String, Value)>) {
+    let Some(properties) = schema["properties"].as_object() else {
+        return;
+    };
+    for (name, property) in properties {
+        let path = format!("{pointer}/{name}");
+        let mut with = |label: &str, value: Option<Value>| {
+            let mut variant = document.clone();
+            let parent = variant
+                .pointer_mut(if pointer.is_empty() { "" } else { pointer })
+                .and_then(Value::as_object_mut)
+                .expect("parent object");
+            match value {
+                Some(value) => parent.insert(name.clone(), value),
+                None => parent.remove(name),
+            };
+            out.push((format!("{path} {label}"), variant));
+        };
+
+        with("missing", None);
+        with("null", Some(Value::Null));
+        // Ids also accept strings (see `features::mcp::ids`); their bounds are on the
+        // integer branch.
+        let numeric = branches(property)
+            .iter()
+            .find(|branch| branch["type"] == "integer")
+            .unwrap_or(property);
+        let integer = numeric["type"] == "integer";
+        if let Some(minimum) = numeric["minimum"].as_f64() {
+            with("at minimum", Some(bound(minimum, 0.0, integer)));
+            with("below minimum", Some(bound(minimum, -1.0, integer)));
+        }
+        if let Some(maximum) = numeric["maximum"].as_f64() {
+            with("at maximum", Some(bound(maximum, 0.0, integer)));
+            with("above maximum", Some(bound(maximum, 1.0, integer)));
+        }
+
+        if property["pattern"].is_string() {
+            with("off pattern", Some(json!("x")));
+        }
+
+        if property["properties"].is_object() {
+            variants(property, document, &path, out);
+        }
+    }
+}
+
+/// The `anyOf` or `oneOf` branches of `schema`, if any.
+fn branches(schema: &Value) -> &[Value] {
+    schema["anyOf"]
+        .as_array()
+        .or_else(|| schema["oneOf"].as_array())
+        .map_or(&[], Vec::as_slice)
+}
+
+fn bound(limit: f64, offset: f64, integer: bool) -> Value {
+    if integer {
+        json!(limit as i64 + offset as i64)
+    } else {
+        json!(limit + offset / 2.0)
+    }
+}
+
+#[test]
+fn every_tool_has_an_argument_parser() {
+    let (definitions, _) = build_tool_schemas();
+    let missing: Vec<&str> = definitions
+        .iter()
+        .map(|definition| definition.name.as_str())
+        .filter(|name| !PARSERS.iter().any(|(tool, _)| tool == name))
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "add these tools to PARSERS: {missing:?}"
+    );
+}
+
+#[test]
+fn schema_and_dto_agree_on_edge_case_arguments() {
+    let (_, input_schemas) = build_tool_schemas();
+    let mut disagreements = Vec::new();
+
+    for (tool, parser) in PARSERS {
+        let (Some(schema), Some(parser)) = (input_schemas.get(*tool), parser) else {
+            continue;
+        };
+        let validator = JSONSchema::compile(schema).expect("input schema compiles");
+        let full = sample(schema);
+        assert!(
+            validator.is_valid(&full),
+            "{tool}: the sample document {full} must satisfy the schema"
+        );
+
+        let mut cases = vec![("complete".to_string(), full.clone())];
+        variants(schema, &full, "", &mut cases);
+        for (label, mut document) in cases {
+            drop_null_optionals(schema, &mut document);
+            let schema_ok = validator.is_valid(&document);
+            let serde = parser(document.clone());
+            if schema_ok != serde.is_ok() {
+                disagreements.push(format!(
+                    "{tool} {label}: schema {}, serde {} ({document})",
+                    verdict(schema_ok),
+                    serde.err().unwrap_or_else(|| "accepts".to_string()),
+                ));
+            }
+        }
+    }
+
+    assert!(
+        disagreements.is_empty(),
+        "argument schema and DTO disagree:\n{}",
+        disagreements.join("\n")
+    );
+}
+
+fn verdict(accepted: bool) -> &'static str {
+    if accepted { "accepts" } else { "rejects" }
+}
@@ -0,0 +1,140 @@
+use chrono::NaiveDate;
+use jsonschema::JSONSchema;
+use serde_json::json;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::features::parliament::SessionRef;
+use mp_writer_mcp_server::features::parliament::sessions::{
+    SESSION_PATTERN, current_session_start,
+};
+
+mod common;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn session_variants_normalise_to_the_canonical_form() {
+    let today = date(2025, 3, 1);
+    for (raw, expected) in [
+        ("2024-25", "2024-25"),
+        ("2024/25", "2024-25"),
+        ("2024–25", "2024-25"),
+        ("2024-2025", "2024-25"),
+        ("2024/2025", "2024-25"),
+        (" 2023-24 ", "2023-24"),
+        ("1999-00", "1999-00"),
+        ("1999-2000", "1999-00"),
+        ("current", "2024-25"),
+        ("Current", "2024-25"),
+        ("PREVIOUS", "2023-24"),
+    ] {
+        let session = SessionRef::parse(raw).unwrap_or_else(|err| panic!("{raw}: {err}"));
+        assert_eq!(session.resolve(today).unwrap(), expected, "{raw}");
+    }
+}
+
+#[test]
+fn malformed_sessions_are_rejected_with_the_expected_format() {
+    for (raw, reason) in [
+        ("24-25", "expected \"YYYY-YY\""),
+        ("2024", "expected \"YYYY-YY\""),
+        ("2024-5", "expected \"YYYY-YY\""),
+        ("2024 - 25", "expected \"YYYY-YY\""),
+        ("last", "expected \"YYYY-YY\""),
+        ("2024-26", "consecutive, e.g. \"2024-25\""),
+        ("2024-2026", "consecutive"),
+        ("2025-24", "consecutive"),
+        ("1700-01", "before 1801-02"),
+    ] {
+        let error = SessionRef::parse(raw).expect_err(raw);
+        assert!(error.contains(reason), "{raw}: {error}");
+        assert!(
+            error.contains(&format!("\"{}\"", raw.trim())),
+            "{raw}: {error}"
+        );
+    }
+
+    let error = SessionRef::parse("2030-31")
+        .unwrap()
+        .resolve(date(2025, 3, 1))
+        .unwrap_err();
+    assert!(error.contains("has not started"), "{error}");
+}
+
+#[test]
+fn sessions_are_taken_to_start_in_september() {
+    assert_eq!(current_session_start(date(2024, 8, 31)), 2023);
+    assert_eq!(current_session_start(date(2024, 9, 1)), 2024);
+    assert_eq!(current_session_start(date(2025, 1, 15)), 2024);
+}
+
+#[test]
+fn the_schema_pattern_accepts_exactly_the_parsed_forms() {
+    let validator =
+        JSONSchema::compile(&json!({"type": "string", "pattern": SESSION_PATTERN})).unwrap();
+    for raw in [
+        "2024-25",
+        "2024/25",
+        "2024–25",
+        "2024-2025",
+        " 2024-25 ",
+        "current",
+        "Previous",
+        "24-25",
+        "2024",
+        "2024 - 25",
+        "last",
+        "cUrRent",
+    ] {
+        // Year checks (consecutive, in range) are left to the parser, so these forms only
+        // differ in syntax.
+        assert_eq!(
+            validator.is_valid(&json!(raw)),
+            SessionRef::parse(raw).is_ok(),
+            "{raw}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn fetch_bills_sends_the_canonical_session() {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"session": "2023/2024", "enableCache": false}),
+    )
+    .await;
+    assert!(response["result"]["isError"].is_null(), "{response}");
+    let requests = server.received_requests().await.unwrap_or_default();
+    let session = requests[0]
+        .url
+        .query_pairs()
+        .find(|(name, _)| name == "session")
+        .map(|(_, value)| value.into_owned());
+    assert_eq!(session.as_deref(), Some("2023-24"));
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"session": "2023-25"}),
+    )
+    .await;
+    let message = response["error"]["message"].as_str().unwrap_or_default();
+    assert!(message.contains("consecutive"), "{response}");
+    assert_eq!(
+        server.received_requests().await.unwrap_or_default().len(),
+        1
+    );
+}
@@ -0,0 +1,172 @@
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const SERVER_BIN: &str = env!("CARGO_BIN_EXE_mp-writer-mcp-server");
+
+async fn slow_member_upstream(delay: Duration) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/Members/7"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(delay)
+                .set_body_json(json!({
+                    "value": {
+                        "id": 7,
+                        "nameDisplayAs": "Example Member",
+                        "latestHouseMembership": {"membershipFrom": "Somewhere", "house": 1}
+                    }
+                })),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port()
+}
+
+fn spawn_server(work_dir: &std::path::Path, port: u16, upstream: &str) -> Child {
+    Command::new(SERVER_BIN)
+        .current_dir(work_dir)
+        .env("MCP_API_KEY", "test")
+        .env("MCP_SERVER_PORT", port.to_string())
+        .env("MCP_DB_PATH", work_dir.join("db"))
+        .env("UPSTREAM_BASE_URL", upstream)
+        .env("UPSTREAM_REQUESTS_PER_SECOND", "0")
+        .env("STATS_SNAPSHOT_INTERVAL_SECS", "0")
+        .env("SHUTDOWN_GRACE_SECONDS", "10")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("server binary should start")
+}
+
+async fn wait_until_ready(http: &reqwest::Client, base: &str) {
+    for _ in 0..100 {
+        if let Ok(response) = http.get(format!("{base}/api/ready")).send().await {
+            if response.status().is_success() {
+                return;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("server did not become ready");
+}
+
+async fn post_mcp(http: &reqwest::Client, base: &str, body: Value) -> reqwest::Response {
+    http.post(format!("{base}/api/mcp"))
+        .header("x-api-key", "test")
+        .header("mcp-protocol-version", common::PROTOCOL_VERSION)
+        .json(&body)
+        .send()
+        .await
+        .expect("request should be answered")
+}
+
+fn send_sigterm(child: &Child) {
+    let status = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .expect("kill should run");
+    assert!(status.success());
+}
+
+#[tokio::test]
+async fn sigterm_drains_a_running_tool_call_and_releases_sled() {
+    let upstream = slow_member_upstream(Duration::from_millis(1500)).await;
+    let work_dir = tempfile::tempdir().expect("temp dir");
+    let port = free_port();
+    let base = format!("http://127.0.0.1:{port}");
+    let mut child = spawn_server(work_dir.path(), port, &upstream.uri());
+
+    let http = reqwest::Client::new();
+    wait_until_ready(&http, &base).await;
+    post_mcp(
+        &http,
+        &base,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": common::PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "1.0"}
+            }
+        }),
+    )
+    .await;
+    post_mcp(
+        &http,
+        &base,
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+    )
+    .await;
+
+    let call = tokio::spawn({
+        let http = http.clone();
+        let base = base.clone();
+        async move {
+            post_mcp(
+                &http,
+                &base,
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": {
+                        "name": "parliament.fetch_members_bulk",
+                        "arguments": {"mpIds": [7], "enableCache": false}
+                    }
+                }),
+            )
+            .await
+            .json::<Value>()
+            .await
+            .expect("JSON body")
+        }
+    });
+
+    // Signal only once the tool call is waiting on the slow upstream.
+    for _ in 0..100 {
+        if !upstream
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .is_empty()
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    send_sigterm(&child);
+
+    let response = call.await.expect("call task");
+    assert_eq!(response["id"], 2);
+    assert!(response["error"].is_null(), "response: {response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["resolved"], 1,
+        "response: {response}"
+    );
+
+    let status = tokio::task::spawn_blocking(move || child.wait())
+        .await
+        .unwrap()
+        .expect("server should exit");
+    assert!(status.success(), "exit status: {status}");
+
+    // The flushed database reopens cleanly after the process has exited.
+    sled::open(work_dir.path().join("db")).expect("sled should reopen after shutdown");
+}
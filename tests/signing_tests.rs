@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use ed25519_dalek::SigningKey;
+use ed25519_dalek::pkcs8::EncodePrivateKey;
+use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::core::signing::{ResponseSigner, canonicalize, payload_digest};
+use mp_writer_mcp_server::features::research::ParliamentDataSource;
+
+mod common;
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn signed_harness(dir: &tempfile::TempDir) -> common::TestHarness {
+    let path = dir.path().join("signing.pem");
+    let pem = signing_key()
+        .to_pkcs8_pem(LineEnding::LF)
+        .expect("encode pem");
+    std::fs::write(&path, pem.as_bytes()).expect("write pem");
+
+    let mock: Arc<dyn ParliamentDataSource> = Arc::new(common::MockParliamentDataSource::new());
+    common::build_service(Some(mock), |config| {
+        config.response_signing_key = Some(path.to_string_lossy().into_owned());
+    })
+}
+
+#[test]
+fn canonicalization_sorts_keys_and_strips_whitespace() {
+    let left: Value = serde_json::from_str(
+        r#"{ "b": [3, {"z": null, "a": "x y"}], "a": {"d": 1.5, "c": true} }"#,
+    )
+    .expect("parse");
+    let right: Value =
+        serde_json::from_str(r#"{"a":{"c":true,"d":1.5},"b":[3,{"a":"x y","z":null}]}"#)
+            .expect("parse");
+
+    let expected = r#"{"a":{"c":true,"d":1.5},"b":[3,{"a":"x y","z":null}]}"#;
+    assert_eq!(canonicalize(&left), expected);
+    assert_eq!(canonicalize(&right), expected);
+    assert_eq!(payload_digest(&left), payload_digest(&right));
+}
+
+#[test]
+fn canonicalization_escapes_strings_and_keeps_array_order() {
+    let value = json!({"quote\"key": "line\nbreak", "list": [2, 1]});
+    assert_eq!(
+        canonicalize(&value),
+        r#"{"list":[2,1],"quote\"key":"line\nbreak"}"#
+    );
+    assert_ne!(
+        payload_digest(&json!({"list": [2, 1]})),
+        payload_digest(&json!({"list": [1, 2]}))
+    );
+}
+
+#[test]
+fn signatures_round_trip_and_detect_tampering() {
+    let signer = ResponseSigner::new(signing_key());
+    let payload = json!({"summary": "Climate", "bills": [{"title": "Climate Bill"}]});
+    let block = signer.sign(&payload);
+
+    assert_eq!(block.algorithm, "ed25519");
+    assert_eq!(block.public_key, signer.public_key_hex());
+    assert!(signer.verify(&payload, &block).is_ok());
+
+    let tampered = json!({"summary": "Climate!", "bills": [{"title": "Climate Bill"}]});
+    assert!(signer.verify(&tampered, &block).is_err());
+
+    let mut backdated = block.clone();
+    backdated.signed_at = "2000-01-01T00:00:00+00:00".to_string();
+    assert!(signer.verify(&payload, &backdated).is_err());
+
+    let other = ResponseSigner::new(SigningKey::from_bytes(&[9u8; 32]));
+    assert!(other.verify(&payload, &block).is_err());
+}
+
+#[tokio::test]
+async fn research_responses_are_signed_when_configured() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let harness = signed_harness(&dir);
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "climate"}),
+    )
+    .await;
+    let mut payload = response["result"]["structuredContent"].clone();
+    let signature = payload
+        .as_object_mut()
+        .and_then(|map| map.remove("signature"))
+        .expect("signature block");
+
+    assert_eq!(signature["algorithm"], "ed25519");
+    assert_eq!(signature["payloadSha256"], payload_digest(&payload));
+
+    let verified = common::call_tool(
+        &harness.service,
+        "utilities.verify_signature",
+        json!({"payload": response["result"]["structuredContent"]}),
+    )
+    .await;
+    assert_eq!(verified["result"]["structuredContent"]["valid"], true);
+
+    payload["summary"] = json!("edited");
+    let rejected = common::call_tool(
+        &harness.service,
+        "utilities.verify_signature",
+        json!({"payload": payload, "signature": signature}),
+    )
+    .await;
+    let rejected = &rejected["result"]["structuredContent"];
+    assert_eq!(rejected["valid"], false);
+    assert!(rejected["reason"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn responses_are_unsigned_without_a_key() {
+    let mock: Arc<dyn ParliamentDataSource> = Arc::new(common::MockParliamentDataSource::new());
+    let harness = common::build_service(Some(mock), |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.run",
+        json!({"topic": "climate"}),
+    )
+    .await;
+    assert!(
+        response["result"]["structuredContent"]
+            .get("signature")
+            .is_none()
+    );
+
+    let verify = common::call_tool(
+        &harness.service,
+        "utilities.verify_signature",
+        json!({"payload": {"summary": "x"}}),
+    )
+    .await;
+    assert_eq!(verify["result"]["isError"], true);
+}
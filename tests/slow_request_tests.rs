@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use axum::body::{Body, to_bytes};
+use axum::http::Request;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+/// The fields of every log line with `message`.
+fn events(logs: &common::CapturedLogs, message: &str) -> Vec<Value> {
+    logs.json_lines()
+        .into_iter()
+        .filter(|line| line["fields"]["message"] == message)
+        .map(|line| line["fields"].clone())
+        .collect()
+}
+
+/// An upstream that answers every request after `delay`.
+async fn slow_upstream(delay: Duration) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"items": []}))
+                .set_delay(delay),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn metrics(harness: &common::TestHarness) -> String {
+    let response = harness
+        .router()
+        .oneshot(
+            Request::get("/api/metrics")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+async fn search_bills(harness: &common::TestHarness) {
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "climate", "enableCache": false}),
+    )
+    .await;
+    assert!(response["result"]["isError"].is_null(), "{response}");
+}
+
+#[tokio::test]
+async fn slow_upstream_attempts_and_tool_calls_are_warned_about_and_counted() {
+    let server = slow_upstream(Duration::from_millis(150)).await;
+    let uri = server.uri();
+    let (logs, _guard) = common::capture_json();
+    let harness = common::build_service(None, |config| {
+        config.upstream_base_url = Some(uri);
+        config.slow_upstream_ms = 50;
+        config.slow_request_ms = 50;
+    });
+    common::initialize(&harness.service).await;
+
+    search_bills(&harness).await;
+
+    let upstream = events(&logs, "slow upstream response");
+    assert_eq!(upstream.len(), 1, "{upstream:?}");
+    assert_eq!(upstream[0]["host"], "bills-api.parliament.uk");
+    assert_eq!(upstream[0]["tool"], "parliament.fetch_bills");
+    assert_eq!(upstream[0]["attempt"], 1);
+    assert_eq!(upstream[0]["status"], 200);
+    assert!(upstream[0]["elapsed_ms"].as_u64().unwrap() >= 150);
+
+    let calls = events(&logs, "slow tools/call");
+    assert_eq!(calls.len(), 1, "{calls:?}");
+    assert_eq!(calls[0]["tool"], "parliament.fetch_bills");
+    assert_eq!(calls[0]["outcome"], "success");
+    assert!(calls[0]["elapsed_ms"].as_u64().unwrap() >= 150);
+
+    let metrics = metrics(&harness).await;
+    assert!(
+        metrics.contains(
+            "mcp_upstream_slow_requests_total{host=\"bills-api.parliament.uk\",tool=\"parliament.fetch_bills\"} 1"
+        ),
+        "{metrics}"
+    );
+    assert!(
+        metrics.contains("mcp_slow_tool_calls_total{tool=\"parliament.fetch_bills\"} 1"),
+        "{metrics}"
+    );
+}
+
+#[tokio::test]
+async fn fast_or_unwatched_requests_are_not_reported() {
+    let server = slow_upstream(Duration::from_millis(150)).await;
+    let uri = server.uri();
+    let (logs, _guard) = common::capture_json();
+    // Thresholds above the delay, then thresholds switched off.
+    for threshold in [5_000, 0] {
+        let uri = uri.clone();
+        let harness = common::build_service(None, |config| {
+            config.upstream_base_url = Some(uri);
+            config.slow_upstream_ms = threshold;
+            config.slow_request_ms = threshold;
+        });
+        common::initialize(&harness.service).await;
+
+        search_bills(&harness).await;
+
+        let metrics = metrics(&harness).await;
+        assert!(
+            !metrics.contains("mcp_upstream_slow_requests_total{"),
+            "{metrics}"
+        );
+        assert!(!metrics.contains("mcp_slow_tool_calls_total{"), "{metrics}");
+    }
+
+    assert!(events(&logs, "slow upstream response").is_empty());
+    assert!(events(&logs, "slow tools/call").is_empty());
+}
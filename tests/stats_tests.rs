@@ -0,0 +1,130 @@
+use serde_json::json;
+
+use mp_writer_mcp_server::core::stats::{StatsRegistry, now_timestamp};
+
+mod common;
+
+#[test]
+fn records_success_and_failure_timestamps() {
+    let stats = StatsRegistry::new();
+    stats.record_tool_at("parliament.fetch_bills", true, 100);
+    stats.record_tool_at("parliament.fetch_bills", false, 200);
+    stats.record_tool_at("parliament.fetch_bills", true, 150);
+    stats.record_upstream_at("bills-api.parliament.uk", false, 300);
+
+    let tool = stats
+        .tool_stats("parliament.fetch_bills")
+        .expect("tool stats");
+    assert_eq!(tool.last_success, Some(150));
+    assert_eq!(tool.last_failure, Some(200));
+    assert_eq!(tool.success_count, 2);
+    assert_eq!(tool.failure_count, 1);
+
+    let host = stats
+        .upstream_stats("bills-api.parliament.uk")
+        .expect("host stats");
+    assert_eq!(host.last_success, None);
+    assert_eq!(host.last_failure, Some(300));
+}
+
+#[test]
+fn staleness_warning_requires_recent_failures_and_old_success() {
+    let stats = StatsRegistry::new();
+    let threshold = 3600;
+    let now = 100_000;
+
+    stats.record_tool_at("parliament.fetch_legislation", true, now - 10);
+    stats.record_tool_at("parliament.fetch_legislation", false, now - 5);
+    assert!(
+        stats
+            .staleness_warning("parliament.fetch_legislation", threshold, now)
+            .is_none(),
+        "a recent success should suppress the warning"
+    );
+
+    stats.record_tool_at("parliament.fetch_bills", true, now - 2 * threshold);
+    stats.record_tool_at("parliament.fetch_bills", false, now - 60);
+    assert!(
+        stats
+            .staleness_warning("parliament.fetch_bills", threshold, now)
+            .is_some()
+    );
+
+    stats.record_tool_at("research.run", true, now - 3 * threshold);
+    stats.record_tool_at("research.run", false, now - 2 * threshold);
+    assert!(
+        stats
+            .staleness_warning("research.run", threshold, now)
+            .is_none(),
+        "failures outside the window are not considered recent"
+    );
+
+    stats.record_tool_at("parliament.search_uk_law", false, now - 1);
+    assert!(
+        stats
+            .staleness_warning("parliament.search_uk_law", threshold, now)
+            .is_some(),
+        "a tool that never succeeded but failed recently is stale"
+    );
+}
+
+#[tokio::test]
+async fn snapshot_survives_restart() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let db = sled::open(temp_dir.path()).expect("sled open");
+    let tree = db.open_tree("stats").expect("tree");
+
+    let stats = StatsRegistry::new();
+    stats.record_tool_at("parliament.fetch_bills", true, 42);
+    stats.persist(&tree).await.expect("persist");
+
+    let restored = StatsRegistry::new();
+    restored.restore(&tree).await.expect("restore");
+    let tool = restored
+        .tool_stats("parliament.fetch_bills")
+        .expect("restored stats");
+    assert_eq!(tool.last_success, Some(42));
+}
+
+#[tokio::test]
+async fn tools_list_annotates_stale_tools() {
+    let harness = common::build_service(None, |_| {});
+    let now = now_timestamp();
+    harness
+        .stats
+        .record_tool_at("parliament.fetch_legislation", true, now - 7200);
+    harness
+        .stats
+        .record_tool_at("parliament.fetch_legislation", false, now - 10);
+
+    common::initialize(&harness.service).await;
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(Some(json!(2)), "tools/list", None),
+            common::context(),
+        )
+        .await
+        .expect("tools/list")
+        .expect("response");
+
+    let tools = response.result["tools"].as_array().expect("tools");
+    let legislation = tools
+        .iter()
+        .find(|tool| tool["name"] == "parliament.fetch_legislation")
+        .expect("legislation tool");
+    assert!(legislation["annotations"]["stalenessWarning"].is_string());
+
+    let bills = tools
+        .iter()
+        .find(|tool| tool["name"] == "parliament.fetch_bills")
+        .expect("bills tool");
+    assert!(bills.get("annotations").is_none());
+
+    let stats = common::call_tool(&harness.service, "admin.tool_stats", json!({})).await;
+    let structured = &stats["result"]["structuredContent"];
+    assert_eq!(
+        structured["tools"]["parliament.fetch_legislation"]["stale"],
+        json!(true)
+    );
+}
@@ -0,0 +1,231 @@
+use std::path::Path;
+
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_statutory_instruments";
+
+/// Statutory Instruments API and legislation.gov.uk `uksi` feed responses for instruments
+/// named like "climate": one in force, one made but not yet in force, a draft awaiting
+/// approval, a withdrawn instrument and a proposed negative.
+const SI_FIXTURES: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/statutory_instruments"
+);
+
+const EMPTY_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Search Results</title></feed>"#;
+
+fn ids(result: &Value) -> Vec<&str> {
+    result["instruments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|instrument| instrument["id"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn instruments_are_numbered_and_ordered_by_laying_date() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(SI_FIXTURES)).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"searchTerm": "climate"})).await;
+
+    assert!(result.get("note").is_none(), "{result}");
+    assert_eq!(
+        ids(&result),
+        ["aB3cD4eF", "Qr8sT1uV", "Wy6zA9bC", "Xk2pQ7Lm", "Pn5mK2jH"]
+    );
+    let instruments = &result["instruments"];
+    assert_eq!(
+        instruments[1],
+        json!({
+            "id": "Qr8sT1uV",
+            "name": "The Climate Change Agreements (Eligible Facilities) (Amendment) Regulations 2025",
+            "number": "2025/45",
+            "department": "Department for Energy Security and Net Zero",
+            "procedure": "negative",
+            "procedureName": "Made negative",
+            "laidDate": "2025-01-20",
+            "madeDate": "2025-01-16",
+            "comingIntoForceDate": "2030-01-01",
+            "status": "made",
+            "url": "https://statutoryinstruments.parliament.uk/instrument/Qr8sT1uV",
+            "legislationUrl": "https://www.legislation.gov.uk/uksi/2025/45"
+        })
+    );
+    assert_eq!(instruments[0]["status"], "laid");
+    assert_eq!(instruments[0]["procedure"], "affirmative");
+    assert_eq!(instruments[0]["laidDate"], "2025-03-10");
+    assert!(instruments[0]["number"].is_null());
+    assert_eq!(instruments[2]["status"], "withdrawn");
+    assert_eq!(instruments[3]["status"], "in-force");
+    assert_eq!(instruments[3]["number"], "2019/1056");
+    assert_eq!(instruments[4]["status"], "proposed");
+    assert!(instruments[4]["laidDate"].is_null());
+}
+
+#[tokio::test]
+async fn department_procedure_and_dates_filter_the_instruments() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(SI_FIXTURES)).await;
+
+    let treasury = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "climate", "department": "treasury", "procedure": "negative"}),
+    )
+    .await;
+    let recent = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "climate", "laidFrom": "2025-01-01"}),
+    )
+    .await;
+    let older_affirmatives = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "climate", "procedure": "affirmative", "laidTo": "2024-12-31"}),
+    )
+    .await;
+    let limited = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "climate", "limit": 2}),
+    )
+    .await;
+
+    assert_eq!(ids(&treasury), ["Wy6zA9bC"]);
+    assert_eq!(ids(&recent), ["aB3cD4eF", "Qr8sT1uV"]);
+    assert_eq!(ids(&older_affirmatives), ["Xk2pQ7Lm"]);
+    assert_eq!(ids(&limited), ["aB3cD4eF", "Qr8sT1uV"]);
+}
+
+#[tokio::test]
+async fn results_are_cached() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(SI_FIXTURES)).await;
+
+    let first =
+        common::call_tool_ok(&harness.service, TOOL, json!({"searchTerm": "climate"})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "Climate", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn a_failed_feed_leaves_instruments_unnumbered_and_uncached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/uksi/data.feed"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let harness = common::recorded_harness(&server, Path::new(SI_FIXTURES)).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"searchTerm": "climate"})).await;
+
+    assert_eq!(result["instruments"].as_array().unwrap().len(), 5);
+    assert!(result["instruments"][1]["number"].is_null());
+    assert!(
+        result["note"]
+            .as_str()
+            .unwrap()
+            .contains("legislation.gov.uk"),
+        "{result}"
+    );
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "climate", "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(cached["cacheMiss"], true, "{cached}");
+}
+
+#[tokio::test]
+async fn more_matches_than_were_read_are_noted() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/StatutoryInstrument"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{"value": {
+                "id": "Hs4tG8kL",
+                "name": "The Education (Student Fees) (Amendment) Regulations 2025",
+                "layingBodyName": "Department for Education",
+                "procedure": {"name": "Made negative"},
+                "commonsLayingDate": "2025-02-03T00:00:00"
+            }}],
+            "totalResults": 250
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/uksi/data.feed"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(EMPTY_FEED.as_bytes(), "application/atom+xml"),
+        )
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"department": "education"})).await;
+
+    assert_eq!(ids(&result), ["Hs4tG8kL"]);
+    assert!(
+        result["note"]
+            .as_str()
+            .unwrap()
+            .contains("100 most recent of 250"),
+        "{result}"
+    );
+}
+
+#[tokio::test]
+async fn an_unavailable_api_is_an_upstream_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/StatutoryInstrument"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let harness = common::recorded_harness(&server, Path::new(SI_FIXTURES)).await;
+
+    let response =
+        common::call_tool(&harness.service, TOOL, json!({"searchTerm": "climate"})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn invalid_filters_are_rejected() {
+    let server = MockServer::start().await;
+    let harness = common::upstream_harness(&server).await;
+
+    for arguments in [
+        json!({"laidFrom": "10/03/2025"}),
+        json!({"procedure": "super-affirmative"}),
+        json!({"limit": 51}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments.clone()).await;
+        assert_eq!(response["error"]["code"], -32602, "{arguments}: {response}");
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,91 @@
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, header};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+mod common;
+
+async fn post(router: axum::Router, body: Value) -> Value {
+    let response = router
+        .oneshot(
+            Request::post("/api/mcp")
+                .header("x-api-key", "test")
+                .header("MCP-Protocol-Version", common::PROTOCOL_VERSION)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+async fn metrics(router: axum::Router) -> String {
+    let response = router
+        .oneshot(
+            Request::get("/api/metrics")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+fn ping_with_session_id() -> Value {
+    json!({"jsonrpc": "2.0", "id": 1, "method": "ping", "sessionId": "abc"})
+}
+
+#[tokio::test]
+async fn strict_mode_rejects_unknown_request_members() {
+    let harness = common::build_service(None, |config| config.strict = true);
+    common::initialize(&harness.service).await;
+
+    let response = post(harness.router(), ping_with_session_id()).await;
+
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["error"]["code"], -32600, "{response}");
+    let message = response["error"]["message"].as_str().unwrap_or_default();
+    assert!(message.contains("`sessionId`"), "{message}");
+}
+
+#[tokio::test]
+async fn lenient_mode_ignores_and_counts_unknown_request_members() {
+    let harness = common::build_service(None, |_| {});
+    common::initialize(&harness.service).await;
+
+    let response = post(harness.router(), ping_with_session_id()).await;
+    assert!(response["error"].is_null(), "{response}");
+    assert!(response["result"].is_object(), "{response}");
+
+    let body = metrics(harness.router()).await;
+    assert!(
+        body.contains("mcp_unknown_fields_total{location=\"request\",field=\"sessionId\"} 1"),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn lenient_arguments_are_counted_per_key() {
+    let harness = common::build_service(None, |config| config.strict_args = false);
+    common::initialize(&harness.service).await;
+
+    for _ in 0..2 {
+        let response = common::call_tool(
+            &harness.service,
+            "parliament.fetch_bills",
+            json!({"searchTerm": "housing", "cacheOnly": true, "reason": "x"}),
+        )
+        .await;
+        assert!(response["error"].is_null(), "{response}");
+    }
+
+    let body = metrics(harness.router()).await;
+    assert!(
+        body.contains("mcp_unknown_fields_total{location=\"arguments\",field=\"reason\"} 2"),
+        "{body}"
+    );
+}
@@ -0,0 +1,147 @@
+use std::net::SocketAddr;
+use std::path::Path;
+
+use axum::http::StatusCode;
+use tokio_util::sync::CancellationToken;
+
+use mp_writer_mcp_server::config::{ListenAddress, TlsConfig};
+use mp_writer_mcp_server::server::{Listener, load_tls_config, reload_tls_config};
+
+mod common;
+
+struct Certificate {
+    cert_pem: String,
+    key_pem: String,
+    der: Vec<u8>,
+}
+
+fn certificate() -> Certificate {
+    let generated =
+        rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).expect("self-signed");
+    Certificate {
+        cert_pem: generated.cert.pem(),
+        key_pem: generated.signing_key.serialize_pem(),
+        der: generated.cert.der().to_vec(),
+    }
+}
+
+fn write_pair(dir: &Path, cert_pem: &str, key_pem: &str) -> TlsConfig {
+    let tls = TlsConfig {
+        cert_path: dir.join("cert.pem"),
+        key_path: dir.join("key.pem"),
+    };
+    std::fs::write(&tls.cert_path, cert_pem).unwrap();
+    std::fs::write(&tls.key_path, key_pem).unwrap();
+    tls
+}
+
+async fn served_certificate(client: &reqwest::Client, address: &str) -> Vec<u8> {
+    let response = client
+        .get(format!("https://{address}/api/health"))
+        .send()
+        .await
+        .expect("https request");
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|info| info.peer_certificate())
+        .expect("peer certificate")
+        .to_vec()
+}
+
+#[test]
+fn certificate_and_key_paths_are_set_together() {
+    assert_eq!(TlsConfig::parse(None, None).unwrap(), None);
+    assert_eq!(
+        TlsConfig::parse(Some("/etc/tls/cert.pem"), Some("/etc/tls/key.pem")).unwrap(),
+        Some(TlsConfig {
+            cert_path: "/etc/tls/cert.pem".into(),
+            key_path: "/etc/tls/key.pem".into(),
+        })
+    );
+
+    let error = TlsConfig::parse(Some("/etc/tls/cert.pem"), None)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("without MCP_TLS_KEY_PATH"), "{error}");
+    let error = TlsConfig::parse(None, Some("/etc/tls/key.pem"))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("without MCP_TLS_CERT_PATH"), "{error}");
+}
+
+#[test]
+fn unusable_certificates_fail_with_a_configuration_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = certificate();
+    let second = certificate();
+
+    let mismatched = write_pair(dir.path(), &first.cert_pem, &second.key_pem);
+    let error = load_tls_config(&mismatched).unwrap_err().to_string();
+    assert!(error.contains("does not match the certificate"), "{error}");
+
+    let no_certificate = write_pair(dir.path(), "not a certificate", &first.key_pem);
+    let error = load_tls_config(&no_certificate).unwrap_err().to_string();
+    assert!(error.contains("contains no PEM certificates"), "{error}");
+
+    let no_key = write_pair(dir.path(), &first.cert_pem, &first.cert_pem);
+    let error = load_tls_config(&no_key).unwrap_err().to_string();
+    assert!(error.contains("cannot read MCP_TLS_KEY_PATH"), "{error}");
+
+    let missing = TlsConfig {
+        cert_path: dir.path().join("absent.pem"),
+        key_path: dir.path().join("key.pem"),
+    };
+    let error = load_tls_config(&missing).unwrap_err().to_string();
+    assert!(error.contains("cannot read MCP_TLS_CERT_PATH"), "{error}");
+}
+
+#[tokio::test]
+async fn https_is_served_and_certificates_reload_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = certificate();
+    let tls = write_pair(dir.path(), &first.cert_pem, &first.key_pem);
+    let acceptor = load_tls_config(&tls).unwrap();
+
+    let harness = common::build_service(None, |_| {});
+    let address: SocketAddr = "127.0.0.1:0".parse().unwrap();
+    let listener = Listener::bind(&ListenAddress::Tcp(address))
+        .await
+        .unwrap()
+        .with_tls(acceptor.clone())
+        .unwrap();
+    let address = listener.to_string();
+    let stop = CancellationToken::new();
+    let server = tokio::spawn(listener.serve(harness.router(), stop.clone()));
+
+    // A fresh client per request, so each one makes a new handshake.
+    let client = || {
+        reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .tls_info(true)
+            .build()
+            .unwrap()
+    };
+    assert_eq!(served_certificate(&client(), &address).await, first.der);
+
+    // A broken renewal is rejected and the running certificate stays.
+    std::fs::write(&tls.key_path, certificate().key_pem).unwrap();
+    assert!(reload_tls_config(&acceptor, &tls).is_err());
+    assert_eq!(served_certificate(&client(), &address).await, first.der);
+
+    let renewed = certificate();
+    write_pair(dir.path(), &renewed.cert_pem, &renewed.key_pem);
+    reload_tls_config(&acceptor, &tls).unwrap();
+    assert_eq!(served_certificate(&client(), &address).await, renewed.der);
+
+    // Plain HTTP is not answered on the TLS port.
+    assert!(
+        reqwest::get(format!("http://{address}/api/health"))
+            .await
+            .map_or(true, |response| !response.status().is_success())
+    );
+
+    stop.cancel();
+    server.await.unwrap().unwrap();
+}
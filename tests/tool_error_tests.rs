@@ -0,0 +1,96 @@
+use serde_json::{Value, json};
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::http_client::redact_url;
+
+mod common;
+
+async fn failing_upstream(status: u16) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(status).set_body_string("upstream says no"))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn call_bills(base_url: String) -> Value {
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(base_url));
+    common::initialize(&harness.service).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_bills",
+        json!({"searchTerm": "climate", "enableCache": false}),
+    )
+    .await;
+    response["result"].clone()
+}
+
+#[tokio::test]
+async fn rate_limited_upstream_is_retryable() {
+    let server = failing_upstream(429).await;
+    let result = call_bills(server.uri()).await;
+
+    assert_eq!(result["isError"], true);
+    let text = result["content"][0]["text"].as_str().expect("text");
+    assert!(text.contains("429"), "unexpected text: {text}");
+
+    let data = &result["structuredContent"];
+    assert_eq!(data["kind"], "upstream");
+    assert_eq!(data["status"], 429);
+    assert_eq!(data["retryable"], true);
+    let url = data["url"].as_str().expect("url");
+    assert!(
+        url.starts_with("https://bills-api.parliament.uk/api/v1/Bills"),
+        "{url}"
+    );
+    assert!(url.contains("searchTerm=climate"), "{url}");
+}
+
+#[tokio::test]
+async fn server_error_is_retryable_and_client_error_is_not() {
+    let server = failing_upstream(500).await;
+    let result = call_bills(server.uri()).await;
+    assert_eq!(result["structuredContent"]["status"], 500);
+    assert_eq!(result["structuredContent"]["retryable"], true);
+    assert_eq!(
+        result["content"][0]["text"],
+        "Upstream service responded with HTTP 500"
+    );
+
+    let server = failing_upstream(404).await;
+    let result = call_bills(server.uri()).await;
+    assert_eq!(result["structuredContent"]["status"], 404);
+    assert_eq!(result["structuredContent"]["retryable"], false);
+}
+
+#[tokio::test]
+async fn network_errors_have_no_status_and_are_retryable() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let address = listener.local_addr().expect("addr");
+    drop(listener);
+
+    let result = call_bills(format!("http://{address}")).await;
+
+    assert_eq!(result["isError"], true);
+    let data = &result["structuredContent"];
+    assert_eq!(data["kind"], "upstream");
+    assert!(data["status"].is_null());
+    assert_eq!(data["retryable"], true);
+    assert!(data["url"].as_str().is_some());
+}
+
+#[test]
+fn sensitive_query_parameters_are_redacted() {
+    assert_eq!(
+        redact_url("https://example.com/path?q=climate&api_key=secret&Token=abc"),
+        "https://example.com/path?q=climate"
+    );
+    assert_eq!(
+        redact_url("https://example.com/path?key=secret"),
+        "https://example.com/path"
+    );
+    assert_eq!(redact_url("not a url?token=abc"), "not a url");
+}
@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::recording::{RecordedBody, RecordedResponse};
+use mp_writer_mcp_server::features::parliament::uk_law::parse_uk_law_feed;
+
+mod common;
+
+const TOOL: &str = "parliament.search_uk_law";
+
+/// legislation.gov.uk Atom feeds for a primary search for "climate change", a secondary
+/// search for "net zero" and a search for "zzqx nonexistent" that matched nothing.
+const UK_LAW_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/uk_law");
+
+fn fixture_feed(query: &str) -> String {
+    let fixture = RecordedResponse::load_dir(Path::new(UK_LAW_FIXTURES))
+        .unwrap()
+        .into_iter()
+        .find(|fixture| fixture.query.iter().any(|(_, value)| value == query))
+        .unwrap();
+    match fixture.body {
+        RecordedBody::Text(feed) => feed,
+        RecordedBody::Json(json) => panic!("expected an Atom feed, got {json}"),
+    }
+}
+
+#[tokio::test]
+async fn a_primary_search_returns_the_feed_entries() {
+    let _server = MockServer::start().await;
+    let harness = common::recorded_harness(&_server, Path::new(UK_LAW_FIXTURES)).await;
+
+    let results = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"query": "climate change", "legislationType": "primary"}),
+    )
+    .await;
+
+    assert_eq!(
+        results[0],
+        json!({
+            "title": "Climate Change Act 2008",
+            "year": "2008",
+            "legislationType": "Primary",
+            "isInForce": true,
+            "url": "https://www.legislation.gov.uk/ukpga/2008/27",
+            "summary": "An Act to set a target for the year 2050 for the reduction of targeted greenhouse gas emissions; to provide for a system of carbon budgeting; to establish a Committee on Climate Change; and for connected purposes.",
+            "lastUpdated": "2024-11-20T15:03:11Z"
+        })
+    );
+    assert_eq!(results[1]["title"], "Climate Change (Scotland) Act 2009");
+    assert_eq!(results[1]["legislationType"], "Primary");
+    assert_eq!(
+        results[1]["url"],
+        "https://www.legislation.gov.uk/asp/2009/12"
+    );
+    assert_eq!(
+        results[2]["title"],
+        "Climate Change and Sustainable Energy Act 2006"
+    );
+    assert_eq!(results[2]["isInForce"], false);
+    assert!(results[2]["summary"].is_null());
+}
+
+#[test]
+fn secondary_legislation_is_typed_from_its_metadata() {
+    let results = parse_uk_law_feed(&fixture_feed("net zero")).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[0].title,
+        "The Climate Change Act 2008 (2050 Target Amendment) Order 2019"
+    );
+    assert_eq!(results[0].year.as_deref(), Some("2019"));
+    assert_eq!(results[0].legislation_type, "Secondary");
+    assert_eq!(
+        results[0].url,
+        "https://www.legislation.gov.uk/uksi/2019/1056"
+    );
+    assert_eq!(results[1].legislation_type, "Secondary");
+    assert_eq!(
+        results[1].url,
+        "https://www.legislation.gov.uk/nisr/2022/164"
+    );
+    assert!(results[1].summary.is_none());
+}
+
+#[tokio::test]
+async fn a_search_with_no_matches_is_empty_and_cached() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(UK_LAW_FIXTURES)).await;
+
+    let results =
+        common::call_tool_ok(&harness.service, TOOL, json!({"query": "zzqx nonexistent"})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"query": "zzqx nonexistent", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(results, json!([]));
+    assert_eq!(cached, json!([]));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn the_cache_holds_every_entry_whatever_the_limit() {
+    let server = MockServer::start().await;
+    let harness = common::recorded_harness(&server, Path::new(UK_LAW_FIXTURES)).await;
+
+    let first = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"query": "climate change", "legislationType": "primary", "limit": 1}),
+    )
+    .await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"query": "climate change", "legislationType": "primary", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first.as_array().unwrap().len(), 1);
+    assert_eq!(cached.as_array().unwrap().len(), 3);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn an_unavailable_feed_is_an_upstream_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/primary+secondary/data.feed"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response =
+        common::call_tool(&harness.service, TOOL, json!({"query": "climate change"})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+}
@@ -0,0 +1,174 @@
+use serde_json::json;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use mp_writer_mcp_server::core::error::AppError;
+use mp_writer_mcp_server::features::parliament::{
+    FetchBillsArgs, FetchCoreDatasetArgs, FetchLegislationArgs, LookupConstituencyArgs,
+    SearchUkLawArgs,
+};
+
+mod common;
+
+async fn mock_upstream() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn query_value(request: &wiremock::Request, key: &str) -> Option<String> {
+    request
+        .url
+        .query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+#[tokio::test]
+async fn postcode_with_path_traversal_is_rejected_before_any_request() {
+    let server = mock_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let result = harness
+        .client
+        .lookup_constituency_offline(LookupConstituencyArgs {
+            postcode: "../members".to_string(),
+            enable_cache: Some(false),
+            cache_only: None,
+        })
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest { .. })));
+    assert!(
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn uk_law_query_with_reserved_characters_stays_in_the_query_string() {
+    let server = mock_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    // The JSON body is not a feed, so the search fails; only the request matters here.
+    let _ = harness
+        .client
+        .search_uk_law(SearchUkLawArgs {
+            query: "a&b=c#d".to_string(),
+            legislation_type: None,
+            limit: Some(5),
+            enable_cache: Some(false),
+            cache_only: None,
+        })
+        .await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.path(), "/primary+secondary/data.feed");
+    assert_eq!(
+        query_value(&requests[0], "title").as_deref(),
+        Some("a&b=c#d")
+    );
+    assert_eq!(requests[0].url.query_pairs().count(), 1);
+    assert!(requests[0].url.fragment().is_none());
+}
+
+#[tokio::test]
+async fn dataset_names_cannot_escape_their_path_segment() {
+    let server = mock_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    harness
+        .client
+        .fetch_core_dataset(FetchCoreDatasetArgs {
+            dataset: "../members?x=1".to_string(),
+            search_term: Some("climate 🌍 & co".to_string()),
+            page: None,
+            per_page: None,
+            enable_cache: Some(false),
+            cache_only: None,
+            fuzzy_match: None,
+            apply_relevance: None,
+            relevance_threshold: None,
+            fields: None,
+        })
+        .await
+        .expect("request should be sent");
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.path(), "/..%2Fmembers%3Fx=1.json");
+    assert_eq!(
+        query_value(&requests[0], "_search").as_deref(),
+        Some("climate 🌍 & co")
+    );
+    assert!(query_value(&requests[0], "x").is_none());
+}
+
+#[tokio::test]
+async fn bill_search_terms_are_percent_encoded() {
+    let server = mock_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    harness
+        .client
+        .fetch_bills(FetchBillsArgs {
+            search_term: Some("a&b=c#d 🏛".to_string()),
+            house: None,
+            session: None,
+            parliament_number: None,
+            enable_cache: Some(false),
+            cache_only: None,
+            apply_relevance: None,
+            relevance_threshold: None,
+            fields: None,
+        })
+        .await
+        .expect("bills request");
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(
+        query_value(&requests[0], "searchTerm").as_deref(),
+        Some("a&b=c#d 🏛")
+    );
+    assert!(query_value(&requests[0], "b").is_none());
+}
+
+#[tokio::test]
+async fn legislation_type_with_path_characters_is_rejected() {
+    let server = mock_upstream().await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+
+    let result = harness
+        .client
+        .fetch_legislation(FetchLegislationArgs {
+            title: Some("Climate".to_string()),
+            year: None,
+            legislation_type: Some("../admin".to_string()),
+            enable_cache: Some(false),
+            cache_only: None,
+            apply_relevance: None,
+            relevance_threshold: None,
+        })
+        .await;
+
+    assert!(matches!(result, Err(AppError::BadRequest { .. })));
+    assert!(
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .is_empty()
+    );
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+
+use mp_writer_mcp_server::core::usage::UsageCollector;
+
+mod common;
+
+const ADMIN_KEY: &str = "admin";
+
+async fn usage_summary(harness: &common::TestHarness, api_key: &str) -> Value {
+    common::call_tool_with_context(
+        &harness.service,
+        "admin.usage_summary",
+        json!({"windowHours": 1}),
+        common::context().with_api_key(api_key),
+    )
+    .await
+}
+
+#[test]
+fn buckets_below_the_threshold_are_suppressed() {
+    let collector = UsageCollector::new(3);
+    for topic in ["Housing", "housing ", "HOUSING", "  housing"] {
+        collector.record_research_topic(topic);
+    }
+    collector.record_research_topic("a very specific personal matter");
+    for _ in 0..3 {
+        collector.record_constituency("Bath");
+    }
+    collector.record_constituency("Wells");
+    collector.record_constituency("Wells");
+
+    let summary = serde_json::to_value(collector.summary(1)).expect("serialise summary");
+
+    assert_eq!(summary["researchRuns"], 5);
+    assert_eq!(summary["constituencyLookups"], 5);
+    assert_eq!(summary["kThreshold"], 3);
+    let topics = summary["topics"].as_array().expect("topics");
+    assert_eq!(topics.len(), 1, "summary: {summary}");
+    assert_eq!(topics[0]["count"], 4);
+    assert_eq!(topics[0]["topicHash"].as_str().map(str::len), Some(16));
+    assert!(!summary.to_string().to_lowercase().contains("housing"));
+    assert!(!summary.to_string().contains("personal"));
+    assert_eq!(
+        summary["constituencies"],
+        json!([{"constituency": "Bath", "count": 3}])
+    );
+    assert_eq!(
+        summary["suppressed"],
+        json!({"topics": 1, "constituencies": 2})
+    );
+}
+
+#[tokio::test]
+async fn research_topics_are_counted_for_admins_only() {
+    let source = Arc::new(common::MockParliamentDataSource::new());
+    let harness = common::build_service(Some(source), |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+        config.usage_stats_enabled = true;
+        config.usage_k_threshold = 2;
+    });
+    common::initialize(&harness.service).await;
+
+    for topic in ["climate", "Climate", "fishing"] {
+        let response =
+            common::call_tool(&harness.service, "research.run", json!({"topic": topic})).await;
+        assert!(response["result"].is_object(), "response: {response}");
+    }
+
+    let denied = usage_summary(&harness, "test").await;
+    assert_eq!(denied["error"]["code"], -32600, "response: {denied}");
+
+    let response = usage_summary(&harness, ADMIN_KEY).await;
+    let summary = &response["result"]["structuredContent"];
+    assert_eq!(summary["windowHours"], 1, "response: {response}");
+    assert_eq!(summary["researchRuns"], 3);
+    assert_eq!(summary["topics"].as_array().map(Vec::len), Some(1));
+    assert_eq!(summary["topics"][0]["count"], 2);
+    assert_eq!(summary["suppressed"]["topics"], 1);
+    assert_eq!(
+        response["result"]["content"].as_array().map(Vec::len),
+        Some(1)
+    );
+}
+
+#[tokio::test]
+async fn summary_reports_when_collection_is_disabled() {
+    let harness = common::build_service(None, |config| {
+        config.admin_api_key = Some(ADMIN_KEY.to_string());
+    });
+    common::initialize(&harness.service).await;
+
+    let response = usage_summary(&harness, ADMIN_KEY).await;
+
+    assert_eq!(response["result"]["isError"], true, "response: {response}");
+    assert_eq!(response["result"]["structuredContent"]["kind"], "config");
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use mp_writer_mcp_server::config::ToolFilterConfig;
+use mp_writer_mcp_server::core::build_info::BuildInfo;
+use mp_writer_mcp_server::features::research::ParliamentDataSource;
+
+mod common;
+
+async fn get_version(harness: &common::TestHarness) -> Value {
+    // No API key: the endpoint is open for triage.
+    let response = harness
+        .router()
+        .oneshot(Request::get("/api/version").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn version_endpoint_reports_the_build_protocols_and_tools() {
+    let harness = common::build_service(None, |config| {
+        config.tool_filter = ToolFilterConfig {
+            enabled: None,
+            disabled: vec!["parliament.fetch_bills".to_string()],
+        };
+    });
+
+    let version = get_version(&harness).await;
+
+    assert_eq!(version["name"], env!("CARGO_PKG_NAME"));
+    assert_eq!(version["version"], env!("CARGO_PKG_VERSION"));
+    assert!(
+        version["gitCommit"]
+            .as_str()
+            .is_some_and(|commit| !commit.is_empty()),
+        "{version}"
+    );
+    let built_at = version["builtAt"].as_str().unwrap();
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(built_at).is_ok(),
+        "{built_at}"
+    );
+    let protocols = version["supportedProtocolVersions"].as_array().unwrap();
+    assert!(protocols.contains(&json!(common::PROTOCOL_VERSION)));
+    let tools = version["tools"].as_array().unwrap();
+    assert!(tools.contains(&json!("research.run")));
+    assert!(!tools.contains(&json!("parliament.fetch_bills")));
+    assert_eq!(tools.len(), harness.service.active_tools().len());
+}
+
+#[tokio::test]
+async fn initialize_server_info_matches_the_version_endpoint() {
+    let harness = common::build_service(None, |_| {});
+    let response = harness
+        .service
+        .handle_jsonrpc(
+            common::request(
+                Some(json!(1)),
+                "initialize",
+                Some(json!({
+                    "protocolVersion": common::PROTOCOL_VERSION,
+                    "clientInfo": {"name": "test-client", "version": "1.0"},
+                    "capabilities": {}
+                })),
+            ),
+            common::context(),
+        )
+        .await
+        .expect("initialize")
+        .expect("response");
+    let server_info = &response.result["serverInfo"];
+
+    assert_eq!(server_info["version"], env!("CARGO_PKG_VERSION"));
+    let version = get_version(&harness).await;
+    for field in [
+        "name",
+        "version",
+        "gitCommit",
+        "builtAt",
+        "supportedProtocolVersions",
+        "tools",
+    ] {
+        assert_eq!(server_info[field], version[field], "{field}");
+    }
+    assert!(server_info["errorCatalogueVersion"].is_string());
+}
+
+#[tokio::test]
+async fn internal_tool_errors_name_the_build_but_bad_requests_do_not() {
+    let mock = Arc::new(common::MockParliamentDataSource::new().panicking(&["bills"]));
+    let harness = common::build_service(Some(mock as Arc<dyn ParliamentDataSource>), |_| {});
+    common::initialize(&harness.service).await;
+    let version = get_version(&harness).await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "research.suggest_keywords",
+        json!({"topic": "climate"}),
+    )
+    .await;
+    let data = &response["result"]["structuredContent"];
+    assert_eq!(data["kind"], "internal", "{response}");
+    for field in ["name", "version", "gitCommit", "builtAt"] {
+        assert_eq!(data["build"][field], version[field], "{field}");
+    }
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.lookup_constituency_offline",
+        json!({"postcode": "not a postcode"}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602, "{response}");
+    assert_eq!(response["error"]["data"], Value::Null, "{response}");
+}
+
+#[tokio::test]
+async fn health_details_report_the_build() {
+    let harness = common::build_service(None, |_| {});
+    let response = harness
+        .router()
+        .oneshot(
+            Request::get("/api/health/details")
+                .header("x-api-key", "test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let details: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(details["build"], json!(harness.service.build_info()));
+}
+
+#[test]
+fn builds_outside_a_checkout_report_unknown() {
+    let info = BuildInfo::from_parts("", "");
+    assert_eq!(info.git_commit, "unknown");
+    assert_eq!(info.built_at, "unknown");
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+
+    let info = BuildInfo::from_parts(" 0123456789ab\n", "0");
+    assert_eq!(info.git_commit, "0123456789ab");
+    assert_eq!(info.built_at, "1970-01-01T00:00:00+00:00");
+}
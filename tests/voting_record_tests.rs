@@ -0,0 +1,238 @@
+use std::path::Path;
+
+use serde_json::{Value, json};
+use wiremock::MockServer;
+
+mod common;
+
+/// Commons Votes API `membervoting` pages: 37 votes in 2024 for member 4514 (two as a
+/// teller) and none in August 2024 for member 172. Lords Votes API: six votes in the first
+/// half of 2024 for member 3743 (one as a teller).
+const VOTING_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/voting");
+
+async fn harness() -> (MockServer, common::TestHarness) {
+    let server = MockServer::start().await;
+    common::mount_recorded(&server, Path::new(VOTING_FIXTURES)).await;
+    let uri = server.uri();
+    let harness = common::build_service(None, |config| config.upstream_base_url = Some(uri));
+    common::initialize(&harness.service).await;
+    (server, harness)
+}
+
+async fn voting_record(harness: &common::TestHarness, arguments: Value) -> Vec<Value> {
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_mp_voting_record",
+        arguments,
+    )
+    .await;
+    assert!(response["result"]["isError"].is_null(), "{response}");
+    response["result"]["structuredContent"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{response}"))
+        .clone()
+}
+
+/// The `skip` of each upstream request, in order.
+async fn pages_requested(server: &MockServer) -> Vec<String> {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|request| {
+            request
+                .url
+                .query_pairs()
+                .find(|(name, _)| name == "queryParameters.skip")
+                .map(|(_, skip)| skip.into_owned())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn votes_come_from_the_votes_api_with_tellers_and_majorities() {
+    let (server, harness) = harness().await;
+
+    let votes = voting_record(
+        &harness,
+        json!({"mpId": 4514, "fromDate": "2024-01-01", "toDate": "2024-12-31", "limit": 30}),
+    )
+    .await;
+
+    // The first page has 25 votes, so a second is read to reach the limit.
+    assert_eq!(votes.len(), 30);
+    assert_eq!(pages_requested(&server).await, ["0", "25"]);
+    assert_eq!(
+        votes[0],
+        json!({
+            "divisionId": "1900",
+            "title": "Finance (No. 2) Bill: Third Reading",
+            "date": "2024-12-18",
+            "vote": "Aye",
+            "majority": "Aye",
+            "teller": false
+        })
+    );
+    assert!(votes.iter().any(|vote| vote["vote"] == "No"));
+
+    let tellers: Vec<&Value> = votes.iter().filter(|vote| vote["teller"] == true).collect();
+    assert_eq!(tellers.len(), 2);
+    assert_eq!(tellers[0]["divisionId"], "1897");
+    assert_eq!(tellers[0]["vote"], "Aye");
+    assert_eq!(tellers[1]["divisionId"], "1883");
+    assert_eq!(tellers[1]["vote"], "No");
+    assert_eq!(tellers[1]["majority"], "No");
+}
+
+#[tokio::test]
+async fn an_mp_with_no_votes_in_range_gets_an_empty_record() {
+    let (server, harness) = harness().await;
+
+    let votes = voting_record(
+        &harness,
+        json!({"mpId": 172, "fromDate": "2024-08-01", "toDate": "2024-08-31"}),
+    )
+    .await;
+
+    assert!(votes.is_empty());
+    assert_eq!(pages_requested(&server).await, ["0"]);
+}
+
+#[tokio::test]
+async fn bill_filters_read_on_until_the_last_page() {
+    let (server, harness) = harness().await;
+
+    let votes = voting_record(
+        &harness,
+        json!({
+            "mpId": 4514,
+            "fromDate": "2024-01-01",
+            "toDate": "2024-12-31",
+            "billId": "Finance",
+            "limit": 30
+        }),
+    )
+    .await;
+
+    assert_eq!(votes.len(), 10);
+    assert!(votes.iter().all(|vote| {
+        vote["title"]
+            .as_str()
+            .unwrap()
+            .starts_with("Finance (No. 2) Bill")
+    }));
+    assert_eq!(pages_requested(&server).await, ["0", "25"]);
+}
+
+#[tokio::test]
+async fn records_are_cached_per_filter() {
+    let (server, harness) = harness().await;
+    let arguments =
+        json!({"mpId": 4514, "fromDate": "2024-01-01", "toDate": "2024-12-31", "limit": 5});
+
+    let first = voting_record(&harness, arguments.clone()).await;
+    let second = voting_record(&harness, arguments).await;
+    assert_eq!(first, second);
+    assert_eq!(first.len(), 5);
+    assert_eq!(pages_requested(&server).await, ["0"]);
+
+    // A different filter is a different record.
+    let finance = voting_record(
+        &harness,
+        json!({
+            "mpId": 4514,
+            "fromDate": "2024-01-01",
+            "toDate": "2024-12-31",
+            "billId": "Finance",
+            "limit": 5
+        }),
+    )
+    .await;
+    assert_eq!(finance.len(), 5);
+    assert_eq!(pages_requested(&server).await, ["0", "0"]);
+}
+
+#[tokio::test]
+async fn malformed_dates_are_rejected() {
+    let (server, harness) = harness().await;
+
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_mp_voting_record",
+        json!({"mpId": 4514, "fromDate": "last year"}),
+    )
+    .await;
+
+    assert_eq!(response["error"]["code"], -32602, "{response}");
+    assert!(pages_requested(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn lords_members_get_content_and_not_content_votes() {
+    let (server, harness) = harness().await;
+
+    let votes = voting_record(
+        &harness,
+        json!({"mpId": 3743, "house": "lords", "fromDate": "2024-01-01", "toDate": "2024-06-30"}),
+    )
+    .await;
+
+    assert_eq!(votes.len(), 6);
+    assert_eq!(
+        votes[0],
+        json!({
+            "divisionId": "1843",
+            "title": "Victims and Prisoners Bill: Consideration of Commons Amendments",
+            "date": "2024-05-22",
+            "vote": "Content",
+            "majority": "Content",
+            "teller": false
+        })
+    );
+    assert_eq!(votes[1]["vote"], "Not Content");
+    assert_eq!(votes[1]["majority"], "Not Content");
+    assert_eq!(votes[2]["teller"], true);
+    assert_eq!(votes[4]["majority"], "Tie");
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.path(), "/data/Divisions/membervoting");
+}
+
+#[tokio::test]
+async fn each_house_has_its_own_cache_entry() {
+    let (_server, harness) = harness().await;
+    let lords =
+        json!({"mpId": 3743, "house": "lords", "fromDate": "2024-01-01", "toDate": "2024-06-30"});
+
+    voting_record(&harness, lords.clone()).await;
+
+    let mut cached = lords.clone();
+    cached["cacheOnly"] = json!(true);
+    assert_eq!(voting_record(&harness, cached).await.len(), 6);
+
+    // The same member and dates in the Commons were never fetched.
+    let response = common::call_tool(
+        &harness.service,
+        "parliament.fetch_mp_voting_record",
+        json!({"mpId": 3743, "fromDate": "2024-01-01", "toDate": "2024-06-30", "cacheOnly": true}),
+    )
+    .await;
+    assert_eq!(
+        response["result"]["structuredContent"]["cacheMiss"], true,
+        "{response}"
+    );
+}
+
+#[test]
+fn the_schema_offers_both_houses() {
+    let (definitions, _) = mp_writer_mcp_server::features::mcp::schemas::build_tool_schemas();
+    let house = &definitions
+        .iter()
+        .find(|definition| definition.name == "parliament.fetch_mp_voting_record")
+        .unwrap()
+        .input_schema["properties"]["house"];
+    assert_eq!(house["enum"], json!(["commons", "lords"]));
+}
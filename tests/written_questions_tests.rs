@@ -0,0 +1,186 @@
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+const TOOL: &str = "parliament.fetch_written_questions";
+const WRITTEN_QUESTIONS: &str = "/api/writtenquestions/questions";
+
+fn questions() -> Value {
+    json!({
+        "results": [
+            {
+                "value": {
+                    "id": 1700002,
+                    "askingMemberId": 4514,
+                    "askingMember": {"id": 4514, "name": "Jane Example"},
+                    "dateTabled": "2024-05-02T00:00:00",
+                    "uin": "24789",
+                    "heading": "Social Rented Housing: Construction",
+                    "answeringBodyName": "Department for Levelling Up, Housing and Communities",
+                    "questionText": "To ask the Secretary of State how many homes were started last year.",
+                    "answerText": "<p>Figures are published <strong>quarterly</strong>.</p><p>The latest show 5,000 starts.</p>",
+                    "dateAnswered": "2024-05-09T00:00:00"
+                },
+                "links": []
+            },
+            {
+                "value": {
+                    "id": 1700001,
+                    "askingMemberId": 4514,
+                    "dateTabled": "2024-04-18T00:00:00",
+                    "uin": "21004",
+                    "heading": "Flood Control",
+                    "answeringBodyName": "Department for Environment, Food and Rural Affairs",
+                    "questionText": "To ask the Secretary of State what funding is planned.",
+                    "answerText": null,
+                    "dateAnswered": null
+                },
+                "links": []
+            }
+        ],
+        "totalResults": 12
+    })
+}
+
+async fn upstream(response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(WRITTEN_QUESTIONS))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn questions_carry_their_answers_and_links() {
+    let server = upstream(ResponseTemplate::new(200).set_body_json(questions())).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let result =
+        common::call_tool_ok(&harness.service, TOOL, json!({"askingMemberId": 4514})).await;
+
+    assert_eq!(result["totalResults"], 12);
+    assert_eq!(
+        result["questions"][0],
+        json!({
+            "id": 1700002,
+            "uin": "24789",
+            "heading": "Social Rented Housing: Construction",
+            "questionText": "To ask the Secretary of State how many homes were started last year.",
+            "dateTabled": "2024-05-02",
+            "askingMemberId": 4514,
+            "askingMemberName": "Jane Example",
+            "answeringBody": "Department for Levelling Up, Housing and Communities",
+            "answerText": "Figures are published quarterly . The latest show 5,000 starts.",
+            "dateAnswered": "2024-05-09",
+            "url": "https://questions-statements.parliament.uk/written-questions/detail/2024-05-02/24789"
+        })
+    );
+    let unanswered = &result["questions"][1];
+    assert!(unanswered["answerText"].is_null(), "{unanswered}");
+    assert!(unanswered["dateAnswered"].is_null(), "{unanswered}");
+}
+
+#[tokio::test]
+async fn filters_are_sent_upstream() {
+    let server = upstream(ResponseTemplate::new(200).set_body_json(questions())).await;
+    let harness = common::upstream_harness(&server).await;
+
+    common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({
+            "askingMemberId": "https://members.parliament.uk/member/4514",
+            "answeringBody": 7,
+            "tabledFrom": "2024-01-01",
+            "tabledTo": "2024-06-30",
+            "answered": "unanswered",
+            "searchTerm": "  flood defences ",
+            "limit": 5
+        }),
+    )
+    .await;
+
+    let requests = server.received_requests().await.unwrap();
+    let query: Vec<(String, String)> = requests[0]
+        .url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    for expected in [
+        ("askingMemberId", "4514"),
+        ("answeringBodies", "7"),
+        ("tabledWhenFrom", "2024-01-01"),
+        ("tabledWhenTo", "2024-06-30"),
+        ("answered", "Unanswered"),
+        ("searchTerm", "flood defences"),
+        ("take", "5"),
+    ] {
+        assert!(
+            query
+                .iter()
+                .any(|(key, value)| key == expected.0 && value == expected.1),
+            "{expected:?} missing from {query:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn results_are_cached_per_filter() {
+    let server = upstream(ResponseTemplate::new(200).set_body_json(questions())).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let first =
+        common::call_tool_ok(&harness.service, TOOL, json!({"searchTerm": "Housing"})).await;
+    let cached = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "housing", "cacheOnly": true}),
+    )
+    .await;
+    let other = common::call_tool_ok(
+        &harness.service,
+        TOOL,
+        json!({"searchTerm": "housing", "answered": "answered", "cacheOnly": true}),
+    )
+    .await;
+
+    assert_eq!(first, cached);
+    assert_eq!(other["cacheMiss"], true, "{other}");
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn upstream_failures_are_retried_then_reported() {
+    let server = upstream(ResponseTemplate::new(503)).await;
+    let harness = common::upstream_harness(&server).await;
+
+    let response = common::call_tool(&harness.service, TOOL, json!({"askingMemberId": 4514})).await;
+
+    assert_eq!(response["result"]["isError"], true, "{response}");
+    assert_eq!(
+        response["result"]["structuredContent"]["kind"], "upstream",
+        "{response}"
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn malformed_filters_are_rejected_before_fetching() {
+    let server = upstream(ResponseTemplate::new(200).set_body_json(questions())).await;
+    let harness = common::upstream_harness(&server).await;
+
+    for arguments in [
+        json!({"tabledFrom": "last week"}),
+        json!({"answered": "yes"}),
+        json!({"answeringBody": 0}),
+        json!({"limit": 101}),
+    ] {
+        let response = common::call_tool(&harness.service, TOOL, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}